@@ -12,12 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod parquet_decimal;
 mod parquet_read;
 mod parquet_write;
 
 pub use arrow;
 pub use arrow_format;
 pub use parquet2 as parquet;
+pub use parquet_decimal::from_parquet_array;
+pub use parquet_decimal::from_parquet_arrow_schema;
+pub use parquet_decimal::from_parquet_chunk;
+pub use parquet_decimal::to_parquet_array;
+pub use parquet_decimal::to_parquet_arrow_schema;
+pub use parquet_decimal::to_parquet_chunk;
+pub use parquet_decimal::to_parquet_field;
 pub use parquet_read::read_columns_many_async;
 pub use parquet_write::write_parquet_file;
 
@@ -0,0 +1,206 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decimals are stored in parquet as the fixed length big-endian two's complement
+//! of the unscaled values, 16 bytes for `Decimal(p, s)` and 32 bytes for `Decimal256`.
+//!
+//! The arrays are converted to `FixedSizeBinary` before writing, and converted back
+//! by the fields of the table schema after reading. Only the top level fields are converted.
+
+use arrow::array::Array;
+use arrow::array::BinaryArray;
+use arrow::array::FixedSizeBinaryArray;
+use arrow::array::PrimitiveArray;
+use arrow::chunk::Chunk;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::datatypes::Schema;
+use arrow::error::Error;
+use arrow::error::Result;
+
+use crate::ArrayRef;
+
+// Same as the extension keys of `common_datavalues`, which `Decimal256` is marked with.
+const EXTENSION_NAME_KEY: &str = "ARROW:extension:databend_name";
+const DECIMAL256_NAME: &str = "Decimal256";
+
+const DECIMAL_SIZE: usize = 16;
+const DECIMAL256_SIZE: usize = 32;
+
+fn is_decimal256(field: &Field) -> bool {
+    matches!(field.data_type(), DataType::LargeBinary)
+        && field.metadata.get(EXTENSION_NAME_KEY).map(|v| v.as_str()) == Some(DECIMAL256_NAME)
+}
+
+/// Returns the field which the column of `field` is written to parquet as.
+pub fn to_parquet_field(field: &Field) -> Field {
+    let data_type = match field.data_type() {
+        DataType::Decimal(_, _) => DataType::FixedSizeBinary(DECIMAL_SIZE),
+        _ if is_decimal256(field) => DataType::FixedSizeBinary(DECIMAL256_SIZE),
+        _ => return field.clone(),
+    };
+    Field::new(&field.name, data_type, field.is_nullable).with_metadata(field.metadata.clone())
+}
+
+/// Returns the schema which the columns of `schema` are written to parquet as.
+pub fn to_parquet_arrow_schema(schema: &Schema) -> Schema {
+    let fields = schema
+        .fields
+        .iter()
+        .map(to_parquet_field)
+        .collect::<Vec<_>>();
+    Schema::from(fields).with_metadata(schema.metadata.clone())
+}
+
+/// Restores the decimal fields of a schema inferred from parquet by the expected schema,
+/// the other fields are kept as they are.
+pub fn from_parquet_arrow_schema(inferred: &Schema, expected: &Schema) -> Schema {
+    let fields = inferred
+        .fields
+        .iter()
+        .map(|field| {
+            expected
+                .fields
+                .iter()
+                .find(|f| f.name == field.name)
+                .filter(|f| to_parquet_field(f).data_type() != f.data_type())
+                .filter(|f| to_parquet_field(f).data_type() == field.data_type())
+                .cloned()
+                .unwrap_or_else(|| field.clone())
+        })
+        .collect::<Vec<_>>();
+    Schema::from(fields).with_metadata(inferred.metadata.clone())
+}
+
+/// Converts an array of `field` to the array written to parquet.
+pub fn to_parquet_array(array: ArrayRef, field: &Field) -> Result<ArrayRef> {
+    match field.data_type() {
+        DataType::Decimal(_, _) => {
+            let array = downcast::<PrimitiveArray<i128>>(array.as_ref())?;
+            let mut values = Vec::with_capacity(array.len() * DECIMAL_SIZE);
+            for v in array.values().iter() {
+                values.extend_from_slice(&v.to_be_bytes());
+            }
+            Ok(Box::new(FixedSizeBinaryArray::from_data(
+                DataType::FixedSizeBinary(DECIMAL_SIZE),
+                values.into(),
+                array.validity().cloned(),
+            )))
+        }
+        _ if is_decimal256(field) => {
+            let array = downcast::<BinaryArray<i64>>(array.as_ref())?;
+            let mut values = Vec::with_capacity(array.len() * DECIMAL256_SIZE);
+            for i in 0..array.len() {
+                let value = array.value(i);
+                if value.len() == DECIMAL256_SIZE {
+                    // Flip the sign bit back to get the two's complement
+                    values.push(value[0] ^ 0x80);
+                    values.extend_from_slice(&value[1..]);
+                } else {
+                    // The values under the nulls may be empty
+                    values.extend_from_slice(&[0; DECIMAL256_SIZE]);
+                }
+            }
+            Ok(Box::new(FixedSizeBinaryArray::from_data(
+                DataType::FixedSizeBinary(DECIMAL256_SIZE),
+                values.into(),
+                array.validity().cloned(),
+            )))
+        }
+        _ => Ok(array),
+    }
+}
+
+/// Converts an array read from parquet back to the array of `field`.
+pub fn from_parquet_array(array: ArrayRef, field: &Field) -> Result<ArrayRef> {
+    match field.data_type() {
+        DataType::Decimal(_, _) => {
+            let array = downcast_fixed_size(array.as_ref(), DECIMAL_SIZE)?;
+            let values = array
+                .values()
+                .chunks_exact(DECIMAL_SIZE)
+                .map(|v| {
+                    let mut bytes = [0u8; DECIMAL_SIZE];
+                    bytes.copy_from_slice(v);
+                    i128::from_be_bytes(bytes)
+                })
+                .collect::<Vec<_>>();
+            Ok(Box::new(PrimitiveArray::<i128>::from_data(
+                field.data_type().clone(),
+                values.into(),
+                array.validity().cloned(),
+            )))
+        }
+        _ if is_decimal256(field) => {
+            let array = downcast_fixed_size(array.as_ref(), DECIMAL256_SIZE)?;
+            let mut values = array.values().to_vec();
+            for v in values.chunks_exact_mut(DECIMAL256_SIZE) {
+                v[0] ^= 0x80;
+            }
+            let offsets = (0..=array.len())
+                .map(|i| (i * DECIMAL256_SIZE) as i64)
+                .collect::<Vec<_>>();
+            Ok(Box::new(BinaryArray::<i64>::from_data(
+                DataType::LargeBinary,
+                offsets.into(),
+                values.into(),
+                array.validity().cloned(),
+            )))
+        }
+        _ => Ok(array),
+    }
+}
+
+/// Converts the arrays of a chunk of `schema` to the arrays written to parquet.
+pub fn to_parquet_chunk(chunk: Chunk<ArrayRef>, schema: &Schema) -> Result<Chunk<ArrayRef>> {
+    let arrays = chunk
+        .into_arrays()
+        .into_iter()
+        .zip(schema.fields.iter())
+        .map(|(array, field)| to_parquet_array(array, field))
+        .collect::<Result<Vec<_>>>()?;
+    Chunk::try_new(arrays)
+}
+
+/// Converts the arrays of a chunk read from parquet back to the arrays of `schema`.
+pub fn from_parquet_chunk(chunk: Chunk<ArrayRef>, schema: &Schema) -> Result<Chunk<ArrayRef>> {
+    let arrays = chunk
+        .into_arrays()
+        .into_iter()
+        .zip(schema.fields.iter())
+        .map(|(array, field)| from_parquet_array(array, field))
+        .collect::<Result<Vec<_>>>()?;
+    Chunk::try_new(arrays)
+}
+
+fn downcast<T: 'static>(array: &dyn Array) -> Result<&T> {
+    array.as_any().downcast_ref::<T>().ok_or_else(|| {
+        Error::InvalidArgumentError(format!(
+            "Unexpected array of type {:?} for decimals",
+            array.data_type()
+        ))
+    })
+}
+
+fn downcast_fixed_size(array: &dyn Array, size: usize) -> Result<&FixedSizeBinaryArray> {
+    let array = downcast::<FixedSizeBinaryArray>(array)?;
+    if array.size() != size {
+        return Err(Error::InvalidArgumentError(format!(
+            "Expected decimals of {} bytes in parquet, but got {} bytes",
+            size,
+            array.size()
+        )));
+    }
+    Ok(array)
+}
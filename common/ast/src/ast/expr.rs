@@ -22,6 +22,7 @@ use common_exception::Result;
 use crate::ast::write_comma_separated_list;
 use crate::ast::write_period_separated_list;
 use crate::ast::Identifier;
+use crate::ast::OrderByExpr;
 use crate::ast::Query;
 use crate::parser::token::Token;
 
@@ -126,7 +127,10 @@ pub enum Expr<'a> {
     /// A literal value, such as string, number, date or NULL
     Literal { span: &'a [Token<'a>], lit: Literal },
     /// `COUNT(*)` expression
-    CountAll { span: &'a [Token<'a>] },
+    CountAll {
+        span: &'a [Token<'a>],
+        window: Option<WindowSpec<'a>>,
+    },
     /// `(foo, bar)`
     Tuple {
         span: &'a [Token<'a>],
@@ -140,6 +144,8 @@ pub enum Expr<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        /// The `OVER (...)` clause if the function is called as a window function
+        window: Option<WindowSpec<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -245,6 +251,40 @@ pub enum MapAccessor<'a> {
     Colon { key: Identifier<'a> },
 }
 
+/// `OVER ([PARTITION BY <expr>, ...] [ORDER BY <expr>, ...] [<frame>])`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowSpec<'a> {
+    pub partition_by: Vec<Expr<'a>>,
+    pub order_by: Vec<OrderByExpr<'a>>,
+    pub window_frame: Option<WindowFrame<'a>>,
+}
+
+/// `ROWS | RANGE BETWEEN <start_bound> AND <end_bound>`
+///
+/// If the frame is specified with only a start bound, the end bound will be `CURRENT ROW`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowFrame<'a> {
+    pub units: WindowFrameUnits,
+    pub start_bound: WindowFrameBound<'a>,
+    pub end_bound: WindowFrameBound<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowFrameUnits {
+    Rows,
+    Range,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WindowFrameBound<'a> {
+    /// `CURRENT ROW`
+    CurrentRow,
+    /// `<expr> PRECEDING` or `UNBOUNDED PRECEDING`
+    Preceding(Option<Box<Expr<'a>>>),
+    /// `<expr> FOLLOWING` or `UNBOUNDED FOLLOWING`
+    Following(Option<Box<Expr<'a>>>),
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum TypeName {
     Boolean,
@@ -258,6 +298,10 @@ pub enum TypeName {
     Int64,
     Float32,
     Float64,
+    Decimal {
+        precision: Option<u64>,
+        scale: Option<u64>,
+    },
     Date,
     Timestamp {
         precision: Option<u64>,
@@ -270,6 +314,10 @@ pub enum TypeName {
         fields_name: Option<Vec<String>>,
         fields_type: Vec<TypeName>,
     },
+    Map {
+        key_type: Box<TypeName>,
+        val_type: Box<TypeName>,
+    },
     Object,
     Variant,
     Nullable(Box<TypeName>),
@@ -366,7 +414,7 @@ impl<'a> Expr<'a> {
             | Expr::Substring { span, .. }
             | Expr::Trim { span, .. }
             | Expr::Literal { span, .. }
-            | Expr::CountAll { span }
+            | Expr::CountAll { span, .. }
             | Expr::Tuple { span, .. }
             | Expr::FunctionCall { span, .. }
             | Expr::Case { span, .. }
@@ -532,6 +580,16 @@ impl Display for TypeName {
             TypeName::Date => {
                 write!(f, "DATE")?;
             }
+            TypeName::Decimal { precision, scale } => {
+                write!(f, "DECIMAL")?;
+                if let Some(precision) = precision {
+                    write!(f, "({}", *precision)?;
+                    if let Some(scale) = scale {
+                        write!(f, ", {}", *scale)?;
+                    }
+                    write!(f, ")")?;
+                }
+            }
             TypeName::Timestamp { precision } => {
                 write!(f, "Timestamp")?;
                 if let Some(precision) = precision {
@@ -575,6 +633,9 @@ impl Display for TypeName {
                 }
                 write!(f, ")")?;
             }
+            TypeName::Map { key_type, val_type } => {
+                write!(f, "MAP({}, {})", key_type, val_type)?;
+            }
             TypeName::Object => {
                 write!(f, "OBJECT")?;
             }
@@ -589,6 +650,63 @@ impl Display for TypeName {
     }
 }
 
+impl<'a> Display for WindowSpec<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        if !self.partition_by.is_empty() {
+            first = false;
+            write!(f, "PARTITION BY ")?;
+            write_comma_separated_list(f, &self.partition_by)?;
+        }
+        if !self.order_by.is_empty() {
+            if !first {
+                write!(f, " ")?;
+            }
+            first = false;
+            write!(f, "ORDER BY ")?;
+            write_comma_separated_list(f, &self.order_by)?;
+        }
+        if let Some(frame) = &self.window_frame {
+            if !first {
+                write!(f, " ")?;
+            }
+            write!(f, "{frame}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Display for WindowFrame<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} BETWEEN {} AND {}",
+            self.units, self.start_bound, self.end_bound
+        )
+    }
+}
+
+impl Display for WindowFrameUnits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFrameUnits::Rows => write!(f, "ROWS"),
+            WindowFrameUnits::Range => write!(f, "RANGE"),
+        }
+    }
+}
+
+impl<'a> Display for WindowFrameBound<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFrameBound::Preceding(Some(expr)) => write!(f, "{expr} PRECEDING"),
+            WindowFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFrameBound::Following(Some(expr)) => write!(f, "{expr} FOLLOWING"),
+        }
+    }
+}
+
 impl Display for TrimWhere {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         f.write_str(match self {
@@ -769,8 +887,11 @@ impl<'a> Display for Expr<'a> {
             Expr::Literal { lit, .. } => {
                 write!(f, "{lit}")?;
             }
-            Expr::CountAll { .. } => {
+            Expr::CountAll { window, .. } => {
                 write!(f, "COUNT(*)")?;
+                if let Some(window) = window {
+                    write!(f, " OVER ({window})")?;
+                }
             }
             Expr::Tuple { exprs, .. } => {
                 write!(f, "(")?;
@@ -785,6 +906,7 @@ impl<'a> Display for Expr<'a> {
                 name,
                 args,
                 params,
+                window,
                 ..
             } => {
                 write!(f, "{name}")?;
@@ -799,6 +921,9 @@ impl<'a> Display for Expr<'a> {
                 }
                 write_comma_separated_list(f, args)?;
                 write!(f, ")")?;
+                if let Some(window) = window {
+                    write!(f, " OVER ({window})")?;
+                }
             }
             Expr::Case {
                 operand,
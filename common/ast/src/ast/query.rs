@@ -26,6 +26,9 @@ use crate::parser::token::Token;
 pub struct Query<'a> {
     pub span: &'a [Token<'a>],
 
+    // With clause, common table expression
+    pub with: Option<With<'a>>,
+
     // Set operator: SELECT or UNION / EXCEPT / INTERSECT
     pub body: SetExpr<'a>,
 
@@ -40,6 +43,22 @@ pub struct Query<'a> {
     pub format: Option<String>,
 }
 
+/// `WITH [RECURSIVE] name [(col, ...)] AS (query), ...`
+#[derive(Debug, Clone, PartialEq)]
+pub struct With<'a> {
+    pub span: &'a [Token<'a>],
+    pub recursive: bool,
+    pub ctes: Vec<CTE<'a>>,
+}
+
+/// A common table expression, which is a named subquery in `WITH` clause
+#[derive(Debug, Clone, PartialEq)]
+pub struct CTE<'a> {
+    pub span: &'a [Token<'a>],
+    pub alias: TableAlias<'a>,
+    pub query: Query<'a>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SetOperation<'a> {
     pub span: &'a [Token<'a>],
@@ -394,7 +413,7 @@ impl<'a> Display for SetExpr<'a> {
                 write!(f, "{}", set_operation.left)?;
                 match set_operation.op {
                     SetOperator::Union => {
-                        write!(f, " UNION ")?;
+                        write!(f, " UNION")?;
                     }
                     SetOperator::Except => {
                         write!(f, " EXCEPT")?;
@@ -406,15 +425,41 @@ impl<'a> Display for SetExpr<'a> {
                 if set_operation.all {
                     write!(f, " ALL")?;
                 }
-                write!(f, "{}", set_operation.right)?;
+                write!(f, " {}", set_operation.right)?;
             }
         }
         Ok(())
     }
 }
 
+impl<'a> Display for CTE<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.alias.name)?;
+        if !self.alias.columns.is_empty() {
+            write!(f, "(")?;
+            write_comma_separated_list(f, &self.alias.columns)?;
+            write!(f, ")")?;
+        }
+        write!(f, " AS ({})", self.query)
+    }
+}
+
+impl<'a> Display for With<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.recursive {
+            write!(f, "RECURSIVE ")?;
+        }
+        write_comma_separated_list(f, &self.ctes)
+    }
+}
+
 impl<'a> Display for Query<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // WITH clause, a list of common table expressions
+        if let Some(with) = &self.with {
+            write!(f, "WITH {with} ")?;
+        }
+
         // Query body
         write!(f, "{}", self.body)?;
 
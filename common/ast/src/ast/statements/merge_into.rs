@@ -0,0 +1,111 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use crate::ast::write_comma_separated_list;
+use crate::ast::Expr;
+use crate::ast::Identifier;
+use crate::ast::TableReference;
+use crate::ast::UpdateExpr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeIntoStmt<'a> {
+    pub target: TableReference<'a>,
+    pub source: TableReference<'a>,
+    pub join_expr: Expr<'a>,
+    pub matched_clauses: Vec<MatchedClause<'a>>,
+    pub unmatched_clauses: Vec<UnmatchedClause<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeClause<'a> {
+    Matched(MatchedClause<'a>),
+    Unmatched(UnmatchedClause<'a>),
+}
+
+/// `WHEN MATCHED [AND <condition>] THEN { UPDATE SET ... | DELETE }`
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchedClause<'a> {
+    pub selection: Option<Expr<'a>>,
+    pub operation: MatchOperation<'a>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchOperation<'a> {
+    Update { update_list: Vec<UpdateExpr<'a>> },
+    Delete,
+}
+
+/// `WHEN NOT MATCHED [AND <condition>] THEN INSERT [(<column>, ...)] VALUES (<expr>, ...)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnmatchedClause<'a> {
+    pub selection: Option<Expr<'a>>,
+    pub columns: Vec<Identifier<'a>>,
+    pub values: Vec<Expr<'a>>,
+}
+
+impl Display for MergeIntoStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "MERGE INTO {} USING {} ON {}",
+            self.target, self.source, self.join_expr
+        )?;
+        for clause in &self.matched_clauses {
+            write!(f, " {clause}")?;
+        }
+        for clause in &self.unmatched_clauses {
+            write!(f, " {clause}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for MatchedClause<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WHEN MATCHED")?;
+        if let Some(selection) = &self.selection {
+            write!(f, " AND {selection}")?;
+        }
+        write!(f, " THEN ")?;
+        match &self.operation {
+            MatchOperation::Update { update_list } => {
+                write!(f, "UPDATE SET ")?;
+                write_comma_separated_list(f, update_list)?;
+            }
+            MatchOperation::Delete => write!(f, "DELETE")?,
+        }
+        Ok(())
+    }
+}
+
+impl Display for UnmatchedClause<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WHEN NOT MATCHED")?;
+        if let Some(selection) = &self.selection {
+            write!(f, " AND {selection}")?;
+        }
+        write!(f, " THEN INSERT")?;
+        if !self.columns.is_empty() {
+            write!(f, " (")?;
+            write_comma_separated_list(f, &self.columns)?;
+            write!(f, ")")?;
+        }
+        write!(f, " VALUES (")?;
+        write_comma_separated_list(f, &self.values)?;
+        write!(f, ")")
+    }
+}
@@ -16,13 +16,16 @@ mod call;
 mod copy;
 mod database;
 mod explain;
+mod index;
 mod insert;
 mod kill;
+mod merge_into;
 mod presign;
 mod show;
 mod stage;
 mod statement;
 mod table;
+mod update;
 mod user;
 mod view;
 
@@ -30,12 +33,15 @@ pub use call::*;
 pub use copy::*;
 pub use database::*;
 pub use explain::*;
+pub use index::*;
 pub use insert::*;
 pub use kill::*;
+pub use merge_into::*;
 pub use presign::*;
 pub use show::*;
 pub use stage::*;
 pub use statement::*;
 pub use table::*;
+pub use update::*;
 pub use user::*;
 pub use view::*;
@@ -65,6 +65,16 @@ pub enum Statement<'a> {
         table_reference: TableReference<'a>,
         selection: Option<Expr<'a>>,
     },
+
+    Update(UpdateStmt<'a>),
+
+    MergeInto(MergeIntoStmt<'a>),
+
+    // Transactions
+    Begin,
+    Commit,
+    Rollback,
+
     // Databases
     ShowDatabases(ShowDatabasesStmt<'a>),
     ShowCreateDatabase(ShowCreateDatabaseStmt<'a>),
@@ -88,6 +98,8 @@ pub enum Statement<'a> {
     RenameTable(RenameTableStmt<'a>),
     TruncateTable(TruncateTableStmt<'a>),
     OptimizeTable(OptimizeTableStmt<'a>),
+    VacuumTable(VacuumTableStmt<'a>),
+    AnalyzeTable(AnalyzeTableStmt<'a>),
     ExistsTable(ExistsTableStmt<'a>),
 
     // Views
@@ -95,6 +107,10 @@ pub enum Statement<'a> {
     AlterView(AlterViewStmt<'a>),
     DropView(DropViewStmt<'a>),
 
+    // Indexes
+    CreateIndex(CreateIndexStmt<'a>),
+    DropIndex(DropIndexStmt<'a>),
+
     // User
     ShowUsers,
     CreateUser(CreateUserStmt),
@@ -190,6 +206,11 @@ impl<'a> Display for Statement<'a> {
                     write!(f, "WHERE {conditions} ")?;
                 }
             }
+            Statement::Update(update) => write!(f, "{update}")?,
+            Statement::MergeInto(merge_into) => write!(f, "{merge_into}")?,
+            Statement::Begin => write!(f, "BEGIN")?,
+            Statement::Commit => write!(f, "COMMIT")?,
+            Statement::Rollback => write!(f, "ROLLBACK")?,
             Statement::Copy(stmt) => write!(f, "{stmt}")?,
             Statement::ShowSettings { like } => {
                 write!(f, "SHOW SETTINGS")?;
@@ -246,10 +267,14 @@ impl<'a> Display for Statement<'a> {
             Statement::RenameTable(stmt) => write!(f, "{stmt}")?,
             Statement::TruncateTable(stmt) => write!(f, "{stmt}")?,
             Statement::OptimizeTable(stmt) => write!(f, "{stmt}")?,
+            Statement::VacuumTable(stmt) => write!(f, "{stmt}")?,
+            Statement::AnalyzeTable(stmt) => write!(f, "{stmt}")?,
             Statement::ExistsTable(stmt) => write!(f, "{stmt}")?,
             Statement::CreateView(stmt) => write!(f, "{stmt}")?,
             Statement::AlterView(stmt) => write!(f, "{stmt}")?,
             Statement::DropView(stmt) => write!(f, "{stmt}")?,
+            Statement::CreateIndex(stmt) => write!(f, "{stmt}")?,
+            Statement::DropIndex(stmt) => write!(f, "{stmt}")?,
             Statement::ShowUsers => write!(f, "SHOW USERS")?,
             Statement::ShowRoles => write!(f, "SHOW ROLES")?,
             Statement::CreateUser(stmt) => write!(f, "{stmt}")?,
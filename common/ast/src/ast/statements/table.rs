@@ -151,7 +151,7 @@ impl Display for CreateTableStmt<'_> {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CreateTableSource<'a> {
-    Columns(Vec<ColumnDefinition<'a>>),
+    Columns(Vec<ColumnDefinition<'a>>, Vec<CheckConstraint<'a>>),
     Like {
         catalog: Option<Identifier<'a>>,
         database: Option<Identifier<'a>>,
@@ -162,9 +162,13 @@ pub enum CreateTableSource<'a> {
 impl Display for CreateTableSource<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            CreateTableSource::Columns(columns) => {
+            CreateTableSource::Columns(columns, check_constraints) => {
                 write!(f, "(")?;
                 write_comma_separated_list(f, columns)?;
+                if !check_constraints.is_empty() {
+                    write!(f, ", ")?;
+                    write_comma_separated_list(f, check_constraints)?;
+                }
                 write!(f, ")")
             }
             CreateTableSource::Like {
@@ -276,9 +280,27 @@ impl Display for AlterTableStmt<'_> {
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AlterTableAction<'a> {
-    RenameTable { new_table: Identifier<'a> },
-    AlterTableClusterKey { cluster_by: Vec<Expr<'a>> },
+    RenameTable {
+        new_table: Identifier<'a>,
+    },
+    AlterTableClusterKey {
+        cluster_by: Vec<Expr<'a>>,
+    },
     DropTableClusterKey,
+    ReclusterTable {
+        is_final: bool,
+        selection: Option<Expr<'a>>,
+    },
+    AddColumn {
+        column: ColumnDefinition<'a>,
+    },
+    DropColumn {
+        column: Identifier<'a>,
+    },
+    RenameColumn {
+        old_column: Identifier<'a>,
+        new_column: Identifier<'a>,
+    },
 }
 
 impl Display for AlterTableAction<'_> {
@@ -294,6 +316,31 @@ impl Display for AlterTableAction<'_> {
             AlterTableAction::DropTableClusterKey => {
                 write!(f, "DROP CLUSTER KEY")
             }
+            AlterTableAction::ReclusterTable {
+                is_final,
+                selection,
+            } => {
+                write!(f, "RECLUSTER")?;
+                if *is_final {
+                    write!(f, " FINAL")?;
+                }
+                if let Some(conditions) = selection {
+                    write!(f, " WHERE {conditions}")?;
+                }
+                Ok(())
+            }
+            AlterTableAction::AddColumn { column } => {
+                write!(f, "ADD COLUMN {column}")
+            }
+            AlterTableAction::DropColumn { column } => {
+                write!(f, "DROP COLUMN {column}")
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => {
+                write!(f, "RENAME COLUMN {old_column} TO {new_column}")
+            }
         }
     }
 }
@@ -385,6 +432,64 @@ impl Display for OptimizeTableStmt<'_> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VacuumTableStmt<'a> {
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+    pub retain_hours: Option<u64>,
+    pub dry_run: bool,
+}
+
+impl Display for VacuumTableStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VACUUM TABLE ")?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )?;
+        if let Some(retain_hours) = self.retain_hours {
+            write!(f, " RETAIN {retain_hours} HOURS")?;
+        }
+        if self.dry_run {
+            write!(f, " DRY RUN")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyzeTableStmt<'a> {
+    pub catalog: Option<Identifier<'a>>,
+    pub database: Option<Identifier<'a>>,
+    pub table: Identifier<'a>,
+    pub columns: Vec<Identifier<'a>>,
+}
+
+impl Display for AnalyzeTableStmt<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ANALYZE TABLE ")?;
+        write_period_separated_list(
+            f,
+            self.catalog
+                .iter()
+                .chain(&self.database)
+                .chain(Some(&self.table)),
+        )?;
+        if !self.columns.is_empty() {
+            write!(f, " COLUMNS (")?;
+            write_comma_separated_list(f, &self.columns)?;
+            write!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ExistsTableStmt<'a> {
     pub catalog: Option<Identifier<'a>>,
@@ -450,6 +555,8 @@ pub struct ColumnDefinition<'a> {
     pub name: Identifier<'a>,
     pub data_type: TypeName,
     pub default_expr: Option<Box<Expr<'a>>>,
+    pub generated_expr: Option<Box<Expr<'a>>>,
+    pub check_constraints: Vec<CheckConstraint<'a>>,
     pub comment: Option<String>,
 }
 
@@ -464,9 +571,30 @@ impl<'a> Display for ColumnDefinition<'a> {
         if let Some(default_expr) = &self.default_expr {
             write!(f, " DEFAULT {default_expr}")?;
         }
+        if let Some(generated_expr) = &self.generated_expr {
+            write!(f, " AS ({generated_expr}) STORED")?;
+        }
+        for check_constraint in &self.check_constraints {
+            write!(f, " {check_constraint}")?;
+        }
         if let Some(comment) = &self.comment {
             write!(f, " COMMENT '{comment}'")?;
         }
         Ok(())
     }
 }
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckConstraint<'a> {
+    pub name: Option<Identifier<'a>>,
+    pub expr: Box<Expr<'a>>,
+}
+
+impl<'a> Display for CheckConstraint<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "CONSTRAINT {name} ")?;
+        }
+        write!(f, "CHECK ({})", self.expr)
+    }
+}
@@ -222,7 +222,9 @@ pub enum ExprElement<'a> {
         lit: Literal,
     },
     /// `Count(*)` expression
-    CountAll,
+    CountAll {
+        window: Option<WindowSpec<'a>>,
+    },
     /// `(foo, bar)`
     Tuple {
         exprs: Vec<Expr<'a>>,
@@ -234,6 +236,7 @@ pub enum ExprElement<'a> {
         name: Identifier<'a>,
         args: Vec<Expr<'a>>,
         params: Vec<Literal>,
+        window: Option<WindowSpec<'a>>,
     },
     /// `CASE ... WHEN ... ELSE ...` expression
     Case {
@@ -410,7 +413,10 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 span: elem.span.0,
                 lit,
             },
-            ExprElement::CountAll => Expr::CountAll { span: elem.span.0 },
+            ExprElement::CountAll { window } => Expr::CountAll {
+                span: elem.span.0,
+                window,
+            },
             ExprElement::Tuple { exprs } => Expr::Tuple {
                 span: elem.span.0,
                 exprs,
@@ -420,12 +426,14 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement<'a>>>> PrattParser<I> for E
                 name,
                 args,
                 params,
+                window,
             } => Expr::FunctionCall {
                 span: elem.span.0,
                 distinct,
                 name,
                 args,
                 params,
+                window,
             },
             ExprElement::Case {
                 operand,
@@ -734,9 +742,12 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             trim_where: Some((trim_where, Box::new(trim_str))),
         },
     );
-    let count_all = value(ExprElement::CountAll, rule! {
-        COUNT ~ "(" ~ "*" ~ ^")"
-    });
+    let count_all = map(
+        rule! {
+            COUNT ~ "(" ~ "*" ~ ^")" ~ #window_clause?
+        },
+        |(_, _, _, _, window)| ExprElement::CountAll { window },
+    );
     let tuple = map(
         rule! {
             "(" ~ #subexpr(0) ~ "," ~ #comma_separated_list1_allow_trailling(subexpr(0))? ~ ","? ~ ^")"
@@ -754,12 +765,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             ~ DISTINCT?
             ~ #comma_separated_list0(subexpr(0))?
             ~ ")"
+            ~ #window_clause?
         },
-        |(name, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params: vec![],
+            window,
         },
     );
     let function_call_with_param = map(
@@ -767,12 +780,14 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             #function_name
             ~ "(" ~ #comma_separated_list1(literal) ~ ")"
             ~ "(" ~ DISTINCT? ~ #comma_separated_list0(subexpr(0))? ~ ")"
+            ~ #window_clause?
         },
-        |(name, _, params, _, _, opt_distinct, opt_args, _)| ExprElement::FunctionCall {
+        |(name, _, params, _, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
             distinct: opt_distinct.is_some(),
             name,
             args: opt_args.unwrap_or_default(),
             params,
+            window,
         },
     );
     let case = map(
@@ -956,6 +971,84 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
     Ok((rest, WithSpan { span, elem }))
 }
 
+pub fn window_clause(i: Input) -> IResult<WindowSpec> {
+    map(
+        rule! {
+            OVER ~ ^"(" ~ ^#window_spec ~ ^")"
+        },
+        |(_, _, spec, _)| spec,
+    )(i)
+}
+
+pub fn window_spec(i: Input) -> IResult<WindowSpec> {
+    map(
+        rule! {
+            ( PARTITION ~ ^BY ~ ^#comma_separated_list1(subexpr(0)) )?
+            ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
+            ~ #window_frame?
+        },
+        |(opt_partition_by, opt_order_by, window_frame)| WindowSpec {
+            partition_by: opt_partition_by
+                .map(|(_, _, exprs)| exprs)
+                .unwrap_or_default(),
+            order_by: opt_order_by.map(|(_, _, exprs)| exprs).unwrap_or_default(),
+            window_frame,
+        },
+    )(i)
+}
+
+pub fn window_frame(i: Input) -> IResult<WindowFrame> {
+    let units = alt((
+        value(WindowFrameUnits::Rows, rule! { ROWS }),
+        value(WindowFrameUnits::Range, rule! { RANGE }),
+    ));
+    let between = map(
+        rule! {
+            BETWEEN ~ ^#window_frame_bound ~ ^AND ~ ^#window_frame_bound
+        },
+        |(_, start_bound, _, end_bound)| (start_bound, end_bound),
+    );
+    // `ROWS <bound>` is a shorthand of `ROWS BETWEEN <bound> AND CURRENT ROW`
+    let start_only = map(window_frame_bound, |start_bound| {
+        (start_bound, WindowFrameBound::CurrentRow)
+    });
+
+    map(
+        rule! {
+            #units ~ ^( #between | #start_only )
+        },
+        |(units, (start_bound, end_bound))| WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        },
+    )(i)
+}
+
+pub fn window_frame_bound(i: Input) -> IResult<WindowFrameBound> {
+    let current_row = map(rule! { CURRENT ~ ROW }, |_| WindowFrameBound::CurrentRow);
+    let unbounded_preceding = map(rule! { UNBOUNDED ~ PRECEDING }, |_| {
+        WindowFrameBound::Preceding(None)
+    });
+    let unbounded_following = map(rule! { UNBOUNDED ~ FOLLOWING }, |_| {
+        WindowFrameBound::Following(None)
+    });
+    let preceding = map(rule! { #subexpr(0) ~ PRECEDING }, |(expr, _)| {
+        WindowFrameBound::Preceding(Some(Box::new(expr)))
+    });
+    let following = map(rule! { #subexpr(0) ~ FOLLOWING }, |(expr, _)| {
+        WindowFrameBound::Following(Some(Box::new(expr)))
+    });
+
+    rule!(
+        #current_row : "`CURRENT ROW`"
+        | #unbounded_preceding : "`UNBOUNDED PRECEDING`"
+        | #unbounded_following : "`UNBOUNDED FOLLOWING`"
+        | #preceding : "`<expr> PRECEDING`"
+        | #following : "`<expr> FOLLOWING`"
+    )(i)
+}
+
 pub fn unary_op(i: Input) -> IResult<UnaryOperator> {
     // Plus and Minus are parsed as binary op at first.
     value(UnaryOperator::Not, rule! { NOT })(i)
@@ -1133,6 +1226,21 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
     );
     let ty_float32 = value(TypeName::Float32, rule! { FLOAT32 | FLOAT });
     let ty_float64 = value(TypeName::Float64, rule! { FLOAT64 | DOUBLE });
+    let ty_decimal = map(
+        rule! {
+            ( DECIMAL | NUMERIC ) ~ ( "(" ~ #literal_u64 ~ ( "," ~ #literal_u64 )? ~ ")" )?
+        },
+        |(_, opt_args)| match opt_args {
+            Some((_, precision, opt_scale, _)) => TypeName::Decimal {
+                precision: Some(precision),
+                scale: opt_scale.map(|(_, scale)| scale),
+            },
+            None => TypeName::Decimal {
+                precision: None,
+                scale: None,
+            },
+        },
+    );
     let ty_array = map(
         rule! { ARRAY ~ ( "(" ~ #type_name ~ ")" )? },
         |(_, opt_item_type)| TypeName::Array {
@@ -1174,6 +1282,13 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
         TypeName::String,
         rule! { ( STRING | VARCHAR | CHAR | CHARACTER | TEXT  ) ~ ( "(" ~ #literal_u64 ~ ")" )? },
     );
+    let ty_map = map(
+        rule! { MAP ~ "(" ~ #type_name ~ "," ~ #type_name ~ ")" },
+        |(_, _, key_type, _, val_type, _)| TypeName::Map {
+            key_type: Box::new(key_type),
+            val_type: Box::new(val_type),
+        },
+    );
     let ty_object = value(TypeName::Object, rule! { OBJECT | MAP });
     let ty_variant = value(TypeName::Variant, rule! { VARIANT | JSON });
     map(
@@ -1189,11 +1304,13 @@ pub fn type_name(i: Input) -> IResult<TypeName> {
             | #ty_int64
             | #ty_float32
             | #ty_float64
+            | #ty_decimal
             | #ty_array
             | #ty_tuple
             | #ty_date
             | #ty_datetime
             | #ty_string
+            | #ty_map
             | #ty_object
             | #ty_variant
             ) ~ NULL? : "type name"
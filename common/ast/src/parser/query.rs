@@ -32,15 +32,20 @@ use crate::util::*;
 pub fn query(i: Input) -> IResult<Query> {
     map(
         consumed(rule! {
-            #set_operation
+            #with?
+            ~ #set_operation
             ~ ( ORDER ~ ^BY ~ ^#comma_separated_list1(order_by_expr) )?
             ~ ( LIMIT ~ ^#comma_separated_list1(expr) )?
             ~ ( OFFSET ~ ^#expr )?
             ~ ( FORMAT ~ #ident )?
             : "`SELECT ...`"
         }),
-        |(span, (body, opt_order_by_block, opt_limit_block, opt_offset_block, opt_format))| Query {
+        |(
+            span,
+            (with, body, opt_order_by_block, opt_limit_block, opt_offset_block, opt_format),
+        )| Query {
             span: span.0,
+            with,
             body,
             order_by: opt_order_by_block
                 .map(|(_, _, order_by)| order_by)
@@ -52,6 +57,35 @@ pub fn query(i: Input) -> IResult<Query> {
     )(i)
 }
 
+pub fn with(i: Input) -> IResult<With> {
+    let cte = map(
+        consumed(rule! {
+            #ident ~ ( "(" ~ ^#comma_separated_list1(ident) ~ ^")" )? ~ AS ~ ^"(" ~ ^#query ~ ^")"
+        }),
+        |(span, (name, opt_columns, _, _, query, _))| CTE {
+            span: span.0,
+            alias: TableAlias {
+                name,
+                columns: opt_columns
+                    .map(|(_, columns, _)| columns)
+                    .unwrap_or_default(),
+            },
+            query,
+        },
+    );
+
+    map(
+        consumed(rule! {
+            WITH ~ RECURSIVE? ~ ^#comma_separated_list1(cte)
+        }),
+        |(span, (_, opt_recursive, ctes))| With {
+            span: span.0,
+            recursive: opt_recursive.is_some(),
+            ctes,
+        },
+    )(i)
+}
+
 pub fn select_target(i: Input) -> IResult<SelectTarget> {
     let qualified_wildcard = map(
         rule! {
@@ -72,20 +72,6 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         },
     );
 
-    let table_reference_only = map(
-        consumed(rule! {
-            #peroid_separated_idents_1_to_3
-        }),
-        |(input, (catalog, database, table))| TableReference::Table {
-            span: input.0,
-            catalog,
-            database,
-            table,
-            alias: None,
-            travel_point: None,
-        },
-    );
-
     let delete = map(
         rule! {
             DELETE ~ FROM ~ #table_reference_only
@@ -97,6 +83,51 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         },
     );
 
+    let update = map(
+        rule! {
+            UPDATE ~ #table_reference_only
+            ~ SET ~ ^#comma_separated_list1(update_expr)
+            ~ ( WHERE ~ ^#expr )?
+        },
+        |(_, table_reference, _, update_list, opt_selection)| {
+            Statement::Update(UpdateStmt {
+                table_reference,
+                update_list,
+                selection: opt_selection.map(|(_, selection)| selection),
+            })
+        },
+    );
+
+    let merge_into = map(
+        rule! {
+            MERGE ~ INTO ~ #aliased_table
+            ~ USING ~ ^#table_reference
+            ~ ON ~ ^#expr
+            ~ #merge_clause+
+        },
+        |(_, _, target, _, source, _, join_expr, clauses)| {
+            let mut matched_clauses = vec![];
+            let mut unmatched_clauses = vec![];
+            for clause in clauses {
+                match clause {
+                    MergeClause::Matched(clause) => matched_clauses.push(clause),
+                    MergeClause::Unmatched(clause) => unmatched_clauses.push(clause),
+                }
+            }
+            Statement::MergeInto(MergeIntoStmt {
+                target,
+                source,
+                join_expr,
+                matched_clauses,
+                unmatched_clauses,
+            })
+        },
+    );
+
+    let begin = value(Statement::Begin, rule! { BEGIN ~ TRANSACTION? });
+    let commit = value(Statement::Commit, rule! { COMMIT });
+    let rollback = value(Statement::Rollback, rule! { ROLLBACK });
+
     let show_settings = map(
         rule! {
             SHOW ~ SETTINGS ~ (LIKE ~ #literal_string)?
@@ -396,6 +427,38 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let vacuum_table = map(
+        rule! {
+            VACUUM ~ TABLE ~ #peroid_separated_idents_1_to_3
+            ~ (RETAIN ~ #literal_u64 ~ HOURS)?
+            ~ (DRY ~ RUN)?
+        },
+        |(_, _, (catalog, database, table), opt_retain, opt_dry_run)| {
+            Statement::VacuumTable(VacuumTableStmt {
+                catalog,
+                database,
+                table,
+                retain_hours: opt_retain.map(|(_, hours, _)| hours),
+                dry_run: opt_dry_run.is_some(),
+            })
+        },
+    );
+    let analyze_table = map(
+        rule! {
+            ANALYZE ~ TABLE ~ #peroid_separated_idents_1_to_3
+            ~ ( COLUMNS ~ ^"(" ~ ^#comma_separated_list1(ident) ~ ^")" )?
+        },
+        |(_, _, (catalog, database, table), opt_columns)| {
+            Statement::AnalyzeTable(AnalyzeTableStmt {
+                catalog,
+                database,
+                table,
+                columns: opt_columns
+                    .map(|(_, _, columns, _)| columns)
+                    .unwrap_or_default(),
+            })
+        },
+    );
     let exists_table = map(
         rule! {
             EXISTS ~ TABLE ~ #peroid_separated_idents_1_to_3
@@ -452,6 +515,51 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             })
         },
     );
+    let create_index = map(
+        rule! {
+            CREATE ~ INDEX ~ ( IF ~ NOT ~ EXISTS )? ~ #ident
+            ~ ON ~ #peroid_separated_idents_1_to_3
+            ~ "(" ~ #ident ~ ")" ~ TYPE ~ #index_type
+        },
+        |(
+            _,
+            _,
+            opt_if_not_exists,
+            index,
+            _,
+            (catalog, database, table),
+            _,
+            column,
+            _,
+            _,
+            index_type,
+        )| {
+            Statement::CreateIndex(CreateIndexStmt {
+                if_not_exists: opt_if_not_exists.is_some(),
+                index,
+                catalog,
+                database,
+                table,
+                column,
+                index_type,
+            })
+        },
+    );
+    let drop_index = map(
+        rule! {
+            DROP ~ INDEX ~ ( IF ~ EXISTS )? ~ #ident
+            ~ ON ~ #peroid_separated_idents_1_to_3
+        },
+        |(_, _, opt_if_exists, index, _, (catalog, database, table))| {
+            Statement::DropIndex(DropIndexStmt {
+                if_exists: opt_if_exists.is_some(),
+                index,
+                catalog,
+                database,
+                table,
+            })
+        },
+    );
     let show_users = value(Statement::ShowUsers, rule! { SHOW ~ USERS });
     let create_user = map(
         rule! {
@@ -747,6 +855,8 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #explain : "`EXPLAIN [PIPELINE | GRAPH] <statement>`"
             | #insert : "`INSERT INTO [TABLE] <table> [(<column>, ...)] (FORMAT <format> | VALUES <values> | <query>)`"
             | #delete : "`DELETE FROM <table> [WHERE ...]`"
+            | #update : "`UPDATE <table> SET <column> = <expr> [, <column> = <expr> , ... ] [WHERE ...]`"
+            | #merge_into : "`MERGE INTO <table> USING <source> ON <expr> { WHEN MATCHED [AND <expr>] THEN { UPDATE SET ... | DELETE } | WHEN NOT MATCHED [AND <expr>] THEN INSERT [(<column>, ...)] VALUES (<expr>, ...) } ...`"
             | #show_settings : "`SHOW SETTINGS [<show_limit>]`"
             | #show_stages : "`SHOW STAGES`"
             | #show_engines : "`SHOW ENGINES`"
@@ -763,6 +873,11 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #alter_database : "`ALTER DATABASE [IF EXISTS] <action>`"
             | #use_database : "`USE <database>`"
         ),
+        rule!(
+            #begin : "`BEGIN [TRANSACTION]`"
+            | #commit : "`COMMIT`"
+            | #rollback : "`ROLLBACK`"
+        ),
         rule!(
             #show_tables : "`SHOW [FULL] TABLES [FROM <database>] [<show_limit>]`"
             | #show_create_table : "`SHOW CREATE TABLE [<database>.]<table>`"
@@ -776,6 +891,8 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #rename_table : "`RENAME TABLE [<database>.]<table> TO <new_table>`"
             | #truncate_table : "`TRUNCATE TABLE [<database>.]<table> [PURGE]`"
             | #optimize_table : "`OPTIMIZE TABLE [<database>.]<table> (ALL | PURGE | COMPACT)`"
+            | #vacuum_table : "`VACUUM TABLE [<database>.]<table> [RETAIN <n> HOURS] [DRY RUN]`"
+            | #analyze_table : "`ANALYZE TABLE [<database>.]<table> [COLUMNS (<column>, ...)]`"
             | #exists_table : "`EXISTS TABLE [<database>.]<table>`"
         ),
         rule!(
@@ -783,6 +900,10 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
             | #drop_view : "`DROP VIEW [IF EXISTS] [<database>.]<view>`"
             | #alter_view : "`ALTER VIEW [<database>.]<view> AS SELECT ...`"
         ),
+        rule!(
+            #create_index : "`CREATE INDEX [IF NOT EXISTS] <index> ON [<database>.]<table> (<column>) TYPE (BLOOM | MINMAX | NGRAM | SET)`"
+            | #drop_index : "`DROP INDEX [IF EXISTS] <index> ON [<database>.]<table>`"
+        ),
         rule!(
             #show_users : "`SHOW USERS`"
             | #create_user : "`CREATE USER [IF NOT EXISTS] '<username>'@'hostname' IDENTIFIED [WITH <auth_type>] [BY <password>] [WITH <role_option> ...]`"
@@ -870,6 +991,70 @@ pub fn insert_source(i: Input) -> IResult<InsertSource> {
     )(i)
 }
 
+pub fn table_reference_only(i: Input) -> IResult<TableReference> {
+    map(
+        consumed(rule! {
+            #peroid_separated_idents_1_to_3
+        }),
+        |(input, (catalog, database, table))| TableReference::Table {
+            span: input.0,
+            catalog,
+            database,
+            table,
+            alias: None,
+            travel_point: None,
+        },
+    )(i)
+}
+
+pub fn update_expr(i: Input) -> IResult<UpdateExpr> {
+    map(rule! { #ident ~ "=" ~ ^#expr }, |(name, _, expr)| {
+        UpdateExpr { name, expr }
+    })(i)
+}
+
+pub fn merge_clause(i: Input) -> IResult<MergeClause> {
+    let update = map(
+        rule! {
+            UPDATE ~ ^SET ~ ^#comma_separated_list1(update_expr)
+        },
+        |(_, _, update_list)| MatchOperation::Update { update_list },
+    );
+    let delete = value(MatchOperation::Delete, rule! { DELETE });
+    let matched = map(
+        rule! {
+            WHEN ~ MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^( #update | #delete )
+        },
+        |(_, _, opt_selection, _, operation)| {
+            MergeClause::Matched(MatchedClause {
+                selection: opt_selection.map(|(_, selection)| selection),
+                operation,
+            })
+        },
+    );
+    let unmatched = map(
+        rule! {
+            WHEN ~ NOT ~ ^MATCHED ~ ( AND ~ ^#expr )? ~ ^THEN ~ ^INSERT
+            ~ ( "(" ~ #comma_separated_list1(ident) ~ ")" )?
+            ~ ^VALUES ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, _, _, opt_selection, _, _, opt_columns, _, _, values, _)| {
+            MergeClause::Unmatched(UnmatchedClause {
+                selection: opt_selection.map(|(_, selection)| selection),
+                columns: opt_columns
+                    .map(|(_, columns, _)| columns)
+                    .unwrap_or_default(),
+                values,
+            })
+        },
+    );
+
+    rule!(
+        #matched
+        | #unmatched
+    )(i)
+}
+
 pub fn rest_tokens<'a>(i: Input<'a>) -> IResult<&'a [Token]> {
     if i.last().map(|token| token.kind) == Some(EOI) {
         Ok((i.slice(i.len() - 1..), i.slice(..i.len() - 1).0))
@@ -883,6 +1068,8 @@ pub fn column_def(i: Input) -> IResult<ColumnDefinition> {
     enum ColumnConstraint<'a> {
         Nullable(bool),
         DefaultExpr(Box<Expr<'a>>),
+        GeneratedExpr(Box<Expr<'a>>),
+        Check(CheckConstraint<'a>),
     }
 
     let nullable = alt((
@@ -895,6 +1082,13 @@ pub fn column_def(i: Input) -> IResult<ColumnDefinition> {
         },
         |(_, default_expr)| ColumnConstraint::DefaultExpr(Box::new(default_expr)),
     );
+    let generated_expr = map(
+        rule! {
+            ( GENERATED ~ ^ALWAYS )? ~ AS ~ ^"(" ~ ^#expr ~ ^")" ~ ^STORED
+        },
+        |(_, _, _, generated_expr, _, _)| ColumnConstraint::GeneratedExpr(Box::new(generated_expr)),
+    );
+    let check = map(check_constraint, ColumnConstraint::Check);
 
     let comment = map(
         rule! {
@@ -907,15 +1101,17 @@ pub fn column_def(i: Input) -> IResult<ColumnDefinition> {
         rule! {
             #ident
             ~ #type_name
-            ~ ( #nullable | #default_expr )*
+            ~ ( #nullable | #default_expr | #generated_expr | #check )*
             ~ ( #comment )?
-            : "`<column name> <type> [DEFAULT <default value>] [COMMENT '<comment>']`"
+            : "`<column name> <type> [DEFAULT <default value>] [AS (<expr>) STORED] [CHECK (<expr>)] [COMMENT '<comment>']`"
         },
         |(name, data_type, constraints, comment)| {
             let mut def = ColumnDefinition {
                 name,
                 data_type,
                 default_expr: None,
+                generated_expr: None,
+                check_constraints: vec![],
                 comment,
             };
             for constraint in constraints {
@@ -923,6 +1119,12 @@ pub fn column_def(i: Input) -> IResult<ColumnDefinition> {
                     ColumnConstraint::DefaultExpr(default_expr) => {
                         def.default_expr = Some(default_expr)
                     }
+                    ColumnConstraint::GeneratedExpr(generated_expr) => {
+                        def.generated_expr = Some(generated_expr)
+                    }
+                    ColumnConstraint::Check(check_constraint) => {
+                        def.check_constraints.push(check_constraint)
+                    }
                     ColumnConstraint::Nullable(nullable) => {
                         if nullable {
                             def.data_type = def.data_type.wrap_nullable();
@@ -935,6 +1137,18 @@ pub fn column_def(i: Input) -> IResult<ColumnDefinition> {
     )(i)
 }
 
+pub fn check_constraint(i: Input) -> IResult<CheckConstraint> {
+    map(
+        rule! {
+            ( CONSTRAINT ~ #ident )? ~ CHECK ~ "(" ~ ^#expr ~ ^")"
+        },
+        |(name, _, _, expr, _)| CheckConstraint {
+            name: name.map(|(_, name)| name),
+            expr: Box::new(expr),
+        },
+    )(i)
+}
+
 pub fn grant_source(i: Input) -> IResult<AccountMgrSource> {
     let role = map(
         rule! {
@@ -1033,11 +1247,31 @@ pub fn grant_option(i: Input) -> IResult<PrincipalIdentity> {
 }
 
 pub fn create_table_source(i: Input) -> IResult<CreateTableSource> {
+    #[derive(Clone)]
+    enum CreateDefinition<'a> {
+        Column(ColumnDefinition<'a>),
+        Check(CheckConstraint<'a>),
+    }
+
+    let create_def = alt((
+        map(check_constraint, CreateDefinition::Check),
+        map(column_def, CreateDefinition::Column),
+    ));
     let columns = map(
         rule! {
-            "(" ~ ^#comma_separated_list1(column_def) ~ ^")"
+            "(" ~ ^#comma_separated_list1(create_def) ~ ^")"
+        },
+        |(_, defs, _)| {
+            let mut columns = vec![];
+            let mut check_constraints = vec![];
+            for def in defs {
+                match def {
+                    CreateDefinition::Column(column) => columns.push(column),
+                    CreateDefinition::Check(check) => check_constraints.push(check),
+                }
+            }
+            CreateTableSource::Columns(columns, check_constraints)
         },
-        |(_, columns, _)| CreateTableSource::Columns(columns),
     );
     let like = map(
         rule! {
@@ -1091,10 +1325,48 @@ pub fn alter_table_action(i: Input) -> IResult<AlterTableAction> {
         |(_, _, _)| AlterTableAction::DropTableClusterKey,
     );
 
+    let recluster_table = map(
+        rule! {
+            RECLUSTER ~ FINAL? ~ ( WHERE ~ ^#expr )?
+        },
+        |(_, opt_is_final, opt_selection)| AlterTableAction::ReclusterTable {
+            is_final: opt_is_final.is_some(),
+            selection: opt_selection.map(|(_, selection)| selection),
+        },
+    );
+
+    let add_column = map(
+        rule! {
+            ADD ~ COLUMN ~ ^#column_def
+        },
+        |(_, _, column)| AlterTableAction::AddColumn { column },
+    );
+
+    let drop_column = map(
+        rule! {
+            DROP ~ COLUMN ~ ^#ident
+        },
+        |(_, _, column)| AlterTableAction::DropColumn { column },
+    );
+
+    let rename_column = map(
+        rule! {
+            RENAME ~ COLUMN ~ ^#ident ~ ^TO ~ ^#ident
+        },
+        |(_, _, old_column, _, new_column)| AlterTableAction::RenameColumn {
+            old_column,
+            new_column,
+        },
+    );
+
     rule!(
         #rename_table
         | #alter_table_cluster_key
         | #drop_table_cluster_key
+        | #recluster_table
+        | #add_column
+        | #drop_column
+        | #rename_column
     )(i)
 }
 
@@ -1309,6 +1581,15 @@ pub fn auth_type(i: Input) -> IResult<AuthType> {
     ))(i)
 }
 
+pub fn index_type(i: Input) -> IResult<IndexType> {
+    alt((
+        value(IndexType::Bloom, rule! { BLOOM }),
+        value(IndexType::MinMax, rule! { MINMAX }),
+        value(IndexType::Ngram, rule! { NGRAM }),
+        value(IndexType::Set, rule! { SET }),
+    ))(i)
+}
+
 pub fn ident_to_string(i: Input) -> IResult<String> {
     map_res(ident, |ident| Ok(ident.name))(i)
 }
@@ -235,12 +235,16 @@ pub enum TokenKind {
     // 2. Search in this file to see if the new keyword is a commented
     //    out reserverd keyword. If so, uncomment the keyword in the
     //    reserved list.
+    #[token("ADD", ignore(ascii_case))]
+    ADD,
     #[token("ALL", ignore(ascii_case))]
     ALL,
     #[token("ANY", ignore(ascii_case))]
     ANY,
     #[token("SOME", ignore(ascii_case))]
     SOME,
+    #[token("ALWAYS", ignore(ascii_case))]
+    ALWAYS,
     #[token("ALTER", ignore(ascii_case))]
     ALTER,
     #[token("ANALYZE", ignore(ascii_case))]
@@ -259,10 +263,14 @@ pub enum TokenKind {
     AWS_KEY_ID,
     #[token("AWS_SECRET_KEY", ignore(ascii_case))]
     AWS_SECRET_KEY,
+    #[token("BEGIN", ignore(ascii_case))]
+    BEGIN,
     #[token("BETWEEN", ignore(ascii_case))]
     BETWEEN,
     #[token("BIGINT", ignore(ascii_case))]
     BIGINT,
+    #[token("BLOOM", ignore(ascii_case))]
+    BLOOM,
     #[token("BOOL", ignore(ascii_case))]
     BOOL,
     #[token("BOOLEAN", ignore(ascii_case))]
@@ -279,16 +287,26 @@ pub enum TokenKind {
     CAST,
     #[token("CENTURY", ignore(ascii_case))]
     CENTURY,
+    #[token("CHECK", ignore(ascii_case))]
+    CHECK,
     #[token("CLUSTER", ignore(ascii_case))]
     CLUSTER,
+    #[token("COLUMN", ignore(ascii_case))]
+    COLUMN,
+    #[token("COLUMNS", ignore(ascii_case))]
+    COLUMNS,
     #[token("COMMENT", ignore(ascii_case))]
     COMMENT,
     #[token("COMMENTS", ignore(ascii_case))]
     COMMENTS,
+    #[token("COMMIT", ignore(ascii_case))]
+    COMMIT,
     #[token("COMPACT", ignore(ascii_case))]
     COMPACT,
     #[token("CONFIGRELOAD", ignore(ascii_case))]
     CONFIGRELOAD,
+    #[token("CONSTRAINT", ignore(ascii_case))]
+    CONSTRAINT,
     #[token("CONNECTION", ignore(ascii_case))]
     CONNECTION,
     #[token("CHAR", ignore(ascii_case))]
@@ -309,6 +327,8 @@ pub enum TokenKind {
     CROSS,
     #[token("CSV", ignore(ascii_case))]
     CSV,
+    #[token("CURRENT", ignore(ascii_case))]
+    CURRENT,
     #[token("CURRENT_TIMESTAMP", ignore(ascii_case))]
     CURRENT_TIMESTAMP,
     #[token("DATABASE", ignore(ascii_case))]
@@ -329,6 +349,8 @@ pub enum TokenKind {
     DAY,
     #[token("DECADE", ignore(ascii_case))]
     DECADE,
+    #[token("DECIMAL", ignore(ascii_case))]
+    DECIMAL,
     #[token("DEFAULT", ignore(ascii_case))]
     DEFAULT,
     #[token("DELETE", ignore(ascii_case))]
@@ -353,6 +375,8 @@ pub enum TokenKind {
     DOWNLOAD,
     #[token("DROP", ignore(ascii_case))]
     DROP,
+    #[token("DRY", ignore(ascii_case))]
+    DRY,
     #[token("EXCEPT", ignore(ascii_case))]
     EXCEPT,
     #[token("ELSE", ignore(ascii_case))]
@@ -383,10 +407,14 @@ pub enum TokenKind {
     FIELDS,
     #[token("FIELD_DELIMITER", ignore(ascii_case))]
     FIELD_DELIMITER,
+    #[token("FOLLOWING", ignore(ascii_case))]
+    FOLLOWING,
     #[token("FILE_FORMAT", ignore(ascii_case))]
     FILE_FORMAT,
     #[token("FILES", ignore(ascii_case))]
     FILES,
+    #[token("FINAL", ignore(ascii_case))]
+    FINAL,
     #[token("FLOAT", ignore(ascii_case))]
     FLOAT,
     #[token("FLOAT32", ignore(ascii_case))]
@@ -411,6 +439,8 @@ pub enum TokenKind {
     FUSE,
     #[token("GITHUB", ignore(ascii_case))]
     GITHUB,
+    #[token("GENERATED", ignore(ascii_case))]
+    GENERATED,
     #[token("GLOBAL", ignore(ascii_case))]
     GLOBAL,
     #[token("GRAPH", ignore(ascii_case))]
@@ -423,6 +453,8 @@ pub enum TokenKind {
     HISTORY,
     #[token("HOUR", ignore(ascii_case))]
     HOUR,
+    #[token("HOURS", ignore(ascii_case))]
+    HOURS,
     #[token("INTERSECT", ignore(ascii_case))]
     INTERSECT,
     #[token("IDENTIFIED", ignore(ascii_case))]
@@ -431,6 +463,8 @@ pub enum TokenKind {
     IF,
     #[token("IN", ignore(ascii_case))]
     IN,
+    #[token("INDEX", ignore(ascii_case))]
+    INDEX,
     #[token("INNER", ignore(ascii_case))]
     INNER,
     #[token("INSERT", ignore(ascii_case))]
@@ -485,8 +519,12 @@ pub enum TokenKind {
     MAP,
     #[token("MASTER_KEY", ignore(ascii_case))]
     MASTER_KEY,
+    #[token("MATCHED", ignore(ascii_case))]
+    MATCHED,
     #[token("MEMORY", ignore(ascii_case))]
     MEMORY,
+    #[token("MERGE", ignore(ascii_case))]
+    MERGE,
     #[token("METRICS", ignore(ascii_case))]
     METRICS,
     #[token("MICROSECONDS", ignore(ascii_case))]
@@ -495,12 +533,16 @@ pub enum TokenKind {
     MILLENIUM,
     #[token("MILLISECONDS", ignore(ascii_case))]
     MILLISECONDS,
+    #[token("MINMAX", ignore(ascii_case))]
+    MINMAX,
     #[token("MINUTE", ignore(ascii_case))]
     MINUTE,
     #[token("MONTH", ignore(ascii_case))]
     MONTH,
     #[token("NATURAL", ignore(ascii_case))]
     NATURAL,
+    #[token("NGRAM", ignore(ascii_case))]
+    NGRAM,
     #[token("NO_PASSWORD", ignore(ascii_case))]
     NO_PASSWORD,
     #[token("NOCONFIGRELOAD", ignore(ascii_case))]
@@ -511,6 +553,8 @@ pub enum TokenKind {
     NOTENANTSETTING,
     #[token("NULL", ignore(ascii_case))]
     NULL,
+    #[token("NUMERIC", ignore(ascii_case))]
+    NUMERIC,
     #[token("OBJECT", ignore(ascii_case))]
     OBJECT,
     #[token("OFFSET", ignore(ascii_case))]
@@ -525,12 +569,16 @@ pub enum TokenKind {
     ORDER,
     #[token("OUTER", ignore(ascii_case))]
     OUTER,
+    #[token("OVER", ignore(ascii_case))]
+    OVER,
     #[token("ON_ERROR", ignore(ascii_case))]
     ON_ERROR,
     #[token("OVERWRITE", ignore(ascii_case))]
     OVERWRITE,
     #[token("PARQUET", ignore(ascii_case))]
     PARQUET,
+    #[token("PARTITION", ignore(ascii_case))]
+    PARTITION,
     #[token("PATTERN", ignore(ascii_case))]
     PATTERN,
     #[token("PIPELINE", ignore(ascii_case))]
@@ -539,6 +587,8 @@ pub enum TokenKind {
     PLAINTEXT_PASSWORD,
     #[token("POSITION", ignore(ascii_case))]
     POSITION,
+    #[token("PRECEDING", ignore(ascii_case))]
+    PRECEDING,
     #[token("PROCESSLIST", ignore(ascii_case))]
     PROCESSLIST,
     #[token("PURGE", ignore(ascii_case))]
@@ -547,12 +597,20 @@ pub enum TokenKind {
     QUARTER,
     #[token("QUERY", ignore(ascii_case))]
     QUERY,
+    #[token("RANGE", ignore(ascii_case))]
+    RANGE,
+    #[token("RECLUSTER", ignore(ascii_case))]
+    RECLUSTER,
     #[token("RECORD_DELIMITER", ignore(ascii_case))]
     RECORD_DELIMITER,
+    #[token("RECURSIVE", ignore(ascii_case))]
+    RECURSIVE,
     #[token("REGEXP", ignore(ascii_case))]
     REGEXP,
     #[token("RENAME", ignore(ascii_case))]
     RENAME,
+    #[token("RETAIN", ignore(ascii_case))]
+    RETAIN,
     #[token("GRANT", ignore(ascii_case))]
     GRANT,
     #[token("ROLE", ignore(ascii_case))]
@@ -569,8 +627,16 @@ pub enum TokenKind {
     GRANTS,
     #[token("RIGHT", ignore(ascii_case))]
     RIGHT,
+    #[token("ROLLBACK", ignore(ascii_case))]
+    ROLLBACK,
+    #[token("ROW", ignore(ascii_case))]
+    ROW,
+    #[token("ROWS", ignore(ascii_case))]
+    ROWS,
     #[token("RLIKE", ignore(ascii_case))]
     RLIKE,
+    #[token("RUN", ignore(ascii_case))]
+    RUN,
     #[token("SCHEMA", ignore(ascii_case))]
     SCHEMA,
     #[token("SCHEMAS", ignore(ascii_case))]
@@ -611,6 +677,8 @@ pub enum TokenKind {
     SUPER,
     #[token("STATUS", ignore(ascii_case))]
     STATUS,
+    #[token("STORED", ignore(ascii_case))]
+    STORED,
     #[token("STRING", ignore(ascii_case))]
     STRING,
     #[token("SUBSTRING", ignore(ascii_case))]
@@ -641,6 +709,8 @@ pub enum TokenKind {
     TOKEN,
     #[token("TRAILING", ignore(ascii_case))]
     TRAILING,
+    #[token("TRANSACTION", ignore(ascii_case))]
+    TRANSACTION,
     #[token("TRANSIENT", ignore(ascii_case))]
     TRANSIENT,
     #[token("TRIM", ignore(ascii_case))]
@@ -667,6 +737,8 @@ pub enum TokenKind {
     UINT8,
     #[token("UNDROP", ignore(ascii_case))]
     UNDROP,
+    #[token("UNBOUNDED", ignore(ascii_case))]
+    UNBOUNDED,
     #[token("UNSIGNED", ignore(ascii_case))]
     UNSIGNED,
     #[token("URL", ignore(ascii_case))]
@@ -679,6 +751,8 @@ pub enum TokenKind {
     USERS,
     #[token("USING", ignore(ascii_case))]
     USING,
+    #[token("VACUUM", ignore(ascii_case))]
+    VACUUM,
     #[token("VALUES", ignore(ascii_case))]
     VALUES,
     #[token("VALIDATION_MODE", ignore(ascii_case))]
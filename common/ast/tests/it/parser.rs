@@ -67,6 +67,9 @@ fn test_statement() {
         r#"show tables"#,
         r#"show tables format TabSeparatedWithNamesAndTypes;"#,
         r#"show processlist;"#,
+        r#"begin transaction;"#,
+        r#"commit;"#,
+        r#"rollback;"#,
         r#"show create table a.b;"#,
         r#"show create table a.b format TabSeparatedWithNamesAndTypes;"#,
         r#"explain pipeline select a from b;"#,
@@ -77,8 +80,15 @@ fn test_statement() {
         r#"create table if not exists a.b (c tuple(m integer, n string), d tuple(integer, string));"#,
         r#"create table a.b like c.d;"#,
         r#"create table t like t2 engine = memory;"#,
+        r#"create table t(a int check (a > 0), b int as (a + 1) stored, constraint c1 check (a < b));"#,
         r#"truncate table a;"#,
         r#"truncate table "a".b;"#,
+        r#"vacuum table a;"#,
+        r#"vacuum table db.b retain 12 hours dry run;"#,
+        r#"analyze table a;"#,
+        r#"analyze table db.b columns (c1, c2);"#,
+        r#"create index idx1 on t (c) type ngram;"#,
+        r#"drop index if exists idx1 on db.t;"#,
         r#"drop table a;"#,
         r#"drop table if exists a."b";"#,
         r#"use "a";"#,
@@ -133,6 +143,10 @@ fn test_statement() {
         r#"drop role if exists 'test'"#,
         r#"ALTER TABLE t CLUSTER BY(c1);"#,
         r#"ALTER TABLE t DROP CLUSTER KEY;"#,
+        r#"ALTER TABLE t RECLUSTER FINAL WHERE c1 > 0;"#,
+        r#"ALTER TABLE t ADD COLUMN c integer default 1;"#,
+        r#"ALTER TABLE t DROP COLUMN c;"#,
+        r#"ALTER TABLE t RENAME COLUMN a TO b;"#,
         r#"ALTER DATABASE IF EXISTS catalog.c RENAME TO a;"#,
         r#"ALTER DATABASE c RENAME TO a;"#,
         r#"ALTER DATABASE catalog.c RENAME TO a;"#,
@@ -353,6 +367,8 @@ fn test_query() {
         r#"select * from t1 union select * from t2 intersect select * from t3"#,
         r#"(select * from t1 union select * from t2) union select * from t3"#,
         r#"select * from t1 union (select * from t2 union select * from t3)"#,
+        r#"with t(a) as (select * from t1) select * from t"#,
+        r#"with recursive t as (select * from t1 union all select * from t) select * from t"#,
     ];
 
     for case in cases {
@@ -23,17 +23,23 @@ use common_datavalues::chrono;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_fuse_meta::meta::StatisticsOfColumns;
 use common_meta_app::schema::TableInfo;
 use common_meta_types::MetaId;
 use common_pipeline::Pipeline;
+use common_planners::AlterTableColumnPlan;
+use common_planners::AnalyzeTablePlan;
 use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::OptimizeTablePlan;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
+use common_planners::ReclusterTablePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
+use common_planners::VacuumTablePlan;
 
 use crate::table_context::TableContext;
 
@@ -167,10 +173,48 @@ pub trait Table: Sync + Send {
         Ok(())
     }
 
+    async fn vacuum(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _plan: VacuumTablePlan,
+    ) -> Result<VacuumStatistics> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support VACUUM",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
+    async fn analyze(&self, _ctx: Arc<dyn TableContext>, _plan: AnalyzeTablePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support ANALYZE",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
+    /// Builds the secondary indexes defined on the table for the data already written.
+    async fn build_indexes(&self, _ctx: Arc<dyn TableContext>, _catalog: &str) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support secondary indexes",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     async fn statistics(&self, _ctx: Arc<dyn TableContext>) -> Result<Option<TableStatistics>> {
         Ok(None)
     }
 
+    /// Statistics of the columns for the optimizer to estimate the cardinality,
+    /// keyed by the index of the column in the table schema.
+    async fn column_statistics(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+    ) -> Result<Option<StatisticsOfColumns>> {
+        Ok(None)
+    }
+
     async fn navigate_to(
         &self,
         _ctx: Arc<dyn TableContext>,
@@ -191,6 +235,14 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    async fn update(&self, _ctx: Arc<dyn TableContext>, _update_plan: UpdatePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support UPDATE",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     async fn compact(&self, _ctx: Arc<dyn TableContext>, _plan: OptimizeTablePlan) -> Result<()> {
         Err(ErrorCode::UnImplement(format!(
             "table {},  of engine type {}, does not support compact",
@@ -198,6 +250,30 @@ pub trait Table: Sync + Send {
             self.get_table_info().engine(),
         )))
     }
+
+    async fn recluster(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _plan: ReclusterTablePlan,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support recluster",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
+    async fn alter_table_column(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        _plan: AlterTableColumnPlan,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "table {},  of engine type {}, does not support altering columns",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
 }
 
 #[derive(Debug)]
@@ -213,3 +289,12 @@ pub struct TableStatistics {
     pub data_size_compressed: Option<u64>,
     pub index_size: Option<u64>,
 }
+
+/// The number of the files removed by VACUUM, or to be removed in the case of dry run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct VacuumStatistics {
+    pub snapshots: u64,
+    pub segments: u64,
+    pub blocks: u64,
+    pub bloom_indexes: u64,
+}
@@ -15,6 +15,8 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
 use common_base::base::Runtime;
@@ -22,11 +24,14 @@ use common_config::Config;
 use common_contexts::DalContext;
 use common_contexts::DalMetrics;
 use common_datablocks::DataBlock;
+use common_datavalues::chrono;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::scalars::FunctionContext;
 use common_fuse_meta::caches::CacheManager;
 use common_io::prelude::FormatSettings;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::UserInfo;
 use common_planners::Partitions;
 use common_planners::PlanNode;
@@ -58,6 +63,21 @@ pub struct ProcessInfo {
     pub mysql_connection_id: Option<u32>,
 }
 
+/// A task run in background by the node, such as the compaction of a table.
+pub struct BackgroundTaskInfo {
+    pub id: u64,
+    pub typ: String,
+    pub state: String,
+    pub database: String,
+    pub table: String,
+    /// Why the task is scheduled
+    pub reason: String,
+    pub node: String,
+    pub created_on: DateTime<Utc>,
+    pub finished_on: Option<DateTime<Utc>>,
+    pub error: Option<String>,
+}
+
 #[async_trait::async_trait]
 pub trait TableContext: Send + Sync {
     /// Build a table instance the plan wants to operate on.
@@ -86,6 +106,16 @@ pub trait TableContext: Send + Sync {
     fn get_fragment_id(&self) -> usize;
     fn get_catalogs(&self) -> Arc<CatalogManager>;
     fn get_catalog(&self, catalog_name: &str) -> Result<Arc<dyn Catalog>>;
+    /// Update the meta of a table. If the session is in a transaction, the new meta is kept in
+    /// the transaction until `COMMIT`, instead of being committed at once.
+    async fn update_table_meta(
+        &self,
+        catalog_name: &str,
+        table_info: &TableInfo,
+        req: UpdateTableMetaReq,
+    ) -> Result<()>;
+    /// Whether the query runs in a transaction started by `BEGIN`.
+    fn in_transaction(&self) -> bool;
     fn get_id(&self) -> String;
     fn get_current_catalog(&self) -> String;
     fn get_current_database(&self) -> String;
@@ -119,4 +149,5 @@ pub trait TableContext: Send + Sync {
     fn get_user_manager(&self) -> Arc<UserApiProvider>;
     fn get_cluster(&self) -> Arc<Cluster>;
     async fn get_processes_info(&self) -> Vec<ProcessInfo>;
+    fn get_background_tasks_info(&self) -> Vec<BackgroundTaskInfo>;
 }
@@ -145,12 +145,33 @@ pub struct QueryConfig {
     pub table_disk_cache_root: String,
     /// Table disk cache size (mb)
     pub table_disk_cache_mb_size: u64,
+    /// Local folder root to spill the data of queries into, the data is spilled into the
+    /// storage if it's empty
+    pub spill_root: String,
+    /// Query result cache memory size (mb)
+    pub query_result_cache_memory_mb_size: u64,
+    /// Query result cache disk root
+    pub query_result_cache_disk_root: String,
+    /// Query result cache disk size (mb)
+    pub query_result_cache_disk_mb_size: u64,
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     pub management_mode: bool,
     pub jwt_key_file: String,
     pub async_insert_max_data_size: u64,
     pub async_insert_busy_timeout: u64,
     pub async_insert_stale_timeout: u64,
+    /// Background compaction of tables enabled
+    pub background_tasks_enabled: bool,
+    /// Interval (seconds) of checking the tables for background tasks
+    pub background_tasks_interval_secs: u64,
+    /// Max number of background tasks running at the same time on a node
+    pub background_tasks_max_concurrency: u64,
+    /// Compact a table if it has more segments than this
+    pub background_compact_segment_threshold: u64,
+    /// Compact a table if the percentage of its small blocks is more than this
+    pub background_compact_small_block_ratio: u64,
+    /// Recluster a table if its blocks overlap deeper than this on the cluster key
+    pub background_recluster_depth_threshold: u64,
 }
 
 impl Default for QueryConfig {
@@ -193,11 +214,21 @@ impl Default for QueryConfig {
             table_memory_cache_mb_size: 256,
             table_disk_cache_root: "_cache".to_string(),
             table_disk_cache_mb_size: 1024,
+            spill_root: "_spill".to_string(),
+            query_result_cache_memory_mb_size: 128,
+            query_result_cache_disk_root: "_result_cache".to_string(),
+            query_result_cache_disk_mb_size: 1024,
             management_mode: false,
             jwt_key_file: "".to_string(),
             async_insert_max_data_size: 10000,
             async_insert_busy_timeout: 200,
             async_insert_stale_timeout: 0,
+            background_tasks_enabled: false,
+            background_tasks_interval_secs: 60,
+            background_tasks_max_concurrency: 2,
+            background_compact_segment_threshold: 100,
+            background_compact_small_block_ratio: 30,
+            background_recluster_depth_threshold: 8,
         }
     }
 }
@@ -628,6 +628,23 @@ pub struct QueryConfig {
     #[clap(long, default_value = "1024")]
     pub table_disk_cache_mb_size: u64,
 
+    /// Local folder root to spill the data of queries into, the data is spilled into the
+    /// storage if it's empty.
+    #[clap(long, default_value = "_spill")]
+    pub spill_root: String,
+
+    /// Query result cache memory size (mb)
+    #[clap(long, default_value = "128")]
+    pub query_result_cache_memory_mb_size: u64,
+
+    /// Query result cache disk root
+    #[clap(long, default_value = "_result_cache")]
+    pub query_result_cache_disk_root: String,
+
+    /// Query result cache disk size (mb)
+    #[clap(long, default_value = "1024")]
+    pub query_result_cache_disk_mb_size: u64,
+
     /// If in management mode, only can do some meta level operations(database/table/user/stage etc.) with metasrv.
     #[clap(long)]
     pub management_mode: bool,
@@ -646,6 +663,30 @@ pub struct QueryConfig {
     /// The maximum timeout in milliseconds since the last insert before inserting collected data.
     #[clap(long, default_value = "0")]
     pub async_insert_stale_timeout: u64,
+
+    /// Background compaction of tables enabled
+    #[clap(long)]
+    pub background_tasks_enabled: bool,
+
+    /// Interval in seconds of checking the tables for background tasks.
+    #[clap(long, default_value = "60")]
+    pub background_tasks_interval_secs: u64,
+
+    /// The maximum number of background tasks running at the same time on a node.
+    #[clap(long, default_value = "2")]
+    pub background_tasks_max_concurrency: u64,
+
+    /// Compact a table in background if it has more segments than this.
+    #[clap(long, default_value = "100")]
+    pub background_compact_segment_threshold: u64,
+
+    /// Compact a table in background if the percentage of its small blocks is more than this.
+    #[clap(long, default_value = "30")]
+    pub background_compact_small_block_ratio: u64,
+
+    /// Recluster a table in background if its blocks overlap deeper than this on the cluster key.
+    #[clap(long, default_value = "8")]
+    pub background_recluster_depth_threshold: u64,
 }
 
 impl Default for QueryConfig {
@@ -696,11 +737,21 @@ impl TryInto<InnerQueryConfig> for QueryConfig {
             table_memory_cache_mb_size: self.table_memory_cache_mb_size,
             table_disk_cache_root: self.table_disk_cache_root,
             table_disk_cache_mb_size: self.table_disk_cache_mb_size,
+            spill_root: self.spill_root,
+            query_result_cache_memory_mb_size: self.query_result_cache_memory_mb_size,
+            query_result_cache_disk_root: self.query_result_cache_disk_root,
+            query_result_cache_disk_mb_size: self.query_result_cache_disk_mb_size,
             management_mode: self.management_mode,
             jwt_key_file: self.jwt_key_file,
             async_insert_max_data_size: self.async_insert_max_data_size,
             async_insert_busy_timeout: self.async_insert_busy_timeout,
             async_insert_stale_timeout: self.async_insert_stale_timeout,
+            background_tasks_enabled: self.background_tasks_enabled,
+            background_tasks_interval_secs: self.background_tasks_interval_secs,
+            background_tasks_max_concurrency: self.background_tasks_max_concurrency,
+            background_compact_segment_threshold: self.background_compact_segment_threshold,
+            background_compact_small_block_ratio: self.background_compact_small_block_ratio,
+            background_recluster_depth_threshold: self.background_recluster_depth_threshold,
         })
     }
 }
@@ -745,11 +796,21 @@ impl From<InnerQueryConfig> for QueryConfig {
             table_memory_cache_mb_size: inner.table_memory_cache_mb_size,
             table_disk_cache_root: inner.table_disk_cache_root,
             table_disk_cache_mb_size: inner.table_disk_cache_mb_size,
+            spill_root: inner.spill_root,
+            query_result_cache_memory_mb_size: inner.query_result_cache_memory_mb_size,
+            query_result_cache_disk_root: inner.query_result_cache_disk_root,
+            query_result_cache_disk_mb_size: inner.query_result_cache_disk_mb_size,
             management_mode: inner.management_mode,
             jwt_key_file: inner.jwt_key_file,
             async_insert_max_data_size: inner.async_insert_max_data_size,
             async_insert_busy_timeout: inner.async_insert_busy_timeout,
             async_insert_stale_timeout: inner.async_insert_stale_timeout,
+            background_tasks_enabled: inner.background_tasks_enabled,
+            background_tasks_interval_secs: inner.background_tasks_interval_secs,
+            background_tasks_max_concurrency: inner.background_tasks_max_concurrency,
+            background_compact_segment_threshold: inner.background_compact_segment_threshold,
+            background_compact_small_block_ratio: inner.background_compact_small_block_ratio,
+            background_recluster_depth_threshold: inner.background_recluster_depth_threshold,
         }
     }
 }
@@ -240,8 +240,8 @@ impl TryFrom<DataBlock> for Chunk<ArrayRef> {
             .columns()
             .iter()
             .zip(v.schema.fields().iter())
-            .map(|(c, f)| c.as_arrow_array(f.data_type().clone()))
-            .collect::<Vec<_>>();
+            .map(|(c, f)| c.try_as_arrow_array(f.data_type().clone()))
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Chunk::try_new(arrays)?)
     }
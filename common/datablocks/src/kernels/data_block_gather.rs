@@ -54,8 +54,8 @@ impl DataBlock {
     ) -> Result<ColumnRef> {
         let arrays = columns
             .iter()
-            .map(|c| c.as_arrow_array(c.data_type()))
-            .collect::<Vec<ArrayRef>>();
+            .map(|c| c.try_as_arrow_array(c.data_type()))
+            .collect::<Result<Vec<ArrayRef>>>()?;
 
         let arrays: Vec<&dyn Array> = arrays.iter().map(|array| array.as_ref()).collect();
         // use_validity set to false, it will be checked inside `make_growable`
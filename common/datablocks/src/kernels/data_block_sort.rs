@@ -46,7 +46,7 @@ impl DataBlock {
             .iter()
             .map(|f| {
                 let c = block.try_column_by_name(&f.column_name)?;
-                Ok(c.as_arrow_array(c.data_type()))
+                c.try_as_arrow_array(c.data_type())
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -87,10 +87,10 @@ impl DataBlock {
             .iter()
             .map(|f| {
                 let left = lhs.try_column_by_name(&f.column_name)?.clone();
-                let left = left.as_arrow_array(left.data_type());
+                let left = left.try_as_arrow_array(left.data_type())?;
 
                 let right = rhs.try_column_by_name(&f.column_name)?.clone();
-                let right = right.as_arrow_array(right.data_type());
+                let right = right.try_as_arrow_array(right.data_type())?;
 
                 Ok(vec![left, right])
             })
@@ -191,7 +191,9 @@ fn compare_array(left: &dyn Array, right: &dyn Array) -> ArrowResult<DynComparat
     }))
 }
 
-fn build_compare(left: &dyn Array, right: &dyn Array) -> ArrowResult<DynComparator> {
+/// Build a comparator of the rows of two arrays of the same type, which supports
+/// all the types that can be sorted.
+pub fn build_compare(left: &dyn Array, right: &dyn Array) -> ArrowResult<DynComparator> {
     match left.data_type() {
         ArrowType::LargeList(_) => compare_array(left, right),
         ArrowType::Extension(name, _, _) => {
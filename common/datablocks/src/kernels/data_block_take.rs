@@ -69,8 +69,8 @@ impl DataBlock {
     ) -> Result<ColumnRef> {
         let arrays: Vec<ArrayRef> = columns
             .iter()
-            .map(|c| c.as_arrow_array(c.data_type()))
-            .collect();
+            .map(|c| c.try_as_arrow_array(c.data_type()))
+            .collect::<Result<_>>()?;
         let arrays: Vec<&dyn Array> = arrays.iter().map(|c| c.as_ref()).collect();
         let taked = Self::take_arrays_by_slices_limit(&arrays, slices, limit);
 
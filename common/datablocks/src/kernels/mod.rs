@@ -23,4 +23,5 @@ mod data_block_sort;
 mod data_block_take;
 
 pub use data_block_group_by_hash::*;
+pub use data_block_sort::build_compare;
 pub use data_block_sort::SortColumnDescription;
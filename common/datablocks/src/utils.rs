@@ -21,6 +21,8 @@ use common_arrow::parquet::compression::CompressionOptions;
 use common_arrow::parquet::encoding::Encoding;
 use common_arrow::parquet::metadata::ThriftFileMetaData;
 use common_arrow::parquet::write::Version;
+use common_arrow::to_parquet_arrow_schema;
+use common_arrow::to_parquet_chunk;
 use common_arrow::write_parquet_file;
 use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
@@ -34,7 +36,9 @@ pub fn serialize_data_blocks_with_compression(
     buf: &mut Vec<u8>,
     compression: CompressionOptions,
 ) -> Result<(u64, ThriftFileMetaData)> {
-    let arrow_schema = schema.as_ref().to_arrow();
+    // Decimals are written as fixed size binaries, see `to_parquet_arrow_schema`
+    let table_schema = schema.as_ref().to_arrow();
+    let arrow_schema = to_parquet_arrow_schema(&table_schema);
 
     let row_group_write_options = WriteOptions {
         write_statistics: false,
@@ -43,7 +47,10 @@ pub fn serialize_data_blocks_with_compression(
     };
     let batches = blocks
         .into_iter()
-        .map(Chunk::try_from)
+        .map(|block| {
+            let chunk = Chunk::try_from(block)?;
+            Ok(to_parquet_chunk(chunk, &table_schema)?)
+        })
         .collect::<Result<Vec<_>>>()?;
 
     let encoding_map = |data_type: &ArrowDataType| match data_type {
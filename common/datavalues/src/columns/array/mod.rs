@@ -19,6 +19,8 @@ use common_arrow::arrow::buffer::Buffer;
 use common_arrow::arrow::datatypes::DataType as ArrowType;
 use common_arrow::arrow::types::Index;
 use common_arrow::ArrayRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
 use common_io::prelude::BinaryWrite;
 
 use crate::prelude::*;
@@ -61,7 +63,37 @@ impl ArrayColumn {
         }
     }
 
+    /// The map array is converted to an array column of (key, value) structs.
+    pub fn from_map_array(array: &MapArray) -> Self {
+        let data_type = from_arrow_type(array.data_type());
+        let map_type: MapType = data_type.clone().try_into().unwrap();
+
+        let entries = array
+            .field()
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .unwrap();
+        let keys = entries.values()[0].clone().into_column();
+        let values = if map_type.value_type().is_nullable() {
+            entries.values()[1].clone().into_nullable_column()
+        } else {
+            entries.values()[1].clone().into_column()
+        };
+        let values = StructColumn::from_data(vec![keys, values], map_type.entries_type());
+
+        let offsets: Vec<i64> = array.offsets().iter().map(|v| *v as i64).collect();
+        Self {
+            data_type,
+            offsets: offsets.into(),
+            values: Arc::new(values),
+        }
+    }
+
     pub fn from_arrow_array(array: &dyn Array) -> Self {
+        if let ArrowType::Map(_, _) = array.data_type() {
+            return Self::from_map_array(array.as_any().downcast_ref::<MapArray>().unwrap());
+        }
+
         Self::new(
             array
                 .as_any()
@@ -109,6 +141,13 @@ impl Column for ArrayColumn {
     }
 
     fn column_meta(&self) -> ColumnMeta {
+        if let DataTypeImpl::Map(data_type) = &self.data_type {
+            return ColumnMeta::Map {
+                key_type: data_type.key_type().clone(),
+                value_type: data_type.value_type().clone(),
+            };
+        }
+
         let data_type: ArrayType = self.data_type.clone().try_into().unwrap();
         ColumnMeta::Array {
             inner_type: data_type.inner_type().clone(),
@@ -124,18 +163,47 @@ impl Column for ArrayColumn {
     }
 
     fn as_arrow_array(&self, data_type: DataTypeImpl) -> ArrayRef {
+        self.try_as_arrow_array(data_type).unwrap()
+    }
+
+    fn try_as_arrow_array(&self, data_type: DataTypeImpl) -> Result<ArrayRef> {
         let arrow_type = data_type.arrow_type();
-        if let ArrowType::LargeList(ref f) = arrow_type {
-            let inner_f = from_arrow_field(f.as_ref());
-            let array = self.values.as_arrow_array(inner_f);
-            Box::new(LargeListArray::from_data(
-                arrow_type,
-                self.offsets.clone(),
-                array,
-                None,
-            ))
-        } else {
-            unreachable!()
+        match arrow_type {
+            ArrowType::LargeList(ref f) => {
+                let inner_f = from_arrow_field(f.as_ref());
+                let array = self.values.try_as_arrow_array(inner_f)?;
+                Ok(Box::new(LargeListArray::from_data(
+                    arrow_type,
+                    self.offsets.clone(),
+                    array,
+                    None,
+                )))
+            }
+            ArrowType::Map(_, _) => {
+                let map_type: MapType = data_type.try_into().unwrap();
+                let array = self.values.try_as_arrow_array(map_type.entries_type())?;
+                // the arrow map array only has 32-bit offsets
+                let offsets = self
+                    .offsets
+                    .iter()
+                    .map(|v| {
+                        i32::try_from(*v).map_err(|_| {
+                            ErrorCode::Overflow(format!(
+                                "Offset {} of the map column exceeds the maximum {} of arrow map array",
+                                v,
+                                i32::MAX
+                            ))
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(Box::new(MapArray::from_data(
+                    arrow_type,
+                    offsets.into(),
+                    array,
+                    None,
+                )))
+            }
+            _ => unreachable!(),
         }
     }
 
@@ -80,6 +80,12 @@ pub trait Column: Send + Sync {
     fn memory_size(&self) -> usize;
     fn arc(&self) -> ColumnRef;
     fn as_arrow_array(&self, logical_type: DataTypeImpl) -> ArrayRef;
+
+    /// Converts the column into the arrow array, returns an error if the column can't be
+    /// represented by the arrow type of `logical_type`.
+    fn try_as_arrow_array(&self, logical_type: DataTypeImpl) -> Result<ArrayRef> {
+        Ok(self.as_arrow_array(logical_type))
+    }
     fn slice(&self, offset: usize, length: usize) -> ColumnRef;
 
     fn filter(&self, filter: &BooleanColumn) -> ColumnRef;
@@ -197,9 +203,10 @@ where A: AsRef<dyn Array>
             Int64 | Interval | Timestamp => Arc::new(Int64Column::from_arrow_array(self.as_ref())),
             Float32 => Arc::new(Float32Column::from_arrow_array(self.as_ref())),
             Float64 => Arc::new(Float64Column::from_arrow_array(self.as_ref())),
-            Array => Arc::new(ArrayColumn::from_arrow_array(self.as_ref())),
+            Decimal => Arc::new(PrimitiveColumn::<i128>::from_arrow_array(self.as_ref())),
+            Array | Map => Arc::new(ArrayColumn::from_arrow_array(self.as_ref())),
             Struct => Arc::new(StructColumn::from_arrow_array(self.as_ref())),
-            String => Arc::new(StringColumn::from_arrow_array(self.as_ref())),
+            String | Decimal256 => Arc::new(StringColumn::from_arrow_array(self.as_ref())),
             Variant => Arc::new(VariantColumn::from_arrow_array(self.as_ref())),
             VariantArray => Arc::new(VariantColumn::from_arrow_array(self.as_ref())),
             VariantObject => Arc::new(VariantColumn::from_arrow_array(self.as_ref())),
@@ -264,7 +271,7 @@ impl std::fmt::Debug for dyn Column + '_ {
                 String => {
                     fmt_dyn!(col, StringColumn, f)
                 },
-                Array => {
+                Array | Map => {
                     fmt_dyn!(col, ArrayColumn, f)
                 },
                 Struct => {
@@ -273,6 +280,9 @@ impl std::fmt::Debug for dyn Column + '_ {
                 Variant | VariantArray | VariantObject => {
                     fmt_dyn!(col, VariantColumn, f)
                 }
+                Decimal => {
+                    fmt_dyn!(col, PrimitiveColumn<i128>, f)
+                }
                 _ => {
                     unimplemented!()
                 }
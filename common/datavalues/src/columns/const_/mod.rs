@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::ArrayRef;
+use common_exception::Result;
 
 use crate::prelude::*;
 
@@ -91,6 +92,11 @@ impl Column for ConstColumn {
         column.as_arrow_array(logical_type)
     }
 
+    fn try_as_arrow_array(&self, logical_type: DataTypeImpl) -> Result<ArrayRef> {
+        let column = self.column.replicate(&[self.length]);
+        column.try_as_arrow_array(logical_type)
+    }
+
     fn arc(&self) -> ColumnRef {
         Arc::new(self.clone())
     }
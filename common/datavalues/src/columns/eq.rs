@@ -102,6 +102,12 @@ pub fn equal(lhs: &dyn Column, rhs: &dyn Column) -> bool {
 
             lhs.values() == rhs.values()
         }
+        Int128 => {
+            let lhs: &PrimitiveColumn<i128> = lhs.as_any().downcast_ref().unwrap();
+            let rhs: &PrimitiveColumn<i128> = rhs.as_any().downcast_ref().unwrap();
+
+            lhs.values() == rhs.values()
+        }
         other => with_match_physical_primitive_type_error!(other, |$T| {
             let lhs: &PrimitiveColumn<$T> = lhs.as_any().downcast_ref().unwrap();
             let rhs: &PrimitiveColumn<$T> = rhs.as_any().downcast_ref().unwrap();
@@ -19,6 +19,7 @@ use std::sync::Arc;
 use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::arrow::bitmap::MutableBitmap;
 use common_arrow::ArrayRef;
+use common_exception::Result;
 pub use mutable::*;
 
 use crate::prelude::*;
@@ -132,6 +133,11 @@ impl Column for NullableColumn {
         result.with_validity(Some(self.validity.clone()))
     }
 
+    fn try_as_arrow_array(&self, logical_type: DataTypeImpl) -> Result<ArrayRef> {
+        let result = self.column.try_as_arrow_array(logical_type)?;
+        Ok(result.with_validity(Some(self.validity.clone())))
+    }
+
     fn arc(&self) -> ColumnRef {
         Arc::new(self.clone())
     }
@@ -122,6 +122,14 @@ impl<T: PrimitiveType> PrimitiveColumn<T> {
                     let array = unary(array, |x| x as i64 * p.0 / p.1, expected_arrow);
                     Self::from_arrow_array(&array)
                 }
+                // decimals of all precisions and scales share the same physical type
+                ArrowDataType::Decimal(_, _) => {
+                    let array = array
+                        .as_any()
+                        .downcast_ref::<PrimitiveArray<T>>()
+                        .expect("primitive cast should be ok");
+                    Self::new(array.clone())
+                }
                 _ => unreachable!(),
             }
         } else {
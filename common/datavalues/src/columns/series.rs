@@ -118,8 +118,8 @@ impl Series {
         let is_nullable = columns[0].is_nullable();
         let arrays = columns
             .iter()
-            .map(|c| c.as_arrow_array(c.data_type()))
-            .collect::<Vec<_>>();
+            .map(|c| c.try_as_arrow_array(c.data_type()))
+            .collect::<Result<Vec<_>>>()?;
 
         let arrays = arrays.iter().map(|a| a.as_ref()).collect::<Vec<_>>();
         let array: ArrayRef = concatenate::concatenate(&arrays)?;
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_arrow::arrow::array::*;
 use common_arrow::arrow::datatypes::DataType as ArrowType;
 use common_arrow::ArrayRef;
+use common_exception::Result;
 
 use crate::prelude::*;
 
@@ -100,6 +101,10 @@ impl Column for StructColumn {
     }
 
     fn as_arrow_array(&self, logical_type: DataTypeImpl) -> ArrayRef {
+        self.try_as_arrow_array(logical_type).unwrap()
+    }
+
+    fn try_as_arrow_array(&self, logical_type: DataTypeImpl) -> Result<ArrayRef> {
         let arrow_type = logical_type.arrow_type();
 
         if let ArrowType::Struct(f) = &arrow_type {
@@ -109,10 +114,10 @@ impl Column for StructColumn {
                 .zip(f.iter())
                 .map(|(v, f)| {
                     let f = from_arrow_field(f);
-                    v.as_arrow_array(f)
+                    v.try_as_arrow_array(f)
                 })
-                .collect();
-            Box::new(StructArray::from_data(arrow_type, arrays, None))
+                .collect::<Result<_>>()?;
+            Ok(Box::new(StructArray::from_data(arrow_type, arrays, None)))
         } else {
             unreachable!()
         }
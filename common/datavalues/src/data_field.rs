@@ -26,6 +26,10 @@ pub struct DataField {
     name: String,
     /// default_expr is serialized representation from PlanExpression
     default_expr: Option<String>,
+    /// generated_expr is the expression computing the value of a stored generated column
+    /// from the other columns, in the same representation as default_expr
+    #[serde(default)]
+    generated_expr: Option<String>,
     data_type: DataTypeImpl,
 }
 
@@ -34,6 +38,7 @@ impl DataField {
         DataField {
             name: name.to_string(),
             default_expr: None,
+            generated_expr: None,
             data_type,
         }
     }
@@ -43,6 +48,7 @@ impl DataField {
         DataField {
             name: name.to_string(),
             default_expr: None,
+            generated_expr: None,
             data_type,
         }
     }
@@ -53,6 +59,12 @@ impl DataField {
         self
     }
 
+    #[must_use]
+    pub fn with_generated_expr(mut self, generated_expr: Option<String>) -> Self {
+        self.generated_expr = generated_expr;
+        self
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
@@ -65,6 +77,15 @@ impl DataField {
         self.default_expr.as_ref()
     }
 
+    pub fn generated_expr(&self) -> Option<&String> {
+        self.generated_expr.as_ref()
+    }
+
+    #[inline]
+    pub fn is_generated(&self) -> bool {
+        self.generated_expr.is_some()
+    }
+
     #[inline]
     pub fn is_nullable(&self) -> bool {
         self.data_type.is_nullable()
@@ -118,6 +139,9 @@ impl std::fmt::Debug for DataField {
         if let Some(ref default_expr) = self.default_expr {
             debug_struct.field("default_expr", default_expr);
         }
+        if let Some(ref generated_expr) = self.generated_expr {
+            debug_struct.field("generated_expr", generated_expr);
+        }
         debug_struct.finish()
     }
 }
@@ -36,6 +36,8 @@ pub enum DataValue {
     Int64(i64),
     UInt64(u64),
     Float64(f64),
+    /// The unscaled value of a decimal.
+    Int128(i128),
     String(Vec<u8>),
 
     // Container struct.
@@ -55,6 +57,7 @@ pub enum ValueType {
     UInt64,
     Int64,
     Float64,
+    Int128,
     String,
     Array,
     Struct,
@@ -85,6 +88,7 @@ impl DataValue {
             DataValue::Int64(_) => ValueType::Int64,
             DataValue::UInt64(_) => ValueType::UInt64,
             DataValue::Float64(_) => ValueType::Float64,
+            DataValue::Int128(_) => ValueType::Int128,
             DataValue::String(_) => ValueType::String,
             DataValue::Array(_) => ValueType::Array,
             DataValue::Struct(_) => ValueType::Struct,
@@ -122,6 +126,7 @@ impl DataValue {
                 UInt64Type::new_impl()
             }
             DataValue::Float64(_) => Float64Type::new_impl(),
+            DataValue::Int128(_) => DecimalType::new_impl(MAX_DECIMAL_PRECISION, 0),
             DataValue::String(_) => StringType::new_impl(),
             DataValue::Array(vals) => {
                 let inner_type = if vals.is_empty() {
@@ -151,6 +156,7 @@ impl DataValue {
             DataValue::Int64(_) => Int64Type::new_impl(),
             DataValue::UInt64(_) => UInt64Type::new_impl(),
             DataValue::Float64(_) => Float64Type::new_impl(),
+            DataValue::Int128(_) => DecimalType::new_impl(MAX_DECIMAL_PRECISION, 0),
             DataValue::String(_) => StringType::new_impl(),
             DataValue::Array(vals) => {
                 let inner_type = if vals.is_empty() {
@@ -191,7 +197,10 @@ impl DataValue {
     pub fn is_numeric(&self) -> bool {
         matches!(
             self,
-            DataValue::Int64(_) | DataValue::UInt64(_) | DataValue::Float64(_)
+            DataValue::Int64(_)
+                | DataValue::UInt64(_)
+                | DataValue::Float64(_)
+                | DataValue::Int128(_)
         )
     }
 
@@ -222,6 +231,18 @@ impl DataValue {
         }
     }
 
+    pub fn as_i128(&self) -> Result<i128> {
+        match self {
+            DataValue::Int64(v) => Ok(*v as i128),
+            DataValue::UInt64(v) => Ok(*v as i128),
+            DataValue::Int128(v) => Ok(*v),
+            other => Result::Err(ErrorCode::BadDataValueType(format!(
+                "Unexpected type:{:?} to get i128 number",
+                other.value_type()
+            ))),
+        }
+    }
+
     pub fn as_bool(&self) -> Result<bool> {
         match self {
             DataValue::Boolean(v) => Ok(*v),
@@ -237,6 +258,7 @@ impl DataValue {
             DataValue::Int64(v) => Ok(*v as f64),
             DataValue::UInt64(v) => Ok(*v as f64),
             DataValue::Float64(v) => Ok(*v),
+            DataValue::Int128(v) => Ok(*v as f64),
             other => Result::Err(ErrorCode::BadDataValueType(format!(
                 "Unexpected type:{:?} to get f64 number",
                 other.value_type()
@@ -249,6 +271,7 @@ impl DataValue {
             DataValue::Int64(v) => Ok(Vec::<u8>::from((*v).to_string())),
             DataValue::UInt64(v) => Ok(Vec::<u8>::from((*v).to_string())),
             DataValue::Float64(v) => Ok(Vec::<u8>::from((*v).to_string())),
+            DataValue::Int128(v) => Ok(Vec::<u8>::from((*v).to_string())),
             DataValue::String(v) => Ok(v.to_owned()),
             DataValue::Variant(v) => Ok(v.to_string().into_bytes()),
             other => Result::Err(ErrorCode::BadDataValueType(format!(
@@ -315,6 +338,7 @@ impl Ord for DataValue {
                 (DataValue::Boolean(v1), DataValue::Boolean(v2)) => v1.cmp(v2),
                 (DataValue::UInt64(v1), DataValue::UInt64(v2)) => v1.cmp(v2),
                 (DataValue::Int64(v1), DataValue::Int64(v2)) => v1.cmp(v2),
+                (DataValue::Int128(v1), DataValue::Int128(v2)) => v1.cmp(v2),
                 (DataValue::Float64(v1), DataValue::Float64(v2)) => {
                     OrderedFloat::from(*v1).cmp(&OrderedFloat::from(*v2))
                 }
@@ -363,7 +387,7 @@ impl Ord for DataValue {
                 .cmp(&OrderedFloat::from(other.as_f64().unwrap()));
         }
 
-        self.as_i64().unwrap().cmp(&other.as_i64().unwrap())
+        self.as_i128().unwrap().cmp(&other.as_i128().unwrap())
     }
 }
 
@@ -394,6 +418,7 @@ impl DFTryFrom<DataValue> for VariantValue {
             DataValue::Int64(v) => Ok(VariantValue::from(json!(v))),
             DataValue::UInt64(v) => Ok(VariantValue::from(json!(v))),
             DataValue::Float64(v) => Ok(VariantValue::from(json!(v))),
+            DataValue::Int128(v) => Ok(VariantValue::from(json!(v as f64))),
             DataValue::String(v) => Ok(VariantValue::from(json!(v))),
             DataValue::Array(v) => Ok(VariantValue::from(json!(v))),
             DataValue::Struct(v) => Ok(VariantValue::from(json!(v))),
@@ -410,6 +435,7 @@ impl DFTryFrom<&DataValue> for VariantValue {
             DataValue::Int64(v) => Ok(VariantValue::from(json!(*v as i64))),
             DataValue::UInt64(v) => Ok(VariantValue::from(json!(*v as u64))),
             DataValue::Float64(v) => Ok(VariantValue::from(json!(*v as f64))),
+            DataValue::Int128(v) => Ok(VariantValue::from(json!(*v as f64))),
             DataValue::String(v) => Ok(VariantValue::from(json!(
                 String::from_utf8(v.to_vec()).unwrap()
             ))),
@@ -429,6 +455,7 @@ try_cast_data_value_to_std!(i8, as_i64);
 try_cast_data_value_to_std!(i16, as_i64);
 try_cast_data_value_to_std!(i32, as_i64);
 try_cast_data_value_to_std!(i64, as_i64);
+try_cast_data_value_to_std!(i128, as_i128);
 
 try_cast_data_value_to_std!(f32, as_f64);
 try_cast_data_value_to_std!(f64, as_f64);
@@ -438,6 +465,7 @@ std_to_data_value!(Int64, i8, i64);
 std_to_data_value!(Int64, i16, i64);
 std_to_data_value!(Int64, i32, i64);
 std_to_data_value!(Int64, i64, i64);
+std_to_data_value!(Int128, i128, i128);
 std_to_data_value!(UInt64, u8, u64);
 std_to_data_value!(UInt64, u16, u64);
 std_to_data_value!(UInt64, u32, u64);
@@ -488,6 +516,7 @@ impl fmt::Display for DataValue {
             DataValue::Float64(v) => write!(f, "{}", v),
             DataValue::Int64(v) => write!(f, "{}", v),
             DataValue::UInt64(v) => write!(f, "{}", v),
+            DataValue::Int128(v) => write!(f, "{}", v),
             DataValue::String(v) => match std::str::from_utf8(v) {
                 Ok(v) => write!(f, "{}", v),
                 Err(_e) => {
@@ -530,6 +559,7 @@ impl fmt::Debug for DataValue {
             DataValue::Int64(v) => write!(f, "{}", v),
             DataValue::UInt64(v) => write!(f, "{}", v),
             DataValue::Float64(v) => write!(f, "{}", v),
+            DataValue::Int128(v) => write!(f, "{}", v),
             DataValue::String(_) => write!(f, "{}", self),
             DataValue::Array(_) => write!(f, "{}", self),
             DataValue::Struct(_) => write!(f, "{}", self),
@@ -27,6 +27,7 @@ macro_rules! for_all_scalar_types {
             { u64 },
             { f32 },
             { f64 },
+            { i128 },
             { bool },
             { Vu8 },
             { ArrayValue },
@@ -192,6 +193,7 @@ macro_rules! with_match_scalar_type {
             PhysicalTypeID::UInt64 => __with_ty__! { u64 },
             PhysicalTypeID::Float32 => __with_ty__! { f32 },
             PhysicalTypeID::Float64 => __with_ty__! { f64 },
+            PhysicalTypeID::Int128 => __with_ty__! { i128 },
 
             _ => $nbody,
         }
@@ -222,6 +224,7 @@ macro_rules! with_match_scalar_types_error {(
         PhysicalTypeID::UInt64 => __with_ty__! { u64 },
         PhysicalTypeID::Float32 => __with_ty__! { f32 },
         PhysicalTypeID::Float64 => __with_ty__! { f64 },
+        PhysicalTypeID::Int128 => __with_ty__! { i128 },
         v => return Err(ErrorCode::BadDataValueType(
             format!("Ops is not support on datatype: {:?}",v)
         ))
@@ -98,6 +98,7 @@ impl_primitive_scalar_type!(i8);
 impl_primitive_scalar_type!(i16);
 impl_primitive_scalar_type!(i32);
 impl_primitive_scalar_type!(i64);
+impl_primitive_scalar_type!(i128);
 impl_primitive_scalar_type!(f32);
 impl_primitive_scalar_type!(f64);
 
@@ -63,11 +63,14 @@ pub enum DataTypeImpl {
     UInt64(UInt64Type),
     Float32(Float32Type),
     Float64(Float64Type),
+    Decimal(DecimalType),
+    Decimal256(Decimal256Type),
     Date(DateType),
     Timestamp(TimestampType),
     String(StringType),
     Struct(StructType),
     Array(ArrayType),
+    Map(MapType),
     Variant(VariantType),
     VariantArray(VariantArrayType),
     VariantObject(VariantObjectType),
@@ -175,6 +178,9 @@ pub fn from_arrow_type(dt: &ArrowType) -> DataTypeImpl {
         ArrowType::Boolean => DataTypeImpl::Boolean(BooleanType::default()),
         ArrowType::Float32 => DataTypeImpl::Float32(Float32Type::default()),
         ArrowType::Float64 => DataTypeImpl::Float64(Float64Type::default()),
+        ArrowType::Decimal(precision, scale) => {
+            DataTypeImpl::Decimal(DecimalType::create(*precision, *scale))
+        }
 
         // TODO support other list
         ArrowType::List(f) | ArrowType::LargeList(f) | ArrowType::FixedSizeList(f, _) => {
@@ -182,6 +188,15 @@ pub fn from_arrow_type(dt: &ArrowType) -> DataTypeImpl {
             DataTypeImpl::Array(ArrayType::create(inner))
         }
 
+        ArrowType::Map(f, _) => match f.data_type() {
+            ArrowType::Struct(fields) if fields.len() == 2 => {
+                let key = from_arrow_field(&fields[0]);
+                let value = from_arrow_field(&fields[1]);
+                DataTypeImpl::Map(MapType::create(key, value))
+            }
+            _ => unimplemented!("data_type: {:?}", dt),
+        },
+
         ArrowType::Binary | ArrowType::LargeBinary | ArrowType::Utf8 | ArrowType::LargeUtf8 => {
             DataTypeImpl::String(StringType::default())
         }
@@ -238,6 +253,16 @@ pub fn from_arrow_field(f: &ArrowField) -> DataTypeImpl {
                 None => return TimestampType::new_impl(0),
             },
             "Interval" => return IntervalType::new_impl(metadata.unwrap().into()),
+            "Decimal256" => {
+                let meta = metadata.unwrap();
+                let (precision, scale) = meta.split_once(',').unwrap();
+                let ty =
+                    Decimal256Type::new_impl(precision.parse().unwrap(), scale.parse().unwrap());
+                return match f.is_nullable {
+                    true => NullableType::new_impl(ty),
+                    false => ty,
+                };
+            }
             "Variant" => return VariantType::new_impl(),
             "VariantArray" => return VariantArrayType::new_impl(),
             "VariantObject" => return VariantObjectType::new_impl(),
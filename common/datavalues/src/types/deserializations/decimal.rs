@@ -0,0 +1,269 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use crate::prelude::*;
+
+pub struct DecimalDeserializer {
+    pub builder: MutablePrimitiveColumn<i128>,
+    pub data_type: DecimalType,
+}
+
+impl DecimalDeserializer {
+    fn read_decimal_text<R: BufferRead>(&self, reader: &mut R) -> Result<i128> {
+        let mut buf = Vec::with_capacity(40);
+        reader.keep_read(&mut buf, |c| {
+            c.is_ascii_digit() || matches!(c, b'.' | b'-' | b'+' | b'e' | b'E')
+        })?;
+        self.data_type.parse_bytes(&buf)
+    }
+}
+
+impl TypeDeserializer for DecimalDeserializer {
+    fn de_binary(&mut self, reader: &mut &[u8], _format: &FormatSettings) -> Result<()> {
+        let mut bytes = [0u8; 16];
+        reader.read_exact(&mut bytes)?;
+        let value = self.data_type.check_value(i128::from_le_bytes(bytes))?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_default(&mut self, _format: &FormatSettings) {
+        self.builder.append_value(i128::default());
+    }
+
+    fn de_fixed_binary_batch(
+        &mut self,
+        reader: &[u8],
+        step: usize,
+        rows: usize,
+        format: &FormatSettings,
+    ) -> Result<()> {
+        for row in 0..rows {
+            let mut reader = &reader[step * row..];
+            self.de_binary(&mut reader, format)?;
+        }
+        Ok(())
+    }
+
+    fn de_json(&mut self, value: &serde_json::Value, _format: &FormatSettings) -> Result<()> {
+        let value = match value {
+            serde_json::Value::Number(v) => self.data_type.parse_bytes(v.to_string().as_bytes()),
+            serde_json::Value::String(v) => self.data_type.parse_bytes(v.as_bytes()),
+            _ => Err(ErrorCode::BadBytes(
+                "Incorrect json value, must be number or string",
+            )),
+        }?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_whole_text(&mut self, reader: &[u8], _format: &FormatSettings) -> Result<()> {
+        let value = self.data_type.parse_bytes(reader)?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_text<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let value = self.read_decimal_text(reader)?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_text_csv<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let maybe_quote = reader.ignore(|f| f == b'\'' || f == b'"')?;
+        let value = self.read_decimal_text(reader)?;
+        if maybe_quote {
+            reader.must_ignore(|f| f == b'\'' || f == b'"')?;
+        }
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn de_text_quoted<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let maybe_quote = reader.ignore_byte(b'\'')?;
+        let value = self.read_decimal_text(reader)?;
+        if maybe_quote {
+            reader.must_ignore_byte(b'\'')?;
+        }
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn append_data_value(&mut self, value: DataValue, _format: &FormatSettings) -> Result<()> {
+        let value = self.data_type.check_value(value.as_i128()?)?;
+        self.builder.append_value(value);
+        Ok(())
+    }
+
+    fn pop_data_value(&mut self) -> Result<DataValue> {
+        self.builder.pop_data_value()
+    }
+
+    fn finish_to_column(&mut self) -> ColumnRef {
+        self.builder.to_column()
+    }
+}
+
+pub struct Decimal256Deserializer {
+    pub builder: MutableStringColumn,
+    pub data_type: Decimal256Type,
+}
+
+impl Decimal256Deserializer {
+    fn read_decimal_text<R: BufferRead>(&self, reader: &mut R) -> Result<I256> {
+        let mut buf = Vec::with_capacity(80);
+        reader.keep_read(&mut buf, |c| {
+            c.is_ascii_digit() || matches!(c, b'.' | b'-' | b'+' | b'e' | b'E')
+        })?;
+        self.data_type.parse_bytes(&buf)
+    }
+
+    fn append_value(&mut self, value: I256) {
+        self.builder.append_value(value.to_bytes());
+    }
+}
+
+impl TypeDeserializer for Decimal256Deserializer {
+    // Same as the serialized value of the string column
+    fn de_binary(&mut self, reader: &mut &[u8], _format: &FormatSettings) -> Result<()> {
+        let len: u64 = reader.read_uvarint()?;
+        if len as usize != I256::BYTE_SIZE {
+            return Err(ErrorCode::BadBytes(format!(
+                "Incorrect binary value of {}, expect {} bytes but got {}",
+                self.data_type.name(),
+                I256::BYTE_SIZE,
+                len
+            )));
+        }
+        let mut bytes = [0u8; I256::BYTE_SIZE];
+        reader.read_exact(&mut bytes)?;
+        let value = self.data_type.check_value(I256::from_bytes(&bytes))?;
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn de_default(&mut self, _format: &FormatSettings) {
+        self.append_value(I256::zero());
+    }
+
+    fn de_fixed_binary_batch(
+        &mut self,
+        reader: &[u8],
+        step: usize,
+        rows: usize,
+        format: &FormatSettings,
+    ) -> Result<()> {
+        for row in 0..rows {
+            let mut reader = &reader[step * row..];
+            self.de_binary(&mut reader, format)?;
+        }
+        Ok(())
+    }
+
+    fn de_json(&mut self, value: &serde_json::Value, _format: &FormatSettings) -> Result<()> {
+        let value = match value {
+            serde_json::Value::Number(v) => self.data_type.parse_bytes(v.to_string().as_bytes()),
+            serde_json::Value::String(v) => self.data_type.parse_bytes(v.as_bytes()),
+            _ => Err(ErrorCode::BadBytes(
+                "Incorrect json value, must be number or string",
+            )),
+        }?;
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn de_whole_text(&mut self, reader: &[u8], _format: &FormatSettings) -> Result<()> {
+        let value = self.data_type.parse_bytes(reader)?;
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn de_text<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let value = self.read_decimal_text(reader)?;
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn de_text_csv<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let maybe_quote = reader.ignore(|f| f == b'\'' || f == b'"')?;
+        let value = self.read_decimal_text(reader)?;
+        if maybe_quote {
+            reader.must_ignore(|f| f == b'\'' || f == b'"')?;
+        }
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn de_text_quoted<R: BufferRead>(
+        &mut self,
+        reader: &mut NestedCheckpointReader<R>,
+        _format: &FormatSettings,
+    ) -> Result<()> {
+        let maybe_quote = reader.ignore_byte(b'\'')?;
+        let value = self.read_decimal_text(reader)?;
+        if maybe_quote {
+            reader.must_ignore_byte(b'\'')?;
+        }
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn append_data_value(&mut self, value: DataValue, _format: &FormatSettings) -> Result<()> {
+        let bytes = value.as_string()?;
+        if bytes.len() != I256::BYTE_SIZE {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Unexpected value {:?} of type {}",
+                value,
+                self.data_type.name()
+            )));
+        }
+        let value = self.data_type.check_value(I256::from_bytes(&bytes))?;
+        self.append_value(value);
+        Ok(())
+    }
+
+    fn pop_data_value(&mut self) -> Result<DataValue> {
+        self.builder.pop_data_value()
+    }
+
+    fn finish_to_column(&mut self) -> ColumnRef {
+        self.builder.to_column()
+    }
+}
@@ -22,6 +22,8 @@ use crate::prelude::*;
 mod array;
 mod boolean;
 mod date;
+mod decimal;
+mod map;
 mod null;
 mod nullable;
 mod number;
@@ -33,6 +35,8 @@ mod variant;
 pub use array::*;
 pub use boolean::*;
 pub use date::*;
+pub use decimal::*;
+pub use map::*;
 pub use null::*;
 pub use nullable::*;
 pub use number::*;
@@ -117,11 +121,14 @@ pub enum TypeDeserializerImpl {
     UInt64(NumberDeserializer<u64>),
     Float32(NumberDeserializer<f32>),
     Float64(NumberDeserializer<f64>),
+    Decimal(DecimalDeserializer),
+    Decimal256(Decimal256Deserializer),
 
     Date(DateDeserializer<i32>),
     Interval(DateDeserializer<i64>),
     Timestamp(TimestampDeserializer),
     String(StringDeserializer),
     Struct(StructDeserializer),
+    Map(MapDeserializer),
     Variant(VariantDeserializer),
 }
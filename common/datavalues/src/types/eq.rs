@@ -15,6 +15,9 @@
 use std::sync::Arc;
 
 use super::type_array::ArrayType;
+use super::type_decimal::DecimalType;
+use super::type_decimal256::Decimal256Type;
+use super::type_map::MapType;
 use super::type_nullable::NullableType;
 use super::type_struct::StructType;
 use super::type_timestamp::TimestampType;
@@ -60,6 +63,20 @@ pub fn equal(lhs: &DataTypeImpl, rhs: &DataTypeImpl) -> bool {
             lhs.precision() == rhs.precision()
         }
 
+        Decimal => {
+            let lhs: DecimalType = lhs.to_owned().try_into().unwrap();
+            let rhs: DecimalType = rhs.to_owned().try_into().unwrap();
+
+            lhs.precision() == rhs.precision() && lhs.scale() == rhs.scale()
+        }
+
+        Decimal256 => {
+            let lhs: Decimal256Type = lhs.to_owned().try_into().unwrap();
+            let rhs: Decimal256Type = rhs.to_owned().try_into().unwrap();
+
+            lhs.precision() == rhs.precision() && lhs.scale() == rhs.scale()
+        }
+
         Nullable => {
             let lhs: NullableType = lhs.to_owned().try_into().unwrap();
             let rhs: NullableType = rhs.to_owned().try_into().unwrap();
@@ -74,6 +91,13 @@ pub fn equal(lhs: &DataTypeImpl, rhs: &DataTypeImpl) -> bool {
             lhs.inner_type() == rhs.inner_type()
         }
 
+        Map => {
+            let lhs: MapType = lhs.to_owned().try_into().unwrap();
+            let rhs: MapType = rhs.to_owned().try_into().unwrap();
+
+            lhs.key_type() == rhs.key_type() && lhs.value_type() == rhs.value_type()
+        }
+
         Struct => {
             let lhs: StructType = lhs.to_owned().try_into().unwrap();
             let rhs: StructType = rhs.to_owned().try_into().unwrap();
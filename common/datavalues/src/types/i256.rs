@@ -0,0 +1,238 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Neg;
+
+use primitive_types::U256;
+
+/// A signed 256-bit integer, which is the unscaled value of `Decimal256`.
+///
+/// The value is kept as sign and magnitude, the magnitude is always less than 2^255,
+/// so that it can be encoded into 32 bytes of two's complement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct I256 {
+    negative: bool,
+    magnitude: U256,
+}
+
+impl I256 {
+    /// The size of the encoded value
+    pub const BYTE_SIZE: usize = 32;
+
+    #[inline]
+    fn new(negative: bool, magnitude: U256) -> Option<Self> {
+        if magnitude.bit(255) {
+            return None;
+        }
+        Some(Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        })
+    }
+
+    #[inline]
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[inline]
+    pub fn abs(self) -> Self {
+        Self {
+            negative: false,
+            magnitude: self.magnitude,
+        }
+    }
+
+    /// Returns `10^exp`, or `None` if it overflows
+    #[inline]
+    pub fn checked_pow10(exp: usize) -> Option<Self> {
+        U256::from(10_u64)
+            .checked_pow(U256::from(exp))
+            .and_then(|magnitude| Self::new(false, magnitude))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        if self.negative == rhs.negative {
+            let magnitude = self.magnitude.checked_add(rhs.magnitude)?;
+            return Self::new(self.negative, magnitude);
+        }
+
+        match self.magnitude.cmp(&rhs.magnitude) {
+            Ordering::Less => Self::new(rhs.negative, rhs.magnitude - self.magnitude),
+            _ => Self::new(self.negative, self.magnitude - rhs.magnitude),
+        }
+    }
+
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.checked_add(-rhs)
+    }
+
+    #[inline]
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        let magnitude = self.magnitude.checked_mul(rhs.magnitude)?;
+        Self::new(self.negative != rhs.negative, magnitude)
+    }
+
+    /// The quotient is truncated toward zero, same as the division of primitive integers.
+    #[inline]
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        let magnitude = self.magnitude.checked_div(rhs.magnitude)?;
+        Self::new(self.negative != rhs.negative, magnitude)
+    }
+
+    /// The remainder has the sign of the dividend, same as the remainder of primitive integers.
+    #[inline]
+    pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+        let magnitude = self.magnitude.checked_rem(rhs.magnitude)?;
+        Self::new(self.negative, magnitude)
+    }
+
+    pub fn to_i128(self) -> Option<i128> {
+        if self.magnitude.bits() > 127 {
+            return None;
+        }
+        let value = self.magnitude.low_u128() as i128;
+        Some(if self.negative { -value } else { value })
+    }
+
+    pub fn to_f64(self) -> f64 {
+        let value = self
+            .magnitude
+            .0
+            .iter()
+            .rev()
+            .fold(0_f64, |acc, limb| acc * 2_f64.powi(64) + *limb as f64);
+        if self.negative { -value } else { value }
+    }
+
+    /// Converts the integral part of a float, returns `None` if it's not finite or overflows.
+    pub fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        let abs = value.abs().trunc();
+        if abs < 2_f64.powi(127) {
+            return Some(Self::from(value.trunc() as i128));
+        }
+        if abs >= 2_f64.powi(255) {
+            return None;
+        }
+
+        // abs = mantissa * 2^shift, and the mantissa has 53 significant bits
+        let shift = abs.log2().floor() as i32 - 52;
+        let mantissa = (abs / 2_f64.powi(shift)) as u64;
+        Self::new(value < 0.0, U256::from(mantissa) << (shift as usize))
+    }
+
+    /// Encodes the value into 32 bytes, which are the big-endian two's complement
+    /// with the sign bit flipped, so that the encoded values have the same order
+    /// as the numbers when compared as bytes.
+    pub fn to_bytes(&self) -> [u8; Self::BYTE_SIZE] {
+        let value = if self.negative {
+            U256::zero().overflowing_sub(self.magnitude).0
+        } else {
+            self.magnitude
+        };
+
+        let mut bytes = [0u8; Self::BYTE_SIZE];
+        value.to_big_endian(&mut bytes);
+        bytes[0] ^= 0x80;
+        bytes
+    }
+
+    /// Decodes the value encoded by `to_bytes`.
+    ///
+    /// The values under the nulls of a nullable column may be empty, they are decoded as zero.
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        if bytes.len() != Self::BYTE_SIZE {
+            return Self::zero();
+        }
+
+        let mut buf = [0u8; Self::BYTE_SIZE];
+        buf.copy_from_slice(bytes);
+        buf[0] ^= 0x80;
+        let value = U256::from_big_endian(&buf);
+        if value.bit(255) {
+            Self {
+                negative: true,
+                magnitude: U256::zero().overflowing_sub(value).0,
+            }
+        } else {
+            Self {
+                negative: false,
+                magnitude: value,
+            }
+        }
+    }
+}
+
+impl From<i128> for I256 {
+    fn from(value: i128) -> Self {
+        Self {
+            negative: value < 0,
+            magnitude: U256::from(value.unsigned_abs()),
+        }
+    }
+}
+
+impl Neg for I256 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            negative: !self.negative && !self.magnitude.is_zero(),
+            magnitude: self.magnitude,
+        }
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl fmt::Display for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.magnitude)
+    }
+}
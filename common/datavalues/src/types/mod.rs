@@ -18,7 +18,10 @@ pub mod type_array;
 pub mod type_boolean;
 pub mod type_coercion;
 pub mod type_date;
+pub mod type_decimal;
+pub mod type_decimal256;
 pub mod type_interval;
+pub mod type_map;
 pub mod type_null;
 pub mod type_nullable;
 pub mod type_primitive;
@@ -35,6 +38,7 @@ pub mod type_id;
 
 mod date_converter;
 pub mod deserializations;
+mod i256;
 pub mod serializations;
 mod type_factory;
 
@@ -43,14 +47,18 @@ pub use data_type::*;
 pub use date_converter::*;
 pub use deserializations::*;
 pub use eq::*;
+pub use i256::I256;
 pub use serializations::TypeSerializer;
 pub use serializations::TypeSerializerImpl;
 pub use type_array::*;
 pub use type_boolean::*;
 pub use type_date::*;
+pub use type_decimal::*;
+pub use type_decimal256::*;
 pub use type_factory::*;
 pub use type_id::*;
 pub use type_interval::*;
+pub use type_map::*;
 pub use type_null::*;
 pub use type_nullable::*;
 pub use type_primitive::*;
@@ -0,0 +1,162 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use opensrv_clickhouse::types::column::ArcColumnWrapper;
+use opensrv_clickhouse::types::column::ColumnFrom;
+use serde_json::Value;
+
+use crate::Column;
+use crate::ColumnRef;
+use crate::Decimal256Type;
+use crate::DecimalType;
+use crate::PrimitiveColumn;
+use crate::Series;
+use crate::StringColumn;
+use crate::TypeSerializer;
+use crate::I256;
+
+#[derive(Debug, Clone)]
+pub struct DecimalSerializer<'a> {
+    pub(crate) data_type: DecimalType,
+    pub(crate) values: &'a [i128],
+}
+
+impl<'a> DecimalSerializer<'a> {
+    pub fn try_create(data_type: DecimalType, col: &'a ColumnRef) -> Result<Self> {
+        let col: &PrimitiveColumn<i128> = Series::check_get(col)?;
+        Ok(Self {
+            data_type,
+            values: col.values(),
+        })
+    }
+}
+
+impl<'a> TypeSerializer<'a> for DecimalSerializer<'a> {
+    fn write_field(&self, row_index: usize, buf: &mut Vec<u8>, _format: &FormatSettings) {
+        let s = self.data_type.format_value(self.values[row_index]);
+        buf.extend_from_slice(s.as_bytes())
+    }
+
+    // Decimals are written as strings in JSON to avoid losing precision.
+    fn write_field_json(&self, row_index: usize, buf: &mut Vec<u8>, format: &FormatSettings) {
+        buf.push(b'\"');
+        self.write_field(row_index, buf, format);
+        buf.push(b'\"');
+    }
+
+    fn serialize_json_values(&self, _format: &FormatSettings) -> Result<Vec<Value>> {
+        let result: Vec<Value> = self
+            .values
+            .iter()
+            .map(|v| Value::String(self.data_type.format_value(*v)))
+            .collect();
+        Ok(result)
+    }
+
+    fn serialize_clickhouse_const(
+        &self,
+        _format: &FormatSettings,
+        size: usize,
+    ) -> Result<opensrv_clickhouse::types::column::ArcColumnData> {
+        let strings: Vec<String> = self
+            .values
+            .iter()
+            .map(|v| self.data_type.format_value(*v))
+            .collect();
+        let mut values: Vec<String> = Vec::with_capacity(self.values.len() * size);
+        for _ in 0..size {
+            for v in strings.iter() {
+                values.push(v.clone())
+            }
+        }
+        Ok(Vec::column_from::<ArcColumnWrapper>(values))
+    }
+
+    fn serialize_clickhouse_column(
+        &self,
+        _format: &FormatSettings,
+    ) -> Result<opensrv_clickhouse::types::column::ArcColumnData> {
+        let values: Vec<String> = self
+            .values
+            .iter()
+            .map(|v| self.data_type.format_value(*v))
+            .collect();
+        Ok(Vec::column_from::<ArcColumnWrapper>(values))
+    }
+}
+
+#[derive(Clone)]
+pub struct Decimal256Serializer<'a> {
+    pub(crate) data_type: Decimal256Type,
+    pub(crate) column: &'a StringColumn,
+}
+
+impl<'a> Decimal256Serializer<'a> {
+    pub fn try_create(data_type: Decimal256Type, col: &'a ColumnRef) -> Result<Self> {
+        let column: &StringColumn = Series::check_get(col)?;
+        Ok(Self { data_type, column })
+    }
+
+    fn format_row(&self, row_index: usize) -> String {
+        let value = I256::from_bytes(unsafe { self.column.value_unchecked(row_index) });
+        self.data_type.format_value(value)
+    }
+
+    fn format_rows(&self) -> Vec<String> {
+        (0..self.column.len())
+            .map(|row| self.format_row(row))
+            .collect()
+    }
+}
+
+impl<'a> TypeSerializer<'a> for Decimal256Serializer<'a> {
+    fn write_field(&self, row_index: usize, buf: &mut Vec<u8>, _format: &FormatSettings) {
+        buf.extend_from_slice(self.format_row(row_index).as_bytes())
+    }
+
+    // Decimals are written as strings in JSON to avoid losing precision.
+    fn write_field_json(&self, row_index: usize, buf: &mut Vec<u8>, format: &FormatSettings) {
+        buf.push(b'\"');
+        self.write_field(row_index, buf, format);
+        buf.push(b'\"');
+    }
+
+    fn serialize_json_values(&self, _format: &FormatSettings) -> Result<Vec<Value>> {
+        Ok(self.format_rows().into_iter().map(Value::String).collect())
+    }
+
+    fn serialize_clickhouse_const(
+        &self,
+        _format: &FormatSettings,
+        size: usize,
+    ) -> Result<opensrv_clickhouse::types::column::ArcColumnData> {
+        let strings = self.format_rows();
+        let mut values: Vec<String> = Vec::with_capacity(strings.len() * size);
+        for _ in 0..size {
+            for v in strings.iter() {
+                values.push(v.clone())
+            }
+        }
+        Ok(Vec::column_from::<ArcColumnWrapper>(values))
+    }
+
+    fn serialize_clickhouse_column(
+        &self,
+        _format: &FormatSettings,
+    ) -> Result<opensrv_clickhouse::types::column::ArcColumnData> {
+        Ok(Vec::column_from::<ArcColumnWrapper>(self.format_rows()))
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use opensrv_clickhouse::types::column::ArrayColumnData;
+use opensrv_clickhouse::types::column::TupleColumnData;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::prelude::*;
+use crate::serializations::write_json_string;
+
+#[derive(Clone)]
+pub struct MapSerializer<'a> {
+    offsets: &'a [i64],
+    key: Box<TypeSerializerImpl<'a>>,
+    value: Box<TypeSerializerImpl<'a>>,
+}
+
+impl<'a> MapSerializer<'a> {
+    pub fn try_create(
+        column: &'a ColumnRef,
+        key_type: &DataTypeImpl,
+        value_type: &DataTypeImpl,
+    ) -> Result<Self> {
+        let column: &ArrayColumn = Series::check_get(column)?;
+        let entries: &StructColumn = Series::check_get(column.values())?;
+        let key = Box::new(key_type.create_serializer(&entries.values()[0])?);
+        let value = Box::new(value_type.create_serializer(&entries.values()[1])?);
+        Ok(Self {
+            offsets: column.offsets(),
+            key,
+            value,
+        })
+    }
+}
+
+impl<'a> TypeSerializer<'a> for MapSerializer<'a> {
+    fn write_field(&self, row_index: usize, buf: &mut Vec<u8>, format: &FormatSettings) {
+        let start = self.offsets[row_index] as usize;
+        let end = self.offsets[row_index + 1] as usize;
+        buf.push(b'{');
+        for i in start..end {
+            if i != start {
+                buf.extend_from_slice(b", ");
+            }
+            self.key.write_field_quoted(i, buf, format, b'\'');
+            buf.push(b':');
+            self.value.write_field_quoted(i, buf, format, b'\'');
+        }
+        buf.push(b'}');
+    }
+
+    // Maps are written as JSON objects, the keys are always strings in JSON.
+    fn write_field_json(&self, row_index: usize, buf: &mut Vec<u8>, format: &FormatSettings) {
+        let start = self.offsets[row_index] as usize;
+        let end = self.offsets[row_index + 1] as usize;
+        buf.push(b'{');
+        for i in start..end {
+            if i != start {
+                buf.push(b',');
+            }
+            let mut key = Vec::new();
+            self.key.write_field(i, &mut key, format);
+            write_json_string(&key, buf, format);
+            buf.push(b':');
+            self.value.write_field_json(i, buf, format);
+        }
+        buf.push(b'}');
+    }
+
+    fn serialize_json_values(&self, format: &FormatSettings) -> Result<Vec<Value>> {
+        let size = self.offsets.len() - 1;
+        let mut result = Vec::with_capacity(size);
+        let values = self.value.serialize_json_values(format)?;
+        let mut iter = values.into_iter();
+        for row in 0..size {
+            let start = self.offsets[row] as usize;
+            let end = self.offsets[row + 1] as usize;
+            let mut object = Map::with_capacity(end - start);
+            for i in start..end {
+                let key = self.key.serialize_field(i, format)?;
+                object.insert(key, iter.next().unwrap_or(Value::Null));
+            }
+            result.push(Value::Object(object));
+        }
+        Ok(result)
+    }
+
+    // ClickHouse stores a map as an array of (key, value) tuples.
+    fn serialize_clickhouse_const(
+        &self,
+        format: &FormatSettings,
+        size: usize,
+    ) -> Result<opensrv_clickhouse::types::column::ArcColumnData> {
+        let len = self.offsets.len() - 1;
+        let mut offsets = opensrv_clickhouse::types::column::List::with_capacity(size * len);
+        let total = self.offsets[len];
+        let mut base = 0;
+        for _ in 0..size {
+            for offset in self.offsets.iter().skip(1) {
+                offsets.push(((*offset) + base) as u64);
+            }
+            base += total;
+        }
+
+        let inner = vec![
+            self.key.serialize_clickhouse_const(format, size)?,
+            self.value.serialize_clickhouse_const(format, size)?,
+        ];
+        let inner_data = Arc::new(TupleColumnData { inner });
+        Ok(Arc::new(ArrayColumnData::create(inner_data, offsets)))
+    }
+
+    fn serialize_clickhouse_column(
+        &self,
+        format: &FormatSettings,
+    ) -> Result<opensrv_clickhouse::types::column::ArcColumnData> {
+        let mut offsets =
+            opensrv_clickhouse::types::column::List::with_capacity(self.offsets.len() - 1);
+        for offset in self.offsets.iter().skip(1) {
+            offsets.push(*offset as u64);
+        }
+
+        let inner = vec![
+            self.key.serialize_clickhouse_column(format)?,
+            self.value.serialize_clickhouse_column(format)?,
+        ];
+        let inner_data = Arc::new(TupleColumnData { inner });
+        Ok(Arc::new(ArrayColumnData::create(inner_data, offsets)))
+    }
+}
@@ -16,7 +16,9 @@ mod array;
 mod boolean;
 mod const_;
 mod date;
+mod decimal;
 pub mod helper;
+mod map;
 mod null;
 mod nullable;
 mod number;
@@ -34,9 +36,12 @@ use common_exception::ToErrorCode;
 use common_io::prelude::FormatSettings;
 pub use const_::ConstSerializer;
 pub use date::DateSerializer;
+pub use decimal::Decimal256Serializer;
+pub use decimal::DecimalSerializer;
 use enum_dispatch::enum_dispatch;
 pub use helper::escape::write_escaped_string;
 pub use helper::json::write_json_string;
+pub use map::MapSerializer;
 pub use null::NullSerializer;
 pub use nullable::NullableSerializer;
 pub use number::NumberSerializer;
@@ -145,12 +150,15 @@ pub enum TypeSerializerImpl<'a> {
     UInt64(NumberSerializer<'a, u64>),
     Float32(NumberSerializer<'a, f32>),
     Float64(NumberSerializer<'a, f64>),
+    Decimal(DecimalSerializer<'a>),
+    Decimal256(Decimal256Serializer<'a>),
 
     Date(DateSerializer<'a, i32>),
     Interval(DateSerializer<'a, i64>),
     Timestamp(TimestampSerializer<'a>),
     String(StringSerializer<'a>),
     Array(ArraySerializer<'a>),
+    Map(MapSerializer<'a>),
     Struct(StructSerializer<'a>),
     Variant(VariantSerializer<'a>),
 }
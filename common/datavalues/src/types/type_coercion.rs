@@ -227,6 +227,138 @@ pub fn numerical_unary_arithmetic_coercion(
     }
 }
 
+/// The number of digits before the decimal point which are required to hold
+/// all the values of an integer or decimal type.
+fn decimal_integer_digits(data_type: &DataTypeImpl) -> Result<usize> {
+    if let Some((precision, scale)) = decimal_precision_scale(data_type) {
+        return Ok(precision - scale);
+    }
+
+    match data_type.data_type_id() {
+        Int8 | UInt8 => Ok(3),
+        Int16 | UInt16 => Ok(5),
+        Int32 | UInt32 => Ok(10),
+        Int64 => Ok(19),
+        UInt64 => Ok(20),
+        Boolean => Ok(1),
+        _ => Result::Err(ErrorCode::BadDataValueType(format!(
+            "Can't construct decimal type from {:?}",
+            data_type
+        ))),
+    }
+}
+
+fn decimal_scale(data_type: &DataTypeImpl) -> usize {
+    decimal_precision_scale(data_type).map_or(0, |(_, scale)| scale)
+}
+
+/// The result is a 256-bit decimal only if one of the arguments is,
+/// so the calculation on 128-bit decimals never spills into 256 bits.
+fn max_decimal_precision(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> usize {
+    if lhs_type.data_type_id() == Decimal256 || rhs_type.data_type_id() == Decimal256 {
+        MAX_DECIMAL256_PRECISION
+    } else {
+        MAX_DECIMAL_PRECISION
+    }
+}
+
+fn construct_decimal_type(
+    integer_digits: usize,
+    scale: usize,
+    max_precision: usize,
+) -> DataTypeImpl {
+    let scale = cmp::min(scale, max_precision);
+    let precision = cmp::max(cmp::min(integer_digits + scale, max_precision), 1);
+    create_decimal_type(precision, scale)
+}
+
+/// Coercion rule for decimal types: at least one of lhs and rhs is a decimal,
+/// the other one is a decimal, an integer or a float.
+/// Decimal with float is coerced to Float64, otherwise the result is a decimal
+/// which can hold both the integer part and the fractional part of the two types.
+pub fn decimal_coercion(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Result<DataTypeImpl> {
+    let lhs_id = lhs_type.data_type_id();
+    let rhs_id = rhs_type.data_type_id();
+
+    if lhs_id.is_floating() || rhs_id.is_floating() {
+        return Ok(Float64Type::new_impl());
+    }
+
+    let integer_digits = cmp::max(
+        decimal_integer_digits(lhs_type)?,
+        decimal_integer_digits(rhs_type)?,
+    );
+    let scale = cmp::max(decimal_scale(lhs_type), decimal_scale(rhs_type));
+    Ok(construct_decimal_type(
+        integer_digits,
+        scale,
+        max_decimal_precision(lhs_type, rhs_type),
+    ))
+}
+
+#[inline]
+pub fn decimal_arithmetic_coercion(
+    op: &DataValueBinaryOperator,
+    lhs_type: &DataTypeImpl,
+    rhs_type: &DataTypeImpl,
+) -> Result<DataTypeImpl> {
+    let lhs_id = lhs_type.data_type_id();
+    let rhs_id = rhs_type.data_type_id();
+
+    if !(lhs_id.is_numeric() || lhs_id.is_decimal())
+        || !(rhs_id.is_numeric() || rhs_id.is_decimal())
+    {
+        return Result::Err(ErrorCode::BadDataValueType(format!(
+            "DataValue Error: Unsupported ({:?}) {} ({:?})",
+            lhs_type, op, rhs_type
+        )));
+    };
+
+    if lhs_id.is_floating() || rhs_id.is_floating() {
+        return Ok(Float64Type::new_impl());
+    }
+
+    let lhs_digits = decimal_integer_digits(lhs_type)?;
+    let rhs_digits = decimal_integer_digits(rhs_type)?;
+    let lhs_scale = decimal_scale(lhs_type);
+    let rhs_scale = decimal_scale(rhs_type);
+    let max_precision = max_decimal_precision(lhs_type, rhs_type);
+
+    match op {
+        DataValueBinaryOperator::Plus | DataValueBinaryOperator::Minus => {
+            let integer_digits = cmp::max(lhs_digits, rhs_digits) + 1;
+            Ok(construct_decimal_type(
+                integer_digits,
+                cmp::max(lhs_scale, rhs_scale),
+                max_precision,
+            ))
+        }
+        DataValueBinaryOperator::Mul => Ok(construct_decimal_type(
+            lhs_digits + rhs_digits,
+            lhs_scale + rhs_scale,
+            max_precision,
+        )),
+        // Same as MySQL, the division keeps at least 6 digits after the decimal point
+        DataValueBinaryOperator::Div => {
+            let scale = cmp::max(lhs_scale, 6);
+            Ok(construct_decimal_type(
+                lhs_digits + rhs_scale,
+                scale,
+                max_precision,
+            ))
+        }
+        DataValueBinaryOperator::Modulo => {
+            let scale = cmp::max(lhs_scale, rhs_scale);
+            Ok(construct_decimal_type(
+                cmp::min(lhs_digits, rhs_digits),
+                scale,
+                max_precision,
+            ))
+        }
+        DataValueBinaryOperator::IntDiv => Ok(Int64Type::new_impl()),
+    }
+}
+
 // coercion rules for compare operations. This is a superset of all numerical coercion rules.
 pub fn compare_coercion(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Result<DataTypeImpl> {
     let lhs_id = lhs_type.data_type_id();
@@ -241,6 +373,12 @@ pub fn compare_coercion(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Res
         return numerical_coercion(lhs_type, rhs_type, true);
     }
 
+    if (lhs_id.is_decimal() && (rhs_id.is_numeric() || rhs_id.is_decimal()))
+        || (rhs_id.is_decimal() && lhs_id.is_numeric())
+    {
+        return decimal_coercion(lhs_type, rhs_type);
+    }
+
     //  one of is nothing
     {
         if lhs_id == TypeID::Null {
@@ -257,6 +395,14 @@ pub fn compare_coercion(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Res
         return Ok(Float64Type::new_impl());
     }
 
+    // one of is String and other is decimal
+    if lhs_id.is_decimal() && rhs_id.is_string() {
+        return Ok(lhs_type.clone());
+    }
+    if rhs_id.is_decimal() && lhs_id.is_string() {
+        return Ok(rhs_type.clone());
+    }
+
     // one of is datetime and other is number or string
     {
         if (lhs_id.is_numeric() || lhs_id.is_string()) && rhs_id.is_date_or_date_time() {
@@ -335,6 +481,14 @@ pub fn merge_types(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Result<D
             let typ = merge_types(a.inner_type(), b.inner_type())?;
             Ok(DataTypeImpl::Array(ArrayType::create(typ)))
         }
+        (Map, Map) => {
+            let a: MapType = lhs_type.to_owned().try_into()?;
+            let b: MapType = rhs_type.to_owned().try_into()?;
+
+            let key = merge_types(a.key_type(), b.key_type())?;
+            let value = merge_types(a.value_type(), b.value_type())?;
+            Ok(DataTypeImpl::Map(MapType::create(key, value)))
+        }
         (Struct, Struct) => {
             let a: StructType = lhs_type.to_owned().try_into()?;
             let b: StructType = rhs_type.to_owned().try_into()?;
@@ -356,6 +510,12 @@ pub fn merge_types(lhs_type: &DataTypeImpl, rhs_type: &DataTypeImpl) -> Result<D
                 types,
             )))
         }
+        (Decimal | Decimal256, _) | (_, Decimal | Decimal256)
+            if (lhs_id.is_numeric() || lhs_id.is_decimal())
+                && (rhs_id.is_numeric() || rhs_id.is_decimal()) =>
+        {
+            decimal_coercion(lhs_type, rhs_type)
+        }
         _ => {
             if lhs_id == rhs_id {
                 return Ok(lhs_type.clone());
@@ -0,0 +1,386 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use rand::prelude::*;
+
+use super::data_type::DataType;
+use super::type_id::TypeID;
+use crate::prelude::*;
+use crate::serializations::DecimalSerializer;
+use crate::serializations::TypeSerializerImpl;
+
+/// The unscaled value of a decimal is stored in 128 bits,
+/// which can hold at most 38 significant digits.
+pub const MAX_DECIMAL_PRECISION: usize = 38;
+/// `DECIMAL` without precision and scale means `DECIMAL(10, 0)`
+pub const DEFAULT_DECIMAL_PRECISION: usize = 10;
+pub const DEFAULT_DECIMAL_SCALE: usize = 0;
+
+/// Decimal type stores the value `v * 10^scale` as an `i128` integer,
+/// so that the arithmetic on decimals is exact.
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+pub struct DecimalType {
+    /// The total number of significant digits, range from 1 to 38
+    precision: usize,
+    /// The number of digits after the decimal point, range from 0 to precision
+    scale: usize,
+}
+
+impl Default for DecimalType {
+    fn default() -> Self {
+        DecimalType::create(DEFAULT_DECIMAL_PRECISION, DEFAULT_DECIMAL_SCALE)
+    }
+}
+
+impl DecimalType {
+    pub fn create(precision: usize, scale: usize) -> Self {
+        DecimalType { precision, scale }
+    }
+
+    pub fn try_create(precision: usize, scale: usize) -> Result<Self> {
+        if precision == 0 || precision > MAX_DECIMAL_PRECISION {
+            return Err(ErrorCode::BadArguments(format!(
+                "Decimal precision must be between 1 and {}, but got {}",
+                MAX_DECIMAL_PRECISION, precision
+            )));
+        }
+        if scale > precision {
+            return Err(ErrorCode::BadArguments(format!(
+                "Decimal scale must be between 0 and the precision {}, but got {}",
+                precision, scale
+            )));
+        }
+        Ok(Self::create(precision, scale))
+    }
+
+    pub fn new_impl(precision: usize, scale: usize) -> DataTypeImpl {
+        DataTypeImpl::Decimal(Self::create(precision, scale))
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// The largest unscaled value which fits in the precision
+    #[inline]
+    pub fn max_value(&self) -> i128 {
+        10_i128.pow(self.precision as u32) - 1
+    }
+
+    #[inline]
+    pub fn check_value(&self, value: i128) -> Result<i128> {
+        let max = self.max_value();
+        if value > max || value < -max {
+            return Err(ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                self.format_value(value),
+                self.name()
+            )));
+        }
+        Ok(value)
+    }
+
+    /// Rescale an unscaled value of scale `from` to scale `to`,
+    /// the dropped digits are rounded half away from zero.
+    #[inline]
+    pub fn rescale(value: i128, from: usize, to: usize) -> Option<i128> {
+        if from <= to {
+            return 10_i128
+                .checked_pow((to - from) as u32)
+                .and_then(|factor| value.checked_mul(factor));
+        }
+
+        let factor = match 10_i128.checked_pow((from - to) as u32) {
+            Some(factor) => factor,
+            None => return Some(0),
+        };
+        let quotient = value / factor;
+        let remainder = value % factor;
+        if remainder.abs() * 2 >= factor {
+            Some(quotient + value.signum())
+        } else {
+            Some(quotient)
+        }
+    }
+
+    pub fn from_i128(&self, value: i128) -> Result<i128> {
+        let value = Self::rescale(value, 0, self.scale).ok_or_else(|| {
+            ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                value,
+                self.name()
+            ))
+        })?;
+        self.check_value(value)
+    }
+
+    pub fn from_f64(&self, value: f64) -> Result<i128> {
+        let scaled = (value * 10_f64.powi(self.scale as i32)).round();
+        if !scaled.is_finite() || scaled.abs() > self.max_value() as f64 {
+            return Err(ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                value,
+                self.name()
+            )));
+        }
+        Ok(scaled as i128)
+    }
+
+    #[inline]
+    pub fn to_f64(&self, value: i128) -> f64 {
+        value as f64 / 10_f64.powi(self.scale as i32)
+    }
+
+    /// Parse a decimal literal like `-123.456`, `1e10` or `.5` into the unscaled value.
+    pub fn parse_bytes(&self, bytes: &[u8]) -> Result<i128> {
+        let overflow = || {
+            ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                String::from_utf8_lossy(bytes),
+                self.name()
+            ))
+        };
+
+        let literal = DecimalLiteral::try_parse(bytes, &self.name())?;
+        let mut value: i128 = 0;
+        for digit in literal.digits() {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add(digit as i128))
+                .ok_or_else(overflow)?;
+        }
+
+        let value = if literal.scale >= 0 {
+            Self::rescale(value, literal.scale as usize, self.scale)
+        } else {
+            Self::rescale(value, 0, self.scale + (-literal.scale) as usize)
+        }
+        .ok_or_else(overflow)?;
+
+        self.check_value(if literal.negative { -value } else { value })
+    }
+
+    /// Format the unscaled value with exactly `scale` digits after the decimal point.
+    pub fn format_value(&self, value: i128) -> String {
+        let digits = value.unsigned_abs().to_string();
+        let mut s = String::with_capacity(digits.len() + 3);
+        if value < 0 {
+            s.push('-');
+        }
+
+        if self.scale == 0 {
+            s.push_str(&digits);
+        } else if digits.len() > self.scale {
+            let (int_part, frac_part) = digits.split_at(digits.len() - self.scale);
+            s.push_str(int_part);
+            s.push('.');
+            s.push_str(frac_part);
+        } else {
+            s.push_str("0.");
+            s.extend(std::iter::repeat('0').take(self.scale - digits.len()));
+            s.push_str(&digits);
+        }
+        s
+    }
+}
+
+/// Create the decimal type of `DECIMAL(precision, scale)`, the unscaled values are stored
+/// in 128 bits if the precision is at most 38, otherwise in 256 bits.
+pub fn try_create_decimal_type(precision: usize, scale: usize) -> Result<DataTypeImpl> {
+    if precision > MAX_DECIMAL_PRECISION {
+        Decimal256Type::try_create(precision, scale).map(DataTypeImpl::Decimal256)
+    } else {
+        DecimalType::try_create(precision, scale).map(DataTypeImpl::Decimal)
+    }
+}
+
+/// Same as `try_create_decimal_type`, but the precision and scale are known to be valid.
+pub fn create_decimal_type(precision: usize, scale: usize) -> DataTypeImpl {
+    if precision > MAX_DECIMAL_PRECISION {
+        Decimal256Type::new_impl(precision, scale)
+    } else {
+        DecimalType::new_impl(precision, scale)
+    }
+}
+
+/// Returns the precision and scale of a decimal type of either width.
+pub fn decimal_precision_scale(data_type: &DataTypeImpl) -> Option<(usize, usize)> {
+    match data_type {
+        DataTypeImpl::Decimal(decimal) => Some((decimal.precision(), decimal.scale())),
+        DataTypeImpl::Decimal256(decimal) => Some((decimal.precision(), decimal.scale())),
+        _ => None,
+    }
+}
+
+impl DataType for DecimalType {
+    fn data_type_id(&self) -> TypeID {
+        TypeID::Decimal
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> String {
+        format!("Decimal({}, {})", self.precision, self.scale)
+    }
+
+    fn default_value(&self) -> DataValue {
+        DataValue::Int128(0)
+    }
+
+    fn random_value(&self) -> DataValue {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        let max = self.max_value();
+        DataValue::Int128(rng.gen_range(-max..=max))
+    }
+
+    fn create_constant_column(&self, data: &DataValue, size: usize) -> Result<ColumnRef> {
+        let value = data.as_i128()?;
+        let column = Series::from_data(&[value]);
+        Ok(Arc::new(ConstColumn::new(column, size)))
+    }
+
+    fn create_column(&self, data: &[DataValue]) -> Result<ColumnRef> {
+        let value = data
+            .iter()
+            .map(|v| v.as_i128())
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Series::from_data(&value))
+    }
+
+    fn arrow_type(&self) -> ArrowType {
+        ArrowType::Decimal(self.precision, self.scale)
+    }
+
+    fn create_serializer_inner<'a>(&self, col: &'a ColumnRef) -> Result<TypeSerializerImpl<'a>> {
+        Ok(DecimalSerializer::<'a>::try_create(*self, col)?.into())
+    }
+
+    fn create_deserializer(&self, capacity: usize) -> TypeDeserializerImpl {
+        DecimalDeserializer {
+            builder: MutablePrimitiveColumn::<i128>::from_data(
+                DataTypeImpl::Decimal(*self),
+                Vec::with_capacity(capacity),
+            ),
+            data_type: *self,
+        }
+        .into()
+    }
+
+    fn create_mutable(&self, capacity: usize) -> Box<dyn MutableColumn> {
+        Box::new(MutablePrimitiveColumn::<i128>::from_data(
+            DataTypeImpl::Decimal(*self),
+            Vec::with_capacity(capacity),
+        ))
+    }
+}
+
+impl std::fmt::Debug for DecimalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A decimal literal like `-123.456e2` which is checked to be well-formed.
+pub(crate) struct DecimalLiteral<'a> {
+    pub negative: bool,
+    /// The digits with an optional decimal point
+    pub mantissa: &'a [u8],
+    /// The number of digits after the decimal point of the integer made of all the digits
+    pub scale: i32,
+}
+
+impl<'a> DecimalLiteral<'a> {
+    pub fn try_parse(bytes: &'a [u8], type_name: &str) -> Result<Self> {
+        let error = || {
+            ErrorCode::BadBytes(format!(
+                "Cannot parse value:{} to {}",
+                String::from_utf8_lossy(bytes),
+                type_name
+            ))
+        };
+
+        let start = bytes
+            .iter()
+            .position(|c| !c.is_ascii_whitespace())
+            .unwrap_or(bytes.len());
+        let end = bytes
+            .iter()
+            .rposition(|c| !c.is_ascii_whitespace())
+            .map_or(start, |pos| pos + 1);
+        let input = &bytes[start..end];
+        let (negative, input) = match input.first() {
+            Some(b'-') => (true, &input[1..]),
+            Some(b'+') => (false, &input[1..]),
+            _ => (false, input),
+        };
+
+        let (mantissa, exponent) = match input.iter().position(|c| *c == b'e' || *c == b'E') {
+            Some(pos) => {
+                let exponent = std::str::from_utf8(&input[pos + 1..])
+                    .ok()
+                    .and_then(|e| e.parse::<i32>().ok())
+                    .ok_or_else(error)?;
+                (&input[..pos], exponent)
+            }
+            None => (input, 0),
+        };
+
+        let mut digits = 0;
+        let mut frac_digits: i32 = 0;
+        let mut seen_point = false;
+        for c in mantissa {
+            match c {
+                b'0'..=b'9' => {
+                    digits += 1;
+                    if seen_point {
+                        frac_digits += 1;
+                    }
+                }
+                b'.' if !seen_point => seen_point = true,
+                _ => return Err(error()),
+            }
+        }
+        if digits == 0 {
+            return Err(error());
+        }
+
+        Ok(Self {
+            negative,
+            mantissa,
+            scale: frac_digits - exponent,
+        })
+    }
+
+    /// The values of the digits, from the most significant one
+    pub fn digits(&self) -> impl Iterator<Item = u8> + 'a {
+        let mantissa = self.mantissa;
+        mantissa
+            .iter()
+            .filter(|c| c.is_ascii_digit())
+            .map(|c| c - b'0')
+    }
+}
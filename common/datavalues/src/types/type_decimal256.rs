@@ -0,0 +1,305 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use rand::prelude::*;
+
+use super::data_type::DataType;
+use super::data_type::ARROW_EXTENSION_META;
+use super::data_type::ARROW_EXTENSION_NAME;
+use super::type_decimal::DecimalLiteral;
+use super::type_id::TypeID;
+use crate::prelude::*;
+use crate::serializations::Decimal256Serializer;
+use crate::serializations::TypeSerializerImpl;
+
+/// The unscaled value of a decimal with more than 38 significant digits is stored
+/// in 256 bits, which can hold at most 76 significant digits.
+pub const MAX_DECIMAL256_PRECISION: usize = 76;
+
+/// Decimal256 type stores the value `v * 10^scale` as an `I256` integer.
+///
+/// There is no 256-bit native type in arrow, so the values are stored in a string column,
+/// each of which is the 32 bytes encoded by `I256::to_bytes`. The encoded values have the
+/// same order as the numbers, so the sorting, grouping and comparisons of the same type
+/// work on the bytes directly.
+#[derive(Clone, Copy, serde::Deserialize, serde::Serialize)]
+pub struct Decimal256Type {
+    /// The total number of significant digits, range from 1 to 76
+    precision: usize,
+    /// The number of digits after the decimal point, range from 0 to precision
+    scale: usize,
+}
+
+impl Decimal256Type {
+    pub fn create(precision: usize, scale: usize) -> Self {
+        Decimal256Type { precision, scale }
+    }
+
+    pub fn try_create(precision: usize, scale: usize) -> Result<Self> {
+        if precision == 0 || precision > MAX_DECIMAL256_PRECISION {
+            return Err(ErrorCode::BadArguments(format!(
+                "Decimal precision must be between 1 and {}, but got {}",
+                MAX_DECIMAL256_PRECISION, precision
+            )));
+        }
+        if scale > precision {
+            return Err(ErrorCode::BadArguments(format!(
+                "Decimal scale must be between 0 and the precision {}, but got {}",
+                precision, scale
+            )));
+        }
+        Ok(Self::create(precision, scale))
+    }
+
+    pub fn new_impl(precision: usize, scale: usize) -> DataTypeImpl {
+        DataTypeImpl::Decimal256(Self::create(precision, scale))
+    }
+
+    pub fn precision(&self) -> usize {
+        self.precision
+    }
+
+    pub fn scale(&self) -> usize {
+        self.scale
+    }
+
+    /// The largest unscaled value which fits in the precision
+    #[inline]
+    pub fn max_value(&self) -> I256 {
+        // 10^76 < 2^255, so it never overflows
+        I256::checked_pow10(self.precision)
+            .and_then(|v| v.checked_sub(I256::from(1)))
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn check_value(&self, value: I256) -> Result<I256> {
+        if value.abs() > self.max_value() {
+            return Err(ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                self.format_value(value),
+                self.name()
+            )));
+        }
+        Ok(value)
+    }
+
+    /// Rescale an unscaled value of scale `from` to scale `to`,
+    /// the dropped digits are rounded half away from zero.
+    #[inline]
+    pub fn rescale(value: I256, from: usize, to: usize) -> Option<I256> {
+        if from <= to {
+            return I256::checked_pow10(to - from).and_then(|factor| value.checked_mul(factor));
+        }
+
+        let factor = match I256::checked_pow10(from - to) {
+            Some(factor) => factor,
+            None => return Some(I256::zero()),
+        };
+        let quotient = value.checked_div(factor)?;
+        let remainder = value.checked_rem(factor)?;
+        if remainder.abs().checked_add(remainder.abs())? >= factor {
+            let one = if value.is_negative() {
+                I256::from(-1)
+            } else {
+                I256::from(1)
+            };
+            quotient.checked_add(one)
+        } else {
+            Some(quotient)
+        }
+    }
+
+    pub fn from_i128(&self, value: i128) -> Result<I256> {
+        let value = Self::rescale(I256::from(value), 0, self.scale).ok_or_else(|| {
+            ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                value,
+                self.name()
+            ))
+        })?;
+        self.check_value(value)
+    }
+
+    pub fn from_f64(&self, value: f64) -> Result<I256> {
+        let scaled = (value * 10_f64.powi(self.scale as i32)).round();
+        match I256::from_f64(scaled) {
+            Some(v) if v.abs() <= self.max_value() => Ok(v),
+            _ => Err(ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                value,
+                self.name()
+            ))),
+        }
+    }
+
+    #[inline]
+    pub fn to_f64(&self, value: I256) -> f64 {
+        value.to_f64() / 10_f64.powi(self.scale as i32)
+    }
+
+    /// Parse a decimal literal like `-123.456`, `1e10` or `.5` into the unscaled value.
+    pub fn parse_bytes(&self, bytes: &[u8]) -> Result<I256> {
+        let overflow = || {
+            ErrorCode::Overflow(format!(
+                "Decimal overflow: the value {} exceeds the range of {}",
+                String::from_utf8_lossy(bytes),
+                self.name()
+            ))
+        };
+
+        let literal = DecimalLiteral::try_parse(bytes, &self.name())?;
+        let ten = I256::from(10);
+        let mut value = I256::zero();
+        for digit in literal.digits() {
+            value = value
+                .checked_mul(ten)
+                .and_then(|v| v.checked_add(I256::from(digit as i128)))
+                .ok_or_else(overflow)?;
+        }
+
+        let value = if literal.scale >= 0 {
+            Self::rescale(value, literal.scale as usize, self.scale)
+        } else {
+            Self::rescale(value, 0, self.scale + (-literal.scale) as usize)
+        }
+        .ok_or_else(overflow)?;
+
+        self.check_value(if literal.negative { -value } else { value })
+    }
+
+    /// Format the unscaled value with exactly `scale` digits after the decimal point.
+    pub fn format_value(&self, value: I256) -> String {
+        let digits = value.abs().to_string();
+        let mut s = String::with_capacity(digits.len() + 3);
+        if value.is_negative() {
+            s.push('-');
+        }
+
+        if self.scale == 0 {
+            s.push_str(&digits);
+        } else if digits.len() > self.scale {
+            let (int_part, frac_part) = digits.split_at(digits.len() - self.scale);
+            s.push_str(int_part);
+            s.push('.');
+            s.push_str(frac_part);
+        } else {
+            s.push_str("0.");
+            s.extend(std::iter::repeat('0').take(self.scale - digits.len()));
+            s.push_str(&digits);
+        }
+        s
+    }
+
+    /// Returns the encoded value of a `DataValue`, which is the value of a `Decimal256Type` column.
+    fn encoded_value(&self, data: &DataValue) -> Result<Vec<u8>> {
+        match data {
+            DataValue::String(v) if v.len() == I256::BYTE_SIZE => Ok(v.clone()),
+            _ => Err(ErrorCode::BadDataValueType(format!(
+                "Unexpected value {:?} of type {}",
+                data,
+                self.name()
+            ))),
+        }
+    }
+}
+
+impl DataType for Decimal256Type {
+    fn data_type_id(&self) -> TypeID {
+        TypeID::Decimal256
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> String {
+        format!("Decimal({}, {})", self.precision, self.scale)
+    }
+
+    fn default_value(&self) -> DataValue {
+        DataValue::String(I256::zero().to_bytes().to_vec())
+    }
+
+    fn random_value(&self) -> DataValue {
+        let mut rng = rand::rngs::SmallRng::from_entropy();
+        let max = 10_i128.pow(self.precision.min(MAX_DECIMAL_PRECISION) as u32) - 1;
+        let value = I256::from(rng.gen_range(-max..=max));
+        DataValue::String(value.to_bytes().to_vec())
+    }
+
+    fn create_constant_column(&self, data: &DataValue, size: usize) -> Result<ColumnRef> {
+        let value = self.encoded_value(data)?;
+        let column = Series::from_data(vec![value]);
+        Ok(Arc::new(ConstColumn::new(column, size)))
+    }
+
+    fn create_column(&self, data: &[DataValue]) -> Result<ColumnRef> {
+        let values = data
+            .iter()
+            .map(|v| self.encoded_value(v))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Series::from_data(values))
+    }
+
+    fn arrow_type(&self) -> ArrowType {
+        ArrowType::LargeBinary
+    }
+
+    fn custom_arrow_meta(&self) -> Option<BTreeMap<String, String>> {
+        let mut mp = BTreeMap::new();
+        mp.insert(ARROW_EXTENSION_NAME.to_string(), "Decimal256".to_string());
+        mp.insert(
+            ARROW_EXTENSION_META.to_string(),
+            format!("{},{}", self.precision, self.scale),
+        );
+        Some(mp)
+    }
+
+    fn create_serializer_inner<'a>(&self, col: &'a ColumnRef) -> Result<TypeSerializerImpl<'a>> {
+        Ok(Decimal256Serializer::<'a>::try_create(*self, col)?.into())
+    }
+
+    fn create_deserializer(&self, capacity: usize) -> TypeDeserializerImpl {
+        Decimal256Deserializer {
+            builder: MutableStringColumn::with_values_capacity(
+                capacity * I256::BYTE_SIZE,
+                capacity,
+            ),
+            data_type: *self,
+        }
+        .into()
+    }
+
+    fn create_mutable(&self, capacity: usize) -> Box<dyn MutableColumn> {
+        Box::new(MutableStringColumn::with_values_capacity(
+            capacity * I256::BYTE_SIZE,
+            capacity,
+        ))
+    }
+}
+
+impl std::fmt::Debug for Decimal256Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
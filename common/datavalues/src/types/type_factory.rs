@@ -97,6 +97,25 @@ impl TypeFactory {
             return self.get(name).map(ArrayType::new_impl);
         }
 
+        if lowercase_name.starts_with("map(") {
+            let args = &lowercase_name[4..lowercase_name.len() - 1];
+            return Self::get_map(args)
+                .ok_or_else(|| {
+                    ErrorCode::IllegalDataType(format!("Unsupported data type: {}", origin_name))
+                })
+                .and_then(|(key, value)| {
+                    let key = self.get(key)?;
+                    let value = self.get(value)?;
+                    Ok(DataTypeImpl::Map(MapType::try_create(key, value)?))
+                });
+        }
+
+        if lowercase_name.starts_with("decimal") || lowercase_name.starts_with("numeric") {
+            return Self::get_decimal(&lowercase_name[7..]).ok_or_else(|| {
+                ErrorCode::IllegalDataType(format!("Unsupported data type: {}", origin_name))
+            })?;
+        }
+
         if lowercase_name.starts_with("tuple(") {
             let names = origin_name[6..origin_name.len() - 1].to_string();
             let bytes = names.as_bytes();
@@ -192,6 +211,45 @@ impl TypeFactory {
             })
     }
 
+    /// Parse the arguments of decimal, like `""`, `"(10)"` or `"(10, 2)"`
+    /// Split the arguments of `map(key, value)` at the top-level comma.
+    fn get_map(args: &str) -> Option<(&str, &str)> {
+        let mut depth = 0;
+        for (index, c) in args.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    return Some((args[..index].trim(), args[index + 1..].trim()));
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn get_decimal(args: &str) -> Option<Result<DataTypeImpl>> {
+        let args = args.trim();
+        if args.is_empty() {
+            return Some(Ok(DecimalType::new_impl(
+                DEFAULT_DECIMAL_PRECISION,
+                DEFAULT_DECIMAL_SCALE,
+            )));
+        }
+
+        let args = args.strip_prefix('(')?.strip_suffix(')')?;
+        let mut args = args.split(',').map(|arg| arg.trim().parse::<usize>());
+        let precision = args.next()?.ok()?;
+        let scale = match args.next() {
+            Some(scale) => scale.ok()?,
+            None => DEFAULT_DECIMAL_SCALE,
+        };
+        if args.next().is_some() {
+            return None;
+        }
+        Some(try_create_decimal_type(precision, scale))
+    }
+
     pub fn register_names(&self) -> Vec<&str> {
         self.case_insensitive_types
             .keys()
@@ -33,6 +33,14 @@ pub enum TypeID {
     Float32,
     Float64,
 
+    /// A fixed-point number with `precision` significant digits and `scale` digits
+    /// after the decimal point, its physical type is Int128 which stores the unscaled value
+    Decimal,
+
+    /// A decimal with more than 38 significant digits, its physical type is String
+    /// which stores the 32 bytes encoded unscaled value
+    Decimal256,
+
     String,
 
     /// A 32-bit date representing the elapsed time since UNIX epoch (1970-01-01)
@@ -51,6 +59,10 @@ pub enum TypeID {
     Array,
     Struct,
 
+    /// A map from keys to values, its physical type is Array
+    /// whose elements are structs of (key, value)
+    Map,
+
     /// Variant is a tagged universal type, which can store values of any other type,
     /// including Object and Array, up to a maximum size of 16 MB.
     Variant,
@@ -110,6 +122,11 @@ impl TypeID {
         matches!(self, TypeID::Float32 | TypeID::Float64)
     }
 
+    #[inline]
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, TypeID::Decimal | TypeID::Decimal256)
+    }
+
     #[inline]
     pub fn is_date_or_date_time(&self) -> bool {
         matches!(self, TypeID::Date | TypeID::Timestamp)
@@ -160,6 +177,11 @@ impl TypeID {
         matches!(self, TypeID::Struct)
     }
 
+    #[inline]
+    pub fn is_map(&self) -> bool {
+        matches!(self, TypeID::Map)
+    }
+
     #[inline]
     pub fn is_quoted(&self) -> bool {
         matches!(self, TypeID::String | TypeID::Date | TypeID::Timestamp)
@@ -190,6 +212,8 @@ impl TypeID {
             TypeID::Int16 | TypeID::UInt16 => Ok(2),
             TypeID::Int32 | TypeID::UInt32 | TypeID::Float32 | TypeID::Date => Ok(4),
             TypeID::Int64 | TypeID::UInt64 | TypeID::Float64 | TypeID::Timestamp => Ok(8),
+            TypeID::Decimal => Ok(16),
+            TypeID::Decimal256 => Ok(32),
             _ => Result::Err(ErrorCode::BadArguments(format!(
                 "Function number_byte_size argument must be numeric types, but got {:?}",
                 self
@@ -215,9 +239,10 @@ impl TypeID {
             UInt64 => PhysicalTypeID::UInt64,
             Float32 => PhysicalTypeID::Float32,
             Float64 => PhysicalTypeID::Float64,
+            Decimal => PhysicalTypeID::Int128,
 
-            String => PhysicalTypeID::String,
-            Array => PhysicalTypeID::Array,
+            String | Decimal256 => PhysicalTypeID::String,
+            Array | Map => PhysicalTypeID::Array,
             Struct => PhysicalTypeID::Struct,
             Variant | VariantArray | VariantObject => PhysicalTypeID::Variant,
         }
@@ -253,6 +278,8 @@ pub enum PhysicalTypeID {
     Int32,
     /// A signed 64-bit integer.
     Int64,
+    /// A signed 128-bit integer.
+    Int128,
     /// An unsigned 8-bit integer.
     UInt8,
     /// An unsigned 16-bit integer.
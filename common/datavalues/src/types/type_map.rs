@@ -0,0 +1,187 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::DataType as ArrowType;
+use common_arrow::arrow::datatypes::Field;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::data_type::DataType;
+use super::data_type::DataTypeImpl;
+use super::type_id::TypeID;
+use crate::prelude::*;
+use crate::serializations::MapSerializer;
+use crate::serializations::TypeSerializerImpl;
+
+pub const MAP_KEY_NAME: &str = "key";
+pub const MAP_VALUE_NAME: &str = "value";
+pub const MAP_ENTRIES_NAME: &str = "entries";
+
+/// Map type stores the entries of each row as an array of (key, value) structs,
+/// a map value is a `DataValue::Array` of `DataValue::Struct(vec![key, value])`.
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct MapType {
+    key: Box<DataTypeImpl>,
+    value: Box<DataTypeImpl>,
+}
+
+impl MapType {
+    pub fn new_impl(key: DataTypeImpl, value: DataTypeImpl) -> DataTypeImpl {
+        DataTypeImpl::Map(Self::create(key, value))
+    }
+
+    pub fn create(key: DataTypeImpl, value: DataTypeImpl) -> Self {
+        MapType {
+            key: Box::new(key),
+            value: Box::new(value),
+        }
+    }
+
+    /// Keys of a map must be non-nullable scalar types which can be compared.
+    pub fn try_create(key: DataTypeImpl, value: DataTypeImpl) -> Result<Self> {
+        let key_id = key.data_type_id();
+        if !(key_id.is_numeric()
+            || key_id.is_string()
+            || key_id.is_decimal()
+            || key_id.is_date_or_date_time()
+            || key_id == TypeID::Boolean)
+        {
+            return Err(ErrorCode::IllegalDataType(format!(
+                "Map key type must be a non-nullable number, string, boolean, date or timestamp, but got {}",
+                key.name()
+            )));
+        }
+        Ok(Self::create(key, value))
+    }
+
+    pub fn key_type(&self) -> &DataTypeImpl {
+        &self.key
+    }
+
+    pub fn value_type(&self) -> &DataTypeImpl {
+        &self.value
+    }
+
+    /// The struct type of the entries, which is the inner type of the underlying array column.
+    pub fn entries_type(&self) -> DataTypeImpl {
+        StructType::new_impl(
+            Some(vec![MAP_KEY_NAME.to_string(), MAP_VALUE_NAME.to_string()]),
+            vec![*self.key.clone(), *self.value.clone()],
+        )
+    }
+
+    fn array_type(&self) -> ArrayType {
+        ArrayType::create(self.entries_type())
+    }
+}
+
+impl DataType for MapType {
+    fn data_type_id(&self) -> TypeID {
+        TypeID::Map
+    }
+
+    #[inline]
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> String {
+        format!("Map({}, {})", self.key.name(), self.value.name())
+    }
+
+    fn sql_name(&self) -> String {
+        format!("MAP({}, {})", self.key.sql_name(), self.value.sql_name())
+    }
+
+    fn default_value(&self) -> DataValue {
+        DataValue::Array(vec![])
+    }
+
+    fn random_value(&self) -> DataValue {
+        // randomly generate a map with 3 entries, the keys may be duplicated.
+        let entries = (0..3)
+            .map(|_| DataValue::Struct(vec![self.key.random_value(), self.value.random_value()]))
+            .collect();
+        DataValue::Array(entries)
+    }
+
+    fn create_constant_column(&self, data: &DataValue, size: usize) -> Result<ColumnRef> {
+        if let DataValue::Array(value) = data {
+            let inner_column = self.entries_type().create_column(value)?;
+            let offsets = vec![0, value.len() as i64];
+            let column = Arc::new(ArrayColumn::from_data(
+                DataTypeImpl::Map(self.clone()),
+                offsets.into(),
+                inner_column,
+            ));
+
+            return Ok(Arc::new(ConstColumn::new(column, size)));
+        }
+
+        Err(ErrorCode::BadDataValueType(format!(
+            "Unexpected type:{:?} to generate map column",
+            data.value_type()
+        )))
+    }
+
+    fn create_column(&self, data: &[DataValue]) -> Result<ColumnRef> {
+        let column = self.array_type().create_column(data)?;
+        let column: &ArrayColumn = Series::check_get(&column)?;
+
+        Ok(Arc::new(ArrayColumn::from_data(
+            DataTypeImpl::Map(self.clone()),
+            column.offsets().to_vec().into(),
+            column.values().clone(),
+        )))
+    }
+
+    fn arrow_type(&self) -> ArrowType {
+        let entries = Field::new(MAP_ENTRIES_NAME, self.entries_type().arrow_type(), false);
+        ArrowType::Map(Box::new(entries), false)
+    }
+
+    fn create_serializer_inner<'a>(&self, col: &'a ColumnRef) -> Result<TypeSerializerImpl<'a>> {
+        Ok(MapSerializer::try_create(col, &self.key, &self.value)?.into())
+    }
+
+    fn create_deserializer(&self, capacity: usize) -> TypeDeserializerImpl {
+        MapDeserializer {
+            key: Box::new(self.key.create_deserializer(capacity)),
+            value: Box::new(self.value.create_deserializer(capacity)),
+            builder: MutableArrayColumn::with_capacity_meta(capacity, ColumnMeta::Map {
+                key_type: *self.key.clone(),
+                value_type: *self.value.clone(),
+            }),
+        }
+        .into()
+    }
+
+    fn create_mutable(&self, capacity: usize) -> Box<dyn MutableColumn> {
+        Box::new(MutableArrayColumn::with_capacity_meta(
+            capacity,
+            ColumnMeta::Map {
+                key_type: *self.key.clone(),
+                value_type: *self.value.clone(),
+            },
+        ))
+    }
+}
+
+impl std::fmt::Debug for MapType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -50,6 +50,10 @@ pub fn create_primitive_datatype<T: PrimitiveType>() -> DataTypeImpl {
         (true, true, 4) => DataTypeImpl::Float32(Float32Type { _t: PhantomData }),
         (true, true, 8) => DataTypeImpl::Float64(Float64Type { _t: PhantomData }),
 
+        // i128 is only used to store the unscaled value of decimals, the column itself doesn't
+        // know the precision and scale, which always come from the schema or the return type
+        (true, false, 16) => DecimalType::new_impl(MAX_DECIMAL_PRECISION, 0),
+
         _ => unimplemented!(),
     }
 }
@@ -67,6 +67,7 @@ impl_primitive!(i8, i64, true, false, 1);
 impl_primitive!(i16, i64, true, false, 2);
 impl_primitive!(i32, i64, true, false, 4);
 impl_primitive!(i64, i64, true, false, 8);
+impl_primitive!(i128, i128, true, false, 16);
 impl_primitive!(f32, f64, true, true, 4);
 impl_primitive!(f64, f64, true, true, 8);
 
@@ -16,4 +16,6 @@ mod create_column;
 mod date_ts_bound;
 mod deserializations;
 mod serializations;
+mod type_decimal;
+mod type_map;
 mod viewer;
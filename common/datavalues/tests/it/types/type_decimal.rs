@@ -0,0 +1,212 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_datavalues::type_coercion::decimal_arithmetic_coercion;
+use common_datavalues::type_coercion::decimal_coercion;
+use common_datavalues::type_coercion::merge_types;
+use common_exception::Result;
+
+#[test]
+fn test_decimal_parse_and_format() -> Result<()> {
+    let decimal = DecimalType::try_create(10, 2)?;
+
+    let tests = vec![
+        ("0", 0, "0.00"),
+        ("1.5", 150, "1.50"),
+        ("-1.234", -123, "-1.23"),
+        ("-1.235", -124, "-1.24"),
+        (" +12.3e2 ", 123000, "1230.00"),
+        (".05", 5, "0.05"),
+        ("99999999.99", 9999999999, "99999999.99"),
+    ];
+
+    for (input, value, output) in tests {
+        let v = decimal.parse_bytes(input.as_bytes())?;
+        assert_eq!(v, value, "parse {}", input);
+        assert_eq!(decimal.format_value(v), output, "format {}", input);
+    }
+
+    assert!(decimal.parse_bytes(b"abc").is_err());
+    assert!(decimal.parse_bytes(b"1.2.3").is_err());
+    assert!(decimal.parse_bytes(b"100000000").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decimal_try_create() -> Result<()> {
+    assert!(DecimalType::try_create(38, 38).is_ok());
+    assert!(DecimalType::try_create(0, 0).is_err());
+    assert!(DecimalType::try_create(39, 0).is_err());
+    assert!(DecimalType::try_create(10, 11).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_decimal_coercion() -> Result<()> {
+    let decimal_10_2 = DecimalType::new_impl(10, 2);
+    let decimal_20_4 = DecimalType::new_impl(20, 4);
+
+    let tests = vec![
+        (
+            decimal_10_2.clone(),
+            decimal_20_4.clone(),
+            DecimalType::new_impl(20, 4),
+        ),
+        (
+            decimal_10_2.clone(),
+            Int32Type::new_impl(),
+            DecimalType::new_impl(12, 2),
+        ),
+        (
+            UInt64Type::new_impl(),
+            decimal_20_4.clone(),
+            DecimalType::new_impl(24, 4),
+        ),
+        (
+            decimal_10_2.clone(),
+            Float32Type::new_impl(),
+            Float64Type::new_impl(),
+        ),
+    ];
+
+    for (lhs, rhs, expect) in tests {
+        assert_eq!(decimal_coercion(&lhs, &rhs)?, expect);
+        assert_eq!(merge_types(&lhs, &rhs)?, expect);
+    }
+
+    let op = DataValueBinaryOperator::Mul;
+    assert_eq!(
+        decimal_arithmetic_coercion(&op, &decimal_10_2, &decimal_20_4)?,
+        DecimalType::new_impl(30, 6)
+    );
+    let op = DataValueBinaryOperator::Div;
+    assert_eq!(
+        decimal_arithmetic_coercion(&op, &decimal_10_2, &decimal_20_4)?,
+        DecimalType::new_impl(18, 6)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decimal_width() -> Result<()> {
+    assert_eq!(
+        try_create_decimal_type(38, 2)?,
+        DecimalType::new_impl(38, 2)
+    );
+    assert_eq!(
+        try_create_decimal_type(39, 2)?,
+        Decimal256Type::new_impl(39, 2)
+    );
+    assert_eq!(
+        try_create_decimal_type(76, 76)?,
+        Decimal256Type::new_impl(76, 76)
+    );
+    assert!(try_create_decimal_type(77, 0).is_err());
+    assert!(try_create_decimal_type(50, 51).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_i256_encoding() -> Result<()> {
+    let values = vec![
+        -I256::checked_pow10(70).unwrap(),
+        I256::from(i128::MIN),
+        I256::from(-1),
+        I256::zero(),
+        I256::from(1),
+        I256::from(i128::MAX),
+        I256::checked_pow10(40).unwrap(),
+    ];
+
+    for (i, v) in values.iter().enumerate() {
+        let bytes = v.to_bytes();
+        assert_eq!(I256::from_bytes(&bytes), *v);
+        if i > 0 {
+            assert!(values[i - 1].to_bytes() < bytes, "order of {}", v);
+        }
+    }
+
+    // the placeholders under the nulls
+    assert_eq!(I256::from_bytes(&[]), I256::zero());
+    Ok(())
+}
+
+#[test]
+fn test_decimal256_parse_and_format() -> Result<()> {
+    let decimal = Decimal256Type::try_create(50, 2)?;
+
+    let tests = vec![
+        ("0", "0.00"),
+        ("-0.005", "-0.01"),
+        (" +12.3e2 ", "1230.00"),
+        (
+            "123456789012345678901234567890123456789012345.675",
+            "123456789012345678901234567890123456789012345.68",
+        ),
+        (
+            "-999999999999999999999999999999999999999999999999.99",
+            "-999999999999999999999999999999999999999999999999.99",
+        ),
+    ];
+
+    for (input, output) in tests {
+        let v = decimal.parse_bytes(input.as_bytes())?;
+        assert_eq!(decimal.format_value(v), output, "format {}", input);
+    }
+
+    assert!(decimal.parse_bytes(b"abc").is_err());
+    assert!(
+        decimal
+            .parse_bytes(b"1000000000000000000000000000000000000000000000000")
+            .is_err()
+    );
+
+    let max = decimal.max_value();
+    assert!(decimal.check_value(max).is_ok());
+    assert!(
+        decimal
+            .check_value(max.checked_add(I256::from(1)).unwrap())
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decimal256_coercion() -> Result<()> {
+    let decimal_50_10 = Decimal256Type::new_impl(50, 10);
+    let decimal_20_4 = DecimalType::new_impl(20, 4);
+    let decimal_30_0 = DecimalType::new_impl(30, 0);
+
+    assert_eq!(
+        decimal_coercion(&decimal_50_10, &decimal_20_4)?,
+        Decimal256Type::new_impl(50, 10)
+    );
+    assert_eq!(
+        merge_types(&decimal_20_4, &decimal_50_10)?,
+        Decimal256Type::new_impl(50, 10)
+    );
+
+    // the 128-bit decimals are never widened implicitly
+    let op = DataValueBinaryOperator::Mul;
+    assert_eq!(
+        decimal_arithmetic_coercion(&op, &decimal_30_0, &decimal_30_0)?,
+        DecimalType::new_impl(38, 0)
+    );
+    assert_eq!(
+        decimal_arithmetic_coercion(&op, &decimal_50_10, &decimal_30_0)?,
+        Decimal256Type::new_impl(76, 10)
+    );
+    Ok(())
+}
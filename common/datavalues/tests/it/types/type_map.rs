@@ -0,0 +1,124 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+
+fn map_string_int64() -> DataTypeImpl {
+    MapType::new_impl(
+        StringType::new_impl(),
+        NullableType::new_impl(Int64Type::new_impl()),
+    )
+}
+
+#[test]
+fn test_map_type_factory() -> Result<()> {
+    let factory = TypeFactory::instance();
+
+    let data_type = factory.get("Map(String, Nullable(Int64))")?;
+    assert_eq!(data_type, map_string_int64());
+    assert_eq!(data_type.name(), "Map(String, Nullable(Int64))");
+
+    let data_type = factory.get("MAP(INT32, MAP(STRING, DECIMAL(10, 2)))")?;
+    assert_eq!(
+        data_type,
+        MapType::new_impl(
+            Int32Type::new_impl(),
+            MapType::new_impl(StringType::new_impl(), DecimalType::new_impl(10, 2)),
+        )
+    );
+
+    // keys can not be nullable or nested
+    assert!(factory.get("Map(Nullable(String), Int64)").is_err());
+    assert!(factory.get("Map(Array(Int32), Int64)").is_err());
+    assert!(factory.get("Map(String)").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_map_text_serde() -> Result<()> {
+    let data_type = map_string_int64();
+    let format = FormatSettings::default();
+
+    let mut deserializer = data_type.create_deserializer(2);
+    deserializer.de_whole_text(b"{'a':1, 'b':NULL}", &format)?;
+    deserializer.de_whole_text(b"{}", &format)?;
+    let column = deserializer.finish_to_column();
+
+    assert_eq!(column.len(), 2);
+    assert_eq!(column.data_type(), data_type);
+    assert_eq!(
+        column.get(0),
+        DataValue::Array(vec![
+            DataValue::Struct(vec![DataValue::String(b"a".to_vec()), DataValue::Int64(1)]),
+            DataValue::Struct(vec![DataValue::String(b"b".to_vec()), DataValue::Null]),
+        ])
+    );
+
+    let serializer = data_type.create_serializer(&column)?;
+    assert_eq!(serializer.serialize_field(0, &format)?, "{'a':1, 'b':NULL}");
+    assert_eq!(serializer.serialize_field(1, &format)?, "{}");
+
+    let values = serializer.serialize_json_values(&format)?;
+    assert_eq!(values[0], serde_json::json!({"a": 1, "b": null}));
+    assert_eq!(values[1], serde_json::json!({}));
+    Ok(())
+}
+
+#[test]
+fn test_map_arrow_roundtrip() -> Result<()> {
+    let data_type = map_string_int64();
+    let column = data_type.create_column(&[
+        DataValue::Array(vec![DataValue::Struct(vec![
+            DataValue::String(b"k".to_vec()),
+            DataValue::Int64(42),
+        ])]),
+        DataValue::Array(vec![]),
+    ])?;
+
+    let array = column.as_arrow_array(data_type.clone());
+    let got = array.into_column();
+    assert_eq!(got.data_type(), data_type);
+    assert_eq!(got.to_values(), column.to_values());
+
+    // the map type is kept after filtering
+    let filter = BooleanColumn::from_slice(&[false, true]);
+    let filtered = column.filter(&filter);
+    assert_eq!(filtered.data_type(), data_type);
+    assert_eq!(filtered.get(0), DataValue::Array(vec![]));
+    Ok(())
+}
+
+#[test]
+fn test_map_arrow_offsets_overflow() -> Result<()> {
+    let data_type = map_string_int64();
+    let column = data_type.create_column(&[DataValue::Array(vec![])])?;
+    let values = Series::check_get::<ArrayColumn>(&column)?.values().clone();
+
+    // the offsets beyond i32 can't be represented by the arrow map array
+    let offsets = vec![0i64, i32::MAX as i64 + 1];
+    let column = ArrayColumn::from_data(data_type.clone(), offsets.into(), values);
+    let res = column.try_as_arrow_array(data_type.clone());
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().code(), ErrorCode::overflow_code());
+
+    // the error is also returned for the nullable map column
+    let column = NullableColumn::wrap_inner(column.arc(), None);
+    let res = column.try_as_arrow_array(NullableType::new_impl(data_type));
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().code(), ErrorCode::overflow_code());
+    Ok(())
+}
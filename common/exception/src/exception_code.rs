@@ -159,6 +159,17 @@ build_exceptions! {
 
     TableInfoError(1106),
     ReadTableDataError(1107),
+
+    // Alter table column error codes.
+    ColumnAlreadyExists(1108),
+    ColumnReferenced(1109),
+
+    // Constraint error codes.
+    CheckConstraintViolated(1110),
+    GeneratedColumnNotWritable(1111),
+
+    // Transaction error codes.
+    TransactionAlreadyStarted(1112),
 }
 
 // Metasvr errors [2001, 3000].
@@ -197,6 +208,8 @@ build_exceptions! {
     DropDbWithDropTime(2315),
     UndropDbWithNoDropTime(2316),
     TxnRetryMaxTimes(2317),
+    TableIndexAlreadyExists(2318),
+    UnknownTableIndex(2319),
 
     // Cluster error codes.
     ClusterUnknownNode(2401),
@@ -24,9 +24,12 @@ use common_arrow::arrow::io::parquet::read;
 use common_arrow::arrow::io::parquet::read::read_columns_many;
 use common_arrow::arrow::io::parquet::read::ArrayIter;
 use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
+use common_arrow::from_parquet_arrow_schema;
+use common_arrow::from_parquet_chunk;
 use common_arrow::parquet::metadata::FileMetaData;
 use common_arrow::parquet::metadata::RowGroupMetaData;
 use common_arrow::parquet::read::read_metadata;
+use common_arrow::to_parquet_arrow_schema;
 use common_datablocks::DataBlock;
 use common_datavalues::remove_nullable;
 use common_datavalues::DataSchema;
@@ -130,7 +133,11 @@ impl InputFormat for ParquetInputFormat {
     fn deserialize_complete_split(&self, split: FileSplit) -> Result<Vec<DataBlock>> {
         let mut cursor = Cursor::new(&split.buf);
         let parquet_metadata = Self::read_meta_data(&mut cursor)?;
-        let infer_schema = read::infer_schema(&parquet_metadata)?;
+        // Decimals are written as fixed size binaries, see `to_parquet_arrow_schema`
+        let infer_schema = from_parquet_arrow_schema(
+            &read::infer_schema(&parquet_metadata)?,
+            &self.arrow_table_schema,
+        );
         let actually_schema = DataSchema::from(&infer_schema);
 
         if actually_schema.num_fields() != self.schema.num_fields() {
@@ -168,12 +175,13 @@ impl InputFormat for ParquetInputFormat {
             )));
         }
 
-        let fields = &self.arrow_table_schema.fields;
+        let fields = &to_parquet_arrow_schema(&self.arrow_table_schema).fields;
         let mut data_blocks = Vec::with_capacity(parquet_metadata.row_groups.len());
 
         for row_group in &parquet_metadata.row_groups {
             let arrays = Self::read_columns(fields, row_group, &mut cursor)?;
             let chunk = Self::deserialize(row_group.num_rows() as usize, arrays)?;
+            let chunk = from_parquet_chunk(chunk, &self.arrow_table_schema)?;
             data_blocks.push(DataBlock::from_chunk(&self.schema, &chunk)?);
         }
 
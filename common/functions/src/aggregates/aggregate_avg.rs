@@ -29,6 +29,7 @@ use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde::Serialize;
 
+use super::aggregate_decimal::AggregateDecimalFunction;
 use super::aggregate_sum::sum_primitive;
 use super::StateAddr;
 use crate::aggregates::aggregate_function_factory::AggregateFunctionDescription;
@@ -226,6 +227,10 @@ pub fn try_create_aggregate_avg_function(
         phid = TypeID::UInt8;
     }
 
+    if phid.is_decimal() {
+        return AggregateDecimalFunction::try_create(display_name, arguments, true);
+    }
+
     with_match_primitive_type_id!(phid, |$T| {
         AggregateAvgFunction::<$T, <$T as PrimitiveType>::LargestType>::try_create(
             display_name,
@@ -0,0 +1,213 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::alloc::Layout;
+use std::fmt;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_io::prelude::*;
+
+use super::aggregate_function::AggregateFunction;
+use super::aggregate_function::AggregateFunctionRef;
+use super::StateAddr;
+
+struct AggregateDecimalState {
+    pub value: I256,
+    pub count: u64,
+}
+
+/// Sum or average of decimals of both widths.
+///
+/// The unscaled values are accumulated with checked 256-bit operations, so that
+/// an overflow is reported as an error instead of wrapping around silently.
+#[derive(Clone)]
+pub struct AggregateDecimalFunction {
+    display_name: String,
+    is_avg: bool,
+    /// The input is a `Decimal256` column, which is physically a string column
+    wide: bool,
+    input_scale: usize,
+    return_type: DataTypeImpl,
+    result: Decimal256Type,
+}
+
+impl AggregateDecimalFunction {
+    /// The sum keeps the scale of the argument, the average has 4 more digits after
+    /// the decimal point. Both have the max precision of the width of the argument.
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        is_avg: bool,
+    ) -> Result<AggregateFunctionRef> {
+        let data_type = arguments[0].data_type();
+        let (_, scale) = decimal_precision_scale(data_type).ok_or_else(|| {
+            ErrorCode::BadDataValueType(format!(
+                "AggregateDecimalFunction does not support type '{:?}'",
+                data_type
+            ))
+        })?;
+
+        let wide = data_type.data_type_id() == TypeID::Decimal256;
+        let max_precision = if wide {
+            MAX_DECIMAL256_PRECISION
+        } else {
+            MAX_DECIMAL_PRECISION
+        };
+        let result_scale = if is_avg {
+            std::cmp::min(scale + 4, max_precision)
+        } else {
+            scale
+        };
+
+        Ok(Arc::new(Self {
+            display_name: display_name.to_string(),
+            is_avg,
+            wide,
+            input_scale: scale,
+            return_type: create_decimal_type(max_precision, result_scale),
+            result: Decimal256Type::create(max_precision, result_scale),
+        }))
+    }
+
+    fn overflow(&self) -> ErrorCode {
+        ErrorCode::Overflow(format!(
+            "Decimal overflow in {}, the result exceeds the range of {:?}",
+            self.display_name, self.return_type
+        ))
+    }
+
+    #[inline]
+    unsafe fn value_unchecked(&self, column: &ColumnRef, row: usize) -> I256 {
+        if self.wide {
+            let column: &StringColumn = Series::static_cast(column);
+            I256::from_bytes(column.value_unchecked(row))
+        } else {
+            let column: &PrimitiveColumn<i128> = Series::static_cast(column);
+            I256::from(column.value_unchecked(row))
+        }
+    }
+
+    #[inline]
+    fn add(&self, state: &mut AggregateDecimalState, value: I256, count: u64) -> Result<()> {
+        state.value = state
+            .value
+            .checked_add(value)
+            .ok_or_else(|| self.overflow())?;
+        state.count += count;
+        Ok(())
+    }
+}
+
+impl AggregateFunction for AggregateDecimalFunction {
+    fn name(&self) -> &str {
+        "AggregateDecimalFunction"
+    }
+
+    fn return_type(&self) -> Result<DataTypeImpl> {
+        Ok(self.return_type.clone())
+    }
+
+    fn init_state(&self, place: StateAddr) {
+        place.write(|| AggregateDecimalState {
+            value: I256::zero(),
+            count: 0,
+        });
+    }
+
+    fn state_layout(&self) -> Layout {
+        Layout::new::<AggregateDecimalState>()
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[ColumnRef],
+        validity: Option<&Bitmap>,
+        input_rows: usize,
+    ) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        for row in 0..input_rows {
+            if validity.map(|v| v.get_bit(row)).unwrap_or(true) {
+                let value = unsafe { self.value_unchecked(&columns[0], row) };
+                self.add(state, value, 1)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, columns: &[ColumnRef], row: usize) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        let value = unsafe { self.value_unchecked(&columns[0], row) };
+        self.add(state, value, 1)
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut BytesMut) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        serialize_into_buf(writer, &(state.value.to_bytes(), state.count))
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &mut &[u8]) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        let (value, count): ([u8; I256::BYTE_SIZE], u64) = deserialize_from_slice(reader)?;
+        state.value = I256::from_bytes(&value);
+        state.count = count;
+        Ok(())
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let rhs = rhs.get::<AggregateDecimalState>();
+        let state = place.get::<AggregateDecimalState>();
+        self.add(state, rhs.value, rhs.count)
+    }
+
+    fn merge_result(&self, place: StateAddr, array: &mut dyn MutableColumn) -> Result<()> {
+        let state = place.get::<AggregateDecimalState>();
+        let value = if self.is_avg {
+            let count = I256::from(std::cmp::max(state.count, 1) as i128);
+            let scale = self.result.scale();
+            // Keep one more digit to round the average
+            Decimal256Type::rescale(state.value, self.input_scale, scale + 1)
+                .and_then(|v| v.checked_div(count))
+                .and_then(|v| Decimal256Type::rescale(v, scale + 1, scale))
+                .ok_or_else(|| self.overflow())?
+        } else {
+            state.value
+        };
+        let value = self
+            .result
+            .check_value(value)
+            .map_err(|_| self.overflow())?;
+
+        if self.wide {
+            let builder: &mut MutableStringColumn = Series::check_get_mutable_column(array)?;
+            builder.append_value(value.to_bytes());
+        } else {
+            let builder: &mut MutablePrimitiveColumn<i128> =
+                Series::check_get_mutable_column(array)?;
+            builder.append_value(value.to_i128().ok_or_else(|| self.overflow())?);
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AggregateDecimalFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
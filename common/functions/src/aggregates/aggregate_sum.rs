@@ -28,6 +28,7 @@ use num::traits::AsPrimitive;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use super::aggregate_decimal::AggregateDecimalFunction;
 use super::aggregate_function::AggregateFunction;
 use super::aggregate_function::AggregateFunctionRef;
 use super::aggregate_function_factory::AggregateFunctionDescription;
@@ -222,6 +223,10 @@ pub fn try_create_aggregate_sum_function(
         phid = TypeID::UInt8;
     }
 
+    if phid.is_decimal() {
+        return AggregateDecimalFunction::try_create(display_name, arguments, false);
+    }
+
     with_match_primitive_type_id!(phid, |$T| {
         AggregateSumFunction::<$T, <$T as PrimitiveType>::LargestType>::try_create(
              display_name,
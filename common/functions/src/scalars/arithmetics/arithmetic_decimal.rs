@@ -0,0 +1,361 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_datavalues::type_coercion::decimal_arithmetic_coercion;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::cast_column_field;
+use crate::scalars::scalar_binary_op;
+use crate::scalars::ArithmeticDivFunction;
+use crate::scalars::ArithmeticMinusFunction;
+use crate::scalars::ArithmeticMulFunction;
+use crate::scalars::ArithmeticPlusFunction;
+use crate::scalars::EvalContext;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::Monotonicity;
+
+/// Binary arithmetic on decimals, one of the arguments is a decimal
+/// and the other one is a decimal or a number.
+///
+/// Both arguments are casted to decimals before the calculation, the unscaled values
+/// are calculated with checked i128 operations, so the result is exact or an overflow error.
+/// If one of the arguments or the result is a `Decimal256`, the calculation is done with
+/// checked `I256` operations instead.
+/// If one of the arguments is a float, the calculation is done on Float64 instead.
+#[derive(Clone)]
+pub struct DecimalArithmeticFunction {
+    op: DataValueBinaryOperator,
+    left_type: DataTypeImpl,
+    right_type: DataTypeImpl,
+    result_type: DataTypeImpl,
+}
+
+impl DecimalArithmeticFunction {
+    pub fn try_create_func(
+        op: DataValueBinaryOperator,
+        args: &[&DataTypeImpl],
+    ) -> Result<Box<dyn Function>> {
+        let result_type = decimal_arithmetic_coercion(&op, args[0], args[1])?;
+
+        let (left_type, right_type) = if result_type.data_type_id().is_floating() {
+            (result_type.clone(), result_type.clone())
+        } else {
+            let wide = [args[0], args[1], &result_type]
+                .iter()
+                .any(|t| t.data_type_id() == TypeID::Decimal256);
+            let new_decimal = |scale| {
+                if wide {
+                    Decimal256Type::new_impl(MAX_DECIMAL256_PRECISION, scale)
+                } else {
+                    DecimalType::new_impl(MAX_DECIMAL_PRECISION, scale)
+                }
+            };
+
+            let left_scale = Self::scale_of(args[0]);
+            let right_scale = Self::scale_of(args[1]);
+            match op {
+                DataValueBinaryOperator::Mul | DataValueBinaryOperator::Div => {
+                    (new_decimal(left_scale), new_decimal(right_scale))
+                }
+                _ => {
+                    let scale = left_scale.max(right_scale);
+                    (new_decimal(scale), new_decimal(scale))
+                }
+            }
+        };
+
+        Ok(Box::new(Self {
+            op,
+            left_type,
+            right_type,
+            result_type,
+        }))
+    }
+
+    fn scale_of(data_type: &DataTypeImpl) -> usize {
+        decimal_precision_scale(data_type).map_or(0, |(_, scale)| scale)
+    }
+
+    fn overflow(&self) -> ErrorCode {
+        ErrorCode::Overflow(format!(
+            "Decimal overflow: the result of ({:?}) {} ({:?}) exceeds the range of {:?}",
+            self.left_type, self.op, self.right_type, self.result_type
+        ))
+    }
+
+    fn eval_float(&self, lhs: &ColumnRef, rhs: &ColumnRef) -> Result<ColumnRef> {
+        let mut ctx = EvalContext::default();
+        let col = match self.op {
+            DataValueBinaryOperator::Plus => {
+                scalar_binary_op::<f64, f64, f64, _>(lhs, rhs, |l, r, _| l + r, &mut ctx)
+            }
+            DataValueBinaryOperator::Minus => {
+                scalar_binary_op::<f64, f64, f64, _>(lhs, rhs, |l, r, _| l - r, &mut ctx)
+            }
+            DataValueBinaryOperator::Mul => {
+                scalar_binary_op::<f64, f64, f64, _>(lhs, rhs, |l, r, _| l * r, &mut ctx)
+            }
+            DataValueBinaryOperator::Div => {
+                scalar_binary_op::<f64, f64, f64, _>(lhs, rhs, |l, r, _| l / r, &mut ctx)
+            }
+            DataValueBinaryOperator::Modulo => {
+                scalar_binary_op::<f64, f64, f64, _>(lhs, rhs, |l, r, _| l % r, &mut ctx)
+            }
+            DataValueBinaryOperator::IntDiv => unreachable!(),
+        }?;
+        Ok(Arc::new(col))
+    }
+
+    fn eval_decimal(&self, lhs: &ColumnRef, rhs: &ColumnRef) -> Result<ColumnRef> {
+        let left_scale = Self::scale_of(&self.left_type);
+        let right_scale = Self::scale_of(&self.right_type);
+        let mut ctx = EvalContext::default();
+
+        if matches!(self.op, DataValueBinaryOperator::IntDiv) {
+            let col = scalar_binary_op::<i128, i128, i64, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| {
+                    if r == 0 {
+                        ctx.set_error(ErrorCode::BadArguments("Division by zero"));
+                        return 0;
+                    }
+                    i64::try_from(l / r).unwrap_or_else(|_| {
+                        ctx.set_error(self.overflow());
+                        0
+                    })
+                },
+                &mut ctx,
+            )?;
+            return Ok(Arc::new(col));
+        }
+
+        let result: DecimalType = self.result_type.to_owned().try_into()?;
+        let result_scale = result.scale();
+        let check = |value: Option<i128>, ctx: &mut EvalContext| -> i128 {
+            match value.and_then(|v| result.check_value(v).ok()) {
+                Some(v) => v,
+                None => {
+                    ctx.set_error(self.overflow());
+                    0
+                }
+            }
+        };
+
+        let col = match self.op {
+            DataValueBinaryOperator::Plus => scalar_binary_op::<i128, i128, i128, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| check(l.checked_add(r), ctx),
+                &mut ctx,
+            ),
+            DataValueBinaryOperator::Minus => scalar_binary_op::<i128, i128, i128, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| check(l.checked_sub(r), ctx),
+                &mut ctx,
+            ),
+            DataValueBinaryOperator::Mul => scalar_binary_op::<i128, i128, i128, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| {
+                    let value = l.checked_mul(r).and_then(|v| {
+                        DecimalType::rescale(v, left_scale + right_scale, result_scale)
+                    });
+                    check(value, ctx)
+                },
+                &mut ctx,
+            ),
+            DataValueBinaryOperator::Div => scalar_binary_op::<i128, i128, i128, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| {
+                    if r == 0 {
+                        ctx.set_error(ErrorCode::BadArguments("Division by zero"));
+                        return 0;
+                    }
+                    // l / r has the scale `left_scale - right_scale`, keep one more digit to round
+                    let value = DecimalType::rescale(l, left_scale, result_scale + right_scale + 1)
+                        .map(|l| l / r)
+                        .and_then(|v| DecimalType::rescale(v, result_scale + 1, result_scale));
+                    check(value, ctx)
+                },
+                &mut ctx,
+            ),
+            DataValueBinaryOperator::Modulo => scalar_binary_op::<i128, i128, i128, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| {
+                    if r == 0 {
+                        ctx.set_error(ErrorCode::BadArguments("Division by zero"));
+                        return 0;
+                    }
+                    check(l.checked_rem(r), ctx)
+                },
+                &mut ctx,
+            ),
+            DataValueBinaryOperator::IntDiv => unreachable!(),
+        }?;
+        Ok(Arc::new(col))
+    }
+
+    fn eval_decimal256(&self, lhs: &ColumnRef, rhs: &ColumnRef) -> Result<ColumnRef> {
+        let left_scale = Self::scale_of(&self.left_type);
+        let right_scale = Self::scale_of(&self.right_type);
+        let mut ctx = EvalContext::default();
+
+        if matches!(self.op, DataValueBinaryOperator::IntDiv) {
+            let col = scalar_binary_op::<Vu8, Vu8, i64, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| {
+                    let (l, r) = (I256::from_bytes(l), I256::from_bytes(r));
+                    if r.is_zero() {
+                        ctx.set_error(ErrorCode::BadArguments("Division by zero"));
+                        return 0;
+                    }
+                    l.checked_div(r)
+                        .and_then(|v| v.to_i128())
+                        .and_then(|v| i64::try_from(v).ok())
+                        .unwrap_or_else(|| {
+                            ctx.set_error(self.overflow());
+                            0
+                        })
+                },
+                &mut ctx,
+            )?;
+            return Ok(Arc::new(col));
+        }
+
+        // The result may be a 128-bit decimal, e.g. the modulo of a Decimal256 and a small decimal
+        let (precision, result_scale) =
+            decimal_precision_scale(&self.result_type).ok_or_else(|| self.overflow())?;
+        let result = Decimal256Type::create(precision, result_scale);
+        let calc = |l: &[u8], r: &[u8], ctx: &mut EvalContext| -> I256 {
+            let (l, r) = (I256::from_bytes(l), I256::from_bytes(r));
+            let value = match self.op {
+                DataValueBinaryOperator::Plus => l.checked_add(r),
+                DataValueBinaryOperator::Minus => l.checked_sub(r),
+                DataValueBinaryOperator::Mul => l.checked_mul(r).and_then(|v| {
+                    Decimal256Type::rescale(v, left_scale + right_scale, result_scale)
+                }),
+                DataValueBinaryOperator::Div | DataValueBinaryOperator::Modulo if r.is_zero() => {
+                    ctx.set_error(ErrorCode::BadArguments("Division by zero"));
+                    return I256::zero();
+                }
+                DataValueBinaryOperator::Div => {
+                    // l / r has the scale `left_scale - right_scale`, keep one more digit to round
+                    Decimal256Type::rescale(l, left_scale, result_scale + right_scale + 1)
+                        .and_then(|l| l.checked_div(r))
+                        .and_then(|v| Decimal256Type::rescale(v, result_scale + 1, result_scale))
+                }
+                DataValueBinaryOperator::Modulo => l.checked_rem(r),
+                DataValueBinaryOperator::IntDiv => unreachable!(),
+            };
+            match value.and_then(|v| result.check_value(v).ok()) {
+                Some(v) => v,
+                None => {
+                    ctx.set_error(self.overflow());
+                    I256::zero()
+                }
+            }
+        };
+
+        if self.result_type.data_type_id() == TypeID::Decimal256 {
+            let col = scalar_binary_op::<Vu8, Vu8, Vu8, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| calc(l, r, ctx).to_bytes().to_vec(),
+                &mut ctx,
+            )?;
+            Ok(Arc::new(col))
+        } else {
+            // The value is checked by the precision of result, so it always fits in i128
+            let col = scalar_binary_op::<Vu8, Vu8, i128, _>(
+                lhs,
+                rhs,
+                |l, r, ctx| calc(l, r, ctx).to_i128().unwrap_or_default(),
+                &mut ctx,
+            )?;
+            Ok(Arc::new(col))
+        }
+    }
+}
+
+impl Function for DecimalArithmeticFunction {
+    fn name(&self) -> &str {
+        "DecimalArithmeticFunction"
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        self.result_type.clone()
+    }
+
+    fn eval(
+        &self,
+        func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        _input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let lhs = cast_column_field(
+            &columns[0],
+            columns[0].data_type(),
+            &self.left_type,
+            &func_ctx,
+        )?;
+        let rhs = cast_column_field(
+            &columns[1],
+            columns[1].data_type(),
+            &self.right_type,
+            &func_ctx,
+        )?;
+
+        if self.left_type.data_type_id().is_floating() {
+            self.eval_float(&lhs, &rhs)
+        } else if self.left_type.data_type_id() == TypeID::Decimal256 {
+            self.eval_decimal256(&lhs, &rhs)
+        } else {
+            self.eval_decimal(&lhs, &rhs)
+        }
+    }
+
+    fn get_monotonicity(&self, args: &[Monotonicity]) -> Result<Monotonicity> {
+        if args.len() != 2 {
+            return Err(ErrorCode::BadArguments(format!(
+                "Invalid argument lengths {} for get_monotonicity",
+                args.len()
+            )));
+        }
+
+        match self.op {
+            DataValueBinaryOperator::Plus => ArithmeticPlusFunction::get_monotonicity(args),
+            DataValueBinaryOperator::Minus => ArithmeticMinusFunction::get_monotonicity(args),
+            DataValueBinaryOperator::Mul => ArithmeticMulFunction::get_monotonicity(args),
+            DataValueBinaryOperator::Div => ArithmeticDivFunction::get_monotonicity(args),
+            _ => Ok(Monotonicity::default()),
+        }
+    }
+}
+
+impl fmt::Display for DecimalArithmeticFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.op)
+    }
+}
@@ -24,6 +24,7 @@ use num::traits::AsPrimitive;
 use num_traits::WrappingSub;
 
 use crate::scalars::BinaryArithmeticFunction;
+use crate::scalars::DecimalArithmeticFunction;
 use crate::scalars::EvalContext;
 use crate::scalars::Function;
 use crate::scalars::FunctionDescription;
@@ -57,6 +58,11 @@ impl ArithmeticMinusFunction {
         args: &[&DataTypeImpl],
     ) -> Result<Box<dyn Function>> {
         let op = DataValueBinaryOperator::Minus;
+
+        if args[0].data_type_id().is_decimal() || args[1].data_type_id().is_decimal() {
+            return DecimalArithmeticFunction::try_create_func(op, args);
+        }
+
         let left_type = args[0].data_type_id();
         let right_type = args[1].data_type_id();
 
@@ -38,6 +38,10 @@ where O: IntegerType + WrappingNeg {
     l.as_().wrapping_neg()
 }
 
+fn neg_decimal256(l: &[u8], _ctx: &mut EvalContext) -> Vec<u8> {
+    (-I256::from_bytes(l)).to_bytes().to_vec()
+}
+
 pub struct ArithmeticNegateFunction;
 
 impl ArithmeticNegateFunction {
@@ -47,6 +51,22 @@ impl ArithmeticNegateFunction {
     ) -> Result<Box<dyn Function>> {
         let op = DataValueUnaryOperator::Negate;
 
+        // The negation of a decimal always fits in the same precision and scale
+        if args[0].data_type_id() == TypeID::Decimal256 {
+            return UnaryArithmeticFunction::<Vu8, Vu8, _>::try_create_func(
+                op,
+                args[0].clone(),
+                neg_decimal256,
+            );
+        }
+        if args[0].data_type_id().is_decimal() {
+            return UnaryArithmeticFunction::<i128, i128, _>::try_create_func(
+                op,
+                args[0].clone(),
+                neg,
+            );
+        }
+
         with_match_primitive_types_error!(args[0].data_type_id(), |$T| {
             let result_type = <$T as ResultTypeOfUnary>::Negate::to_data_type();
             match result_type.data_type_id() {
@@ -141,6 +141,28 @@ impl<T: ComparisonImpl> ComparisonFunctionCreator<T> {
             return ComparisonFunction::try_create_func(display_name, func);
         }
 
+        // Decimals are compared by the unscaled values after casting to the same scale
+        if lhs_id.is_decimal() || rhs_id.is_decimal() {
+            let least_supertype = compare_coercion(args[0], args[1])?;
+            if least_supertype.data_type_id() == TypeID::Decimal256 {
+                // The encoded 256-bit values have the same order as the numbers
+                let func = Arc::new(ComparisonScalarImpl::<Vu8, Vu8, _>::new(
+                    least_supertype,
+                    true,
+                    T::eval_binary,
+                ));
+                return ComparisonFunction::try_create_func(display_name, func);
+            }
+            if least_supertype.data_type_id().is_decimal() {
+                let func = Arc::new(ComparisonScalarImpl::<i128, i128, _>::new(
+                    least_supertype,
+                    true,
+                    T::eval_primitive::<i128, i128, i128>,
+                ));
+                return ComparisonFunction::try_create_func(display_name, func);
+            }
+        }
+
         if args[0].eq(args[1]) {
             return with_match_physical_primitive_type!(lhs_id.to_physical_type(), |$T| {
                 let func = Arc::new(ComparisonPrimitiveImpl::<$T, _>::new(args[0].clone(), false, T::eval_simd::<$T>));
@@ -0,0 +1,255 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datavalues::prelude::*;
+use common_datavalues::with_match_primitive_type_id;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use num::cast::AsPrimitive;
+use num::NumCast;
+use serde_json::json;
+
+pub fn cast_from_decimal(
+    column: &ColumnRef,
+    from_type: &DataTypeImpl,
+    data_type: &DataTypeImpl,
+) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let from: DecimalType = from_type.to_owned().try_into()?;
+    let c = Series::remove_nullable(column);
+    let c: &PrimitiveColumn<i128> = Series::check_get(&c)?;
+    let size = c.len();
+
+    let error = || {
+        ErrorCode::BadDataValueType(format!(
+            "Cast error happens in casting from {} to {}",
+            from_type.name(),
+            data_type.name()
+        ))
+    };
+
+    with_match_primitive_type_id!(data_type.data_type_id(), |$T| {
+        let mut builder = ColumnBuilder::<$T>::with_capacity(size);
+        for v in c.iter() {
+            let value: $T = if <$T as PrimitiveType>::FLOATING {
+                <$T as NumCast>::from(from.to_f64(*v))
+            } else {
+                DecimalType::rescale(*v, from.scale(), 0).and_then(|v| <$T as NumCast>::from(v))
+            }
+            .ok_or_else(error)?;
+            builder.append(value);
+        }
+        Ok((builder.build(size), None))
+    }, {
+        match data_type.data_type_id() {
+            TypeID::Decimal => {
+                let to: DecimalType = data_type.to_owned().try_into()?;
+                let mut builder = ColumnBuilder::<i128>::with_capacity(size);
+                for v in c.iter() {
+                    let value = DecimalType::rescale(*v, from.scale(), to.scale()).ok_or_else(error)?;
+                    builder.append(to.check_value(value)?);
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::Decimal256 => {
+                let to: Decimal256Type = data_type.to_owned().try_into()?;
+                let mut builder = ColumnBuilder::<Vu8>::with_capacity(size);
+                for v in c.iter() {
+                    let value = Decimal256Type::rescale(I256::from(*v), from.scale(), to.scale())
+                        .ok_or_else(error)?;
+                    builder.append(&to.check_value(value)?.to_bytes());
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::Boolean => {
+                let mut builder = ColumnBuilder::<bool>::with_capacity(size);
+                for v in c.iter() {
+                    builder.append(*v != 0);
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::String => {
+                let mut builder = ColumnBuilder::<Vu8>::with_capacity(size);
+                for v in c.iter() {
+                    builder.append(from.format_value(*v).as_bytes());
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::Variant => {
+                let mut builder = ColumnBuilder::<VariantValue>::with_capacity(size);
+                for v in c.iter() {
+                    builder.append(&VariantValue::from(json!(from.to_f64(*v))));
+                }
+                Ok((builder.build(size), None))
+            }
+            _ => Err(error()),
+        }
+    })
+}
+
+pub fn cast_to_decimal(
+    column: &ColumnRef,
+    from_type: &DataTypeImpl,
+    data_type: &DataTypeImpl,
+) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let to: DecimalType = data_type.to_owned().try_into()?;
+    let column = Series::remove_nullable(column);
+    let size = column.len();
+    let mut builder = ColumnBuilder::<i128>::with_capacity(size);
+
+    with_match_primitive_type_id!(from_type.data_type_id(), |$T| {
+        let col: &PrimitiveColumn<$T> = Series::check_get(&column)?;
+        for v in col.iter() {
+            let value = if <$T as PrimitiveType>::FLOATING {
+                to.from_f64(AsPrimitive::<f64>::as_(*v))?
+            } else {
+                to.from_i128(AsPrimitive::<i128>::as_(*v))?
+            };
+            builder.append(value);
+        }
+    }, {
+        if from_type.data_type_id() == TypeID::Boolean {
+            let col: &BooleanColumn = Series::check_get(&column)?;
+            for v in col.iter() {
+                builder.append(to.from_i128(v as i128)?);
+            }
+        } else {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Cast error happens in casting from {} to {}",
+                from_type.name(),
+                data_type.name()
+            )));
+        }
+    });
+    Ok((builder.build(size), None))
+}
+
+pub fn cast_from_decimal256(
+    column: &ColumnRef,
+    from_type: &DataTypeImpl,
+    data_type: &DataTypeImpl,
+) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let from: Decimal256Type = from_type.to_owned().try_into()?;
+    let c = Series::remove_nullable(column);
+    let c: &StringColumn = Series::check_get(&c)?;
+    let size = c.len();
+
+    let error = || {
+        ErrorCode::BadDataValueType(format!(
+            "Cast error happens in casting from {} to {}",
+            from_type.name(),
+            data_type.name()
+        ))
+    };
+
+    with_match_primitive_type_id!(data_type.data_type_id(), |$T| {
+        let mut builder = ColumnBuilder::<$T>::with_capacity(size);
+        for v in c.iter() {
+            let v = I256::from_bytes(v);
+            let value: $T = if <$T as PrimitiveType>::FLOATING {
+                <$T as NumCast>::from(from.to_f64(v))
+            } else {
+                Decimal256Type::rescale(v, from.scale(), 0)
+                    .and_then(|v| v.to_i128())
+                    .and_then(|v| <$T as NumCast>::from(v))
+            }
+            .ok_or_else(error)?;
+            builder.append(value);
+        }
+        Ok((builder.build(size), None))
+    }, {
+        match data_type.data_type_id() {
+            TypeID::Decimal => {
+                let to: DecimalType = data_type.to_owned().try_into()?;
+                let mut builder = ColumnBuilder::<i128>::with_capacity(size);
+                for v in c.iter() {
+                    let value = Decimal256Type::rescale(I256::from_bytes(v), from.scale(), to.scale())
+                        .and_then(|v| v.to_i128())
+                        .ok_or_else(error)?;
+                    builder.append(to.check_value(value)?);
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::Decimal256 => {
+                let to: Decimal256Type = data_type.to_owned().try_into()?;
+                let mut builder = ColumnBuilder::<Vu8>::with_capacity(size);
+                for v in c.iter() {
+                    let value = Decimal256Type::rescale(I256::from_bytes(v), from.scale(), to.scale())
+                        .ok_or_else(error)?;
+                    builder.append(&to.check_value(value)?.to_bytes());
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::Boolean => {
+                let mut builder = ColumnBuilder::<bool>::with_capacity(size);
+                for v in c.iter() {
+                    builder.append(!I256::from_bytes(v).is_zero());
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::String => {
+                let mut builder = ColumnBuilder::<Vu8>::with_capacity(size);
+                for v in c.iter() {
+                    builder.append(from.format_value(I256::from_bytes(v)).as_bytes());
+                }
+                Ok((builder.build(size), None))
+            }
+            TypeID::Variant => {
+                let mut builder = ColumnBuilder::<VariantValue>::with_capacity(size);
+                for v in c.iter() {
+                    builder.append(&VariantValue::from(json!(from.to_f64(I256::from_bytes(v)))));
+                }
+                Ok((builder.build(size), None))
+            }
+            _ => Err(error()),
+        }
+    })
+}
+
+pub fn cast_to_decimal256(
+    column: &ColumnRef,
+    from_type: &DataTypeImpl,
+    data_type: &DataTypeImpl,
+) -> Result<(ColumnRef, Option<Bitmap>)> {
+    let to: Decimal256Type = data_type.to_owned().try_into()?;
+    let column = Series::remove_nullable(column);
+    let size = column.len();
+    let mut builder = ColumnBuilder::<Vu8>::with_capacity(size);
+
+    with_match_primitive_type_id!(from_type.data_type_id(), |$T| {
+        let col: &PrimitiveColumn<$T> = Series::check_get(&column)?;
+        for v in col.iter() {
+            let value = if <$T as PrimitiveType>::FLOATING {
+                to.from_f64(AsPrimitive::<f64>::as_(*v))?
+            } else {
+                to.from_i128(AsPrimitive::<i128>::as_(*v))?
+            };
+            builder.append(&value.to_bytes());
+        }
+    }, {
+        if from_type.data_type_id() == TypeID::Boolean {
+            let col: &BooleanColumn = Series::check_get(&column)?;
+            for v in col.iter() {
+                builder.append(&to.from_i128(v as i128)?.to_bytes());
+            }
+        } else {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Cast error happens in casting from {} to {}",
+                from_type.name(),
+                data_type.name()
+            )));
+        }
+    });
+    Ok((builder.build(size), None))
+}
@@ -92,6 +92,38 @@ pub fn cast_from_string(
                 Some(nullable_column.ensure_validity().clone()),
             ))
         }
+        TypeID::Decimal => {
+            let decimal: DecimalType = data_type.to_owned().try_into()?;
+            let mut builder = NullableColumnBuilder::<i128>::with_capacity(size);
+            for v in str_column.iter() {
+                match decimal.parse_bytes(v) {
+                    Ok(d) => builder.append(d, true),
+                    Err(_) => builder.append_null(),
+                }
+            }
+            let column = builder.build(size);
+            let nullable_column: &NullableColumn = Series::check_get(&column)?;
+            Ok((
+                nullable_column.inner().clone(),
+                Some(nullable_column.ensure_validity().clone()),
+            ))
+        }
+        TypeID::Decimal256 => {
+            let decimal: Decimal256Type = data_type.to_owned().try_into()?;
+            let mut builder = NullableColumnBuilder::<Vu8>::with_capacity(size);
+            for v in str_column.iter() {
+                match decimal.parse_bytes(v) {
+                    Ok(d) => builder.append(&d.to_bytes(), true),
+                    Err(_) => builder.append_null(),
+                }
+            }
+            let column = builder.build(size);
+            let nullable_column: &NullableColumn = Series::check_get(&column)?;
+            Ok((
+                nullable_column.inner().clone(),
+                Some(nullable_column.ensure_validity().clone()),
+            ))
+        }
         TypeID::Interval => todo!(),
         _ => arrow_cast_compute(column, from_type, data_type, cast_options, func_ctx),
     }
@@ -123,6 +123,52 @@ pub fn cast_from_variant(
                     Some(nullable_column.ensure_validity().clone()),
                 ))
             }
+            TypeID::Decimal => {
+                let decimal: DecimalType = data_type.to_owned().try_into()?;
+                let mut builder = NullableColumnBuilder::<i128>::with_capacity(size);
+
+                for value in variant_column.iter() {
+                    let parsed = match value.as_ref() {
+                        JsonValue::Bool(v) => decimal.from_i128(*v as i128).ok(),
+                        JsonValue::Number(v) => decimal.parse_bytes(v.to_string().as_bytes()).ok(),
+                        JsonValue::String(v) => decimal.parse_bytes(v.as_bytes()).ok(),
+                        _ => None,
+                    };
+                    match parsed {
+                        Some(v) => builder.append(v, true),
+                        None => builder.append_null(),
+                    }
+                }
+                let column = builder.build(size);
+                let nullable_column: &NullableColumn = Series::check_get(&column)?;
+                Ok((
+                    nullable_column.inner().clone(),
+                    Some(nullable_column.ensure_validity().clone()),
+                ))
+            }
+            TypeID::Decimal256 => {
+                let decimal: Decimal256Type = data_type.to_owned().try_into()?;
+                let mut builder = NullableColumnBuilder::<Vu8>::with_capacity(size);
+
+                for value in variant_column.iter() {
+                    let parsed = match value.as_ref() {
+                        JsonValue::Bool(v) => decimal.from_i128(*v as i128).ok(),
+                        JsonValue::Number(v) => decimal.parse_bytes(v.to_string().as_bytes()).ok(),
+                        JsonValue::String(v) => decimal.parse_bytes(v.as_bytes()).ok(),
+                        _ => None,
+                    };
+                    match parsed {
+                        Some(v) => builder.append(&v.to_bytes(), true),
+                        None => builder.append_null(),
+                    }
+                }
+                let column = builder.build(size);
+                let nullable_column: &NullableColumn = Series::check_get(&column)?;
+                Ok((
+                    nullable_column.inner().clone(),
+                    Some(nullable_column.ensure_validity().clone()),
+                ))
+            }
             TypeID::Date => {
                 let mut builder = NullableColumnBuilder::<i32>::with_capacity(size);
 
@@ -26,6 +26,10 @@ use common_exception::Result;
 use common_io::prelude::FormatSettings;
 
 use super::cast_from_datetimes::cast_from_date;
+use super::cast_from_decimal::cast_from_decimal;
+use super::cast_from_decimal::cast_from_decimal256;
+use super::cast_from_decimal::cast_to_decimal;
+use super::cast_from_decimal::cast_to_decimal256;
 use super::cast_from_string::cast_from_string;
 use super::cast_from_variant::cast_from_variant;
 use crate::scalars::expressions::cast_from_datetimes::cast_from_timestamp;
@@ -101,7 +105,12 @@ pub fn cast_with_type(
     func_ctx: &FunctionContext,
 ) -> Result<ColumnRef> {
     // they are pyhsically the same type
-    if &column.data_type() == target_type {
+    if &column.data_type() == target_type && !target_type.data_type_id().is_decimal() {
+        return Ok(column.clone());
+    }
+
+    // decimals of all precisions and scales are physically the same type
+    if from_type == target_type && from_type.data_type_id().is_decimal() {
         return Ok(column.clone());
     }
 
@@ -154,6 +163,8 @@ pub fn cast_with_type(
         TypeID::Variant | TypeID::VariantArray | TypeID::VariantObject => {
             cast_from_variant(column, &nonull_data_type, func_ctx)
         }
+        TypeID::Decimal => cast_from_decimal(column, &nonull_from_type, &nonull_data_type),
+        TypeID::Decimal256 => cast_from_decimal256(column, &nonull_from_type, &nonull_data_type),
         _ => arrow_cast_compute(
             column,
             &nonull_from_type,
@@ -313,6 +324,10 @@ pub fn arrow_cast_compute(
         return cast_to_variant(column, from_type, data_type, func_ctx);
     } else if data_type.data_type_id() == TypeID::Timestamp {
         return cast_to_timestamp(column, from_type);
+    } else if data_type.data_type_id() == TypeID::Decimal {
+        return cast_to_decimal(column, from_type, data_type);
+    } else if data_type.data_type_id() == TypeID::Decimal256 {
+        return cast_to_decimal256(column, from_type, data_type);
     } else if data_type.data_type_id() == TypeID::Date {
         return arrow_cast_compute(
             column,
@@ -323,7 +338,7 @@ pub fn arrow_cast_compute(
         );
     }
 
-    let arrow_array = column.as_arrow_array(from_type.clone());
+    let arrow_array = column.try_as_arrow_array(from_type.clone())?;
     let arrow_options = cast_options.as_arrow();
     let result: ArrayRef =
         cast::cast(arrow_array.as_ref(), &data_type.arrow_type(), arrow_options)?;
@@ -30,6 +30,7 @@ use super::FunctionAdapter;
 use super::FunctionFeatures;
 use super::HashesFunction;
 use super::LogicFunction;
+use super::MapFunction;
 use super::MathsFunction;
 use super::OtherFunction;
 use super::SemiStructuredFunction;
@@ -81,6 +82,7 @@ static FUNCTION_FACTORY: Lazy<Arc<FunctionFactory>> = Lazy::new(|| {
     HashesFunction::register(&mut function_factory);
     ConditionalFunction::register(&mut function_factory);
     LogicFunction::register(&mut function_factory);
+    MapFunction::register(&mut function_factory);
     DateFunction::register(&mut function_factory);
     OtherFunction::register(&mut function_factory);
     UUIDFunction::register(&mut function_factory);
@@ -0,0 +1,134 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_datavalues::type_coercion::compare_coercion;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::cast_column_field;
+use crate::scalars::Function;
+use crate::scalars::FunctionContext;
+use crate::scalars::FunctionDescription;
+use crate::scalars::FunctionFeatures;
+
+/// `map[key]` returns the value of the key in the map, or NULL if the key does not exist.
+#[derive(Clone)]
+pub struct MapGetFunction {
+    display_name: String,
+    map_type: MapType,
+}
+
+impl MapGetFunction {
+    pub fn try_create(display_name: &str, args: &[&DataTypeImpl]) -> Result<Box<dyn Function>> {
+        let map_type = check_map_key_args(display_name, args)?;
+        Ok(Box::new(MapGetFunction {
+            display_name: display_name.to_string(),
+            map_type,
+        }))
+    }
+
+    pub fn desc() -> FunctionDescription {
+        FunctionDescription::creator(Box::new(Self::try_create))
+            .features(FunctionFeatures::default().deterministic().num_arguments(2))
+    }
+}
+
+impl Function for MapGetFunction {
+    fn name(&self) -> &str {
+        &*self.display_name
+    }
+
+    fn return_type(&self) -> DataTypeImpl {
+        wrap_nullable(self.map_type.value_type())
+    }
+
+    fn eval(
+        &self,
+        func_ctx: FunctionContext,
+        columns: &ColumnsWithField,
+        input_rows: usize,
+    ) -> Result<ColumnRef> {
+        let column = columns[0].column().convert_full_column();
+        let map_column: &ArrayColumn = Series::check_get(&column)?;
+        let entries: &StructColumn = Series::check_get(map_column.values())?;
+        let values = &entries.values()[1];
+
+        let positions = lookup_map_keys(&self.map_type, &func_ctx, columns, input_rows)?;
+        let result = positions
+            .into_iter()
+            .map(|pos| pos.map_or(DataValue::Null, |pos| values.get(pos)))
+            .collect::<Vec<_>>();
+        self.return_type().create_column(&result)
+    }
+}
+
+impl fmt::Display for MapGetFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}()", self.display_name)
+    }
+}
+
+/// Check the arguments of `(map, key)` and returns the map type,
+/// the key must be comparable with the key type of the map.
+pub(crate) fn check_map_key_args(display_name: &str, args: &[&DataTypeImpl]) -> Result<MapType> {
+    let error = || {
+        ErrorCode::IllegalDataType(format!(
+            "Invalid argument types for function '{}': ({:?}, {:?})",
+            display_name.to_uppercase(),
+            args[0],
+            args[1]
+        ))
+    };
+
+    if !args[0].data_type_id().is_map() {
+        return Err(error());
+    }
+    let map_type: MapType = args[0].clone().try_into()?;
+    compare_coercion(map_type.key_type(), args[1]).map_err(|_| error())?;
+    Ok(map_type)
+}
+
+/// Find the position of the key in the entries of each row, the last entry wins
+/// if there are duplicated keys.
+pub(crate) fn lookup_map_keys(
+    map_type: &MapType,
+    func_ctx: &FunctionContext,
+    columns: &ColumnsWithField,
+    input_rows: usize,
+) -> Result<Vec<Option<usize>>> {
+    let column = columns[0].column().convert_full_column();
+    let map_column: &ArrayColumn = Series::check_get(&column)?;
+    let entries: &StructColumn = Series::check_get(map_column.values())?;
+    let keys = &entries.values()[0];
+
+    let key_column = cast_column_field(
+        &columns[1],
+        columns[1].data_type(),
+        map_type.key_type(),
+        func_ctx,
+    )?;
+
+    let offsets = map_column.offsets();
+    let mut positions = Vec::with_capacity(input_rows);
+    for row in 0..input_rows {
+        let key = key_column.get(row);
+        let start = offsets[row] as usize;
+        let end = offsets[row + 1] as usize;
+        positions.push((start..end).rev().find(|pos| keys.get(*pos) == key));
+    }
+    Ok(positions)
+}
@@ -16,3 +16,21 @@ pub enum WindowFunction {
     AggregateFunction,
     BuiltInFunction,
 }
+
+/// Window functions which can only be called with `OVER` clause, in contrast
+/// to aggregate functions which can be used as window functions as well.
+pub const BUILTIN_WINDOW_FUNCTIONS: [&str; 7] = [
+    "row_number",
+    "rank",
+    "dense_rank",
+    "lag",
+    "lead",
+    "first_value",
+    "last_value",
+];
+
+pub fn is_builtin_window_function(name: &str) -> bool {
+    BUILTIN_WINDOW_FUNCTIONS
+        .iter()
+        .any(|func| func.eq_ignore_ascii_case(name))
+}
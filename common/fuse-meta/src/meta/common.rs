@@ -19,6 +19,8 @@ use common_datavalues::DataValue;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::meta::HyperLogLog;
+
 pub type ColumnId = u32;
 pub type FormatVersion = u64;
 pub type SnapshotId = Uuid;
@@ -37,6 +39,17 @@ pub struct ColumnStatistics {
     #[serde(alias = "unset_bits")]
     pub null_count: u64,
     pub in_memory_size: u64,
+    /// Sketch of the distinct values, only collected into the statistics of `ANALYZE TABLE`,
+    /// the statistics of blocks and segments do not have it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distinct_values: Option<HyperLogLog>,
+}
+
+impl ColumnStatistics {
+    /// Returns the estimated number of distinct non-null values, if the sketch is collected.
+    pub fn number_of_distinct_values(&self) -> Option<u64> {
+        self.distinct_values.as_ref().map(|v| v.estimate())
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
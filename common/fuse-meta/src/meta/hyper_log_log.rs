@@ -0,0 +1,139 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use serde::de::Error;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+/// The number of bits of the hash used to choose the register.
+///
+/// 2^8 registers give a standard error of about 6.5% while keeping the serialized
+/// sketch small.
+const PRECISION: u32 = 8;
+const NUM_REGISTERS: usize = 1 << PRECISION;
+
+/// Each register is stored as one char of the alphabet, the max value of a register is
+/// `64 - PRECISION + 1`, which is less than the length of the alphabet.
+const ALPHABET: &[u8; 62] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// A HyperLogLog sketch to estimate the number of distinct values of a column.
+///
+/// The values are added by their 64 bits hashes, so the sketches built by the same hash
+/// function can be merged, e.g. the sketches of blocks are merged into the sketch of the
+/// table by `ANALYZE TABLE`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl Default for HyperLogLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        HyperLogLog {
+            registers: vec![0; NUM_REGISTERS],
+        }
+    }
+
+    pub fn add_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - PRECISION)) as usize;
+        // the position of the leftmost 1-bit of the remaining bits, the sentinel bit
+        // makes sure the position never exceeds `64 - PRECISION + 1`.
+        let rest = (hash << PRECISION) | (1 << (PRECISION - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    pub fn merge(&mut self, other: &HyperLogLog) {
+        for (l, r) in self.registers.iter_mut().zip(other.registers.iter()) {
+            if *r > *l {
+                *l = *r;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.registers.iter().all(|r| *r == 0)
+    }
+
+    /// Returns the estimated number of distinct values.
+    pub fn estimate(&self) -> u64 {
+        let m = NUM_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let mut sum = 0.0;
+        let mut zeros = 0;
+        for r in &self.registers {
+            sum += 1.0 / (1u64 << *r) as f64;
+            if *r == 0 {
+                zeros += 1;
+            }
+        }
+
+        let estimate = alpha * m * m / sum;
+        if estimate <= 2.5 * m && zeros > 0 {
+            // small range correction, use linear counting
+            (m * (m / zeros as f64).ln()).round() as u64
+        } else {
+            estimate.round() as u64
+        }
+    }
+}
+
+impl Serialize for HyperLogLog {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        let encoded: String = self
+            .registers
+            .iter()
+            .map(|r| ALPHABET[*r as usize] as char)
+            .collect();
+        serializer.serialize_str(&encoded)
+    }
+}
+
+impl<'de> Deserialize<'de> for HyperLogLog {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where D: Deserializer<'de> {
+        let encoded = String::deserialize(deserializer)?;
+        if encoded.len() != NUM_REGISTERS {
+            return Err(D::Error::custom(format!(
+                "invalid length of HyperLogLog registers: {}",
+                encoded.len()
+            )));
+        }
+
+        let registers = encoded
+            .bytes()
+            .map(|c| {
+                ALPHABET
+                    .iter()
+                    .position(|a| *a == c)
+                    .map(|r| r as u8)
+                    .ok_or_else(|| {
+                        D::Error::custom(format!("invalid HyperLogLog register: {}", c as char))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HyperLogLog { registers })
+    }
+}
@@ -16,6 +16,7 @@ mod common;
 
 /// Re-exports meta data structures of current version, i.e. v1
 mod current;
+mod hyper_log_log;
 mod v0;
 mod v1;
 mod versions;
@@ -31,6 +32,8 @@ pub use common::Statistics;
 pub use common::StatisticsOfColumns;
 pub use common::Versioned;
 pub use current::*;
+pub use hyper_log_log::HyperLogLog;
 pub use versions::BlockBloomFilterIndexVersion;
 pub use versions::SegmentInfoVersion;
 pub use versions::SnapshotVersion;
+pub use versions::TableSnapshotStatisticsVersion;
@@ -15,8 +15,10 @@
 mod index;
 mod segment;
 mod snapshot;
+mod snapshot_statistics;
 
 pub use index::BlockBloomFilterIndex;
 pub use segment::BlockMeta;
 pub use segment::SegmentInfo;
 pub use snapshot::TableSnapshot;
+pub use snapshot_statistics::TableSnapshotStatistics;
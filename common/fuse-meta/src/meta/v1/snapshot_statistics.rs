@@ -0,0 +1,61 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::meta::common::FormatVersion;
+use crate::meta::SnapshotId;
+use crate::meta::StatisticsOfColumns;
+use crate::meta::Versioned;
+
+/// The column statistics of a table snapshot, collected by `ANALYZE TABLE`.
+///
+/// Unlike the summary statistics of snapshot, which are merged from the statistics of
+/// blocks while writing, these are recomputed from all the data of the snapshot, and are
+/// the only statistics carrying the sketches of distinct values. Histograms of the columns
+/// are not collected.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TableSnapshotStatistics {
+    /// format version of snapshot statistics
+    format_version: FormatVersion,
+
+    /// id of the snapshot which these statistics are collected for
+    pub snapshot_id: SnapshotId,
+
+    /// number of rows of the snapshot
+    pub row_count: u64,
+
+    /// statistics of the analyzed columns, keyed by the column (leaf) id
+    pub column_statistics: StatisticsOfColumns,
+}
+
+impl TableSnapshotStatistics {
+    pub fn new(
+        snapshot_id: SnapshotId,
+        row_count: u64,
+        column_statistics: StatisticsOfColumns,
+    ) -> Self {
+        Self {
+            format_version: TableSnapshotStatistics::VERSION,
+            snapshot_id,
+            row_count,
+            column_statistics,
+        }
+    }
+
+    pub fn format_version(&self) -> u64 {
+        self.format_version
+    }
+}
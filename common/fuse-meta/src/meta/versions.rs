@@ -63,6 +63,12 @@ impl SnapshotVersion {
     }
 }
 
+impl Versioned<1> for v1::TableSnapshotStatistics {}
+
+pub enum TableSnapshotStatisticsVersion {
+    V1(PhantomData<v1::TableSnapshotStatistics>),
+}
+
 impl Versioned<0> for DataBlock {}
 
 impl Versioned<1> for BlockBloomFilterIndex {}
@@ -105,6 +111,20 @@ mod converters {
         }
     }
 
+    impl TryFrom<u64> for TableSnapshotStatisticsVersion {
+        type Error = ErrorCode;
+        fn try_from(value: u64) -> Result<Self, Self::Error> {
+            match value {
+                1 => Ok(TableSnapshotStatisticsVersion::V1(ver_eq::<_, 1>(
+                    PhantomData,
+                ))),
+                _ => Err(ErrorCode::LogicalError(format!(
+                    "unknown table snapshot statistics version {value}, versions supported: 1"
+                ))),
+            }
+        }
+    }
+
     impl TryFrom<u64> for BlockBloomFilterIndexVersion {
         type Error = ErrorCode;
         fn try_from(value: u64) -> Result<Self, Self::Error> {
@@ -20,9 +20,15 @@ use crate::HashTableGrower;
 use crate::HashTableIteratorKind;
 use crate::HashTableKeyable;
 
-static BITS_FOR_BUCKET: u8 = 8;
-static NUM_BUCKETS: usize = 1 << BITS_FOR_BUCKET;
-static MAX_BUCKECT: usize = NUM_BUCKETS - 1;
+const BITS_FOR_BUCKET: u8 = 8;
+pub const NUM_BUCKETS: usize = 1 << BITS_FOR_BUCKET;
+const MAX_BUCKECT: usize = NUM_BUCKETS - 1;
+
+/// Returns the bucket of the hash value in the two-level hash table.
+#[inline(always)]
+pub fn get_bucket_from_hash(hash_value: u64) -> usize {
+    ((hash_value >> (64 - BITS_FOR_BUCKET)) & (MAX_BUCKECT as u64)) as usize
+}
 
 pub enum HashTableKind<
     Key: HashTableKeyable,
@@ -161,25 +167,20 @@ impl<Key: HashTableKeyable, Entity: HashTableEntity<Key>, Grower: HashTableGrowe
     #[inline(always)]
     pub fn insert_key(&mut self, key: &Key, inserted: &mut bool) -> *mut Entity {
         let hash = key.fast_hash();
-        let bucket = self.get_bucket_from_hash(&hash);
+        let bucket = get_bucket_from_hash(hash);
         self.hash_tables[bucket].insert_hash_key(key, hash, inserted)
     }
 
     #[inline(always)]
     pub fn insert_hash_key(&mut self, key: &Key, hash: u64, inserted: &mut bool) -> *mut Entity {
-        let bucket = self.get_bucket_from_hash(&hash);
+        let bucket = get_bucket_from_hash(hash);
         self.hash_tables[bucket].insert_hash_key(key, hash, inserted)
     }
 
     #[inline(always)]
     pub fn find_key(&self, key: &Key) -> Option<*mut Entity> {
         let hash = key.fast_hash();
-        let bucket = self.get_bucket_from_hash(&hash);
+        let bucket = get_bucket_from_hash(hash);
         self.hash_tables[bucket].find_key(key)
     }
-
-    #[inline(always)]
-    fn get_bucket_from_hash(&self, hash_value: &u64) -> usize {
-        ((hash_value >> (64 - BITS_FOR_BUCKET)) & (MAX_BUCKECT as u64)) as usize
-    }
 }
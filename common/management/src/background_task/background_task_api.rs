@@ -0,0 +1,29 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+#[async_trait::async_trait]
+pub trait BackgroundTaskApi: Sync + Send {
+    // Lock the table for the background task of the node, so that the nodes of the cluster
+    // never run the background tasks of a table at the same time.
+    // Returns the seq of the lock, or None if the table is locked by other node already.
+    async fn lock_table(&self, table_id: u64, node_id: &str) -> Result<Option<u64>>;
+
+    // Unlock the table locked by `lock_table`.
+    async fn unlock_table(&self, table_id: u64, seq: u64) -> Result<()>;
+
+    // Get the node holding the lock of the table.
+    async fn get_table_lock(&self, table_id: u64) -> Result<Option<String>>;
+}
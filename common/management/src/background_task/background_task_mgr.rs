@@ -0,0 +1,118 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Add;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+use common_base::base::escape_for_key;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_api::KVApi;
+use common_meta_types::KVMeta;
+use common_meta_types::MatchSeq;
+use common_meta_types::OkOrExist;
+use common_meta_types::Operation;
+use common_meta_types::UpsertKVReq;
+
+use crate::background_task::BackgroundTaskApi;
+
+pub static BACKGROUND_TASK_API_KEY_PREFIX: &str = "__fd_background_tasks";
+
+pub struct BackgroundTaskMgr {
+    kv_api: Arc<dyn KVApi>,
+    lift_time: Duration,
+    table_lock_prefix: String,
+}
+
+impl BackgroundTaskMgr {
+    pub fn create(kv_api: Arc<dyn KVApi>, tenant: &str, lift_time: Duration) -> Result<Self> {
+        if tenant.is_empty() {
+            return Err(ErrorCode::TenantIsEmpty(
+                "Tenant can not empty(while background task mgr create)",
+            ));
+        }
+
+        Ok(BackgroundTaskMgr {
+            kv_api,
+            lift_time,
+            table_lock_prefix: format!(
+                "{}/{}/table_locks",
+                BACKGROUND_TASK_API_KEY_PREFIX,
+                escape_for_key(tenant)?
+            ),
+        })
+    }
+
+    fn new_lift_time(&self) -> KVMeta {
+        let now = std::time::SystemTime::now();
+        let expire_at = now
+            .add(self.lift_time)
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards");
+
+        KVMeta {
+            expire_at: Some(expire_at.as_secs()),
+        }
+    }
+
+    fn table_lock_key(&self, table_id: u64) -> String {
+        format!("{}/{}", self.table_lock_prefix, table_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl BackgroundTaskApi for BackgroundTaskMgr {
+    async fn lock_table(&self, table_id: u64, node_id: &str) -> Result<Option<u64>> {
+        // Only when there are no record, i.e. seq=0.
+        // The lock expires after the lift time, in case of the node crashes with the lock.
+        let seq = MatchSeq::Exact(0);
+        let meta = Some(self.new_lift_time());
+        let value = Operation::Update(serde_json::to_vec(node_id)?);
+        let lock_key = self.table_lock_key(table_id);
+        let upsert_lock = self
+            .kv_api
+            .upsert_kv(UpsertKVReq::new(&lock_key, seq, value, meta));
+
+        let res = upsert_lock.await?.into_add_result()?;
+
+        match res.res {
+            OkOrExist::Ok(v) => Ok(Some(v.seq)),
+            OkOrExist::Exists(_) => Ok(None),
+        }
+    }
+
+    async fn unlock_table(&self, table_id: u64, seq: u64) -> Result<()> {
+        let lock_key = self.table_lock_key(table_id);
+        // The lock may be expired and taken by other node, which is kept by the seq.
+        self.kv_api
+            .upsert_kv(UpsertKVReq::new(
+                &lock_key,
+                MatchSeq::Exact(seq),
+                Operation::Delete,
+                None,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    async fn get_table_lock(&self, table_id: u64) -> Result<Option<String>> {
+        let lock_key = self.table_lock_key(table_id);
+        match self.kv_api.get_kv(&lock_key).await? {
+            Some(v) => Ok(Some(serde_json::from_slice(&v.data)?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod background_task;
 mod cluster;
 mod quota;
 mod role;
@@ -21,6 +22,8 @@ mod stage;
 mod udf;
 mod user;
 
+pub use background_task::BackgroundTaskApi;
+pub use background_task::BackgroundTaskMgr;
 pub use cluster::ClusterApi;
 pub use cluster::ClusterMgr;
 pub use quota::QuotaApi;
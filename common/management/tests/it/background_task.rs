@@ -0,0 +1,68 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_exception::Result;
+use common_management::*;
+use common_meta_api::KVApi;
+use common_meta_embedded::MetaEmbedded;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_lock_table() -> Result<()> {
+    let (kv_api, task_api) = new_background_task_api().await?;
+
+    let seq = task_api.lock_table(1, "node_1").await?;
+    assert!(seq.is_some());
+    let value = kv_api
+        .get_kv("__fd_background_tasks/test%2dtenant%2did/table_locks/1")
+        .await?;
+    assert_eq!(value.unwrap().data, serde_json::to_vec("node_1")?);
+    assert_eq!(
+        task_api.get_table_lock(1).await?,
+        Some("node_1".to_string())
+    );
+
+    // locked by other node
+    assert_eq!(task_api.lock_table(1, "node_2").await?, None);
+
+    // other tables are not affected
+    assert!(task_api.lock_table(2, "node_2").await?.is_some());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_unlock_table() -> Result<()> {
+    let (_, task_api) = new_background_task_api().await?;
+
+    let seq = task_api.lock_table(1, "node_1").await?.unwrap();
+
+    // unlock with a stale seq takes no effect
+    task_api.unlock_table(1, seq + 1).await?;
+    assert_eq!(task_api.lock_table(1, "node_2").await?, None);
+
+    task_api.unlock_table(1, seq).await?;
+    assert_eq!(task_api.get_table_lock(1).await?, None);
+    assert!(task_api.lock_table(1, "node_2").await?.is_some());
+    Ok(())
+}
+
+async fn new_background_task_api() -> Result<(Arc<MetaEmbedded>, BackgroundTaskMgr)> {
+    let test_api = Arc::new(MetaEmbedded::new_temp().await?);
+    let task_manager =
+        BackgroundTaskMgr::create(test_api.clone(), "test-tenant-id", Duration::from_secs(60))?;
+    Ok((test_api, task_manager))
+}
@@ -22,6 +22,8 @@ use common_meta_app::schema::CountTablesReply;
 use common_meta_app::schema::CountTablesReq;
 use common_meta_app::schema::CreateDatabaseReply;
 use common_meta_app::schema::CreateDatabaseReq;
+use common_meta_app::schema::CreateTableIndexReply;
+use common_meta_app::schema::CreateTableIndexReq;
 use common_meta_app::schema::CreateTableReply;
 use common_meta_app::schema::CreateTableReq;
 use common_meta_app::schema::DBIdTableName;
@@ -35,6 +37,8 @@ use common_meta_app::schema::DbIdList;
 use common_meta_app::schema::DbIdListKey;
 use common_meta_app::schema::DropDatabaseReply;
 use common_meta_app::schema::DropDatabaseReq;
+use common_meta_app::schema::DropTableIndexReply;
+use common_meta_app::schema::DropTableIndexReq;
 use common_meta_app::schema::DropTableReply;
 use common_meta_app::schema::DropTableReq;
 use common_meta_app::schema::GetDatabaseReq;
@@ -57,6 +61,8 @@ use common_meta_app::schema::UndropDatabaseReply;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReply;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReply;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReply;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableOptionReply;
@@ -68,6 +74,7 @@ use common_meta_types::app_error::DatabaseAlreadyExists;
 use common_meta_types::app_error::DropDbWithDropTime;
 use common_meta_types::app_error::DropTableWithDropTime;
 use common_meta_types::app_error::TableAlreadyExists;
+use common_meta_types::app_error::TableIndexAlreadyExists;
 use common_meta_types::app_error::TableVersionMismatched;
 use common_meta_types::app_error::TxnRetryMaxTimes;
 use common_meta_types::app_error::UndropDbHasNoHistory;
@@ -77,6 +84,7 @@ use common_meta_types::app_error::UndropTableHasNoHistory;
 use common_meta_types::app_error::UndropTableWithNoDropTime;
 use common_meta_types::app_error::UnknownTable;
 use common_meta_types::app_error::UnknownTableId;
+use common_meta_types::app_error::UnknownTableIndex;
 use common_meta_types::ConditionResult;
 use common_meta_types::GCDroppedDataReply;
 use common_meta_types::GCDroppedDataReq;
@@ -1701,6 +1709,182 @@ impl<KV: KVApi> SchemaApi for KV {
         }
     }
 
+    #[tracing::instrument(level = "debug", ret, err, skip_all)]
+    async fn update_multi_table_meta(
+        &self,
+        req: UpdateMultiTableMetaReq,
+    ) -> Result<UpdateMultiTableMetaReply, MetaError> {
+        debug!(req = debug(&req), "SchemaApi: {}", func_name!());
+
+        loop {
+            let mut condition = Vec::with_capacity(req.update_table_metas.len());
+            let mut if_then = Vec::with_capacity(req.update_table_metas.len());
+
+            for update in req.update_table_metas.iter() {
+                let tbid = TableId {
+                    table_id: update.table_id,
+                };
+                let (tb_meta_seq, table_meta): (_, Option<TableMeta>) =
+                    get_struct_value(self, &tbid).await?;
+
+                if tb_meta_seq == 0 || table_meta.is_none() {
+                    return Err(MetaError::AppError(AppError::UnknownTableId(
+                        UnknownTableId::new(update.table_id, "update_multi_table_meta"),
+                    )));
+                }
+                if update.seq.match_seq(tb_meta_seq).is_err() {
+                    return Err(MetaError::AppError(AppError::from(
+                        TableVersionMismatched::new(
+                            update.table_id,
+                            update.seq,
+                            tb_meta_seq,
+                            "update_multi_table_meta",
+                        ),
+                    )));
+                }
+
+                // table is not changed
+                condition.push(txn_cond_seq(&tbid, Eq, tb_meta_seq));
+                // tb_id -> tb_meta
+                if_then.push(txn_op_put(&tbid, serialize_struct(&update.new_table_meta)?));
+            }
+
+            let txn_req = TxnRequest {
+                condition,
+                if_then,
+                else_then: vec![],
+            };
+
+            let (succ, _responses) = send_txn(self, txn_req).await?;
+
+            debug!(succ = display(succ), "update_multi_table_meta");
+
+            if succ {
+                return Ok(UpdateMultiTableMetaReply {});
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", ret, err, skip_all)]
+    async fn create_table_index(
+        &self,
+        req: CreateTableIndexReq,
+    ) -> Result<CreateTableIndexReply, MetaError> {
+        debug!(req = debug(&req), "SchemaApi: {}", func_name!());
+
+        let tbid = TableId {
+            table_id: req.table_id,
+        };
+
+        loop {
+            let (tb_meta_seq, table_meta): (_, Option<TableMeta>) =
+                get_struct_value(self, &tbid).await?;
+
+            debug!(ident = display(&tbid), "create_table_index");
+
+            if tb_meta_seq == 0 || table_meta.is_none() {
+                return Err(MetaError::AppError(AppError::UnknownTableId(
+                    UnknownTableId::new(req.table_id, "create_table_index"),
+                )));
+            }
+            let mut table_meta = table_meta.unwrap();
+
+            if table_meta.indexes.contains_key(&req.index.name) {
+                return if req.if_not_exists {
+                    Ok(CreateTableIndexReply {})
+                } else {
+                    Err(MetaError::AppError(AppError::TableIndexAlreadyExists(
+                        TableIndexAlreadyExists::new(&req.index.name, "create_table_index"),
+                    )))
+                };
+            }
+
+            table_meta
+                .indexes
+                .insert(req.index.name.clone(), req.index.clone());
+            table_meta.updated_on = Utc::now();
+
+            let txn_req = TxnRequest {
+                condition: vec![
+                    // table is not changed
+                    txn_cond_seq(&tbid, Eq, tb_meta_seq),
+                ],
+                if_then: vec![
+                    txn_op_put(&tbid, serialize_struct(&table_meta)?), // tb_id -> tb_meta
+                ],
+                else_then: vec![],
+            };
+
+            let (succ, _responses) = send_txn(self, txn_req).await?;
+
+            debug!(
+                id = debug(&tbid),
+                succ = display(succ),
+                "create_table_index"
+            );
+
+            if succ {
+                return Ok(CreateTableIndexReply {});
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", ret, err, skip_all)]
+    async fn drop_table_index(
+        &self,
+        req: DropTableIndexReq,
+    ) -> Result<DropTableIndexReply, MetaError> {
+        debug!(req = debug(&req), "SchemaApi: {}", func_name!());
+
+        let tbid = TableId {
+            table_id: req.table_id,
+        };
+
+        loop {
+            let (tb_meta_seq, table_meta): (_, Option<TableMeta>) =
+                get_struct_value(self, &tbid).await?;
+
+            debug!(ident = display(&tbid), "drop_table_index");
+
+            if tb_meta_seq == 0 || table_meta.is_none() {
+                return Err(MetaError::AppError(AppError::UnknownTableId(
+                    UnknownTableId::new(req.table_id, "drop_table_index"),
+                )));
+            }
+            let mut table_meta = table_meta.unwrap();
+
+            if table_meta.indexes.remove(&req.name).is_none() {
+                return if req.if_exists {
+                    Ok(DropTableIndexReply {})
+                } else {
+                    Err(MetaError::AppError(AppError::UnknownTableIndex(
+                        UnknownTableIndex::new(&req.name, "drop_table_index"),
+                    )))
+                };
+            }
+            table_meta.updated_on = Utc::now();
+
+            let txn_req = TxnRequest {
+                condition: vec![
+                    // table is not changed
+                    txn_cond_seq(&tbid, Eq, tb_meta_seq),
+                ],
+                if_then: vec![
+                    txn_op_put(&tbid, serialize_struct(&table_meta)?), // tb_id -> tb_meta
+                ],
+                else_then: vec![],
+            };
+
+            let (succ, _responses) = send_txn(self, txn_req).await?;
+
+            debug!(id = debug(&tbid), succ = display(succ), "drop_table_index");
+
+            if succ {
+                return Ok(DropTableIndexReply {});
+            }
+        }
+    }
+
     #[tracing::instrument(level = "debug", ret, err, skip_all)]
     async fn gc_dropped_data(
         &self,
@@ -24,6 +24,7 @@ use common_exception::ErrorCode;
 use common_meta_app::schema::CountTablesReq;
 use common_meta_app::schema::CreateDatabaseReply;
 use common_meta_app::schema::CreateDatabaseReq;
+use common_meta_app::schema::CreateTableIndexReq;
 use common_meta_app::schema::CreateTableReq;
 use common_meta_app::schema::DBIdTableName;
 use common_meta_app::schema::DatabaseId;
@@ -34,6 +35,7 @@ use common_meta_app::schema::DatabaseNameIdent;
 use common_meta_app::schema::DbIdList;
 use common_meta_app::schema::DbIdListKey;
 use common_meta_app::schema::DropDatabaseReq;
+use common_meta_app::schema::DropTableIndexReq;
 use common_meta_app::schema::DropTableReq;
 use common_meta_app::schema::GetDatabaseReq;
 use common_meta_app::schema::GetTableReq;
@@ -46,12 +48,15 @@ use common_meta_app::schema::TableIdList;
 use common_meta_app::schema::TableIdListKey;
 use common_meta_app::schema::TableIdToName;
 use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableIndex;
+use common_meta_app::schema::TableIndexType;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::TableNameIdent;
 use common_meta_app::schema::TableStatistics;
 use common_meta_app::schema::UndropDatabaseReq;
 use common_meta_app::schema::UndropTableReq;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_app::schema::UpsertTableOptionReq;
 use common_meta_types::GCDroppedDataReq;
@@ -236,7 +241,9 @@ impl SchemaApiTestSuite {
         suite.table_create_get_drop(&b.build().await).await?;
         suite.table_rename(&b.build().await).await?;
         suite.table_update_meta(&b.build().await).await?;
+        suite.table_update_multi_meta(&b.build().await).await?;
         suite.table_upsert_option(&b.build().await).await?;
+        suite.table_create_drop_index(&b.build().await).await?;
         suite.table_list(&b.build().await).await?;
         suite
             .table_drop_undrop_list_history(&b.build().await)
@@ -1879,6 +1886,113 @@ impl SchemaApiTestSuite {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn table_update_multi_meta<MT: SchemaApi>(&self, mt: &MT) -> anyhow::Result<()> {
+        let tenant = "tenant1";
+        let db_name = "db1";
+        let tbl_names = ["tb1", "tb2"];
+
+        let table_meta = || TableMeta {
+            schema: Arc::new(DataSchema::new(vec![DataField::new(
+                "number",
+                u64::to_data_type(),
+            )])),
+            engine: "JSON".to_string(),
+            options: Default::default(),
+            created_on: Utc::now(),
+            ..TableMeta::default()
+        };
+
+        info!("--- prepare db and tables");
+        {
+            let plan = CreateDatabaseReq {
+                if_not_exists: false,
+                name_ident: DatabaseNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: db_name.to_string(),
+                },
+                meta: DatabaseMeta {
+                    engine: "".to_string(),
+                    ..DatabaseMeta::default()
+                },
+            };
+            mt.create_database(plan).await?;
+
+            for tbl_name in tbl_names {
+                let req = CreateTableReq {
+                    if_not_exists: false,
+                    name_ident: TableNameIdent {
+                        tenant: tenant.to_string(),
+                        db_name: db_name.to_string(),
+                        table_name: tbl_name.to_string(),
+                    },
+                    table_meta: table_meta(),
+                };
+                mt.create_table(req).await?;
+            }
+        }
+
+        info!("--- update the metas of all tables");
+        {
+            let mut update_table_metas = vec![];
+            let mut new_table_metas = vec![];
+            for (i, tbl_name) in tbl_names.iter().enumerate() {
+                let table = mt.get_table((tenant, db_name, *tbl_name).into()).await?;
+                let mut new_table_meta = table.meta.clone();
+                new_table_meta.statistics = TableStatistics {
+                    data_bytes: i as u64 + 1,
+                    ..Default::default()
+                };
+                update_table_metas.push(UpdateTableMetaReq {
+                    table_id: table.ident.table_id,
+                    seq: MatchSeq::Exact(table.ident.seq),
+                    new_table_meta: new_table_meta.clone(),
+                });
+                new_table_metas.push(new_table_meta);
+            }
+            mt.update_multi_table_meta(UpdateMultiTableMetaReq { update_table_metas })
+                .await?;
+
+            for (tbl_name, new_table_meta) in tbl_names.iter().zip(new_table_metas) {
+                let table = mt.get_table((tenant, db_name, *tbl_name).into()).await?;
+                assert_eq!(table.meta, new_table_meta);
+            }
+        }
+
+        info!("--- version mismatch of one table, none of the tables is updated");
+        {
+            let mut update_table_metas = vec![];
+            let mut old_table_metas = vec![];
+            for (i, tbl_name) in tbl_names.iter().enumerate() {
+                let table = mt.get_table((tenant, db_name, *tbl_name).into()).await?;
+                let mut new_table_meta = table.meta.clone();
+                new_table_meta.statistics = TableStatistics {
+                    data_bytes: 10,
+                    ..Default::default()
+                };
+                update_table_metas.push(UpdateTableMetaReq {
+                    table_id: table.ident.table_id,
+                    // the seq of the last table mismatches
+                    seq: MatchSeq::Exact(table.ident.seq + i as u64),
+                    new_table_meta,
+                });
+                old_table_metas.push(table.meta.clone());
+            }
+            let res = mt
+                .update_multi_table_meta(UpdateMultiTableMetaReq { update_table_metas })
+                .await;
+
+            let err = ErrorCode::from(res.unwrap_err());
+            assert_eq!(ErrorCode::table_version_mismatched_code(), err.code());
+
+            for (tbl_name, old_table_meta) in tbl_names.iter().zip(old_table_metas) {
+                let table = mt.get_table((tenant, db_name, *tbl_name).into()).await?;
+                assert_eq!(table.meta, old_table_meta);
+            }
+        }
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn table_upsert_option<MT: SchemaApi>(&self, mt: &MT) -> anyhow::Result<()> {
         let tenant = "tenant1";
@@ -2026,6 +2140,134 @@ impl SchemaApiTestSuite {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip_all)]
+    async fn table_create_drop_index<MT: SchemaApi>(&self, mt: &MT) -> anyhow::Result<()> {
+        let tenant = "tenant1";
+        let db_name = "db1";
+        let tbl_name = "tb2";
+
+        info!("--- prepare db and table");
+        let table_id = {
+            let plan = CreateDatabaseReq {
+                if_not_exists: false,
+                name_ident: DatabaseNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: db_name.to_string(),
+                },
+                meta: DatabaseMeta {
+                    engine: "".to_string(),
+                    ..DatabaseMeta::default()
+                },
+            };
+            mt.create_database(plan).await?;
+
+            let req = CreateTableReq {
+                if_not_exists: false,
+                name_ident: TableNameIdent {
+                    tenant: tenant.to_string(),
+                    db_name: db_name.to_string(),
+                    table_name: tbl_name.to_string(),
+                },
+                table_meta: TableMeta {
+                    schema: Arc::new(DataSchema::new(vec![DataField::new(
+                        "number",
+                        u64::to_data_type(),
+                    )])),
+                    engine: "JSON".to_string(),
+                    ..TableMeta::default()
+                },
+            };
+            mt.create_table(req).await?.table_id
+        };
+
+        let index = TableIndex {
+            name: "idx1".to_string(),
+            column: "number".to_string(),
+            index_type: TableIndexType::Set,
+        };
+
+        info!("--- create index");
+        {
+            let old = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+
+            mt.create_table_index(CreateTableIndexReq {
+                if_not_exists: false,
+                table_id,
+                index: index.clone(),
+            })
+            .await?;
+
+            let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+            assert!(old.ident.seq < table.ident.seq);
+            assert_eq!(table.meta.indexes.get("idx1"), Some(&index));
+        }
+
+        info!("--- create existing index");
+        {
+            let got = mt
+                .create_table_index(CreateTableIndexReq {
+                    if_not_exists: false,
+                    table_id,
+                    index: index.clone(),
+                })
+                .await;
+            let err = ErrorCode::from(got.unwrap_err());
+            assert_eq!(ErrorCode::TableIndexAlreadyExists("").code(), err.code());
+
+            mt.create_table_index(CreateTableIndexReq {
+                if_not_exists: true,
+                table_id,
+                index: index.clone(),
+            })
+            .await?;
+        }
+
+        info!("--- create index with not exist table id");
+        {
+            let got = mt
+                .create_table_index(CreateTableIndexReq {
+                    if_not_exists: true,
+                    table_id: 1024,
+                    index: index.clone(),
+                })
+                .await;
+            let err = ErrorCode::from(got.unwrap_err());
+            assert_eq!(ErrorCode::UnknownTableId("").code(), err.code());
+        }
+
+        info!("--- drop index");
+        {
+            mt.drop_table_index(DropTableIndexReq {
+                if_exists: false,
+                table_id,
+                name: "idx1".to_string(),
+            })
+            .await?;
+
+            let table = mt.get_table((tenant, db_name, tbl_name).into()).await?;
+            assert!(table.meta.indexes.is_empty());
+
+            let got = mt
+                .drop_table_index(DropTableIndexReq {
+                    if_exists: false,
+                    table_id,
+                    name: "idx1".to_string(),
+                })
+                .await;
+            let err = ErrorCode::from(got.unwrap_err());
+            assert_eq!(ErrorCode::UnknownTableIndex("").code(), err.code());
+
+            mt.drop_table_index(DropTableIndexReq {
+                if_exists: true,
+                table_id,
+                name: "idx1".to_string(),
+            })
+            .await?;
+        }
+
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     async fn database_drop_out_of_retention_time_history<MT: SchemaApi + AsKVApi>(
         self,
@@ -38,26 +38,35 @@ pub use database::UndropDatabaseReq;
 pub use table::CountTablesKey;
 pub use table::CountTablesReply;
 pub use table::CountTablesReq;
+pub use table::CreateTableIndexReply;
+pub use table::CreateTableIndexReq;
 pub use table::CreateTableReply;
 pub use table::CreateTableReq;
 pub use table::DBIdTableName;
+pub use table::DropTableIndexReply;
+pub use table::DropTableIndexReq;
 pub use table::DropTableReply;
 pub use table::DropTableReq;
 pub use table::GetTableReq;
 pub use table::ListTableReq;
 pub use table::RenameTableReply;
 pub use table::RenameTableReq;
+pub use table::TableCheckConstraint;
 pub use table::TableId;
 pub use table::TableIdList;
 pub use table::TableIdListKey;
 pub use table::TableIdToName;
 pub use table::TableIdent;
+pub use table::TableIndex;
+pub use table::TableIndexType;
 pub use table::TableInfo;
 pub use table::TableMeta;
 pub use table::TableNameIdent;
 pub use table::TableStatistics;
 pub use table::UndropTableReply;
 pub use table::UndropTableReq;
+pub use table::UpdateMultiTableMetaReply;
+pub use table::UpdateMultiTableMetaReq;
 pub use table::UpdateTableMetaReply;
 pub use table::UpdateTableMetaReq;
 pub use table::UpsertTableOptionReply;
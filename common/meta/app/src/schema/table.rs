@@ -165,6 +165,53 @@ pub struct TableStatistics {
     pub index_data_bytes: u64,
 }
 
+/// The kinds of the secondary indexes that can be defined on a column.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableIndexType {
+    Bloom,
+    MinMax,
+    Ngram,
+    Set,
+}
+
+impl Display for TableIndexType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TableIndexType::Bloom => write!(f, "BLOOM"),
+            TableIndexType::MinMax => write!(f, "MINMAX"),
+            TableIndexType::Ngram => write!(f, "NGRAM"),
+            TableIndexType::Set => write!(f, "SET"),
+        }
+    }
+}
+
+/// A secondary index defined by `CREATE INDEX`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TableIndex {
+    pub name: String,
+    pub column: String,
+    pub index_type: TableIndexType,
+}
+
+impl Display for TableIndex {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({}) TYPE {}", self.name, self.column, self.index_type)
+    }
+}
+
+/// A CHECK constraint, the rows of the table must not evaluate the expression to false.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct TableCheckConstraint {
+    pub name: String,
+    pub expr: String,
+}
+
+impl Display for TableCheckConstraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "CONSTRAINT {} CHECK ({})", self.name, self.expr)
+    }
+}
+
 /// The essential state that defines what a table is.
 ///
 /// It is what a meta store just needs to save.
@@ -184,6 +231,10 @@ pub struct TableMeta {
     pub updated_on: DateTime<Utc>,
     pub comment: String,
     pub field_comments: Vec<String>,
+    // The secondary indexes, keyed by the index name.
+    pub indexes: BTreeMap<String, TableIndex>,
+    // The CHECK constraints, keyed by the constraint name.
+    pub check_constraints: BTreeMap<String, TableCheckConstraint>,
 
     // if used in CreateTableReq, this field MUST set to None.
     pub drop_on: Option<DateTime<Utc>>,
@@ -254,6 +305,8 @@ impl Default for TableMeta {
             updated_on: Utc::now(),
             comment: "".to_string(),
             field_comments: vec![],
+            indexes: BTreeMap::new(),
+            check_constraints: BTreeMap::new(),
             drop_on: None,
             statistics: Default::default(),
         }
@@ -278,12 +331,20 @@ impl Display for TableMeta {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Engine: {}={:?}, Schema: {}, Options: {:?}, FieldComments: {:?} CreatedOn: {:?} DropOn: {:?}",
+            "Engine: {}={:?}, Schema: {}, Options: {:?}, FieldComments: {:?} Indexes: {:?} CheckConstraints: {:?} CreatedOn: {:?} DropOn: {:?}",
             self.engine,
             self.engine_options,
             self.schema,
             self.options,
             self.field_comments,
+            self.indexes
+                .values()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>(),
+            self.check_constraints
+                .values()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>(),
             self.created_on,
             self.drop_on,
         )
@@ -531,9 +592,59 @@ impl Display for UpsertTableOptionReq {
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct UpsertTableOptionReply {}
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreateTableIndexReq {
+    pub if_not_exists: bool,
+    pub table_id: u64,
+    pub index: TableIndex,
+}
+
+impl Display for CreateTableIndexReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "create-table-index(if_not_exists={}): table-id:{} index:{}",
+            self.if_not_exists, self.table_id, self.index
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CreateTableIndexReply {}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DropTableIndexReq {
+    pub if_exists: bool,
+    pub table_id: u64,
+    pub name: String,
+}
+
+impl Display for DropTableIndexReq {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "drop-table-index(if_exists={}): table-id:{} index:{}",
+            self.if_exists, self.table_id, self.name
+        )
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct DropTableIndexReply {}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct UpdateTableMetaReply {}
 
+/// Updates the metas of several tables atomically: either all of them are updated,
+/// or none of them is if the seq of any table does not match.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpdateMultiTableMetaReq {
+    pub update_table_metas: Vec<UpdateTableMetaReq>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct UpdateMultiTableMetaReply {}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
 pub struct GetTableReq {
     pub inner: TableNameIdent,
@@ -205,6 +205,38 @@ impl TableVersionMismatched {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[error("TableIndexAlreadyExists: {index_name} while {context}")]
+pub struct TableIndexAlreadyExists {
+    index_name: String,
+    context: String,
+}
+
+impl TableIndexAlreadyExists {
+    pub fn new(index_name: impl Into<String>, context: impl Into<String>) -> Self {
+        Self {
+            index_name: index_name.into(),
+            context: context.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, thiserror::Error)]
+#[error("UnknownTableIndex: {index_name} while {context}")]
+pub struct UnknownTableIndex {
+    index_name: String,
+    context: String,
+}
+
+impl UnknownTableIndex {
+    pub fn new(index_name: impl Into<String>, context: impl Into<String>) -> Self {
+        Self {
+            index_name: index_name.into(),
+            context: context.into(),
+        }
+    }
+}
+
 #[derive(thiserror::Error, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[error("UnknownDatabase: `{db_name}` while `{context}`")]
 pub struct UnknownDatabase {
@@ -437,6 +469,12 @@ pub enum AppError {
     #[error(transparent)]
     TxnRetryMaxTimes(#[from] TxnRetryMaxTimes),
 
+    #[error(transparent)]
+    TableIndexAlreadyExists(#[from] TableIndexAlreadyExists),
+
+    #[error(transparent)]
+    UnknownTableIndex(#[from] UnknownTableIndex),
+
     // share api errors
     #[error(transparent)]
     ShareAlreadyExists(#[from] ShareAlreadyExists),
@@ -517,6 +555,18 @@ impl AppErrorMessage for UndropTableHasNoHistory {
     }
 }
 
+impl AppErrorMessage for TableIndexAlreadyExists {
+    fn message(&self) -> String {
+        format!("Index '{}' already exists", self.index_name)
+    }
+}
+
+impl AppErrorMessage for UnknownTableIndex {
+    fn message(&self) -> String {
+        format!("Unknown index '{}'", self.index_name)
+    }
+}
+
 impl AppErrorMessage for ShareAlreadyExists {
     fn message(&self) -> String {
         format!("Share '{}' already exists", self.share_name)
@@ -637,6 +687,10 @@ impl From<AppError> for ErrorCode {
             AppError::UnknownShareAccount(err) => ErrorCode::UnknownShareAccount(err.message()),
             AppError::WrongShareObject(err) => ErrorCode::WrongShareObject(err.message()),
             AppError::TxnRetryMaxTimes(err) => ErrorCode::TxnRetryMaxTimes(err.message()),
+            AppError::TableIndexAlreadyExists(err) => {
+                ErrorCode::TableIndexAlreadyExists(err.message())
+            }
+            AppError::UnknownTableIndex(err) => ErrorCode::UnknownTableIndex(err.message()),
         }
     }
 }
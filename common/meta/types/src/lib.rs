@@ -75,6 +75,7 @@ pub use app_error::DropDbWithDropTime;
 pub use app_error::DropTableWithDropTime;
 pub use app_error::ShareAlreadyExists;
 pub use app_error::TableAlreadyExists;
+pub use app_error::TableIndexAlreadyExists;
 pub use app_error::TableVersionMismatched;
 pub use app_error::UndropDbHasNoHistory;
 pub use app_error::UndropDbWithNoDropTime;
@@ -86,6 +87,7 @@ pub use app_error::UnknownDatabaseId;
 pub use app_error::UnknownShare;
 pub use app_error::UnknownTable;
 pub use app_error::UnknownTableId;
+pub use app_error::UnknownTableIndex;
 pub use app_error::WrongShareObject;
 pub use applied_state::AppliedState;
 pub use change::AddResult;
@@ -39,11 +39,14 @@ mod plan_expression_validator;
 mod plan_expression_visitor;
 mod plan_filter;
 mod plan_having;
+mod plan_index_create;
+mod plan_index_drop;
 mod plan_insert_into;
 mod plan_kill;
 mod plan_limit;
 mod plan_limit_by;
 mod plan_list;
+mod plan_merge_into;
 mod plan_node;
 mod plan_node_builder;
 mod plan_node_display;
@@ -82,16 +85,21 @@ mod plan_sink;
 mod plan_sort;
 mod plan_subqueries_set;
 mod plan_table_alter_cluster_key;
+mod plan_table_alter_column;
+mod plan_table_analyze;
 mod plan_table_create;
 mod plan_table_describe;
 mod plan_table_drop;
 mod plan_table_drop_cluster_key;
 mod plan_table_exists;
 mod plan_table_optimize;
+mod plan_table_recluster;
 mod plan_table_rename;
 mod plan_table_show_create;
 mod plan_table_truncate;
 mod plan_table_undrop;
+mod plan_table_vacuum;
+mod plan_update;
 mod plan_use_database;
 mod plan_user_alter;
 mod plan_user_create;
@@ -166,6 +174,8 @@ pub use plan_expression_visitor::ExpressionVisitor;
 pub use plan_expression_visitor::Recursion;
 pub use plan_filter::FilterPlan;
 pub use plan_having::HavingPlan;
+pub use plan_index_create::CreateIndexPlan;
+pub use plan_index_drop::DropIndexPlan;
 pub use plan_insert_into::InsertInputSource;
 pub use plan_insert_into::InsertPlan;
 pub use plan_insert_into::InsertValueBlock;
@@ -173,6 +183,9 @@ pub use plan_kill::KillPlan;
 pub use plan_limit::LimitPlan;
 pub use plan_limit_by::LimitByPlan;
 pub use plan_list::ListPlan;
+pub use plan_merge_into::MergeIntoPlan;
+pub use plan_merge_into::MergeMatchedClause;
+pub use plan_merge_into::MergeUnmatchedClause;
 pub use plan_node::PlanNode;
 pub use plan_node_builder::PlanBuilder;
 pub use plan_node_extras::Extras;
@@ -219,6 +232,9 @@ pub use plan_sink::SINK_SCHEMA;
 pub use plan_sort::SortPlan;
 pub use plan_subqueries_set::SubQueriesSetPlan;
 pub use plan_table_alter_cluster_key::AlterTableClusterKeyPlan;
+pub use plan_table_alter_column::AlterTableColumnAction;
+pub use plan_table_alter_column::AlterTableColumnPlan;
+pub use plan_table_analyze::AnalyzeTablePlan;
 pub use plan_table_create::CreateTablePlan;
 pub use plan_table_create::TableOptions;
 pub use plan_table_describe::DescribeTablePlan;
@@ -227,11 +243,14 @@ pub use plan_table_drop_cluster_key::DropTableClusterKeyPlan;
 pub use plan_table_exists::ExistsTablePlan;
 pub use plan_table_optimize::OptimizeTableAction;
 pub use plan_table_optimize::OptimizeTablePlan;
+pub use plan_table_recluster::ReclusterTablePlan;
 pub use plan_table_rename::RenameTableEntity;
 pub use plan_table_rename::RenameTablePlan;
 pub use plan_table_show_create::ShowCreateTablePlan;
 pub use plan_table_truncate::TruncateTablePlan;
 pub use plan_table_undrop::UndropTablePlan;
+pub use plan_table_vacuum::VacuumTablePlan;
+pub use plan_update::UpdatePlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_user_alter::AlterUserPlan;
 pub use plan_user_create::CreateUserPlan;
@@ -0,0 +1,68 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_meta_app::schema::TableIdent;
+
+use crate::Expression;
+
+/// MERGE INTO the target table using the rows of source.
+///
+/// The expressions are evaluated on the joined rows, in which the columns of target table
+/// are named by `target_columns` and the columns of source are named by `source_columns`.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeIntoPlan {
+    pub catalog_name: String,
+    pub database_name: String,
+    pub table_name: String,
+    pub table_id: TableIdent,
+    /// Names of the columns of target table, in the order of table schema
+    pub target_columns: Vec<String>,
+    /// Names of the columns of source, in the order of source schema
+    pub source_columns: Vec<String>,
+    /// Equi-join keys evaluated on the target rows, paired with `source_keys`
+    pub target_keys: Vec<Expression>,
+    /// Equi-join keys evaluated on the source rows, paired with `target_keys`
+    pub source_keys: Vec<Expression>,
+    /// Non-equi join conditions, evaluated on the joined rows
+    pub join_filter: Option<Expression>,
+    pub matched_clauses: Vec<MergeMatchedClause>,
+    pub unmatched_clauses: Vec<MergeUnmatchedClause>,
+}
+
+/// `WHEN MATCHED [AND <selection>] THEN UPDATE SET ... | DELETE`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeMatchedClause {
+    pub selection: Option<Expression>,
+    /// Mapping: (index of the column in table schema) -> (expression of the new value),
+    /// `None` if the matched rows are deleted
+    pub update_list: Option<Vec<(usize, Expression)>>,
+}
+
+/// `WHEN NOT MATCHED [AND <selection>] THEN INSERT ... VALUES ...`
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct MergeUnmatchedClause {
+    pub selection: Option<Expression>,
+    /// The value of each column in table schema, evaluated on the source rows
+    pub values: Vec<Expression>,
+}
+
+impl MergeIntoPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
@@ -0,0 +1,39 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct VacuumTablePlan {
+    pub catalog: String,
+    pub database: String,
+    pub table: String,
+    /// Overrides the retention period of the table if specified.
+    pub retain_hours: Option<u64>,
+    /// Only reports the files to be removed, but removes nothing.
+    pub dry_run: bool,
+}
+
+impl VacuumTablePlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::new(vec![
+            DataField::new("snapshots", u64::to_data_type()),
+            DataField::new("segments", u64::to_data_type()),
+            DataField::new("blocks", u64::to_data_type()),
+            DataField::new("bloom_indexes", u64::to_data_type()),
+        ]))
+    }
+}
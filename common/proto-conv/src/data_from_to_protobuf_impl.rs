@@ -69,7 +69,8 @@ impl FromToProto<pb::DataField> for dv::DataField {
                 reason: "DataField.data_type can not be None".to_string(),
             })?)?,
         )
-        .with_default_expr(p.default_expr);
+        .with_default_expr(p.default_expr)
+        .with_generated_expr(p.generated_expr);
         Ok(v)
     }
 
@@ -80,6 +81,7 @@ impl FromToProto<pb::DataField> for dv::DataField {
             name: self.name().clone(),
             default_expr: self.default_expr().cloned(),
             data_type: Some(self.data_type().to_pb()?),
+            generated_expr: self.generated_expr().cloned(),
         };
         Ok(p)
     }
@@ -126,6 +128,12 @@ impl FromToProto<pb::DataType> for dv::DataTypeImpl {
                 Ok(dv::DataTypeImpl::VariantObject(dv::VariantObjectType {}))
             }
             Dt::IntervalType(x) => Ok(dv::DataTypeImpl::Interval(dv::IntervalType::from_pb(x)?)),
+            // Both widths share the same message, the width is decided by the precision
+            Dt::DecimalType(x) if x.precision as usize > dv::MAX_DECIMAL_PRECISION => Ok(
+                dv::DataTypeImpl::Decimal256(dv::Decimal256Type::from_pb(x)?),
+            ),
+            Dt::DecimalType(x) => Ok(dv::DataTypeImpl::Decimal(dv::DecimalType::from_pb(x)?)),
+            Dt::MapType(x) => Ok(dv::DataTypeImpl::Map(dv::MapType::from_pb(*x)?)),
         }
     }
 
@@ -318,6 +326,36 @@ impl FromToProto<pb::DataType> for dv::DataTypeImpl {
                 };
                 Ok(p)
             }
+            dv::DataTypeImpl::Decimal(x) => {
+                let inn = x.to_pb()?;
+
+                let p = pb::DataType {
+                    ver: VER,
+                    min_compatible: MIN_COMPATIBLE_VER,
+                    dt: Some(Dt::DecimalType(inn)),
+                };
+                Ok(p)
+            }
+            dv::DataTypeImpl::Decimal256(x) => {
+                let inn = x.to_pb()?;
+
+                let p = pb::DataType {
+                    ver: VER,
+                    min_compatible: MIN_COMPATIBLE_VER,
+                    dt: Some(Dt::DecimalType(inn)),
+                };
+                Ok(p)
+            }
+            dv::DataTypeImpl::Map(x) => {
+                let inn = x.to_pb()?;
+
+                let p = pb::DataType {
+                    ver: VER,
+                    min_compatible: MIN_COMPATIBLE_VER,
+                    dt: Some(Dt::MapType(Box::new(inn))),
+                };
+                Ok(p)
+            }
         }
     }
 }
@@ -370,6 +408,52 @@ impl FromToProto<pb::Timestamp> for dv::TimestampType {
     }
 }
 
+impl FromToProto<pb::Decimal> for dv::DecimalType {
+    fn from_pb(p: pb::Decimal) -> Result<Self, Incompatible>
+    where Self: Sized {
+        check_ver(p.ver, p.min_compatible)?;
+        dv::DecimalType::try_create(p.precision as usize, p.scale as usize).map_err(|e| {
+            Incompatible {
+                reason: format!("invalid Decimal: {}", e.message()),
+            }
+        })
+    }
+
+    fn to_pb(&self) -> Result<pb::Decimal, Incompatible> {
+        let p = pb::Decimal {
+            ver: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            precision: self.precision() as u64,
+            scale: self.scale() as u64,
+        };
+
+        Ok(p)
+    }
+}
+
+impl FromToProto<pb::Decimal> for dv::Decimal256Type {
+    fn from_pb(p: pb::Decimal) -> Result<Self, Incompatible>
+    where Self: Sized {
+        check_ver(p.ver, p.min_compatible)?;
+        dv::Decimal256Type::try_create(p.precision as usize, p.scale as usize).map_err(|e| {
+            Incompatible {
+                reason: format!("invalid Decimal: {}", e.message()),
+            }
+        })
+    }
+
+    fn to_pb(&self) -> Result<pb::Decimal, Incompatible> {
+        let p = pb::Decimal {
+            ver: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            precision: self.precision() as u64,
+            scale: self.scale() as u64,
+        };
+
+        Ok(p)
+    }
+}
+
 impl FromToProto<pb::Struct> for dv::StructType {
     fn from_pb(p: pb::Struct) -> Result<Self, Incompatible>
     where Self: Sized {
@@ -442,6 +526,39 @@ impl FromToProto<pb::Array> for dv::ArrayType {
     }
 }
 
+impl FromToProto<pb::Map> for dv::MapType {
+    fn from_pb(p: pb::Map) -> Result<Self, Incompatible>
+    where Self: Sized {
+        check_ver(p.ver, p.min_compatible)?;
+
+        let key = p.key.ok_or_else(|| Incompatible {
+            reason: "Map.key can not be None".to_string(),
+        })?;
+        let value = p.value.ok_or_else(|| Incompatible {
+            reason: "Map.value can not be None".to_string(),
+        })?;
+
+        let key_dt = dv::DataTypeImpl::from_pb(key.as_ref().clone())?;
+        let value_dt = dv::DataTypeImpl::from_pb(value.as_ref().clone())?;
+
+        Ok(dv::MapType::create(key_dt, value_dt))
+    }
+
+    fn to_pb(&self) -> Result<pb::Map, Incompatible> {
+        let key_pb_type = self.key_type().to_pb()?;
+        let value_pb_type = self.value_type().to_pb()?;
+
+        let p = pb::Map {
+            ver: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            key: Some(Box::new(key_pb_type)),
+            value: Some(Box::new(value_pb_type)),
+        };
+
+        Ok(p)
+    }
+}
+
 impl FromToProto<pb::VariantArray> for dv::VariantArrayType {
     fn from_pb(p: pb::VariantArray) -> Result<Self, Incompatible>
     where Self: Sized {
@@ -22,6 +22,7 @@ use common_datavalues::chrono::DateTime;
 use common_datavalues::chrono::Utc;
 use common_meta_app::schema as mt;
 use common_protos::pb;
+use num::FromPrimitive;
 
 use crate::check_ver;
 use crate::FromToProto;
@@ -163,6 +164,16 @@ impl FromToProto<pb::TableMeta> for mt::TableMeta {
             },
             comment: p.comment,
             field_comments: p.field_comments,
+            indexes: p
+                .indexes
+                .into_iter()
+                .map(|(k, v)| Ok((k, mt::TableIndex::from_pb(v)?)))
+                .collect::<Result<_, Incompatible>>()?,
+            check_constraints: p
+                .check_constraints
+                .into_iter()
+                .map(|(k, v)| Ok((k, mt::TableCheckConstraint::from_pb(v)?)))
+                .collect::<Result<_, Incompatible>>()?,
             statistics: p
                 .statistics
                 .map(mt::TableStatistics::from_pb)
@@ -191,12 +202,92 @@ impl FromToProto<pb::TableMeta> for mt::TableMeta {
             },
             comment: self.comment.clone(),
             field_comments: self.field_comments.clone(),
+            indexes: self
+                .indexes
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.to_pb()?)))
+                .collect::<Result<_, Incompatible>>()?,
+            check_constraints: self
+                .check_constraints
+                .iter()
+                .map(|(k, v)| Ok((k.clone(), v.to_pb()?)))
+                .collect::<Result<_, Incompatible>>()?,
             statistics: Some(self.statistics.to_pb()?),
         };
         Ok(p)
     }
 }
 
+impl FromToProto<pb::table_index::TableIndexType> for mt::TableIndexType {
+    fn from_pb(p: pb::table_index::TableIndexType) -> Result<Self, Incompatible> {
+        match p {
+            pb::table_index::TableIndexType::Bloom => Ok(mt::TableIndexType::Bloom),
+            pb::table_index::TableIndexType::MinMax => Ok(mt::TableIndexType::MinMax),
+            pb::table_index::TableIndexType::Ngram => Ok(mt::TableIndexType::Ngram),
+            pb::table_index::TableIndexType::Set => Ok(mt::TableIndexType::Set),
+        }
+    }
+
+    fn to_pb(&self) -> Result<pb::table_index::TableIndexType, Incompatible> {
+        match self {
+            mt::TableIndexType::Bloom => Ok(pb::table_index::TableIndexType::Bloom),
+            mt::TableIndexType::MinMax => Ok(pb::table_index::TableIndexType::MinMax),
+            mt::TableIndexType::Ngram => Ok(pb::table_index::TableIndexType::Ngram),
+            mt::TableIndexType::Set => Ok(pb::table_index::TableIndexType::Set),
+        }
+    }
+}
+
+impl FromToProto<pb::TableIndex> for mt::TableIndex {
+    fn from_pb(p: pb::TableIndex) -> Result<Self, Incompatible> {
+        check_ver(p.ver, p.min_compatible)?;
+
+        let index_type = FromPrimitive::from_i32(p.index_type).ok_or_else(|| Incompatible {
+            reason: format!("invalid TableIndexType: {}", p.index_type),
+        })?;
+
+        let v = Self {
+            name: p.name,
+            column: p.column,
+            index_type: mt::TableIndexType::from_pb(index_type)?,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::TableIndex, Incompatible> {
+        let p = pb::TableIndex {
+            ver: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            name: self.name.clone(),
+            column: self.column.clone(),
+            index_type: self.index_type.to_pb()? as i32,
+        };
+        Ok(p)
+    }
+}
+
+impl FromToProto<pb::TableCheckConstraint> for mt::TableCheckConstraint {
+    fn from_pb(p: pb::TableCheckConstraint) -> Result<Self, Incompatible> {
+        check_ver(p.ver, p.min_compatible)?;
+
+        let v = Self {
+            name: p.name,
+            expr: p.expr,
+        };
+        Ok(v)
+    }
+
+    fn to_pb(&self) -> Result<pb::TableCheckConstraint, Incompatible> {
+        let p = pb::TableCheckConstraint {
+            ver: VER,
+            min_compatible: MIN_COMPATIBLE_VER,
+            name: self.name.clone(),
+            expr: self.expr.clone(),
+        };
+        Ok(p)
+    }
+}
+
 impl FromToProto<pb::TableStatistics> for mt::TableStatistics {
     fn from_pb(p: pb::TableStatistics) -> Result<Self, Incompatible> {
         check_ver(p.ver, p.min_compatible)?;
@@ -107,7 +107,8 @@ fn new_table_meta() -> mt::TableMeta {
                 dv::DataField::new("int8", dv::Int8Type::default().into()),
                 dv::DataField::new("int16", dv::Int16Type::default().into()),
                 dv::DataField::new("int32", dv::Int32Type::default().into()),
-                dv::DataField::new("int64", dv::Int64Type::default().into()),
+                dv::DataField::new("int64", dv::Int64Type::default().into())
+                    .with_generated_expr(Some("int32 + 1".to_string())),
                 dv::DataField::new("uint8", dv::UInt8Type::default().into()),
                 dv::DataField::new("uint16", dv::UInt16Type::default().into()),
                 dv::DataField::new("uint32", dv::UInt32Type::default().into()),
@@ -152,6 +153,8 @@ fn new_table_meta() -> mt::TableMeta {
         updated_on: Utc.ymd(2014, 11, 29).and_hms(12, 0, 10),
         comment: s("table_comment"),
         field_comments: vec!["c".to_string(); 21],
+        indexes: BTreeMap::new(),
+        check_constraints: BTreeMap::new(),
         drop_on: None,
         statistics: Default::default(),
     }
@@ -181,17 +184,74 @@ fn test_pb_from_to() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_decimal_pb_from_to() -> anyhow::Result<()> {
+    let data_type: dv::DataTypeImpl = dv::DecimalType::create(18, 4).into();
+    let p = data_type.to_pb()?;
+    let got = dv::DataTypeImpl::from_pb(p)?;
+    assert_eq!(data_type, got);
+    Ok(())
+}
+
+#[test]
+fn test_map_pb_from_to() -> anyhow::Result<()> {
+    let data_type = dv::MapType::new_impl(
+        dv::StringType::new_impl(),
+        dv::NullableType::new_impl(dv::Int64Type::new_impl()),
+    );
+    let p = data_type.to_pb()?;
+    let got = dv::DataTypeImpl::from_pb(p)?;
+    assert_eq!(data_type, got);
+    Ok(())
+}
+
+#[test]
+fn test_table_index_pb_from_to() -> anyhow::Result<()> {
+    let mut tbl = new_table_meta();
+    tbl.indexes = btreemap! {
+        s("idx_a") => mt::TableIndex {
+            name: s("idx_a"),
+            column: s("string"),
+            index_type: mt::TableIndexType::Ngram,
+        },
+        s("idx_b") => mt::TableIndex {
+            name: s("idx_b"),
+            column: s("int64"),
+            index_type: mt::TableIndexType::Set,
+        },
+    };
+    let p = tbl.to_pb()?;
+    let got = mt::TableMeta::from_pb(p)?;
+    assert_eq!(tbl, got);
+    Ok(())
+}
+
+#[test]
+fn test_table_check_constraint_pb_from_to() -> anyhow::Result<()> {
+    let mut tbl = new_table_meta();
+    tbl.check_constraints = btreemap! {
+        s("chk_a") => mt::TableCheckConstraint {
+            name: s("chk_a"),
+            expr: s("a >= 0"),
+        },
+    };
+    let p = tbl.to_pb()?;
+    let got = mt::TableMeta::from_pb(p)?;
+    assert_eq!(tbl, got);
+    Ok(())
+}
+
 #[test]
 fn test_incompatible() -> anyhow::Result<()> {
     let db_meta = new_db_meta();
     let mut p = db_meta.to_pb()?;
-    p.ver = 3;
-    p.min_compatible = 3;
+    p.ver = 7;
+    p.min_compatible = 7;
 
     let res = mt::DatabaseMeta::from_pb(p);
     assert_eq!(
         Incompatible {
-            reason: s("executable ver=2 is smaller than the message min compatible ver: 3")
+            reason: s("executable ver=6 is smaller than the message min compatible ver: 7")
         },
         res.unwrap_err()
     );
@@ -114,13 +114,13 @@ fn test_user_incompatible() -> anyhow::Result<()> {
     {
         let user_info = test_user_info();
         let mut p = user_info.to_pb()?;
-        p.ver = 3;
-        p.min_compatible = 3;
+        p.ver = 7;
+        p.min_compatible = 7;
 
         let res = mt::UserInfo::from_pb(p);
         assert_eq!(
             Incompatible {
-                reason: s("executable ver=2 is smaller than the message min compatible ver: 3")
+                reason: s("executable ver=6 is smaller than the message min compatible ver: 7")
             },
             res.unwrap_err()
         );
@@ -129,13 +129,13 @@ fn test_user_incompatible() -> anyhow::Result<()> {
     {
         let user_stage_info = test_user_stage_info();
         let mut p = user_stage_info.to_pb()?;
-        p.ver = 3;
-        p.min_compatible = 3;
+        p.ver = 7;
+        p.min_compatible = 7;
 
         let res = mt::UserStageInfo::from_pb(p);
         assert_eq!(
             Incompatible {
-                reason: s("executable ver=2 is smaller than the message min compatible ver: 3")
+                reason: s("executable ver=6 is smaller than the message min compatible ver: 7")
             },
             res.unwrap_err()
         );
@@ -123,6 +123,27 @@ impl Settings {
                 level: ScopeLevel::Session,
                 desc: "Enable planner v2 by setting this variable to 1, default value: 0",
             },
+            // enable_cbo
+            SettingValue {
+                default_value: DataValue::UInt64(1),
+                user_setting: UserSetting::create("enable_cbo", DataValue::UInt64(1)),
+                level: ScopeLevel::Session,
+                desc: "Enable cost-based join reordering of planner v2 if value != 0, default value: 1",
+            },
+            // enable_runtime_filter
+            SettingValue {
+                default_value: DataValue::UInt64(1),
+                user_setting: UserSetting::create("enable_runtime_filter", DataValue::UInt64(1)),
+                level: ScopeLevel::Session,
+                desc: "Enable runtime filters pushed from hash join into FUSE table scans if value != 0, default value: 1",
+            },
+            // enable_prewhere
+            SettingValue {
+                default_value: DataValue::UInt64(1),
+                user_setting: UserSetting::create("enable_prewhere", DataValue::UInt64(1)),
+                level: ScopeLevel::Session,
+                desc: "Enable prewhere, reading the columns of push-down filters first if value != 0, default value: 1",
+            },
             SettingValue {
                 default_value: DataValue::String("\n".as_bytes().to_vec()),
                 user_setting: UserSetting::create(
@@ -180,6 +201,33 @@ impl Settings {
                 level: ScopeLevel::Session,
                 desc: "The threshold of keys to open two-level aggregation, default value: 10000",
             },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "group_by_spilling_memory_limit",
+                    DataValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The query memory limit in bytes to spill group by states, 0 disables spilling, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "max_bytes_before_external_sort",
+                    DataValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The bytes of sorted blocks to spill in ORDER BY, 0 disables external sort, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "join_spilling_memory_limit",
+                    DataValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "The build side bytes of hash join to spill partitions, 0 disables spilling, default value: 0",
+            },
             SettingValue {
                 default_value: DataValue::UInt64(0),
                 user_setting: UserSetting::create("enable_async_insert", DataValue::UInt64(0)),
@@ -201,6 +249,30 @@ impl Settings {
                 level: ScopeLevel::Session,
                 desc: "The timeout in seconds for waiting for processing of async insert, default value: 100",
             },
+            SettingValue {
+                default_value: DataValue::UInt64(1000),
+                user_setting: UserSetting::create(
+                    "max_cte_recursive_depth",
+                    DataValue::UInt64(1000),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Max iterations of recursive common table expression, default value: 1000",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(0),
+                user_setting: UserSetting::create(
+                    "enable_query_result_cache",
+                    DataValue::UInt64(0),
+                ),
+                level: ScopeLevel::Session,
+                desc: "Enable the result cache of SELECT queries on FUSE tables if value != 0, default value: 0",
+            },
+            SettingValue {
+                default_value: DataValue::UInt64(24),
+                user_setting: UserSetting::create("data_retention_period", DataValue::UInt64(24)),
+                level: ScopeLevel::Session,
+                desc: "The retention period in hours of the table snapshots kept by VACUUM, default value: 24",
+            },
         ];
 
         let settings = Arc::new(RwLock::new(HashMap::default()));
@@ -288,6 +360,21 @@ impl Settings {
         self.try_get_u64(KEY)
     }
 
+    pub fn get_enable_cbo(&self) -> Result<u64> {
+        let key = "enable_cbo";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_enable_runtime_filter(&self) -> Result<u64> {
+        let key = "enable_runtime_filter";
+        self.try_get_u64(key)
+    }
+
+    pub fn get_enable_prewhere(&self) -> Result<u64> {
+        let key = "enable_prewhere";
+        self.try_get_u64(key)
+    }
+
     pub fn get_field_delimiter(&self) -> Result<Vec<u8>> {
         let key = "field_delimiter";
         self.check_and_get_setting_value(key)
@@ -334,6 +421,36 @@ impl Settings {
         self.try_set_u64(key, val, false)
     }
 
+    // Get the query memory limit to spill group by states
+    pub fn get_group_by_spilling_memory_limit(&self) -> Result<u64> {
+        let key = "group_by_spilling_memory_limit";
+        self.try_get_u64(key)
+    }
+
+    // Get the bytes of buffered blocks to spill sorted runs in ORDER BY
+    pub fn get_max_bytes_before_external_sort(&self) -> Result<u64> {
+        let key = "max_bytes_before_external_sort";
+        self.try_get_u64(key)
+    }
+
+    // Get the bytes of the hash join build side to spill partitions
+    pub fn get_join_spilling_memory_limit(&self) -> Result<u64> {
+        let key = "join_spilling_memory_limit";
+        self.try_get_u64(key)
+    }
+
+    // Get enable_query_result_cache.
+    pub fn get_enable_query_result_cache(&self) -> Result<u64> {
+        let key = "enable_query_result_cache";
+        self.try_get_u64(key)
+    }
+
+    // Get data_retention_period.
+    pub fn get_data_retention_period(&self) -> Result<u64> {
+        let key = "data_retention_period";
+        self.try_get_u64(key)
+    }
+
     pub fn get_enable_async_insert(&self) -> Result<u64> {
         let key = "enable_async_insert";
         self.try_get_u64(key)
@@ -364,6 +481,16 @@ impl Settings {
         self.try_set_u64(key, val, false)
     }
 
+    pub fn get_max_cte_recursive_depth(&self) -> Result<u64> {
+        let key = "max_cte_recursive_depth";
+        self.try_get_u64(key)
+    }
+
+    pub fn set_max_cte_recursive_depth(&self, val: u64) -> Result<()> {
+        let key = "max_cte_recursive_depth";
+        self.try_set_u64(key, val, false)
+    }
+
     pub fn has_setting(&self, key: &str) -> bool {
         let settings = self.settings.read();
         settings.get(key).is_some()
@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_exception::Result;
 use common_grpc::RpcClientConf;
+use common_management::BackgroundTaskApi;
+use common_management::BackgroundTaskMgr;
 use common_management::QuotaApi;
 use common_management::QuotaMgr;
 use common_management::RoleApi;
@@ -66,4 +69,16 @@ impl UserApiProvider {
     pub fn get_setting_api_client(&self, tenant: &str) -> Result<Arc<dyn SettingApi>> {
         Ok(Arc::new(SettingMgr::create(self.client.clone(), tenant)?))
     }
+
+    pub fn get_background_task_api_client(
+        &self,
+        tenant: &str,
+        lift_time: Duration,
+    ) -> Result<Arc<dyn BackgroundTaskApi>> {
+        Ok(Arc::new(BackgroundTaskMgr::create(
+            self.client.clone(),
+            tenant,
+            lift_time,
+        )?))
+    }
 }
@@ -200,6 +200,15 @@ async fn main(_global_tracker: Arc<RuntimeTracker>) -> common_exception::Result<
         info!("Databend async insert has been enabled.")
     }
 
+    // Background tasks
+    {
+        let background_task_manager = session_manager.get_background_task_manager();
+        background_task_manager.start(session_manager.clone());
+        if conf.query.background_tasks_enabled {
+            info!("Databend background tasks have been enabled.")
+        }
+    }
+
     // Print information to users.
     println!("Databend Query");
     println!();
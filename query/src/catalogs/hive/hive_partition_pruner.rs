@@ -82,6 +82,7 @@ impl HivePartitionPruner {
                     max: v,
                     null_count: 0,
                     in_memory_size: 0,
+                    distinct_values: None,
                 };
                 stats.insert(index as u32, column_stats);
             }
@@ -44,9 +44,11 @@ impl SystemDatabase {
             system::DatabasesTable::create(sys_db_meta.next_table_id()),
             Arc::new(system::TracingTable::create(sys_db_meta.next_table_id())),
             system::ProcessesTable::create(sys_db_meta.next_table_id()),
+            system::BackgroundTasksTable::create(sys_db_meta.next_table_id()),
             system::ConfigsTable::create(sys_db_meta.next_table_id()),
             system::MetricsTable::create(sys_db_meta.next_table_id()),
             system::ColumnsTable::create(sys_db_meta.next_table_id()),
+            system::ColumnStatisticsTable::create(sys_db_meta.next_table_id()),
             system::UsersTable::create(sys_db_meta.next_table_id()),
             Arc::new(system::QueryLogTable::create(
                 sys_db_meta.next_table_id(),
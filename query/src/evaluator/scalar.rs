@@ -98,6 +98,9 @@ impl Evaluator {
             Scalar::AggregateFunction(_) => Err(ErrorCode::LogicalError(
                 "Cannot evaluate aggregate function",
             )),
+            Scalar::WindowFunction(_) => {
+                Err(ErrorCode::LogicalError("Cannot evaluate window function"))
+            }
         }
     }
 }
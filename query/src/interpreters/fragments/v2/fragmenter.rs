@@ -30,9 +30,13 @@ use crate::sql::executor::Exchange;
 use crate::sql::executor::ExchangeSink;
 use crate::sql::executor::ExchangeSource;
 use crate::sql::executor::HashJoin;
+use crate::sql::executor::NestedLoopJoin;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalPlanReplacer;
+use crate::sql::executor::SetOperation;
+use crate::sql::executor::SortMergeJoin;
 use crate::sql::executor::TableScan;
+use crate::sql::executor::UnionAll;
 
 /// Visitor to split a `PhysicalPlan` into fragments.
 pub struct Fragmenter {
@@ -151,6 +155,86 @@ impl PhysicalPlanReplacer for Fragmenter {
         }))
     }
 
+    fn replace_nested_loop_join(&mut self, plan: &NestedLoopJoin) -> Result<PhysicalPlan> {
+        let mut fragments = vec![];
+        let left_input = self.replace(plan.left.as_ref())?;
+
+        // Consume current fragments to prevent them being consumed by `right_input`.
+        fragments.append(&mut self.fragments);
+        let right_input = self.replace(plan.right.as_ref())?;
+
+        fragments.append(&mut self.fragments);
+        self.fragments = fragments;
+
+        Ok(PhysicalPlan::NestedLoopJoin(NestedLoopJoin {
+            left: Box::new(left_input),
+            right: Box::new(right_input),
+            conditions: plan.conditions.clone(),
+            join_type: plan.join_type.clone(),
+        }))
+    }
+
+    fn replace_sort_merge_join(&mut self, plan: &SortMergeJoin) -> Result<PhysicalPlan> {
+        let mut fragments = vec![];
+        let left_input = self.replace(plan.left.as_ref())?;
+
+        // Consume current fragments to prevent them being consumed by `right_input`.
+        fragments.append(&mut self.fragments);
+        let right_input = self.replace(plan.right.as_ref())?;
+
+        fragments.append(&mut self.fragments);
+        self.fragments = fragments;
+
+        Ok(PhysicalPlan::SortMergeJoin(SortMergeJoin {
+            left: Box::new(left_input),
+            right: Box::new(right_input),
+            left_keys: plan.left_keys.clone(),
+            right_keys: plan.right_keys.clone(),
+            other_conditions: plan.other_conditions.clone(),
+            join_type: plan.join_type.clone(),
+        }))
+    }
+
+    fn replace_union_all(&mut self, plan: &UnionAll) -> Result<PhysicalPlan> {
+        let mut fragments = vec![];
+        let left_input = self.replace(plan.left.as_ref())?;
+
+        // Consume current fragments to prevent them being consumed by `right_input`.
+        fragments.append(&mut self.fragments);
+        let right_input = self.replace(plan.right.as_ref())?;
+
+        fragments.append(&mut self.fragments);
+        self.fragments = fragments;
+
+        Ok(PhysicalPlan::UnionAll(UnionAll {
+            left: Box::new(left_input),
+            right: Box::new(right_input),
+            left_columns: plan.left_columns.clone(),
+            right_columns: plan.right_columns.clone(),
+            schema: plan.schema.clone(),
+        }))
+    }
+
+    fn replace_set_operation(&mut self, plan: &SetOperation) -> Result<PhysicalPlan> {
+        let mut fragments = vec![];
+        let left_input = self.replace(plan.left.as_ref())?;
+
+        // Consume current fragments to prevent them being consumed by `right_input`.
+        fragments.append(&mut self.fragments);
+        let right_input = self.replace(plan.right.as_ref())?;
+
+        fragments.append(&mut self.fragments);
+        self.fragments = fragments;
+
+        Ok(PhysicalPlan::SetOperation(SetOperation {
+            left: Box::new(left_input),
+            right: Box::new(right_input),
+            op_type: plan.op_type.clone(),
+            left_columns: plan.left_columns.clone(),
+            right_columns: plan.right_columns.clone(),
+        }))
+    }
+
     fn replace_exchange(&mut self, plan: &Exchange) -> Result<PhysicalPlan> {
         // Recursively rewrite input
         let input = self.replace(plan.input.as_ref())?;
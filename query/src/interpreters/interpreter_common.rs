@@ -32,6 +32,7 @@ use tracing::warn;
 
 use crate::pipelines::executor::PipelineCompleteExecutor;
 use crate::pipelines::processors::TransformAddOn;
+use crate::pipelines::processors::TransformCheckConstraints;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 use crate::storages::stage::StageSourceHelper;
@@ -43,26 +44,61 @@ pub fn append2table(
     source_schema: DataSchemaRef,
     mut pipeline: Pipeline,
 ) -> Result<()> {
-    let need_fill_missing_columns = table.schema() != source_schema;
+    fill_missing_columns(ctx.clone(), table.clone(), source_schema, &mut pipeline)?;
+    check_table_constraints(ctx.clone(), table.clone(), &mut pipeline)?;
+
+    table.append2(ctx.clone(), &mut pipeline)?;
+    let async_runtime = ctx.get_storage_runtime();
+    let query_need_abort = ctx.query_need_abort();
+
+    pipeline.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
+    let executor = PipelineCompleteExecutor::try_create(async_runtime, query_need_abort, pipeline)?;
+    executor.execute()
+}
+
+/// Fills the columns missing in the source with the default values, and computes the values of
+/// the stored generated columns.
+pub fn fill_missing_columns(
+    ctx: Arc<QueryContext>,
+    table: Arc<dyn Table>,
+    source_schema: DataSchemaRef,
+    pipeline: &mut Pipeline,
+) -> Result<()> {
+    let table_schema = table.schema();
+    let need_fill_missing_columns =
+        table_schema != source_schema || table_schema.fields().iter().any(|f| f.is_generated());
     if need_fill_missing_columns {
         pipeline.add_transform(|transform_input_port, transform_output_port| {
             TransformAddOn::try_create(
                 transform_input_port,
                 transform_output_port,
                 source_schema.clone(),
-                table.schema(),
+                table_schema.clone(),
                 ctx.clone(),
             )
         })?;
     }
+    Ok(())
+}
 
-    table.append2(ctx.clone(), &mut pipeline)?;
-    let async_runtime = ctx.get_storage_runtime();
-    let query_need_abort = ctx.query_need_abort();
-
-    pipeline.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
-    let executor = PipelineCompleteExecutor::try_create(async_runtime, query_need_abort, pipeline)?;
-    executor.execute()
+/// Rejects the rows violating the CHECK constraints of the table.
+pub fn check_table_constraints(
+    ctx: Arc<QueryContext>,
+    table: Arc<dyn Table>,
+    pipeline: &mut Pipeline,
+) -> Result<()> {
+    let table_info = table.get_table_info();
+    if !table_info.meta.check_constraints.is_empty() {
+        pipeline.add_transform(|transform_input_port, transform_output_port| {
+            TransformCheckConstraints::try_create(
+                transform_input_port,
+                transform_output_port,
+                ctx.clone(),
+                table_info,
+            )
+        })?;
+    }
+    Ok(())
 }
 
 pub async fn commit2table(
@@ -29,7 +29,9 @@ use regex::Regex;
 use tracing::info;
 
 use super::append2table;
+use super::check_table_constraints;
 use super::commit2table;
+use super::fill_missing_columns;
 use crate::interpreters::Interpreter;
 use crate::interpreters::SelectInterpreterV2;
 use crate::pipelines::executor::PipelineCompleteExecutor;
@@ -154,6 +156,14 @@ impl CopyInterpreterV2 {
 
         let table = ctx.get_table(catalog_name, db_name, tbl_name).await?;
 
+        // the stage files hold the columns of the table except the generated ones
+        fill_missing_columns(
+            ctx.clone(),
+            table.clone(),
+            read_source_plan.schema(),
+            &mut pipeline,
+        )?;
+        check_table_constraints(ctx.clone(), table.clone(), &mut pipeline)?;
         table.append2(ctx.clone(), &mut pipeline)?;
         pipeline.set_max_threads(settings.get_max_threads()? as usize);
 
@@ -123,15 +123,29 @@ impl InterpreterFactoryV2 {
             Plan::AlterTableClusterKey(alter_table_cluster_key) => Ok(Arc::new(
                 AlterTableClusterKeyInterpreter::try_create(ctx, *alter_table_cluster_key.clone())?,
             )),
+            Plan::AlterTableColumn(alter_table_column) => Ok(Arc::new(
+                AlterTableColumnInterpreter::try_create(ctx, *alter_table_column.clone())?,
+            )),
             Plan::DropTableClusterKey(drop_table_cluster_key) => Ok(Arc::new(
                 DropTableClusterKeyInterpreter::try_create(ctx, *drop_table_cluster_key.clone())?,
             )),
+            Plan::ReclusterTable(recluster_table) => Ok(Arc::new(
+                ReclusterTableInterpreter::try_create(ctx, *recluster_table.clone())?,
+            )),
             Plan::TruncateTable(truncate_table) => Ok(Arc::new(
                 TruncateTableInterpreter::try_create(ctx, *truncate_table.clone())?,
             )),
             Plan::OptimizeTable(optimize_table) => Ok(Arc::new(
                 OptimizeTableInterpreter::try_create(ctx, *optimize_table.clone())?,
             )),
+            Plan::VacuumTable(vacuum_table) => Ok(Arc::new(VacuumTableInterpreter::try_create(
+                ctx,
+                *vacuum_table.clone(),
+            )?)),
+            Plan::AnalyzeTable(analyze_table) => Ok(Arc::new(AnalyzeTableInterpreter::try_create(
+                ctx,
+                *analyze_table.clone(),
+            )?)),
             Plan::ExistsTable(exists_table) => Ok(Arc::new(ExistsTableInterpreter::try_create(
                 ctx,
                 *exists_table.clone(),
@@ -151,6 +165,16 @@ impl InterpreterFactoryV2 {
                 *drop_view.clone(),
             )?)),
 
+            // Indexes
+            Plan::CreateIndex(create_index) => Ok(Arc::new(CreateIndexInterpreter::try_create(
+                ctx,
+                *create_index.clone(),
+            )?)),
+            Plan::DropIndex(drop_index) => Ok(Arc::new(DropIndexInterpreter::try_create(
+                ctx,
+                *drop_index.clone(),
+            )?)),
+
             // Users
             Plan::CreateUser(create_user) => Ok(Arc::new(CreateUserInterpreter::try_create(
                 ctx,
@@ -172,6 +196,30 @@ impl InterpreterFactoryV2 {
                 *delete.clone(),
             )?)),
 
+            Plan::Update(update) => Ok(Arc::new(UpdateInterpreter::try_create(
+                ctx,
+                *update.clone(),
+            )?)),
+
+            Plan::MergeInto(merge_into) => Ok(Arc::new(MergeIntoInterpreter::try_create(
+                ctx,
+                *merge_into.clone(),
+            )?)),
+
+            // Transactions
+            Plan::Begin => Ok(Arc::new(TransactionInterpreter::try_create(
+                ctx,
+                TransactionAction::Begin,
+            )?)),
+            Plan::Commit => Ok(Arc::new(TransactionInterpreter::try_create(
+                ctx,
+                TransactionAction::Commit,
+            )?)),
+            Plan::Rollback => Ok(Arc::new(TransactionInterpreter::try_create(
+                ctx,
+                TransactionAction::Rollback,
+            )?)),
+
             // Roles
             Plan::CreateRole(create_role) => Ok(Arc::new(CreateRoleInterpreter::try_create(
                 ctx,
@@ -0,0 +1,115 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_meta_app::schema::CreateTableIndexReq;
+use common_meta_app::schema::TableIndexType;
+use common_meta_types::GrantObject;
+use common_meta_types::UserPrivilegeType;
+use common_planners::CreateIndexPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+use tracing::info;
+
+use crate::interpreters::Interpreter;
+use crate::sessions::BackgroundTaskType;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub struct CreateIndexInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: CreateIndexPlan,
+}
+
+impl CreateIndexInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: CreateIndexPlan) -> Result<Self> {
+        Ok(CreateIndexInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CreateIndexInterpreter {
+    fn name(&self) -> &str {
+        "CreateIndexInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let plan = &self.plan;
+        self.ctx
+            .get_current_session()
+            .validate_privilege(
+                &GrantObject::Table(
+                    plan.catalog.clone(),
+                    plan.database.clone(),
+                    plan.table.clone(),
+                ),
+                UserPrivilegeType::Alter,
+            )
+            .await?;
+
+        let catalog = self.ctx.get_catalog(&plan.catalog)?;
+        let table = catalog
+            .get_table(&plan.tenant, &plan.database, &plan.table)
+            .await?;
+        let exists = table
+            .get_table_info()
+            .meta
+            .indexes
+            .contains_key(&plan.index.name);
+
+        catalog
+            .create_table_index(CreateTableIndexReq {
+                if_not_exists: plan.if_not_exists,
+                table_id: table.get_id(),
+                index: plan.index.clone(),
+            })
+            .await?;
+
+        // the bloom filters and the min/max indexes are built for all the blocks already,
+        // the other types of indexes are backfilled for the existing blocks in background.
+        let need_backfill = matches!(
+            plan.index.index_type,
+            TableIndexType::Ngram | TableIndexType::Set
+        );
+        if !exists && need_backfill {
+            // the task reloads the table, to build the index just created
+            let ctx: Arc<dyn TableContext> = self.ctx.clone();
+            let session_mgr = self.ctx.get_current_session().get_session_manager();
+            let id = session_mgr.get_background_task_manager().submit_task(
+                &ctx,
+                &session_mgr,
+                BackgroundTaskType::BuildIndex,
+                &plan.catalog,
+                &plan.database,
+                &plan.table,
+                table.get_id(),
+                format!("index {} created", plan.index.name),
+            )?;
+            info!(
+                "background task {} submitted to build index {} of table {}",
+                id,
+                plan.index,
+                table.name()
+            );
+        }
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
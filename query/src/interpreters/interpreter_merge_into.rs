@@ -0,0 +1,113 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::stream::ProcessorExecutorStream;
+use crate::interpreters::Interpreter;
+use crate::interpreters::SelectInterpreterV2;
+use crate::pipelines::executor::PipelinePullingExecutor;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::plans::MergeInto;
+use crate::sql::plans::Plan;
+use crate::storages::fuse::FuseTable;
+
+/// interprets MergeInto
+pub struct MergeIntoInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: MergeInto,
+}
+
+impl MergeIntoInterpreter {
+    /// Create the MergeIntoInterpreter from MergeInto
+    pub fn try_create(ctx: Arc<QueryContext>, plan: MergeInto) -> Result<Self> {
+        Ok(MergeIntoInterpreter { ctx, plan })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for MergeIntoInterpreter {
+    /// Get the name of current interpreter
+    fn name(&self) -> &str {
+        "MergeIntoInterpreter"
+    }
+
+    /// Get the schema of MergeInto
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "merge_into_interpreter_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        // TODO check privilege
+        let merge_into_plan = &self.plan.merge_into_plan;
+        let catalog_name = merge_into_plan.catalog_name.as_str();
+        let db_name = merge_into_plan.database_name.as_str();
+        let tbl_name = merge_into_plan.table_name.as_str();
+        let tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
+        // the target table is probed by the hash join state built from the source
+        let fuse_table = FuseTable::try_from_table(tbl.as_ref()).map_err(|_| {
+            ErrorCode::UnImplement(format!(
+                "table {},  of engine type {}, does not support MERGE INTO",
+                tbl.name(),
+                tbl.engine(),
+            ))
+        })?;
+
+        // the source is streamed into the hash table before touching the target table, the
+        // rows are merged into the table and committed as a single snapshot
+        let select_interpreter = match self.plan.source.as_ref() {
+            Plan::Query {
+                s_expr,
+                metadata,
+                bind_context,
+            } => SelectInterpreterV2::try_create(
+                self.ctx.clone(),
+                *bind_context.clone(),
+                s_expr.clone(),
+                metadata.clone(),
+            )?,
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "the source of MERGE INTO must be a query",
+                ));
+            }
+        };
+        let pipeline = select_interpreter.create_new_pipeline().await?;
+        let executor = PipelinePullingExecutor::try_create(
+            self.ctx.get_storage_runtime(),
+            self.ctx.query_need_abort(),
+            pipeline,
+        )?;
+        let source = Box::pin(ProcessorExecutorStream::create(executor)?);
+
+        fuse_table
+            .do_merge_into(self.ctx.clone(), merge_into_plan, source)
+            .await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            Arc::new(DataSchema::empty()),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -17,21 +17,51 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
 use super::plan_schedulers::schedule_query_v2;
 use crate::clusters::ClusterHelper;
 use crate::interpreters::stream::ProcessorExecutorStream;
+use crate::interpreters::stream::QueryResultCacheStream;
 use crate::interpreters::Interpreter;
 use crate::pipelines::executor::PipelinePullingExecutor;
 use crate::pipelines::Pipeline;
 use crate::sessions::QueryContext;
+use crate::sessions::QueryResultCache;
 use crate::sessions::TableContext;
+use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalPlanBuilder;
 use crate::sql::executor::PipelineBuilder;
 use crate::sql::optimizer::SExpr;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
 use crate::sql::BindContext;
 use crate::sql::MetadataRef;
+use crate::storages::fuse::FuseTable;
+use crate::storages::Table;
+
+/// The settings which only tune the execution of a query, they are not a part of the key of
+/// the query result cache as they don't change the result.
+const RESULT_NEUTRAL_SETTINGS: &[&str] = &[
+    "max_threads",
+    "flight_client_timeout",
+    "storage_read_buffer_size",
+    "enable_new_processor_framework",
+    "enable_cbo",
+    "enable_runtime_filter",
+    "enable_prewhere",
+    "group_by_two_level_threshold",
+    "group_by_spilling_memory_limit",
+    "max_bytes_before_external_sort",
+    "join_spilling_memory_limit",
+    "enable_async_insert",
+    "wait_for_async_insert",
+    "wait_for_async_insert_timeout",
+    "enable_query_result_cache",
+    "data_retention_period",
+];
 
 /// Interpret SQL query with ne&w SQL planner
 pub struct SelectInterpreterV2 {
@@ -55,31 +85,15 @@ impl SelectInterpreterV2 {
             metadata,
         })
     }
-}
-
-#[async_trait::async_trait]
-impl Interpreter for SelectInterpreterV2 {
-    fn name(&self) -> &str {
-        "SelectInterpreterV2"
-    }
 
-    fn schema(&self) -> DataSchemaRef {
-        self.bind_context.output_schema()
-    }
-
-    #[tracing::instrument(level = "debug", name = "select_interpreter_v2_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
-    async fn execute(&self) -> Result<SendableDataBlockStream> {
-        let builder = PhysicalPlanBuilder::new(self.metadata.clone());
-        let physical_plan = builder.build(&self.s_expr)?;
-        if let Some(handle) = self.ctx.get_http_query() {
-            return handle
-                .execute(self.ctx.clone(), &physical_plan, &self.bind_context.columns)
-                .await;
-        }
+    async fn execute_physical_plan(
+        &self,
+        physical_plan: &PhysicalPlan,
+    ) -> Result<SendableDataBlockStream> {
         if self.ctx.get_cluster().is_empty() {
             let last_schema = physical_plan.output_schema()?;
             let pb = PipelineBuilder::create(self.ctx.clone());
-            let mut build_res = pb.finalize(&physical_plan)?;
+            let mut build_res = pb.finalize(physical_plan)?;
 
             // Render result set with given output schema
             PipelineBuilder::render_result_set(
@@ -103,7 +117,7 @@ impl Interpreter for SelectInterpreterV2 {
         } else {
             // Cluster mode
             let build_res =
-                schedule_query_v2(self.ctx.clone(), &self.bind_context.columns, &physical_plan)
+                schedule_query_v2(self.ctx.clone(), &self.bind_context.columns, physical_plan)
                     .await?;
 
             let async_runtime = self.ctx.get_storage_runtime();
@@ -119,6 +133,164 @@ impl Interpreter for SelectInterpreterV2 {
         }
     }
 
+    /// The key of the query result cache, `None` if the result of the query can't be cached.
+    ///
+    /// A result is cached only if all the referenced tables are FUSE tables and the query is
+    /// deterministic, the snapshot of each table is a part of the key, so are the tenant and
+    /// the settings which may change the result.
+    ///
+    /// The http handler also checks it before execution, the results of the queries which are
+    /// not cached are written into the result table by the pipeline directly.
+    pub fn result_cache_key(
+        ctx: &QueryContext,
+        s_expr: &SExpr,
+        metadata: &MetadataRef,
+    ) -> Result<Option<String>> {
+        let settings = ctx.get_settings();
+        if settings.get_enable_query_result_cache()? == 0 {
+            return Ok(None);
+        }
+
+        let query = match ctx.get_normalized_query() {
+            None => return Ok(None),
+            Some(query) => query,
+        };
+
+        if !Self::is_deterministic(s_expr) {
+            return Ok(None);
+        }
+
+        let metadata = metadata.read();
+        if metadata.tables().is_empty() {
+            return Ok(None);
+        }
+
+        let mut parts = vec![
+            query,
+            ctx.get_tenant(),
+            ctx.get_current_catalog(),
+            ctx.get_current_database(),
+            ctx.get_current_user()?.identity().to_string(),
+        ];
+
+        for (name, value) in settings.get_setting_values_short() {
+            if !RESULT_NEUTRAL_SETTINGS.contains(&name.as_str()) {
+                parts.push(format!("{}={}", name, value));
+            }
+        }
+
+        for table_entry in metadata.tables() {
+            let table = match FuseTable::try_from_table(table_entry.table.as_ref()) {
+                Err(_) => return Ok(None),
+                Ok(table) => table,
+            };
+
+            parts.push(table_entry.catalog.clone());
+            parts.push(table.get_id().to_string());
+            parts.push(table.snapshot_loc().unwrap_or_default());
+        }
+
+        Ok(Some(QueryResultCache::cache_key(&parts)))
+    }
+
+    fn is_deterministic(s_expr: &SExpr) -> bool {
+        let scalars: Vec<&Scalar> = match s_expr.plan() {
+            RelOperator::LogicalGet(plan) => plan.push_down_predicates.iter().flatten().collect(),
+            RelOperator::PhysicalScan(plan) => plan.push_down_predicates.iter().flatten().collect(),
+            RelOperator::LogicalInnerJoin(plan) => plan
+                .left_conditions
+                .iter()
+                .chain(plan.right_conditions.iter())
+                .chain(plan.other_conditions.iter())
+                .collect(),
+            RelOperator::PhysicalHashJoin(plan) => plan
+                .build_keys
+                .iter()
+                .chain(plan.probe_keys.iter())
+                .chain(plan.other_conditions.iter())
+                .collect(),
+            RelOperator::PhysicalNestedLoopJoin(plan) => plan.conditions.iter().collect(),
+            RelOperator::PhysicalSortMergeJoin(plan) => plan
+                .left_keys
+                .iter()
+                .chain(plan.right_keys.iter())
+                .chain(plan.other_conditions.iter())
+                .collect(),
+            RelOperator::EvalScalar(plan) => plan.items.iter().map(|item| &item.scalar).collect(),
+            RelOperator::Filter(plan) => plan.predicates.iter().collect(),
+            RelOperator::Aggregate(plan) => {
+                // Aggregate functions are not deterministic by themselves, check their arguments.
+                let mut scalars = plan
+                    .group_items
+                    .iter()
+                    .map(|item| &item.scalar)
+                    .collect::<Vec<_>>();
+                for item in plan.aggregate_functions.iter() {
+                    match &item.scalar {
+                        Scalar::AggregateFunction(agg) => scalars.extend(agg.args.iter()),
+                        scalar => scalars.push(scalar),
+                    }
+                }
+                scalars
+            }
+            RelOperator::Window(_) => return false,
+            _ => vec![],
+        };
+
+        scalars.iter().all(|scalar| scalar.is_deterministic())
+            && s_expr.children().iter().all(Self::is_deterministic)
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for SelectInterpreterV2 {
+    fn name(&self) -> &str {
+        "SelectInterpreterV2"
+    }
+
+    fn schema(&self) -> DataSchemaRef {
+        self.bind_context.output_schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "select_interpreter_v2_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let result_cache_key = Self::result_cache_key(&self.ctx, &self.s_expr, &self.metadata)?;
+        if let Some(key) = &result_cache_key {
+            let cache = self.ctx.get_query_result_cache();
+            let format_settings = self.ctx.get_format_settings()?;
+            if let Some(blocks) = cache.get(key, self.schema(), format_settings) {
+                return Ok(Box::pin(DataBlockStream::create(
+                    self.schema(),
+                    None,
+                    blocks,
+                )));
+            }
+        }
+
+        let builder = PhysicalPlanBuilder::new(self.metadata.clone());
+        let physical_plan = builder.build(&self.s_expr)?;
+
+        // The result set of the http handler is written into the result table by the pipeline,
+        // so a query to be cached is executed as a stream.
+        if result_cache_key.is_none() {
+            if let Some(handle) = self.ctx.get_http_query() {
+                return handle
+                    .execute(self.ctx.clone(), &physical_plan, &self.bind_context.columns)
+                    .await;
+            }
+        }
+
+        let stream = self.execute_physical_plan(&physical_plan).await?;
+        match result_cache_key {
+            None => Ok(stream),
+            Some(key) => Ok(Box::pin(QueryResultCacheStream::create(
+                stream,
+                self.ctx.get_query_result_cache(),
+                key,
+            ))),
+        }
+    }
+
     /// This method will create a new pipeline
     /// The QueryPipelineBuilder will use the optimized plan to generate a Pipeline
     async fn create_new_pipeline(&self) -> Result<Pipeline> {
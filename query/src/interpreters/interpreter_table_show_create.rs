@@ -76,6 +76,13 @@ impl Interpreter for ShowCreateTableInterpreter {
                     }
                     None => "".to_string(),
                 };
+                let generated_expr = match field.generated_expr() {
+                    Some(expr) => {
+                        let expression = PlanParser::parse_expr(expr)?;
+                        format!(" AS ({}) STORED", expression.column_name())
+                    }
+                    None => "".to_string(),
+                };
                 // compatibility: creating table in the old planner will not have `fields_comments`
                 let comment = if field_comments.len() == n_fields && !field_comments[idx].is_empty()
                 {
@@ -88,15 +95,24 @@ impl Interpreter for ShowCreateTableInterpreter {
                     "".to_string()
                 };
                 let column = format!(
-                    "  `{}` {}{}{}",
+                    "  `{}` {}{}{}{}",
                     field.name(),
                     format_data_type_sql(field.data_type()),
                     default_expr,
+                    generated_expr,
                     comment
                 );
 
                 columns.push(column);
             }
+            for check_constraint in table.get_table_info().meta.check_constraints.values() {
+                let expression = PlanParser::parse_expr(&check_constraint.expr)?;
+                columns.push(format!(
+                    "  CONSTRAINT `{}` CHECK ({})",
+                    check_constraint.name,
+                    expression.column_name()
+                ));
+            }
             // Format is:
             //  (
             //      x,
@@ -0,0 +1,59 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_exception::Result;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::sessions::QueryContext;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransactionAction {
+    Begin,
+    Commit,
+    Rollback,
+}
+
+/// Starts, commits or rolls back the transaction of the session.
+pub struct TransactionInterpreter {
+    ctx: Arc<QueryContext>,
+    action: TransactionAction,
+}
+
+impl TransactionInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, action: TransactionAction) -> Result<Self> {
+        Ok(TransactionInterpreter { ctx, action })
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for TransactionInterpreter {
+    fn name(&self) -> &str {
+        "TransactionInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        match self.action {
+            TransactionAction::Begin => self.ctx.begin_transaction()?,
+            TransactionAction::Commit => self.ctx.commit_transaction().await?,
+            TransactionAction::Rollback => self.ctx.rollback_transaction().await?,
+        }
+        let schema = Arc::new(DataSchema::empty());
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
+    }
+}
@@ -34,10 +34,13 @@ mod interpreter_explain_v2;
 mod interpreter_factory;
 mod interpreter_factory_interceptor;
 mod interpreter_factory_v2;
+mod interpreter_index_create;
+mod interpreter_index_drop;
 mod interpreter_insert;
 mod interpreter_insert_v2;
 mod interpreter_kill;
 mod interpreter_list;
+mod interpreter_merge_into;
 mod interpreter_presign;
 mod interpreter_privilege_grant;
 mod interpreter_privilege_revoke;
@@ -60,16 +63,22 @@ mod interpreter_show_stages;
 mod interpreter_show_tables;
 mod interpreter_show_tables_status;
 mod interpreter_show_users;
+mod interpreter_table_alter_column;
+mod interpreter_table_analyze;
 mod interpreter_table_create;
 mod interpreter_table_create_v2;
 mod interpreter_table_describe;
 mod interpreter_table_drop;
 mod interpreter_table_exists;
 mod interpreter_table_optimize;
+mod interpreter_table_recluster;
 mod interpreter_table_rename;
 mod interpreter_table_show_create;
 mod interpreter_table_truncate;
 mod interpreter_table_undrop;
+mod interpreter_table_vacuum;
+mod interpreter_transaction;
+mod interpreter_update;
 mod interpreter_use_database;
 mod interpreter_user_alter;
 mod interpreter_user_create;
@@ -98,7 +107,9 @@ pub use interpreter_call::CallInterpreter;
 pub use interpreter_cluster_key_alter::AlterTableClusterKeyInterpreter;
 pub use interpreter_cluster_key_drop::DropTableClusterKeyInterpreter;
 pub use interpreter_common::append2table;
+pub use interpreter_common::check_table_constraints;
 pub use interpreter_common::commit2table;
+pub use interpreter_common::fill_missing_columns;
 pub use interpreter_common::list_files_from_dal;
 pub use interpreter_common::list_files_from_meta_api;
 pub use interpreter_database_create::CreateDatabaseInterpreter;
@@ -113,10 +124,13 @@ pub use interpreter_explain_v2::ExplainInterpreterV2;
 pub use interpreter_factory::InterpreterFactory;
 pub use interpreter_factory_interceptor::InterceptorInterpreter;
 pub use interpreter_factory_v2::InterpreterFactoryV2;
+pub use interpreter_index_create::CreateIndexInterpreter;
+pub use interpreter_index_drop::DropIndexInterpreter;
 pub use interpreter_insert::InsertInterpreter;
 pub use interpreter_insert_v2::InsertInterpreterV2;
 pub use interpreter_kill::KillInterpreter;
 pub use interpreter_list::ListInterpreter;
+pub use interpreter_merge_into::MergeIntoInterpreter;
 pub use interpreter_privilege_grant::GrantPrivilegeInterpreter;
 pub use interpreter_privilege_revoke::RevokePrivilegeInterpreter;
 pub use interpreter_query_log::InterpreterQueryLog;
@@ -140,15 +154,22 @@ pub use interpreter_show_stages::ShowStagesInterpreter;
 pub use interpreter_show_tables::ShowTablesInterpreter;
 pub use interpreter_show_tables_status::ShowTablesStatusInterpreter;
 pub use interpreter_show_users::ShowUsersInterpreter;
+pub use interpreter_table_alter_column::AlterTableColumnInterpreter;
+pub use interpreter_table_analyze::AnalyzeTableInterpreter;
 pub use interpreter_table_create::CreateTableInterpreter;
 pub use interpreter_table_describe::DescribeTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
 pub use interpreter_table_exists::ExistsTableInterpreter;
 pub use interpreter_table_optimize::OptimizeTableInterpreter;
+pub use interpreter_table_recluster::ReclusterTableInterpreter;
 pub use interpreter_table_rename::RenameTableInterpreter;
 pub use interpreter_table_show_create::ShowCreateTableInterpreter;
 pub use interpreter_table_truncate::TruncateTableInterpreter;
 pub use interpreter_table_undrop::UndropTableInterpreter;
+pub use interpreter_table_vacuum::VacuumTableInterpreter;
+pub use interpreter_transaction::TransactionAction;
+pub use interpreter_transaction::TransactionInterpreter;
+pub use interpreter_update::UpdateInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
 pub use interpreter_user_alter::AlterUserInterpreter;
 pub use interpreter_user_create::CreateUserInterpreter;
@@ -13,5 +13,7 @@
 // limitations under the License.
 
 mod processor_executor_stream;
+mod query_result_cache_stream;
 
 pub use processor_executor_stream::ProcessorExecutorStream;
+pub use query_result_cache_stream::QueryResultCacheStream;
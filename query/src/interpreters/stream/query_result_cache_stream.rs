@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::sessions::QueryResultCache;
+
+/// Pass through the result blocks of a query and put them into the query result cache
+/// once the query is finished.
+///
+/// The blocks are not cached if the query fails or the result is too large for the cache.
+pub struct QueryResultCacheStream {
+    input: SendableDataBlockStream,
+    cache: Arc<QueryResultCache>,
+    key: String,
+    blocks: Vec<DataBlock>,
+    bytes: usize,
+    max_bytes: usize,
+    discarded: bool,
+}
+
+impl QueryResultCacheStream {
+    pub fn create(
+        input: SendableDataBlockStream,
+        cache: Arc<QueryResultCache>,
+        key: String,
+    ) -> Self {
+        let max_bytes = cache.max_entry_bytes() as usize;
+        QueryResultCacheStream {
+            input,
+            cache,
+            key,
+            blocks: vec![],
+            bytes: 0,
+            max_bytes,
+            discarded: false,
+        }
+    }
+
+    fn discard(&mut self) {
+        self.discarded = true;
+        self.blocks.clear();
+    }
+}
+
+impl Stream for QueryResultCacheStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_ = Pin::get_mut(self);
+        let next = self_.input.poll_next_unpin(ctx);
+        match &next {
+            Poll::Ready(Some(Ok(block))) if !self_.discarded => {
+                self_.bytes += block.memory_size();
+                match self_.bytes > self_.max_bytes {
+                    true => self_.discard(),
+                    false => self_.blocks.push(block.clone()),
+                }
+            }
+            Poll::Ready(Some(Err(_))) => self_.discard(),
+            Poll::Ready(None) if !self_.discarded => {
+                let blocks = std::mem::take(&mut self_.blocks);
+                if let Err(cause) = self_.cache.put(self_.key.clone(), blocks) {
+                    tracing::warn!("Cannot put query result cache {}: {}", self_.key, cause);
+                }
+                // Put the result only once even if the stream is polled again.
+                self_.discarded = true;
+            }
+            _ => {}
+        }
+        next
+    }
+}
@@ -38,7 +38,6 @@ use common_planners::WindowFuncPlan;
 
 use super::processors::transforms::TransformWindowFunc;
 use super::processors::transforms::WindowFuncCompact;
-use super::processors::SortMergeCompactor;
 use crate::pipelines::pipeline::Pipeline;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::port::OutputPort;
@@ -55,7 +54,7 @@ use crate::pipelines::processors::TransformFilter;
 use crate::pipelines::processors::TransformHaving;
 use crate::pipelines::processors::TransformLimit;
 use crate::pipelines::processors::TransformLimitBy;
-use crate::pipelines::processors::TransformSortMerge;
+use crate::pipelines::processors::TransformSortExternal;
 use crate::pipelines::processors::TransformSortPartial;
 use crate::pipelines::Pipe;
 use crate::pipelines::PipelineBuildResult;
@@ -402,15 +401,17 @@ impl PlanVisitor for QueryPipelineBuilder {
         // processor 1: [sorted blocks ...] ---> merge to one sorted block
         // processor 2: [sorted blocks ...] ---> merge to one sorted block
         // processor 3: [sorted blocks ...] ---> merge to one sorted block
+        //
+        // The sorted blocks are spilled as sorted runs if they exceed
+        // max_bytes_before_external_sort, and the runs are merged block by block.
         self.main_pipeline
             .add_transform(|transform_input_port, transform_output_port| {
-                TransformSortMerge::try_create(
+                TransformSortExternal::try_create(
+                    self.ctx.clone(),
                     transform_input_port,
                     transform_output_port,
-                    SortMergeCompactor::new(
-                        rows_limit,
-                        get_sort_descriptions(&plan.schema, &plan.order_by)?,
-                    ),
+                    rows_limit,
+                    get_sort_descriptions(&plan.schema, &plan.order_by)?,
                 )
             })?;
 
@@ -422,13 +423,12 @@ impl PlanVisitor for QueryPipelineBuilder {
         self.main_pipeline.resize(1)?;
         self.main_pipeline
             .add_transform(|transform_input_port, transform_output_port| {
-                TransformSortMerge::try_create(
+                TransformSortExternal::try_create(
+                    self.ctx.clone(),
                     transform_input_port,
                     transform_output_port,
-                    SortMergeCompactor::new(
-                        rows_limit,
-                        get_sort_descriptions(&plan.schema, &plan.order_by)?,
-                    ),
+                    rows_limit,
+                    get_sort_descriptions(&plan.schema, &plan.order_by)?,
                 )
             })
     }
@@ -25,6 +25,7 @@ pub use sinks::Sink;
 pub use sinks::Sinker;
 pub use sinks::SubqueryReceiveSink;
 pub use sinks::SyncSenderSink;
+pub use sinks::UnionReceiveSink;
 pub use sources::AsyncSource;
 pub use sources::AsyncSourcer;
 pub use sources::BlocksSource;
@@ -34,6 +35,7 @@ pub use sources::FileSplitter;
 pub use sources::FileSplitterState;
 pub use sources::MultiFileSplitter;
 pub use sources::OperatorInfo;
+pub use sources::RecursiveCteSource;
 pub use sources::StreamSource;
 pub use sources::StreamSourceV2;
 pub use sources::SyncReceiverCkSource;
@@ -55,15 +57,25 @@ pub use transforms::KeyU32HashTable;
 pub use transforms::KeyU512HashTable;
 pub use transforms::KeyU64HashTable;
 pub use transforms::KeyU8HashTable;
+pub use transforms::LoserTree;
 pub use transforms::MarkJoinCompactor;
+pub use transforms::NestedLoopJoinState;
+pub use transforms::ProbeRuntimeFilter;
 pub use transforms::ProjectionTransform;
+pub use transforms::RuntimeFilter;
 pub use transforms::SerializerHashTable;
+pub use transforms::SetOperationState;
 pub use transforms::SinkBuildHashTable;
+pub use transforms::SinkNestedLoopJoinBuild;
+pub use transforms::SinkSetOperationBuild;
+pub use transforms::SinkSortMergeJoinBuild;
 pub use transforms::SortMergeCompactor;
+pub use transforms::SortMergeJoinState;
 pub use transforms::TransformAddOn;
 pub use transforms::TransformAggregator;
 pub use transforms::TransformBlockCompact;
 pub use transforms::TransformCastSchema;
+pub use transforms::TransformCheckConstraints;
 pub use transforms::TransformCompact;
 pub use transforms::TransformCreateSets;
 pub use transforms::TransformDummy;
@@ -72,5 +84,13 @@ pub use transforms::TransformHashJoinProbe;
 pub use transforms::TransformHaving;
 pub use transforms::TransformLimit;
 pub use transforms::TransformLimitBy;
+pub use transforms::TransformMergeBlock;
+pub use transforms::TransformNestedLoopJoin;
+pub use transforms::TransformSetOperation;
+pub use transforms::TransformSortExternal;
 pub use transforms::TransformSortMerge;
+pub use transforms::TransformSortMergeJoin;
 pub use transforms::TransformSortPartial;
+pub use transforms::TransformWindow;
+pub use transforms::TransformWindowShuffle;
+pub use transforms::WindowEvaluator;
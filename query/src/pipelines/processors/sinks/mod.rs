@@ -18,6 +18,7 @@ mod empty_sink;
 mod subquery_receive_sink;
 mod sync_sink;
 mod sync_sink_sender;
+mod union_receive_sink;
 
 pub use async_sink::AsyncSink;
 pub use async_sink::AsyncSinker;
@@ -27,3 +28,4 @@ pub use subquery_receive_sink::SubqueryReceiveSink;
 pub use sync_sink::Sink;
 pub use sync_sink::Sinker;
 pub use sync_sink_sender::SyncSenderSink;
+pub use union_receive_sink::UnionReceiveSink;
@@ -0,0 +1,60 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_channel::Sender;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::AsyncSink;
+use crate::pipelines::processors::AsyncSinker;
+
+/// Send the blocks of an input of `UNION ALL` to the pipeline merging them.
+pub struct UnionReceiveSink {
+    sender: Option<Sender<DataBlock>>,
+}
+
+impl UnionReceiveSink {
+    pub fn create(sender: Sender<DataBlock>, input: Arc<InputPort>) -> ProcessorPtr {
+        AsyncSinker::create(input, UnionReceiveSink {
+            sender: Some(sender),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncSink for UnionReceiveSink {
+    const NAME: &'static str = "UnionReceiveSink";
+
+    async fn on_finish(&mut self) -> Result<()> {
+        // The merging pipeline finishes once all the senders are dropped.
+        drop(self.sender.take());
+        Ok(())
+    }
+
+    #[async_trait::unboxed_simple]
+    async fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        if let Some(sender) = &self.sender {
+            if sender.send(data_block).await.is_err() {
+                // The merging pipeline is finished early, e.g. by `LIMIT`,
+                // the remaining blocks are discarded.
+                self.sender = None;
+            }
+        }
+        Ok(())
+    }
+}
@@ -18,6 +18,7 @@ mod deserializer;
 mod empty_source;
 mod file_splitter;
 mod multi_file_splitter;
+mod recursive_cte_source;
 mod stream_source;
 mod stream_source_v2;
 mod sync_ck_source_receiver;
@@ -33,6 +34,7 @@ pub use file_splitter::FileSplitter;
 pub use file_splitter::FileSplitterState;
 pub use multi_file_splitter::MultiFileSplitter;
 pub use multi_file_splitter::OperatorInfo;
+pub use recursive_cte_source::RecursiveCteSource;
 pub use stream_source::StreamSource;
 pub use stream_source_v2::StreamSourceV2;
 pub use sync_ck_source_receiver::SyncReceiverCkSource;
@@ -0,0 +1,182 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::scalars::default_column_cast;
+use futures::TryStreamExt;
+
+use crate::interpreters::ProcessorExecutorStream;
+use crate::pipelines::executor::PipelinePullingExecutor;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::AsyncSource;
+use crate::pipelines::processors::AsyncSourcer;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::executor::ColumnID;
+use crate::sql::executor::PhysicalPlan;
+use crate::sql::executor::PipelineBuilder;
+use crate::sql::executor::RecursiveCte;
+use crate::storages::memory::MemoryTable;
+use crate::storages::Table;
+
+/// Evaluate a recursive CTE:
+///
+/// 1. Evaluate the non-recursive term, and take the result as working table.
+/// 2. Evaluate the recursive term with the CTE referencing the working table, and
+///    replace the working table with the result.
+/// 3. Repeat step 2 until the working table is empty.
+///
+/// The output is the union of all the working tables. The terms are executed with
+/// standalone pipelines, since the memory table is read when building the pipeline.
+pub struct RecursiveCteSource {
+    ctx: Arc<QueryContext>,
+    plan: RecursiveCte,
+    working_table: Arc<dyn Table>,
+    max_depth: u64,
+
+    depth: u64,
+    started: bool,
+    working_blocks: Vec<DataBlock>,
+    output_blocks: VecDeque<DataBlock>,
+}
+
+impl RecursiveCteSource {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        plan: RecursiveCte,
+    ) -> Result<ProcessorPtr> {
+        let working_table = ctx
+            .get_materialized_table(plan.working_table)
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!(
+                    "Working table of recursive CTE `{}` is not registered",
+                    plan.cte_name
+                ))
+            })?;
+        let max_depth = ctx.get_settings().get_max_cte_recursive_depth()?;
+
+        AsyncSourcer::create(ctx.clone(), output, RecursiveCteSource {
+            ctx,
+            plan,
+            working_table,
+            max_depth,
+            depth: 0,
+            started: false,
+            working_blocks: vec![],
+            output_blocks: VecDeque::new(),
+        })
+    }
+
+    fn replace_working_table(&self, blocks: Vec<DataBlock>) -> Result<()> {
+        match self.working_table.as_any().downcast_ref::<MemoryTable>() {
+            Some(table) => {
+                table.replace_blocks(blocks);
+                Ok(())
+            }
+            None => Err(ErrorCode::LogicalError(format!(
+                "Working table of recursive CTE `{}` must be a memory table",
+                self.plan.cte_name
+            ))),
+        }
+    }
+
+    /// Execute one of the terms, and cast the result into the schema of working table.
+    async fn execute_term(
+        &self,
+        plan: &PhysicalPlan,
+        columns: &[ColumnID],
+    ) -> Result<Vec<DataBlock>> {
+        // Use a standalone context, so the partitions of current query won't be touched.
+        let ctx = QueryContext::create_from(self.ctx.clone());
+        let mut build_res = PipelineBuilder::create(ctx.clone()).finalize(plan)?;
+        build_res.set_max_threads(ctx.get_settings().get_max_threads()? as usize);
+
+        let executor = PipelinePullingExecutor::from_pipelines(
+            ctx.get_storage_runtime(),
+            ctx.query_need_abort(),
+            build_res,
+        )?;
+        let stream = ProcessorExecutorStream::create(executor)?;
+        let blocks = stream.try_collect::<Vec<_>>().await?;
+
+        let schema: DataSchemaRef = self.working_table.schema();
+        blocks
+            .iter()
+            .filter(|block| !block.is_empty())
+            .map(|block| {
+                let columns = columns
+                    .iter()
+                    .zip(schema.fields())
+                    .map(|(id, field)| {
+                        let column = block.try_column_by_name(id)?;
+                        default_column_cast(column, field.data_type())
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(DataBlock::create(schema.clone(), columns))
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl AsyncSource for RecursiveCteSource {
+    const NAME: &'static str = "RecursiveCteSource";
+
+    #[async_trait::unboxed_simple]
+    async fn generate(&mut self) -> Result<Option<DataBlock>> {
+        loop {
+            if let Some(block) = self.output_blocks.pop_front() {
+                return Ok(Some(block));
+            }
+
+            let blocks = if !self.started {
+                self.started = true;
+                self.execute_term(&self.plan.left, &self.plan.left_columns)
+                    .await?
+            } else {
+                if self.working_blocks.is_empty() {
+                    self.replace_working_table(vec![])?;
+                    return Ok(None);
+                }
+
+                if self.depth >= self.max_depth {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "recursive CTE `{}` exceeds the max recursive depth {}, \
+                        which can be changed by setting `max_cte_recursive_depth`",
+                        self.plan.cte_name, self.max_depth
+                    )));
+                }
+                self.depth += 1;
+
+                let working_blocks = std::mem::take(&mut self.working_blocks);
+                self.replace_working_table(working_blocks)?;
+                self.execute_term(&self.plan.right, &self.plan.right_columns)
+                    .await?
+            };
+
+            self.output_blocks.extend(blocks.iter().map(|block| {
+                DataBlock::create(self.plan.schema.clone(), block.columns().to_vec())
+            }));
+            self.working_blocks = blocks;
+        }
+    }
+}
@@ -25,6 +25,7 @@ use common_datablocks::HashMethodKeysU512;
 use common_datablocks::HashMethodKeysU64;
 use common_datablocks::HashMethodKeysU8;
 use common_datablocks::HashMethodSerializer;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataType;
 use common_datavalues::MutableColumn;
 use common_datavalues::ScalarColumn;
@@ -34,12 +35,15 @@ use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 
+use super::aggregator_spill::create_aggregator_spiller;
+use super::aggregator_spill::spill_aggregate_states;
 use crate::pipelines::processors::transforms::group_by::AggregatorState;
 use crate::pipelines::processors::transforms::group_by::GroupColumnsBuilder;
 use crate::pipelines::processors::transforms::group_by::KeysColumnIter;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::StateEntity;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
+use crate::pipelines::processors::transforms::Spiller;
 use crate::pipelines::processors::AggregatorParams;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
@@ -71,6 +75,10 @@ pub struct FinalAggregator<
     // used for deserialization only, so we can reuse it during the loop
     temp_place: Option<StateAddr>,
     ctx: Arc<QueryContext>,
+
+    spiller: Spiller,
+    // the schema of the input blocks, which is also the schema of the spilled blocks
+    input_schema: Option<DataSchemaRef>,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
@@ -87,6 +95,7 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
         } else {
             state.alloc_layout2(&params)
         };
+        let spiller = create_aggregator_spiller(ctx.clone(), "group_by_final")?;
 
         Ok(Self {
             is_generated: false,
@@ -96,6 +105,8 @@ impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + S
             params,
             temp_place,
             ctx,
+            spiller,
+            input_schema: None,
         })
     }
 }
@@ -137,6 +148,40 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
     const NAME: &'static str = "GroupByFinalTransform";
 
     fn consume(&mut self, block: DataBlock) -> Result<()> {
+        self.merge_block(block)?;
+        self.spill_states()
+    }
+
+    fn finish_consume(&mut self) -> Result<()> {
+        self.spill_remaining_states()
+    }
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.spiller.is_spilled() {
+            if let Some(data_blocks) = self.next_spilled_bucket()? {
+                for data_block in data_blocks {
+                    self.merge_block(data_block)?;
+                }
+
+                let data_block = self.generate_data()?;
+                self.reset_state();
+                return Ok(data_block);
+            }
+        }
+
+        self.generate_data()
+    }
+    fn spiller(&mut self) -> Option<&mut Spiller> {
+        Some(&mut self.spiller)
+    }
+}
+
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<true, Method> {
+    fn merge_block(&mut self, block: DataBlock) -> Result<()> {
+        if self.input_schema.is_none() {
+            self.input_schema = Some(block.schema().clone());
+        }
+
         // 1.1 and 1.2.
         let aggregate_function_len = self.params.aggregate_functions.len();
         let keys_column = block.column(aggregate_function_len);
@@ -179,7 +224,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         Ok(())
     }
 
-    fn generate(&mut self) -> Result<Option<DataBlock>> {
+    fn generate_data(&mut self) -> Result<Option<DataBlock>> {
         match self.state.len() == 0 || self.is_generated {
             true => {
                 self.drop_states();
@@ -237,6 +282,40 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
     const NAME: &'static str = "GroupByFinalTransform";
 
     fn consume(&mut self, block: DataBlock) -> Result<()> {
+        self.merge_block(block)?;
+        self.spill_states()
+    }
+
+    fn finish_consume(&mut self) -> Result<()> {
+        self.spill_remaining_states()
+    }
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.spiller.is_spilled() {
+            if let Some(data_blocks) = self.next_spilled_bucket()? {
+                for data_block in data_blocks {
+                    self.merge_block(data_block)?;
+                }
+
+                let data_block = self.generate_data()?;
+                self.reset_state();
+                return Ok(data_block);
+            }
+        }
+
+        self.generate_data()
+    }
+    fn spiller(&mut self) -> Option<&mut Spiller> {
+        Some(&mut self.spiller)
+    }
+}
+
+impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> FinalAggregator<false, Method> {
+    fn merge_block(&mut self, block: DataBlock) -> Result<()> {
+        if self.input_schema.is_none() {
+            self.input_schema = Some(block.schema().clone());
+        }
+
         let key_array = block.column(0);
         let keys_iter = self.method.keys_iter_from_column(key_array)?;
 
@@ -254,7 +333,7 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         Ok(())
     }
 
-    fn generate(&mut self) -> Result<Option<DataBlock>> {
+    fn generate_data(&mut self) -> Result<Option<DataBlock>> {
         match self.state.len() == 0 || self.is_generated {
             true => Ok(None),
             false => {
@@ -276,6 +355,58 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
 impl<const FINAL: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     FinalAggregator<FINAL, Method>
 {
+    /// Spill the states bucket by bucket if the memory usage of the query exceeds the limit.
+    fn spill_states(&mut self) -> Result<()> {
+        if self.state.len() == 0 || !self.spiller.need_spill() {
+            return Ok(());
+        }
+
+        self.spill_all_states()
+    }
+
+    fn spill_all_states(&mut self) -> Result<()> {
+        if let Some(schema) = &self.input_schema {
+            spill_aggregate_states(
+                &mut self.spiller,
+                &self.method,
+                &self.state,
+                &self.params,
+                schema,
+            )?;
+            self.reset_state();
+        }
+        Ok(())
+    }
+
+    /// Once the states are spilled, the remaining in-memory states are spilled too when the
+    /// input is finished, then the buckets are read back and merged one by one, so only one
+    /// bucket is in memory.
+    fn spill_remaining_states(&mut self) -> Result<()> {
+        match self.spiller.is_spilled() && self.state.len() != 0 {
+            true => self.spill_all_states(),
+            false => Ok(()),
+        }
+    }
+
+    /// The next spilled bucket, which has been loaded in the async step of the transform.
+    fn next_spilled_bucket(&mut self) -> Result<Option<Vec<DataBlock>>> {
+        match self.spiller.next_partition() {
+            None => Ok(None),
+            Some(bucket) => Ok(Some(self.spiller.read_partition(bucket)?)),
+        }
+    }
+
+    fn reset_state(&mut self) {
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.temp_place = match self.params.aggregate_functions.is_empty() {
+            true => None,
+            false => self.state.alloc_layout2(&self.params),
+        };
+        self.is_generated = false;
+        self.states_dropped = false;
+    }
+
     fn drop_states(&mut self) {
         if !self.states_dropped {
             let aggregator_params = self.params.as_ref();
@@ -33,11 +33,14 @@ use common_exception::Result;
 use common_functions::aggregates::StateAddr;
 use common_functions::aggregates::StateAddrs;
 
+use super::aggregator_spill::create_aggregator_spiller;
+use super::aggregator_spill::spill_aggregate_states;
 use crate::pipelines::processors::transforms::group_by::AggregatorState;
 use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
 use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
 use crate::pipelines::processors::transforms::group_by::StateEntity;
 use crate::pipelines::processors::transforms::transform_aggregator::Aggregator;
+use crate::pipelines::processors::transforms::Spiller;
 use crate::pipelines::processors::AggregatorParams;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
@@ -73,21 +76,28 @@ pub struct PartialAggregator<
     state: Method::State,
     params: Arc<AggregatorParams>,
     ctx: Arc<QueryContext>,
+    spiller: Spiller,
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method> + Send>
     PartialAggregator<HAS_AGG, Method>
 {
-    pub fn create(ctx: Arc<QueryContext>, method: Method, params: Arc<AggregatorParams>) -> Self {
+    pub fn create(
+        ctx: Arc<QueryContext>,
+        method: Method,
+        params: Arc<AggregatorParams>,
+    ) -> Result<Self> {
         let state = method.aggregate_state();
-        Self {
+        let spiller = create_aggregator_spiller(ctx.clone(), "group_by_partial")?;
+        Ok(Self {
             is_generated: false,
             states_dropped: false,
             state,
             method,
             params,
             ctx,
-        }
+            spiller,
+        })
     }
 
     #[inline(always)]
@@ -249,11 +259,18 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         }
 
         let places = Self::lookup_state(&self.params, group_keys_iter, &mut self.state);
-        Self::execute(&self.params, &block, &places)
+        Self::execute(&self.params, &block, &places)?;
+        self.spill_states()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
-        self.generate_data()
+        match self.generate_data()? {
+            Some(data_block) => Ok(Some(data_block)),
+            None => self.generate_spilled_data(),
+        }
+    }
+    fn spiller(&mut self) -> Option<&mut Spiller> {
+        Some(&mut self.spiller)
     }
 }
 
@@ -278,15 +295,18 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
         }
 
         Self::lookup_key(group_keys_iter, &mut self.state);
-        Ok(())
+        self.spill_states()
     }
 
     fn generate(&mut self) -> Result<Option<DataBlock>> {
         match self.state.len() == 0 || self.is_generated {
-            true => {
-                self.drop_states();
-                Ok(None)
-            }
+            true => match self.generate_spilled_data()? {
+                Some(data_block) => Ok(Some(data_block)),
+                None => {
+                    self.drop_states();
+                    Ok(None)
+                }
+            },
             false => {
                 self.is_generated = true;
                 let mut keys_column_builder = self.method.keys_column_builder(self.state.len());
@@ -301,11 +321,51 @@ impl<Method: HashMethod + PolymorphicKeysHelper<Method> + Send> Aggregator
             }
         }
     }
+    fn spiller(&mut self) -> Option<&mut Spiller> {
+        Some(&mut self.spiller)
+    }
 }
 
 impl<const HAS_AGG: bool, Method: HashMethod + PolymorphicKeysHelper<Method>>
     PartialAggregator<HAS_AGG, Method>
 {
+    /// Spill the states if the memory usage of the query exceeds the limit,
+    /// the spilled states will be sent to the final aggregator after the in-memory states.
+    fn spill_states(&mut self) -> Result<()> {
+        if self.state.len() == 0 || !self.spiller.need_spill() {
+            return Ok(());
+        }
+
+        spill_aggregate_states(
+            &mut self.spiller,
+            &self.method,
+            &self.state,
+            &self.params,
+            &self.params.schema,
+        )?;
+
+        self.drop_states();
+        self.state = self.method.aggregate_state();
+        self.states_dropped = false;
+        Ok(())
+    }
+
+    /// Generate the next spilled bucket, which has been loaded in the async step of the transform.
+    fn generate_spilled_data(&mut self) -> Result<Option<DataBlock>> {
+        match self.spiller.next_partition() {
+            None => Ok(None),
+            Some(bucket) => {
+                let data_blocks = self.spiller.read_partition(bucket)?;
+                match data_blocks.is_empty() {
+                    true => Ok(Some(DataBlock::empty_with_schema(
+                        self.params.schema.clone(),
+                    ))),
+                    false => Ok(Some(DataBlock::concat_blocks(&data_blocks)?)),
+                }
+            }
+        }
+    }
+
     fn drop_states(&mut self) {
         if !self.states_dropped {
             let aggregator_params = self.params.as_ref();
@@ -0,0 +1,100 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::MutableColumn;
+use common_datavalues::MutableStringColumn;
+use common_datavalues::ScalarColumnBuilder;
+use common_exception::Result;
+use common_functions::aggregates::StateAddr;
+use common_hashtable::NUM_BUCKETS;
+
+use crate::pipelines::processors::transforms::group_by::AggregatorState;
+use crate::pipelines::processors::transforms::group_by::KeysColumnBuilder;
+use crate::pipelines::processors::transforms::group_by::PolymorphicKeysHelper;
+use crate::pipelines::processors::transforms::group_by::StateEntity;
+use crate::pipelines::processors::transforms::Spiller;
+use crate::pipelines::processors::AggregatorParams;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+pub fn create_aggregator_spiller(ctx: Arc<QueryContext>, kind: &str) -> Result<Spiller> {
+    let memory_limit = ctx.get_settings().get_group_by_spilling_memory_limit()? as usize;
+    Spiller::create(ctx, kind, memory_limit)
+}
+
+/// Spill the aggregate states and group keys bucket by bucket, the bucket of a key is the same as
+/// the bucket in the two-level hash table.
+///
+/// The spilled blocks have the same layout as the output of the partial aggregator, the serialized
+/// state of each aggregate function followed by the group keys.
+pub fn spill_aggregate_states<Method: HashMethod + PolymorphicKeysHelper<Method>>(
+    spiller: &mut Spiller,
+    method: &Method,
+    state: &Method::State,
+    params: &AggregatorParams,
+    schema: &DataSchemaRef,
+) -> Result<()> {
+    let funcs = &params.aggregate_functions;
+    let offsets_aggregate_states = &params.offsets_aggregate_states;
+
+    let mut buckets = (0..NUM_BUCKETS).map(|_| vec![]).collect::<Vec<_>>();
+    for group_entity in state.iter() {
+        let key = group_entity.get_state_key();
+        let bucket = <Method::State as AggregatorState<Method>>::key_bucket(key);
+        buckets[bucket].push(group_entity);
+    }
+
+    let mut bytes = BytesMut::new();
+    for (bucket, group_entities) in buckets.into_iter().enumerate() {
+        if group_entities.is_empty() {
+            continue;
+        }
+
+        let mut state_builders: Vec<MutableStringColumn> = (0..funcs.len())
+            .map(|_| MutableStringColumn::with_capacity(group_entities.len() * 4))
+            .collect();
+        let mut group_key_builder = method.keys_column_builder(group_entities.len());
+
+        for group_entity in group_entities {
+            if !funcs.is_empty() {
+                let place: StateAddr = (*group_entity.get_state_value()).into();
+
+                for (idx, func) in funcs.iter().enumerate() {
+                    let arg_place = place.next(offsets_aggregate_states[idx]);
+                    func.serialize(arg_place, &mut bytes)?;
+                    state_builders[idx].append_value(&bytes[..]);
+                    bytes.clear();
+                }
+            }
+
+            group_key_builder.append_value(group_entity.get_state_key());
+        }
+
+        let mut columns = Vec::with_capacity(funcs.len() + 1);
+        for mut builder in state_builders {
+            columns.push(builder.to_column());
+        }
+        columns.push(group_key_builder.finish());
+
+        spiller.spill(bucket, vec![DataBlock::create(schema.clone(), columns)])?;
+    }
+
+    Ok(())
+}
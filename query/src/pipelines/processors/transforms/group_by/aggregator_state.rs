@@ -21,11 +21,13 @@ use common_datablocks::HashMethodFixedKeys;
 use common_datablocks::HashMethodSerializer;
 use common_datavalues::prelude::*;
 use common_functions::aggregates::StateAddr;
+use common_hashtable::get_bucket_from_hash;
 use common_hashtable::HashMapIteratorKind;
 use common_hashtable::HashMapKind;
 use common_hashtable::HashTableEntity;
 use common_hashtable::HashTableKeyable;
 use common_hashtable::KeyValueEntity;
+use common_hashtable::NUM_BUCKETS;
 
 use crate::pipelines::processors::transforms::group_by::aggregator_state_entity::ShortFixedKeyable;
 use crate::pipelines::processors::transforms::group_by::aggregator_state_entity::ShortFixedKeysStateEntity;
@@ -81,6 +83,9 @@ pub trait AggregatorState<Method: HashMethod>: Sync + Send {
 
     fn entity_by_key(&mut self, key: &Self::Key, inserted: &mut bool) -> *mut Self::Entity;
 
+    /// The bucket of the key in the two-level hash table, it's used to partition the states when spilling.
+    fn key_bucket(key: &Self::Key) -> usize;
+
     fn is_two_level(&self) -> bool {
         false
     }
@@ -187,6 +192,12 @@ where
         self.entity(*key, inserted)
     }
 
+    #[inline(always)]
+    fn key_bucket(key: &Self::Key) -> usize {
+        // The keys are small enough to be used as the bucket directly.
+        key.lookup() as usize % NUM_BUCKETS
+    }
+
     #[inline(always)]
     fn is_two_level(&self) -> bool {
         self.two_level_flag
@@ -260,6 +271,11 @@ where
         self.entity(*key, inserted)
     }
 
+    #[inline(always)]
+    fn key_bucket(key: &Self::Key) -> usize {
+        get_bucket_from_hash(key.fast_hash())
+    }
+
     #[inline(always)]
     fn is_two_level(&self) -> bool {
         self.two_level_flag
@@ -348,6 +364,11 @@ impl AggregatorState<HashMethodSerializer> for SerializedKeysAggregatorState {
         state_entity
     }
 
+    #[inline(always)]
+    fn key_bucket(keys_ref: &KeysRef) -> usize {
+        get_bucket_from_hash(keys_ref.fast_hash())
+    }
+
     #[inline(always)]
     fn is_two_level(&self) -> bool {
         self.two_level_flag
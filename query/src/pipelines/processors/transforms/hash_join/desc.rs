@@ -24,6 +24,7 @@ use crate::sql::executor::HashJoin;
 use crate::sql::executor::PhysicalScalar;
 use crate::sql::plans::JoinType;
 
+#[derive(Clone)]
 pub struct HashJoinDesc {
     pub(crate) build_keys: Vec<EvalNode<ColumnID>>,
     pub(crate) probe_keys: Vec<EvalNode<ColumnID>>,
@@ -54,7 +55,9 @@ impl HashJoinDesc {
         })
     }
 
-    fn join_predicate(other_conditions: &[PhysicalScalar]) -> Result<Option<PhysicalScalar>> {
+    pub(crate) fn join_predicate(
+        other_conditions: &[PhysicalScalar],
+    ) -> Result<Option<PhysicalScalar>> {
         if other_conditions.is_empty() {
             return Ok(None);
         }
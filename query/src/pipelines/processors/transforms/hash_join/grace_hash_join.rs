@@ -0,0 +1,446 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataTypeImpl;
+use common_exception::Result;
+use common_hashtable::HashTableKeyable;
+use parking_lot::Mutex;
+
+use super::HashJoinDesc;
+use super::HashTable;
+use super::JoinHashTable;
+use super::ProbeState;
+use crate::pipelines::processors::transforms::group_by::keys_ref::KeysRef;
+use crate::pipelines::processors::transforms::SpilledFile;
+use crate::pipelines::processors::transforms::Spiller;
+use crate::pipelines::processors::HashJoinState;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::planner::plans::JoinType;
+
+const PARTITION_BITS: usize = 4;
+const NUM_PARTITIONS: usize = 1 << PARTITION_BITS;
+/// The max level to partition a partition again, each level uses different bits of the key hash.
+const MAX_SPILL_LEVEL: usize = 3;
+
+/// The partition of the key hash in the level.
+#[inline(always)]
+fn partition_of(hash: u64, level: usize) -> usize {
+    let shift = 64 - PARTITION_BITS * (level + 1);
+    ((hash >> shift) as usize) & (NUM_PARTITIONS - 1)
+}
+
+/// State of the grace hash join.
+///
+/// Once the build side exceeds `join_spilling_memory_limit`, both the build side and the probe
+/// side are partitioned by the hash of the join keys and spilled, and the pairs of partitions
+/// are joined after probing. A partition that still exceeds the limit is partitioned again with
+/// the next level of the hash until `MAX_SPILL_LEVEL`.
+pub struct JoinSpillState {
+    level: usize,
+    memory_limit: usize,
+    build_bytes: AtomicUsize,
+    spilled: AtomicBool,
+    hash_key_types: Vec<DataTypeImpl>,
+    build_schema: DataSchemaRef,
+    build_spiller: Mutex<Spiller>,
+    probe_spiller: Mutex<Spiller>,
+    /// Serialize the writing of the spilled files, so the files spilled before a flush
+    /// have been written once the flush returns.
+    flush_lock: tokio::sync::Mutex<()>,
+    /// Partitions that have not been joined.
+    pending_partitions: Mutex<VecDeque<usize>>,
+    /// The mark join results of the joined partitions.
+    pub(crate) mark_blocks: Mutex<Vec<DataBlock>>,
+}
+
+impl JoinSpillState {
+    /// Create the spill state, returns `None` if spilling is disabled or not supported by the join.
+    pub fn try_create(
+        ctx: &Arc<QueryContext>,
+        hash_join_desc: &HashJoinDesc,
+        hash_key_types: &[DataTypeImpl],
+        build_schema: DataSchemaRef,
+        level: usize,
+    ) -> Result<Option<JoinSpillState>> {
+        let memory_limit = ctx.get_settings().get_join_spilling_memory_limit()? as usize;
+        let supported = matches!(
+            hash_join_desc.join_type,
+            JoinType::Inner
+                | JoinType::Left
+                | JoinType::Single
+                | JoinType::Semi
+                | JoinType::Anti
+                | JoinType::Mark
+        );
+
+        if memory_limit == 0 || !supported || hash_key_types.is_empty() || level > MAX_SPILL_LEVEL {
+            return Ok(None);
+        }
+
+        Ok(Some(JoinSpillState {
+            level,
+            memory_limit,
+            build_bytes: AtomicUsize::new(0),
+            spilled: AtomicBool::new(false),
+            hash_key_types: hash_key_types.to_vec(),
+            build_schema,
+            build_spiller: Mutex::new(Spiller::create(ctx.clone(), "join_build", 0)?),
+            probe_spiller: Mutex::new(Spiller::create(ctx.clone(), "join_probe", 0)?),
+            flush_lock: tokio::sync::Mutex::new(()),
+            pending_partitions: Mutex::new(VecDeque::new()),
+            mark_blocks: Mutex::new(vec![]),
+        }))
+    }
+
+    pub fn is_spilled(&self) -> bool {
+        self.spilled.load(Ordering::Acquire)
+    }
+
+    /// Account the bytes of a build block, returns whether the build side is spilled.
+    pub fn add_build_bytes(&self, bytes: usize) -> bool {
+        let build_bytes = self.build_bytes.fetch_add(bytes, Ordering::AcqRel) + bytes;
+        if build_bytes > self.memory_limit {
+            self.spilled.store(true, Ordering::Release);
+        }
+        self.is_spilled()
+    }
+}
+
+/// A pair of spilled partitions to join, the build partition is built into `table`
+/// file by file before probing.
+pub struct PartitionJoinTask {
+    table: Arc<JoinHashTable>,
+    partition: usize,
+    build_finished: bool,
+    build_rows: usize,
+    probe_finished: bool,
+}
+
+/// The partition join tasks of a probe processor, a task is followed by the tasks of
+/// its sub-partitions if its build partition is partitioned again.
+///
+/// The spilled files are read one by one by `read` in the async step of the processor.
+#[derive(Default)]
+pub struct SpilledJoinTasks {
+    tasks: Vec<PartitionJoinTask>,
+    reading: Option<SpilledFile>,
+    loaded: Option<Vec<DataBlock>>,
+}
+
+impl SpilledJoinTasks {
+    /// Whether a spilled file has to be read before joining.
+    pub fn need_read(&self) -> bool {
+        self.reading.is_some()
+    }
+
+    /// Write the files spilled by the partitions being joined, then read the spilled file.
+    pub async fn read(&mut self) -> Result<()> {
+        for task in self.tasks.iter() {
+            task.table.write_spilled_files().await?;
+        }
+
+        if let Some(file) = self.reading.take() {
+            self.loaded = Some(file.read().await?);
+        }
+        Ok(())
+    }
+}
+
+impl JoinHashTable {
+    /// Spill the chunks which have been pushed to the row space.
+    pub(crate) fn spill_build_chunks(&self) -> Result<()> {
+        let chunks = std::mem::take(&mut *self.row_space.chunks.write().unwrap());
+        for chunk in chunks {
+            self.spill_build_block(&chunk.data_block)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn spill_build_block(&self, input: &DataBlock) -> Result<()> {
+        let spill_state = match &self.spill_state {
+            None => return Ok(()),
+            Some(spill_state) => spill_state,
+        };
+
+        let func_ctx = self.ctx.try_get_function_context()?;
+        let build_keys = self
+            .hash_join_desc
+            .build_keys
+            .iter()
+            .map(|expr| Ok(expr.eval(&func_ctx, input)?.vector().clone()))
+            .collect::<Result<Vec<ColumnRef>>>()?;
+
+        let indices = self.partition_indices(&build_keys, input.num_rows(), spill_state.level)?;
+        let blocks = DataBlock::scatter_block(input, &indices, NUM_PARTITIONS)?;
+
+        let mut spiller = spill_state.build_spiller.lock();
+        for (partition, block) in blocks.into_iter().enumerate() {
+            spiller.spill(partition, vec![block])?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn spill_probe_block(&self, input: &DataBlock) -> Result<()> {
+        let spill_state = match &self.spill_state {
+            None => return Ok(()),
+            Some(spill_state) => spill_state,
+        };
+
+        if self.hash_join_desc.join_type == JoinType::Mark {
+            // The nulls of the probe side are checked globally, see `mark_join`.
+            if let Some(validity) = input.column(0).validity().1 {
+                if validity.unset_bits() > 0 {
+                    let mut has_null = self.hash_join_desc.marker_join_desc.has_null.write();
+                    *has_null = true;
+                }
+            }
+        }
+
+        let func_ctx = self.ctx.try_get_function_context()?;
+        let probe_keys = self
+            .hash_join_desc
+            .probe_keys
+            .iter()
+            .map(|expr| Ok(expr.eval(&func_ctx, input)?.vector().clone()))
+            .collect::<Result<Vec<ColumnRef>>>()?;
+
+        let indices = self.partition_indices(&probe_keys, input.num_rows(), spill_state.level)?;
+        let blocks = DataBlock::scatter_block(input, &indices, NUM_PARTITIONS)?;
+
+        let mut spiller = spill_state.probe_spiller.lock();
+        for (partition, block) in blocks.into_iter().enumerate() {
+            spiller.spill(partition, vec![block])?;
+        }
+        Ok(())
+    }
+
+    /// Spill the rest of the build side, all the partitions are pending to join.
+    pub(crate) fn finish_spilled_build(&self) -> Result<()> {
+        self.spill_build_chunks()?;
+
+        if let Some(spill_state) = &self.spill_state {
+            let mut pending_partitions = spill_state.pending_partitions.lock();
+            pending_partitions.extend(0..NUM_PARTITIONS);
+        }
+        Ok(())
+    }
+
+    /// Write the spilled files of the build side and the probe side.
+    pub(crate) async fn write_spilled_files(&self) -> Result<()> {
+        if let Some(spill_state) = &self.spill_state {
+            let _guard = spill_state.flush_lock.lock().await;
+            let writes = spill_state.build_spiller.lock().take_pending_writes();
+            writes.write().await?;
+            let writes = spill_state.probe_spiller.lock().take_pending_writes();
+            writes.write().await?;
+        }
+        Ok(())
+    }
+
+    /// Join the spilled partitions one by one, returns the results of probing a spilled probe file.
+    /// The results are empty if a spilled file has to be read first, see `SpilledJoinTasks::read`.
+    pub(crate) fn join_next_spilled_partition(
+        &self,
+        tasks: &mut SpilledJoinTasks,
+        probe_state: &mut ProbeState,
+    ) -> Result<Option<Vec<DataBlock>>> {
+        let mut loaded = tasks.loaded.take();
+        loop {
+            if tasks.tasks.is_empty() {
+                match self.next_spilled_partition()? {
+                    None => return Ok(None),
+                    Some(task) => {
+                        tasks.tasks.push(task);
+                        continue;
+                    }
+                }
+            }
+
+            let (task, parents) = tasks.tasks.split_last_mut().unwrap();
+            let parent = parents.last().map_or(self, |parent| parent.table.as_ref());
+            let spill_state = match &parent.spill_state {
+                None => return Ok(None),
+                Some(spill_state) => spill_state,
+            };
+
+            if !task.build_finished {
+                if let Some(blocks) = loaded.take() {
+                    for block in blocks {
+                        task.build_rows += block.num_rows();
+                        task.table.build(block)?;
+                    }
+                    continue;
+                }
+
+                let file = spill_state
+                    .build_spiller
+                    .lock()
+                    .pop_partition_file(task.partition);
+                if let Some(file) = file {
+                    tasks.reading = Some(file);
+                    return Ok(Some(vec![]));
+                }
+
+                // Only the probe side with matched rows produces results for these joins,
+                // the probe partition is cleaned up along with the spiller.
+                let skip = task.build_rows == 0
+                    && matches!(
+                        self.hash_join_desc.join_type,
+                        JoinType::Inner | JoinType::Semi | JoinType::Mark
+                    );
+                if skip {
+                    tasks.tasks.pop();
+                    continue;
+                }
+
+                task.build_finished = true;
+                task.table.finish()?;
+            }
+
+            if !task.probe_finished {
+                if let Some(blocks) = loaded.take() {
+                    let mut results = Vec::with_capacity(blocks.len());
+                    for block in blocks.iter() {
+                        probe_state.clear();
+                        results.extend(task.table.probe(block, probe_state)?);
+                    }
+                    return Ok(Some(results));
+                }
+
+                let file = spill_state
+                    .probe_spiller
+                    .lock()
+                    .pop_partition_file(task.partition);
+                match file {
+                    None => task.probe_finished = true,
+                    Some(file) => {
+                        tasks.reading = Some(file);
+                        return Ok(Some(vec![]));
+                    }
+                }
+            }
+
+            if task.table.is_spilled() {
+                if let Some(sub_task) = task.table.next_spilled_partition()? {
+                    tasks.tasks.push(sub_task);
+                    continue;
+                }
+            } else if self.hash_join_desc.join_type == JoinType::Mark {
+                let blocks = task.table.mark_join_blocks()?;
+                if let Some(spill_state) = &self.spill_state {
+                    spill_state.mark_blocks.lock().extend(blocks);
+                }
+            }
+
+            tasks.tasks.pop();
+        }
+    }
+
+    /// Create the join task of the next pending partition, whose build partition is
+    /// read back file by file.
+    fn next_spilled_partition(&self) -> Result<Option<PartitionJoinTask>> {
+        let spill_state = match &self.spill_state {
+            None => return Ok(None),
+            Some(spill_state) => spill_state,
+        };
+
+        let partition = match spill_state.pending_partitions.lock().pop_front() {
+            None => return Ok(None),
+            Some(partition) => partition,
+        };
+
+        Ok(Some(PartitionJoinTask {
+            table: self.create_partition_table(spill_state)?,
+            partition,
+            build_finished: false,
+            build_rows: 0,
+            probe_finished: false,
+        }))
+    }
+
+    /// Create the join state of a partition, which spills with the next level of the hash.
+    fn create_partition_table(&self, spill_state: &JoinSpillState) -> Result<Arc<JoinHashTable>> {
+        let hash_join_desc = self.hash_join_desc.clone();
+        let sub_spill_state = JoinSpillState::try_create(
+            &self.ctx,
+            &hash_join_desc,
+            &spill_state.hash_key_types,
+            spill_state.build_schema.clone(),
+            spill_state.level + 1,
+        )?;
+
+        Ok(Arc::new(JoinHashTable::try_create(
+            self.ctx.clone(),
+            Self::create_hash_table(&spill_state.hash_key_types)?,
+            spill_state.build_schema.clone(),
+            hash_join_desc,
+            sub_spill_state,
+        )?))
+    }
+
+    fn partition_indices(
+        &self,
+        keys: &[ColumnRef],
+        rows: usize,
+        level: usize,
+    ) -> Result<Vec<usize>> {
+        let keys = keys.iter().collect::<Vec<&ColumnRef>>();
+
+        macro_rules! partition_indices {
+            ($table: expr) => {{
+                let keys_state = $table.hash_method.build_keys_state(&keys, rows)?;
+                let keys_iter = $table.hash_method.build_keys_iter(&keys_state)?;
+                keys_iter
+                    .take(rows)
+                    .map(|key| partition_of(key.fast_hash(), level))
+                    .collect::<Vec<_>>()
+            }};
+        }
+
+        let indices = match &*self.hash_table.read() {
+            HashTable::SerializerHashTable(table) => {
+                let keys_state = table.hash_method.build_keys_state(&keys, rows)?;
+                let keys_iter = table.hash_method.build_keys_iter(&keys_state)?;
+                keys_iter
+                    .take(rows)
+                    .map(|key| {
+                        let keys_ref = KeysRef::create(key.as_ptr() as usize, key.len());
+                        partition_of(keys_ref.fast_hash(), level)
+                    })
+                    .collect::<Vec<_>>()
+            }
+            HashTable::KeyU8HashTable(table) => partition_indices!(table),
+            HashTable::KeyU16HashTable(table) => partition_indices!(table),
+            HashTable::KeyU32HashTable(table) => partition_indices!(table),
+            HashTable::KeyU64HashTable(table) => partition_indices!(table),
+            HashTable::KeyU128HashTable(table) => partition_indices!(table),
+            HashTable::KeyU256HashTable(table) => partition_indices!(table),
+            HashTable::KeyU512HashTable(table) => partition_indices!(table),
+        };
+
+        Ok(indices)
+    }
+}
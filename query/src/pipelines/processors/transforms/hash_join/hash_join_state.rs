@@ -12,10 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_datablocks::DataBlock;
 use common_exception::Result;
 
 use super::ProbeState;
+use super::RuntimeFilter;
+use super::SpilledJoinTasks;
 
 #[async_trait::async_trait]
 /// Concurrent hash table for hash join.
@@ -42,6 +46,40 @@ pub trait HashJoinState: Send + Sync {
     /// Wait until the build phase is finished
     async fn wait_finish(&self) -> Result<()>;
 
+    /// Require the runtime filter of the probe key, which is published at the end of
+    /// the build phase. It should be called before the build phase is finished.
+    fn require_runtime_filter(&self, key_index: usize) -> Result<()>;
+
+    /// Get the runtime filter of the probe key, `None` if it's not required or can't be
+    /// published, e.g. the build side is spilled.
+    fn runtime_filter(&self, key_index: usize) -> Option<Arc<RuntimeFilter>>;
+
     /// Get mark join results
     fn mark_join_blocks(&self) -> Result<Vec<DataBlock>>;
+
+    /// Attach a probe processor to state
+    fn probe_attach(&self) -> Result<()>;
+
+    /// Detach a probe processor from state, the spilled partitions are joined
+    /// after all the probe processors have been detached.
+    fn probe_detach(&self) -> Result<()>;
+
+    /// Wait until all the probe processors are detached
+    async fn wait_probe_finish(&self) -> Result<()>;
+
+    /// Whether the build side is spilled into partitions, which means the probe side
+    /// is spilled too and the join is done partition by partition.
+    fn is_spilled(&self) -> bool;
+
+    /// Write the spilled files of the build side and the probe side.
+    async fn flush_spill(&self) -> Result<()>;
+
+    /// Join the spilled partitions and retrieve the results of a spilled probe file,
+    /// returns `None` if all the partitions are joined. The results are empty if a
+    /// spilled file has to be read by `SpilledJoinTasks::read` first.
+    fn join_spilled_partitions(
+        &self,
+        tasks: &mut SpilledJoinTasks,
+        probe_state: &mut ProbeState,
+    ) -> Result<Option<Vec<DataBlock>>>;
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::borrow::BorrowMut;
+use std::collections::HashMap as StdHashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -44,7 +45,10 @@ use parking_lot::RwLock;
 use primitive_types::U256;
 use primitive_types::U512;
 
+use super::JoinSpillState;
 use super::ProbeState;
+use super::RuntimeFilter;
+use super::SpilledJoinTasks;
 use crate::pipelines::processors::transforms::group_by::keys_ref::KeysRef;
 use crate::pipelines::processors::transforms::hash_join::desc::HashJoinDesc;
 use crate::pipelines::processors::transforms::hash_join::row::RowPtr;
@@ -120,6 +124,15 @@ pub struct MarkJoinDesc {
     pub(crate) has_null: RwLock<bool>,
 }
 
+impl Clone for MarkJoinDesc {
+    fn clone(&self) -> Self {
+        MarkJoinDesc {
+            marker_index: self.marker_index,
+            has_null: RwLock::new(*self.has_null.read()),
+        }
+    }
+}
+
 pub struct JoinHashTable {
     pub(crate) ctx: Arc<QueryContext>,
     /// Reference count
@@ -131,6 +144,15 @@ pub struct JoinHashTable {
     pub(crate) hash_join_desc: HashJoinDesc,
     pub(crate) row_ptrs: RwLock<Vec<RowPtr>>,
     finished_notify: Arc<Notify>,
+    /// Reference count of the probe processors
+    probe_ref_count: Mutex<usize>,
+    is_probe_finished: Mutex<bool>,
+    probe_finished_notify: Arc<Notify>,
+    /// The state to spill the build side and the probe side into partitions (grace hash join),
+    /// `None` if spilling is disabled
+    pub(crate) spill_state: Option<JoinSpillState>,
+    /// The runtime filters of the required probe keys, published after the build phase
+    runtime_filters: RwLock<StdHashMap<usize, Option<Arc<RuntimeFilter>>>>,
 }
 
 impl JoinHashTable {
@@ -142,80 +164,64 @@ impl JoinHashTable {
     ) -> Result<Arc<JoinHashTable>> {
         let hash_key_types: Vec<DataTypeImpl> =
             build_keys.iter().map(|expr| expr.data_type()).collect();
-        let method = DataBlock::choose_hash_method_with_types(&hash_key_types)?;
+        let spill_state = JoinSpillState::try_create(
+            &ctx,
+            &hash_join_desc,
+            &hash_key_types,
+            build_schema.clone(),
+            0,
+        )?;
+
+        Ok(Arc::new(JoinHashTable::try_create(
+            ctx,
+            Self::create_hash_table(&hash_key_types)?,
+            build_schema,
+            hash_join_desc,
+            spill_state,
+        )?))
+    }
+
+    pub(crate) fn create_hash_table(hash_key_types: &[DataTypeImpl]) -> Result<HashTable> {
+        let method = DataBlock::choose_hash_method_with_types(hash_key_types)?;
         Ok(match method {
-            HashMethodKind::Serializer(_) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::SerializerHashTable(SerializerHashTable {
-                    hash_table: HashMap::<KeysRef, Vec<RowPtr>>::create(),
-                    hash_method: HashMethodSerializer::default(),
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU8(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU8HashTable(KeyU8HashTable {
-                    hash_table: HashMap::<u8, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU16(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU16HashTable(KeyU16HashTable {
-                    hash_table: HashMap::<u16, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU32(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU32HashTable(KeyU32HashTable {
-                    hash_table: HashMap::<u32, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU64(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeyU64HashTable(KeyU64HashTable {
-                    hash_table: HashMap::<u64, Vec<RowPtr>>::create(),
-                    hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU128(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+            HashMethodKind::Serializer(_) => HashTable::SerializerHashTable(SerializerHashTable {
+                hash_table: HashMap::<KeysRef, Vec<RowPtr>>::create(),
+                hash_method: HashMethodSerializer::default(),
+            }),
+            HashMethodKind::KeysU8(hash_method) => HashTable::KeyU8HashTable(KeyU8HashTable {
+                hash_table: HashMap::<u8, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU16(hash_method) => HashTable::KeyU16HashTable(KeyU16HashTable {
+                hash_table: HashMap::<u16, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU32(hash_method) => HashTable::KeyU32HashTable(KeyU32HashTable {
+                hash_table: HashMap::<u32, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU64(hash_method) => HashTable::KeyU64HashTable(KeyU64HashTable {
+                hash_table: HashMap::<u64, Vec<RowPtr>>::create(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU128(hash_method) => {
                 HashTable::KeyU128HashTable(KeyU128HashTable {
                     hash_table: HashMap::<u128, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU256(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+                })
+            }
+            HashMethodKind::KeysU256(hash_method) => {
                 HashTable::KeyU256HashTable(KeyU256HashTable {
                     hash_table: HashMap::<U256, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU512(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
+                })
+            }
+            HashMethodKind::KeysU512(hash_method) => {
                 HashTable::KeyU512HashTable(KeyU512HashTable {
                     hash_table: HashMap::<U512, Vec<RowPtr>>::create(),
                     hash_method,
-                }),
-                build_schema,
-                hash_join_desc,
-            )?),
+                })
+            }
         })
     }
 
@@ -224,6 +230,7 @@ impl JoinHashTable {
         hash_table: HashTable,
         mut build_data_schema: DataSchemaRef,
         hash_join_desc: HashJoinDesc,
+        spill_state: Option<JoinSpillState>,
     ) -> Result<Self> {
         if hash_join_desc.join_type == JoinType::Left
             || hash_join_desc.join_type == JoinType::Single
@@ -246,9 +253,40 @@ impl JoinHashTable {
             hash_table: RwLock::new(hash_table),
             row_ptrs: RwLock::new(vec![]),
             finished_notify: Arc::new(Notify::new()),
+            probe_ref_count: Mutex::new(0),
+            is_probe_finished: Mutex::new(false),
+            probe_finished_notify: Arc::new(Notify::new()),
+            spill_state,
+            runtime_filters: RwLock::new(StdHashMap::new()),
         })
     }
 
+    /// Builds the required runtime filters from the build keys. The probe rows filtered out
+    /// are never matched, which are only skipped by the joins discarding the unmatched rows.
+    fn build_runtime_filters(&self) -> Result<()> {
+        if !matches!(
+            self.hash_join_desc.join_type,
+            JoinType::Inner | JoinType::Semi
+        ) {
+            return Ok(());
+        }
+
+        let mut runtime_filters = self.runtime_filters.write();
+        if runtime_filters.is_empty() {
+            return Ok(());
+        }
+
+        let chunks = self.row_space.chunks.read().unwrap();
+        for (key_index, runtime_filter) in runtime_filters.iter_mut() {
+            let columns = chunks
+                .iter()
+                .map(|chunk| chunk.cols[*key_index].clone())
+                .collect::<Vec<_>>();
+            *runtime_filter = RuntimeFilter::try_create(self.ctx.clone(), &columns)?.map(Arc::new);
+        }
+        Ok(())
+    }
+
     // Merge build block and probe block that have the same number of rows
     pub(crate) fn merge_eq_block(
         &self,
@@ -406,6 +444,13 @@ impl JoinHashTable {
 #[async_trait::async_trait]
 impl HashJoinState for JoinHashTable {
     fn build(&self, input: DataBlock) -> Result<()> {
+        if let Some(spill_state) = &self.spill_state {
+            if spill_state.add_build_bytes(input.memory_size()) {
+                self.spill_build_chunks()?;
+                return self.spill_build_block(&input);
+            }
+        }
+
         let func_ctx = self.ctx.try_get_function_context()?;
         let build_cols = self
             .hash_join_desc
@@ -417,6 +462,11 @@ impl HashJoinState for JoinHashTable {
     }
 
     fn probe(&self, input: &DataBlock, probe_state: &mut ProbeState) -> Result<Vec<DataBlock>> {
+        if self.is_spilled() {
+            self.spill_probe_block(input)?;
+            return Ok(vec![]);
+        }
+
         match self.hash_join_desc.join_type {
             JoinType::Inner
             | JoinType::Semi
@@ -454,6 +504,12 @@ impl HashJoinState for JoinHashTable {
     }
 
     fn finish(&self) -> Result<()> {
+        if self.is_spilled() {
+            return self.finish_spilled_build();
+        }
+
+        self.build_runtime_filters()?;
+
         macro_rules! insert_key {
             ($table: expr, $markers: expr, $method: expr, $chunk: expr, $columns: expr,  $chunk_index: expr, ) => {{
                 let keys_state = $method.build_keys_state(&$columns, $chunk.num_rows())?;
@@ -610,14 +666,86 @@ impl HashJoinState for JoinHashTable {
     }
 
     async fn wait_finish(&self) -> Result<()> {
+        // Register the waiter before checking, so that the notification is not missed.
+        let notified = self.finished_notify.notified();
         if !self.is_finished()? {
-            self.finished_notify.notified().await;
+            notified.await;
         }
 
         Ok(())
     }
 
+    fn require_runtime_filter(&self, key_index: usize) -> Result<()> {
+        if key_index >= self.hash_join_desc.build_keys.len() {
+            return Err(ErrorCode::LogicalError(format!(
+                "Invalid key index {} of runtime filter",
+                key_index
+            )));
+        }
+        self.runtime_filters.write().insert(key_index, None);
+        Ok(())
+    }
+
+    fn runtime_filter(&self, key_index: usize) -> Option<Arc<RuntimeFilter>> {
+        self.runtime_filters
+            .read()
+            .get(&key_index)
+            .cloned()
+            .flatten()
+    }
+
+    fn probe_attach(&self) -> Result<()> {
+        let mut count = self.probe_ref_count.lock().unwrap();
+        *count += 1;
+        Ok(())
+    }
+
+    fn probe_detach(&self) -> Result<()> {
+        let mut count = self.probe_ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            let mut is_probe_finished = self.is_probe_finished.lock().unwrap();
+            *is_probe_finished = true;
+            self.probe_finished_notify.notify_waiters();
+        }
+        Ok(())
+    }
+
+    async fn wait_probe_finish(&self) -> Result<()> {
+        let notified = self.probe_finished_notify.notified();
+        let is_probe_finished = *self.is_probe_finished.lock().unwrap();
+        if !is_probe_finished {
+            notified.await;
+        }
+
+        Ok(())
+    }
+
+    fn is_spilled(&self) -> bool {
+        self.spill_state
+            .as_ref()
+            .map_or(false, |spill_state| spill_state.is_spilled())
+    }
+
+    async fn flush_spill(&self) -> Result<()> {
+        self.write_spilled_files().await
+    }
+
+    fn join_spilled_partitions(
+        &self,
+        tasks: &mut SpilledJoinTasks,
+        probe_state: &mut ProbeState,
+    ) -> Result<Option<Vec<DataBlock>>> {
+        self.join_next_spilled_partition(tasks, probe_state)
+    }
+
     fn mark_join_blocks(&self) -> Result<Vec<DataBlock>> {
+        if let Some(spill_state) = &self.spill_state {
+            if spill_state.is_spilled() {
+                return Ok(std::mem::take(&mut *spill_state.mark_blocks.lock()));
+            }
+        }
+
         let mut row_ptrs = self.row_ptrs.write();
         let has_null = self.hash_join_desc.marker_join_desc.has_null.read();
         let mut validity = MutableBitmap::with_capacity(row_ptrs.len());
@@ -13,13 +13,17 @@
 // limitations under the License.
 
 mod desc;
+mod grace_hash_join;
 mod hash_join_state;
 mod join_hash_table;
 mod probe_state;
 mod result_blocks;
 pub(crate) mod row;
+mod runtime_filter;
 
 pub use desc::HashJoinDesc;
+pub use grace_hash_join::JoinSpillState;
+pub use grace_hash_join::SpilledJoinTasks;
 pub use hash_join_state::HashJoinState;
 pub use join_hash_table::HashTable;
 pub use join_hash_table::JoinHashTable;
@@ -34,3 +38,6 @@ pub use join_hash_table::MarkJoinDesc;
 pub use join_hash_table::SerializerHashTable;
 pub use probe_state::ProbeState;
 pub use result_blocks::*;
+pub use runtime_filter::ProbeRuntimeFilter;
+pub use runtime_filter::RuntimeFilter;
+pub use runtime_filter::RUNTIME_FILTER_MAX_IN_LIST_SIZE;
@@ -0,0 +1,186 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::col;
+use common_planners::Expression;
+
+use super::HashJoinState;
+use crate::sessions::TableContext;
+use crate::storages::index::BloomFilter;
+use crate::storages::index::SupportedType;
+
+/// The maximum number of the distinct build keys kept in the IN-list of a runtime filter,
+/// a bloom filter of the build keys is built instead if there are more of them.
+pub const RUNTIME_FILTER_MAX_IN_LIST_SIZE: usize = 128;
+
+/// The false positive rate of the bloom filter of the build keys.
+const RUNTIME_FILTER_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// Filter on a probe key of hash join, derived from the build keys after the build phase.
+///
+/// The probe rows filtered out can't be matched by any build row, so they can be skipped
+/// before the join, e.g. by the scan of the probe side.
+pub struct RuntimeFilter {
+    /// The minimum and the maximum of the build keys, `None` if there are no build keys
+    /// other than NULLs, in which case no probe rows can be matched.
+    range: Option<(DataValue, DataValue)>,
+    /// The distinct build keys in ascending order, if there are no more than
+    /// `RUNTIME_FILTER_MAX_IN_LIST_SIZE` of them.
+    in_list: Option<Vec<DataValue>>,
+    /// The bloom filter of the build keys, if there are too many of them for an IN-list.
+    bloom_filter: Option<BloomFilter>,
+}
+
+impl RuntimeFilter {
+    /// Builds the runtime filter from the columns of a build key, returns `None` if the type
+    /// of the key is not supported.
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        columns: &[ColumnRef],
+    ) -> Result<Option<RuntimeFilter>> {
+        if let Some(column) = columns.first() {
+            if !Self::is_supported_type(&column.data_type()) {
+                return Ok(None);
+            }
+        }
+
+        let mut range: Option<(DataValue, DataValue)> = None;
+        let mut distinct_values = BTreeSet::new();
+        let mut num_values = 0u64;
+        for column in columns {
+            for row in 0..column.len() {
+                let value = column.get(row);
+                if value.is_null() {
+                    continue;
+                }
+
+                num_values += 1;
+                let (min, max) = range.get_or_insert_with(|| (value.clone(), value.clone()));
+                if value < *min {
+                    *min = value.clone();
+                } else if value > *max {
+                    *max = value.clone();
+                }
+                if distinct_values.len() <= RUNTIME_FILTER_MAX_IN_LIST_SIZE {
+                    distinct_values.insert(value);
+                }
+            }
+        }
+
+        if distinct_values.len() <= RUNTIME_FILTER_MAX_IN_LIST_SIZE {
+            return Ok(Some(RuntimeFilter {
+                range,
+                in_list: Some(distinct_values.into_iter().collect()),
+                bloom_filter: None,
+            }));
+        }
+
+        let mut bloom_filter =
+            BloomFilter::with_rate(num_values, RUNTIME_FILTER_FALSE_POSITIVE_RATE, 0);
+        for column in columns {
+            bloom_filter.add(column, ctx.clone())?;
+        }
+        Ok(Some(RuntimeFilter {
+            range,
+            in_list: None,
+            bloom_filter: Some(bloom_filter),
+        }))
+    }
+
+    /// Returns true if no probe rows can be matched.
+    pub fn is_empty(&self) -> bool {
+        self.range.is_none()
+    }
+
+    /// Returns the predicate on the probe column implied by the filter, with which the blocks
+    /// are pruned by the column statistics and the bloom indexes.
+    pub fn to_expression(&self, column_name: &str, data_type: &DataTypeImpl) -> Option<Expression> {
+        let (min, max) = self.range.as_ref()?;
+        let literal = |value: &DataValue| {
+            Expression::create_literal_with_type(value.clone(), data_type.clone())
+        };
+
+        let column = col(column_name);
+        let range = column.gt_eq(literal(min)).and(column.lt_eq(literal(max)));
+        match &self.in_list {
+            Some(in_list) => {
+                let points = in_list
+                    .iter()
+                    .map(|value| column.eq(literal(value)))
+                    .reduce(|left, right| left.or(right))?;
+                Some(range.and(points))
+            }
+            None => Some(range),
+        }
+    }
+
+    /// Returns whether each row of the probe column may be matched by the build keys.
+    pub fn eval(&self, column: &ColumnRef, ctx: Arc<dyn TableContext>) -> Result<Vec<bool>> {
+        let (min, max) = match &self.range {
+            Some(range) => range,
+            None => return Ok(vec![false; column.len()]),
+        };
+
+        let column = column.convert_full_column();
+        let mut picked = match &self.bloom_filter {
+            Some(bloom_filter) => bloom_filter.find_column(&column, ctx)?,
+            None => vec![true; column.len()],
+        };
+        for (row, picked) in picked.iter_mut().enumerate() {
+            if !*picked {
+                continue;
+            }
+
+            let value = column.get(row);
+            *picked = !value.is_null()
+                && value >= *min
+                && value <= *max
+                && match &self.in_list {
+                    Some(in_list) => in_list.binary_search(&value).is_ok(),
+                    None => true,
+                };
+        }
+        Ok(picked)
+    }
+}
+
+impl SupportedType for RuntimeFilter {}
+
+/// A runtime filter on a probe key, which is published by the hash join after the build phase.
+#[derive(Clone)]
+pub struct ProbeRuntimeFilter {
+    join_state: Arc<dyn HashJoinState>,
+    key_index: usize,
+}
+
+impl ProbeRuntimeFilter {
+    pub fn create(join_state: Arc<dyn HashJoinState>, key_index: usize) -> Result<Self> {
+        join_state.require_runtime_filter(key_index)?;
+        Ok(ProbeRuntimeFilter {
+            join_state,
+            key_index,
+        })
+    }
+
+    /// Waits until the build phase is finished, and returns the runtime filter if it's published.
+    pub async fn wait(&self) -> Result<Option<Arc<RuntimeFilter>>> {
+        self.join_state.wait_finish().await?;
+        Ok(self.join_state.runtime_filter(self.key_index))
+    }
+}
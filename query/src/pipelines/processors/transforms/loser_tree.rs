@@ -0,0 +1,117 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+
+/// A loser tree to merge k sorted streams.
+///
+/// Each internal node keeps the loser of the match between its two children and the winner
+/// of the whole tree is kept at the top, so replacing the head of the winner stream only replays
+/// the matches on the path from its leaf to the root, that is `log(k)` comparisons.
+///
+/// The heads with equal values are popped in the order of the streams, which keeps the merge stable.
+pub struct LoserTree<T, F> {
+    // tree[0] is the winner, tree[1..k] are the losers of the internal nodes.
+    tree: Vec<usize>,
+    // The heads of the streams, `None` means the stream is exhausted.
+    values: Vec<Option<T>>,
+    compare: F,
+}
+
+impl<T, F> LoserTree<T, F>
+where F: Fn(&T, &T) -> Ordering
+{
+    pub fn create(values: Vec<Option<T>>, compare: F) -> Self {
+        let streams = values.len();
+
+        // Fill the nodes with a virtual stream which beats all the others, then replay the
+        // leaves one by one, each leaf pushes one virtual stream out of the tree.
+        let mut tree = LoserTree {
+            tree: vec![streams; streams.max(1)],
+            values,
+            compare,
+        };
+
+        for leaf in (0..streams).rev() {
+            tree.replay(leaf);
+        }
+
+        tree
+    }
+
+    /// The index of the stream with the smallest head, `None` if all the streams are exhausted.
+    pub fn winner(&self) -> Option<usize> {
+        let winner = self.tree[0];
+        match self.values.get(winner) {
+            Some(Some(_)) => Some(winner),
+            _ => None,
+        }
+    }
+
+    /// The smallest head of the streams.
+    pub fn peek(&self) -> Option<&T> {
+        self.values
+            .get(self.tree[0])
+            .and_then(|value| value.as_ref())
+    }
+
+    /// Replace the head of the winner stream with its next value, `None` if the stream is exhausted.
+    pub fn update(&mut self, value: Option<T>) {
+        let winner = self.tree[0];
+        if winner < self.values.len() {
+            self.values[winner] = value;
+            self.replay(winner);
+        }
+    }
+
+    fn replay(&mut self, leaf: usize) {
+        let streams = self.values.len();
+
+        let mut winner = leaf;
+        let mut node = (leaf + streams) / 2;
+        while node > 0 {
+            let loser = self.tree[node];
+            // The virtual stream `streams` beats all the real streams, and it's only kept in
+            // the tree while the leaves are replayed in `create`.
+            let loser_wins = match (loser == streams, winner == streams) {
+                (true, true) => false,
+                (true, false) => true,
+                (false, true) => false,
+                (false, false) => self.beats(loser, winner),
+            };
+            if loser_wins {
+                self.tree[node] = winner;
+                winner = loser;
+            }
+            node /= 2;
+        }
+
+        self.tree[0] = winner;
+    }
+
+    fn beats(&self, left: usize, right: usize) -> bool {
+        match (&self.values[left], &self.values[right]) {
+            (Some(left_value), Some(right_value)) => {
+                match (self.compare)(left_value, right_value) {
+                    Ordering::Less => true,
+                    Ordering::Greater => false,
+                    Ordering::Equal => left < right,
+                }
+            }
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => left < right,
+        }
+    }
+}
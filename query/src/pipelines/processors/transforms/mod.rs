@@ -14,11 +14,14 @@
 
 mod aggregator;
 pub(crate) mod hash_join;
+mod loser_tree;
+mod spiller;
 mod transform;
 mod transform_addon;
 mod transform_aggregator;
 mod transform_block_compact;
 mod transform_cast_schema;
+mod transform_check_constraints;
 mod transform_compact;
 mod transform_create_sets;
 mod transform_dummy;
@@ -31,11 +34,18 @@ mod transform_hash_join;
 mod transform_limit;
 mod transform_limit_by;
 mod transform_mark_join;
+mod transform_merge_block;
+mod transform_nested_loop_join;
 mod transform_project;
 mod transform_rename;
+mod transform_set_operation;
+mod transform_sort_external;
 mod transform_sort_merge;
+mod transform_sort_merge_join;
 mod transform_sort_partial;
+mod transform_window;
 mod transform_window_func;
+mod transform_window_shuffle;
 
 pub mod group_by;
 
@@ -52,12 +62,21 @@ pub use hash_join::KeyU32HashTable;
 pub use hash_join::KeyU512HashTable;
 pub use hash_join::KeyU64HashTable;
 pub use hash_join::KeyU8HashTable;
+pub use hash_join::ProbeRuntimeFilter;
+pub use hash_join::RuntimeFilter;
 pub use hash_join::SerializerHashTable;
+pub use loser_tree::LoserTree;
+pub use spiller::SpillWrites;
+pub use spiller::SpilledFile;
+pub use spiller::Spiller;
+pub use transform_addon::GeneratedColumnsExecutor;
 pub use transform_addon::TransformAddOn;
 pub use transform_aggregator::TransformAggregator;
 pub use transform_block_compact::BlockCompactor;
 pub use transform_block_compact::TransformBlockCompact;
 pub use transform_cast_schema::TransformCastSchema;
+pub use transform_check_constraints::CheckConstraintsExecutor;
+pub use transform_check_constraints::TransformCheckConstraints;
 pub use transform_compact::Compactor;
 pub use transform_compact::TransformCompact;
 pub use transform_create_sets::SubqueryReceiver;
@@ -76,11 +95,25 @@ pub use transform_limit::TransformLimit;
 pub use transform_limit_by::TransformLimitBy;
 pub use transform_mark_join::MarkJoinCompactor;
 pub use transform_mark_join::TransformMarkJoin;
+pub use transform_merge_block::TransformMergeBlock;
+pub use transform_nested_loop_join::NestedLoopJoinState;
+pub use transform_nested_loop_join::SinkNestedLoopJoinBuild;
+pub use transform_nested_loop_join::TransformNestedLoopJoin;
 pub use transform_project::TransformProject;
 pub use transform_rename::TransformRename;
+pub use transform_set_operation::SetOperationState;
+pub use transform_set_operation::SinkSetOperationBuild;
+pub use transform_set_operation::TransformSetOperation;
+pub use transform_sort_external::TransformSortExternal;
 pub use transform_sort_merge::SortMergeCompactor;
 pub use transform_sort_merge::TransformSortMerge;
+pub use transform_sort_merge_join::SinkSortMergeJoinBuild;
+pub use transform_sort_merge_join::SortMergeJoinState;
+pub use transform_sort_merge_join::TransformSortMergeJoin;
 pub use transform_sort_partial::get_sort_descriptions;
 pub use transform_sort_partial::TransformSortPartial;
+pub use transform_window::TransformWindow;
+pub use transform_window::WindowEvaluator;
 pub use transform_window_func::TransformWindowFunc;
 pub use transform_window_func::WindowFuncCompact;
+pub use transform_window_shuffle::TransformWindowShuffle;
@@ -0,0 +1,321 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_base::base::uuid::Uuid;
+use common_base::base::MemoryTracker;
+use common_base::base::TrySpawn;
+use common_datablocks::serialize_data_blocks;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_formats::FormatFactory;
+use common_io::prelude::FileSplit;
+use common_io::prelude::FormatSettings;
+use opendal::Operator;
+
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// Spill data blocks into the spill location of the query when the memory usage of the query
+/// exceeds the limit.
+///
+/// The spilled blocks are grouped into partitions, each partition can be read back
+/// independently, so the caller can process the spilled data partition by partition.
+///
+/// `spill` only serializes the blocks, the files are written by `flush` and read back by
+/// `load_partition` or `SpilledFile::read`, which are expected to be called in the async
+/// step of the processors, so the processors never block the executor threads on the I/O.
+pub struct Spiller {
+    ctx: Arc<QueryContext>,
+    operator: Operator,
+    location_prefix: String,
+    memory_limit: usize,
+    format_settings: FormatSettings,
+    schema: Option<DataSchemaRef>,
+    // The spilled files of the partitions which have not been read back.
+    partitions: BTreeMap<usize, VecDeque<String>>,
+    // The partitions which have been read back but not been taken.
+    loaded: BTreeMap<usize, Vec<DataBlock>>,
+    pending_writes: Vec<(String, Vec<u8>)>,
+    spilled_files: usize,
+}
+
+impl Spiller {
+    /// Create a spiller, `memory_limit` is the memory usage in bytes of the query
+    /// to start spilling, zero means never spill.
+    pub fn create(ctx: Arc<QueryContext>, kind: &str, memory_limit: usize) -> Result<Self> {
+        let (operator, prefix) = ctx.get_spill_operator();
+        let location_prefix = format!("{}{}_{}", prefix, kind, Uuid::new_v4());
+        let format_settings = ctx.get_format_settings()?;
+
+        Ok(Spiller {
+            ctx,
+            operator,
+            location_prefix,
+            memory_limit,
+            format_settings,
+            schema: None,
+            partitions: BTreeMap::new(),
+            loaded: BTreeMap::new(),
+            pending_writes: vec![],
+            spilled_files: 0,
+        })
+    }
+
+    /// Whether the memory usage of the current query exceeds the limit.
+    pub fn need_spill(&self) -> bool {
+        if self.memory_limit == 0 {
+            return false;
+        }
+
+        match MemoryTracker::current() {
+            None => false,
+            Some(tracker) => tracker.get_memory_usage() > self.memory_limit as i64,
+        }
+    }
+
+    pub fn is_spilled(&self) -> bool {
+        !self.partitions.is_empty() || !self.loaded.is_empty()
+    }
+
+    /// The smallest partition that has spilled data.
+    pub fn next_partition(&self) -> Option<usize> {
+        let spilled = self.partitions.keys().next().cloned();
+        let loaded = self.loaded.keys().next().cloned();
+        match (spilled, loaded) {
+            (Some(spilled), Some(loaded)) => Some(spilled.min(loaded)),
+            (spilled, loaded) => spilled.or(loaded),
+        }
+    }
+
+    /// Serialize the blocks into a file of the partition, the file is written by `flush`.
+    /// The blocks are kept in memory if the partition has been loaded.
+    pub fn spill(&mut self, partition: usize, blocks: Vec<DataBlock>) -> Result<()> {
+        let blocks = blocks
+            .into_iter()
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>();
+
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(loaded) = self.loaded.get_mut(&partition) {
+            loaded.extend(blocks);
+            return Ok(());
+        }
+
+        let schema = self
+            .schema
+            .get_or_insert_with(|| blocks[0].schema().clone())
+            .clone();
+
+        let mut data = vec![];
+        serialize_data_blocks(blocks, &schema, &mut data)?;
+
+        let location = format!(
+            "{}/{}_{}",
+            self.location_prefix, partition, self.spilled_files
+        );
+        self.spilled_files += 1;
+        if self.spilled_files == 1 {
+            self.ctx.set_spilled();
+        }
+
+        tracing::debug!("Spilled partition {} into {}", partition, location);
+        self.partitions
+            .entry(partition)
+            .or_default()
+            .push_back(location.clone());
+        self.pending_writes.push((location, data));
+        Ok(())
+    }
+
+    pub fn has_pending_writes(&self) -> bool {
+        !self.pending_writes.is_empty()
+    }
+
+    /// Take the files to write, so they can be written without holding the spiller.
+    pub fn take_pending_writes(&mut self) -> SpillWrites {
+        SpillWrites {
+            operator: self.operator.clone(),
+            writes: std::mem::take(&mut self.pending_writes),
+        }
+    }
+
+    /// Write the spilled files into the storage.
+    pub async fn flush(&mut self) -> Result<()> {
+        self.take_pending_writes().write().await
+    }
+
+    /// Whether there is no spilled file of the partition to read back.
+    pub fn is_loaded(&self, partition: usize) -> bool {
+        !self.partitions.contains_key(&partition)
+    }
+
+    /// Read back all the spilled files of the partition, the files are removed after reading.
+    pub async fn load_partition(&mut self, partition: usize) -> Result<()> {
+        self.flush().await?;
+
+        let mut blocks = vec![];
+        while let Some(file) = self.pop_partition_file(partition) {
+            blocks.extend(file.read().await?);
+        }
+
+        self.loaded.entry(partition).or_default().extend(blocks);
+        Ok(())
+    }
+
+    /// Whether there are files to write or the next partition needs to be loaded.
+    pub fn has_pending_io(&self) -> bool {
+        self.has_pending_writes()
+            || matches!(self.next_partition(), Some(partition) if !self.is_loaded(partition))
+    }
+
+    /// Write the spilled files, and load the next partition.
+    pub async fn process_pending_io(&mut self) -> Result<()> {
+        self.flush().await?;
+
+        match self.next_partition() {
+            Some(partition) if !self.is_loaded(partition) => self.load_partition(partition).await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Take the blocks of the partition, the partition must have been loaded.
+    pub fn read_partition(&mut self, partition: usize) -> Result<Vec<DataBlock>> {
+        if !self.is_loaded(partition) {
+            return Err(ErrorCode::LogicalError(format!(
+                "The spilled partition {} is not loaded, it's a bug.",
+                partition
+            )));
+        }
+
+        Ok(self.loaded.remove(&partition).unwrap_or_default())
+    }
+
+    /// Take the earliest spilled file of the partition, so a large partition can be read
+    /// file by file. Returns `None` if all the files of the partition have been taken.
+    pub fn pop_partition_file(&mut self, partition: usize) -> Option<SpilledFile> {
+        let locations = self.partitions.get_mut(&partition)?;
+        let location = locations.pop_front();
+        if locations.is_empty() {
+            self.partitions.remove(&partition);
+        }
+
+        let location = location?;
+        let data = self
+            .pending_writes
+            .iter()
+            .position(|(pending, _)| pending == &location)
+            .map(|index| self.pending_writes.remove(index).1);
+
+        Some(SpilledFile {
+            operator: self.operator.clone(),
+            schema: self.schema.clone(),
+            format_settings: self.format_settings.clone(),
+            location,
+            data,
+        })
+    }
+
+    /// Read back the blocks of the earliest spilled file of the partition.
+    /// Returns `None` if all the files of the partition have been read.
+    pub async fn read_partition_file(
+        &mut self,
+        partition: usize,
+    ) -> Result<Option<Vec<DataBlock>>> {
+        match self.pop_partition_file(partition) {
+            None => Ok(None),
+            Some(file) => Ok(Some(file.read().await?)),
+        }
+    }
+}
+
+impl Drop for Spiller {
+    fn drop(&mut self) {
+        // Clean up the files that have not been read back, e.g. the query is aborted.
+        let locations = std::mem::take(&mut self.partitions)
+            .into_values()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        if !locations.is_empty() {
+            let operator = self.operator.clone();
+            self.ctx.get_storage_runtime().spawn(async move {
+                for location in locations {
+                    let _ = operator.object(&location).delete().await;
+                }
+            });
+        }
+    }
+}
+
+/// The spilled files taken from a spiller to write.
+pub struct SpillWrites {
+    operator: Operator,
+    writes: Vec<(String, Vec<u8>)>,
+}
+
+impl SpillWrites {
+    pub async fn write(self) -> Result<()> {
+        for (location, data) in self.writes {
+            self.operator.object(&location).write(data).await?;
+        }
+        Ok(())
+    }
+}
+
+/// A spilled file taken from a spiller to read back, the file is removed after reading.
+pub struct SpilledFile {
+    operator: Operator,
+    schema: Option<DataSchemaRef>,
+    format_settings: FormatSettings,
+    location: String,
+    // The data of the file if it has not been written.
+    data: Option<Vec<u8>>,
+}
+
+impl SpilledFile {
+    pub async fn read(self) -> Result<Vec<DataBlock>> {
+        let schema = match self.schema {
+            None => return Ok(vec![]),
+            Some(schema) => schema,
+        };
+
+        let data = match self.data {
+            Some(data) => data,
+            None => {
+                let object = self.operator.object(&self.location);
+                let data = object.read().await?;
+                object.delete().await?;
+                data
+            }
+        };
+
+        let input_format =
+            FormatFactory::instance().get_input("parquet", schema, self.format_settings)?;
+        input_format.deserialize_complete_split(FileSplit {
+            path: Some(self.location),
+            start_offset: 0,
+            start_row: 0,
+            buf: data,
+        })
+    }
+}
@@ -18,6 +18,7 @@ use common_datablocks::DataBlock;
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
 use common_exception::Result;
 use common_planners::Expression;
@@ -29,6 +30,7 @@ use crate::pipelines::processors::transforms::transform::Transform;
 use crate::pipelines::processors::transforms::transform::Transformer;
 use crate::pipelines::processors::transforms::ExpressionExecutor;
 use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
 use crate::sql::PlanParser;
 
 pub struct TransformAddOn {
@@ -36,6 +38,8 @@ pub struct TransformAddOn {
     default_nonexpr_fields: Vec<DataField>,
 
     expression_executor: ExpressionExecutor,
+    // the stored generated columns, computed once the missing columns are filled
+    generated_columns_executor: Option<GeneratedColumnsExecutor>,
     output_schema: DataSchemaRef,
 }
 
@@ -54,20 +58,10 @@ where Self: Transform
         let mut default_nonexpr_fields = Vec::new();
 
         for f in output_schema.fields() {
-            if !input_schema.has_field(f.name()) {
+            if !f.is_generated() && !input_schema.has_field(f.name()) {
                 if let Some(expr) = f.default_expr() {
-                    let expression = PlanParser::parse_expr(expr)?;
-                    let expression = Expression::Alias(
-                        f.name().to_string(),
-                        Box::new(Expression::Cast {
-                            expr: Box::new(expression),
-                            data_type: f.data_type().clone(),
-                            pg_style: false,
-                        }),
-                    );
-
                     default_expr_fields.push(f.clone());
-                    default_exprs.push(expression);
+                    default_exprs.push(cast_expr(f, expr)?);
                 } else {
                     default_nonexpr_fields.push(f.clone());
                 }
@@ -75,7 +69,7 @@ where Self: Transform
         }
         let schema_after_default_expr = Arc::new(DataSchema::new(default_expr_fields.clone()));
         let expression_executor = ExpressionExecutor::try_create(
-            ctx,
+            ctx.clone(),
             "stream_addon",
             input_schema,
             schema_after_default_expr,
@@ -83,10 +77,14 @@ where Self: Transform
             true,
         )?;
 
+        let generated_columns_executor =
+            GeneratedColumnsExecutor::try_create(ctx, output_schema.clone())?;
+
         Ok(Transformer::create(input, output, Self {
             default_expr_fields,
             default_nonexpr_fields,
             expression_executor,
+            generated_columns_executor,
             output_schema,
         }))
     }
@@ -112,6 +110,89 @@ impl Transform for TransformAddOn {
 
             block = block.add_column(column, f.clone())?;
         }
+
+        // the values of the generated columns are always computed, never taken from the input
+        match &self.generated_columns_executor {
+            Some(executor) => executor.execute(block),
+            None => block.resort(self.output_schema.clone()),
+        }
+    }
+}
+
+/// Computes the values of the stored generated columns of a table from its other columns.
+pub struct GeneratedColumnsExecutor {
+    generated_fields: Vec<DataField>,
+    // the other columns of the table, which the generated columns are computed from
+    input_schema: DataSchemaRef,
+    expression_executor: ExpressionExecutor,
+    output_schema: DataSchemaRef,
+}
+
+impl GeneratedColumnsExecutor {
+    /// Returns `None` if the table has no generated columns.
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        table_schema: DataSchemaRef,
+    ) -> Result<Option<Self>> {
+        let mut generated_fields = Vec::new();
+        let mut generated_exprs = Vec::new();
+        for f in table_schema.fields() {
+            if let Some(expr) = f.generated_expr() {
+                generated_fields.push(f.clone());
+                generated_exprs.push(cast_expr(f, expr)?);
+            }
+        }
+        if generated_fields.is_empty() {
+            return Ok(None);
+        }
+
+        let input_schema = DataSchemaRefExt::create(
+            table_schema
+                .fields()
+                .iter()
+                .filter(|f| !f.is_generated())
+                .cloned()
+                .collect(),
+        );
+        let expression_executor = ExpressionExecutor::try_create(
+            ctx,
+            "stream_addon_generated",
+            input_schema.clone(),
+            DataSchemaRefExt::create(generated_fields.clone()),
+            generated_exprs,
+            true,
+        )?;
+        Ok(Some(Self {
+            generated_fields,
+            input_schema,
+            expression_executor,
+            output_schema: table_schema,
+        }))
+    }
+
+    /// Computes the generated columns of the block holding the other columns of the table, the
+    /// values of the generated columns in the block, if any, are discarded.
+    pub fn execute(&self, block: DataBlock) -> Result<DataBlock> {
+        let mut block = block.resort(self.input_schema.clone())?;
+        let generated_block = self.expression_executor.execute(&block)?;
+        for f in self.generated_fields.iter() {
+            block = block.add_column(
+                generated_block.try_column_by_name(f.name())?.clone(),
+                f.clone(),
+            )?;
+        }
         block.resort(self.output_schema.clone())
     }
 }
+
+fn cast_expr(f: &DataField, expr: &str) -> Result<Expression> {
+    let expression = PlanParser::parse_expr(expr)?;
+    Ok(Expression::Alias(
+        f.name().to_string(),
+        Box::new(Expression::Cast {
+            expr: Box::new(expression),
+            data_type: f.data_type().clone(),
+            pg_style: false,
+        }),
+    ))
+}
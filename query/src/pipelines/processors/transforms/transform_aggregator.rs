@@ -25,6 +25,7 @@ use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
 use crate::pipelines::processors::processor::ProcessorPtr;
 use crate::pipelines::processors::transforms::aggregator::*;
+use crate::pipelines::processors::transforms::Spiller;
 use crate::pipelines::processors::AggregatorTransformParams;
 use crate::pipelines::processors::Processor;
 use crate::sessions::QueryContext;
@@ -157,84 +158,84 @@ impl TransformAggregator {
                 HashMethodKind::KeysU8(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU8PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU8PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU16(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU16PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU16PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU32(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU32PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU32PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU64(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU64PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU64PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU128(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU128PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU128PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU256(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU256PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU256PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU512(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU512PartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    KeysU512PartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::Serializer(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SerializerPartialAggregator::<false>::create(ctx, method, aggregator_params),
+                    SerializerPartialAggregator::<false>::create(ctx, method, aggregator_params)?,
                 ),
             },
             false => match transform_params.method {
                 HashMethodKind::KeysU8(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU8PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU8PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU16(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU16PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU16PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU32(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU32PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU32PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU64(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU64PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU64PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU128(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU128PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU128PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU256(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU256PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU256PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::KeysU512(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    KeysU512PartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    KeysU512PartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
                 HashMethodKind::Serializer(method) => AggregatorTransform::create(
                     transform_params.transform_input_port,
                     transform_params.transform_output_port,
-                    SerializerPartialAggregator::<true>::create(ctx, method, aggregator_params),
+                    SerializerPartialAggregator::<true>::create(ctx, method, aggregator_params)?,
                 ),
             },
         }
@@ -245,7 +246,18 @@ pub trait Aggregator: Sized + Send {
     const NAME: &'static str;
 
     fn consume(&mut self, data: DataBlock) -> Result<()>;
+
+    /// Called once all the input is consumed, before generating.
+    fn finish_consume(&mut self) -> Result<()> {
+        Ok(())
+    }
+
     fn generate(&mut self) -> Result<Option<DataBlock>>;
+
+    /// The spiller of the aggregator, whose I/O is done in the async step of the transform.
+    fn spiller(&mut self) -> Option<&mut Spiller> {
+        None
+    }
 }
 
 enum AggregatorTransform<TAggregator: Aggregator> {
@@ -278,6 +290,7 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
                 Ok(AggregatorTransform::Generate(GenerateState {
                     inner: s.inner,
                     is_finished: false,
+                    consume_finished: false,
                     output_port: s.output_port,
                     output_data_block: None,
                 }))
@@ -287,6 +300,7 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
     }
 }
 
+#[async_trait::async_trait]
 impl<TAggregator: Aggregator + 'static> Processor for AggregatorTransform<TAggregator> {
     fn name(&self) -> &'static str {
         TAggregator::NAME
@@ -311,12 +325,32 @@ impl<TAggregator: Aggregator + 'static> Processor for AggregatorTransform<TAggre
             AggregatorTransform::Generate(state) => state.generate(),
         }
     }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match self {
+            AggregatorTransform::Finished => Ok(()),
+            AggregatorTransform::ConsumeData(state) => match state.inner.spiller() {
+                None => Ok(()),
+                Some(spiller) => spiller.flush().await,
+            },
+            AggregatorTransform::Generate(state) => match state.inner.spiller() {
+                None => Ok(()),
+                Some(spiller) => spiller.process_pending_io().await,
+            },
+        }
+    }
 }
 
 impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
     #[inline(always)]
     fn consume_event(&mut self) -> Result<Event> {
         if let AggregatorTransform::ConsumeData(state) = self {
+            if let Some(spiller) = state.inner.spiller() {
+                if spiller.has_pending_writes() {
+                    return Ok(Event::Async);
+                }
+            }
+
             if state.input_data_block.is_some() {
                 return Ok(Event::Sync);
             }
@@ -373,6 +407,14 @@ impl<TAggregator: Aggregator + 'static> AggregatorTransform<TAggregator> {
                 return Ok(Event::Finished);
             }
 
+            if state.consume_finished {
+                if let Some(spiller) = state.inner.spiller() {
+                    if spiller.has_pending_io() {
+                        return Ok(Event::Async);
+                    }
+                }
+            }
+
             return Ok(Event::Sync);
         }
 
@@ -400,12 +442,18 @@ impl<TAggregator: Aggregator> ConsumeState<TAggregator> {
 struct GenerateState<TAggregator: Aggregator> {
     inner: TAggregator,
     is_finished: bool,
+    consume_finished: bool,
     output_port: Arc<OutputPort>,
     output_data_block: Option<DataBlock>,
 }
 
 impl<TAggregator: Aggregator> GenerateState<TAggregator> {
     pub fn generate(&mut self) -> Result<()> {
+        if !self.consume_finished {
+            self.consume_finished = true;
+            return self.inner.finish_consume();
+        }
+
         let generate_data = self.inner.generate()?;
 
         if generate_data.is_none() {
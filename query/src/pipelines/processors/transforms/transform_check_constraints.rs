@@ -0,0 +1,129 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::Series;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableCheckConstraint;
+use common_meta_app::schema::TableInfo;
+use common_planners::not;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::transforms::transform::Transform;
+use crate::pipelines::processors::transforms::transform::Transformer;
+use crate::pipelines::processors::transforms::ExpressionExecutor;
+use crate::sessions::TableContext;
+use crate::sql::PlanParser;
+
+/// Checks the rows written into a table against the CHECK constraints of the table.
+///
+/// A row violates a constraint only if the expression of the constraint evaluates to false,
+/// the rows evaluating it to NULL satisfy the constraint.
+pub struct CheckConstraintsExecutor {
+    table: String,
+    constraints: Vec<(TableCheckConstraint, ExpressionExecutor)>,
+}
+
+impl CheckConstraintsExecutor {
+    pub fn try_create(ctx: Arc<dyn TableContext>, table_info: &TableInfo) -> Result<Self> {
+        let schema = table_info.schema();
+        let constraints = table_info
+            .meta
+            .check_constraints
+            .values()
+            .map(|constraint| {
+                // the rows evaluating `NOT <expr>` to true are the violations
+                let expr = not(PlanParser::parse_expr(&constraint.expr)?);
+                let expr_field = expr.to_data_field(&schema)?;
+                let executor = ExpressionExecutor::try_create(
+                    ctx.clone(),
+                    "check constraint executor",
+                    schema.clone(),
+                    DataSchemaRefExt::create(vec![expr_field]),
+                    vec![expr],
+                    false,
+                )?;
+                Ok((constraint.clone(), executor))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            table: table_info.desc.clone(),
+            constraints,
+        })
+    }
+
+    /// Returns an error naming the constraint and the first row violating it, if any.
+    pub fn check(&self, block: &DataBlock) -> Result<()> {
+        for (constraint, executor) in &self.constraints {
+            let result = executor.execute(block)?;
+            let violations = DataBlock::cast_to_nonull_boolean(result.column(0))?;
+            let violated_row = match DataBlock::try_as_const_bool(&violations)? {
+                Some(const_bool) => (const_bool && block.num_rows() > 0).then_some(0),
+                None => {
+                    let boolean_col: &BooleanColumn = Series::check_get(&violations)?;
+                    boolean_col.values().iter().position(|v| v)
+                }
+            };
+
+            if let Some(row) = violated_row {
+                let values = block
+                    .columns()
+                    .iter()
+                    .map(|column| column.get(row).to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(ErrorCode::CheckConstraintViolated(format!(
+                    "check constraint {} of table {} is violated by the row ({}): {}",
+                    constraint.name, self.table, values, constraint.expr
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct TransformCheckConstraints {
+    executor: CheckConstraintsExecutor,
+}
+
+impl TransformCheckConstraints
+where Self: Transform
+{
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        ctx: Arc<dyn TableContext>,
+        table_info: &TableInfo,
+    ) -> Result<ProcessorPtr> {
+        let executor = CheckConstraintsExecutor::try_create(ctx, table_info)?;
+        Ok(Transformer::create(input, output, Self { executor }))
+    }
+}
+
+impl Transform for TransformCheckConstraints {
+    const NAME: &'static str = "CheckConstraintsTransform";
+
+    fn transform(&mut self, block: DataBlock) -> Result<DataBlock> {
+        self.executor.check(&block)?;
+        Ok(block)
+    }
+}
@@ -21,13 +21,14 @@ use common_datavalues::DataSchemaRef;
 use common_exception::Result;
 
 use super::hash_join::ProbeState;
+use super::hash_join::SpilledJoinTasks;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
 use crate::pipelines::processors::processor::ProcessorPtr;
 use crate::pipelines::processors::transforms::hash_join::HashJoinState;
+use crate::pipelines::processors::AsyncSink;
 use crate::pipelines::processors::Processor;
-use crate::pipelines::processors::Sink;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 
@@ -42,21 +43,30 @@ impl SinkBuildHashTable {
     }
 }
 
-impl Sink for SinkBuildHashTable {
+#[async_trait::async_trait]
+impl AsyncSink for SinkBuildHashTable {
     const NAME: &'static str = "BuildHashTable";
 
-    fn on_finish(&mut self) -> Result<()> {
-        self.join_state.detach()
+    async fn on_finish(&mut self) -> Result<()> {
+        self.join_state.detach()?;
+        self.join_state.flush_spill().await
     }
 
-    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
-        self.join_state.build(data_block)
+    #[async_trait::unboxed_simple]
+    async fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        self.join_state.build(data_block)?;
+        self.join_state.flush_spill().await
     }
 }
 
 enum HashJoinStep {
     Build,
     Probe,
+    // Wait for all the probe processors to finish spilling the probe side
+    WaitProbe,
+    // Join the spilled partitions
+    JoinSpilled,
+    Finished,
 }
 
 pub struct TransformHashJoinProbe {
@@ -68,18 +78,23 @@ pub struct TransformHashJoinProbe {
     step: HashJoinStep,
     join_state: Arc<dyn HashJoinState>,
     probe_state: ProbeState,
+    probe_attached: bool,
+    // Whether the probe side has been spilled and the spilled files need to be written
+    probe_spilled: bool,
+    spilled_tasks: SpilledJoinTasks,
 }
 
 impl TransformHashJoinProbe {
-    pub fn create(
+    pub fn try_create(
         ctx: Arc<QueryContext>,
         input_port: Arc<InputPort>,
         output_port: Arc<OutputPort>,
         join_state: Arc<dyn HashJoinState>,
         _output_schema: DataSchemaRef,
-    ) -> ProcessorPtr {
+    ) -> Result<ProcessorPtr> {
         let default_block_size = ctx.get_settings().get_max_block_size().unwrap_or(102400);
-        ProcessorPtr::create(Box::new(TransformHashJoinProbe {
+        join_state.probe_attach()?;
+        Ok(ProcessorPtr::create(Box::new(TransformHashJoinProbe {
             input_data: None,
             output_data_blocks: VecDeque::new(),
             input_port,
@@ -87,7 +102,18 @@ impl TransformHashJoinProbe {
             step: HashJoinStep::Build,
             join_state,
             probe_state: ProbeState::with_capacity(default_block_size as usize),
-        }))
+            probe_attached: true,
+            probe_spilled: false,
+            spilled_tasks: SpilledJoinTasks::default(),
+        })))
+    }
+
+    fn probe_detach(&mut self) -> Result<()> {
+        if self.probe_attached {
+            self.probe_attached = false;
+            self.join_state.probe_detach()?;
+        }
+        Ok(())
     }
 
     fn probe(&mut self, block: &DataBlock) -> Result<()> {
@@ -110,10 +136,36 @@ impl Processor for TransformHashJoinProbe {
 
     fn event(&mut self) -> Result<Event> {
         match self.step {
-            HashJoinStep::Build => Ok(Event::Async),
+            HashJoinStep::Build | HashJoinStep::WaitProbe => Ok(Event::Async),
+            HashJoinStep::Finished => {
+                self.output_port.finish();
+                Ok(Event::Finished)
+            }
+            HashJoinStep::JoinSpilled => {
+                if self.output_port.is_finished() {
+                    return Ok(Event::Finished);
+                }
+
+                if !self.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                if !self.output_data_blocks.is_empty() {
+                    let data = self.output_data_blocks.pop_front().unwrap();
+                    self.output_port.push_data(Ok(data));
+                    return Ok(Event::NeedConsume);
+                }
+
+                if self.spilled_tasks.need_read() {
+                    return Ok(Event::Async);
+                }
+
+                Ok(Event::Sync)
+            }
             HashJoinStep::Probe => {
                 if self.output_port.is_finished() {
                     self.input_port.finish();
+                    self.probe_detach()?;
                     return Ok(Event::Finished);
                 }
 
@@ -128,6 +180,10 @@ impl Processor for TransformHashJoinProbe {
                     return Ok(Event::NeedConsume);
                 }
 
+                if self.probe_spilled {
+                    return Ok(Event::Async);
+                }
+
                 if self.input_data.is_some() {
                     return Ok(Event::Sync);
                 }
@@ -139,6 +195,12 @@ impl Processor for TransformHashJoinProbe {
                 }
 
                 if self.input_port.is_finished() {
+                    self.probe_detach()?;
+                    if self.join_state.is_spilled() {
+                        self.step = HashJoinStep::WaitProbe;
+                        return Ok(Event::Async);
+                    }
+
                     self.output_port.finish();
                     return Ok(Event::Finished);
                 }
@@ -151,10 +213,22 @@ impl Processor for TransformHashJoinProbe {
 
     fn process(&mut self) -> Result<()> {
         match self.step {
-            HashJoinStep::Build => Ok(()),
+            HashJoinStep::Build | HashJoinStep::WaitProbe | HashJoinStep::Finished => Ok(()),
             HashJoinStep::Probe => {
                 if let Some(data) = self.input_data.take() {
                     self.probe(&data)?;
+                    self.probe_spilled = self.join_state.is_spilled();
+                }
+                Ok(())
+            }
+            HashJoinStep::JoinSpilled => {
+                let blocks = self
+                    .join_state
+                    .join_spilled_partitions(&mut self.spilled_tasks, &mut self.probe_state)?;
+
+                match blocks {
+                    Some(blocks) => self.output_data_blocks.extend(blocks),
+                    None => self.step = HashJoinStep::Finished,
                 }
                 Ok(())
             }
@@ -162,11 +236,31 @@ impl Processor for TransformHashJoinProbe {
     }
 
     async fn async_process(&mut self) -> Result<()> {
-        if let HashJoinStep::Build = &self.step {
-            self.join_state.wait_finish().await?;
-            self.step = HashJoinStep::Probe;
+        match &self.step {
+            HashJoinStep::Build => {
+                self.join_state.wait_finish().await?;
+                self.step = HashJoinStep::Probe;
+            }
+            HashJoinStep::Probe => {
+                self.join_state.flush_spill().await?;
+                self.probe_spilled = false;
+            }
+            HashJoinStep::WaitProbe => {
+                self.join_state.wait_probe_finish().await?;
+                self.join_state.flush_spill().await?;
+                self.step = HashJoinStep::JoinSpilled;
+            }
+            HashJoinStep::JoinSpilled => self.spilled_tasks.read().await?,
+            HashJoinStep::Finished => {}
         }
 
         Ok(())
     }
 }
+
+impl Drop for TransformHashJoinProbe {
+    fn drop(&mut self) {
+        // Don't block the other probe processors if the processor is finished early.
+        let _ = self.probe_detach();
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_channel::Receiver;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+
+/// Pass through the blocks of the left input of `UNION ALL`, then the blocks of
+/// the right input received from `UnionReceiveSink`.
+pub struct TransformMergeBlock {
+    finished: bool,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+
+    receiver: Receiver<DataBlock>,
+    receiver_result: Option<DataBlock>,
+}
+
+impl TransformMergeBlock {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        receiver: Receiver<DataBlock>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformMergeBlock {
+            finished: false,
+            input,
+            output,
+            receiver,
+            receiver_result: None,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformMergeBlock {
+    fn name(&self) -> &'static str {
+        "TransformMergeBlock"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            // Unblock the senders of the right input, which is no longer needed.
+            self.receiver.close();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.receiver_result.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if !self.input.is_finished() {
+            if !self.input.has_data() {
+                self.input.set_need_data();
+                return Ok(Event::NeedData);
+            }
+
+            self.output.push_data(self.input.pull_data().unwrap());
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.finished {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        Ok(Event::Async)
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        match self.receiver.recv().await {
+            Ok(data_block) => self.receiver_result = Some(data_block),
+            // All the senders are dropped, the right input is finished.
+            Err(_) => self.finished = true,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,275 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_base::base::tokio::sync::Notify;
+use common_datablocks::DataBlock;
+use common_datavalues::Column;
+use common_datavalues::ConstColumn;
+use common_exception::Result;
+
+use crate::evaluator::EvalNode;
+use crate::evaluator::Evaluator;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::HashJoinDesc;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::processors::Sink;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::executor::ColumnID;
+use crate::sql::executor::NestedLoopJoin;
+
+/// The shared state of a nested-loop join, which buffers the whole right input
+/// and evaluates the join conditions against every pair of rows.
+pub struct NestedLoopJoinState {
+    ctx: Arc<QueryContext>,
+    predicate: Option<EvalNode<ColumnID>>,
+    build_blocks: Mutex<Vec<DataBlock>>,
+    /// The concatenated right input, available once all the build sinks are finished
+    build_block: Mutex<Option<DataBlock>>,
+    ref_count: Mutex<usize>,
+    is_finished: Mutex<bool>,
+    finished_notify: Notify,
+}
+
+impl NestedLoopJoinState {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        join: &NestedLoopJoin,
+    ) -> Result<Arc<NestedLoopJoinState>> {
+        let predicate = HashJoinDesc::join_predicate(&join.conditions)?
+            .as_ref()
+            .map(Evaluator::eval_physical_scalar)
+            .transpose()?;
+
+        Ok(Arc::new(NestedLoopJoinState {
+            ctx,
+            predicate,
+            build_blocks: Mutex::new(vec![]),
+            build_block: Mutex::new(None),
+            ref_count: Mutex::new(0),
+            is_finished: Mutex::new(false),
+            finished_notify: Notify::new(),
+        }))
+    }
+
+    fn attach(&self) {
+        *self.ref_count.lock().unwrap() += 1;
+    }
+
+    fn detach(&self) -> Result<()> {
+        let mut count = self.ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            let blocks = std::mem::take(&mut *self.build_blocks.lock().unwrap());
+            let blocks = blocks
+                .into_iter()
+                .filter(|block| !block.is_empty())
+                .collect::<Vec<_>>();
+            if !blocks.is_empty() {
+                *self.build_block.lock().unwrap() = Some(DataBlock::concat_blocks(&blocks)?);
+            }
+
+            *self.is_finished.lock().unwrap() = true;
+            self.finished_notify.notify_waiters();
+        }
+        Ok(())
+    }
+
+    async fn wait_finish(&self) {
+        let notified = self.finished_notify.notified();
+        let is_finished = *self.is_finished.lock().unwrap();
+        if !is_finished {
+            notified.await;
+        }
+    }
+
+    fn build(&self, data_block: DataBlock) -> Result<()> {
+        self.build_blocks.lock().unwrap().push(data_block);
+        Ok(())
+    }
+
+    fn probe(&self, data_block: &DataBlock) -> Result<Vec<DataBlock>> {
+        let build_block = self.build_block.lock().unwrap();
+        let build_block = match build_block.as_ref() {
+            Some(build_block) => build_block,
+            None => return Ok(vec![]),
+        };
+
+        let func_ctx = self.ctx.try_get_function_context()?;
+        let mut results = Vec::with_capacity(data_block.num_rows());
+        for row in 0..data_block.num_rows() {
+            // Pair the current row of the left input with every row of the right input.
+            let mut merged_block = DataBlock::empty();
+            for (column, field) in data_block
+                .columns()
+                .iter()
+                .zip(data_block.schema().fields().iter())
+            {
+                let value = column.slice(row, 1);
+                merged_block = merged_block.add_column(
+                    ConstColumn::new(value, build_block.num_rows()).arc(),
+                    field.clone(),
+                )?;
+            }
+            for (column, field) in build_block
+                .columns()
+                .iter()
+                .zip(build_block.schema().fields().iter())
+            {
+                merged_block = merged_block.add_column(column.clone(), field.clone())?;
+            }
+
+            let merged_block = match &self.predicate {
+                Some(predicate) => {
+                    let filter_vector = predicate.eval(&func_ctx, &merged_block)?;
+                    DataBlock::filter_block(merged_block, filter_vector.vector())?
+                }
+                None => merged_block,
+            };
+            if !merged_block.is_empty() {
+                // Materialize the constant columns of the left row.
+                let columns = merged_block
+                    .columns()
+                    .iter()
+                    .map(|column| column.convert_full_column())
+                    .collect::<Vec<_>>();
+                results.push(DataBlock::create(merged_block.schema().clone(), columns));
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+pub struct SinkNestedLoopJoinBuild {
+    state: Arc<NestedLoopJoinState>,
+}
+
+impl SinkNestedLoopJoinBuild {
+    pub fn create(state: Arc<NestedLoopJoinState>) -> Self {
+        state.attach();
+        Self { state }
+    }
+}
+
+impl Sink for SinkNestedLoopJoinBuild {
+    const NAME: &'static str = "NestedLoopJoinBuild";
+
+    fn on_finish(&mut self) -> Result<()> {
+        self.state.detach()
+    }
+
+    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        self.state.build(data_block)
+    }
+}
+
+pub struct TransformNestedLoopJoin {
+    build_finished: bool,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    state: Arc<NestedLoopJoinState>,
+
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+}
+
+impl TransformNestedLoopJoin {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        state: Arc<NestedLoopJoinState>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformNestedLoopJoin {
+            build_finished: false,
+            input,
+            output,
+            state,
+            input_data: None,
+            output_data: None,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformNestedLoopJoin {
+    fn name(&self) -> &'static str {
+        "TransformNestedLoopJoin"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if !self.build_finished {
+            return Ok(Event::Async);
+        }
+
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.has_data() {
+            self.input_data = Some(self.input.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data.take() {
+            let results = self.state.probe(&data_block)?;
+            if !results.is_empty() {
+                self.output_data = Some(DataBlock::concat_blocks(&results)?);
+            }
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        self.state.wait_finish().await;
+        self.build_finished = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,249 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_base::base::tokio::sync::Notify;
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::BooleanColumn;
+use common_datavalues::ColumnRef;
+use common_exception::Result;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::processors::Sink;
+use crate::sql::plans::SetOperationType;
+
+/// The shared state of `INTERSECT ALL` and `EXCEPT ALL`, which counts the
+/// occurrences of every row of the right input.
+pub struct SetOperationState {
+    op_type: SetOperationType,
+    /// Positions of the compared columns in the blocks of the left input
+    probe_keys: Vec<usize>,
+    counts: Mutex<HashMap<Vec<u8>, usize>>,
+    ref_count: Mutex<usize>,
+    is_finished: Mutex<bool>,
+    finished_notify: Notify,
+}
+
+impl SetOperationState {
+    pub fn create(op_type: SetOperationType, probe_keys: Vec<usize>) -> Arc<SetOperationState> {
+        Arc::new(SetOperationState {
+            op_type,
+            probe_keys,
+            counts: Mutex::new(HashMap::new()),
+            ref_count: Mutex::new(0),
+            is_finished: Mutex::new(false),
+            finished_notify: Notify::new(),
+        })
+    }
+
+    fn attach(&self) {
+        *self.ref_count.lock().unwrap() += 1;
+    }
+
+    fn detach(&self) {
+        let mut count = self.ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            *self.is_finished.lock().unwrap() = true;
+            self.finished_notify.notify_waiters();
+        }
+    }
+
+    async fn wait_finish(&self) {
+        let notified = self.finished_notify.notified();
+        let is_finished = *self.is_finished.lock().unwrap();
+        if !is_finished {
+            notified.await;
+        }
+    }
+
+    fn build(&self, data_block: &DataBlock) -> Result<()> {
+        let columns: Vec<&ColumnRef> = data_block.columns().iter().collect();
+        let keys = serialize_keys(&columns, data_block.num_rows())?;
+        let mut counts = self.counts.lock().unwrap();
+        for key in keys {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+        Ok(())
+    }
+
+    fn probe(&self, data_block: DataBlock) -> Result<DataBlock> {
+        let columns = self
+            .probe_keys
+            .iter()
+            .map(|index| data_block.column(*index))
+            .collect::<Vec<_>>();
+        let keys = serialize_keys(&columns, data_block.num_rows())?;
+        let mut counts = self.counts.lock().unwrap();
+        let mut picked = Vec::with_capacity(keys.len());
+        for key in keys {
+            // Every row of the right input matches at most one row of the left input.
+            let matched = match counts.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    true
+                }
+                _ => false,
+            };
+
+            picked.push(match self.op_type {
+                SetOperationType::IntersectAll => matched,
+                SetOperationType::ExceptAll => !matched,
+            });
+        }
+
+        DataBlock::filter_block_with_bool_column(
+            data_block,
+            &BooleanColumn::from_arrow_data(Bitmap::from_iter(picked)),
+        )
+    }
+}
+
+// NULLs are serialized as equal values, as the set operations require.
+fn serialize_keys(columns: &[&ColumnRef], rows: usize) -> Result<Vec<Vec<u8>>> {
+    let hash_method = HashMethodSerializer::default();
+    let keys_state = hash_method.build_keys_state(columns, rows)?;
+    let keys = hash_method
+        .build_keys_iter(&keys_state)?
+        .map(|key| key.to_vec())
+        .collect();
+    Ok(keys)
+}
+
+pub struct SinkSetOperationBuild {
+    state: Arc<SetOperationState>,
+}
+
+impl SinkSetOperationBuild {
+    pub fn create(state: Arc<SetOperationState>) -> Self {
+        state.attach();
+        Self { state }
+    }
+}
+
+impl Sink for SinkSetOperationBuild {
+    const NAME: &'static str = "SetOperationBuild";
+
+    fn on_finish(&mut self) -> Result<()> {
+        self.state.detach();
+        Ok(())
+    }
+
+    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        self.state.build(&data_block)
+    }
+}
+
+pub struct TransformSetOperation {
+    build_finished: bool,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    state: Arc<SetOperationState>,
+
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+}
+
+impl TransformSetOperation {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        state: Arc<SetOperationState>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformSetOperation {
+            build_finished: false,
+            input,
+            output,
+            state,
+            input_data: None,
+            output_data: None,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformSetOperation {
+    fn name(&self) -> &'static str {
+        "TransformSetOperation"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if !self.build_finished {
+            return Ok(Event::Async);
+        }
+
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.has_data() {
+            self.input_data = Some(self.input.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data.take() {
+            let data_block = self.state.probe(data_block)?;
+            if !data_block.is_empty() {
+                self.output_data = Some(data_block);
+            }
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        self.state.wait_finish().await;
+        self.build_finished = true;
+        Ok(())
+    }
+}
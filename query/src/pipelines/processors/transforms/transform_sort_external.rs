@@ -0,0 +1,598 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::LoserTree;
+use super::Spiller;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+type SortKeyComparator = Box<dyn Fn(&Vec<DataValue>, &Vec<DataValue>) -> Ordering + Send>;
+
+/// Merge the sorted blocks into sorted blocks, it's the same as `TransformSortMerge` if all
+/// the blocks can be kept in memory.
+///
+/// Once the buffered blocks exceed `max_bytes_before_external_sort`, they are merged into
+/// a sorted run and spilled. When the input is finished, the spilled runs and the blocks left
+/// in memory are merged by a loser tree, block by block, so only the current block of each run
+/// is kept in memory. The spilled files are written and read in the async step.
+pub struct TransformSortExternal {
+    ctx: Arc<QueryContext>,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    state: SortState,
+
+    limit: Option<usize>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    max_block_size: usize,
+    max_bytes_before_external_sort: usize,
+
+    buffered_blocks: Vec<DataBlock>,
+    buffered_bytes: usize,
+    output_blocks: VecDeque<DataBlock>,
+    spiller: Option<Spiller>,
+    spilled_runs: usize,
+    merger: Option<SortedRunsMerger>,
+}
+
+enum SortState {
+    Consume,
+    Spill,
+    Finalize,
+    Merge,
+    Finished,
+}
+
+impl TransformSortExternal {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        limit: Option<usize>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+    ) -> Result<ProcessorPtr> {
+        let settings = ctx.get_settings();
+        let max_block_size = settings.get_max_block_size()? as usize;
+        let max_bytes_before_external_sort =
+            settings.get_max_bytes_before_external_sort()? as usize;
+
+        Ok(ProcessorPtr::create(Box::new(TransformSortExternal {
+            ctx,
+            input,
+            output,
+            state: SortState::Consume,
+            limit,
+            sort_columns_descriptions,
+            max_block_size: max_block_size.max(1),
+            max_bytes_before_external_sort,
+            buffered_blocks: vec![],
+            buffered_bytes: 0,
+            output_blocks: VecDeque::new(),
+            spiller: None,
+            spilled_runs: 0,
+            merger: None,
+        })))
+    }
+
+    fn need_spill(&self) -> bool {
+        self.max_bytes_before_external_sort > 0
+            && self.buffered_bytes > self.max_bytes_before_external_sort
+    }
+
+    /// Merge the buffered blocks into blocks of a sorted run.
+    fn sort_buffered_blocks(&mut self) -> Result<Vec<DataBlock>> {
+        let blocks = std::mem::take(&mut self.buffered_blocks);
+        self.buffered_bytes = 0;
+
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let block =
+            DataBlock::merge_sort_blocks(&blocks, &self.sort_columns_descriptions, self.limit)?;
+        DataBlock::split_block_by_size(&block, self.max_block_size)
+    }
+
+    /// Spill the buffered blocks as a sorted run, each block of the run is spilled
+    /// into a file, so the run can be read back block by block.
+    fn spill_run(&mut self) -> Result<()> {
+        let blocks = self.sort_buffered_blocks()?;
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        if self.spiller.is_none() {
+            self.spiller = Some(Spiller::create(self.ctx.clone(), "sort", 0)?);
+        }
+
+        let run = self.spilled_runs;
+        if let Some(spiller) = self.spiller.as_mut() {
+            for block in blocks {
+                spiller.spill(run, vec![block])?;
+            }
+        }
+
+        self.spilled_runs += 1;
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> Result<()> {
+        let spiller = match self.spiller.take() {
+            Some(spiller) => spiller,
+            None => {
+                if !self.buffered_blocks.is_empty() {
+                    let block = DataBlock::merge_sort_blocks(
+                        &self.buffered_blocks,
+                        &self.sort_columns_descriptions,
+                        self.limit,
+                    )?;
+                    self.buffered_blocks.clear();
+                    self.buffered_bytes = 0;
+                    self.output_blocks.push_back(block);
+                }
+
+                self.state = SortState::Finished;
+                return Ok(());
+            }
+        };
+
+        // The blocks left in memory are merged as the last run without spilling.
+        let mut runs = (0..self.spilled_runs)
+            .map(SortedRun::spilled)
+            .collect::<Vec<_>>();
+        runs.push(SortedRun::in_memory(self.sort_buffered_blocks()?));
+
+        self.merger = Some(SortedRunsMerger::create(
+            spiller,
+            runs,
+            self.sort_columns_descriptions.clone(),
+            self.limit,
+            self.max_block_size,
+        ));
+        self.state = SortState::Merge;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformSortExternal {
+    fn name(&self) -> &'static str {
+        "SortExternalTransform"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output_blocks.is_empty() {
+            if !self.output.can_push() {
+                return Ok(Event::NeedConsume);
+            }
+
+            let block = self.output_blocks.pop_front().unwrap();
+            self.output.push_data(Ok(block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(spiller) = &self.spiller {
+            if spiller.has_pending_writes() {
+                return Ok(Event::Async);
+            }
+        }
+
+        match self.state {
+            SortState::Spill | SortState::Finalize => Ok(Event::Sync),
+            SortState::Merge => match self.output.can_push() {
+                true if self.merger.as_ref().map_or(false, |m| m.need_load()) => Ok(Event::Async),
+                true => Ok(Event::Sync),
+                false => Ok(Event::NeedConsume),
+            },
+            SortState::Finished => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+            SortState::Consume => {
+                if self.input.is_finished() {
+                    self.state = SortState::Finalize;
+                    return Ok(Event::Sync);
+                }
+
+                if self.input.has_data() {
+                    let block = self.input.pull_data().unwrap()?;
+                    self.buffered_bytes += block.memory_size();
+                    self.buffered_blocks.push(block);
+
+                    if self.need_spill() {
+                        self.state = SortState::Spill;
+                        return Ok(Event::Sync);
+                    }
+                }
+
+                self.input.set_need_data();
+                Ok(Event::NeedData)
+            }
+        }
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.state {
+            SortState::Spill => {
+                self.spill_run()?;
+                self.state = SortState::Consume;
+                Ok(())
+            }
+            SortState::Finalize => self.finalize(),
+            SortState::Merge => {
+                let merger = self.merger.as_mut().ok_or_else(|| {
+                    ErrorCode::LogicalError("The sorted runs merger is not created, it's a bug.")
+                })?;
+
+                match merger.next_block()? {
+                    Some(block) if block.is_empty() => {}
+                    Some(block) => self.output_blocks.push_back(block),
+                    None => {
+                        self.merger = None;
+                        self.state = SortState::Finished;
+                    }
+                }
+                Ok(())
+            }
+            _ => Err(ErrorCode::LogicalError("State invalid. it's a bug.")),
+        }
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        if let Some(spiller) = self.spiller.as_mut() {
+            spiller.flush().await?;
+        }
+
+        if let Some(merger) = self.merger.as_mut() {
+            merger.load().await?;
+        }
+        Ok(())
+    }
+}
+
+struct SortedRun {
+    // The spilled partition of the run, `None` if all the blocks of the run are in memory.
+    partition: Option<usize>,
+    blocks: VecDeque<DataBlock>,
+}
+
+impl SortedRun {
+    fn spilled(partition: usize) -> SortedRun {
+        SortedRun {
+            partition: Some(partition),
+            blocks: VecDeque::new(),
+        }
+    }
+
+    fn in_memory(blocks: Vec<DataBlock>) -> SortedRun {
+        SortedRun {
+            partition: None,
+            blocks: blocks
+                .into_iter()
+                .filter(|block| !block.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Whether the next block of the run has to be read from the spilled files.
+    fn need_load(&self) -> bool {
+        self.blocks.is_empty() && self.partition.is_some()
+    }
+
+    async fn load(&mut self, spiller: &mut Spiller) -> Result<()> {
+        while let Some(partition) = self.partition {
+            if !self.blocks.is_empty() {
+                break;
+            }
+
+            match spiller.read_partition_file(partition).await? {
+                None => self.partition = None,
+                Some(blocks) => self
+                    .blocks
+                    .extend(blocks.into_iter().filter(|block| !block.is_empty())),
+            }
+        }
+        Ok(())
+    }
+
+    fn next_block(&mut self) -> Result<Option<DataBlock>> {
+        if self.need_load() {
+            return Err(ErrorCode::LogicalError(
+                "The next block of the sorted run is not loaded, it's a bug.",
+            ));
+        }
+
+        Ok(self.blocks.pop_front())
+    }
+}
+
+/// The current row of a sorted run.
+struct RunCursor {
+    // Increased when the cursor moves to the next block.
+    block_seq: usize,
+    block: DataBlock,
+    sort_columns: Vec<ColumnRef>,
+    row: usize,
+}
+
+impl RunCursor {
+    fn try_create(block: DataBlock, descriptions: &[SortColumnDescription]) -> Result<Self> {
+        let mut cursor = RunCursor {
+            block_seq: 0,
+            block: DataBlock::empty(),
+            sort_columns: vec![],
+            row: 0,
+        };
+        cursor.reset(block, descriptions)?;
+        Ok(cursor)
+    }
+
+    fn reset(&mut self, block: DataBlock, descriptions: &[SortColumnDescription]) -> Result<()> {
+        self.sort_columns = descriptions
+            .iter()
+            .map(|description| {
+                let column = block.try_column_by_name(&description.column_name)?;
+                Ok(column.convert_full_column())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.block = block;
+        self.block_seq += 1;
+        self.row = 0;
+        Ok(())
+    }
+
+    fn sort_key(&self) -> Vec<DataValue> {
+        self.sort_columns
+            .iter()
+            .map(|column| column.get(self.row))
+            .collect()
+    }
+}
+
+/// Slice of the output block, the rows `[start, start + len)` of the current block of a run.
+struct MergeSlice {
+    run: usize,
+    block_seq: usize,
+    block: DataBlock,
+    start: usize,
+    len: usize,
+}
+
+/// K-way merge the sorted runs with a loser tree.
+///
+/// The next block of a spilled run is loaded by `load` in the async step, the merging stops at
+/// the run whose current block is exhausted until its next block is loaded.
+struct SortedRunsMerger {
+    spiller: Spiller,
+    runs: Vec<SortedRun>,
+    cursors: Vec<Option<RunCursor>>,
+    // Created once the first block of each run is loaded.
+    tree: Option<LoserTree<Vec<DataValue>, SortKeyComparator>>,
+    // The run waiting for its next block to be loaded, which is the winner of the tree.
+    waiting_run: Option<usize>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    remaining_rows: Option<usize>,
+    max_block_size: usize,
+}
+
+impl SortedRunsMerger {
+    fn create(
+        spiller: Spiller,
+        runs: Vec<SortedRun>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+        limit: Option<usize>,
+        max_block_size: usize,
+    ) -> Self {
+        SortedRunsMerger {
+            spiller,
+            runs,
+            cursors: vec![],
+            tree: None,
+            waiting_run: None,
+            sort_columns_descriptions,
+            remaining_rows: limit,
+            max_block_size,
+        }
+    }
+
+    fn need_load(&self) -> bool {
+        match (&self.tree, self.waiting_run) {
+            (None, _) => self.runs.iter().any(|run| run.need_load()),
+            (Some(_), Some(run)) => self.runs[run].need_load(),
+            (Some(_), None) => false,
+        }
+    }
+
+    async fn load(&mut self) -> Result<()> {
+        for (index, run) in self.runs.iter_mut().enumerate() {
+            if self.tree.is_none() || self.waiting_run == Some(index) {
+                run.load(&mut self.spiller).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn create_tree(&mut self) -> Result<()> {
+        let mut cursors = Vec::with_capacity(self.runs.len());
+        let mut sort_keys = Vec::with_capacity(self.runs.len());
+        for run in self.runs.iter_mut() {
+            match run.next_block()? {
+                None => {
+                    cursors.push(None);
+                    sort_keys.push(None);
+                }
+                Some(block) => {
+                    let cursor = RunCursor::try_create(block, &self.sort_columns_descriptions)?;
+                    sort_keys.push(Some(cursor.sort_key()));
+                    cursors.push(Some(cursor));
+                }
+            }
+        }
+
+        let descriptions = self.sort_columns_descriptions.clone();
+        let comparator: SortKeyComparator =
+            Box::new(move |left, right| compare_sort_keys(&descriptions, left, right));
+
+        self.cursors = cursors;
+        self.tree = Some(LoserTree::create(sort_keys, comparator));
+        Ok(())
+    }
+
+    /// Move the cursor of the run to the next block and update the tree,
+    /// returns false if the next block of the run has not been loaded.
+    fn next_run_block(&mut self, run: usize) -> Result<bool> {
+        if self.runs[run].need_load() {
+            self.waiting_run = Some(run);
+            return Ok(false);
+        }
+
+        let tree = self.tree.as_mut().unwrap();
+        match self.runs[run].next_block()? {
+            None => {
+                self.cursors[run] = None;
+                tree.update(None);
+            }
+            Some(block) => {
+                let cursor = self.cursors[run].as_mut().unwrap();
+                cursor.reset(block, &self.sort_columns_descriptions)?;
+                tree.update(Some(cursor.sort_key()));
+            }
+        }
+        Ok(true)
+    }
+
+    /// Merge the next output block, `None` if all the runs are exhausted or the limit is reached.
+    /// The output block may be empty if a run is waiting for its next block to be loaded.
+    fn next_block(&mut self) -> Result<Option<DataBlock>> {
+        if self.tree.is_none() {
+            self.create_tree()?;
+        }
+
+        if let Some(run) = self.waiting_run.take() {
+            if !self.next_run_block(run)? {
+                return Ok(Some(DataBlock::empty()));
+            }
+        }
+
+        let max_rows = match self.remaining_rows {
+            None => self.max_block_size,
+            Some(remaining_rows) => remaining_rows.min(self.max_block_size),
+        };
+
+        let mut slices: Vec<MergeSlice> = vec![];
+        let mut rows = 0;
+        while rows < max_rows {
+            let run = match self.tree.as_ref().unwrap().winner() {
+                None => break,
+                Some(run) => run,
+            };
+
+            let cursor = self.cursors[run].as_mut().ok_or_else(|| {
+                ErrorCode::LogicalError("The cursor of the sorted run is exhausted, it's a bug.")
+            })?;
+
+            match slices.last_mut() {
+                Some(slice)
+                    if slice.run == run
+                        && slice.block_seq == cursor.block_seq
+                        && slice.start + slice.len == cursor.row =>
+                {
+                    slice.len += 1;
+                }
+                _ => slices.push(MergeSlice {
+                    run,
+                    block_seq: cursor.block_seq,
+                    block: cursor.block.clone(),
+                    start: cursor.row,
+                    len: 1,
+                }),
+            }
+            rows += 1;
+
+            cursor.row += 1;
+            if cursor.row < cursor.block.num_rows() {
+                let sort_key = cursor.sort_key();
+                self.tree.as_mut().unwrap().update(Some(sort_key));
+            } else if !self.next_run_block(run)? {
+                break;
+            }
+        }
+
+        if rows == 0 {
+            return match self.waiting_run {
+                None => Ok(None),
+                Some(_) => Ok(Some(DataBlock::empty())),
+            };
+        }
+
+        if let Some(remaining_rows) = self.remaining_rows.as_mut() {
+            *remaining_rows -= rows;
+        }
+
+        let blocks = slices
+            .iter()
+            .map(|slice| slice.block.slice(slice.start, slice.len))
+            .collect::<Vec<_>>();
+        Ok(Some(DataBlock::concat_blocks(&blocks)?))
+    }
+}
+
+/// Compare the sort keys of two rows, the same as the ordering of `DataBlock::sort_block`.
+fn compare_sort_keys(
+    descriptions: &[SortColumnDescription],
+    left: &[DataValue],
+    right: &[DataValue],
+) -> Ordering {
+    for ((description, left), right) in descriptions.iter().zip(left).zip(right) {
+        let ordering = match (left.is_null(), right.is_null()) {
+            (true, true) => Ordering::Equal,
+            (true, false) if description.nulls_first => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (false, true) if description.nulls_first => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) if description.asc => left.cmp(right),
+            (false, false) => right.cmp(left),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
@@ -0,0 +1,376 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_arrow::ArrayRef;
+use common_base::base::tokio::sync::Notify;
+use common_datablocks::build_compare;
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::Series;
+use common_exception::Result;
+
+use crate::evaluator::EvalNode;
+use crate::evaluator::Evaluator;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::HashJoinDesc;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::processors::Sink;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::executor::ColumnID;
+use crate::sql::executor::SortMergeJoin;
+
+/// The right input of a sort-merge join, sorted by the join keys.
+struct SortedBlock {
+    block: DataBlock,
+    keys: Vec<ArrayRef>,
+}
+
+/// The shared state of a sort-merge join.
+///
+/// Every block of the right input is sorted by the join keys when it's received, and the
+/// sorted blocks are merged once the right input is finished. Then every block of the left
+/// input is sorted and merged with the right input. The blocks already in the order of the
+/// keys, e.g. the blocks of a table clustered by the keys, are not sorted again.
+pub struct SortMergeJoinState {
+    ctx: Arc<QueryContext>,
+    left_keys: Vec<ColumnID>,
+    right_keys: Vec<ColumnID>,
+    other_predicate: Option<EvalNode<ColumnID>>,
+    build_blocks: Mutex<Vec<DataBlock>>,
+    sorted_block: Mutex<Option<Arc<SortedBlock>>>,
+    ref_count: Mutex<usize>,
+    is_finished: Mutex<bool>,
+    finished_notify: Notify,
+}
+
+impl SortMergeJoinState {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        join: &SortMergeJoin,
+    ) -> Result<Arc<SortMergeJoinState>> {
+        let other_predicate = HashJoinDesc::join_predicate(&join.other_conditions)?
+            .as_ref()
+            .map(Evaluator::eval_physical_scalar)
+            .transpose()?;
+
+        Ok(Arc::new(SortMergeJoinState {
+            ctx,
+            left_keys: join.left_keys.clone(),
+            right_keys: join.right_keys.clone(),
+            other_predicate,
+            build_blocks: Mutex::new(vec![]),
+            sorted_block: Mutex::new(None),
+            ref_count: Mutex::new(0),
+            is_finished: Mutex::new(false),
+            finished_notify: Notify::new(),
+        }))
+    }
+
+    fn attach(&self) {
+        *self.ref_count.lock().unwrap() += 1;
+    }
+
+    fn detach(&self) -> Result<()> {
+        let mut count = self.ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            let blocks = std::mem::take(&mut *self.build_blocks.lock().unwrap());
+            if !blocks.is_empty() {
+                let sort_columns = sort_columns_descriptions(&self.right_keys);
+                let block = DataBlock::merge_sort_blocks(&blocks, &sort_columns, None)?;
+                let keys = key_arrays(&block, &self.right_keys)?;
+                *self.sorted_block.lock().unwrap() = Some(Arc::new(SortedBlock { block, keys }));
+            }
+
+            *self.is_finished.lock().unwrap() = true;
+            self.finished_notify.notify_waiters();
+        }
+        Ok(())
+    }
+
+    async fn wait_finish(&self) {
+        let notified = self.finished_notify.notified();
+        let is_finished = *self.is_finished.lock().unwrap();
+        if !is_finished {
+            notified.await;
+        }
+    }
+
+    fn build(&self, data_block: DataBlock) -> Result<()> {
+        let data_block = sort_by_keys(data_block, &self.right_keys)?;
+        if !data_block.is_empty() {
+            self.build_blocks.lock().unwrap().push(data_block);
+        }
+        Ok(())
+    }
+
+    fn probe(&self, data_block: DataBlock) -> Result<DataBlock> {
+        let sorted_block = match self.sorted_block.lock().unwrap().clone() {
+            Some(sorted_block) => sorted_block,
+            None => return Ok(DataBlock::empty()),
+        };
+
+        let left_block = sort_by_keys(data_block, &self.left_keys)?;
+        if left_block.is_empty() {
+            return Ok(DataBlock::empty());
+        }
+
+        let left_keys = key_arrays(&left_block, &self.left_keys)?;
+        let right_keys = &sorted_block.keys;
+        let compare = compare_keys(&left_keys, right_keys)?;
+        let compare_left = compare_keys(&left_keys, &left_keys)?;
+        let compare_right = compare_keys(right_keys, right_keys)?;
+        let left_rows = left_block.num_rows();
+        let right_rows = sorted_block.block.num_rows();
+
+        let mut left_indices: Vec<u32> = vec![];
+        let mut right_indices: Vec<u32> = vec![];
+        let (mut i, mut j) = (0, 0);
+        while i < left_rows && j < right_rows {
+            match compare(i, j) {
+                Ordering::Less => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal => {
+                    let mut end = j + 1;
+                    while end < right_rows && compare_right(j, end) == Ordering::Equal {
+                        end += 1;
+                    }
+                    let start = i;
+                    while i < left_rows && compare_left(start, i) == Ordering::Equal {
+                        for right_row in j..end {
+                            left_indices.push(i as u32);
+                            right_indices.push(right_row as u32);
+                        }
+                        i += 1;
+                    }
+                    j = end;
+                }
+            }
+        }
+
+        if left_indices.is_empty() {
+            return Ok(DataBlock::empty());
+        }
+
+        let mut merged_block = DataBlock::block_take_by_indices(&left_block, &left_indices)?;
+        let right_block = DataBlock::block_take_by_indices(&sorted_block.block, &right_indices)?;
+        for (column, field) in right_block
+            .columns()
+            .iter()
+            .zip(right_block.schema().fields().iter())
+        {
+            merged_block = merged_block.add_column(column.clone(), field.clone())?;
+        }
+
+        match &self.other_predicate {
+            Some(other_predicate) => {
+                let func_ctx = self.ctx.try_get_function_context()?;
+                let filter_vector = other_predicate.eval(&func_ctx, &merged_block)?;
+                DataBlock::filter_block(merged_block, filter_vector.vector())
+            }
+            None => Ok(merged_block),
+        }
+    }
+}
+
+fn sort_columns_descriptions(keys: &[ColumnID]) -> Vec<SortColumnDescription> {
+    keys.iter()
+        .map(|key| SortColumnDescription {
+            column_name: key.clone(),
+            asc: true,
+            nulls_first: false,
+        })
+        .collect()
+}
+
+fn key_arrays(block: &DataBlock, keys: &[ColumnID]) -> Result<Vec<ArrayRef>> {
+    keys.iter()
+        .map(|key| {
+            let column = block.try_column_by_name(key)?;
+            column.try_as_arrow_array(column.data_type())
+        })
+        .collect()
+}
+
+/// Compare the keys of a row of the left arrays with the keys of a row of the right arrays.
+fn compare_keys(
+    left: &[ArrayRef],
+    right: &[ArrayRef],
+) -> Result<impl Fn(usize, usize) -> Ordering> {
+    let comparators = left
+        .iter()
+        .zip(right.iter())
+        .map(|(left, right)| Ok(build_compare(left.as_ref(), right.as_ref())?))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(move |i, j| {
+        comparators
+            .iter()
+            .map(|comparator| comparator(i, j))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    })
+}
+
+/// Discard the rows with NULL keys, which never match, and sort the other rows by the keys.
+/// The block is kept as it is if the rows are already in order, which is checked in linear time.
+fn sort_by_keys(block: DataBlock, keys: &[ColumnID]) -> Result<DataBlock> {
+    let columns = keys
+        .iter()
+        .map(|key| block.try_column_by_name(key).cloned())
+        .collect::<Result<Vec<_>>>()?;
+    let block = if columns.iter().any(|column| column.is_nullable()) {
+        let valid = (0..block.num_rows())
+            .map(|row| columns.iter().all(|column| !column.null_at(row)))
+            .collect::<Vec<_>>();
+        DataBlock::filter_block(block, &Series::from_data(valid))?
+    } else {
+        block
+    };
+
+    if block.num_rows() <= 1 {
+        return Ok(block);
+    }
+
+    let arrays = key_arrays(&block, keys)?;
+    let compare = compare_keys(&arrays, &arrays)?;
+    if (1..block.num_rows()).all(|row| compare(row - 1, row) != Ordering::Greater) {
+        return Ok(block);
+    }
+    DataBlock::sort_block(&block, &sort_columns_descriptions(keys), None)
+}
+
+pub struct SinkSortMergeJoinBuild {
+    state: Arc<SortMergeJoinState>,
+}
+
+impl SinkSortMergeJoinBuild {
+    pub fn create(state: Arc<SortMergeJoinState>) -> Self {
+        state.attach();
+        Self { state }
+    }
+}
+
+impl Sink for SinkSortMergeJoinBuild {
+    const NAME: &'static str = "SortMergeJoinBuild";
+
+    fn on_finish(&mut self) -> Result<()> {
+        self.state.detach()
+    }
+
+    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        self.state.build(data_block)
+    }
+}
+
+pub struct TransformSortMergeJoin {
+    build_finished: bool,
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    state: Arc<SortMergeJoinState>,
+
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+}
+
+impl TransformSortMergeJoin {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        state: Arc<SortMergeJoinState>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformSortMergeJoin {
+            build_finished: false,
+            input,
+            output,
+            state,
+            input_data: None,
+            output_data: None,
+        })))
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformSortMergeJoin {
+    fn name(&self) -> &'static str {
+        "TransformSortMergeJoin"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if !self.build_finished {
+            return Ok(Event::Async);
+        }
+
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.take() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.has_data() {
+            self.input_data = Some(self.input.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data.take() {
+            let data_block = self.state.probe(data_block)?;
+            if !data_block.is_empty() {
+                self.output_data = Some(data_block);
+            }
+        }
+        Ok(())
+    }
+
+    async fn async_process(&mut self) -> Result<()> {
+        self.state.wait_finish().await;
+        self.build_finished = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,462 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::Arc;
+
+use bumpalo::Bump;
+use common_arrow::arrow::compute::partition::lexicographical_partition_ranges;
+use common_arrow::arrow::compute::sort::SortColumn;
+use common_arrow::ArrayRef;
+use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
+use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::MutableColumn;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionFactory;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+use common_functions::window::WindowFrame;
+use common_functions::window::WindowFrameBound;
+use common_functions::window::WindowFrameUnits;
+use segment_tree::ops::Identity;
+use segment_tree::SegmentPoint;
+
+use super::transform_window_func::Agg;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::Processor;
+use crate::sql::executor::ColumnID;
+use crate::sql::executor::WindowFunctionDesc;
+
+/// Evaluate a window function over the rows sorted by the partition keys and order keys.
+pub struct WindowEvaluator {
+    func: WindowFunctionDesc,
+    aggregate_function: Option<AggregateFunctionRef>,
+    partition_by: Vec<ColumnID>,
+    order_by: Vec<ColumnID>,
+    window_frame: WindowFrame,
+}
+
+/// Evaluate a window function partition by partition, the result is appended to the blocks
+/// as a new column.
+///
+/// The input is sorted by the partition keys and order keys, only the rows of the last
+/// partition, which may continue in the next block, are buffered.
+pub struct TransformWindow {
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    evaluator: WindowEvaluator,
+
+    input_data: Option<DataBlock>,
+    output_data: VecDeque<DataBlock>,
+    buffered_blocks: Vec<DataBlock>,
+}
+
+impl TransformWindow {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        evaluator: WindowEvaluator,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(TransformWindow {
+            input,
+            output,
+            evaluator,
+            input_data: None,
+            output_data: VecDeque::new(),
+            buffered_blocks: vec![],
+        })))
+    }
+
+    fn consume(&mut self, block: DataBlock) -> Result<()> {
+        if block.is_empty() {
+            return Ok(());
+        }
+
+        // Without partition keys, the whole input is one partition.
+        if self.evaluator.partition_by.is_empty() {
+            self.buffered_blocks.push(block);
+            return Ok(());
+        }
+
+        if let Some(last) = self.buffered_blocks.last() {
+            let last_key = self.evaluator.partition_key(last, last.num_rows() - 1)?;
+            if last_key != self.evaluator.partition_key(&block, 0)? {
+                self.flush()?;
+            }
+        }
+
+        let num_rows = block.num_rows();
+        let partition_by = self
+            .evaluator
+            .collect_columns(&block, &self.evaluator.partition_by)?;
+        let last_start = WindowEvaluator::partition_ranges(&partition_by, num_rows)?
+            .last()
+            .map_or(0, |range| range.start);
+
+        if last_start > 0 {
+            self.buffered_blocks.push(block.slice(0, last_start));
+            self.flush()?;
+            self.buffered_blocks
+                .push(block.slice(last_start, num_rows - last_start));
+        } else {
+            self.buffered_blocks.push(block);
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let blocks = std::mem::take(&mut self.buffered_blocks);
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let block = DataBlock::concat_blocks(&blocks)?;
+        let column = self.evaluator.evaluate(&block)?;
+        let field = DataField::new(
+            self.evaluator.func.column_id.as_str(),
+            self.evaluator.func.sig.return_type.clone(),
+        );
+        self.output_data.push_back(block.add_column(column, field)?);
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformWindow {
+    fn name(&self) -> &'static str {
+        "WindowTransform"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(block) = self.output_data.pop_front() {
+            self.output.push_data(Ok(block));
+            return Ok(Event::NeedConsume);
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if self.input.is_finished() {
+            if !self.buffered_blocks.is_empty() {
+                return Ok(Event::Sync);
+            }
+
+            self.output.finish();
+            return Ok(Event::Finished);
+        }
+
+        if self.input.has_data() {
+            self.input_data = Some(self.input.pull_data().unwrap()?);
+            return Ok(Event::Sync);
+        }
+
+        self.input.set_need_data();
+        Ok(Event::NeedData)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match self.input_data.take() {
+            Some(block) => self.consume(block),
+            // The input is finished, evaluate the last partition.
+            None => self.flush(),
+        }
+    }
+}
+
+impl WindowEvaluator {
+    pub fn try_create(
+        func: WindowFunctionDesc,
+        partition_by: Vec<ColumnID>,
+        order_by: Vec<ColumnID>,
+        window_frame: WindowFrame,
+    ) -> Result<Self> {
+        let factory = AggregateFunctionFactory::instance();
+        let aggregate_function = if factory.check(&func.sig.name) {
+            let arg_fields = func
+                .args
+                .iter()
+                .zip(func.sig.args.iter())
+                .map(|(name, data_type)| DataField::new(name.as_str(), data_type.clone()))
+                .collect();
+            Some(factory.get(&func.sig.name, func.sig.params.clone(), arg_fields)?)
+        } else {
+            None
+        };
+
+        Ok(WindowEvaluator {
+            func,
+            aggregate_function,
+            partition_by,
+            order_by,
+            window_frame,
+        })
+    }
+
+    fn evaluate(&self, block: &DataBlock) -> Result<ColumnRef> {
+        let num_rows = block.num_rows();
+        let partition_by = self.collect_columns(block, &self.partition_by)?;
+        let mut peer_keys = partition_by.clone();
+        peer_keys.extend(self.collect_columns(block, &self.order_by)?);
+
+        // Rows are sorted by (partition keys, order keys), so both partitions and
+        // peer groups (rows with the same order keys) are consecutive ranges.
+        let partitions = Self::partition_ranges(&partition_by, num_rows)?;
+        let peers = Self::partition_ranges(&peer_keys, num_rows)?;
+
+        let args = self
+            .func
+            .args
+            .iter()
+            .map(|name| Ok(block.try_column_by_name(name)?.clone()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let return_type = &self.func.sig.return_type;
+        let mut builder = return_type.create_mutable(num_rows);
+        match &self.aggregate_function {
+            Some(function) => {
+                self.evaluate_aggregate(function, &args, &partitions, &peers, &mut builder)?
+            }
+            None => self.evaluate_builtin(&args, &partitions, &peers, return_type, &mut builder)?,
+        }
+
+        Ok(builder.to_column())
+    }
+
+    fn evaluate_builtin(
+        &self,
+        args: &[ColumnRef],
+        partitions: &[Range<usize>],
+        peers: &[Range<usize>],
+        return_type: &DataTypeImpl,
+        builder: &mut Box<dyn MutableColumn>,
+    ) -> Result<()> {
+        let name = self.func.sig.name.as_str();
+        let mut peer_index = 0;
+        for partition in partitions {
+            let mut dense_rank = 0;
+            for i in partition.clone() {
+                if i >= peers[peer_index].end {
+                    peer_index += 1;
+                }
+                let peer = &peers[peer_index];
+                if i == peer.start {
+                    dense_rank += 1;
+                }
+
+                let value = match name {
+                    "row_number" => DataValue::UInt64((i - partition.start + 1) as u64),
+                    "rank" => DataValue::UInt64((peer.start - partition.start + 1) as u64),
+                    "dense_rank" => DataValue::UInt64(dense_rank),
+                    "lag" | "lead" => {
+                        let offset = self.func.sig.params[0].as_u64()? as usize;
+                        let target = if name == "lag" {
+                            i.checked_sub(offset)
+                                .filter(|target| *target >= partition.start)
+                        } else {
+                            Some(i + offset).filter(|target| *target < partition.end)
+                        };
+                        match (target, args.get(1)) {
+                            (Some(target), _) => args[0].get(target),
+                            (None, Some(default)) => default.get(i),
+                            (None, None) => DataValue::Null,
+                        }
+                    }
+                    "first_value" | "last_value" => {
+                        let frame = self.frame_range(i, partition, peer);
+                        if frame.is_empty() {
+                            DataValue::Null
+                        } else if name == "first_value" {
+                            args[0].get(frame.start)
+                        } else {
+                            args[0].get(frame.end - 1)
+                        }
+                    }
+                    _ => {
+                        return Err(ErrorCode::UnImplement(format!(
+                            "Unsupported window function: {}",
+                            name
+                        )));
+                    }
+                };
+
+                if value.is_null() && return_type.is_nullable() {
+                    builder.append_default();
+                } else {
+                    builder.append_data_value(value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate_aggregate(
+        &self,
+        function: &AggregateFunctionRef,
+        args: &[ColumnRef],
+        partitions: &[Range<usize>],
+        peers: &[Range<usize>],
+        builder: &mut Box<dyn MutableColumn>,
+    ) -> Result<()> {
+        let num_rows = partitions.last().map(|range| range.end).unwrap_or(0);
+        let arena = Arc::new(Bump::with_capacity(
+            2 * num_rows * function.state_layout().size(),
+        ));
+
+        // Build a segment tree over the states of each row, so that the aggregation
+        // of any frame can be computed in O(log n).
+        let states = (0..num_rows)
+            .map(|i| {
+                let row = args
+                    .iter()
+                    .map(|column| column.slice(i, 1))
+                    .collect::<Vec<_>>();
+                let place: StateAddr = arena.alloc_layout(function.state_layout()).into();
+                function.init_state(place);
+                function.accumulate(place, &row, None, 1)?;
+                Ok(place)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let agg = Agg::create(function.clone(), arena.clone());
+        let empty_state = agg.identity();
+        let segment_tree = SegmentPoint::build(states, agg);
+
+        let mut peer_index = 0;
+        let mut result = Ok(());
+        for partition in partitions {
+            for i in partition.clone() {
+                if i >= peers[peer_index].end {
+                    peer_index += 1;
+                }
+                let peer = &peers[peer_index];
+
+                let frame = self.frame_range(i, partition, peer);
+                let merged = if frame.is_empty() {
+                    function.merge_result(empty_state, builder.as_mut())
+                } else {
+                    let state = segment_tree.query(frame.start, frame.end);
+                    function.merge_result(state, builder.as_mut())
+                };
+                if merged.is_err() {
+                    result = merged;
+                    break;
+                }
+            }
+        }
+
+        // Drop the aggregate states
+        if function.need_manual_drop_state() {
+            for state in segment_tree.view() {
+                unsafe { function.drop_state(*state) }
+            }
+            unsafe { function.drop_state(empty_state) }
+        }
+
+        result
+    }
+
+    /// Compute the frame of the `i`-th row, `peer` is the peer group of the row.
+    fn frame_range(&self, i: usize, partition: &Range<usize>, peer: &Range<usize>) -> Range<usize> {
+        let WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        } = self.window_frame;
+
+        let start = match (units, start_bound) {
+            (_, WindowFrameBound::Preceding(None)) => partition.start,
+            (_, WindowFrameBound::Following(None)) => partition.end,
+            (WindowFrameUnits::Rows, WindowFrameBound::Preceding(Some(n))) => {
+                std::cmp::max(partition.start, i.saturating_sub(n as usize))
+            }
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => i,
+            (WindowFrameUnits::Rows, WindowFrameBound::Following(Some(n))) => {
+                std::cmp::min(partition.end, i + n as usize)
+            }
+            // RANGE frames with offsets are rejected by the planner.
+            (WindowFrameUnits::Range, _) => peer.start,
+        };
+
+        let end = match (units, end_bound) {
+            (_, WindowFrameBound::Preceding(None)) => partition.start,
+            (_, WindowFrameBound::Following(None)) => partition.end,
+            (WindowFrameUnits::Rows, WindowFrameBound::Preceding(Some(n))) => {
+                std::cmp::max(partition.start, (i + 1).saturating_sub(n as usize))
+            }
+            (WindowFrameUnits::Rows, WindowFrameBound::CurrentRow) => i + 1,
+            (WindowFrameUnits::Rows, WindowFrameBound::Following(Some(n))) => {
+                std::cmp::min(partition.end, i + 1 + n as usize)
+            }
+            (WindowFrameUnits::Range, _) => peer.end,
+        };
+
+        start..std::cmp::max(start, end)
+    }
+
+    fn partition_key(&self, block: &DataBlock, row: usize) -> Result<Vec<DataValue>> {
+        self.partition_by
+            .iter()
+            .map(|name| Ok(block.try_column_by_name(name)?.get(row)))
+            .collect()
+    }
+
+    fn collect_columns(&self, block: &DataBlock, names: &[ColumnID]) -> Result<Vec<ArrayRef>> {
+        names
+            .iter()
+            .map(|name| {
+                let column = block.try_column_by_name(name)?;
+                column.try_as_arrow_array(column.data_type())
+            })
+            .collect()
+    }
+
+    fn partition_ranges(keys: &[ArrayRef], num_rows: usize) -> Result<Vec<Range<usize>>> {
+        if keys.is_empty() {
+            return Ok(vec![0..num_rows]);
+        }
+
+        let sort_columns = keys
+            .iter()
+            .map(|array| SortColumn {
+                values: array.as_ref(),
+                options: None,
+            })
+            .collect::<Vec<_>>();
+        Ok(lexicographical_partition_ranges(&sort_columns)?.collect())
+    }
+}
@@ -162,7 +162,7 @@ impl WindowFuncCompact {
             window_frame.units,
             window_frame.start_bound,
             window_frame.end_bound,
-        );
+        )?;
 
         // function calculate
         let mut arguments: Vec<ColumnWithField> = Vec::with_capacity(args.len());
@@ -227,14 +227,8 @@ impl WindowFuncCompact {
         frame_units: WindowFrameUnits,
         start: WindowFrameBound,
         end: WindowFrameBound,
-    ) -> Vec<Range<usize>> {
-        let partition_by_arrow_array = partition_by
-            .iter()
-            .map(|expr| {
-                let c = block.try_column_by_name(&expr.column_name()).unwrap();
-                c.as_arrow_array(c.data_type())
-            })
-            .collect::<Vec<ArrayRef>>();
+    ) -> common_exception::Result<Vec<Range<usize>>> {
+        let partition_by_arrow_array = Self::arrow_arrays(block, partition_by)?;
         let partition_by_sort_column = partition_by_arrow_array
             .iter()
             .map(|array| SortColumn {
@@ -253,7 +247,7 @@ impl WindowFuncCompact {
         let mut partition_boundaries = partition_boundaries.into_iter();
         let mut partition = partition_boundaries.next().unwrap();
 
-        match (frame_units, start, end) {
+        let frame_bounds = match (frame_units, start, end) {
             (_, WindowFrameBound::Preceding(None), WindowFrameBound::Following(None)) => (0..block
                 .num_rows())
                 .map(|i| {
@@ -307,13 +301,7 @@ impl WindowFuncCompact {
             (WindowFrameUnits::Range, frame_start, frame_end) => match (frame_start, frame_end) {
                 (WindowFrameBound::Preceding(None), WindowFrameBound::CurrentRow) => {
                     let mut partition_by_sort_column = partition_by_sort_column;
-                    let order_by_arrow_array = order_by
-                        .iter()
-                        .map(|expr| {
-                            let c = block.try_column_by_name(&expr.column_name()).unwrap();
-                            c.as_arrow_array(c.data_type())
-                        })
-                        .collect::<Vec<ArrayRef>>();
+                    let order_by_arrow_array = Self::arrow_arrays(block, order_by)?;
                     let order_by_sort_column = order_by_arrow_array
                         .iter()
                         .map(|array| SortColumn {
@@ -340,13 +328,7 @@ impl WindowFuncCompact {
                 }
                 (WindowFrameBound::CurrentRow, WindowFrameBound::Following(None)) => {
                     let mut partition_by_sort_column = partition_by_sort_column;
-                    let order_by_arrow_array = order_by
-                        .iter()
-                        .map(|expr| {
-                            let c = block.try_column_by_name(&expr.column_name()).unwrap();
-                            c.as_arrow_array(c.data_type())
-                        })
-                        .collect::<Vec<ArrayRef>>();
+                    let order_by_arrow_array = Self::arrow_arrays(block, order_by)?;
                     let order_by_sort_column = order_by_arrow_array
                         .iter()
                         .map(|array| SortColumn {
@@ -462,7 +444,21 @@ impl WindowFuncCompact {
                         .collect::<Vec<_>>()
                 }
             },
-        }
+        };
+        Ok(frame_bounds)
+    }
+
+    fn arrow_arrays(
+        block: &DataBlock,
+        exprs: &[Expression],
+    ) -> common_exception::Result<Vec<ArrayRef>> {
+        exprs
+            .iter()
+            .map(|expr| {
+                let c = block.try_column_by_name(&expr.column_name())?;
+                c.try_as_arrow_array(c.data_type())
+            })
+            .collect()
     }
 
     /// Actually, the computation is row based
@@ -492,11 +488,17 @@ impl WindowFuncCompact {
     }
 }
 
-struct Agg {
+pub(super) struct Agg {
     func: AggregateFunctionRef,
     arena: Arc<Bump>,
 }
 
+impl Agg {
+    pub(super) fn create(func: AggregateFunctionRef, arena: Arc<Bump>) -> Self {
+        Agg { func, arena }
+    }
+}
+
 impl Operation<StateAddr> for Agg {
     fn combine(&self, a: &StateAddr, b: &StateAddr) -> StateAddr {
         let place = self.arena.alloc_layout(self.func.state_layout());
@@ -0,0 +1,202 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::ColumnRef;
+use common_exception::Result;
+use common_hashtable::HashTableKeyable;
+
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::transforms::group_by::keys_ref::KeysRef;
+use crate::pipelines::processors::Processor;
+use crate::sql::executor::ColumnID;
+
+/// Repartition the blocks by the hash of the window partition keys, so all the rows of
+/// a window partition go to the same output and the outputs can be evaluated in parallel.
+pub struct TransformWindowShuffle {
+    inputs: Vec<Arc<InputPort>>,
+    outputs: Vec<Arc<OutputPort>>,
+
+    partition_by: Vec<ColumnID>,
+    hash_method: HashMethodSerializer,
+
+    input_data: Option<DataBlock>,
+    output_data: Vec<VecDeque<DataBlock>>,
+}
+
+impl TransformWindowShuffle {
+    pub fn create(size: usize, partition_by: Vec<ColumnID>) -> Self {
+        let mut inputs = Vec::with_capacity(size);
+        let mut outputs = Vec::with_capacity(size);
+
+        for _index in 0..size {
+            inputs.push(InputPort::create());
+            outputs.push(OutputPort::create());
+        }
+
+        TransformWindowShuffle {
+            inputs,
+            outputs,
+            partition_by,
+            hash_method: HashMethodSerializer::default(),
+            input_data: None,
+            output_data: (0..size).map(|_| VecDeque::new()).collect(),
+        }
+    }
+
+    pub fn get_inputs(&self) -> &[Arc<InputPort>] {
+        &self.inputs
+    }
+
+    pub fn get_outputs(&self) -> &[Arc<OutputPort>] {
+        &self.outputs
+    }
+
+    fn finish_inputs(&mut self) {
+        for input in &self.inputs {
+            input.finish();
+        }
+    }
+
+    fn finish_outputs(&mut self) {
+        for output in &self.outputs {
+            output.finish();
+        }
+    }
+
+    fn scatter_indices(&self, block: &DataBlock) -> Result<Vec<usize>> {
+        let rows = block.num_rows();
+        let keys = self
+            .partition_by
+            .iter()
+            .map(|name| block.try_column_by_name(name))
+            .collect::<Result<Vec<&ColumnRef>>>()?;
+
+        let keys_state = self.hash_method.build_keys_state(&keys, rows)?;
+        let keys_iter = self.hash_method.build_keys_iter(&keys_state)?;
+        let size = self.outputs.len() as u64;
+        Ok(keys_iter
+            .take(rows)
+            .map(|key| {
+                let keys_ref = KeysRef::create(key.as_ptr() as usize, key.len());
+                (keys_ref.fast_hash() % size) as usize
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformWindowShuffle {
+    fn name(&self) -> &'static str {
+        "WindowShuffleTransform"
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.outputs.iter().all(|output| output.is_finished()) {
+            self.finish_inputs();
+            return Ok(Event::Finished);
+        }
+
+        let mut has_pending = false;
+        for (output, blocks) in self.outputs.iter().zip(self.output_data.iter_mut()) {
+            if output.is_finished() {
+                blocks.clear();
+                continue;
+            }
+
+            if output.can_push() {
+                if let Some(block) = blocks.pop_front() {
+                    output.push_data(Ok(block));
+                }
+            }
+
+            has_pending |= !blocks.is_empty();
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        // Stop pulling until the scattered blocks are consumed.
+        if has_pending {
+            for input in &self.inputs {
+                input.set_not_need_data();
+            }
+            return Ok(Event::NeedConsume);
+        }
+
+        let mut all_finished = true;
+        for input in &self.inputs {
+            if input.is_finished() {
+                continue;
+            }
+
+            all_finished = false;
+            input.set_need_data();
+            if self.input_data.is_none() && input.has_data() {
+                self.input_data = Some(input.pull_data().unwrap()?);
+            }
+        }
+
+        if self.input_data.is_some() {
+            return Ok(Event::Sync);
+        }
+
+        if !all_finished {
+            return Ok(Event::NeedData);
+        }
+
+        // Finish the outputs once the pushed blocks are consumed.
+        let pushed = self
+            .outputs
+            .iter()
+            .any(|output| !output.is_finished() && !output.can_push());
+        if pushed {
+            return Ok(Event::NeedConsume);
+        }
+
+        self.finish_outputs();
+        Ok(Event::Finished)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(block) = self.input_data.take() {
+            if block.is_empty() {
+                return Ok(());
+            }
+
+            let indices = self.scatter_indices(&block)?;
+            let scattered = DataBlock::scatter_block(&block, &indices, self.outputs.len())?;
+            for (index, block) in scattered.into_iter().enumerate() {
+                if !block.is_empty() {
+                    self.output_data[index].push_back(block);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -41,6 +41,7 @@ use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterFactoryV2;
 use crate::interpreters::InterpreterQueryLog;
+use crate::interpreters::SelectInterpreterV2;
 use crate::pipelines::executor::PipelineCompleteExecutor;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::Pipe;
@@ -51,6 +52,7 @@ use crate::sessions::SessionRef;
 use crate::sessions::TableContext;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PipelineBuilder;
+use crate::sql::plans::Plan;
 use crate::sql::ColumnBinding;
 use crate::sql::DfParser;
 use crate::sql::DfStatement;
@@ -198,9 +200,19 @@ impl ExecuteState {
             false
         };
 
+        // The results of the queries with the result cache are returned by the stream of the
+        // interpreter, instead of being written into the block buffer by the pipeline.
+        let mut is_cached = false;
         let interpreter = if is_v2 {
             let mut planner = Planner::new(ctx.clone());
             let (plan, _, _) = planner.plan_sql(sql).await?;
+            if let Plan::Query {
+                s_expr, metadata, ..
+            } = &plan
+            {
+                is_cached =
+                    SelectInterpreterV2::result_cache_key(&ctx, s_expr, metadata)?.is_some();
+            }
             InterpreterFactoryV2::get(ctx.clone(), &plan)
         } else {
             let plan = match PlanParser::parse(ctx.clone(), sql).await {
@@ -212,7 +224,8 @@ impl ExecuteState {
             };
             InterpreterFactory::get(ctx.clone(), plan)
         }?;
-        if is_v2 && is_select {
+
+        if is_v2 && is_select && !is_cached {
             let _ = interpreter
                 .start()
                 .await
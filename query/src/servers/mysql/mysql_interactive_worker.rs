@@ -290,6 +290,15 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
     // Check the query is a federated or driver setup command.
     // Here we fake some values for the command which Databend not supported.
     fn federated_server_command_check(&self, query: &str) -> Option<DataBlock> {
+        // COMMIT and ROLLBACK end the transaction started by BEGIN,
+        // they are faked only if the session is not in a transaction.
+        if self.session.get_transaction().is_some() {
+            let query = query.trim_start().to_uppercase();
+            if query.starts_with("COMMIT") || query.starts_with("ROLLBACK") {
+                return None;
+            }
+        }
+
         let federated = MySQLFederated::create();
         federated.check(query)
     }
@@ -0,0 +1,490 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_base::base::tokio::sync::OwnedSemaphorePermit;
+use common_base::base::tokio::sync::Semaphore;
+use common_base::base::tokio::time::interval;
+use common_base::base::tokio::time::sleep;
+use common_base::base::TrySpawn;
+use common_catalog::table_context::BackgroundTaskInfo;
+use common_config::QueryConfig;
+use common_datavalues::chrono::DateTime;
+use common_datavalues::chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_management::BackgroundTaskApi;
+use common_metrics::label_counter;
+use common_planners::OptimizeTableAction;
+use common_planners::OptimizeTablePlan;
+use common_planners::ReclusterTablePlan;
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+use tracing::info;
+use tracing::warn;
+
+use super::metrics::METRIC_BACKGROUND_TASK_FAILED_NUMBERS;
+use super::metrics::METRIC_BACKGROUND_TASK_SUCCEEDED_NUMBERS;
+use crate::catalogs::CATALOG_DEFAULT;
+use crate::sessions::SessionManager;
+use crate::sessions::SessionType;
+use crate::sessions::TableContext;
+use crate::storages::fuse::FuseTable;
+
+/// The max number of the finished tasks kept for `system.background_tasks`.
+const MAX_FINISHED_TASKS: usize = 1000;
+
+/// The lock of a table expires after this, in case the node crashes with the lock.
+const TABLE_LOCK_LIFT_TIME: Duration = Duration::from_secs(3600);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackgroundTaskType {
+    Compact,
+    Recluster,
+    BuildIndex,
+}
+
+impl Display for BackgroundTaskType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackgroundTaskType::Compact => write!(f, "Compact"),
+            BackgroundTaskType::Recluster => write!(f, "Recluster"),
+            BackgroundTaskType::BuildIndex => write!(f, "BuildIndex"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackgroundTaskState {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl Display for BackgroundTaskState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BackgroundTaskState::Running => write!(f, "Running"),
+            BackgroundTaskState::Succeeded => write!(f, "Succeeded"),
+            BackgroundTaskState::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+struct BackgroundTask {
+    id: u64,
+    typ: BackgroundTaskType,
+    state: BackgroundTaskState,
+    database: String,
+    table: String,
+    reason: String,
+    node: String,
+    created_on: DateTime<Utc>,
+    finished_on: Option<DateTime<Utc>>,
+    error: Option<String>,
+}
+
+/// Runs the maintenance of the FUSE tables in background.
+///
+/// The tables of the default catalog are checked periodically, the ones having too many
+/// segments or small blocks are compacted, and the ones whose blocks overlap too deep on
+/// the cluster key are reclustered. The statements may also submit tasks, e.g. the backfill of
+/// the indexes just created. At most `background_tasks_max_concurrency` tasks run at the same
+/// time on a node. A table is locked in the meta service by the node running its task, so that
+/// the nodes of the cluster never work on the same table.
+pub struct BackgroundTaskManager {
+    conf: QueryConfig,
+    next_id: AtomicU64,
+    semaphore: Arc<Semaphore>,
+    // the tables having task running on this node
+    running_tables: Mutex<HashSet<u64>>,
+    tasks: RwLock<VecDeque<BackgroundTask>>,
+}
+
+impl BackgroundTaskManager {
+    pub fn init(conf: &QueryConfig) -> BackgroundTaskManager {
+        let max_concurrency = std::cmp::max(1, conf.background_tasks_max_concurrency as usize);
+        BackgroundTaskManager {
+            conf: conf.clone(),
+            next_id: AtomicU64::new(1),
+            semaphore: Arc::new(Semaphore::new(max_concurrency)),
+            running_tables: Mutex::new(HashSet::new()),
+            tasks: RwLock::new(VecDeque::new()),
+        }
+    }
+
+    pub fn start(self: &Arc<Self>, session_mgr: Arc<SessionManager>) {
+        if !self.conf.background_tasks_enabled {
+            return;
+        }
+
+        let this = self.clone();
+        let check_interval =
+            Duration::from_secs(std::cmp::max(1, self.conf.background_tasks_interval_secs));
+        session_mgr.get_storage_runtime().spawn(async move {
+            let mut intv = interval(check_interval);
+            loop {
+                intv.tick().await;
+                if let Err(cause) = this.check_tables(&session_mgr).await {
+                    warn!("Failed to check the tables for background tasks: {}", cause);
+                }
+            }
+        });
+    }
+
+    pub fn tasks_info(&self) -> Vec<BackgroundTaskInfo> {
+        self.tasks
+            .read()
+            .iter()
+            .map(|task| BackgroundTaskInfo {
+                id: task.id,
+                typ: task.typ.to_string(),
+                state: task.state.to_string(),
+                database: task.database.clone(),
+                table: task.table.clone(),
+                reason: task.reason.clone(),
+                node: task.node.clone(),
+                created_on: task.created_on,
+                finished_on: task.finished_on,
+                error: task.error.clone(),
+            })
+            .collect()
+    }
+
+    /// Submits a task of the table, which runs once a slot is free and the table is unlocked.
+    ///
+    /// Returns the id of the task, the task is shown in `system.background_tasks` from now on.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_task(
+        self: &Arc<Self>,
+        ctx: &Arc<dyn TableContext>,
+        session_mgr: &Arc<SessionManager>,
+        typ: BackgroundTaskType,
+        catalog: &str,
+        database: &str,
+        table: &str,
+        table_id: u64,
+        reason: String,
+    ) -> Result<u64> {
+        let node = ctx.get_cluster().local_id();
+        let lock_api = ctx
+            .get_user_manager()
+            .get_background_task_api_client(&ctx.get_tenant(), TABLE_LOCK_LIFT_TIME)?;
+        let id = self.add_task(typ, database, table, reason, &node);
+
+        let this = self.clone();
+        let session_mgr = session_mgr.clone();
+        let catalog = catalog.to_string();
+        let database = database.to_string();
+        let table = table.to_string();
+        let retry_interval =
+            Duration::from_secs(std::cmp::max(1, self.conf.background_tasks_interval_secs));
+        session_mgr.clone().get_storage_runtime().spawn(async move {
+            let res = async {
+                // Wait for the task of the table running on any node. The slot is taken after
+                // the table is locked, so that the waiting task never holds a slot.
+                let (_running, lock_seq) = loop {
+                    let running = match RunningTableGuard::try_create(&this, table_id) {
+                        Some(running) => running,
+                        None => {
+                            sleep(retry_interval).await;
+                            continue;
+                        }
+                    };
+                    match lock_api.lock_table(table_id, &node).await? {
+                        Some(seq) => break (running, seq),
+                        None => {
+                            drop(running);
+                            sleep(retry_interval).await;
+                        }
+                    }
+                };
+
+                let res = match this.semaphore.clone().acquire_owned().await {
+                    Ok(_permit) => {
+                        Self::run_task(&session_mgr, typ, catalog, database, table).await
+                    }
+                    Err(e) => Err(ErrorCode::LogicalError(e.to_string())),
+                };
+                if let Err(cause) = lock_api.unlock_table(table_id, lock_seq).await {
+                    warn!("Failed to unlock table {}: {}", table_id, cause);
+                }
+                res
+            }
+            .await;
+            this.finish_task(id, res);
+        });
+        Ok(id)
+    }
+
+    async fn check_tables(self: &Arc<Self>, session_mgr: &Arc<SessionManager>) -> Result<()> {
+        let session = session_mgr.create_session(SessionType::Dummy).await?;
+        let ctx: Arc<dyn TableContext> = session.create_query_context().await?;
+        let tenant = ctx.get_tenant();
+        let node = ctx.get_cluster().local_id();
+        let lock_api = ctx
+            .get_user_manager()
+            .get_background_task_api_client(&tenant, TABLE_LOCK_LIFT_TIME)?;
+
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        for database in catalog.list_databases(&tenant).await? {
+            for table in catalog.list_tables(&tenant, database.name()).await? {
+                let fuse_table = match FuseTable::try_from_table(table.as_ref()) {
+                    Ok(fuse_table) if fuse_table.check_mutable().is_ok() => fuse_table,
+                    _ => continue,
+                };
+
+                let table_id = table.get_id();
+                let running = match RunningTableGuard::try_create(self, table_id) {
+                    Some(running) => running,
+                    None => continue,
+                };
+
+                let permit = match self.semaphore.clone().try_acquire_owned() {
+                    Ok(permit) => permit,
+                    // all the slots are busy, check again next time
+                    Err(_) => return Ok(()),
+                };
+
+                let (typ, reason) = match self.check_table(&ctx, fuse_table).await {
+                    Ok(Some(task)) => task,
+                    Ok(None) => continue,
+                    Err(cause) => {
+                        warn!("Failed to check table {}: {}", table.name(), cause);
+                        continue;
+                    }
+                };
+
+                let seq = match lock_api.lock_table(table_id, &node).await? {
+                    Some(seq) => seq,
+                    // the table is locked by other node
+                    None => continue,
+                };
+
+                self.spawn_task(
+                    session_mgr,
+                    lock_api.clone(),
+                    running,
+                    permit,
+                    typ,
+                    table_id,
+                    seq,
+                    database.name(),
+                    table.name(),
+                    reason,
+                    &node,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Compaction goes first, the reclustering of a table is checked once it is compact enough.
+    async fn check_table(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        table: &FuseTable,
+    ) -> Result<Option<(BackgroundTaskType, String)>> {
+        let compact = table
+            .check_compact(
+                ctx,
+                self.conf.background_compact_segment_threshold,
+                self.conf.background_compact_small_block_ratio,
+            )
+            .await?;
+        if let Some(reason) = compact {
+            return Ok(Some((BackgroundTaskType::Compact, reason)));
+        }
+
+        let recluster = table
+            .check_recluster(ctx, self.conf.background_recluster_depth_threshold)
+            .await?;
+        Ok(recluster.map(|reason| (BackgroundTaskType::Recluster, reason)))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn spawn_task(
+        self: &Arc<Self>,
+        session_mgr: &Arc<SessionManager>,
+        lock_api: Arc<dyn BackgroundTaskApi>,
+        running: RunningTableGuard,
+        permit: OwnedSemaphorePermit,
+        typ: BackgroundTaskType,
+        table_id: u64,
+        lock_seq: u64,
+        database: &str,
+        table: &str,
+        reason: String,
+        node: &str,
+    ) {
+        let id = self.add_task(typ, database, table, reason, node);
+
+        let this = self.clone();
+        let session_mgr = session_mgr.clone();
+        let database = database.to_string();
+        let table = table.to_string();
+        session_mgr.clone().get_storage_runtime().spawn(async move {
+            let _running = running;
+            let _permit = permit;
+            let catalog = CATALOG_DEFAULT.to_string();
+            let res = Self::run_task(&session_mgr, typ, catalog, database, table).await;
+            if let Err(cause) = lock_api.unlock_table(table_id, lock_seq).await {
+                warn!("Failed to unlock table {}: {}", table_id, cause);
+            }
+            this.finish_task(id, res);
+        });
+    }
+
+    fn add_task(
+        &self,
+        typ: BackgroundTaskType,
+        database: &str,
+        table: &str,
+        reason: String,
+        node: &str,
+    ) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        info!(
+            "Background task {} started: {} table {}.{}, {}",
+            id, typ, database, table, reason
+        );
+
+        self.tasks.write().push_back(BackgroundTask {
+            id,
+            typ,
+            state: BackgroundTaskState::Running,
+            database: database.to_string(),
+            table: table.to_string(),
+            reason,
+            node: node.to_string(),
+            created_on: Utc::now(),
+            finished_on: None,
+            error: None,
+        });
+        id
+    }
+
+    async fn run_task(
+        session_mgr: &Arc<SessionManager>,
+        typ: BackgroundTaskType,
+        catalog: String,
+        database: String,
+        table: String,
+    ) -> Result<()> {
+        // a new context for each task, which reads the latest snapshot of the table
+        let session = session_mgr.create_session(SessionType::Dummy).await?;
+        let ctx = session.create_query_context().await?;
+        let tbl = ctx.get_table(&catalog, &database, &table).await?;
+        match typ {
+            BackgroundTaskType::Compact => {
+                let plan = OptimizeTablePlan {
+                    catalog,
+                    database,
+                    table,
+                    action: OptimizeTableAction::Compact,
+                };
+                tbl.compact(ctx, plan).await
+            }
+            BackgroundTaskType::Recluster => {
+                let plan = ReclusterTablePlan {
+                    tenant: ctx.get_tenant(),
+                    catalog,
+                    database,
+                    table,
+                    is_final: false,
+                    selection: None,
+                };
+                tbl.recluster(ctx, plan).await
+            }
+            BackgroundTaskType::BuildIndex => tbl.build_indexes(ctx, &catalog).await,
+        }
+    }
+
+    fn finish_task(&self, id: u64, res: Result<()>) {
+        let mut tasks = self.tasks.write();
+        if let Some(task) = tasks.iter_mut().find(|task| task.id == id) {
+            task.finished_on = Some(Utc::now());
+            match res {
+                Ok(_) => {
+                    info!("Background task {} succeeded", id);
+                    label_counter(
+                        METRIC_BACKGROUND_TASK_SUCCEEDED_NUMBERS,
+                        &self.conf.tenant_id,
+                        &self.conf.cluster_id,
+                    );
+                    task.state = BackgroundTaskState::Succeeded;
+                }
+                Err(cause) => {
+                    warn!("Background task {} failed: {}", id, cause);
+                    label_counter(
+                        METRIC_BACKGROUND_TASK_FAILED_NUMBERS,
+                        &self.conf.tenant_id,
+                        &self.conf.cluster_id,
+                    );
+                    task.state = BackgroundTaskState::Failed;
+                    task.error = Some(cause.to_string());
+                }
+            }
+        }
+
+        // evict the oldest finished tasks
+        while tasks.len() > MAX_FINISHED_TASKS {
+            match tasks
+                .iter()
+                .position(|task| task.state != BackgroundTaskState::Running)
+            {
+                Some(pos) => {
+                    tasks.remove(pos);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Marks a table as having a task running on this node, the mark is cleared on drop.
+///
+/// Only the owner of the mark clears it, so that a task failing to lock the table never
+/// clears the mark of the task running on the table.
+struct RunningTableGuard {
+    manager: Arc<BackgroundTaskManager>,
+    table_id: u64,
+}
+
+impl RunningTableGuard {
+    /// Returns `None` if the table is marked already.
+    fn try_create(manager: &Arc<BackgroundTaskManager>, table_id: u64) -> Option<Self> {
+        if !manager.running_tables.lock().insert(table_id) {
+            return None;
+        }
+        Some(RunningTableGuard {
+            manager: manager.clone(),
+            table_id,
+        })
+    }
+}
+
+impl Drop for RunningTableGuard {
+    fn drop(&mut self) {
+        self.manager.running_tables.lock().remove(&self.table_id);
+    }
+}
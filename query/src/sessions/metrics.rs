@@ -14,3 +14,7 @@
 
 pub static METRIC_SESSION_CONNECT_NUMBERS: &str = "session.connect_numbers";
 pub static METRIC_SESSION_CLOSE_NUMBERS: &str = "session.close_numbers";
+pub static METRIC_QUERY_RESULT_CACHE_HIT_NUMBERS: &str = "query_result_cache.hit_numbers";
+pub static METRIC_QUERY_RESULT_CACHE_MISS_NUMBERS: &str = "query_result_cache.miss_numbers";
+pub static METRIC_BACKGROUND_TASK_SUCCEEDED_NUMBERS: &str = "background_task.succeeded_numbers";
+pub static METRIC_BACKGROUND_TASK_FAILED_NUMBERS: &str = "background_task.failed_numbers";
@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod background_task_manager;
 mod metrics;
 mod query_affect;
 pub mod query_ctx;
 mod query_ctx_shared;
+mod query_result_cache;
 mod session;
 mod session_ctx;
 mod session_info;
@@ -26,11 +28,16 @@ mod session_ref;
 mod session_settings;
 mod session_status;
 mod session_type;
+mod transaction;
 
+pub use background_task_manager::BackgroundTaskManager;
+pub use background_task_manager::BackgroundTaskState;
+pub use background_task_manager::BackgroundTaskType;
 pub use common_catalog::table_context::TableContext;
 pub use query_affect::QueryAffect;
 pub use query_ctx::QueryContext;
 pub use query_ctx_shared::QueryContextShared;
+pub use query_result_cache::QueryResultCache;
 pub use session::Session;
 pub use session_ctx::SessionContext;
 pub use session_info::ProcessInfo;
@@ -40,3 +47,5 @@ pub use session_ref::SessionRef;
 pub use session_settings::Settings;
 pub use session_status::SessionStatus;
 pub use session_type::SessionType;
+pub use transaction::PendingTable;
+pub use transaction::Transaction;
@@ -27,6 +27,7 @@ use common_base::base::Progress;
 use common_base::base::ProgressValues;
 use common_base::base::Runtime;
 use common_base::base::TrySpawn;
+use common_catalog::table_context::BackgroundTaskInfo;
 use common_contexts::DalContext;
 use common_contexts::DalMetrics;
 use common_datablocks::DataBlock;
@@ -35,6 +36,8 @@ use common_exception::Result;
 use common_functions::scalars::FunctionContext;
 use common_io::prelude::FormatSettings;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_types::MetaId;
 use common_meta_types::UserInfo;
 use common_planners::Expression;
 use common_planners::PartInfoPtr;
@@ -53,6 +56,7 @@ use opendal::Operator;
 use parking_lot::Mutex;
 use parking_lot::RwLock;
 use tracing::debug;
+use tracing::warn;
 
 use crate::api::DataExchangeManager;
 use crate::auth::AuthMgr;
@@ -61,12 +65,16 @@ use crate::catalogs::CatalogManager;
 use crate::clusters::Cluster;
 use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::query_affect::QueryAffect;
+use crate::sessions::PendingTable;
 use crate::sessions::ProcessInfo;
 use crate::sessions::QueryContextShared;
+use crate::sessions::QueryResultCache;
 use crate::sessions::SessionRef;
 use crate::sessions::Settings;
 use crate::sessions::TableContext;
+use crate::sessions::Transaction;
 use crate::storages::cache::CacheManager;
+use crate::storages::fuse::FuseTable;
 use crate::storages::stage::StageTable;
 use crate::storages::Table;
 use crate::Config;
@@ -110,6 +118,9 @@ impl QueryContext {
     ) -> Result<Arc<dyn Table>> {
         let catalog = self.get_catalog(catalog_name)?;
         if table_args.is_none() {
+            if let Some(table) = self.get_materialized_table(table_info.ident.table_id) {
+                return Ok(table);
+            }
             catalog.get_table_by_info(table_info)
         } else {
             Ok(catalog
@@ -146,6 +157,18 @@ impl QueryContext {
         self.shared.get_table(catalog, database, table).await
     }
 
+    pub fn next_materialized_table_id(&self) -> MetaId {
+        self.shared.next_materialized_table_id()
+    }
+
+    pub fn add_materialized_table(&self, table: Arc<dyn Table>) {
+        self.shared.add_materialized_table(table)
+    }
+
+    pub fn get_materialized_table(&self, table_id: MetaId) -> Option<Arc<dyn Table>> {
+        self.shared.get_materialized_table(table_id)
+    }
+
     pub async fn set_current_database(&self, new_database_name: String) -> Result<()> {
         let tenant_id = self.get_tenant();
         let catalog = self.get_catalog(self.get_current_catalog().as_str())?;
@@ -187,6 +210,29 @@ impl QueryContext {
         self.shared.get_http_query()
     }
 
+    /// Attach the formatted AST of the `SELECT` statement, which is a part of the query result cache key.
+    pub fn attach_normalized_query(&self, query: String) {
+        self.shared.attach_normalized_query(query);
+    }
+
+    pub fn get_normalized_query(&self) -> Option<String> {
+        self.shared.get_normalized_query()
+    }
+
+    pub fn get_query_result_cache(&self) -> Arc<QueryResultCache> {
+        self.shared.session.session_mgr.get_query_result_cache()
+    }
+
+    /// The operator and the path prefix to spill the data of the query.
+    pub fn get_spill_operator(&self) -> (Operator, String) {
+        self.shared.get_spill_operator()
+    }
+
+    /// Mark the query has spilled data, which is removed when the query is finished or killed.
+    pub fn set_spilled(&self) {
+        self.shared.set_spilled()
+    }
+
     pub fn get_auth_manager(&self) -> Arc<AuthMgr> {
         self.shared.get_auth_manager()
     }
@@ -242,6 +288,87 @@ impl QueryContext {
     pub fn set_affect(self: &Arc<Self>, affect: QueryAffect) {
         self.shared.set_affect(affect)
     }
+
+    /// Start a transaction in the session, the following writes of the session are committed
+    /// by `commit_transaction`.
+    pub fn begin_transaction(&self) -> Result<()> {
+        let session = &self.shared.session;
+        if session.get_transaction().is_some() {
+            return Err(ErrorCode::TransactionAlreadyStarted(
+                "there is already a transaction in progress",
+            ));
+        }
+        session.set_transaction(Some(Arc::new(Transaction::default())));
+        Ok(())
+    }
+
+    /// Commit the table metas written in the transaction of the session all together,
+    /// nothing is committed if any of the tables is changed by others since it was written.
+    ///
+    /// The data written in the transaction is purged if it is certainly not committed.
+    pub async fn commit_transaction(&self) -> Result<()> {
+        if let Some(transaction) = self.shared.session.take_transaction() {
+            if let Some((catalog_name, pending_tables)) = transaction.take_pending_tables() {
+                let catalog = self.get_catalog(&catalog_name)?;
+                let req = Transaction::update_req(&pending_tables);
+                if let Err(e) = catalog.update_multi_table_meta(req).await {
+                    // on the other errors, e.g. timeout, the metas may have been committed
+                    let code = e.code();
+                    if code == ErrorCode::table_version_mismatched_code()
+                        || code == ErrorCode::unknown_table_id_code()
+                    {
+                        self.purge_pending_tables(catalog.as_ref(), &pending_tables)
+                            .await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Discard the table metas written in the transaction of the session, and purge the data
+    /// written in it.
+    pub async fn rollback_transaction(&self) -> Result<()> {
+        if let Some(transaction) = self.shared.session.take_transaction() {
+            if let Some((catalog_name, pending_tables)) = transaction.take_pending_tables() {
+                let catalog = self.get_catalog(&catalog_name)?;
+                self.purge_pending_tables(catalog.as_ref(), &pending_tables)
+                    .await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Purges the snapshots, segments and blocks written in a discarded transaction, which are
+    /// not referenced by any committed snapshot. A failure of purging is only logged, since
+    /// the transaction is discarded anyway.
+    async fn purge_pending_tables(&self, catalog: &dyn Catalog, pending_tables: &[PendingTable]) {
+        for pending_table in pending_tables {
+            if let Err(e) = self.purge_pending_table(catalog, pending_table).await {
+                warn!(
+                    "failed to purge the data written in the transaction, table {}: {}",
+                    pending_table.table_info.desc, e
+                );
+            }
+        }
+    }
+
+    async fn purge_pending_table(
+        &self,
+        catalog: &dyn Catalog,
+        pending_table: &PendingTable,
+    ) -> Result<()> {
+        let table_info = &pending_table.table_info;
+        let table = catalog.get_table_by_info(&TableInfo {
+            meta: pending_table.base_meta.clone(),
+            ..table_info.clone()
+        })?;
+        let table = FuseTable::try_from_table(table.as_ref())?;
+        let mut metas = pending_table.replaced_metas.clone();
+        metas.push(table_info.meta.clone());
+        table.purge_uncommitted(self, &metas).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -340,6 +467,25 @@ impl TableContext for QueryContext {
             .get_catalogs()
             .get_catalog(catalog_name.as_ref())
     }
+    async fn update_table_meta(
+        &self,
+        catalog_name: &str,
+        table_info: &TableInfo,
+        req: UpdateTableMetaReq,
+    ) -> Result<()> {
+        match &self.shared.transaction {
+            Some(transaction) => transaction.update_table_meta(catalog_name, table_info, req),
+            None => {
+                self.get_catalog(catalog_name)?
+                    .update_table_meta(req)
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+    fn in_transaction(&self) -> bool {
+        self.shared.transaction.is_some()
+    }
     fn get_id(&self) -> String {
         self.shared.init_query_id.as_ref().read().clone()
     }
@@ -451,6 +597,15 @@ impl TableContext for QueryContext {
             .processes_info()
             .await
     }
+
+    // Get the background tasks of the node.
+    fn get_background_tasks_info(&self) -> Vec<BackgroundTaskInfo> {
+        self.shared
+            .session
+            .get_session_manager()
+            .get_background_task_manager()
+            .tasks_info()
+    }
 }
 
 impl TrySpawn for QueryContext {
@@ -482,6 +637,7 @@ impl QueryContextShared {
         if self.ref_count.fetch_sub(1, Ordering::Release) == 1 {
             std::sync::atomic::fence(Acquire);
             debug!("Destroy QueryContext");
+            self.remove_spilled_data();
             self.session.destroy_context_shared();
         }
     }
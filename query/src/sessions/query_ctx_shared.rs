@@ -15,20 +15,24 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use common_base::base::Progress;
 use common_base::base::Runtime;
+use common_base::base::TrySpawn;
 use common_contexts::DalContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_types::MetaId;
 use common_meta_types::UserInfo;
 use common_planners::PlanNode;
 use common_users::RoleCacheMgr;
 use common_users::UserApiProvider;
 use futures::future::AbortHandle;
+use opendal::Operator;
 use parking_lot::Mutex;
 use parking_lot::RwLock;
 use uuid::Uuid;
@@ -40,6 +44,7 @@ use crate::servers::http::v1::HttpQueryHandle;
 use crate::sessions::query_affect::QueryAffect;
 use crate::sessions::Session;
 use crate::sessions::Settings;
+use crate::sessions::Transaction;
 use crate::sql::SQLCommon;
 use crate::storages::Table;
 use crate::Config;
@@ -71,15 +76,23 @@ pub struct QueryContextShared {
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
     pub(in crate::sessions) subquery_index: Arc<AtomicUsize>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
+    pub(in crate::sessions) normalized_query: Arc<RwLock<Option<String>>>,
     pub(in crate::sessions) http_query: Arc<RwLock<Option<HttpQueryHandle>>>,
     pub(in crate::sessions) running_plan: Arc<RwLock<Option<PlanNode>>>,
     pub(in crate::sessions) tables_refs: Arc<Mutex<HashMap<DatabaseAndTable, Arc<dyn Table>>>>,
+    pub(in crate::sessions) materialized_tables: Arc<RwLock<HashMap<MetaId, Arc<dyn Table>>>>,
+    pub(in crate::sessions) materialized_table_id: Arc<AtomicU64>,
     pub(in crate::sessions) dal_ctx: Arc<DalContext>,
     pub(in crate::sessions) user_manager: Arc<UserApiProvider>,
     pub(in crate::sessions) auth_manager: Arc<AuthMgr>,
     pub(in crate::sessions) affect: Arc<Mutex<Option<QueryAffect>>>,
+    /// The transaction of the session when the query starts, the table metas written by the
+    /// query are buffered in it instead of being committed at once.
+    pub(in crate::sessions) transaction: Option<Arc<Transaction>>,
 
     pub(in crate::sessions) query_need_abort: Arc<AtomicBool>,
+    /// Whether the query has spilled data, which is removed when the query is finished or killed.
+    pub(in crate::sessions) spilled: Arc<AtomicBool>,
 }
 
 impl QueryContextShared {
@@ -90,6 +103,7 @@ impl QueryContextShared {
         let conf = session.get_config();
 
         let user_manager = session.session_mgr.get_user_api_provider();
+        let transaction = session.get_transaction();
 
         Ok(Arc::new(QueryContextShared {
             session,
@@ -104,14 +118,19 @@ impl QueryContextShared {
             ref_count: Arc::new(AtomicUsize::new(0)),
             subquery_index: Arc::new(AtomicUsize::new(1)),
             running_query: Arc::new(RwLock::new(None)),
+            normalized_query: Arc::new(RwLock::new(None)),
             http_query: Arc::new(RwLock::new(None)),
             running_plan: Arc::new(RwLock::new(None)),
             tables_refs: Arc::new(Mutex::new(HashMap::new())),
+            materialized_tables: Arc::new(RwLock::new(HashMap::new())),
+            materialized_table_id: Arc::new(AtomicU64::new(0)),
             dal_ctx: Arc::new(Default::default()),
             user_manager: user_manager.clone(),
             auth_manager: Arc::new(AuthMgr::create(conf, user_manager.clone()).await?),
             query_need_abort: Arc::new(AtomicBool::new(false)),
+            spilled: Arc::new(AtomicBool::new(false)),
             affect: Arc::new(Mutex::new(None)),
+            transaction,
         }))
     }
 
@@ -135,9 +154,36 @@ impl QueryContextShared {
         while let Some(source_abort_handle) = sources_abort_handle.pop() {
             source_abort_handle.abort();
         }
+        self.remove_spilled_data();
         // TODO: Wait for the query to be processed (write out the last error)
     }
 
+    /// The operator and the path prefix to spill the data of the query.
+    pub fn get_spill_operator(&self) -> (Operator, String) {
+        let (operator, prefix) = self.session.session_mgr.get_spill_operator();
+        let prefix = format!("{}{}/", prefix, self.init_query_id.read());
+        (operator.layer(self.dal_ctx.as_ref().clone()), prefix)
+    }
+
+    pub fn set_spilled(&self) {
+        self.spilled.store(true, Ordering::Release);
+    }
+
+    /// Remove all the spilled data of the query in the background.
+    pub fn remove_spilled_data(&self) {
+        if !self.spilled.load(Ordering::Acquire) {
+            return;
+        }
+
+        let (operator, prefix) = self.get_spill_operator();
+        let runtime = self.session.session_mgr.get_storage_runtime();
+        runtime.spawn(async move {
+            if let Err(cause) = operator.batch().remove_all(&prefix).await {
+                tracing::warn!("Failed to remove the spilled data {}: {}", prefix, cause);
+            }
+        });
+    }
+
     pub fn get_cluster(&self) -> Arc<Cluster> {
         self.cluster_cache.clone()
     }
@@ -228,8 +274,18 @@ impl QueryContextShared {
     ) -> Result<Arc<dyn Table>> {
         let tenant = self.get_tenant();
         let table_meta_key = (catalog.to_string(), database.to_string(), table.to_string());
-        let catalog = self.get_catalogs().get_catalog(catalog)?;
-        let cache_table = catalog.get_table(tenant.as_str(), database, table).await?;
+        let catalog_name = catalog;
+        let catalog = self.get_catalogs().get_catalog(catalog_name)?;
+        let mut cache_table = catalog.get_table(tenant.as_str(), database, table).await?;
+
+        // The table written in the transaction is read with its pending meta.
+        if let Some(transaction) = &self.transaction {
+            if let Some(table_info) =
+                transaction.get_pending_table_info(catalog_name, cache_table.get_id())
+            {
+                cache_table = catalog.get_table_by_info(&table_info)?;
+            }
+        }
 
         let mut tables_refs = self.tables_refs.lock();
 
@@ -239,6 +295,25 @@ impl QueryContextShared {
         }
     }
 
+    /// Allocate a table id for a table materialized in this query.
+    /// The ids are allocated from the top of the id space, so they won't conflict with
+    /// the ids of tables in catalogs.
+    pub fn next_materialized_table_id(&self) -> MetaId {
+        MetaId::MAX - self.materialized_table_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    /// Register a table materialized in this query, e.g. the result of a recursive CTE,
+    /// which is not stored in any catalog.
+    pub fn add_materialized_table(&self, table: Arc<dyn Table>) {
+        let mut materialized_tables = self.materialized_tables.write();
+        materialized_tables.insert(table.get_id(), table);
+    }
+
+    pub fn get_materialized_table(&self, table_id: MetaId) -> Option<Arc<dyn Table>> {
+        let materialized_tables = self.materialized_tables.read();
+        materialized_tables.get(&table_id).cloned()
+    }
+
     /// Init runtime when first get
     pub fn try_get_runtime(&self) -> Result<Arc<Runtime>> {
         let mut query_runtime = self.runtime.write();
@@ -276,6 +351,15 @@ impl QueryContextShared {
         running_query.as_ref().unwrap_or(&"".to_string()).clone()
     }
 
+    pub fn attach_normalized_query(&self, query: String) {
+        let mut normalized_query = self.normalized_query.write();
+        *normalized_query = Some(query);
+    }
+
+    pub fn get_normalized_query(&self) -> Option<String> {
+        self.normalized_query.read().clone()
+    }
+
     pub fn attach_query_plan(&self, plan: &PlanNode) {
         let mut running_plan = self.running_plan.write();
         *running_plan = Some(plan.clone());
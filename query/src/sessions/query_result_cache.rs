@@ -0,0 +1,229 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common_cache::BytesMeter;
+use common_cache::Cache;
+use common_cache::DefaultHashBuilder;
+use common_cache::LruCache;
+use common_cache::LruDiskCache;
+use common_config::QueryConfig;
+use common_datablocks::serialize_data_blocks;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_formats::FormatFactory;
+use common_io::prelude::FileSplit;
+use common_io::prelude::FormatSettings;
+use common_metrics::label_counter;
+use parking_lot::Mutex;
+use sha2::Digest;
+use sha2::Sha256;
+use tracing::warn;
+
+use super::metrics::METRIC_QUERY_RESULT_CACHE_HIT_NUMBERS;
+use super::metrics::METRIC_QUERY_RESULT_CACHE_MISS_NUMBERS;
+
+type MemoryCache = LruCache<String, Arc<Vec<u8>>, DefaultHashBuilder, BytesMeter>;
+
+/// The disk cache is opened at the first access, so nothing is created on the disk
+/// if the result cache is never used.
+struct DiskCache {
+    root: Option<PathBuf>,
+    capacity: u64,
+    cache: Option<LruDiskCache>,
+}
+
+impl DiskCache {
+    fn cache(&mut self) -> Option<&mut LruDiskCache> {
+        if let Some(root) = self.root.take() {
+            match LruDiskCache::new(&root, self.capacity) {
+                Ok(cache) => self.cache = Some(cache),
+                Err(cause) => warn!("Cannot open query result disk cache {:?}: {}", root, cause),
+            }
+        }
+
+        self.cache.as_mut()
+    }
+}
+
+/// Cache of the result blocks of `SELECT` queries.
+///
+/// The entries are keyed by the normalized SQL, the relevant settings and the snapshots of the
+/// referenced tables, so a committed snapshot changes the key and the stale entries are never
+/// hit again, they are evicted by the LRU policy.
+///
+/// The result blocks are serialized in parquet and kept in a memory LRU cache, which is backed
+/// by an LRU cache of files on the local disk.
+pub struct QueryResultCache {
+    memory_cache: Mutex<MemoryCache>,
+    memory_capacity: u64,
+    disk_cache: Mutex<DiskCache>,
+    cluster_id: String,
+    tenant_id: String,
+}
+
+impl QueryResultCache {
+    pub fn init(config: &QueryConfig) -> QueryResultCache {
+        let memory_capacity = config.query_result_cache_memory_mb_size * 1024 * 1024;
+        let disk_capacity = config.query_result_cache_disk_mb_size * 1024 * 1024;
+        let disk_root = match disk_capacity {
+            0 => None,
+            _ => Some(PathBuf::from(&config.query_result_cache_disk_root)),
+        };
+
+        QueryResultCache {
+            memory_cache: Mutex::new(LruCache::with_meter_and_hasher(
+                memory_capacity,
+                BytesMeter,
+                DefaultHashBuilder::new(),
+            )),
+            memory_capacity,
+            disk_cache: Mutex::new(DiskCache {
+                root: disk_root,
+                capacity: disk_capacity,
+                cache: None,
+            }),
+            cluster_id: config.cluster_id.clone(),
+            tenant_id: config.tenant_id.clone(),
+        }
+    }
+
+    /// Build the cache key from the parts which determine the result of a query.
+    pub fn cache_key<T: AsRef<[u8]>>(parts: &[T]) -> String {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            let part = part.as_ref();
+            hasher.update((part.len() as u64).to_le_bytes());
+            hasher.update(part);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// The max bytes of the result blocks that can be cached.
+    pub fn max_entry_bytes(&self) -> u64 {
+        let disk_capacity = self.disk_cache.lock().capacity;
+        std::cmp::max(self.memory_capacity, disk_capacity)
+    }
+
+    /// Get the cached result blocks, a broken entry is taken as a miss.
+    pub fn get(
+        &self,
+        key: &str,
+        schema: DataSchemaRef,
+        format_settings: FormatSettings,
+    ) -> Option<Vec<DataBlock>> {
+        let blocks = match self.get_data(key) {
+            None => None,
+            Some(data) => match Self::deserialize(schema, format_settings, data.as_ref()) {
+                Ok(blocks) => Some(blocks),
+                Err(cause) => {
+                    warn!("Cannot read query result cache {}: {}", key, cause);
+                    None
+                }
+            },
+        };
+
+        let metric = match blocks {
+            Some(_) => METRIC_QUERY_RESULT_CACHE_HIT_NUMBERS,
+            None => METRIC_QUERY_RESULT_CACHE_MISS_NUMBERS,
+        };
+        label_counter(metric, &self.tenant_id, &self.cluster_id);
+
+        blocks
+    }
+
+    pub fn put(&self, key: String, blocks: Vec<DataBlock>) -> Result<()> {
+        let data = Self::serialize(blocks)?;
+        let size = data.len() as u64;
+
+        if let Some(cache) = self.disk_cache.lock().cache() {
+            if cache.can_store(size) {
+                cache.insert_bytes(&key, &data)?;
+            }
+        }
+
+        if size <= self.memory_capacity {
+            self.memory_cache.lock().put(key, Arc::new(data));
+        }
+
+        Ok(())
+    }
+
+    fn get_data(&self, key: &str) -> Option<Arc<Vec<u8>>> {
+        if let Some(data) = self.memory_cache.lock().get(key) {
+            return Some(data.clone());
+        }
+
+        let data = {
+            let mut disk_cache = self.disk_cache.lock();
+            let cache = disk_cache.cache()?;
+            let mut data = vec![];
+            match cache.get(key) {
+                Err(_) => return None,
+                Ok(mut file) => {
+                    if let Err(cause) = file.read_to_end(&mut data) {
+                        warn!("Cannot read query result disk cache {}: {}", key, cause);
+                        return None;
+                    }
+                }
+            }
+            Arc::new(data)
+        };
+
+        // Promote the entry to the memory cache.
+        if data.len() as u64 <= self.memory_capacity {
+            self.memory_cache.lock().put(key.to_string(), data.clone());
+        }
+
+        Some(data)
+    }
+
+    fn serialize(blocks: Vec<DataBlock>) -> Result<Vec<u8>> {
+        let blocks = blocks
+            .into_iter()
+            .filter(|block| !block.is_empty())
+            .collect::<Vec<_>>();
+
+        // An empty entry stands for an empty result.
+        let mut data = vec![];
+        if !blocks.is_empty() {
+            let schema = blocks[0].schema().clone();
+            serialize_data_blocks(blocks, &schema, &mut data)?;
+        }
+        Ok(data)
+    }
+
+    fn deserialize(
+        schema: DataSchemaRef,
+        format_settings: FormatSettings,
+        data: &[u8],
+    ) -> Result<Vec<DataBlock>> {
+        if data.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let input_format =
+            FormatFactory::instance().get_input("parquet", schema, format_settings)?;
+        input_format.deserialize_complete_split(FileSplit {
+            path: None,
+            start_offset: 0,
+            start_row: 0,
+            buf: data.to_vec(),
+        })
+    }
+}
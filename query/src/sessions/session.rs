@@ -36,6 +36,7 @@ use crate::sessions::SessionManager;
 use crate::sessions::SessionStatus;
 use crate::sessions::SessionType;
 use crate::sessions::Settings;
+use crate::sessions::Transaction;
 use crate::Config;
 
 pub struct Session {
@@ -199,6 +200,18 @@ impl Session {
         self.session_ctx.get_current_database()
     }
 
+    pub fn get_transaction(self: &Arc<Self>) -> Option<Arc<Transaction>> {
+        self.session_ctx.get_transaction()
+    }
+
+    pub fn set_transaction(self: &Arc<Self>, transaction: Option<Arc<Transaction>>) {
+        self.session_ctx.set_transaction(transaction);
+    }
+
+    pub fn take_transaction(self: &Arc<Self>) -> Option<Arc<Transaction>> {
+        self.session_ctx.take_transaction()
+    }
+
     pub fn get_current_catalog(self: &Arc<Self>) -> String {
         self.session_ctx.get_current_catalog()
     }
@@ -23,6 +23,7 @@ use futures::channel::oneshot::Sender;
 use parking_lot::RwLock;
 
 use crate::sessions::QueryContextShared;
+use crate::sessions::Transaction;
 use crate::Config;
 
 pub struct SessionContext {
@@ -36,6 +37,8 @@ pub struct SessionContext {
     client_host: RwLock<Option<SocketAddr>>,
     io_shutdown_tx: RwLock<Option<Sender<Sender<()>>>>,
     query_context_shared: RwLock<Option<Arc<QueryContextShared>>>,
+    // The explicit transaction started by `BEGIN`, if any.
+    transaction: RwLock<Option<Arc<Transaction>>>,
 }
 
 impl SessionContext {
@@ -51,6 +54,7 @@ impl SessionContext {
             current_database: RwLock::new("default".to_string()),
             io_shutdown_tx: Default::default(),
             query_context_shared: Default::default(),
+            transaction: Default::default(),
         })
     }
 
@@ -167,4 +171,20 @@ impl SessionContext {
         let mut lock = self.query_context_shared.write();
         lock.take()
     }
+
+    pub fn get_transaction(&self) -> Option<Arc<Transaction>> {
+        let lock = self.transaction.read();
+        lock.clone()
+    }
+
+    pub fn set_transaction(&self, transaction: Option<Arc<Transaction>>) {
+        let mut lock = self.transaction.write();
+        *lock = transaction
+    }
+
+    // Take the transaction, the session is not in a transaction any more.
+    pub fn take_transaction(&self) -> Option<Arc<Transaction>> {
+        let mut lock = self.transaction.write();
+        lock.take()
+    }
 }
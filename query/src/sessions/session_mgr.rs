@@ -27,7 +27,9 @@ use common_contexts::DalRuntime;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_metrics::label_counter;
+use common_storage::init_fs_operator;
 use common_storage::init_operator;
+use common_storage::StorageFsConfig;
 use common_tracing::init_logging;
 use common_users::RoleCacheMgr;
 use common_users::UserApiProvider;
@@ -47,7 +49,9 @@ use crate::interpreters::AsyncInsertQueue;
 use crate::servers::http::v1::HttpQueryManager;
 use crate::sessions::session::Session;
 use crate::sessions::session_ref::SessionRef;
+use crate::sessions::BackgroundTaskManager;
 use crate::sessions::ProcessInfo;
+use crate::sessions::QueryResultCache;
 use crate::sessions::SessionManagerStatus;
 use crate::sessions::SessionType;
 use crate::storages::cache::CacheManager;
@@ -63,9 +67,13 @@ pub struct SessionManager {
     pub(in crate::sessions) max_sessions: usize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
     pub(in crate::sessions) storage_cache_manager: Arc<CacheManager>,
+    pub(in crate::sessions) query_result_cache: Arc<QueryResultCache>,
+    pub(in crate::sessions) background_task_manager: Arc<BackgroundTaskManager>,
     pub status: Arc<RwLock<SessionManagerStatus>>,
     storage_operator: Operator,
     storage_runtime: Arc<Runtime>,
+    spill_operator: Operator,
+    spill_prefix: String,
 
     user_api_provider: Arc<UserApiProvider>,
     role_cache_manager: Arc<RoleCacheMgr>,
@@ -91,6 +99,8 @@ impl SessionManager {
 
         let catalogs = Arc::new(CatalogManager::try_new(&conf).await?);
         let storage_cache_manager = Arc::new(CacheManager::init(&conf.query));
+        let query_result_cache = Arc::new(QueryResultCache::init(&conf.query));
+        let background_task_manager = Arc::new(BackgroundTaskManager::init(&conf.query));
 
         // Cluster discovery.
         let discovery = ClusterDiscovery::create_global(conf.clone()).await?;
@@ -110,6 +120,17 @@ impl SessionManager {
         let storage_operator = Self::init_storage_operator(&conf)
             .await?
             .layer(DalRuntime::new(storage_runtime.inner()));
+        let (spill_operator, spill_prefix) = match conf.query.spill_root.is_empty() {
+            true => (storage_operator.clone(), "_spill/".to_string()),
+            false => {
+                let spill_operator = init_fs_operator(&StorageFsConfig {
+                    root: conf.query.spill_root.clone(),
+                })
+                .await?
+                .layer(DalRuntime::new(storage_runtime.inner()));
+                (spill_operator, String::new())
+            }
+        };
 
         let http_query_manager = HttpQueryManager::create_global(conf.clone()).await?;
         let max_sessions = conf.query.max_active_sessions as usize;
@@ -142,9 +163,13 @@ impl SessionManager {
             active_sessions,
             data_exchange_manager: exchange_manager,
             storage_cache_manager,
+            query_result_cache,
+            background_task_manager,
             status,
             storage_operator,
             storage_runtime,
+            spill_operator,
+            spill_prefix,
             user_api_provider,
             role_cache_manager,
             mysql_conn_map,
@@ -174,10 +199,23 @@ impl SessionManager {
         self.storage_operator.clone()
     }
 
+    /// The operator and the path prefix where the queries spill their data into.
+    pub fn get_spill_operator(self: &Arc<Self>) -> (Operator, String) {
+        (self.spill_operator.clone(), self.spill_prefix.clone())
+    }
+
     pub fn get_storage_cache_manager(&self) -> Arc<CacheManager> {
         self.storage_cache_manager.clone()
     }
 
+    pub fn get_query_result_cache(&self) -> Arc<QueryResultCache> {
+        self.query_result_cache.clone()
+    }
+
+    pub fn get_background_task_manager(&self) -> Arc<BackgroundTaskManager> {
+        self.background_task_manager.clone()
+    }
+
     pub fn get_data_exchange_manager(&self) -> Arc<DataExchangeManager> {
         self.data_exchange_manager.clone()
     }
@@ -0,0 +1,130 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::schema::UpdateMultiTableMetaReq;
+use common_meta_app::schema::UpdateTableMetaReq;
+use common_meta_types::MatchSeq;
+use common_meta_types::MetaId;
+use parking_lot::Mutex;
+
+/// The pending writes of an explicit transaction, started by `BEGIN`.
+///
+/// The snapshots written by the statements of the transaction are not committed to the meta
+/// service one by one. Instead, the new meta of each touched table is kept here, and all of
+/// them are committed together by `COMMIT`, or discarded by `ROLLBACK`.
+#[derive(Default)]
+pub struct Transaction {
+    /// The catalog the tables written in the transaction belong to.
+    catalog: Mutex<Option<String>>,
+    /// The tables written in the transaction, keyed by the table id.
+    pending_tables: Mutex<BTreeMap<MetaId, PendingTable>>,
+}
+
+/// A table written in a transaction.
+pub struct PendingTable {
+    /// The table info with the pending meta.
+    ///
+    /// The ident is the one the table was first written with, the pending meta is committed
+    /// only if the table is not changed by others since then.
+    pub table_info: TableInfo,
+    /// The meta of the table before it was first written in the transaction.
+    pub base_meta: TableMeta,
+    /// The metas written by the previous statements of the transaction, which are replaced
+    /// by the pending meta and never committed.
+    pub replaced_metas: Vec<TableMeta>,
+}
+
+impl Transaction {
+    /// Keeps the new meta of a table until the transaction is committed.
+    pub fn update_table_meta(
+        &self,
+        catalog_name: &str,
+        table_info: &TableInfo,
+        req: UpdateTableMetaReq,
+    ) -> Result<()> {
+        {
+            let mut catalog = self.catalog.lock();
+            match catalog.as_ref() {
+                Some(catalog) if catalog != catalog_name => {
+                    return Err(ErrorCode::UnImplement(format!(
+                        "a transaction can not write the tables of different catalogs, {} and {}",
+                        catalog, catalog_name
+                    )));
+                }
+                Some(_) => {}
+                None => *catalog = Some(catalog_name.to_string()),
+            }
+        }
+
+        let meta = req.new_table_meta;
+        let mut pending_tables = self.pending_tables.lock();
+        pending_tables
+            .entry(req.table_id)
+            .and_modify(|t| {
+                let replaced = std::mem::replace(&mut t.table_info.meta, meta.clone());
+                t.replaced_metas.push(replaced);
+            })
+            .or_insert_with(|| PendingTable {
+                table_info: TableInfo {
+                    meta: meta.clone(),
+                    ..table_info.clone()
+                },
+                base_meta: table_info.meta.clone(),
+                replaced_metas: vec![],
+            });
+        Ok(())
+    }
+
+    /// Returns the table info with the pending meta, if the table is written in the transaction.
+    pub fn get_pending_table_info(
+        &self,
+        catalog_name: &str,
+        table_id: MetaId,
+    ) -> Option<TableInfo> {
+        match self.catalog.lock().as_ref() {
+            Some(catalog) if catalog == catalog_name => self
+                .pending_tables
+                .lock()
+                .get(&table_id)
+                .map(|t| t.table_info.clone()),
+            _ => None,
+        }
+    }
+
+    /// Takes the tables written in the transaction, and the catalog they belong to.
+    pub fn take_pending_tables(&self) -> Option<(String, Vec<PendingTable>)> {
+        let catalog = self.catalog.lock().take()?;
+        let pending_tables = std::mem::take(&mut *self.pending_tables.lock());
+        Some((catalog, pending_tables.into_values().collect()))
+    }
+
+    /// Builds the request committing the pending metas of the tables all together.
+    pub fn update_req(pending_tables: &[PendingTable]) -> UpdateMultiTableMetaReq {
+        let update_table_metas = pending_tables
+            .iter()
+            .map(|t| UpdateTableMetaReq {
+                table_id: t.table_info.ident.table_id,
+                seq: MatchSeq::Exact(t.table_info.ident.seq),
+                new_table_meta: t.table_info.meta.clone(),
+            })
+            .collect();
+        UpdateMultiTableMetaReq { update_table_metas }
+    }
+}
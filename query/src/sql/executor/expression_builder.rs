@@ -133,6 +133,7 @@ where ExpressionBuilder<T>: FiledNameFormat
                 })
             }
             Scalar::SubqueryExpr(_) => Err(ErrorCode::UnImplement("Unsupported subquery expr")),
+            Scalar::WindowFunction(_) => Err(ErrorCode::UnImplement("Unsupported window function")),
         }
     }
 
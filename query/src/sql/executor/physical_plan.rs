@@ -24,13 +24,17 @@ use common_datavalues::NullableType;
 use common_datavalues::ToDataType;
 use common_datavalues::Vu8;
 use common_exception::Result;
+use common_functions::window::WindowFrame;
+use common_meta_types::MetaId;
 use common_planners::ReadDataSourcePlan;
 use common_planners::StageKind;
 
 use super::physical_scalar::PhysicalScalar;
 use super::AggregateFunctionDesc;
 use super::SortDesc;
+use super::WindowFunctionDesc;
 use crate::sql::plans::JoinType;
+use crate::sql::plans::SetOperationType;
 use crate::sql::IndexType;
 
 pub type ColumnID = String;
@@ -175,6 +179,27 @@ impl Limit {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct Window {
+    pub input: Box<PhysicalPlan>,
+    pub func: WindowFunctionDesc,
+    pub partition_by: Vec<ColumnID>,
+    pub order_by: Vec<SortDesc>,
+    pub window_frame: WindowFrame,
+}
+
+impl Window {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        let input_schema = self.input.output_schema()?;
+        let mut fields = input_schema.fields().clone();
+        fields.push(DataField::new(
+            self.func.column_id.as_str(),
+            self.func.sig.return_type.clone(),
+        ));
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct HashJoin {
     pub build: Box<PhysicalPlan>,
@@ -231,6 +256,92 @@ impl HashJoin {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct NestedLoopJoin {
+    pub left: Box<PhysicalPlan>,
+    /// The inner side, which is kept in memory
+    pub right: Box<PhysicalPlan>,
+    pub conditions: Vec<PhysicalScalar>,
+    pub join_type: JoinType,
+}
+
+impl NestedLoopJoin {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        let mut fields = self.left.output_schema()?.fields().clone();
+        fields.extend(self.right.output_schema()?.fields().clone());
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SortMergeJoin {
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    pub left_keys: Vec<ColumnID>,
+    pub right_keys: Vec<ColumnID>,
+    pub other_conditions: Vec<PhysicalScalar>,
+    pub join_type: JoinType,
+}
+
+impl SortMergeJoin {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        let mut fields = self.left.output_schema()?.fields().clone();
+        fields.extend(self.right.output_schema()?.fields().clone());
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct UnionAll {
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    pub left_columns: Vec<ColumnID>,
+    pub right_columns: Vec<ColumnID>,
+    /// Output schema, the i-th field is the union of the i-th columns of both sides
+    pub schema: DataSchemaRef,
+}
+
+impl UnionAll {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SetOperation {
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    pub op_type: SetOperationType,
+    pub left_columns: Vec<ColumnID>,
+    pub right_columns: Vec<ColumnID>,
+}
+
+impl SetOperation {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        self.left.output_schema()
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecursiveCte {
+    /// The non-recursive term
+    pub left: Box<PhysicalPlan>,
+    /// The recursive term, which reads the rows of the last iteration from the working table
+    pub right: Box<PhysicalPlan>,
+    pub cte_name: String,
+    pub working_table: MetaId,
+    pub left_columns: Vec<ColumnID>,
+    pub right_columns: Vec<ColumnID>,
+    /// Output schema, the i-th field takes the i-th columns of both terms
+    pub schema: DataSchemaRef,
+}
+
+impl RecursiveCte {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Exchange {
     pub input: Box<PhysicalPlan>,
@@ -291,7 +402,13 @@ pub enum PhysicalPlan {
     AggregateFinal(AggregateFinal),
     Sort(Sort),
     Limit(Limit),
+    Window(Window),
     HashJoin(HashJoin),
+    NestedLoopJoin(NestedLoopJoin),
+    SortMergeJoin(SortMergeJoin),
+    UnionAll(UnionAll),
+    SetOperation(SetOperation),
+    RecursiveCte(RecursiveCte),
     Exchange(Exchange),
 
     /// Synthesized by fragmenter
@@ -318,7 +435,13 @@ impl PhysicalPlan {
             PhysicalPlan::AggregateFinal(plan) => plan.output_schema(),
             PhysicalPlan::Sort(plan) => plan.output_schema(),
             PhysicalPlan::Limit(plan) => plan.output_schema(),
+            PhysicalPlan::Window(plan) => plan.output_schema(),
             PhysicalPlan::HashJoin(plan) => plan.output_schema(),
+            PhysicalPlan::NestedLoopJoin(plan) => plan.output_schema(),
+            PhysicalPlan::SortMergeJoin(plan) => plan.output_schema(),
+            PhysicalPlan::UnionAll(plan) => plan.output_schema(),
+            PhysicalPlan::SetOperation(plan) => plan.output_schema(),
+            PhysicalPlan::RecursiveCte(plan) => plan.output_schema(),
             PhysicalPlan::Exchange(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSource(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSink(plan) => plan.output_schema(),
@@ -335,9 +458,25 @@ impl PhysicalPlan {
             PhysicalPlan::AggregateFinal(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::Sort(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::Limit(plan) => Box::new(std::iter::once(plan.input.as_ref())),
+            PhysicalPlan::Window(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::HashJoin(plan) => Box::new(
                 std::iter::once(plan.probe.as_ref()).chain(std::iter::once(plan.build.as_ref())),
             ),
+            PhysicalPlan::NestedLoopJoin(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
+            PhysicalPlan::SortMergeJoin(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
+            PhysicalPlan::UnionAll(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
+            PhysicalPlan::SetOperation(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
+            PhysicalPlan::RecursiveCte(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
             PhysicalPlan::Exchange(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::ExchangeSource(_) => Box::new(std::iter::empty()),
             PhysicalPlan::ExchangeSink(plan) => Box::new(std::iter::once(plan.input.as_ref())),
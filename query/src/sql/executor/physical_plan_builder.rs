@@ -14,6 +14,8 @@
 
 use std::collections::BTreeMap;
 
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Extras;
@@ -26,9 +28,15 @@ use super::Exchange as PhysicalExchange;
 use super::Filter;
 use super::HashJoin;
 use super::Limit;
+use super::NestedLoopJoin;
 use super::Project;
+use super::RecursiveCte;
+use super::SetOperation;
 use super::Sort;
+use super::SortMergeJoin;
 use super::TableScan;
+use super::UnionAll;
+use super::Window;
 use crate::sql::executor::util::check_physical;
 use crate::sql::executor::AggregateFunctionDesc;
 use crate::sql::executor::AggregateFunctionSignature;
@@ -38,6 +46,8 @@ use crate::sql::executor::ExpressionBuilderWithoutRenaming;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalScalar;
 use crate::sql::executor::SortDesc;
+use crate::sql::executor::WindowFunctionDesc;
+use crate::sql::executor::WindowFunctionSignature;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::AggregateMode;
 use crate::sql::plans::Exchange;
@@ -87,7 +97,7 @@ impl PhysicalPlanBuilder {
 
                 Ok(PhysicalPlan::TableScan(TableScan {
                     name_mapping,
-                    source: Box::new(metadata.table(scan.table_index).source.clone()),
+                    source: Box::new(source),
                 }))
             }
             RelOperator::PhysicalHashJoin(join) => {
@@ -125,6 +135,50 @@ impl PhysicalPlanBuilder {
                     from_correlated_subquery: join.from_correlated_subquery,
                 }))
             }
+            RelOperator::PhysicalNestedLoopJoin(join) => {
+                let left_side = self.build(s_expr.child(0)?)?;
+                let right_side = self.build(s_expr.child(1)?)?;
+                Ok(PhysicalPlan::NestedLoopJoin(NestedLoopJoin {
+                    left: Box::new(left_side),
+                    right: Box::new(right_side),
+                    conditions: join
+                        .conditions
+                        .iter()
+                        .map(|v| {
+                            let mut builder = PhysicalScalarBuilder;
+                            builder.build(v)
+                        })
+                        .collect::<Result<_>>()?,
+                    join_type: join.join_type.clone(),
+                }))
+            }
+            RelOperator::PhysicalSortMergeJoin(join) => {
+                let left_side = self.build(s_expr.child(0)?)?;
+                let right_side = self.build(s_expr.child(1)?)?;
+                Ok(PhysicalPlan::SortMergeJoin(SortMergeJoin {
+                    left: Box::new(left_side),
+                    right: Box::new(right_side),
+                    left_keys: join
+                        .left_keys
+                        .iter()
+                        .map(Self::build_join_key)
+                        .collect::<Result<_>>()?,
+                    right_keys: join
+                        .right_keys
+                        .iter()
+                        .map(Self::build_join_key)
+                        .collect::<Result<_>>()?,
+                    other_conditions: join
+                        .other_conditions
+                        .iter()
+                        .map(|v| {
+                            let mut builder = PhysicalScalarBuilder;
+                            builder.build(v)
+                        })
+                        .collect::<Result<_>>()?,
+                    join_type: join.join_type.clone(),
+                }))
+            }
             RelOperator::Project(project) => {
                 let input = self.build(s_expr.child(0)?)?;
                 let input_schema = input.output_schema()?;
@@ -250,6 +304,117 @@ impl PhysicalPlanBuilder {
                 limit: limit.limit,
                 offset: limit.offset,
             })),
+            RelOperator::Window(window) => {
+                let func = &window.function;
+                let column_id = |scalar: &Scalar| {
+                    if let Scalar::BoundColumnRef(col) = scalar {
+                        Ok(col.column.index.to_string())
+                    } else {
+                        Err(ErrorCode::LogicalError(
+                            "Window function input must be a BoundColumnRef".to_string(),
+                        ))
+                    }
+                };
+                Ok(PhysicalPlan::Window(Window {
+                    input: Box::new(self.build(s_expr.child(0)?)?),
+                    func: WindowFunctionDesc {
+                        sig: WindowFunctionSignature {
+                            name: func.func_name.clone(),
+                            args: func.args.iter().map(|s| s.data_type()).collect(),
+                            params: func.params.clone(),
+                            return_type: *func.return_type.clone(),
+                        },
+                        column_id: window.index.to_string(),
+                        args: func.args.iter().map(column_id).collect::<Result<_>>()?,
+                    },
+                    partition_by: func
+                        .partition_by
+                        .iter()
+                        .map(column_id)
+                        .collect::<Result<_>>()?,
+                    order_by: func
+                        .order_by
+                        .iter()
+                        .map(|item| {
+                            Ok(SortDesc {
+                                asc: item.asc,
+                                nulls_first: item.nulls_first,
+                                order_by: column_id(&item.expr)?,
+                            })
+                        })
+                        .collect::<Result<_>>()?,
+                    window_frame: func.frame,
+                }))
+            }
+            RelOperator::UnionAll(union_all) => {
+                let fields = union_all
+                    .output_columns
+                    .iter()
+                    .zip(union_all.left_columns.iter())
+                    .map(|(index, column)| {
+                        DataField::new(index.to_string().as_str(), *column.data_type.clone())
+                    })
+                    .collect();
+                Ok(PhysicalPlan::UnionAll(UnionAll {
+                    left: Box::new(self.build(s_expr.child(0)?)?),
+                    right: Box::new(self.build(s_expr.child(1)?)?),
+                    left_columns: union_all
+                        .left_columns
+                        .iter()
+                        .map(|column| column.index.to_string())
+                        .collect(),
+                    right_columns: union_all
+                        .right_columns
+                        .iter()
+                        .map(|column| column.index.to_string())
+                        .collect(),
+                    schema: DataSchemaRefExt::create(fields),
+                }))
+            }
+            RelOperator::RecursiveCte(recursive_cte) => {
+                let fields = recursive_cte
+                    .output_columns
+                    .iter()
+                    .zip(recursive_cte.left_columns.iter())
+                    .map(|(index, column)| {
+                        DataField::new(index.to_string().as_str(), *column.data_type.clone())
+                    })
+                    .collect();
+                Ok(PhysicalPlan::RecursiveCte(RecursiveCte {
+                    left: Box::new(self.build(s_expr.child(0)?)?),
+                    right: Box::new(self.build(s_expr.child(1)?)?),
+                    cte_name: recursive_cte.cte_name.clone(),
+                    working_table: recursive_cte.working_table,
+                    left_columns: recursive_cte
+                        .left_columns
+                        .iter()
+                        .map(|column| column.index.to_string())
+                        .collect(),
+                    right_columns: recursive_cte
+                        .right_columns
+                        .iter()
+                        .map(|column| column.index.to_string())
+                        .collect(),
+                    schema: DataSchemaRefExt::create(fields),
+                }))
+            }
+            RelOperator::SetOperation(set_operation) => {
+                Ok(PhysicalPlan::SetOperation(SetOperation {
+                    left: Box::new(self.build(s_expr.child(0)?)?),
+                    right: Box::new(self.build(s_expr.child(1)?)?),
+                    op_type: set_operation.op_type.clone(),
+                    left_columns: set_operation
+                        .left_columns
+                        .iter()
+                        .map(|column| column.index.to_string())
+                        .collect(),
+                    right_columns: set_operation
+                        .right_columns
+                        .iter()
+                        .map(|column| column.index.to_string())
+                        .collect(),
+                }))
+            }
             RelOperator::Exchange(exchange) => {
                 let mut keys = vec![];
                 let kind = match exchange {
@@ -276,6 +441,16 @@ impl PhysicalPlanBuilder {
             ))),
         }
     }
+
+    fn build_join_key(key: &Scalar) -> Result<ColumnID> {
+        match key {
+            Scalar::BoundColumnRef(column_ref) => Ok(column_ref.column.index.to_string()),
+            _ => Err(ErrorCode::LogicalError(format!(
+                "Sort-merge join key must be a column reference, but got {:?}",
+                key
+            ))),
+        }
+    }
 }
 
 pub struct PhysicalScalarBuilder;
@@ -27,11 +27,17 @@ use crate::sql::executor::ExchangeSource;
 use crate::sql::executor::Filter;
 use crate::sql::executor::HashJoin;
 use crate::sql::executor::Limit;
+use crate::sql::executor::NestedLoopJoin;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalScalar;
 use crate::sql::executor::Project;
+use crate::sql::executor::RecursiveCte;
+use crate::sql::executor::SetOperation;
 use crate::sql::executor::Sort;
+use crate::sql::executor::SortMergeJoin;
 use crate::sql::executor::TableScan;
+use crate::sql::executor::UnionAll;
+use crate::sql::executor::Window;
 use crate::sql::plans::JoinType;
 
 impl PhysicalPlan {
@@ -58,7 +64,13 @@ impl<'a> Display for PhysicalPlanIndentFormatDisplay<'a> {
             PhysicalPlan::AggregateFinal(aggregate) => write!(f, "{}", aggregate)?,
             PhysicalPlan::Sort(sort) => write!(f, "{}", sort)?,
             PhysicalPlan::Limit(limit) => write!(f, "{}", limit)?,
+            PhysicalPlan::Window(window) => write!(f, "{}", window)?,
             PhysicalPlan::HashJoin(join) => write!(f, "{}", join)?,
+            PhysicalPlan::NestedLoopJoin(join) => write!(f, "{}", join)?,
+            PhysicalPlan::SortMergeJoin(join) => write!(f, "{}", join)?,
+            PhysicalPlan::UnionAll(union_all) => write!(f, "{}", union_all)?,
+            PhysicalPlan::SetOperation(set_operation) => write!(f, "{}", set_operation)?,
+            PhysicalPlan::RecursiveCte(recursive_cte) => write!(f, "{}", recursive_cte)?,
             PhysicalPlan::Exchange(exchange) => write!(f, "{}", exchange)?,
             PhysicalPlan::ExchangeSource(source) => write!(f, "{}", source)?,
             PhysicalPlan::ExchangeSink(sink) => write!(f, "{}", sink)?,
@@ -148,6 +160,42 @@ impl Display for Sort {
     }
 }
 
+impl Display for Window {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let partition_by = self
+            .partition_by
+            .iter()
+            .map(String::to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let order_by = self
+            .order_by
+            .iter()
+            .map(|item| {
+                format!(
+                    "{} {}",
+                    item.order_by,
+                    if item.asc { "ASC" } else { "DESC" }
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        write!(
+            f,
+            "Window: function: {}({}), partition by: [{}], order by: [{}], frame: {} BETWEEN {} AND {}",
+            self.func.sig.name,
+            self.func.args.join(", "),
+            partition_by,
+            order_by,
+            self.window_frame.units,
+            self.window_frame.start_bound,
+            self.window_frame.end_bound
+        )
+    }
+}
+
 impl Display for EvalScalar {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let scalars = self
@@ -273,6 +321,78 @@ impl Display for HashJoin {
     }
 }
 
+impl Display for NestedLoopJoin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|scalar| format!("{}", scalar))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        write!(
+            f,
+            "NestedLoopJoin: {}, conditions: [{}]",
+            &self.join_type, conditions
+        )
+    }
+}
+
+impl Display for SortMergeJoin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let join_filters = self
+            .other_conditions
+            .iter()
+            .map(|scalar| format!("{}", scalar))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        write!(
+            f,
+            "SortMergeJoin: {}, left keys: [{}], right keys: [{}], join filters: [{}]",
+            &self.join_type,
+            self.left_keys.join(", "),
+            self.right_keys.join(", "),
+            join_filters,
+        )
+    }
+}
+
+impl Display for UnionAll {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "UnionAll: left columns: [{}], right columns: [{}]",
+            self.left_columns.join(", "),
+            self.right_columns.join(", "),
+        )
+    }
+}
+
+impl Display for RecursiveCte {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "RecursiveCte: {}, left columns: [{}], right columns: [{}]",
+            &self.cte_name,
+            self.left_columns.join(", "),
+            self.right_columns.join(", "),
+        )
+    }
+}
+
+impl Display for SetOperation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SetOperation: {}, left columns: [{}], right columns: [{}]",
+            &self.op_type,
+            self.left_columns.join(", "),
+            self.right_columns.join(", "),
+        )
+    }
+}
+
 impl Display for Exchange {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let keys = self
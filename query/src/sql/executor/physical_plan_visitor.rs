@@ -23,10 +23,16 @@ use super::ExchangeSource;
 use super::Filter;
 use super::HashJoin;
 use super::Limit;
+use super::NestedLoopJoin;
 use super::PhysicalPlan;
 use super::Project;
+use super::RecursiveCte;
+use super::SetOperation;
 use super::Sort;
+use super::SortMergeJoin;
 use super::TableScan;
+use super::UnionAll;
+use super::Window;
 
 pub trait PhysicalPlanReplacer {
     fn replace(&mut self, plan: &PhysicalPlan) -> Result<PhysicalPlan> {
@@ -39,7 +45,13 @@ pub trait PhysicalPlanReplacer {
             PhysicalPlan::AggregateFinal(plan) => self.replace_aggregate_final(plan),
             PhysicalPlan::Sort(plan) => self.replace_sort(plan),
             PhysicalPlan::Limit(plan) => self.replace_limit(plan),
+            PhysicalPlan::Window(plan) => self.replace_window(plan),
             PhysicalPlan::HashJoin(plan) => self.replace_hash_join(plan),
+            PhysicalPlan::NestedLoopJoin(plan) => self.replace_nested_loop_join(plan),
+            PhysicalPlan::SortMergeJoin(plan) => self.replace_sort_merge_join(plan),
+            PhysicalPlan::UnionAll(plan) => self.replace_union_all(plan),
+            PhysicalPlan::SetOperation(plan) => self.replace_set_operation(plan),
+            PhysicalPlan::RecursiveCte(plan) => self.replace_recursive_cte(plan),
             PhysicalPlan::Exchange(plan) => self.replace_exchange(plan),
             PhysicalPlan::ExchangeSource(plan) => self.replace_exchange_source(plan),
             PhysicalPlan::ExchangeSink(plan) => self.replace_exchange_sink(plan),
@@ -114,6 +126,73 @@ pub trait PhysicalPlanReplacer {
         }))
     }
 
+    fn replace_nested_loop_join(&mut self, plan: &NestedLoopJoin) -> Result<PhysicalPlan> {
+        let left = self.replace(&plan.left)?;
+        let right = self.replace(&plan.right)?;
+
+        Ok(PhysicalPlan::NestedLoopJoin(NestedLoopJoin {
+            left: Box::new(left),
+            right: Box::new(right),
+            conditions: plan.conditions.clone(),
+            join_type: plan.join_type.clone(),
+        }))
+    }
+
+    fn replace_sort_merge_join(&mut self, plan: &SortMergeJoin) -> Result<PhysicalPlan> {
+        let left = self.replace(&plan.left)?;
+        let right = self.replace(&plan.right)?;
+
+        Ok(PhysicalPlan::SortMergeJoin(SortMergeJoin {
+            left: Box::new(left),
+            right: Box::new(right),
+            left_keys: plan.left_keys.clone(),
+            right_keys: plan.right_keys.clone(),
+            other_conditions: plan.other_conditions.clone(),
+            join_type: plan.join_type.clone(),
+        }))
+    }
+
+    fn replace_union_all(&mut self, plan: &UnionAll) -> Result<PhysicalPlan> {
+        let left = self.replace(&plan.left)?;
+        let right = self.replace(&plan.right)?;
+
+        Ok(PhysicalPlan::UnionAll(UnionAll {
+            left: Box::new(left),
+            right: Box::new(right),
+            left_columns: plan.left_columns.clone(),
+            right_columns: plan.right_columns.clone(),
+            schema: plan.schema.clone(),
+        }))
+    }
+
+    fn replace_set_operation(&mut self, plan: &SetOperation) -> Result<PhysicalPlan> {
+        let left = self.replace(&plan.left)?;
+        let right = self.replace(&plan.right)?;
+
+        Ok(PhysicalPlan::SetOperation(SetOperation {
+            left: Box::new(left),
+            right: Box::new(right),
+            op_type: plan.op_type.clone(),
+            left_columns: plan.left_columns.clone(),
+            right_columns: plan.right_columns.clone(),
+        }))
+    }
+
+    fn replace_recursive_cte(&mut self, plan: &RecursiveCte) -> Result<PhysicalPlan> {
+        let left = self.replace(&plan.left)?;
+        let right = self.replace(&plan.right)?;
+
+        Ok(PhysicalPlan::RecursiveCte(RecursiveCte {
+            left: Box::new(left),
+            right: Box::new(right),
+            cte_name: plan.cte_name.clone(),
+            working_table: plan.working_table,
+            left_columns: plan.left_columns.clone(),
+            right_columns: plan.right_columns.clone(),
+            schema: plan.schema.clone(),
+        }))
+    }
+
     fn replace_sort(&mut self, plan: &Sort) -> Result<PhysicalPlan> {
         let input = self.replace(&plan.input)?;
 
@@ -133,6 +212,18 @@ pub trait PhysicalPlanReplacer {
         }))
     }
 
+    fn replace_window(&mut self, plan: &Window) -> Result<PhysicalPlan> {
+        let input = self.replace(&plan.input)?;
+
+        Ok(PhysicalPlan::Window(Window {
+            input: Box::new(input),
+            func: plan.func.clone(),
+            partition_by: plan.partition_by.clone(),
+            order_by: plan.order_by.clone(),
+            window_frame: plan.window_frame,
+        }))
+    }
+
     fn replace_exchange(&mut self, plan: &Exchange) -> Result<PhysicalPlan> {
         let input = self.replace(&plan.input)?;
 
@@ -194,10 +285,33 @@ impl PhysicalPlan {
                 PhysicalPlan::Limit(plan) => {
                     Self::traverse(&plan.input, pre_visit, visit, post_visit);
                 }
+                PhysicalPlan::Window(plan) => {
+                    Self::traverse(&plan.input, pre_visit, visit, post_visit);
+                }
                 PhysicalPlan::HashJoin(plan) => {
                     Self::traverse(&plan.build, pre_visit, visit, post_visit);
                     Self::traverse(&plan.probe, pre_visit, visit, post_visit);
                 }
+                PhysicalPlan::NestedLoopJoin(plan) => {
+                    Self::traverse(&plan.left, pre_visit, visit, post_visit);
+                    Self::traverse(&plan.right, pre_visit, visit, post_visit);
+                }
+                PhysicalPlan::SortMergeJoin(plan) => {
+                    Self::traverse(&plan.left, pre_visit, visit, post_visit);
+                    Self::traverse(&plan.right, pre_visit, visit, post_visit);
+                }
+                PhysicalPlan::UnionAll(plan) => {
+                    Self::traverse(&plan.left, pre_visit, visit, post_visit);
+                    Self::traverse(&plan.right, pre_visit, visit, post_visit);
+                }
+                PhysicalPlan::SetOperation(plan) => {
+                    Self::traverse(&plan.left, pre_visit, visit, post_visit);
+                    Self::traverse(&plan.right, pre_visit, visit, post_visit);
+                }
+                PhysicalPlan::RecursiveCte(plan) => {
+                    Self::traverse(&plan.left, pre_visit, visit, post_visit);
+                    Self::traverse(&plan.right, pre_visit, visit, post_visit);
+                }
                 PhysicalPlan::Exchange(plan) => {
                     Self::traverse(&plan.input, pre_visit, visit, post_visit);
                 }
@@ -65,6 +65,21 @@ pub struct AggregateFunctionSignature {
     pub return_type: DataTypeImpl,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowFunctionDesc {
+    pub sig: WindowFunctionSignature,
+    pub column_id: ColumnID,
+    pub args: Vec<ColumnID>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WindowFunctionSignature {
+    pub name: String,
+    pub args: Vec<DataTypeImpl>,
+    pub params: Vec<DataValue>,
+    pub return_type: DataTypeImpl,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct SortDesc {
     pub asc: bool,
@@ -14,7 +14,10 @@
 
 use std::sync::Arc;
 
+use async_channel::Sender;
+use common_datablocks::DataBlock;
 use common_datablocks::SortColumnDescription;
+use common_datavalues::remove_nullable;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
@@ -32,33 +35,61 @@ use super::ExchangeSource;
 use super::Filter;
 use super::HashJoin;
 use super::Limit;
+use super::NestedLoopJoin;
 use super::Project;
+use super::RecursiveCte;
+use super::SetOperation;
 use super::Sort;
+use super::SortMergeJoin;
 use super::TableScan;
+use super::UnionAll;
+use super::Window;
 use crate::evaluator::EvalNode;
 use crate::evaluator::Evaluator;
 use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
 use crate::pipelines::processors::transforms::ExpressionTransformV2;
 use crate::pipelines::processors::transforms::HashJoinDesc;
 use crate::pipelines::processors::transforms::TransformFilterV2;
 use crate::pipelines::processors::transforms::TransformMarkJoin;
 use crate::pipelines::processors::transforms::TransformProject;
 use crate::pipelines::processors::transforms::TransformRename;
+use crate::pipelines::processors::transforms::TransformSetOperation;
 use crate::pipelines::processors::AggregatorParams;
 use crate::pipelines::processors::AggregatorTransformParams;
+use crate::pipelines::processors::AsyncSinker;
+use crate::pipelines::processors::HashJoinState;
 use crate::pipelines::processors::JoinHashTable;
 use crate::pipelines::processors::MarkJoinCompactor;
+use crate::pipelines::processors::NestedLoopJoinState;
+use crate::pipelines::processors::ProbeRuntimeFilter;
+use crate::pipelines::processors::RecursiveCteSource;
+use crate::pipelines::processors::RuntimeFilter;
+use crate::pipelines::processors::SetOperationState;
 use crate::pipelines::processors::SinkBuildHashTable;
+use crate::pipelines::processors::SinkNestedLoopJoinBuild;
+use crate::pipelines::processors::SinkSetOperationBuild;
+use crate::pipelines::processors::SinkSortMergeJoinBuild;
 use crate::pipelines::processors::Sinker;
-use crate::pipelines::processors::SortMergeCompactor;
+use crate::pipelines::processors::SortMergeJoinState;
 use crate::pipelines::processors::TransformAggregator;
 use crate::pipelines::processors::TransformHashJoinProbe;
 use crate::pipelines::processors::TransformLimit;
-use crate::pipelines::processors::TransformSortMerge;
+use crate::pipelines::processors::TransformMergeBlock;
+use crate::pipelines::processors::TransformNestedLoopJoin;
+use crate::pipelines::processors::TransformSortExternal;
+use crate::pipelines::processors::TransformSortMergeJoin;
 use crate::pipelines::processors::TransformSortPartial;
+use crate::pipelines::processors::TransformWindow;
+use crate::pipelines::processors::TransformWindowShuffle;
+use crate::pipelines::processors::UnionReceiveSink;
+use crate::pipelines::processors::WindowEvaluator;
+use crate::pipelines::Pipe;
 use crate::pipelines::Pipeline;
 use crate::pipelines::PipelineBuildResult;
 use crate::pipelines::SinkPipeBuilder;
+use crate::pipelines::SourcePipeBuilder;
 use crate::sessions::QueryContext;
 use crate::sessions::TableContext;
 use crate::sql::executor::physical_plan::ColumnID;
@@ -67,11 +98,15 @@ use crate::sql::executor::AggregateFunctionDesc;
 use crate::sql::executor::PhysicalScalar;
 use crate::sql::plans::JoinType;
 use crate::sql::ColumnBinding;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::SupportedType;
 
 pub struct PipelineBuilder {
     ctx: Arc<QueryContext>,
     main_pipeline: Pipeline,
     pub pipelines: Vec<Pipeline>,
+    /// The runtime filters of the hash joins, to be pushed down to the scans of the probe keys
+    runtime_filters: Vec<(ColumnID, ProbeRuntimeFilter)>,
 }
 
 impl PipelineBuilder {
@@ -80,6 +115,7 @@ impl PipelineBuilder {
             ctx,
             pipelines: vec![],
             main_pipeline: Pipeline::create(),
+            runtime_filters: vec![],
         }
     }
 
@@ -110,7 +146,13 @@ impl PipelineBuilder {
             PhysicalPlan::AggregateFinal(aggregate) => self.build_aggregate_final(aggregate),
             PhysicalPlan::Sort(sort) => self.build_sort(sort),
             PhysicalPlan::Limit(limit) => self.build_limit(limit),
+            PhysicalPlan::Window(window) => self.build_window(window),
             PhysicalPlan::HashJoin(join) => self.build_join(join),
+            PhysicalPlan::NestedLoopJoin(join) => self.build_nested_loop_join(join),
+            PhysicalPlan::SortMergeJoin(join) => self.build_sort_merge_join(join),
+            PhysicalPlan::UnionAll(union_all) => self.build_union_all(union_all),
+            PhysicalPlan::SetOperation(set_operation) => self.build_set_operation(set_operation),
+            PhysicalPlan::RecursiveCte(recursive_cte) => self.build_recursive_cte(recursive_cte),
             PhysicalPlan::ExchangeSink(sink) => self.build_exchange_sink(sink),
             PhysicalPlan::ExchangeSource(source) => self.build_exchange_source(source),
             PhysicalPlan::Exchange(_) => Err(ErrorCode::LogicalError(
@@ -149,7 +191,7 @@ impl PipelineBuilder {
             let input_port = InputPort::create();
             sink_pipeline_builder.add_sink(
                 input_port.clone(),
-                Sinker::<SinkBuildHashTable>::create(
+                AsyncSinker::<SinkBuildHashTable>::create(
                     input_port,
                     SinkBuildHashTable::try_create(join_state.clone())?,
                 ),
@@ -166,6 +208,237 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    fn build_nested_loop_join(&mut self, join: &NestedLoopJoin) -> Result<()> {
+        let state = NestedLoopJoinState::try_create(self.ctx.clone(), join)?;
+        self.expand_nested_loop_join_right_pipeline(&join.right, state.clone())?;
+
+        self.build_pipeline(&join.left)?;
+        self.main_pipeline.add_transform(|input, output| {
+            TransformNestedLoopJoin::try_create(input, output, state.clone())
+        })
+    }
+
+    fn expand_nested_loop_join_right_pipeline(
+        &mut self,
+        right: &PhysicalPlan,
+        state: Arc<NestedLoopJoinState>,
+    ) -> Result<()> {
+        let right_side_context = QueryContext::create_from(self.ctx.clone());
+        let right_side_builder = PipelineBuilder::create(right_side_context);
+        let mut right_res = right_side_builder.finalize(right)?;
+
+        assert!(right_res.main_pipeline.is_pulling_pipeline()?);
+        let mut sink_pipeline_builder = SinkPipeBuilder::create();
+        for _index in 0..right_res.main_pipeline.output_len() {
+            let input_port = InputPort::create();
+            sink_pipeline_builder.add_sink(
+                input_port.clone(),
+                Sinker::<SinkNestedLoopJoinBuild>::create(
+                    input_port,
+                    SinkNestedLoopJoinBuild::create(state.clone()),
+                ),
+            );
+        }
+
+        right_res
+            .main_pipeline
+            .add_pipe(sink_pipeline_builder.finalize());
+
+        self.pipelines.push(right_res.main_pipeline);
+        self.pipelines
+            .extend(right_res.sources_pipelines.into_iter());
+        Ok(())
+    }
+
+    fn build_sort_merge_join(&mut self, join: &SortMergeJoin) -> Result<()> {
+        let state = SortMergeJoinState::try_create(self.ctx.clone(), join)?;
+        self.expand_sort_merge_join_right_pipeline(&join.right, state.clone())?;
+
+        self.build_pipeline(&join.left)?;
+        self.main_pipeline.add_transform(|input, output| {
+            TransformSortMergeJoin::try_create(input, output, state.clone())
+        })
+    }
+
+    fn expand_sort_merge_join_right_pipeline(
+        &mut self,
+        right: &PhysicalPlan,
+        state: Arc<SortMergeJoinState>,
+    ) -> Result<()> {
+        let right_side_context = QueryContext::create_from(self.ctx.clone());
+        let right_side_builder = PipelineBuilder::create(right_side_context);
+        let mut right_res = right_side_builder.finalize(right)?;
+
+        assert!(right_res.main_pipeline.is_pulling_pipeline()?);
+        let mut sink_pipeline_builder = SinkPipeBuilder::create();
+        for _index in 0..right_res.main_pipeline.output_len() {
+            let input_port = InputPort::create();
+            sink_pipeline_builder.add_sink(
+                input_port.clone(),
+                Sinker::<SinkSortMergeJoinBuild>::create(
+                    input_port,
+                    SinkSortMergeJoinBuild::create(state.clone()),
+                ),
+            );
+        }
+
+        right_res
+            .main_pipeline
+            .add_pipe(sink_pipeline_builder.finalize());
+
+        self.pipelines.push(right_res.main_pipeline);
+        self.pipelines
+            .extend(right_res.sources_pipelines.into_iter());
+        Ok(())
+    }
+
+    fn build_union_all(&mut self, union_all: &UnionAll) -> Result<()> {
+        self.build_pipeline(&union_all.left)?;
+        Self::project_columns(
+            &mut self.main_pipeline,
+            union_all.left.output_schema()?,
+            &union_all.left_columns,
+            union_all.schema.clone(),
+        )?;
+
+        let (tx, rx) = async_channel::bounded(self.main_pipeline.output_len());
+        self.expand_union_all_right_pipeline(union_all, tx)?;
+
+        self.main_pipeline.add_transform(|input, output| {
+            TransformMergeBlock::try_create(input, output, rx.clone())
+        })
+    }
+
+    fn expand_union_all_right_pipeline(
+        &mut self,
+        union_all: &UnionAll,
+        tx: Sender<DataBlock>,
+    ) -> Result<()> {
+        let right_context = QueryContext::create_from(self.ctx.clone());
+        let right_builder = PipelineBuilder::create(right_context);
+        let mut right_res = right_builder.finalize(&union_all.right)?;
+
+        assert!(right_res.main_pipeline.is_pulling_pipeline()?);
+        Self::project_columns(
+            &mut right_res.main_pipeline,
+            union_all.right.output_schema()?,
+            &union_all.right_columns,
+            union_all.schema.clone(),
+        )?;
+
+        let mut sink_pipeline_builder = SinkPipeBuilder::create();
+        for _index in 0..right_res.main_pipeline.output_len() {
+            let input_port = InputPort::create();
+            sink_pipeline_builder.add_sink(
+                input_port.clone(),
+                UnionReceiveSink::create(tx.clone(), input_port),
+            );
+        }
+
+        right_res
+            .main_pipeline
+            .add_pipe(sink_pipeline_builder.finalize());
+
+        self.pipelines.push(right_res.main_pipeline);
+        self.pipelines
+            .extend(right_res.sources_pipelines.into_iter());
+        Ok(())
+    }
+
+    fn build_recursive_cte(&mut self, recursive_cte: &RecursiveCte) -> Result<()> {
+        // The iterations depend on each other, so evaluate them with a single source.
+        let output = OutputPort::create();
+        let mut source_builder = SourcePipeBuilder::create();
+        source_builder.add_source(
+            output.clone(),
+            RecursiveCteSource::try_create(self.ctx.clone(), output, recursive_cte.clone())?,
+        );
+        self.main_pipeline.add_pipe(source_builder.finalize());
+        Ok(())
+    }
+
+    fn build_set_operation(&mut self, set_operation: &SetOperation) -> Result<()> {
+        let left_schema = set_operation.left.output_schema()?;
+        let probe_keys = set_operation
+            .left_columns
+            .iter()
+            .map(|id| left_schema.index_of(id.as_str()))
+            .collect::<Result<Vec<usize>>>()?;
+        let state = SetOperationState::create(set_operation.op_type.clone(), probe_keys);
+        self.expand_set_operation_build_pipeline(set_operation, state.clone())?;
+
+        self.build_pipeline(&set_operation.left)?;
+        self.main_pipeline.add_transform(|input, output| {
+            TransformSetOperation::try_create(input, output, state.clone())
+        })
+    }
+
+    fn expand_set_operation_build_pipeline(
+        &mut self,
+        set_operation: &SetOperation,
+        state: Arc<SetOperationState>,
+    ) -> Result<()> {
+        let build_side_context = QueryContext::create_from(self.ctx.clone());
+        let build_side_builder = PipelineBuilder::create(build_side_context);
+        let mut build_res = build_side_builder.finalize(&set_operation.right)?;
+
+        assert!(build_res.main_pipeline.is_pulling_pipeline()?);
+        let right_schema = set_operation.right.output_schema()?;
+        let projections = set_operation
+            .right_columns
+            .iter()
+            .map(|id| right_schema.index_of(id.as_str()))
+            .collect::<Result<Vec<usize>>>()?;
+        build_res.main_pipeline.add_transform(|input, output| {
+            Ok(TransformProject::create(input, output, projections.clone()))
+        })?;
+
+        let mut sink_pipeline_builder = SinkPipeBuilder::create();
+        for _index in 0..build_res.main_pipeline.output_len() {
+            let input_port = InputPort::create();
+            sink_pipeline_builder.add_sink(
+                input_port.clone(),
+                Sinker::<SinkSetOperationBuild>::create(
+                    input_port,
+                    SinkSetOperationBuild::create(state.clone()),
+                ),
+            );
+        }
+
+        build_res
+            .main_pipeline
+            .add_pipe(sink_pipeline_builder.finalize());
+
+        self.pipelines.push(build_res.main_pipeline);
+        self.pipelines
+            .extend(build_res.sources_pipelines.into_iter());
+        Ok(())
+    }
+
+    /// Project the given columns of an input of `UNION ALL` in order, and rename them
+    /// to the output schema.
+    fn project_columns(
+        pipeline: &mut Pipeline,
+        input_schema: DataSchemaRef,
+        columns: &[ColumnID],
+        output_schema: DataSchemaRef,
+    ) -> Result<()> {
+        let projections = columns
+            .iter()
+            .map(|id| input_schema.index_of(id.as_str()))
+            .collect::<Result<Vec<usize>>>()?;
+        pipeline.add_transform(|input, output| {
+            Ok(TransformProject::create(input, output, projections.clone()))
+        })?;
+        pipeline.add_transform(|input, output| {
+            Ok(TransformRename::create(
+                input,
+                output,
+                output_schema.clone(),
+            ))
+        })
+    }
+
     pub fn render_result_set(
         input_schema: DataSchemaRef,
         result_columns: &[ColumnBinding],
@@ -201,7 +474,17 @@ impl PipelineBuilder {
     fn build_table_scan(&mut self, scan: &TableScan) -> Result<()> {
         let table = self.ctx.build_table_from_source_plan(&scan.source)?;
         self.ctx.try_set_partitions(scan.source.parts.clone())?;
-        table.read2(self.ctx.clone(), &scan.source, &mut self.main_pipeline)?;
+        let runtime_filters = self.take_runtime_filters(scan);
+        match FuseTable::try_from_table(table.as_ref()) {
+            Ok(fuse_table) if !runtime_filters.is_empty() => fuse_table
+                .do_read2_with_runtime_filters(
+                    self.ctx.clone(),
+                    &scan.source,
+                    &mut self.main_pipeline,
+                    runtime_filters,
+                )?,
+            _ => table.read2(self.ctx.clone(), &scan.source, &mut self.main_pipeline)?,
+        }
         let schema = scan.source.schema();
         let projections = scan
             .name_mapping
@@ -222,6 +505,20 @@ impl PipelineBuilder {
         })
     }
 
+    /// Takes the runtime filters on the columns of the scan, with the names of the columns.
+    fn take_runtime_filters(&mut self, scan: &TableScan) -> Vec<(String, ProbeRuntimeFilter)> {
+        let mut runtime_filters = vec![];
+        let mut pending = vec![];
+        for (column_id, runtime_filter) in std::mem::take(&mut self.runtime_filters) {
+            match scan.name_mapping.iter().find(|(_, id)| **id == column_id) {
+                Some((name, _)) => runtime_filters.push((name.clone(), runtime_filter)),
+                None => pending.push((column_id, runtime_filter)),
+            }
+        }
+        self.runtime_filters = pending;
+        runtime_filters
+    }
+
     fn build_filter(&mut self, filter: &Filter) -> Result<()> {
         self.build_pipeline(&filter.input)?;
 
@@ -398,12 +695,14 @@ impl PipelineBuilder {
             TransformSortPartial::try_create(input, output, None, sort_desc.clone())
         })?;
 
-        // Merge
+        // Merge, spill sorted runs if the blocks exceed max_bytes_before_external_sort
         self.main_pipeline.add_transform(|input, output| {
-            TransformSortMerge::try_create(
+            TransformSortExternal::try_create(
+                self.ctx.clone(),
                 input,
                 output,
-                SortMergeCompactor::new(None, sort_desc.clone()),
+                None,
+                sort_desc.clone(),
             )
         })?;
 
@@ -411,10 +710,94 @@ impl PipelineBuilder {
 
         // Concat merge in single thread
         self.main_pipeline.add_transform(|input, output| {
-            TransformSortMerge::try_create(
+            TransformSortExternal::try_create(
+                self.ctx.clone(),
                 input,
                 output,
-                SortMergeCompactor::new(None, sort_desc.clone()),
+                None,
+                sort_desc.clone(),
+            )
+        })
+    }
+
+    fn build_window(&mut self, window: &Window) -> Result<()> {
+        self.build_pipeline(&window.input)?;
+
+        // Sort the input by partition keys and order keys, so that a partition
+        // is consecutive rows and rows in a partition are ordered.
+        let sort_desc: Vec<SortColumnDescription> = window
+            .partition_by
+            .iter()
+            .map(|column_id| SortColumnDescription {
+                column_name: column_id.clone(),
+                asc: true,
+                nulls_first: false,
+            })
+            .chain(window.order_by.iter().map(|desc| SortColumnDescription {
+                column_name: desc.order_by.clone(),
+                asc: desc.asc,
+                nulls_first: desc.nulls_first,
+            }))
+            .collect();
+
+        // The input is already sorted if it's a window with the same partition keys and order keys.
+        let sorted = match window.input.as_ref() {
+            PhysicalPlan::Window(input) => {
+                input.partition_by == window.partition_by && input.order_by == window.order_by
+            }
+            _ => false,
+        };
+
+        if !sorted {
+            if window.partition_by.is_empty() {
+                // Without partition keys, the whole input is one partition.
+                self.main_pipeline.resize(1)?;
+            } else {
+                // Repartition by the partition keys, so each pipe has whole partitions.
+                let processor = TransformWindowShuffle::create(
+                    self.main_pipeline.output_len(),
+                    window.partition_by.clone(),
+                );
+                let inputs_port = processor.get_inputs().to_vec();
+                let outputs_port = processor.get_outputs().to_vec();
+                self.main_pipeline.add_pipe(Pipe::ResizePipe {
+                    processor: ProcessorPtr::create(Box::new(processor)),
+                    inputs_port,
+                    outputs_port,
+                });
+            }
+
+            if !sort_desc.is_empty() {
+                self.main_pipeline.add_transform(|input, output| {
+                    TransformSortPartial::try_create(input, output, None, sort_desc.clone())
+                })?;
+                self.main_pipeline.add_transform(|input, output| {
+                    TransformSortExternal::try_create(
+                        self.ctx.clone(),
+                        input,
+                        output,
+                        None,
+                        sort_desc.clone(),
+                    )
+                })?;
+            }
+        }
+
+        let order_by: Vec<ColumnID> = window
+            .order_by
+            .iter()
+            .map(|desc| desc.order_by.clone())
+            .collect();
+        self.main_pipeline.add_transform(|input, output| {
+            TransformWindow::try_create(
+                input,
+                output,
+                WindowEvaluator::try_create(
+                    window.func.clone(),
+                    window.partition_by.clone(),
+                    order_by.clone(),
+                    window.window_frame,
+                )?,
             )
         })
     }
@@ -429,16 +812,18 @@ impl PipelineBuilder {
     }
 
     fn build_join_probe(&mut self, join: &HashJoin, state: Arc<JoinHashTable>) -> Result<()> {
+        let runtime_filters = self.probe_runtime_filters(join, state.clone())?;
+        self.runtime_filters.extend(runtime_filters);
         self.build_pipeline(&join.probe)?;
 
         self.main_pipeline.add_transform(|input, output| {
-            Ok(TransformHashJoinProbe::create(
+            TransformHashJoinProbe::try_create(
                 self.ctx.clone(),
                 input,
                 output,
                 state.clone(),
                 join.output_schema()?,
-            ))
+            )
         })?;
 
         if join.join_type == JoinType::Mark {
@@ -455,6 +840,64 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    /// Creates the runtime filters of the probe keys, which are the columns of the table scanned
+    /// by the probe side through filters and projections only. The rows of the probe side
+    /// filtered out can't be matched, so it only applies to inner joins and semi joins.
+    fn probe_runtime_filters(
+        &self,
+        join: &HashJoin,
+        state: Arc<JoinHashTable>,
+    ) -> Result<Vec<(ColumnID, ProbeRuntimeFilter)>> {
+        if !matches!(join.join_type, JoinType::Inner | JoinType::Semi)
+            || self.ctx.get_settings().get_enable_runtime_filter()? == 0
+        {
+            return Ok(vec![]);
+        }
+
+        let scan = match Self::probe_table_scan(&join.probe) {
+            Some(scan) => scan,
+            None => return Ok(vec![]),
+        };
+
+        let mut runtime_filters = vec![];
+        for (key_index, (probe_key, build_key)) in
+            join.probe_keys.iter().zip(&join.build_keys).enumerate()
+        {
+            let column_id = match probe_key {
+                PhysicalScalar::Variable { column_id, .. } => column_id,
+                _ => continue,
+            };
+            if !scan.name_mapping.values().any(|id| id == column_id) {
+                continue;
+            }
+
+            // the values of the build keys are only comparable with the probe keys of the same type
+            let data_type = remove_nullable(&probe_key.data_type());
+            if data_type != remove_nullable(&build_key.data_type())
+                || !RuntimeFilter::is_supported_type(&data_type)
+            {
+                continue;
+            }
+
+            let join_state = state.clone() as Arc<dyn HashJoinState>;
+            runtime_filters.push((
+                column_id.clone(),
+                ProbeRuntimeFilter::create(join_state, key_index)?,
+            ));
+        }
+        Ok(runtime_filters)
+    }
+
+    fn probe_table_scan(plan: &PhysicalPlan) -> Option<&TableScan> {
+        match plan {
+            PhysicalPlan::TableScan(scan) => Some(scan),
+            PhysicalPlan::Filter(Filter { input, .. })
+            | PhysicalPlan::Project(Project { input, .. })
+            | PhysicalPlan::EvalScalar(EvalScalar { input, .. }) => Self::probe_table_scan(input),
+            _ => None,
+        }
+    }
+
     pub fn build_exchange_source(&mut self, exchange_source: &ExchangeSource) -> Result<()> {
         let exchange_manager = self.ctx.get_exchange_manager();
         let build_res = exchange_manager.get_fragment_source(
@@ -15,189 +15,228 @@
 mod explore_rules;
 mod implement_rules;
 
+use std::collections::HashMap;
+
 use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::sql::optimizer::cascades::explore_rules::get_explore_rule_set;
 use crate::sql::optimizer::cascades::implement_rules::get_implement_rule_set;
-use crate::sql::optimizer::group::Group;
+use crate::sql::optimizer::cost::Cost;
+use crate::sql::optimizer::cost::CostEstimation;
+use crate::sql::optimizer::cost::CostModel;
+use crate::sql::optimizer::cost::DefaultCostModel;
+use crate::sql::optimizer::cost::StatInfo;
+use crate::sql::optimizer::cost::StatInfoDeriver;
 use crate::sql::optimizer::m_expr::MExpr;
 use crate::sql::optimizer::memo::Memo;
-use crate::sql::optimizer::rule::RulePtr;
 use crate::sql::optimizer::rule::RuleSet;
 use crate::sql::optimizer::rule::TransformState;
-use crate::sql::optimizer::RequiredProperty;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalHashJoin;
+use crate::sql::plans::PhysicalNestedLoopJoin;
+use crate::sql::plans::PhysicalSortMergeJoin;
+use crate::sql::plans::RelOperator;
 use crate::sql::IndexType;
+use crate::sql::MetadataRef;
+
+/// Exploration stops once there are so many expressions in the memo,
+/// to bound the time of optimizing the queries joining lots of tables.
+const MAX_EXPLORED_EXPRESSIONS: usize = 10000;
 
-/// A cascades-style search engine to enumerate possible alternations of a relational expression and
-/// find the optimal one.
-///
-/// NOTICE: we don't support cost-based optimization and lower bound searching for now.
-#[allow(dead_code)]
+/// Cost-based optimizer in the style of Cascades. The equivalent join orders of a plan
+/// are explored into `Memo`, and the physical plan with the lowest cost is extracted from it.
 pub struct CascadesOptimizer {
-    // optimize_context: OptimizeContext,
+    metadata: MetadataRef,
     memo: Memo,
     explore_rules: RuleSet,
     implement_rules: RuleSet,
+    cost_model: Box<dyn CostModel>,
+
+    /// The best physical plan of each group and its cost
+    best_plans: HashMap<IndexType, (Cost, SExpr)>,
 }
 
 impl CascadesOptimizer {
-    #[allow(dead_code)]
-    pub fn create() -> Self {
+    pub fn create(metadata: MetadataRef) -> Self {
         CascadesOptimizer {
+            metadata: metadata.clone(),
             memo: Memo::create(),
             explore_rules: get_explore_rule_set(),
             implement_rules: get_implement_rule_set(),
+            cost_model: Box::new(DefaultCostModel::create(metadata)),
+            best_plans: HashMap::new(),
         }
     }
 
-    #[allow(dead_code)]
-    fn init(&mut self, expression: SExpr) -> Result<()> {
-        self.memo.init(expression)?;
-
-        Ok(())
-    }
-
-    #[allow(dead_code)]
     pub fn optimize(&mut self, expression: SExpr) -> Result<SExpr> {
-        self.init(expression)?;
-
-        self.explore_group(self.memo.root().unwrap().group_index())?;
-
-        self.implement_group(self.memo.root().unwrap().group_index())?;
+        self.memo.init(expression)?;
 
-        self.find_optimal_plan()
-    }
+        self.explore()?;
 
-    #[allow(dead_code)]
-    fn explore_group(&mut self, group_index: IndexType) -> Result<()> {
-        let group = self.memo.group(group_index);
-        let expressions: Vec<MExpr> = group.iter().cloned().collect();
-        for m_expr in expressions {
-            self.explore_expr(m_expr)?;
+        for group_index in 0..self.memo.num_groups() {
+            self.derive_stat_info(group_index)?;
         }
 
-        Ok(())
+        self.implement()?;
+
+        let root_group = self
+            .memo
+            .root()
+            .ok_or_else(|| ErrorCode::LogicalError("Memo should have been initialized"))?
+            .group_index();
+        let (_, plan) = self.optimize_group(root_group)?;
+        Ok(plan)
     }
 
-    #[allow(dead_code)]
-    fn explore_expr(&mut self, m_expr: MExpr) -> Result<()> {
-        for child in m_expr.children() {
-            self.explore_group(*child)?;
-        }
+    /// Apply the exploration rules to the expressions in the memo repeatedly,
+    /// until no more expression can be generated.
+    fn explore(&mut self) -> Result<()> {
+        let mut num_exprs = self.num_exprs();
+        loop {
+            for group_index in 0..self.memo.num_groups() {
+                let group = self.memo.group(group_index);
+                let expressions: Vec<MExpr> = group.iter().cloned().collect();
+                for m_expr in expressions {
+                    let results = Self::apply_rules(&self.memo, &m_expr, &self.explore_rules)?;
+                    self.insert_expressions(m_expr.group_index(), results)?;
+                }
+            }
 
-        let mut state = TransformState::new();
-        for rule in self.explore_rules.iter() {
-            m_expr.apply_rule(&self.memo, rule, &mut state)?;
+            let new_num_exprs = self.num_exprs();
+            if new_num_exprs == num_exprs || new_num_exprs >= MAX_EXPLORED_EXPRESSIONS {
+                break;
+            }
+            num_exprs = new_num_exprs;
         }
-        self.insert_from_transform_state(m_expr.group_index(), state)?;
 
         Ok(())
     }
 
-    #[allow(dead_code)]
-    fn implement_group(&mut self, group_index: IndexType) -> Result<()> {
-        let group = self.memo.group(group_index);
-        let expressions: Vec<MExpr> = group.iter().cloned().collect();
-        for m_expr in expressions {
-            self.implement_expr(m_expr)?;
+    /// Implement the logical expressions of all the groups with physical ones, the
+    /// implementation rules don't generate new groups so that one pass is enough.
+    fn implement(&mut self) -> Result<()> {
+        for group_index in 0..self.memo.num_groups() {
+            let group = self.memo.group(group_index);
+            let expressions: Vec<MExpr> = group.iter().cloned().collect();
+            for m_expr in expressions {
+                let results = Self::apply_rules(&self.memo, &m_expr, &self.implement_rules)?;
+                self.insert_expressions(m_expr.group_index(), results)?;
+            }
         }
 
         Ok(())
     }
 
-    #[allow(dead_code)]
-    fn implement_expr(&mut self, m_expr: MExpr) -> Result<()> {
-        for child in m_expr.children() {
-            self.implement_group(*child)?;
+    fn apply_rules(memo: &Memo, m_expr: &MExpr, rules: &RuleSet) -> Result<Vec<SExpr>> {
+        let mut state = TransformState::with_memo(memo);
+        for rule in rules.iter() {
+            m_expr.apply_rule(memo, rule, &mut state)?;
         }
 
-        let mut state = TransformState::new();
-        for rule in self.implement_rules.iter() {
-            m_expr.apply_rule(&self.memo, rule, &mut state)?;
-        }
-        self.insert_from_transform_state(m_expr.group_index(), state)?;
-
-        Ok(())
+        Ok(state.results().to_vec())
     }
 
-    #[allow(dead_code)]
-    fn insert_from_transform_state(
+    fn insert_expressions(
         &mut self,
         group_index: IndexType,
-        state: TransformState,
+        expressions: Vec<SExpr>,
     ) -> Result<()> {
-        for result in state.results() {
-            self.insert_expression(group_index, result)?;
+        for expression in expressions {
+            self.memo.insert(Some(group_index), expression)?;
         }
 
         Ok(())
     }
 
-    #[allow(dead_code)]
-    fn insert_expression(&mut self, group_index: IndexType, expression: &SExpr) -> Result<()> {
-        self.memo.insert(Some(group_index), expression.clone())?;
-
-        Ok(())
+    fn num_exprs(&self) -> usize {
+        (0..self.memo.num_groups())
+            .map(|group_index| self.memo.group(group_index).num_exprs())
+            .sum()
     }
 
-    #[allow(dead_code)]
-    fn apply_rule(&self, m_expr: &MExpr, rule: &RulePtr, state: &mut TransformState) -> Result<()> {
-        m_expr.apply_rule(&self.memo, rule, state)?;
-
-        Ok(())
-    }
+    /// Derive the statistics of a group from its first expression, which is always logical.
+    fn derive_stat_info(&mut self, group_index: IndexType) -> Result<()> {
+        if self.memo.group(group_index).stat_info().is_some() {
+            return Ok(());
+        }
 
-    #[allow(dead_code)]
-    fn find_optimal_plan(&self) -> Result<SExpr> {
-        let root_group = self.memo.root().unwrap();
+        let m_expr = self.memo.group(group_index).m_expr(0).clone();
+        for child in m_expr.children() {
+            self.derive_stat_info(*child)?;
+        }
 
-        let required_prop = RequiredProperty::default();
+        let children = m_expr
+            .children()
+            .iter()
+            .map(|child| {
+                self.memo.group(*child).stat_info().ok_or_else(|| {
+                    ErrorCode::LogicalError("Statistics of the group should have been derived")
+                })
+            })
+            .collect::<Result<Vec<&StatInfo>>>()?;
+        let stat_info = {
+            let metadata = self.metadata.read();
+            StatInfoDeriver::new(&metadata).derive(m_expr.plan(), &children)?
+        };
+        self.memo.set_stat_info(group_index, stat_info);
 
-        self.optimize_group(root_group, &required_prop)
+        Ok(())
     }
 
-    /// We don't have cost mechanism for evaluate cost of plans, so we just extract
-    /// first physical plan in a group that satisfies given RequiredProperty.
-    #[allow(dead_code)]
-    fn optimize_group(&self, group: &Group, required_prop: &RequiredProperty) -> Result<SExpr> {
-        for m_expr in group.iter() {
-            if m_expr.plan().is_physical() {
-                let plan = m_expr.plan();
-
-                // TODO: Check properties
-                // let physical = plan.as_physical_plan().unwrap();
-                // let relational_prop = group.relational_prop().unwrap();
-                // let dummy_physical_prop = PhysicalProperty::default();
-                // let required_prop = physical.compute_required_prop(required_prop);
-                //
-                // if !required_prop.provided_by(relational_prop, &dummy_physical_prop) {
-                //     continue;
-                // }
-
-                let children = self.optimize_m_expr(m_expr, required_prop)?;
-                let result = SExpr::create(plan.clone(), children, None);
-                return Ok(result);
-            }
+    /// Find the physical plan with the lowest cost of a group. Among the plans with the same
+    /// cost, the one inserted first is chosen, i.e. the join order written in the query is kept.
+    fn optimize_group(&mut self, group_index: IndexType) -> Result<(Cost, SExpr)> {
+        if let Some(best_plan) = self.best_plans.get(&group_index) {
+            return Ok(best_plan.clone());
         }
 
-        Err(ErrorCode::LogicalError("Cannot find an appropriate plan"))
-    }
+        let group = self.memo.group(group_index);
+        let cardinality = group.stat_info().map_or(0.0, |stat| stat.cardinality);
+        let expressions: Vec<MExpr> = group
+            .iter()
+            .filter(|m_expr| m_expr.plan().is_physical())
+            .cloned()
+            .collect();
+
+        let mut best_plan: Option<(Cost, SExpr)> = None;
+        for m_expr in expressions.iter() {
+            let mut cost = self.cost_model.compute_cost(&self.memo, m_expr)?;
+            let mut children = Vec::with_capacity(m_expr.arity());
+            for child in m_expr.children() {
+                let (child_cost, child_plan) = self.optimize_group(*child)?;
+                cost = cost + child_cost;
+                children.push(child_plan);
+            }
 
-    #[allow(dead_code)]
-    fn optimize_m_expr(
-        &self,
-        m_expr: &MExpr,
-        required_prop: &RequiredProperty,
-    ) -> Result<Vec<SExpr>> {
-        let mut children = vec![];
-        for child in m_expr.children() {
-            let group = self.memo.group(*child);
-            children.push(self.optimize_group(group, required_prop)?);
+            if matches!(&best_plan, Some((best_cost, _)) if *best_cost <= cost) {
+                continue;
+            }
+            let plan = match m_expr.plan() {
+                RelOperator::PhysicalHashJoin(join) => PhysicalHashJoin {
+                    estimation: Some(CostEstimation { cardinality, cost }),
+                    ..join.clone()
+                }
+                .into(),
+                RelOperator::PhysicalNestedLoopJoin(join) => PhysicalNestedLoopJoin {
+                    estimation: Some(CostEstimation { cardinality, cost }),
+                    ..join.clone()
+                }
+                .into(),
+                RelOperator::PhysicalSortMergeJoin(join) => PhysicalSortMergeJoin {
+                    estimation: Some(CostEstimation { cardinality, cost }),
+                    ..join.clone()
+                }
+                .into(),
+                plan => plan.clone(),
+            };
+            best_plan = Some((cost, SExpr::create(plan, children, None)));
         }
 
-        Ok(children)
+        let best_plan =
+            best_plan.ok_or_else(|| ErrorCode::LogicalError("Cannot find an appropriate plan"))?;
+        self.best_plans.insert(group_index, best_plan.clone());
+        Ok(best_plan)
     }
 }
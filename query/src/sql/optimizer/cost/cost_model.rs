@@ -0,0 +1,131 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+
+use crate::sql::optimizer::cost::Cost;
+use crate::sql::optimizer::cost::CostModel;
+use crate::sql::optimizer::MExpr;
+use crate::sql::optimizer::Memo;
+use crate::sql::plans::JoinType;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::IndexType;
+use crate::sql::MetadataRef;
+
+/// Cost of inserting a row into the hash table of hash join,
+/// relative to the cost of processing a row by the other operators.
+const COST_FACTOR_HASH_TABLE_PER_ROW: f64 = 2.0;
+
+/// Cost of comparing a row while sorting, which is multiplied by the logarithm of the rows.
+const COST_FACTOR_SORT_PER_ROW: f64 = 2.0;
+
+/// Cost model in which the cost of an operator is proportional to the number of rows it processes.
+pub struct DefaultCostModel {
+    metadata: MetadataRef,
+}
+
+impl DefaultCostModel {
+    pub fn create(metadata: MetadataRef) -> Self {
+        DefaultCostModel { metadata }
+    }
+
+    /// Cost of sorting a side of sort-merge join by the join keys.
+    ///
+    /// The blocks of a table clustered by the keys are sorted when written, the operator
+    /// only checks their order and merges them without sorting, see `SortMergeJoinState`.
+    fn sort_cost(&self, memo: &Memo, group_index: IndexType, keys: &[Scalar]) -> Result<f64> {
+        if self.is_clustered_by(memo, group_index, keys) {
+            return Ok(0.0);
+        }
+
+        let rows = cardinality(memo, group_index)?;
+        Ok(rows * rows.log2().max(1.0) * COST_FACTOR_SORT_PER_ROW)
+    }
+
+    /// Check if the group is a scan of a table whose cluster keys start with the join keys.
+    fn is_clustered_by(&self, memo: &Memo, group_index: IndexType, keys: &[Scalar]) -> bool {
+        let table_index = match memo.group(group_index).m_expr(0).plan() {
+            RelOperator::LogicalGet(get) => get.table_index,
+            RelOperator::PhysicalScan(scan) => scan.table_index,
+            _ => return false,
+        };
+
+        let metadata = self.metadata.read();
+        let cluster_keys = metadata.table(table_index).table.cluster_keys();
+        if keys.is_empty() || keys.len() > cluster_keys.len() {
+            return false;
+        }
+        keys.iter()
+            .zip(cluster_keys.iter())
+            .all(|(key, cluster_key)| {
+                let column = match key {
+                    Scalar::BoundColumnRef(column_ref) => metadata.column(column_ref.column.index),
+                    _ => return false,
+                };
+                column.table_index == Some(table_index)
+                    && matches!(cluster_key, Expression::Column(name) if name == &column.name)
+            })
+    }
+}
+
+impl CostModel for DefaultCostModel {
+    fn compute_cost(&self, memo: &Memo, m_expr: &MExpr) -> Result<Cost> {
+        match m_expr.plan() {
+            RelOperator::PhysicalScan(_) | RelOperator::LogicalGet(_) => {
+                Ok(Cost(cardinality(memo, m_expr.group_index())?))
+            }
+            RelOperator::PhysicalHashJoin(join) => {
+                let probe = cardinality(memo, m_expr.children()[0])?;
+                let build = cardinality(memo, m_expr.children()[1])?;
+                if join.join_type == JoinType::Cross {
+                    // Every pair of the rows is processed
+                    Ok(Cost(probe * build))
+                } else {
+                    Ok(Cost(probe + build * COST_FACTOR_HASH_TABLE_PER_ROW))
+                }
+            }
+            RelOperator::PhysicalNestedLoopJoin(_) => {
+                // Every pair of the rows is processed
+                let left = cardinality(memo, m_expr.children()[0])?;
+                let right = cardinality(memo, m_expr.children()[1])?;
+                Ok(Cost(left * right))
+            }
+            RelOperator::PhysicalSortMergeJoin(join) => {
+                let left_group = m_expr.children()[0];
+                let right_group = m_expr.children()[1];
+                let merge = cardinality(memo, left_group)? + cardinality(memo, right_group)?;
+                let sort = self.sort_cost(memo, left_group, &join.left_keys)?
+                    + self.sort_cost(memo, right_group, &join.right_keys)?;
+                Ok(Cost(merge + sort))
+            }
+            _ => {
+                let mut rows = 0.0;
+                for child in m_expr.children() {
+                    rows += cardinality(memo, *child)?;
+                }
+                Ok(Cost(rows))
+            }
+        }
+    }
+}
+
+fn cardinality(memo: &Memo, group_index: IndexType) -> Result<f64> {
+    memo.group(group_index)
+        .stat_info()
+        .map(|stat_info| stat_info.cardinality)
+        .ok_or_else(|| ErrorCode::LogicalError("Statistics of the group should have been derived"))
+}
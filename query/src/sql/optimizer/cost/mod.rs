@@ -0,0 +1,61 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod cost_model;
+mod stat_info;
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::Add;
+
+use common_exception::Result;
+pub use cost_model::DefaultCostModel;
+pub use stat_info::ColumnStat;
+pub use stat_info::StatInfo;
+pub use stat_info::StatInfoDeriver;
+
+use crate::sql::optimizer::MExpr;
+use crate::sql::optimizer::Memo;
+
+/// Cost of a plan, the lower the better.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Cost(pub f64);
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Cost(self.0 + rhs.0)
+    }
+}
+
+impl Display for Cost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.2}", self.0)
+    }
+}
+
+/// Estimated output cardinality and accumulated cost of a plan chosen
+/// by the cost-based optimizer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CostEstimation {
+    pub cardinality: f64,
+    pub cost: Cost,
+}
+
+pub trait CostModel {
+    /// Compute the cost of the operator of `m_expr` itself, excluding the costs of its children.
+    /// The statistics of the groups in `memo` must have been derived.
+    fn compute_cost(&self, memo: &Memo, m_expr: &MExpr) -> Result<Cost>;
+}
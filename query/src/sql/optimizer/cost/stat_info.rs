@@ -0,0 +1,314 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::ComparisonOp;
+use crate::sql::plans::JoinType;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::SetOperationType;
+use crate::sql::IndexType;
+use crate::sql::Metadata;
+
+/// Selectivity of the predicates which can't be estimated with the statistics.
+const DEFAULT_SELECTIVITY: f64 = 0.2;
+
+#[derive(Clone, Debug)]
+pub struct ColumnStat {
+    /// Estimated number of distinct values.
+    pub ndv: f64,
+}
+
+/// Statistics of the output of a `Group`, shared by the expressions in the group.
+#[derive(Clone, Debug, Default)]
+pub struct StatInfo {
+    /// Estimated number of output rows.
+    pub cardinality: f64,
+    /// Statistics of the output columns whose numbers of distinct values are known.
+    pub column_stats: HashMap<IndexType, ColumnStat>,
+}
+
+impl StatInfo {
+    fn with_cardinality(mut self, cardinality: f64) -> Self {
+        self.cardinality = cardinality;
+        for stat in self.column_stats.values_mut() {
+            stat.ndv = stat.ndv.min(cardinality);
+        }
+        self
+    }
+
+    /// Number of distinct values of `scalar`, if it's a column with known statistics.
+    fn ndv(&self, scalar: &Scalar) -> Option<f64> {
+        match scalar {
+            Scalar::BoundColumnRef(column_ref) => self
+                .column_stats
+                .get(&column_ref.column.index)
+                .map(|stat| stat.ndv),
+            _ => None,
+        }
+    }
+}
+
+/// Derive the statistics of the output of an operator from the statistics of its inputs.
+pub struct StatInfoDeriver<'a> {
+    metadata: &'a Metadata,
+}
+
+impl<'a> StatInfoDeriver<'a> {
+    pub fn new(metadata: &'a Metadata) -> Self {
+        StatInfoDeriver { metadata }
+    }
+
+    pub fn derive(&self, plan: &RelOperator, children: &[&StatInfo]) -> Result<StatInfo> {
+        match plan {
+            RelOperator::LogicalGet(get) => {
+                Ok(self.derive_scan(get.table_index, &get.columns, &get.push_down_predicates))
+            }
+            RelOperator::PhysicalScan(scan) => {
+                Ok(self.derive_scan(scan.table_index, &scan.columns, &scan.push_down_predicates))
+            }
+            RelOperator::LogicalInnerJoin(join) => Ok(derive_join(
+                &join.join_type,
+                &join.left_conditions,
+                &join.right_conditions,
+                &join.other_conditions,
+                child(children, 0)?,
+                child(children, 1)?,
+            )),
+            RelOperator::PhysicalHashJoin(join) => Ok(derive_join(
+                &join.join_type,
+                &join.probe_keys,
+                &join.build_keys,
+                &join.other_conditions,
+                child(children, 0)?,
+                child(children, 1)?,
+            )),
+            RelOperator::PhysicalNestedLoopJoin(join) => Ok(derive_join(
+                &join.join_type,
+                &[],
+                &[],
+                &join.conditions,
+                child(children, 0)?,
+                child(children, 1)?,
+            )),
+            RelOperator::PhysicalSortMergeJoin(join) => Ok(derive_join(
+                &join.join_type,
+                &join.left_keys,
+                &join.right_keys,
+                &join.other_conditions,
+                child(children, 0)?,
+                child(children, 1)?,
+            )),
+            RelOperator::Filter(filter) => {
+                Ok(derive_filter(child(children, 0)?, &filter.predicates))
+            }
+            RelOperator::Aggregate(aggregate) => {
+                let input = child(children, 0)?;
+                if aggregate.group_items.is_empty() {
+                    return Ok(StatInfo {
+                        cardinality: 1.0,
+                        column_stats: HashMap::new(),
+                    });
+                }
+
+                // Each combination of the distinct values of the group items
+                // is assumed to exist in the input.
+                let mut cardinality = 1.0;
+                let mut column_stats = HashMap::new();
+                for item in aggregate.group_items.iter() {
+                    let ndv = input.ndv(&item.scalar).unwrap_or(input.cardinality);
+                    cardinality *= ndv;
+                    column_stats.insert(item.index, ColumnStat { ndv });
+                }
+                Ok(StatInfo {
+                    cardinality,
+                    column_stats,
+                }
+                .with_cardinality(cardinality.min(input.cardinality)))
+            }
+            RelOperator::Limit(limit) => {
+                let input = child(children, 0)?;
+                let mut cardinality = (input.cardinality - limit.offset as f64).max(0.0);
+                if let Some(limit) = limit.limit {
+                    cardinality = cardinality.min(limit as f64);
+                }
+                Ok(input.clone().with_cardinality(cardinality))
+            }
+            RelOperator::UnionAll(union_all) => {
+                let left = child(children, 0)?;
+                let right = child(children, 1)?;
+                // Each output column has at most the distinct values of both sides.
+                let mut column_stats = HashMap::new();
+                for ((output, left_column), right_column) in union_all
+                    .output_columns
+                    .iter()
+                    .zip(union_all.left_columns.iter())
+                    .zip(union_all.right_columns.iter())
+                {
+                    let left_ndv = left.column_stats.get(&left_column.index);
+                    let right_ndv = right.column_stats.get(&right_column.index);
+                    if let (Some(left_stat), Some(right_stat)) = (left_ndv, right_ndv) {
+                        column_stats.insert(*output, ColumnStat {
+                            ndv: left_stat.ndv + right_stat.ndv,
+                        });
+                    }
+                }
+                Ok(StatInfo {
+                    cardinality: left.cardinality + right.cardinality,
+                    column_stats,
+                })
+            }
+            RelOperator::SetOperation(set_operation) => {
+                let left = child(children, 0)?;
+                let right = child(children, 1)?;
+                let cardinality = match set_operation.op_type {
+                    SetOperationType::IntersectAll => left.cardinality.min(right.cardinality),
+                    SetOperationType::ExceptAll => left.cardinality,
+                };
+                Ok(left.clone().with_cardinality(cardinality))
+            }
+            // The number of iterations is unknown, take the non-recursive term as estimation.
+            RelOperator::RecursiveCte(_) => Ok(StatInfo {
+                cardinality: child(children, 0)?.cardinality,
+                column_stats: HashMap::new(),
+            }),
+            RelOperator::Project(_)
+            | RelOperator::EvalScalar(_)
+            | RelOperator::Sort(_)
+            | RelOperator::Exchange(_)
+            | RelOperator::Window(_) => Ok(child(children, 0)?.clone()),
+            RelOperator::Pattern(_) => Err(ErrorCode::LogicalError(
+                "Cannot derive statistics of pattern",
+            )),
+        }
+    }
+
+    fn derive_scan(
+        &self,
+        table_index: IndexType,
+        columns: &ColumnSet,
+        predicates: &Option<Vec<Scalar>>,
+    ) -> StatInfo {
+        let table = self.metadata.table(table_index);
+        let mut column_stats = HashMap::new();
+        if let Some(statistics) = &table.column_statistics {
+            // The statistics are keyed by the index of the column in the table schema,
+            // which is the order of the columns of the table in metadata.
+            let table_columns = self.metadata.columns_by_table_index(table_index);
+            for (index, column) in table_columns.iter().enumerate() {
+                if !columns.contains(&column.column_index) {
+                    continue;
+                }
+                if let Some(ndv) = statistics
+                    .get(&(index as u32))
+                    .and_then(|stat| stat.number_of_distinct_values())
+                {
+                    column_stats.insert(column.column_index, ColumnStat { ndv: ndv as f64 });
+                }
+            }
+        }
+
+        let cardinality = table.source.statistics.read_rows as f64;
+        let stat_info = StatInfo {
+            cardinality,
+            column_stats,
+        }
+        .with_cardinality(cardinality);
+        match predicates {
+            Some(predicates) => derive_filter(&stat_info, predicates),
+            None => stat_info,
+        }
+    }
+}
+
+fn child<'a>(children: &[&'a StatInfo], index: usize) -> Result<&'a StatInfo> {
+    children
+        .get(index)
+        .copied()
+        .ok_or_else(|| ErrorCode::LogicalError(format!("Invalid children index: {}", index)))
+}
+
+fn derive_filter(input: &StatInfo, predicates: &[Scalar]) -> StatInfo {
+    let selectivity: f64 = predicates
+        .iter()
+        .map(|predicate| selectivity(input, predicate))
+        .product();
+    input
+        .clone()
+        .with_cardinality(input.cardinality * selectivity)
+}
+
+fn selectivity(input: &StatInfo, predicate: &Scalar) -> f64 {
+    match predicate {
+        Scalar::ComparisonExpr(ComparisonExpr {
+            op: ComparisonOp::Equal,
+            left,
+            right,
+            ..
+        }) => match (left.as_ref(), right.as_ref()) {
+            (column @ Scalar::BoundColumnRef(_), Scalar::ConstantExpr(_))
+            | (Scalar::ConstantExpr(_), column @ Scalar::BoundColumnRef(_)) => input
+                .ndv(column)
+                .map_or(DEFAULT_SELECTIVITY, |ndv| 1.0 / ndv.max(1.0)),
+            _ => DEFAULT_SELECTIVITY,
+        },
+        _ => DEFAULT_SELECTIVITY,
+    }
+}
+
+fn derive_join(
+    join_type: &JoinType,
+    left_keys: &[Scalar],
+    right_keys: &[Scalar],
+    other_conditions: &[Scalar],
+    left: &StatInfo,
+    right: &StatInfo,
+) -> StatInfo {
+    let mut inner = left.cardinality * right.cardinality;
+    for (left_key, right_key) in left_keys.iter().zip(right_keys.iter()) {
+        // The distinct values of the side with fewer distinct values
+        // are assumed to be contained by the other side.
+        let left_ndv = left.ndv(left_key).unwrap_or(left.cardinality);
+        let right_ndv = right.ndv(right_key).unwrap_or(right.cardinality);
+        let ndv = left_ndv.max(right_ndv);
+        if ndv > 0.0 {
+            inner /= ndv;
+        }
+    }
+    inner *= DEFAULT_SELECTIVITY.powi(other_conditions.len() as i32);
+
+    let cardinality = match join_type {
+        JoinType::Inner | JoinType::Cross => inner,
+        JoinType::Left => inner.max(left.cardinality),
+        JoinType::Right => inner.max(right.cardinality),
+        JoinType::Full => inner.max(left.cardinality).max(right.cardinality),
+        JoinType::Semi | JoinType::Anti | JoinType::Single => left.cardinality,
+        // The outer side of mark join is the right child.
+        JoinType::Mark => right.cardinality,
+    };
+
+    let mut column_stats = left.column_stats.clone();
+    column_stats.extend(right.column_stats.clone());
+    StatInfo {
+        cardinality,
+        column_stats,
+    }
+    .with_cardinality(cardinality)
+}
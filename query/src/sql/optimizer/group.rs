@@ -16,6 +16,7 @@ use std::iter::Iterator;
 
 use common_exception::Result;
 
+use crate::sql::optimizer::cost::StatInfo;
 use crate::sql::optimizer::m_expr::MExpr;
 use crate::sql::optimizer::property::RelationalProperty;
 use crate::sql::IndexType;
@@ -28,6 +29,9 @@ pub struct Group {
 
     /// Relational property shared by expressions in a same `Group`
     relational_prop: Option<RelationalProperty>,
+
+    /// Statistics of the output shared by expressions in a same `Group`
+    stat_info: Option<StatInfo>,
 }
 
 impl Group {
@@ -36,6 +40,7 @@ impl Group {
             group_index: index,
             expressions: vec![],
             relational_prop: None,
+            stat_info: None,
         }
     }
 
@@ -43,6 +48,14 @@ impl Group {
         self.group_index
     }
 
+    pub fn num_exprs(&self) -> usize {
+        self.expressions.len()
+    }
+
+    pub fn m_expr(&self, index: usize) -> &MExpr {
+        &self.expressions[index]
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &MExpr> {
         self.expressions.iter()
     }
@@ -59,4 +72,12 @@ impl Group {
     pub fn relational_prop(&self) -> Option<&RelationalProperty> {
         self.relational_prop.as_ref()
     }
+
+    pub fn set_stat_info(&mut self, stat_info: StatInfo) {
+        self.stat_info = Some(stat_info);
+    }
+
+    pub fn stat_info(&self) -> Option<&StatInfo> {
+        self.stat_info.as_ref()
+    }
 }
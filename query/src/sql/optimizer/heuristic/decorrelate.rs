@@ -628,11 +628,25 @@ impl SubqueryRewriter {
                 Ok(SExpr::create_unary(plan.plan().clone(), flatten_plan))
             }
 
+            RelOperator::Window(_) => Err(ErrorCode::UnImplement(
+                "Window function in correlated subquery is not supported",
+            )),
+
+            RelOperator::UnionAll(_) | RelOperator::SetOperation(_) => Err(ErrorCode::UnImplement(
+                "Set operation in correlated subquery is not supported",
+            )),
+
+            RelOperator::RecursiveCte(_) => Err(ErrorCode::UnImplement(
+                "Recursive CTE in correlated subquery is not supported",
+            )),
+
             RelOperator::Exchange(_)
             | RelOperator::Pattern(_)
             | RelOperator::LogicalGet(_)
             | RelOperator::PhysicalScan(_)
-            | RelOperator::PhysicalHashJoin(_) => Err(ErrorCode::LogicalError(
+            | RelOperator::PhysicalHashJoin(_)
+            | RelOperator::PhysicalNestedLoopJoin(_)
+            | RelOperator::PhysicalSortMergeJoin(_) => Err(ErrorCode::LogicalError(
                 "Invalid plan type for flattening subquery",
             )),
         }
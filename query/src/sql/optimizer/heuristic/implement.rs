@@ -20,8 +20,13 @@ use crate::sql::optimizer::rule::RuleSet;
 use crate::sql::optimizer::rule::TransformState;
 use crate::sql::optimizer::SExpr;
 
-static DEFAULT_IMPLEMENT_RULES: Lazy<Vec<RuleID>> =
-    Lazy::new(|| vec![RuleID::ImplementGet, RuleID::ImplementHashJoin]);
+static DEFAULT_IMPLEMENT_RULES: Lazy<Vec<RuleID>> = Lazy::new(|| {
+    vec![
+        RuleID::ImplementGet,
+        RuleID::ImplementHashJoin,
+        RuleID::ImplementNestedLoopJoin,
+    ]
+});
 
 pub struct HeuristicImplementor {
     implement_rule_list: RuleSet,
@@ -38,7 +43,11 @@ impl HeuristicImplementor {
         for rule in self.implement_rule_list.iter() {
             if s_expr.match_pattern(rule.pattern()) {
                 rule.apply(s_expr, state)?;
-                break;
+                // A rule may decline to implement the expression, e.g. `ImplementHashJoin`
+                // leaves the joins without equi-conditions to `ImplementNestedLoopJoin`.
+                if !state.results().is_empty() {
+                    break;
+                }
             }
         }
         Ok(())
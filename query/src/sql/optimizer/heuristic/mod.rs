@@ -25,6 +25,7 @@ use once_cell::sync::Lazy;
 use super::rule::RuleID;
 use super::util::validate_distributed_query;
 use crate::sessions::QueryContext;
+use crate::sql::optimizer::cascades::CascadesOptimizer;
 use crate::sql::optimizer::heuristic::decorrelate::decorrelate_subquery;
 use crate::sql::optimizer::heuristic::implement::HeuristicImplementor;
 pub use crate::sql::optimizer::heuristic::rule_list::RuleList;
@@ -64,6 +65,7 @@ pub struct HeuristicOptimizer {
     metadata: MetadataRef,
 
     enable_distributed_optimization: bool,
+    enable_join_reorder: bool,
 }
 
 impl HeuristicOptimizer {
@@ -80,15 +82,25 @@ impl HeuristicOptimizer {
             _ctx: ctx,
             metadata,
             enable_distributed_optimization,
+            enable_join_reorder: false,
         }
     }
 
+    /// Reorder the joins with the cascades optimizer after the rewriting rules are applied.
+    pub fn set_enable_join_reorder(&mut self, enable_join_reorder: bool) {
+        self.enable_join_reorder = enable_join_reorder;
+    }
+
     fn pre_optimize(&mut self, s_expr: SExpr) -> Result<SExpr> {
         let result = decorrelate_subquery(self.metadata.clone(), s_expr)?;
         Ok(result)
     }
 
     fn post_optimize(&mut self, s_expr: SExpr) -> Result<SExpr> {
+        if self.enable_join_reorder {
+            let mut cascades = CascadesOptimizer::create(self.metadata.clone());
+            return cascades.optimize(s_expr);
+        }
         Ok(s_expr)
     }
 
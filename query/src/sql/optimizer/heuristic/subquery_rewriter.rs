@@ -119,19 +119,28 @@ impl SubqueryRewriter {
                 Ok(SExpr::create_unary(plan.into(), input))
             }
 
-            RelOperator::LogicalInnerJoin(_) => Ok(SExpr::create_binary(
+            RelOperator::LogicalInnerJoin(_)
+            | RelOperator::UnionAll(_)
+            | RelOperator::SetOperation(_)
+            | RelOperator::RecursiveCte(_) => Ok(SExpr::create_binary(
                 s_expr.plan().clone(),
                 self.rewrite(s_expr.child(0)?)?,
                 self.rewrite(s_expr.child(1)?)?,
             )),
 
-            RelOperator::Project(_) | RelOperator::Limit(_) | RelOperator::Sort(_) => Ok(
-                SExpr::create_unary(s_expr.plan().clone(), self.rewrite(s_expr.child(0)?)?),
-            ),
+            RelOperator::Project(_)
+            | RelOperator::Limit(_)
+            | RelOperator::Sort(_)
+            | RelOperator::Window(_) => Ok(SExpr::create_unary(
+                s_expr.plan().clone(),
+                self.rewrite(s_expr.child(0)?)?,
+            )),
 
             RelOperator::LogicalGet(_) => Ok(s_expr.clone()),
 
             RelOperator::PhysicalHashJoin(_)
+            | RelOperator::PhysicalNestedLoopJoin(_)
+            | RelOperator::PhysicalSortMergeJoin(_)
             | RelOperator::Pattern(_)
             | RelOperator::Exchange(_)
             | RelOperator::PhysicalScan(_) => Err(ErrorCode::LogicalError("Invalid plan type")),
@@ -198,6 +207,8 @@ impl SubqueryRewriter {
 
             Scalar::AggregateFunction(_) => Ok((scalar.clone(), s_expr.clone())),
 
+            Scalar::WindowFunction(_) => Ok((scalar.clone(), s_expr.clone())),
+
             Scalar::FunctionCall(func) => {
                 let mut args = vec![];
                 let mut s_expr = s_expr.clone();
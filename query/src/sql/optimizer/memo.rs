@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use common_exception::Result;
 
+use crate::sql::optimizer::cost::StatInfo;
 use crate::sql::optimizer::group::Group;
 use crate::sql::optimizer::m_expr::MExpr;
 use crate::sql::optimizer::s_expr::SExpr;
+use crate::sql::optimizer::RelExpr;
 use crate::sql::IndexType;
 
 /// `Memo` is a search space which memoize possible plans of a query.
@@ -26,6 +30,10 @@ use crate::sql::IndexType;
 pub struct Memo {
     groups: Vec<Group>,
     root: Option<IndexType>,
+
+    /// Group index of every expression in the memo, keyed by the fingerprint of the
+    /// expression, to prevent the same expression from being inserted repeatedly.
+    m_expr_lookup: HashMap<String, IndexType>,
 }
 
 impl Memo {
@@ -33,6 +41,7 @@ impl Memo {
         Memo {
             groups: vec![],
             root: None,
+            m_expr_lookup: HashMap::new(),
         }
     }
 
@@ -64,36 +73,47 @@ impl Memo {
             children_group.push(group);
         }
 
-        if let Some(group_index) = expression.original_group() {
-            // The expression is extracted by PatternExtractor, no need to reinsert.
-            return Ok(group_index);
+        if target_group.is_none() {
+            if let Some(group_index) = expression.original_group() {
+                // The expression is extracted by PatternExtractor, no need to reinsert.
+                return Ok(group_index);
+            }
         }
 
-        let mut _new_group = false;
+        let plan = expression.plan();
+        let fingerprint = format!("{:?}{:?}", plan, &children_group);
+        if let Some(group_index) = self.m_expr_lookup.get(&fingerprint) {
+            // The same expression has been inserted, e.g. generated by exploration again.
+            return Ok(*group_index);
+        }
 
         // Create new group if not specified
         let group_index = match target_group {
             Some(index) => index,
-            _ => {
-                _new_group = true;
-                self.add_group()
-            }
+            _ => self.add_group(),
         };
 
-        // if new_group {
-        // let group = self.group_mut(group_index);
-        // let relational_prop = expression.compute_relational_prop();
-        // group.set_relational_prop(relational_prop);
-        // }
-
-        let plan = expression.plan();
-
         let group_expression = MExpr::create(group_index, plan.clone(), children_group);
+        if self.group(group_index).relational_prop().is_none() {
+            // Relational property can only be derived from logical expressions,
+            // which are always inserted before the physical ones of a group.
+            if let Ok(relational_prop) =
+                RelExpr::with_m_expr(&group_expression, self).derive_relational_prop()
+            {
+                self.group_mut(group_index)
+                    .set_relational_prop(relational_prop);
+            }
+        }
         self.insert_m_expr(group_index, group_expression)?;
+        self.m_expr_lookup.insert(fingerprint, group_index);
 
         Ok(group_index)
     }
 
+    pub fn num_groups(&self) -> usize {
+        self.groups.len()
+    }
+
     pub fn group(&self, index: IndexType) -> &Group {
         &self.groups[index]
     }
@@ -102,6 +122,10 @@ impl Memo {
         self.group_mut(group_index).insert(expression)
     }
 
+    pub fn set_stat_info(&mut self, group_index: IndexType, stat_info: StatInfo) {
+        self.group_mut(group_index).set_stat_info(stat_info);
+    }
+
     fn group_mut(&mut self, index: IndexType) -> &mut Group {
         &mut self.groups[index]
     }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod cascades;
+mod cost;
 mod group;
 mod heuristic;
 mod m_expr;
@@ -26,6 +27,8 @@ mod util;
 use std::sync::Arc;
 
 use common_exception::Result;
+pub use cost::Cost;
+pub use cost::CostEstimation;
 pub use heuristic::HeuristicOptimizer;
 pub use heuristic::DEFAULT_REWRITE_RULES;
 pub use m_expr::MExpr;
@@ -121,11 +124,9 @@ pub fn optimize_query(
     let enable_distributed_query = opt_ctx.config.enable_distributed_optimization
         && !contains_local_table_scan(&s_expr, &metadata);
 
-    let mut heuristic = HeuristicOptimizer::new(ctx, metadata, rules, enable_distributed_query);
-    let optimized = heuristic.optimize(s_expr)?;
-    // TODO: enable cascades optimizer
-    // let mut cascades = CascadesOptimizer::create(ctx);
-    // cascades.optimize(s_expr)
+    let enable_join_reorder = ctx.get_settings().get_enable_cbo()? != 0;
 
-    Ok(optimized)
+    let mut heuristic = HeuristicOptimizer::new(ctx, metadata, rules, enable_distributed_query);
+    heuristic.set_enable_join_reorder(enable_join_reorder);
+    heuristic.optimize(s_expr)
 }
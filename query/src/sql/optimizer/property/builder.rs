@@ -63,7 +63,7 @@ impl<'a> RelExpr<'a> {
                 rel_expr.derive_relational_prop()
             }
             RelExpr::MExpr { expr, memo } => memo
-                .group(expr.group_index())
+                .group(expr.children()[index])
                 .relational_prop()
                 .cloned()
                 .ok_or_else(|| {
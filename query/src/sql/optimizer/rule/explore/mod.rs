@@ -0,0 +1,30 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod rule_commute_join;
+mod rule_right_associate_join;
+
+pub use rule_commute_join::RuleCommuteJoin;
+pub use rule_right_associate_join::RuleRightAssociateJoin;
+
+use crate::sql::plans::JoinType;
+use crate::sql::plans::LogicalInnerJoin;
+
+/// Only the inner joins and cross joins which are not generated from
+/// correlated subqueries can be reordered freely.
+fn can_reorder(join: &LogicalInnerJoin) -> bool {
+    matches!(join.join_type, JoinType::Inner | JoinType::Cross)
+        && join.marker_index.is_none()
+        && !join.from_correlated_subquery
+}
@@ -0,0 +1,207 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use super::can_reorder;
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::Memo;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::JoinType;
+use crate::sql::plans::LogicalInnerJoin;
+use crate::sql::plans::PatternPlan;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+use crate::sql::ScalarExpr;
+
+/// Rotate a left deep join to the right: `(A ⋈ B) ⋈ C` => `A ⋈ (B ⋈ C)`.
+/// Together with `RuleCommuteJoin`, every join order of the inner joins can be explored.
+pub struct RuleRightAssociateJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleRightAssociateJoin {
+    pub fn new() -> Self {
+        RuleRightAssociateJoin {
+            id: RuleID::RightAssociateJoin,
+            pattern: SExpr::create_binary(
+                PatternPlan {
+                    plan_type: RelOp::LogicalInnerJoin,
+                }
+                .into(),
+                SExpr::create_binary(
+                    PatternPlan {
+                        plan_type: RelOp::LogicalInnerJoin,
+                    }
+                    .into(),
+                    SExpr::create_leaf(
+                        PatternPlan {
+                            plan_type: RelOp::Pattern,
+                        }
+                        .into(),
+                    ),
+                    SExpr::create_leaf(
+                        PatternPlan {
+                            plan_type: RelOp::Pattern,
+                        }
+                        .into(),
+                    ),
+                ),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleRightAssociateJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, s_expr: &SExpr, state: &mut TransformState) -> Result<()> {
+        // The output columns of the children are required to redistribute the join conditions
+        let memo = match state.memo() {
+            Some(memo) => memo,
+            None => return Ok(()),
+        };
+
+        let top_join: LogicalInnerJoin = s_expr.plan().clone().try_into()?;
+        let left_child = s_expr.child(0)?;
+        let bottom_join: LogicalInnerJoin = left_child.plan().clone().try_into()?;
+        if !can_reorder(&top_join) || !can_reorder(&bottom_join) {
+            return Ok(());
+        }
+
+        let a = left_child.child(0)?;
+        let b = left_child.child(1)?;
+        let c = s_expr.child(1)?;
+        let (a_columns, b_columns, c_columns) = match (
+            output_columns(memo, a),
+            output_columns(memo, b),
+            output_columns(memo, c),
+        ) {
+            (Some(a_columns), Some(b_columns), Some(c_columns)) => {
+                (a_columns, b_columns, c_columns)
+            }
+            _ => return Ok(()),
+        };
+        let bc_columns: ColumnSet = b_columns.union(&c_columns).cloned().collect();
+
+        // Conditions of the new joins `B ⋈ C` and `A ⋈ (B ⋈ C)`
+        let mut new_bottom = JoinConditions::default();
+        let mut new_top = JoinConditions::default();
+        let equi_conditions = bottom_join
+            .left_conditions
+            .into_iter()
+            .zip(bottom_join.right_conditions.into_iter())
+            .chain(
+                top_join
+                    .left_conditions
+                    .into_iter()
+                    .zip(top_join.right_conditions.into_iter()),
+            );
+        for (left, right) in equi_conditions {
+            let left_used = left.used_columns();
+            let right_used = right.used_columns();
+            if left_used.is_subset(&b_columns) && right_used.is_subset(&c_columns) {
+                new_bottom.add_equi_condition(left, right);
+            } else if left_used.is_subset(&c_columns) && right_used.is_subset(&b_columns) {
+                new_bottom.add_equi_condition(right, left);
+            } else if left_used.is_subset(&a_columns) && right_used.is_subset(&bc_columns) {
+                new_top.add_equi_condition(left, right);
+            } else if left_used.is_subset(&bc_columns) && right_used.is_subset(&a_columns) {
+                new_top.add_equi_condition(right, left);
+            } else {
+                // The condition can't be evaluated by either of the new joins
+                return Ok(());
+            }
+        }
+        for condition in bottom_join
+            .other_conditions
+            .into_iter()
+            .chain(top_join.other_conditions.into_iter())
+        {
+            if condition.used_columns().is_subset(&bc_columns) {
+                new_bottom.other_conditions.push(condition);
+            } else {
+                new_top.other_conditions.push(condition);
+            }
+        }
+
+        let (new_bottom, new_top) = match (new_bottom.into_join(), new_top.into_join()) {
+            (Some(new_bottom), Some(new_top)) => (new_bottom, new_top),
+            _ => return Ok(()),
+        };
+        state.add_result(SExpr::create_binary(
+            new_top.into(),
+            a.clone(),
+            SExpr::create_binary(new_bottom.into(), b.clone(), c.clone()),
+        ));
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
+
+fn output_columns(memo: &Memo, s_expr: &SExpr) -> Option<ColumnSet> {
+    let group_index = s_expr.original_group()?;
+    memo.group(group_index)
+        .relational_prop()
+        .map(|prop| prop.output_columns.clone())
+}
+
+#[derive(Default)]
+struct JoinConditions {
+    left_conditions: Vec<Scalar>,
+    right_conditions: Vec<Scalar>,
+    other_conditions: Vec<Scalar>,
+}
+
+impl JoinConditions {
+    fn add_equi_condition(&mut self, left: Scalar, right: Scalar) {
+        self.left_conditions.push(left);
+        self.right_conditions.push(right);
+    }
+
+    /// Returns `None` if the conditions can't form an inner join or a cross join.
+    fn into_join(self) -> Option<LogicalInnerJoin> {
+        let join_type = if !self.left_conditions.is_empty() {
+            JoinType::Inner
+        } else if self.other_conditions.is_empty() {
+            JoinType::Cross
+        } else {
+            return None;
+        };
+        Some(LogicalInnerJoin {
+            left_conditions: self.left_conditions,
+            right_conditions: self.right_conditions,
+            other_conditions: self.other_conditions,
+            join_type,
+            marker_index: None,
+            from_correlated_subquery: false,
+        })
+    }
+}
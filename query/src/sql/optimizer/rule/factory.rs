@@ -20,6 +20,8 @@ use super::rewrite::RuleNormalizeScalarFilter;
 use super::rewrite::RulePushDownFilterEvalScalar;
 use super::rewrite::RulePushDownFilterJoin;
 use super::rewrite::RulePushDownFilterProject;
+use crate::sql::optimizer::rule::explore::RuleCommuteJoin;
+use crate::sql::optimizer::rule::explore::RuleRightAssociateJoin;
 use crate::sql::optimizer::rule::rewrite::RuleEliminateFilter;
 use crate::sql::optimizer::rule::rewrite::RuleEliminateProject;
 use crate::sql::optimizer::rule::rewrite::RuleMergeEvalScalar;
@@ -29,6 +31,8 @@ use crate::sql::optimizer::rule::rewrite::RulePushDownFilterScan;
 use crate::sql::optimizer::rule::rewrite::RuleSplitAggregate;
 use crate::sql::optimizer::rule::rule_implement_get::RuleImplementGet;
 use crate::sql::optimizer::rule::rule_implement_hash_join::RuleImplementHashJoin;
+use crate::sql::optimizer::rule::rule_implement_nested_loop_join::RuleImplementNestedLoopJoin;
+use crate::sql::optimizer::rule::rule_implement_sort_merge_join::RuleImplementSortMergeJoin;
 use crate::sql::optimizer::rule::RuleID;
 use crate::sql::optimizer::rule::RulePtr;
 
@@ -43,6 +47,8 @@ impl RuleFactory {
         match id {
             RuleID::ImplementGet => Ok(Box::new(RuleImplementGet::new())),
             RuleID::ImplementHashJoin => Ok(Box::new(RuleImplementHashJoin::new())),
+            RuleID::ImplementNestedLoopJoin => Ok(Box::new(RuleImplementNestedLoopJoin::new())),
+            RuleID::ImplementSortMergeJoin => Ok(Box::new(RuleImplementSortMergeJoin::new())),
             RuleID::EliminateEvalScalar => Ok(Box::new(RuleEliminateEvalScalar::new())),
             RuleID::PushDownFilterProject => Ok(Box::new(RulePushDownFilterProject::new())),
             RuleID::PushDownFilterEvalScalar => Ok(Box::new(RulePushDownFilterEvalScalar::new())),
@@ -58,6 +64,8 @@ impl RuleFactory {
             RuleID::NormalizeDisjunctiveFilter => {
                 Ok(Box::new(RuleNormalizeDisjunctiveFilter::new()))
             }
+            RuleID::CommuteJoin => Ok(Box::new(RuleCommuteJoin::new())),
+            RuleID::RightAssociateJoin => Ok(Box::new(RuleRightAssociateJoin::new())),
         }
     }
 }
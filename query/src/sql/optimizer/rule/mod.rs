@@ -19,10 +19,13 @@ use common_exception::Result;
 
 use crate::sql::optimizer::SExpr;
 
+mod explore;
 mod factory;
 mod rewrite;
 mod rule_implement_get;
 mod rule_implement_hash_join;
+mod rule_implement_nested_loop_join;
+mod rule_implement_sort_merge_join;
 mod rule_set;
 mod transform_state;
 
@@ -58,9 +61,15 @@ pub enum RuleID {
     MergeFilter,
     SplitAggregate,
 
+    // Exploration rules
+    CommuteJoin,
+    RightAssociateJoin,
+
     // Implementation rules
     ImplementGet,
     ImplementHashJoin,
+    ImplementNestedLoopJoin,
+    ImplementSortMergeJoin,
 }
 
 impl Display for RuleID {
@@ -68,6 +77,8 @@ impl Display for RuleID {
         match self {
             RuleID::ImplementGet => write!(f, "ImplementGet"),
             RuleID::ImplementHashJoin => write!(f, "ImplementHashJoin"),
+            RuleID::ImplementNestedLoopJoin => write!(f, "ImplementNestedLoopJoin"),
+            RuleID::ImplementSortMergeJoin => write!(f, "ImplementSortMergeJoin"),
             RuleID::PushDownFilterProject => write!(f, "PushDownFilterProject"),
             RuleID::PushDownFilterEvalScalar => write!(f, "PushDownFilterEvalScalar"),
             RuleID::PushDownFilterJoin => write!(f, "PushDownFilterJoin"),
@@ -81,6 +92,8 @@ impl Display for RuleID {
             RuleID::NormalizeScalarFilter => write!(f, "NormalizeScalarFilter"),
             RuleID::SplitAggregate => write!(f, "SplitAggregate"),
             RuleID::NormalizeDisjunctiveFilter => write!(f, "NormalizeDisjunctiveFilter"),
+            RuleID::CommuteJoin => write!(f, "CommuteJoin"),
+            RuleID::RightAssociateJoin => write!(f, "RightAssociateJoin"),
         }
     }
 }
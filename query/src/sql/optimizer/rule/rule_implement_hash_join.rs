@@ -14,6 +14,7 @@
 
 use common_exception::Result;
 
+use crate::sql::optimizer::rule::rule_implement_nested_loop_join::is_nested_loop_join;
 use crate::sql::optimizer::rule::transform_state::TransformState;
 use crate::sql::optimizer::rule::Rule;
 use crate::sql::optimizer::rule::RuleID;
@@ -62,6 +63,10 @@ impl Rule for RuleImplementHashJoin {
     fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
         let plan = expression.plan().clone();
         let logical_join: LogicalInnerJoin = plan.try_into()?;
+        if is_nested_loop_join(&logical_join) {
+            // Implemented by `RuleImplementNestedLoopJoin`
+            return Ok(());
+        }
 
         let result = SExpr::create(
             PhysicalHashJoin {
@@ -71,6 +76,7 @@ impl Rule for RuleImplementHashJoin {
                 join_type: logical_join.join_type,
                 marker_index: logical_join.marker_index,
                 from_correlated_subquery: logical_join.from_correlated_subquery,
+                estimation: None,
             }
             .into(),
             expression.children().to_vec(),
@@ -0,0 +1,96 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::JoinType;
+use crate::sql::plans::LogicalInnerJoin;
+use crate::sql::plans::PatternPlan;
+use crate::sql::plans::PhysicalNestedLoopJoin;
+use crate::sql::plans::RelOp;
+
+/// Check if an inner join only has non-equi conditions, e.g. `t1.a < t2.b`, which cannot
+/// be used as the keys of hash join.
+pub fn is_nested_loop_join(join: &LogicalInnerJoin) -> bool {
+    join.join_type == JoinType::Inner
+        && join.left_conditions.is_empty()
+        && !join.other_conditions.is_empty()
+}
+
+pub struct RuleImplementNestedLoopJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleImplementNestedLoopJoin {
+    pub fn new() -> Self {
+        RuleImplementNestedLoopJoin {
+            id: RuleID::ImplementNestedLoopJoin,
+            pattern: SExpr::create_binary(
+                PatternPlan {
+                    plan_type: RelOp::LogicalInnerJoin,
+                }
+                .into(),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleImplementNestedLoopJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let plan = expression.plan().clone();
+        let logical_join: LogicalInnerJoin = plan.try_into()?;
+        if !is_nested_loop_join(&logical_join) {
+            return Ok(());
+        }
+
+        let result = SExpr::create(
+            PhysicalNestedLoopJoin {
+                conditions: logical_join.other_conditions,
+                join_type: logical_join.join_type,
+                estimation: None,
+            }
+            .into(),
+            expression.children().to_vec(),
+            expression.original_group(),
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::rule::transform_state::TransformState;
+use crate::sql::optimizer::rule::Rule;
+use crate::sql::optimizer::rule::RuleID;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::JoinType;
+use crate::sql::plans::LogicalInnerJoin;
+use crate::sql::plans::PatternPlan;
+use crate::sql::plans::PhysicalSortMergeJoin;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+
+/// Implement inner equi-join with sort-merge join, which is only chosen by the cascades
+/// optimizer if it's cheaper than hash join, i.e. the inputs are clustered by the join keys.
+pub struct RuleImplementSortMergeJoin {
+    id: RuleID,
+    pattern: SExpr,
+}
+
+impl RuleImplementSortMergeJoin {
+    pub fn new() -> Self {
+        RuleImplementSortMergeJoin {
+            id: RuleID::ImplementSortMergeJoin,
+            pattern: SExpr::create_binary(
+                PatternPlan {
+                    plan_type: RelOp::LogicalInnerJoin,
+                }
+                .into(),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+                SExpr::create_leaf(
+                    PatternPlan {
+                        plan_type: RelOp::Pattern,
+                    }
+                    .into(),
+                ),
+            ),
+        }
+    }
+}
+
+impl Rule for RuleImplementSortMergeJoin {
+    fn id(&self) -> RuleID {
+        self.id
+    }
+
+    fn apply(&self, expression: &SExpr, state: &mut TransformState) -> Result<()> {
+        let plan = expression.plan().clone();
+        let logical_join: LogicalInnerJoin = plan.try_into()?;
+
+        // The inputs are sorted by the columns of the join keys.
+        let is_column = |scalar: &Scalar| matches!(scalar, Scalar::BoundColumnRef(_));
+        if logical_join.join_type != JoinType::Inner
+            || logical_join.left_conditions.is_empty()
+            || !logical_join.left_conditions.iter().all(is_column)
+            || !logical_join.right_conditions.iter().all(is_column)
+        {
+            return Ok(());
+        }
+
+        let result = SExpr::create(
+            PhysicalSortMergeJoin {
+                left_keys: logical_join.left_conditions,
+                right_keys: logical_join.right_conditions,
+                other_conditions: logical_join.other_conditions,
+                join_type: logical_join.join_type,
+                estimation: None,
+            }
+            .into(),
+            expression.children().to_vec(),
+            expression.original_group(),
+        );
+        state.add_result(result);
+
+        Ok(())
+    }
+
+    fn pattern(&self) -> &SExpr {
+        &self.pattern
+    }
+}
@@ -13,15 +13,34 @@
 // limitations under the License.
 
 use crate::sql::optimizer::s_expr::SExpr;
+use crate::sql::optimizer::Memo;
 
 #[derive(Clone, Default)]
-pub struct TransformState {
+pub struct TransformState<'a> {
     results: Vec<SExpr>,
+
+    /// `Memo` of the cascades optimizer, in which the properties of the groups
+    /// referenced by the expression to transform can be found.
+    memo: Option<&'a Memo>,
 }
 
-impl TransformState {
+impl<'a> TransformState<'a> {
     pub fn new() -> Self {
-        TransformState { results: vec![] }
+        TransformState {
+            results: vec![],
+            memo: None,
+        }
+    }
+
+    pub fn with_memo(memo: &'a Memo) -> Self {
+        TransformState {
+            results: vec![],
+            memo: Some(memo),
+        }
+    }
+
+    pub fn memo(&self) -> Option<&'a Memo> {
+        self.memo
     }
 
     pub fn add_result(&mut self, result: SExpr) {
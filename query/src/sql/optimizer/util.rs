@@ -44,6 +44,8 @@ pub fn validate_distributed_query(s_expr: &SExpr) -> bool {
                 | JoinType::Mark
                 | JoinType::Single => false,
             },
+            RelOperator::PhysicalNestedLoopJoin(join) => join.join_type == JoinType::Inner,
+            RelOperator::PhysicalSortMergeJoin(join) => join.join_type == JoinType::Inner,
             _ => true,
         }
 }
@@ -23,6 +23,7 @@ use common_datavalues::DataTypeImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::sql::binder::contain_window_function;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::select::SelectList;
 use crate::sql::binder::Binder;
@@ -136,6 +137,10 @@ impl<'a> AggregateRewriter<'a> {
             Scalar::SubqueryExpr(_) => Ok(scalar.clone()),
 
             Scalar::AggregateFunction(agg_func) => self.replace_aggregate_function(agg_func),
+
+            // Window functions in select list have been replaced by `WindowRewriter`,
+            // and aggregate functions in them are rewritten there.
+            Scalar::WindowFunction(_) => Ok(scalar.clone()),
         }
     }
 
@@ -348,6 +353,11 @@ impl<'a> Binder {
                 .bind(expr)
                 .await
                 .or_else(|e| Self::resolve_alias_item(bind_context, expr, available_aliases, e))?;
+            if contain_window_function(&scalar_expr) {
+                return Err(ErrorCode::SemanticError(expr.span().display_error(
+                    "window functions are not allowed in GROUP BY clause".to_string(),
+                )));
+            }
 
             if bind_context
                 .aggregate_info
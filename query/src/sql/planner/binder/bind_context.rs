@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use common_ast::ast::Identifier;
 use common_ast::ast::TableAlias;
 use common_ast::DisplayError;
@@ -20,7 +22,9 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::AggregateInfo;
+use super::WindowInfo;
 use crate::sql::common::IndexType;
+use crate::sql::optimizer::SExpr;
 
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct ColumnBinding {
@@ -41,6 +45,16 @@ pub struct ColumnBinding {
     pub visible_in_unqualified_wildcard: bool,
 }
 
+/// Common table expression declared in `WITH` clause, which is bound once and copied
+/// at where it is referenced.
+#[derive(Clone, Debug)]
+pub struct CteInfo {
+    /// Bound CTE query
+    pub s_expr: SExpr,
+    /// Output columns of the CTE query, named with the CTE name and column aliases
+    pub columns: Vec<ColumnBinding>,
+}
+
 /// `BindContext` stores all the free variables in a query and tracks the context of binding procedure.
 #[derive(Clone, Default, Debug)]
 pub struct BindContext {
@@ -50,6 +64,8 @@ pub struct BindContext {
 
     pub aggregate_info: AggregateInfo,
 
+    pub window_info: WindowInfo,
+
     /// True if there is aggregation in current context, which means
     /// non-grouping columns cannot be referenced outside aggregation
     /// functions, otherwise a grouping error will be raised.
@@ -57,6 +73,9 @@ pub struct BindContext {
 
     /// Format type of query output.
     pub format: Option<String>,
+
+    /// CTEs visible in current context, keyed by CTE name.
+    pub ctes_map: HashMap<String, CteInfo>,
 }
 
 impl BindContext {
@@ -65,12 +84,15 @@ impl BindContext {
     }
 
     pub fn with_parent(parent: Box<BindContext>) -> Self {
+        let ctes_map = parent.ctes_map.clone();
         BindContext {
             parent: Some(parent),
             columns: vec![],
             aggregate_info: Default::default(),
+            window_info: Default::default(),
             in_grouping: false,
             format: None,
+            ctes_map,
         }
     }
 
@@ -78,6 +100,7 @@ impl BindContext {
     pub fn replace(&self) -> Self {
         let mut bind_context = BindContext::new();
         bind_context.parent = self.parent.clone();
+        bind_context.ctes_map = self.ctes_map.clone();
         bind_context
     }
 
@@ -98,20 +121,31 @@ impl BindContext {
     /// Apply table alias like `SELECT * FROM t AS t1(a, b, c)`.
     /// This method will rename column bindings according to table alias.
     pub fn apply_table_alias(&mut self, alias: &TableAlias) -> Result<()> {
+        let columns_alias = alias
+            .columns
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        self.apply_alias(&alias.name.name.to_lowercase(), &columns_alias)
+    }
+
+    /// Rename table name and column names of column bindings, the column names are
+    /// renamed in order and the rest columns keep their names.
+    pub fn apply_alias(&mut self, table_name: &str, columns_alias: &[String]) -> Result<()> {
         for column in self.columns.iter_mut() {
             column.database_name = None;
-            column.table_name = Some(alias.name.name.to_lowercase());
+            column.table_name = Some(table_name.to_string());
         }
 
-        if alias.columns.len() > self.columns.len() {
+        if columns_alias.len() > self.columns.len() {
             return Err(ErrorCode::SemanticError(format!(
                 "table has {} columns available but {} columns specified",
                 self.columns.len(),
-                alias.columns.len()
+                columns_alias.len()
             )));
         }
-        for (index, column_name) in alias.columns.iter().map(ToString::to_string).enumerate() {
-            self.columns[index].column_name = column_name;
+        for (index, column_name) in columns_alias.iter().enumerate() {
+            self.columns[index].column_name = column_name.clone();
         }
         Ok(())
     }
@@ -31,6 +31,7 @@ use common_storage::parse_uri_location;
 use common_storage::UriLocation;
 
 use crate::sessions::TableContext;
+use crate::sql::binder::insert::insertable_schema;
 use crate::sql::binder::Binder;
 use crate::sql::plans::CopyPlanV2;
 use crate::sql::plans::Plan;
@@ -224,7 +225,7 @@ impl<'a> Binder {
         let from = ReadDataSourcePlan {
             catalog: dst_catalog_name.to_string(),
             source_info: SourceInfo::StageSource(StageTableInfo {
-                schema: table.schema(),
+                schema: insertable_schema(&table.schema()),
                 stage_info,
                 path,
                 files: vec![],
@@ -282,7 +283,7 @@ impl<'a> Binder {
         let from = ReadDataSourcePlan {
             catalog: dst_catalog_name.to_string(),
             source_info: SourceInfo::StageSource(StageTableInfo {
-                schema: table.schema(),
+                schema: insertable_schema(&table.schema()),
                 stage_info,
                 path,
                 files: vec![],
@@ -0,0 +1,612 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_ast::ast::Query;
+use common_ast::ast::SetExpr;
+use common_ast::ast::SetOperator;
+use common_ast::ast::TableAlias;
+use common_ast::ast::TableReference;
+use common_ast::ast::With;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+use common_meta_types::MetaId;
+use common_planners::SourceInfo;
+
+use crate::catalogs::CATALOG_DEFAULT;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::binder::Binder;
+use crate::sql::binder::ColumnBinding;
+use crate::sql::binder::CteInfo;
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::SExpr;
+use crate::sql::plans::Aggregate;
+use crate::sql::plans::AndExpr;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::EvalScalar;
+use crate::sql::plans::Filter;
+use crate::sql::plans::FunctionCall;
+use crate::sql::plans::LogicalGet;
+use crate::sql::plans::LogicalInnerJoin;
+use crate::sql::plans::Operator;
+use crate::sql::plans::OrExpr;
+use crate::sql::plans::Project;
+use crate::sql::plans::RecursiveCte;
+use crate::sql::plans::RelOperator;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarItem;
+use crate::sql::plans::SetOperation;
+use crate::sql::plans::Sort;
+use crate::sql::plans::SortItem;
+use crate::sql::plans::SubqueryExpr;
+use crate::sql::plans::UnionAll;
+use crate::sql::plans::Window;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
+use crate::sql::BindContext;
+use crate::sql::IndexType;
+use crate::sql::MetadataRef;
+use crate::storages::memory::MemoryTable;
+use crate::storages::Table;
+use crate::storages::ToReadDataSourcePlan;
+
+impl<'a> Binder {
+    /// Bind the CTEs declared in `WITH` clause and register them into `bind_context`, so
+    /// that they can be referenced as tables in the query.
+    pub(super) async fn bind_with(
+        &mut self,
+        bind_context: &mut BindContext,
+        with: &With<'a>,
+    ) -> Result<()> {
+        let mut cte_names = HashSet::new();
+        for cte in with.ctes.iter() {
+            let cte_name = cte.alias.name.name.to_lowercase();
+            if !cte_names.insert(cte_name.clone()) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "duplicate CTE name `{cte_name}` in WITH clause"
+                )));
+            }
+
+            let columns_alias = cte
+                .alias
+                .columns
+                .iter()
+                .map(|column| column.name.to_lowercase())
+                .collect::<Vec<_>>();
+
+            // CTE query can only see the CTEs declared before it.
+            let mut cte_context = BindContext::new();
+            cte_context.ctes_map = bind_context.ctes_map.clone();
+            let (s_expr, mut cte_bind_context) =
+                if with.recursive && query_references_table(&cte.query, &cte_name) {
+                    self.bind_recursive_cte(&cte_context, &cte_name, &columns_alias, &cte.query)
+                        .await?
+                } else {
+                    self.bind_query(&cte_context, &cte.query).await?
+                };
+            cte_bind_context.apply_alias(&cte_name, &columns_alias)?;
+
+            bind_context.ctes_map.insert(cte_name, CteInfo {
+                s_expr,
+                columns: cte_bind_context.columns,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Bind a reference of CTE in `FROM` clause. The bound CTE query is copied with fresh
+    /// table and column indexes, so the CTE can be referenced more than once.
+    pub(super) fn bind_cte(
+        &mut self,
+        bind_context: &BindContext,
+        cte_info: &CteInfo,
+        alias: &Option<TableAlias<'a>>,
+    ) -> Result<(SExpr, BindContext)> {
+        let mut copier = CteCopier::new(self.ctx.clone(), self.metadata.clone());
+        let s_expr = copier.copy_s_expr(&cte_info.s_expr)?;
+
+        let mut cte_bind_context = BindContext::with_parent(Box::new(bind_context.clone()));
+        for column in cte_info.columns.iter() {
+            cte_bind_context.add_column_binding(copier.copy_column_binding(column));
+        }
+        if let Some(alias) = alias {
+            cte_bind_context.apply_table_alias(alias)?;
+        }
+        Ok((s_expr, cte_bind_context))
+    }
+
+    /// Bind a recursive CTE in the form of `non_recursive_term UNION ALL recursive_term`,
+    /// the reference of the CTE in the recursive term reads a working table holding the
+    /// rows produced by the last iteration. See `RecursiveCte` for the evaluation.
+    async fn bind_recursive_cte(
+        &mut self,
+        cte_context: &BindContext,
+        cte_name: &str,
+        columns_alias: &[String],
+        query: &Query<'a>,
+    ) -> Result<(SExpr, BindContext)> {
+        let set_operation = match &query.body {
+            SetExpr::SetOperation(set_operation)
+                if set_operation.op == SetOperator::Union
+                    && set_operation.all
+                    && query.with.is_none()
+                    && query.order_by.is_empty()
+                    && query.limit.is_empty()
+                    && query.offset.is_none() =>
+            {
+                set_operation
+            }
+            _ => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "recursive CTE `{cte_name}` must be in the form of `non_recursive_term UNION ALL recursive_term`"
+                )));
+            }
+        };
+        if set_expr_references_table(&set_operation.left, cte_name) {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive reference to CTE `{cte_name}` must not appear within its non-recursive term"
+            )));
+        }
+
+        let (left_expr, mut left_context) = self
+            .bind_set_expr(cte_context, &set_operation.left, &[])
+            .await?;
+        left_context.apply_alias(cte_name, columns_alias)?;
+
+        // Column types of the CTE are determined by the non-recursive term.
+        let fields = left_context
+            .columns
+            .iter()
+            .map(|column| DataField::new(&column.column_name, *column.data_type.clone()))
+            .collect::<Vec<_>>();
+        let working_table: Arc<dyn Table> = Arc::new(MemoryTable::create_with_blocks(
+            create_cte_table_info(&self.ctx, cte_name, DataSchemaRefExt::create(fields)),
+            vec![],
+        ));
+        self.ctx.add_materialized_table(working_table.clone());
+
+        let database = self.ctx.get_current_database();
+        let source = working_table.read_plan(self.ctx.clone(), None).await?;
+        let table_index = self.metadata.write().add_table(
+            CATALOG_DEFAULT.to_owned(),
+            database.clone(),
+            working_table.clone(),
+            source,
+            None,
+        );
+        let (working_expr, mut working_context) =
+            self.bind_base_table(&BindContext::new(), &database, table_index)?;
+        working_context.apply_alias(cte_name, &[])?;
+
+        let mut recursive_context = cte_context.clone();
+        recursive_context
+            .ctes_map
+            .insert(cte_name.to_string(), CteInfo {
+                s_expr: working_expr,
+                columns: working_context.columns,
+            });
+        let (right_expr, right_context) = self
+            .bind_set_expr(&recursive_context, &set_operation.right, &[])
+            .await?;
+        if right_context.columns.len() != left_context.columns.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "recursive term of CTE `{cte_name}` has {} columns, but non-recursive term has {} columns",
+                right_context.columns.len(),
+                left_context.columns.len()
+            )));
+        }
+
+        // The output columns are derived columns, like `UNION ALL`.
+        let mut bind_context = left_context.clone();
+        let mut output_columns = Vec::with_capacity(left_context.columns.len());
+        for column in bind_context.columns.iter_mut() {
+            column.index = self.metadata.write().add_column(
+                column.column_name.clone(),
+                *column.data_type.clone(),
+                None,
+            );
+            output_columns.push(column.index);
+        }
+
+        let recursive_cte = RecursiveCte {
+            cte_name: cte_name.to_string(),
+            working_table: working_table.get_id(),
+            left_columns: left_context.columns,
+            right_columns: right_context.columns,
+            output_columns,
+        };
+        let s_expr = SExpr::create_binary(recursive_cte.into(), left_expr, right_expr);
+        Ok((s_expr, bind_context))
+    }
+}
+
+fn create_cte_table_info(ctx: &QueryContext, cte_name: &str, schema: DataSchemaRef) -> TableInfo {
+    TableInfo {
+        desc: format!("'{}'", cte_name),
+        name: cte_name.to_string(),
+        ident: TableIdent::new(ctx.next_materialized_table_id(), 0),
+        meta: TableMeta {
+            schema,
+            engine: "MEMORY".to_string(),
+            ..Default::default()
+        },
+    }
+}
+
+/// Copy a bound CTE query with fresh table and column indexes in metadata. The working
+/// tables of the recursive CTEs are copied as well, so the copies are evaluated separately.
+struct CteCopier {
+    ctx: Arc<QueryContext>,
+    metadata: MetadataRef,
+    /// Mapping from the original column indexes to the copied ones
+    columns: HashMap<IndexType, IndexType>,
+    /// Mapping from the original working table ids to the copied working tables
+    working_tables: HashMap<MetaId, Arc<dyn Table>>,
+}
+
+impl CteCopier {
+    fn new(ctx: Arc<QueryContext>, metadata: MetadataRef) -> Self {
+        CteCopier {
+            ctx,
+            metadata,
+            columns: HashMap::new(),
+            working_tables: HashMap::new(),
+        }
+    }
+
+    fn copy_s_expr(&mut self, s_expr: &SExpr) -> Result<SExpr> {
+        // The working table must be copied before the recursive term reading it.
+        if let RelOperator::RecursiveCte(recursive_cte) = s_expr.plan() {
+            self.copy_working_table(recursive_cte)?;
+        }
+
+        let children = s_expr
+            .children()
+            .iter()
+            .map(|child| self.copy_s_expr(child))
+            .collect::<Result<Vec<_>>>()?;
+        let plan = self.copy_operator(s_expr.plan())?;
+        Ok(SExpr::create(plan, children, None))
+    }
+
+    fn copy_working_table(&mut self, recursive_cte: &RecursiveCte) -> Result<()> {
+        let table = self
+            .ctx
+            .get_materialized_table(recursive_cte.working_table)
+            .ok_or_else(|| {
+                ErrorCode::LogicalError(format!(
+                    "Working table of recursive CTE `{}` is not registered",
+                    recursive_cte.cte_name
+                ))
+            })?;
+        let table: Arc<dyn Table> = Arc::new(MemoryTable::create_with_blocks(
+            create_cte_table_info(&self.ctx, &recursive_cte.cte_name, table.schema()),
+            vec![],
+        ));
+        self.ctx.add_materialized_table(table.clone());
+        self.working_tables
+            .insert(recursive_cte.working_table, table);
+        Ok(())
+    }
+
+    fn copy_operator(&mut self, plan: &RelOperator) -> Result<RelOperator> {
+        Ok(match plan {
+            RelOperator::LogicalGet(get) => {
+                let table_index = self.copy_table(get.table_index);
+                LogicalGet {
+                    table_index,
+                    columns: self.copy_column_set(&get.columns),
+                    push_down_predicates: get
+                        .push_down_predicates
+                        .as_ref()
+                        .map(|predicates| self.copy_scalars(predicates))
+                        .transpose()?,
+                }
+                .into()
+            }
+            RelOperator::LogicalInnerJoin(join) => LogicalInnerJoin {
+                left_conditions: self.copy_scalars(&join.left_conditions)?,
+                right_conditions: self.copy_scalars(&join.right_conditions)?,
+                other_conditions: self.copy_scalars(&join.other_conditions)?,
+                marker_index: join.marker_index.map(|index| self.copy_index(index)),
+                ..join.clone()
+            }
+            .into(),
+            RelOperator::Project(project) => Project {
+                columns: self.copy_column_set(&project.columns),
+            }
+            .into(),
+            RelOperator::EvalScalar(eval_scalar) => EvalScalar {
+                items: self.copy_scalar_items(&eval_scalar.items)?,
+            }
+            .into(),
+            RelOperator::Filter(filter) => Filter {
+                predicates: self.copy_scalars(&filter.predicates)?,
+                ..filter.clone()
+            }
+            .into(),
+            RelOperator::Aggregate(aggregate) => Aggregate {
+                group_items: self.copy_scalar_items(&aggregate.group_items)?,
+                aggregate_functions: self.copy_scalar_items(&aggregate.aggregate_functions)?,
+                ..aggregate.clone()
+            }
+            .into(),
+            RelOperator::Sort(sort) => Sort {
+                items: sort
+                    .items
+                    .iter()
+                    .map(|item| SortItem {
+                        index: self.copy_index(item.index),
+                        ..item.clone()
+                    })
+                    .collect(),
+            }
+            .into(),
+            RelOperator::Limit(limit) => limit.clone().into(),
+            RelOperator::Window(window) => Window {
+                function: self.copy_window_function(&window.function)?,
+                index: self.copy_index(window.index),
+            }
+            .into(),
+            RelOperator::UnionAll(union_all) => UnionAll {
+                left_columns: self.copy_column_bindings(&union_all.left_columns),
+                right_columns: self.copy_column_bindings(&union_all.right_columns),
+                output_columns: self.copy_indexes(&union_all.output_columns),
+            }
+            .into(),
+            RelOperator::SetOperation(set_operation) => SetOperation {
+                left_columns: self.copy_column_bindings(&set_operation.left_columns),
+                right_columns: self.copy_column_bindings(&set_operation.right_columns),
+                ..set_operation.clone()
+            }
+            .into(),
+            RelOperator::RecursiveCte(recursive_cte) => RecursiveCte {
+                working_table: self.working_tables[&recursive_cte.working_table].get_id(),
+                left_columns: self.copy_column_bindings(&recursive_cte.left_columns),
+                right_columns: self.copy_column_bindings(&recursive_cte.right_columns),
+                output_columns: self.copy_indexes(&recursive_cte.output_columns),
+                ..recursive_cte.clone()
+            }
+            .into(),
+            RelOperator::PhysicalScan(_)
+            | RelOperator::PhysicalHashJoin(_)
+            | RelOperator::PhysicalNestedLoopJoin(_)
+            | RelOperator::PhysicalSortMergeJoin(_)
+            | RelOperator::Exchange(_)
+            | RelOperator::Pattern(_) => {
+                return Err(ErrorCode::LogicalError(format!(
+                    "Invalid operator in bound CTE query: {:?}",
+                    plan.rel_op()
+                )));
+            }
+        })
+    }
+
+    /// Add the table into metadata again, the columns of the copied table are mapped
+    /// to the original ones by position.
+    fn copy_table(&mut self, table_index: IndexType) -> IndexType {
+        let (table_entry, columns) = {
+            let metadata = self.metadata.read();
+            (
+                metadata.table(table_index).clone(),
+                metadata.columns_by_table_index(table_index),
+            )
+        };
+
+        let mut table = table_entry.table;
+        let mut source = table_entry.source;
+        if let Some(working_table) = self.working_tables.get(&table.get_id()) {
+            table = working_table.clone();
+            source.source_info = SourceInfo::TableSource(table.get_table_info().clone());
+        }
+
+        let mut metadata = self.metadata.write();
+        let new_table_index = metadata.add_table(
+            table_entry.catalog,
+            table_entry.database,
+            table,
+            source,
+            table_entry.column_statistics,
+        );
+        for (column, new_column) in columns
+            .iter()
+            .zip(metadata.columns_by_table_index(new_table_index))
+        {
+            self.columns
+                .insert(column.column_index, new_column.column_index);
+        }
+        new_table_index
+    }
+
+    /// Map a column index to the copied one, the derived columns are copied lazily.
+    fn copy_index(&mut self, index: IndexType) -> IndexType {
+        if let Some(new_index) = self.columns.get(&index) {
+            return *new_index;
+        }
+
+        let mut metadata = self.metadata.write();
+        let column = metadata.column(index).clone();
+        let new_index = metadata.add_column(column.name, column.data_type, None);
+        self.columns.insert(index, new_index);
+        new_index
+    }
+
+    fn copy_indexes(&mut self, indexes: &[IndexType]) -> Vec<IndexType> {
+        indexes
+            .iter()
+            .map(|index| self.copy_index(*index))
+            .collect()
+    }
+
+    fn copy_column_set(&mut self, columns: &ColumnSet) -> ColumnSet {
+        columns
+            .iter()
+            .map(|index| self.copy_index(*index))
+            .collect()
+    }
+
+    fn copy_column_binding(&mut self, column: &ColumnBinding) -> ColumnBinding {
+        ColumnBinding {
+            index: self.copy_index(column.index),
+            ..column.clone()
+        }
+    }
+
+    fn copy_column_bindings(&mut self, columns: &[ColumnBinding]) -> Vec<ColumnBinding> {
+        columns
+            .iter()
+            .map(|column| self.copy_column_binding(column))
+            .collect()
+    }
+
+    fn copy_scalar_items(&mut self, items: &[ScalarItem]) -> Result<Vec<ScalarItem>> {
+        items
+            .iter()
+            .map(|item| {
+                Ok(ScalarItem {
+                    scalar: self.copy_scalar(&item.scalar)?,
+                    index: self.copy_index(item.index),
+                })
+            })
+            .collect()
+    }
+
+    fn copy_scalars(&mut self, scalars: &[Scalar]) -> Result<Vec<Scalar>> {
+        scalars
+            .iter()
+            .map(|scalar| self.copy_scalar(scalar))
+            .collect()
+    }
+
+    fn copy_window_function(&mut self, function: &WindowFunction) -> Result<WindowFunction> {
+        Ok(WindowFunction {
+            args: self.copy_scalars(&function.args)?,
+            partition_by: self.copy_scalars(&function.partition_by)?,
+            order_by: function
+                .order_by
+                .iter()
+                .map(|order_by| {
+                    Ok(WindowOrderBy {
+                        expr: self.copy_scalar(&order_by.expr)?,
+                        ..order_by.clone()
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            ..function.clone()
+        })
+    }
+
+    fn copy_scalar(&mut self, scalar: &Scalar) -> Result<Scalar> {
+        Ok(match scalar {
+            Scalar::BoundColumnRef(column_ref) => BoundColumnRef {
+                column: self.copy_column_binding(&column_ref.column),
+            }
+            .into(),
+            Scalar::ConstantExpr(_) => scalar.clone(),
+            Scalar::AndExpr(expr) => AndExpr {
+                left: Box::new(self.copy_scalar(&expr.left)?),
+                right: Box::new(self.copy_scalar(&expr.right)?),
+                return_type: expr.return_type.clone(),
+            }
+            .into(),
+            Scalar::OrExpr(expr) => OrExpr {
+                left: Box::new(self.copy_scalar(&expr.left)?),
+                right: Box::new(self.copy_scalar(&expr.right)?),
+                return_type: expr.return_type.clone(),
+            }
+            .into(),
+            Scalar::ComparisonExpr(expr) => ComparisonExpr {
+                left: Box::new(self.copy_scalar(&expr.left)?),
+                right: Box::new(self.copy_scalar(&expr.right)?),
+                ..expr.clone()
+            }
+            .into(),
+            Scalar::AggregateFunction(function) => {
+                let mut function = function.clone();
+                function.args = self.copy_scalars(&function.args)?;
+                function.into()
+            }
+            Scalar::WindowFunction(function) => self.copy_window_function(function)?.into(),
+            Scalar::FunctionCall(function) => FunctionCall {
+                arguments: self.copy_scalars(&function.arguments)?,
+                ..function.clone()
+            }
+            .into(),
+            Scalar::CastExpr(cast) => CastExpr {
+                argument: Box::new(self.copy_scalar(&cast.argument)?),
+                ..cast.clone()
+            }
+            .into(),
+            Scalar::SubqueryExpr(subquery) => SubqueryExpr {
+                subquery: Box::new(self.copy_s_expr(&subquery.subquery)?),
+                child_expr: subquery
+                    .child_expr
+                    .as_ref()
+                    .map(|expr| self.copy_scalar(expr).map(Box::new))
+                    .transpose()?,
+                index: subquery.index.map(|index| self.copy_index(index)),
+                outer_columns: self.copy_column_set(&subquery.outer_columns),
+                ..subquery.clone()
+            }
+            .into(),
+        })
+    }
+}
+
+/// Check if the query references a table with given name in `FROM` clause.
+fn query_references_table(query: &Query, table_name: &str) -> bool {
+    set_expr_references_table(&query.body, table_name)
+}
+
+fn set_expr_references_table(set_expr: &SetExpr, table_name: &str) -> bool {
+    match set_expr {
+        SetExpr::Select(stmt) => stmt
+            .from
+            .iter()
+            .any(|table_ref| table_reference_references_table(table_ref, table_name)),
+        SetExpr::Query(query) => query_references_table(query, table_name),
+        SetExpr::SetOperation(set_operation) => {
+            set_expr_references_table(&set_operation.left, table_name)
+                || set_expr_references_table(&set_operation.right, table_name)
+        }
+    }
+}
+
+fn table_reference_references_table(table_ref: &TableReference, table_name: &str) -> bool {
+    match table_ref {
+        TableReference::Table {
+            catalog: None,
+            database: None,
+            table,
+            ..
+        } => table.name.to_lowercase() == table_name,
+        TableReference::Table { .. } | TableReference::TableFunction { .. } => false,
+        TableReference::Join { join, .. } => {
+            table_reference_references_table(&join.left, table_name)
+                || table_reference_references_table(&join.right, table_name)
+        }
+        TableReference::Subquery { subquery, .. } => query_references_table(subquery, table_name),
+    }
+}
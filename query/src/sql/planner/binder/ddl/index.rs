@@ -0,0 +1,143 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_ast::ast::CreateIndexStmt;
+use common_ast::ast::DropIndexStmt;
+use common_ast::ast::IndexType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableIndex;
+use common_meta_app::schema::TableIndexType;
+use common_planners::CreateIndexPlan;
+use common_planners::DropIndexPlan;
+
+use crate::sessions::TableContext;
+use crate::sql::binder::Binder;
+use crate::sql::plans::Plan;
+use crate::storages::index::BloomFilter;
+use crate::storages::index::BloomIndexOptions;
+use crate::storages::index::SetIndex;
+use crate::storages::index::SupportedType;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_create_index(
+        &mut self,
+        stmt: &CreateIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let CreateIndexStmt {
+            if_not_exists,
+            index,
+            catalog,
+            database,
+            table,
+            column,
+            index_type,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| ident.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| ident.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = table.name.to_lowercase();
+        let index_type = match index_type {
+            IndexType::Bloom => TableIndexType::Bloom,
+            IndexType::MinMax => TableIndexType::MinMax,
+            IndexType::Ngram => TableIndexType::Ngram,
+            IndexType::Set => TableIndexType::Set,
+        };
+
+        // the indexed column must exist, and be of the type the index supports
+        let schema = self
+            .ctx
+            .get_table(&catalog, &database, &table)
+            .await?
+            .schema();
+        let field = schema
+            .field_with_name(&column.name)
+            .map_err(|_| {
+                ErrorCode::UnknownColumn(format!(
+                    "column {} not found in table {}.{}",
+                    column.name, database, table
+                ))
+            })?
+            .clone();
+        let supported = match index_type {
+            TableIndexType::Bloom => BloomFilter::is_supported_type(field.data_type()),
+            TableIndexType::Set => SetIndex::is_supported_type(field.data_type()),
+            TableIndexType::Ngram => BloomIndexOptions::is_string_column(&field),
+            TableIndexType::MinMax => true,
+        };
+        if !supported {
+            return Err(ErrorCode::BadArguments(format!(
+                "index of type {} is not supported on column {} of type {:?}",
+                index_type,
+                column.name,
+                field.data_type()
+            )));
+        }
+
+        let plan = CreateIndexPlan {
+            if_not_exists: *if_not_exists,
+            tenant,
+            catalog,
+            database,
+            table,
+            index: TableIndex {
+                name: index.name.to_lowercase(),
+                column: column.name.clone(),
+                index_type,
+            },
+        };
+        Ok(Plan::CreateIndex(Box::new(plan)))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_drop_index(
+        &mut self,
+        stmt: &DropIndexStmt<'a>,
+    ) -> Result<Plan> {
+        let DropIndexStmt {
+            if_exists,
+            index,
+            catalog,
+            database,
+            table,
+        } = stmt;
+
+        let tenant = self.ctx.get_tenant();
+        let catalog = catalog
+            .as_ref()
+            .map(|ident| ident.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| ident.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = table.name.to_lowercase();
+
+        let plan = DropIndexPlan {
+            if_exists: *if_exists,
+            tenant,
+            catalog,
+            database,
+            table,
+            index: index.name.to_lowercase(),
+        };
+        Ok(Plan::DropIndex(Box::new(plan)))
+    }
+}
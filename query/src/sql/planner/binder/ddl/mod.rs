@@ -14,6 +14,7 @@
 
 mod account;
 mod database;
+mod index;
 mod stage;
 mod table;
 mod view;
@@ -22,14 +22,18 @@ use common_ast::ast::*;
 use common_ast::parser::parse_sql;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
+use common_datavalues::remove_nullable;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
 use common_datavalues::ToDataType;
 use common_datavalues::TypeFactory;
+use common_datavalues::TypeID;
 use common_datavalues::Vu8;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_meta_app::schema::TableCheckConstraint;
 use common_meta_app::schema::TableMeta;
 use common_planners::OptimizeTableAction;
 use common_planners::*;
@@ -39,16 +43,20 @@ use crate::catalogs::DatabaseCatalog;
 use crate::sessions::TableContext;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::Binder;
+use crate::sql::executor::ExpressionBuilderWithoutRenaming;
 use crate::sql::is_reserved_opt_key;
 use crate::sql::optimizer::optimize;
 use crate::sql::optimizer::OptimizerConfig;
 use crate::sql::optimizer::OptimizerContext;
 use crate::sql::plans::create_table_v2::CreateTablePlanV2;
 use crate::sql::plans::Plan;
+use crate::sql::plans::Scalar;
 use crate::sql::BindContext;
 use crate::sql::ColumnBinding;
 use crate::sql::ScalarExpr;
 use crate::sql::OPT_KEY_DATABASE_ID;
+use crate::storages::fuse::FUSE_OPT_KEY_DATA_RETENTION_PERIOD;
+use crate::storages::index::BloomIndexOptions;
 
 struct SelectBuilder {
     from: String,
@@ -350,10 +358,10 @@ impl<'a> Binder {
         }
 
         // Build table schema
-        let (schema, field_comments) = match (&source, &as_query) {
+        let (schema, field_comments, check_constraints) = match (&source, &as_query) {
             (Some(source), None) => {
                 // `CREATE TABLE` without `AS SELECT ...`
-                self.analyze_create_table_schema(source).await?
+                self.analyze_create_table_schema(&table, source).await?
             }
             (None, Some(query)) => {
                 // `CREATE TABLE AS SELECT ...` without column definitions
@@ -369,12 +377,12 @@ impl<'a> Binder {
                         )
                     })
                     .collect();
-                (DataSchemaRefExt::create(fields), vec![])
+                (DataSchemaRefExt::create(fields), vec![], BTreeMap::new())
             }
             (Some(source), Some(query)) => {
                 // e.g. `CREATE TABLE t (i INT) AS SELECT * from old_t` with columns speicified
-                let (source_schema, source_coments) =
-                    self.analyze_create_table_schema(source).await?;
+                let (source_schema, source_coments, check_constraints) =
+                    self.analyze_create_table_schema(&table, source).await?;
                 let init_bind_context = BindContext::new();
                 let (_s_expr, bind_context) = self.bind_query(&init_bind_context, query).await?;
                 let query_fields: Vec<DataField> = bind_context
@@ -392,6 +400,7 @@ impl<'a> Binder {
                 (
                     DataSchemaRefExt::create(source_fields.to_vec()),
                     source_coments,
+                    check_constraints,
                 )
             }
             _ => Err(ErrorCode::BadArguments(
@@ -399,11 +408,17 @@ impl<'a> Binder {
             ))?,
         };
 
+        if engine == Engine::Fuse {
+            // the columns of ngram and token bloom filters must be string columns of the table
+            BloomIndexOptions::from_table_options(&options)?.check_schema(&schema)?;
+        }
+
         let mut table_meta = TableMeta {
             schema: schema.clone(),
             engine: engine.to_string(),
             options: options.clone(),
             field_comments,
+            check_constraints,
             ..Default::default()
         };
 
@@ -584,6 +599,104 @@ impl<'a> Binder {
                     table,
                 },
             ))),
+            AlterTableAction::ReclusterTable {
+                is_final,
+                selection,
+            } => {
+                let mut expression = None;
+                if let Some(expr) = selection {
+                    let table_reference = TableReference::Table {
+                        span: &[],
+                        catalog: stmt.catalog.clone(),
+                        database: stmt.database.clone(),
+                        table: stmt.table.clone(),
+                        alias: None,
+                        travel_point: None,
+                    };
+                    let (_, context) = self
+                        .bind_table_reference(&BindContext::new(), &table_reference)
+                        .await?;
+
+                    let mut scalar_binder =
+                        ScalarBinder::new(&context, self.ctx.clone(), self.metadata.clone());
+                    let (scalar, _) = scalar_binder.bind(expr).await?;
+                    let eb = ExpressionBuilderWithoutRenaming::create(self.metadata.clone());
+                    expression = Some(eb.build(&scalar)?);
+                }
+
+                Ok(Plan::ReclusterTable(Box::new(ReclusterTablePlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    is_final: *is_final,
+                    selection: expression,
+                })))
+            }
+            AlterTableAction::AddColumn { column } => {
+                let bind_context = BindContext::new();
+                let mut scalar_binder =
+                    ScalarBinder::new(&bind_context, self.ctx.clone(), self.metadata.clone());
+                let name = column.name.name.clone();
+                if column.generated_expr.is_some() || !column.check_constraints.is_empty() {
+                    return Err(ErrorCode::SemanticError(format!(
+                        "generated column and check constraint are not supported by the added column {}",
+                        name
+                    )));
+                }
+                let data_type = TypeFactory::instance().get(column.data_type.to_string())?;
+                let default_expr = match &column.default_expr {
+                    Some(default_expr) => {
+                        // the existing rows are read with the default value, which must be
+                        // the same whenever it is evaluated
+                        let (scalar, _) = scalar_binder.bind(default_expr).await?;
+                        if !matches!(scalar, Scalar::ConstantExpr(_)) {
+                            return Err(ErrorCode::SemanticError(format!(
+                                "default value of the added column {} must be a constant, but got {}",
+                                name, default_expr
+                            )));
+                        }
+                        Some(default_expr.to_string())
+                    }
+                    None => None,
+                };
+                let field = DataField::new(&name, data_type).with_default_expr(default_expr);
+
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action: AlterTableColumnAction::AddColumn {
+                        field,
+                        comment: column.comment.clone().unwrap_or_default(),
+                    },
+                })))
+            }
+            AlterTableAction::DropColumn { column } => {
+                Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                    tenant,
+                    catalog,
+                    database,
+                    table,
+                    action: AlterTableColumnAction::DropColumn {
+                        column: column.name.clone(),
+                    },
+                })))
+            }
+            AlterTableAction::RenameColumn {
+                old_column,
+                new_column,
+            } => Ok(Plan::AlterTableColumn(Box::new(AlterTableColumnPlan {
+                tenant,
+                catalog,
+                database,
+                table,
+                action: AlterTableColumnAction::RenameColumn {
+                    column: old_column.name.clone(),
+                    new_column: new_column.name.clone(),
+                },
+            }))),
         }
     }
 
@@ -707,6 +820,67 @@ impl<'a> Binder {
         })))
     }
 
+    pub(in crate::sql::planner::binder) async fn bind_vacuum_table(
+        &mut self,
+        stmt: &VacuumTableStmt<'a>,
+    ) -> Result<Plan> {
+        let VacuumTableStmt {
+            catalog,
+            database,
+            table,
+            retain_hours,
+            dry_run,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| catalog.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| ident.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = table.name.to_lowercase();
+
+        Ok(Plan::VacuumTable(Box::new(VacuumTablePlan {
+            catalog,
+            database,
+            table,
+            retain_hours: *retain_hours,
+            dry_run: *dry_run,
+        })))
+    }
+
+    pub(in crate::sql::planner::binder) async fn bind_analyze_table(
+        &mut self,
+        stmt: &AnalyzeTableStmt<'a>,
+    ) -> Result<Plan> {
+        let AnalyzeTableStmt {
+            catalog,
+            database,
+            table,
+            columns,
+        } = stmt;
+
+        let catalog = catalog
+            .as_ref()
+            .map(|catalog| catalog.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_catalog());
+        let database = database
+            .as_ref()
+            .map(|ident| ident.name.to_lowercase())
+            .unwrap_or_else(|| self.ctx.get_current_database());
+        let table = table.name.to_lowercase();
+        let columns = columns.iter().map(|ident| ident.name.clone()).collect();
+
+        Ok(Plan::AnalyzeTable(Box::new(AnalyzeTablePlan {
+            catalog,
+            database,
+            table,
+            columns,
+        })))
+    }
+
     pub(in crate::sql::planner::binder) async fn bind_exists_table(
         &mut self,
         stmt: &ExistsTableStmt<'a>,
@@ -736,11 +910,16 @@ impl<'a> Binder {
 
     async fn analyze_create_table_schema(
         &self,
+        table: &str,
         source: &CreateTableSource<'a>,
-    ) -> Result<(DataSchemaRef, Vec<String>)> {
+    ) -> Result<(
+        DataSchemaRef,
+        Vec<String>,
+        BTreeMap<String, TableCheckConstraint>,
+    )> {
         let bind_context = BindContext::new();
         match source {
-            CreateTableSource::Columns(columns) => {
+            CreateTableSource::Columns(columns, table_check_constraints) => {
                 let mut scalar_binder =
                     ScalarBinder::new(&bind_context, self.ctx.clone(), self.metadata.clone());
                 let mut fields = Vec::with_capacity(columns.len());
@@ -749,18 +928,114 @@ impl<'a> Binder {
                     let name = column.name.name.clone();
                     let data_type = TypeFactory::instance().get(column.data_type.to_string())?;
 
-                    let field = DataField::new(&name, data_type).with_default_expr({
-                        if let Some(default_expr) = &column.default_expr {
-                            scalar_binder.bind(default_expr).await?;
-                            Some(default_expr.to_string())
-                        } else {
-                            None
-                        }
-                    });
+                    if column.default_expr.is_some() && column.generated_expr.is_some() {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "generated column {} can not have a default value",
+                            name
+                        )));
+                    }
+
+                    let field = DataField::new(&name, data_type)
+                        .with_default_expr({
+                            if let Some(default_expr) = &column.default_expr {
+                                scalar_binder.bind(default_expr).await?;
+                                Some(default_expr.to_string())
+                            } else {
+                                None
+                            }
+                        })
+                        .with_generated_expr(
+                            column.generated_expr.as_ref().map(|expr| expr.to_string()),
+                        );
                     fields.push(field);
                     fields_comments.push(column.comment.clone().unwrap_or_default());
                 }
-                Ok((DataSchemaRefExt::create(fields), fields_comments))
+                let schema = DataSchemaRefExt::create(fields);
+
+                // The expressions of generated columns and check constraints are bound
+                // against the columns of the table being created.
+                let schema_bind_context = Self::schema_bind_context(&schema);
+                let mut scalar_binder = ScalarBinder::new(
+                    &schema_bind_context,
+                    self.ctx.clone(),
+                    self.metadata.clone(),
+                );
+
+                for (column, field) in columns.iter().zip(schema.fields()) {
+                    if let Some(generated_expr) = &column.generated_expr {
+                        let (scalar, _) = scalar_binder.bind(generated_expr).await?;
+                        if !scalar.is_deterministic() {
+                            return Err(ErrorCode::SemanticError(format!(
+                                "expression of generated column {} is not deterministic: {}",
+                                field.name(),
+                                generated_expr
+                            )));
+                        }
+                        if let Some(index) = scalar
+                            .used_columns()
+                            .into_iter()
+                            .find(|index| schema.field(*index).is_generated())
+                        {
+                            return Err(ErrorCode::SemanticError(format!(
+                                "generated column {} can not reference the generated column {}",
+                                field.name(),
+                                schema.field(index).name()
+                            )));
+                        }
+                    }
+                }
+
+                let column_check_constraints = columns.iter().flat_map(|column| {
+                    column
+                        .check_constraints
+                        .iter()
+                        .map(move |check| (Some(column.name.name.as_str()), check))
+                });
+                let all_check_constraints = column_check_constraints
+                    .chain(table_check_constraints.iter().map(|check| (None, check)));
+
+                let mut check_constraints = BTreeMap::new();
+                for (column, check) in all_check_constraints {
+                    let (scalar, data_type) = scalar_binder.bind(&check.expr).await?;
+                    if remove_nullable(&data_type).data_type_id() != TypeID::Boolean {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "expression of check constraint must be a boolean, but got {}: {}",
+                            data_type.name(),
+                            check.expr
+                        )));
+                    }
+                    if !scalar.is_deterministic() {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "expression of check constraint is not deterministic: {}",
+                            check.expr
+                        )));
+                    }
+
+                    let name = match &check.name {
+                        Some(name) => {
+                            let name = name.name.to_lowercase();
+                            if check_constraints.contains_key(&name) {
+                                return Err(ErrorCode::SemanticError(format!(
+                                    "duplicated check constraint name: {}",
+                                    name
+                                )));
+                            }
+                            name
+                        }
+                        None => Self::check_constraint_name(
+                            &check_constraints,
+                            table,
+                            column,
+                            table_check_constraints,
+                        ),
+                    };
+                    check_constraints.insert(name.clone(), TableCheckConstraint {
+                        name,
+                        expr: check.expr.to_string(),
+                    });
+                }
+
+                Ok((schema, fields_comments, check_constraints))
             }
             CreateTableSource::Like {
                 catalog,
@@ -777,11 +1052,44 @@ impl<'a> Binder {
                 );
                 let table_name = table.name.to_lowercase();
                 let table = self.ctx.get_table(&catalog, &database, &table_name).await?;
-                Ok((table.schema(), table.field_comments().clone()))
+                Ok((
+                    table.schema(),
+                    table.field_comments().clone(),
+                    table.get_table_info().meta.check_constraints.clone(),
+                ))
             }
         }
     }
 
+    /// Generates a name for an unnamed check constraint, `<table>_<column>_check` for the
+    /// constraints of a column and `<table>_check` for the constraints of the table, followed by
+    /// a number if the name is taken.
+    fn check_constraint_name(
+        check_constraints: &BTreeMap<String, TableCheckConstraint>,
+        table: &str,
+        column: Option<&str>,
+        table_check_constraints: &[CheckConstraint<'a>],
+    ) -> String {
+        let prefix = match column {
+            Some(column) => format!("{}_{}_check", table, column.to_lowercase()),
+            None => format!("{}_check", table),
+        };
+        // the explicit names declared later must not be taken either
+        let is_taken = |name: &str| {
+            check_constraints.contains_key(name)
+                || table_check_constraints.iter().any(
+                    |check| matches!(&check.name, Some(ident) if ident.name.to_lowercase() == name),
+                )
+        };
+        if !is_taken(&prefix) {
+            return prefix;
+        }
+        (1..)
+            .map(|i| format!("{}{}", prefix, i))
+            .find(|name| !is_taken(name))
+            .unwrap()
+    }
+
     fn insert_table_option_with_validation(
         &self,
         options: &mut BTreeMap<String, String>,
@@ -793,6 +1101,10 @@ impl<'a> Binder {
                 "the following table options are reserved, please do not specify them in the CREATE TABLE statement: {}",
                 key
             )))
+        } else if key == FUSE_OPT_KEY_DATA_RETENTION_PERIOD && value.parse::<u64>().is_err() {
+            Err(ErrorCode::BadOption(format!(
+                "Invalid value of table option {key}: {value}, expect the number of hours"
+            )))
         } else if options.insert(key.clone(), value).is_some() {
             Err(ErrorCode::BadOption(format!(
                 "Duplicated table option: {key}"
@@ -808,19 +1120,7 @@ impl<'a> Binder {
         schema: DataSchemaRef,
     ) -> Result<Vec<String>> {
         // Build a temporary BindContext to resolve the expr
-        let mut bind_context = BindContext::new();
-        for field in schema.fields() {
-            let column = ColumnBinding {
-                database_name: None,
-                table_name: None,
-                column_name: field.name().to_lowercase(),
-                // A dummy index is fine, since we won't actually evaluate the expression
-                index: 0,
-                data_type: Box::new(field.data_type().clone()),
-                visible_in_unqualified_wildcard: false,
-            };
-            bind_context.columns.push(column);
-        }
+        let bind_context = Self::schema_bind_context(&schema);
         let mut scalar_binder =
             ScalarBinder::new(&bind_context, self.ctx.clone(), self.metadata.clone());
 
@@ -839,6 +1139,24 @@ impl<'a> Binder {
         Ok(cluster_keys)
     }
 
+    /// Builds a temporary BindContext to resolve the expressions over the columns of `schema`,
+    /// the index of a column is its position in the schema.
+    fn schema_bind_context(schema: &DataSchemaRef) -> BindContext {
+        let mut bind_context = BindContext::new();
+        for (index, field) in schema.fields().iter().enumerate() {
+            let column = ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: field.name().to_lowercase(),
+                index,
+                data_type: Box::new(field.data_type().clone()),
+                visible_in_unqualified_wildcard: false,
+            };
+            bind_context.columns.push(column);
+        }
+        bind_context
+    }
+
     fn concat_fields(
         &self,
         mut source_fields: Vec<DataField>,
@@ -14,9 +14,12 @@
 
 use common_ast::ast::Expr;
 use common_ast::parser::token::Token;
+use common_ast::DisplayError;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::sql::binder::aggregate::AggregateRewriter;
+use crate::sql::binder::contain_window_function;
 use crate::sql::binder::split_conjunctions;
 use crate::sql::binder::ScalarBinder;
 use crate::sql::optimizer::SExpr;
@@ -37,6 +40,11 @@ impl<'a> Binder {
         let mut scalar_binder =
             ScalarBinder::new(bind_context, self.ctx.clone(), self.metadata.clone());
         let (scalar, _) = scalar_binder.bind(having).await?;
+        if contain_window_function(&scalar) {
+            return Err(ErrorCode::SemanticError(having.span().display_error(
+                "window functions are not allowed in HAVING clause".to_string(),
+            )));
+        }
         let mut rewriter = AggregateRewriter::new(bind_context, self.metadata.clone());
         Ok((rewriter.visit(&scalar)?, having.span()))
     }
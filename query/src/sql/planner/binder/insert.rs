@@ -79,13 +79,22 @@ impl<'a> Binder {
         let table_id = table.get_id();
 
         let schema: DataSchemaRef = match stmt.columns.is_empty() {
-            true => table.schema(),
+            true => insertable_schema(&table.schema()),
             false => {
                 let schema = table.schema();
                 let fields = stmt
                     .columns
                     .iter()
-                    .map(|ident| schema.field_with_name(&ident.name).map(|v| v.clone()))
+                    .map(|ident| {
+                        let field = schema.field_with_name(&ident.name)?;
+                        if field.is_generated() {
+                            return Err(ErrorCode::GeneratedColumnNotWritable(format!(
+                                "generated column {} can not be inserted into",
+                                field.name()
+                            )));
+                        }
+                        Ok(field.clone())
+                    })
                     .collect::<Result<Vec<_>>>()?;
                 DataSchemaRefExt::create(fields)
             }
@@ -461,3 +470,19 @@ async fn exprs_to_datavalue<'a>(
     let datavalues: Vec<DataValue> = res.columns().iter().map(|col| col.get(0)).collect();
     Ok(datavalues)
 }
+
+/// The columns written by `INSERT` and `COPY` if not specified, that is all the columns of
+/// the table except the stored generated ones, whose values are always computed.
+pub(in crate::sql::planner::binder) fn insertable_schema(schema: &DataSchemaRef) -> DataSchemaRef {
+    if schema.fields().iter().any(|f| f.is_generated()) {
+        let fields = schema
+            .fields()
+            .iter()
+            .filter(|f| !f.is_generated())
+            .cloned()
+            .collect();
+        DataSchemaRefExt::create(fields)
+    } else {
+        schema.clone()
+    }
+}
@@ -229,7 +229,7 @@ pub fn check_duplicate_join_tables(
     Ok(())
 }
 
-struct JoinConditionResolver<'a> {
+pub(super) struct JoinConditionResolver<'a> {
     ctx: Arc<QueryContext>,
 
     metadata: MetadataRef,
@@ -0,0 +1,324 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_ast::ast::JoinCondition;
+use common_ast::ast::MatchOperation;
+use common_ast::ast::MergeIntoStmt;
+use common_ast::ast::TableReference;
+use common_ast::DisplayError;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::MergeIntoPlan;
+use common_planners::MergeMatchedClause;
+use common_planners::MergeUnmatchedClause;
+
+use crate::sessions::TableContext;
+use crate::sql::binder::insert::insertable_schema;
+use crate::sql::binder::join::JoinConditionResolver;
+use crate::sql::binder::Binder;
+use crate::sql::binder::ScalarBinder;
+use crate::sql::executor::format_field_name;
+use crate::sql::executor::ExpressionBuilderWithRenaming;
+use crate::sql::optimizer::optimize;
+use crate::sql::optimizer::OptimizerConfig;
+use crate::sql::optimizer::OptimizerContext;
+use crate::sql::plans::MergeInto;
+use crate::sql::plans::Plan;
+use crate::sql::plans::Scalar;
+use crate::sql::BindContext;
+use crate::sql::PlanParser;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_merge_into(
+        &mut self,
+        bind_context: &BindContext,
+        stmt: &'a MergeIntoStmt<'a>,
+    ) -> Result<Plan> {
+        let MergeIntoStmt {
+            target,
+            source,
+            join_expr,
+            matched_clauses,
+            unmatched_clauses,
+        } = stmt;
+
+        let (catalog_name, database_name, table_name) = if let TableReference::Table {
+            catalog,
+            database,
+            table,
+            ..
+        } = target
+        {
+            (
+                catalog
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_catalog(), |i| i.name.clone()),
+                database
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_database(), |i| i.name.clone()),
+                table.name.clone(),
+            )
+        } else {
+            return Err(ErrorCode::LogicalError(
+                "should not happen, parser should have report error already",
+            ));
+        };
+
+        let (_, target_context) = self.bind_table_reference(bind_context, target).await?;
+        let (source_expr, source_context) = self.bind_table_reference(bind_context, source).await?;
+
+        let table = self
+            .ctx
+            .get_table(&catalog_name, &database_name, &table_name)
+            .await?;
+        let tbl_info = table.get_table_info();
+        let table_id = tbl_info.ident.clone();
+        let schema = tbl_info.meta.schema.clone();
+
+        // The columns are named in the same way as the physical plan, so the target columns
+        // and the source columns won't conflict with each other in the joined rows.
+        let target_columns = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let column = target_context
+                    .columns
+                    .iter()
+                    .find(|column| column.column_name == *field.name())
+                    .ok_or_else(|| {
+                        ErrorCode::UnknownColumn(format!("Column [{}] not found", field.name()))
+                    })?;
+                Ok(self.column_field_name(column.index))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let source_columns = source_context
+            .columns
+            .iter()
+            .map(|column| self.column_field_name(column.index))
+            .collect::<Vec<_>>();
+
+        // Split the join condition into equi-join keys and the other conditions
+        let mut join_context = bind_context.replace();
+        for column in target_context
+            .all_column_bindings()
+            .iter()
+            .chain(source_context.all_column_bindings())
+        {
+            join_context.add_column_binding(column.clone());
+        }
+        let join_condition = JoinCondition::On(Box::new(join_expr.clone()));
+        let mut target_conditions: Vec<Scalar> = vec![];
+        let mut source_conditions: Vec<Scalar> = vec![];
+        let mut other_conditions: Vec<Scalar> = vec![];
+        let mut join_condition_resolver = JoinConditionResolver::new(
+            self.ctx.clone(),
+            self.metadata.clone(),
+            &target_context,
+            &source_context,
+            &mut join_context,
+            &join_condition,
+        );
+        join_condition_resolver
+            .resolve(
+                &mut target_conditions,
+                &mut source_conditions,
+                &mut other_conditions,
+            )
+            .await?;
+        if target_conditions.is_empty() {
+            return Err(ErrorCode::SemanticError(join_expr.span().display_error(
+                "MERGE INTO requires at least one equi-join condition between target and source"
+                    .to_string(),
+            )));
+        }
+
+        let eb = ExpressionBuilderWithRenaming::create(self.metadata.clone());
+        let target_keys = target_conditions
+            .iter()
+            .map(|scalar| eb.build(scalar))
+            .collect::<Result<Vec<_>>>()?;
+        let source_keys = source_conditions
+            .iter()
+            .map(|scalar| eb.build(scalar))
+            .collect::<Result<Vec<_>>>()?;
+        let join_filter = other_conditions
+            .iter()
+            .map(|scalar| eb.build(scalar))
+            .reduce(|acc, expr| Ok(acc?.and(expr?)))
+            .transpose()?;
+
+        let mut matched = Vec::with_capacity(matched_clauses.len());
+        for clause in matched_clauses {
+            let mut scalar_binder =
+                ScalarBinder::new(&join_context, self.ctx.clone(), self.metadata.clone());
+            let selection = match &clause.selection {
+                Some(expr) => Some(eb.build(&scalar_binder.bind(expr).await?.0)?),
+                None => None,
+            };
+            let update_list = match &clause.operation {
+                MatchOperation::Update { update_list } => {
+                    let mut update_columns: Vec<(usize, Expression)> =
+                        Vec::with_capacity(update_list.len());
+                    for update_expr in update_list {
+                        let col_name = update_expr.name.name.as_str();
+                        let (idx, field) = schema.column_with_name(col_name).ok_or_else(|| {
+                            ErrorCode::UnknownColumn(format!("Column [{}] not found", col_name))
+                        })?;
+                        if field.is_generated() {
+                            return Err(ErrorCode::GeneratedColumnNotWritable(format!(
+                                "generated column {} can not be updated",
+                                col_name
+                            )));
+                        }
+                        if update_columns.iter().any(|(i, _)| *i == idx) {
+                            return Err(ErrorCode::SemanticError(
+                                update_expr.name.span.display_error(format!(
+                                    "multiple assignments to same column \"{}\"",
+                                    col_name
+                                )),
+                            ));
+                        }
+
+                        let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+                        update_columns.push((idx, cast_to_field(eb.build(&scalar)?, field)));
+                    }
+                    Some(update_columns)
+                }
+                MatchOperation::Delete => None,
+            };
+            matched.push(MergeMatchedClause {
+                selection,
+                update_list,
+            });
+        }
+
+        let mut unmatched = Vec::with_capacity(unmatched_clauses.len());
+        for clause in unmatched_clauses {
+            // Only the columns of source are visible in `WHEN NOT MATCHED` clauses
+            let mut scalar_binder =
+                ScalarBinder::new(&source_context, self.ctx.clone(), self.metadata.clone());
+            let selection = match &clause.selection {
+                Some(expr) => Some(eb.build(&scalar_binder.bind(expr).await?.0)?),
+                None => None,
+            };
+
+            let columns = if clause.columns.is_empty() {
+                insertable_schema(&schema)
+                    .fields()
+                    .iter()
+                    .map(|field| field.name().clone())
+                    .collect::<Vec<_>>()
+            } else {
+                clause
+                    .columns
+                    .iter()
+                    .map(|ident| ident.name.clone())
+                    .collect::<Vec<_>>()
+            };
+            if columns.len() != clause.values.len() {
+                return Err(ErrorCode::SemanticError(format!(
+                    "INSERT has {} target columns but {} expressions",
+                    columns.len(),
+                    clause.values.len()
+                )));
+            }
+
+            for name in columns.iter() {
+                match schema.column_with_name(name) {
+                    Some((_, field)) if field.is_generated() => {
+                        return Err(ErrorCode::GeneratedColumnNotWritable(format!(
+                            "generated column {} can not be inserted into",
+                            name
+                        )));
+                    }
+                    Some(_) => {}
+                    None => {
+                        return Err(ErrorCode::UnknownColumn(format!(
+                            "Column [{}] not found",
+                            name
+                        )));
+                    }
+                }
+            }
+
+            let mut values = Vec::with_capacity(schema.fields().len());
+            for field in schema.fields() {
+                let value = match columns.iter().position(|name| name == field.name()) {
+                    Some(position) => {
+                        let (scalar, _) = scalar_binder.bind(&clause.values[position]).await?;
+                        eb.build(&scalar)?
+                    }
+                    // the generated columns are computed when the rows are written
+                    None => default_value(field)?,
+                };
+                values.push(cast_to_field(value, field));
+            }
+            unmatched.push(MergeUnmatchedClause { selection, values });
+        }
+
+        // The source is evaluated as a standalone query
+        let source_plan = Plan::Query {
+            s_expr: source_expr,
+            metadata: self.metadata.clone(),
+            bind_context: Box::new(source_context),
+        };
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig::default()));
+        let source_plan = optimize(self.ctx.clone(), opt_ctx, source_plan)?;
+
+        let merge_into_plan = MergeIntoPlan {
+            catalog_name,
+            database_name,
+            table_name,
+            table_id,
+            target_columns,
+            source_columns,
+            target_keys,
+            source_keys,
+            join_filter,
+            matched_clauses: matched,
+            unmatched_clauses: unmatched,
+        };
+        Ok(Plan::MergeInto(Box::new(MergeInto {
+            source: Box::new(source_plan),
+            merge_into_plan,
+        })))
+    }
+
+    fn column_field_name(&self, index: usize) -> String {
+        format_field_name(self.metadata.read().column(index).name.as_str(), index)
+    }
+}
+
+fn cast_to_field(expr: Expression, field: &DataField) -> Expression {
+    Expression::Cast {
+        expr: Box::new(expr),
+        data_type: field.data_type().clone(),
+        pg_style: false,
+    }
+}
+
+fn default_value(field: &DataField) -> Result<Expression> {
+    match field.default_expr() {
+        Some(expr) => PlanParser::parse_expr(expr),
+        None => Ok(Expression::create_literal_with_type(
+            field.data_type().default_value(),
+            field.data_type().clone(),
+        )),
+    }
+}
@@ -17,6 +17,7 @@ use std::sync::Arc;
 pub use aggregate::AggregateInfo;
 pub use bind_context::BindContext;
 pub use bind_context::ColumnBinding;
+pub use bind_context::CteInfo;
 use common_ast::ast::Statement;
 use common_ast::parser::parse_sql;
 use common_ast::parser::tokenize_sql;
@@ -37,6 +38,7 @@ use common_planners::ShowGrantsPlan;
 use common_planners::UseDatabasePlan;
 pub use scalar::ScalarBinder;
 pub use scalar_common::*;
+pub use window::WindowInfo;
 
 use super::plans::Plan;
 use crate::catalogs::CatalogManager;
@@ -46,6 +48,7 @@ use crate::sql::planner::metadata::MetadataRef;
 mod aggregate;
 mod bind_context;
 mod copy;
+mod cte;
 mod ddl;
 mod delete;
 mod distinct;
@@ -54,6 +57,7 @@ mod insert;
 mod join;
 mod kill;
 mod limit;
+mod merge_into;
 mod presign;
 mod project;
 mod scalar;
@@ -64,6 +68,8 @@ mod setting;
 mod show;
 mod sort;
 mod table;
+mod update;
+mod window;
 
 /// Binder is responsible to transform AST of a query into a canonical logical SExpr.
 ///
@@ -166,6 +172,8 @@ impl<'a> Binder {
             Statement::RenameTable(stmt) => self.bind_rename_table(stmt).await?,
             Statement::TruncateTable(stmt) => self.bind_truncate_table(stmt).await?,
             Statement::OptimizeTable(stmt) => self.bind_optimize_table(stmt).await?,
+            Statement::VacuumTable(stmt) => self.bind_vacuum_table(stmt).await?,
+            Statement::AnalyzeTable(stmt) => self.bind_analyze_table(stmt).await?,
             Statement::ExistsTable(stmt) => self.bind_exists_table(stmt).await?,
 
             // Views
@@ -173,6 +181,10 @@ impl<'a> Binder {
             Statement::AlterView(stmt) => self.bind_alter_view(stmt).await?,
             Statement::DropView(stmt) => self.bind_drop_view(stmt).await?,
 
+            // Indexes
+            Statement::CreateIndex(stmt) => self.bind_create_index(stmt).await?,
+            Statement::DropIndex(stmt) => self.bind_drop_index(stmt).await?,
+
             // Users
             Statement::CreateUser(stmt) => self.bind_create_user(stmt).await?,
             Statement::DropUser { if_exists, user } => Plan::DropUser(Box::new(DropUserPlan {
@@ -228,6 +240,13 @@ impl<'a> Binder {
                 self.bind_delete(bind_context, table_reference, selection)
                     .await?
             }
+            Statement::Update(stmt) => self.bind_update(bind_context, stmt).await?,
+            Statement::MergeInto(stmt) => self.bind_merge_into(bind_context, stmt).await?,
+
+            // Transactions
+            Statement::Begin => Plan::Begin,
+            Statement::Commit => Plan::Commit,
+            Statement::Rollback => Plan::Rollback,
 
             // Permissions
             Statement::Grant(stmt) => self.bind_grant(stmt).await?,
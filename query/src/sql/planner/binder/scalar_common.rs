@@ -181,3 +181,23 @@ pub fn contain_subquery(scalar: &Scalar) -> bool {
         _ => false,
     }
 }
+
+pub fn contain_window_function(scalar: &Scalar) -> bool {
+    match scalar {
+        Scalar::WindowFunction(_) => true,
+        Scalar::ComparisonExpr(ComparisonExpr { left, right, .. }) => {
+            contain_window_function(left) || contain_window_function(right)
+        }
+        Scalar::AndExpr(AndExpr { left, right, .. }) => {
+            contain_window_function(left) || contain_window_function(right)
+        }
+        Scalar::OrExpr(OrExpr { left, right, .. }) => {
+            contain_window_function(left) || contain_window_function(right)
+        }
+        Scalar::FunctionCall(FunctionCall { arguments, .. }) => {
+            arguments.iter().any(contain_window_function)
+        }
+        Scalar::CastExpr(CastExpr { argument, .. }) => contain_window_function(argument),
+        _ => false,
+    }
+}
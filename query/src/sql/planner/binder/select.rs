@@ -26,19 +26,28 @@ use common_ast::ast::SelectTarget;
 use common_ast::ast::SetExpr;
 use common_ast::ast::SetOperator;
 use common_ast::ast::TableReference;
+use common_ast::DisplayError;
 use common_datavalues::type_coercion::merge_types;
+use common_datavalues::DataTypeImpl;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
+use crate::sql::binder::scalar_common::contain_window_function;
 use crate::sql::binder::scalar_common::split_conjunctions;
 use crate::sql::optimizer::SExpr;
 use crate::sql::planner::binder::scalar::ScalarBinder;
 use crate::sql::planner::binder::BindContext;
 use crate::sql::planner::binder::Binder;
 use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::EvalScalar;
 use crate::sql::plans::Filter;
 use crate::sql::plans::JoinType;
 use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarItem;
+use crate::sql::plans::SetOperation;
+use crate::sql::plans::SetOperationType;
+use crate::sql::plans::UnionAll;
 
 // A normalized IR for `SELECT` clause.
 #[derive(Debug, Default)]
@@ -90,6 +99,10 @@ impl<'a> Binder {
             .normalize_select_list(&from_context, &stmt.select_list)
             .await?;
 
+        // Window functions in select list will be replaced with the output columns
+        // of `Window` plans.
+        self.analyze_window_select(&mut from_context, &mut select_list)?;
+
         let (mut scalar_items, projections) = self.analyze_projection(&select_list)?;
 
         // This will potentially add some alias group items to `from_context` if find some.
@@ -128,6 +141,10 @@ impl<'a> Binder {
                 .await?;
         }
 
+        if !from_context.window_info.window_functions.is_empty() {
+            s_expr = self.bind_window(&from_context, s_expr)?;
+        }
+
         if stmt.distinct {
             s_expr = self.bind_distinct(&from_context, &projections, &mut scalar_items, s_expr)?;
         }
@@ -181,14 +198,20 @@ impl<'a> Binder {
         bind_context: &BindContext,
         query: &Query<'_>,
     ) -> Result<(SExpr, BindContext)> {
+        // CTEs declared in `WITH` clause are only visible in current query
+        let mut bind_context = bind_context.clone();
+        if let Some(with) = &query.with {
+            self.bind_with(&mut bind_context, with).await?;
+        }
+
         let (mut s_expr, mut bind_context) = match query.body {
             SetExpr::Select(_) | SetExpr::Query(_) => {
-                self.bind_set_expr(bind_context, &query.body, &query.order_by)
+                self.bind_set_expr(&bind_context, &query.body, &query.order_by)
                     .await?
             }
             SetExpr::SetOperation(_) => {
                 let (mut s_expr, bind_context) =
-                    self.bind_set_expr(bind_context, &query.body, &[]).await?;
+                    self.bind_set_expr(&bind_context, &query.body, &[]).await?;
                 if !query.order_by.is_empty() {
                     s_expr = self
                         .bind_order_by_for_set_operation(&bind_context, s_expr, &query.order_by)
@@ -235,6 +258,11 @@ impl<'a> Binder {
         let mut scalar_binder =
             ScalarBinder::new(bind_context, self.ctx.clone(), self.metadata.clone());
         let (scalar, _) = scalar_binder.bind(expr).await?;
+        if contain_window_function(&scalar) {
+            return Err(ErrorCode::SemanticError(expr.span().display_error(
+                "window functions are not allowed in WHERE clause".to_string(),
+            )));
+        }
         let filter_plan = Filter {
             predicates: split_conjunctions(&scalar),
             is_having: false,
@@ -251,33 +279,55 @@ impl<'a> Binder {
         op: &SetOperator,
         all: &bool,
     ) -> Result<(SExpr, BindContext)> {
-        let (left_expr, mut left_bind_context) =
-            self.bind_set_expr(bind_context, left, &[]).await?;
-        let (right_expr, mut right_bind_context) =
-            self.bind_set_expr(bind_context, right, &[]).await?;
+        let (left_expr, left_bind_context) = self.bind_set_expr(bind_context, left, &[]).await?;
+        let (right_expr, right_bind_context) = self.bind_set_expr(bind_context, right, &[]).await?;
         if left_bind_context.columns.len() != right_bind_context.columns.len() {
             return Err(ErrorCode::SemanticError(
                 "SetOperation must have the same number of columns",
             ));
-        } else {
-            for (left_col, right_col) in left_bind_context
-                .columns
-                .iter_mut()
-                .zip(right_bind_context.columns.iter_mut())
-            {
-                if left_col.data_type != right_col.data_type {
-                    let coercion_type = merge_types(&left_col.data_type, &right_col.data_type)?;
-                    left_col.data_type = Box::new(coercion_type.clone());
-                    right_col.data_type = Box::new(coercion_type);
-                }
-                if left_col.data_type != right_col.data_type {
-                    return Err(ErrorCode::SemanticError(
-                        "SetOperation's types cannot be matched",
-                    ));
-                }
+        }
+
+        // Unify the types of the columns of both sides
+        let mut data_types = Vec::with_capacity(left_bind_context.columns.len());
+        for (left_col, right_col) in left_bind_context
+            .columns
+            .iter()
+            .zip(right_bind_context.columns.iter())
+        {
+            if left_col.data_type == right_col.data_type {
+                data_types.push(*left_col.data_type.clone());
+            } else {
+                let coercion_type = merge_types(&left_col.data_type, &right_col.data_type)
+                    .map_err(|_| {
+                        ErrorCode::SemanticError("SetOperation's types cannot be matched")
+                    })?;
+                data_types.push(coercion_type);
             }
         }
+        let (left_expr, left_bind_context) =
+            self.cast_set_operation_input(left_bind_context, left_expr, &data_types)?;
+        let (right_expr, right_bind_context) =
+            self.cast_set_operation_input(right_bind_context, right_expr, &data_types)?;
+
         match (op, all) {
+            (SetOperator::Union, true) => {
+                self.bind_union_all(left_bind_context, right_bind_context, left_expr, right_expr)
+            }
+            (SetOperator::Union, false) => {
+                let (s_expr, bind_context) = self.bind_union_all(
+                    left_bind_context,
+                    right_bind_context,
+                    left_expr,
+                    right_expr,
+                )?;
+                let s_expr = self.bind_distinct(
+                    &bind_context,
+                    bind_context.all_column_bindings(),
+                    &mut HashMap::new(),
+                    s_expr,
+                )?;
+                Ok((s_expr, bind_context))
+            }
             (SetOperator::Intersect, false) => {
                 // Transfer Intersect to Semi join
                 self.bind_intersect(left_bind_context, right_bind_context, left_expr, right_expr)
@@ -286,12 +336,113 @@ impl<'a> Binder {
                 // Transfer Except to Anti join
                 self.bind_except(left_bind_context, right_bind_context, left_expr, right_expr)
             }
-            _ => Err(ErrorCode::UnImplement(
-                "Unsupported query type, currently, databend only support intersect distinct and except distinct",
-            )),
+            (SetOperator::Intersect, true) => self.bind_set_operation_all(
+                left_bind_context,
+                right_bind_context,
+                left_expr,
+                right_expr,
+                SetOperationType::IntersectAll,
+            ),
+            (SetOperator::Except, true) => self.bind_set_operation_all(
+                left_bind_context,
+                right_bind_context,
+                left_expr,
+                right_expr,
+                SetOperationType::ExceptAll,
+            ),
         }
     }
 
+    /// Cast the output columns of an input of set operation to the unified types,
+    /// the casted values are evaluated as derived columns.
+    fn cast_set_operation_input(
+        &mut self,
+        mut bind_context: BindContext,
+        s_expr: SExpr,
+        data_types: &[DataTypeImpl],
+    ) -> Result<(SExpr, BindContext)> {
+        let mut items = vec![];
+        for (column, data_type) in bind_context.columns.iter_mut().zip(data_types.iter()) {
+            if column.data_type.as_ref() == data_type {
+                continue;
+            }
+
+            let index = self.metadata.write().add_column(
+                column.column_name.clone(),
+                data_type.clone(),
+                None,
+            );
+            items.push(ScalarItem {
+                scalar: CastExpr {
+                    argument: Box::new(
+                        BoundColumnRef {
+                            column: column.clone(),
+                        }
+                        .into(),
+                    ),
+                    from_type: column.data_type.clone(),
+                    target_type: Box::new(data_type.clone()),
+                }
+                .into(),
+                index,
+            });
+            column.index = index;
+            column.data_type = Box::new(data_type.clone());
+        }
+
+        if items.is_empty() {
+            return Ok((s_expr, bind_context));
+        }
+        let s_expr = SExpr::create_unary(EvalScalar { items }.into(), s_expr);
+        Ok((s_expr, bind_context))
+    }
+
+    fn bind_union_all(
+        &mut self,
+        left_context: BindContext,
+        right_context: BindContext,
+        left_expr: SExpr,
+        right_expr: SExpr,
+    ) -> Result<(SExpr, BindContext)> {
+        // The output columns are derived columns, since a column of left side
+        // may be output more than once.
+        let mut bind_context = left_context.clone();
+        let mut output_columns = Vec::with_capacity(left_context.columns.len());
+        for column in bind_context.columns.iter_mut() {
+            column.index = self.metadata.write().add_column(
+                column.column_name.clone(),
+                *column.data_type.clone(),
+                None,
+            );
+            output_columns.push(column.index);
+        }
+
+        let union_all = UnionAll {
+            left_columns: left_context.columns,
+            right_columns: right_context.columns,
+            output_columns,
+        };
+        let s_expr = SExpr::create_binary(union_all.into(), left_expr, right_expr);
+        Ok((s_expr, bind_context))
+    }
+
+    fn bind_set_operation_all(
+        &mut self,
+        left_context: BindContext,
+        right_context: BindContext,
+        left_expr: SExpr,
+        right_expr: SExpr,
+        op_type: SetOperationType,
+    ) -> Result<(SExpr, BindContext)> {
+        let set_operation = SetOperation {
+            op_type,
+            left_columns: left_context.columns.clone(),
+            right_columns: right_context.columns,
+        };
+        let s_expr = SExpr::create_binary(set_operation.into(), left_expr, right_expr);
+        Ok((s_expr, left_context))
+    }
+
     fn bind_intersect(
         &mut self,
         left_context: BindContext,
@@ -23,6 +23,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::sessions::TableContext;
+use crate::sql::binder::contain_window_function;
 use crate::sql::binder::scalar::ScalarBinder;
 use crate::sql::binder::select::SelectList;
 use crate::sql::binder::Binder;
@@ -146,6 +147,11 @@ impl<'a> Binder {
                     let mut scalar_binder =
                         ScalarBinder::new(&bind_context, self.ctx.clone(), self.metadata.clone());
                     let (bound_expr, _) = scalar_binder.bind(&order.expr).await?;
+                    if contain_window_function(&bound_expr) {
+                        return Err(ErrorCode::SemanticError(order.expr.span().display_error(
+                            "window functions are not allowed in ORDER BY clause, use an alias of select item instead".to_string(),
+                        )));
+                    }
                     let rewrite_scalar = self
                         .rewrite_scalar_with_replacement(&bound_expr, &|nest_scalar| {
                             if let Scalar::BoundColumnRef(BoundColumnRef { column }) = nest_scalar {
@@ -76,6 +76,7 @@ impl<'a> Binder {
             database.to_string(),
             table_meta,
             source,
+            None,
         );
 
         self.bind_base_table(bind_context, database, table_index)
@@ -95,6 +96,14 @@ impl<'a> Binder {
                 alias,
                 travel_point,
             } => {
+                // Check if the table is a CTE declared in `WITH` clause
+                if catalog.is_none() && database.is_none() {
+                    let cte_name = table.name.to_lowercase();
+                    if let Some(cte_info) = bind_context.ctes_map.get(&cte_name) {
+                        return self.bind_cte(bind_context, cte_info, alias);
+                    }
+                }
+
                 // Get catalog name
                 let catalog = catalog
                     .as_ref()
@@ -150,11 +159,14 @@ impl<'a> Binder {
                         let source = table_meta
                             .read_plan_with_catalog(self.ctx.clone(), catalog.clone(), None)
                             .await?;
+                        let column_statistics =
+                            table_meta.column_statistics(self.ctx.clone()).await?;
                         let table_index = self.metadata.write().add_table(
                             catalog,
                             database.clone(),
                             table_meta,
                             source,
+                            column_statistics,
                         );
 
                         let (s_expr, mut bind_context) =
@@ -211,6 +223,7 @@ impl<'a> Binder {
                     "system".to_string(),
                     table.clone(),
                     source,
+                    None,
                 );
 
                 let (s_expr, mut bind_context) =
@@ -235,7 +248,7 @@ impl<'a> Binder {
         }
     }
 
-    fn bind_base_table(
+    pub(super) fn bind_base_table(
         &mut self,
         bind_context: &BindContext,
         database_name: &str,
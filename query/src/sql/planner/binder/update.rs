@@ -0,0 +1,146 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use common_ast::ast::TableReference;
+use common_ast::ast::UpdateStmt;
+use common_ast::DisplayError;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::UpdatePlan;
+
+use crate::sessions::TableContext;
+use crate::sql::binder::delete::DeleteCollectPushDowns;
+use crate::sql::binder::Binder;
+use crate::sql::binder::ScalarBinder;
+use crate::sql::executor::ExpressionBuilderWithoutRenaming;
+use crate::sql::plans::Plan;
+use crate::sql::statements::query::QueryASTIRVisitor;
+use crate::sql::BindContext;
+
+impl<'a> Binder {
+    pub(in crate::sql::planner::binder) async fn bind_update(
+        &mut self,
+        bind_context: &BindContext,
+        stmt: &'a UpdateStmt<'a>,
+    ) -> Result<Plan> {
+        let UpdateStmt {
+            table_reference,
+            update_list,
+            selection,
+        } = stmt;
+
+        let (catalog_name, database_name, table_name) = if let TableReference::Table {
+            catalog,
+            database,
+            table,
+            ..
+        } = table_reference
+        {
+            (
+                catalog
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_catalog(), |i| i.name.clone()),
+                database
+                    .as_ref()
+                    .map_or_else(|| self.ctx.get_current_database(), |i| i.name.clone()),
+                table.name.clone(),
+            )
+        } else {
+            return Err(ErrorCode::LogicalError(
+                "should not happen, parser should have report error already",
+            ));
+        };
+
+        let (_, context) = self
+            .bind_table_reference(bind_context, table_reference)
+            .await?;
+
+        let table = self
+            .ctx
+            .get_table(&catalog_name, &database_name, &table_name)
+            .await?;
+        let tbl_info = table.get_table_info();
+        let table_id = tbl_info.ident.clone();
+        let schema = tbl_info.meta.schema.as_ref();
+
+        let mut scalar_binder =
+            ScalarBinder::new(&context, self.ctx.clone(), self.metadata.clone());
+        let eb = ExpressionBuilderWithoutRenaming::create(self.metadata.clone());
+
+        let mut update_columns = Vec::with_capacity(update_list.len());
+        for update_expr in update_list {
+            let col_name = update_expr.name.name.as_str();
+            let idx = match schema.column_with_name(col_name) {
+                Some((_, field)) if field.is_generated() => {
+                    return Err(ErrorCode::GeneratedColumnNotWritable(format!(
+                        "generated column {} can not be updated",
+                        col_name
+                    )));
+                }
+                Some((idx, _)) => idx,
+                None => {
+                    return Err(ErrorCode::UnknownColumn(format!(
+                        "Column [{}] not found",
+                        col_name
+                    )));
+                }
+            };
+            if update_columns.iter().any(|(i, _)| *i == idx) {
+                return Err(ErrorCode::SemanticError(
+                    update_expr.name.span.display_error(format!(
+                        "multiple assignments to same column \"{}\"",
+                        col_name
+                    )),
+                ));
+            }
+
+            let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+            update_columns.push((idx, eb.build(&scalar)?));
+        }
+
+        let mut expression = None;
+        let mut require_columns: HashSet<String> = HashSet::new();
+        if let Some(expr) = selection {
+            let (scalar, _) = scalar_binder.bind(expr).await?;
+            let mut pred_expr = eb.build(&scalar)?;
+            DeleteCollectPushDowns::visit_filter(&mut pred_expr, &mut require_columns)?;
+            expression = Some(pred_expr);
+        }
+
+        let mut projection = vec![];
+        for col_name in require_columns {
+            if let Some((idx, _)) = schema.column_with_name(col_name.as_str()) {
+                projection.push(idx);
+            } else {
+                return Err(ErrorCode::UnknownColumn(format!(
+                    "Column [{}] not found",
+                    col_name
+                )));
+            }
+        }
+
+        let plan = UpdatePlan {
+            catalog_name,
+            database_name,
+            table_name,
+            table_id,
+            selection: expression,
+            update_list: update_columns,
+            projection,
+        };
+        Ok(Plan::Update(Box::new(plan)))
+    }
+}
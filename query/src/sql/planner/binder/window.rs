@@ -0,0 +1,330 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use common_exception::Result;
+
+use crate::sql::binder::aggregate::AggregateRewriter;
+use crate::sql::binder::select::SelectList;
+use crate::sql::binder::Binder;
+use crate::sql::binder::ColumnBinding;
+use crate::sql::optimizer::SExpr;
+use crate::sql::planner::metadata::MetadataRef;
+use crate::sql::planner::semantic::GroupingChecker;
+use crate::sql::plans::AndExpr;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::CastExpr;
+use crate::sql::plans::ComparisonExpr;
+use crate::sql::plans::EvalScalar;
+use crate::sql::plans::FunctionCall;
+use crate::sql::plans::OrExpr;
+use crate::sql::plans::Scalar;
+use crate::sql::plans::ScalarExpr;
+use crate::sql::plans::ScalarItem;
+use crate::sql::plans::Window;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
+use crate::sql::BindContext;
+use crate::sql::IndexType;
+
+#[derive(Default, Clone, PartialEq, Debug)]
+pub struct WindowInfo {
+    /// Window functions, the aggregate functions involved in them have been
+    /// rewritten by `AggregateRewriter`.
+    pub window_functions: Vec<ScalarItem>,
+
+    /// Mapping: (window function display name) -> (index of window function in `window_functions`)
+    /// This is used to find a window function in current context.
+    pub window_functions_map: HashMap<String, usize>,
+}
+
+impl WindowInfo {
+    /// Check if the column is the output column of a window function.
+    pub fn is_window_column(&self, index: IndexType) -> bool {
+        self.window_functions.iter().any(|item| item.index == index)
+    }
+}
+
+/// Replace the window functions in a scalar expression with BoundColumnRefs to
+/// their output columns, and add the window functions into `WindowInfo`.
+pub(super) struct WindowRewriter<'a> {
+    pub bind_context: &'a mut BindContext,
+    pub metadata: MetadataRef,
+}
+
+impl<'a> WindowRewriter<'a> {
+    pub fn new(bind_context: &'a mut BindContext, metadata: MetadataRef) -> Self {
+        Self {
+            bind_context,
+            metadata,
+        }
+    }
+
+    pub fn visit(&mut self, scalar: &Scalar) -> Result<Scalar> {
+        match scalar {
+            Scalar::BoundColumnRef(_) => Ok(scalar.clone()),
+            Scalar::ConstantExpr(_) => Ok(scalar.clone()),
+            Scalar::AndExpr(scalar) => Ok(AndExpr {
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::OrExpr(scalar) => Ok(OrExpr {
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::ComparisonExpr(scalar) => Ok(ComparisonExpr {
+                op: scalar.op.clone(),
+                left: Box::new(self.visit(&scalar.left)?),
+                right: Box::new(self.visit(&scalar.right)?),
+                return_type: scalar.return_type.clone(),
+            }
+            .into()),
+            Scalar::FunctionCall(func) => {
+                let new_args = func
+                    .arguments
+                    .iter()
+                    .map(|arg| self.visit(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(FunctionCall {
+                    arguments: new_args,
+                    func_name: func.func_name.clone(),
+                    arg_types: func.arg_types.clone(),
+                    return_type: func.return_type.clone(),
+                }
+                .into())
+            }
+            Scalar::CastExpr(cast) => Ok(CastExpr {
+                argument: Box::new(self.visit(&cast.argument)?),
+                from_type: cast.from_type.clone(),
+                target_type: cast.target_type.clone(),
+            }
+            .into()),
+
+            // Window functions can't be nested in aggregate functions, which is
+            // checked by `TypeChecker`.
+            Scalar::AggregateFunction(_) => Ok(scalar.clone()),
+            Scalar::SubqueryExpr(_) => Ok(scalar.clone()),
+
+            Scalar::WindowFunction(window) => self.replace_window_function(window),
+        }
+    }
+
+    fn replace_window_function(&mut self, window: &WindowFunction) -> Result<Scalar> {
+        let index = match self
+            .bind_context
+            .window_info
+            .window_functions_map
+            .get(&window.display_name)
+        {
+            Some(i) => self.bind_context.window_info.window_functions[*i].index,
+            None => {
+                // Rewrite aggregate functions in the window function, e.g.
+                // `SELECT a, RANK() OVER (ORDER BY SUM(b)) FROM t GROUP BY a`.
+                let mut rewriter = AggregateRewriter::new(self.bind_context, self.metadata.clone());
+                let args = window
+                    .args
+                    .iter()
+                    .map(|arg| rewriter.visit(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                let partition_by = window
+                    .partition_by
+                    .iter()
+                    .map(|expr| rewriter.visit(expr))
+                    .collect::<Result<Vec<_>>>()?;
+                let order_by = window
+                    .order_by
+                    .iter()
+                    .map(|item| {
+                        Ok(WindowOrderBy {
+                            expr: rewriter.visit(&item.expr)?,
+                            asc: item.asc,
+                            nulls_first: item.nulls_first,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let replaced_window = WindowFunction {
+                    args,
+                    partition_by,
+                    order_by,
+                    ..window.clone()
+                };
+                let index = self.metadata.write().add_column(
+                    window.display_name.clone(),
+                    *window.return_type.clone(),
+                    None,
+                );
+
+                let window_info = &mut self.bind_context.window_info;
+                window_info.window_functions.push(ScalarItem {
+                    scalar: replaced_window.into(),
+                    index,
+                });
+                window_info.window_functions_map.insert(
+                    window.display_name.clone(),
+                    window_info.window_functions.len() - 1,
+                );
+                index
+            }
+        };
+
+        Ok(BoundColumnRef {
+            column: ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: window.display_name.clone(),
+                index,
+                data_type: window.return_type.clone(),
+                visible_in_unqualified_wildcard: true,
+            },
+        }
+        .into())
+    }
+}
+
+impl<'a> Binder {
+    /// Analyze window functions in select clause, this will rewrite window functions.
+    /// See `WindowRewriter` for more details.
+    pub(super) fn analyze_window_select(
+        &mut self,
+        bind_context: &mut BindContext,
+        select_list: &mut SelectList<'a>,
+    ) -> Result<()> {
+        for item in select_list.items.iter_mut() {
+            let mut rewriter = WindowRewriter::new(bind_context, self.metadata.clone());
+            let new_scalar = rewriter.visit(&item.scalar)?;
+            item.scalar = new_scalar;
+        }
+
+        Ok(())
+    }
+
+    /// Build a `Window` plan for each window function. The arguments, partition keys
+    /// and order keys of window functions are evaluated by an `EvalScalar` below them.
+    pub(super) fn bind_window(
+        &mut self,
+        bind_context: &BindContext,
+        child: SExpr,
+    ) -> Result<SExpr> {
+        let mut scalar_items: Vec<ScalarItem> = vec![];
+        let mut windows: Vec<Window> =
+            Vec::with_capacity(bind_context.window_info.window_functions.len());
+        for item in bind_context.window_info.window_functions.iter() {
+            let window = WindowFunction::try_from(item.scalar.clone())?;
+            let name = &window.func_name;
+
+            let args = window
+                .args
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| {
+                    self.bind_window_input(
+                        bind_context,
+                        arg,
+                        format!("{name}_arg_{i}"),
+                        &mut scalar_items,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let partition_by = window
+                .partition_by
+                .iter()
+                .enumerate()
+                .map(|(i, expr)| {
+                    self.bind_window_input(
+                        bind_context,
+                        expr,
+                        format!("{name}_partition_by_{i}"),
+                        &mut scalar_items,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let order_by = window
+                .order_by
+                .iter()
+                .enumerate()
+                .map(|(i, order)| {
+                    Ok(WindowOrderBy {
+                        expr: self.bind_window_input(
+                            bind_context,
+                            &order.expr,
+                            format!("{name}_order_by_{i}"),
+                            &mut scalar_items,
+                        )?,
+                        asc: order.asc,
+                        nulls_first: order.nulls_first,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            windows.push(Window {
+                function: WindowFunction {
+                    args,
+                    partition_by,
+                    order_by,
+                    ..window
+                },
+                index: item.index,
+            });
+        }
+
+        let mut new_expr = child;
+        if !scalar_items.is_empty() {
+            let eval_scalar = EvalScalar {
+                items: scalar_items,
+            };
+            new_expr = SExpr::create_unary(eval_scalar.into(), new_expr);
+        }
+        for window in windows {
+            new_expr = SExpr::create_unary(window.into(), new_expr);
+        }
+
+        Ok(new_expr)
+    }
+
+    /// Replace an input of window function with a BoundColumnRef, the scalar
+    /// expression will be added into `scalar_items` if it's not a column.
+    fn bind_window_input(
+        &mut self,
+        bind_context: &BindContext,
+        scalar: &Scalar,
+        name: String,
+        scalar_items: &mut Vec<ScalarItem>,
+    ) -> Result<Scalar> {
+        let scalar = if bind_context.in_grouping {
+            let mut grouping_checker = GroupingChecker::new(bind_context);
+            grouping_checker.resolve(scalar, None)?
+        } else {
+            scalar.clone()
+        };
+        if let Scalar::BoundColumnRef(_) = scalar {
+            return Ok(scalar);
+        }
+
+        let column_binding = self.create_column_binding(None, None, name, scalar.data_type());
+        scalar_items.push(ScalarItem {
+            scalar,
+            index: column_binding.index,
+        });
+        Ok(BoundColumnRef {
+            column: column_binding,
+        }
+        .into())
+    }
+}
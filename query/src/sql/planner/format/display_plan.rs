@@ -50,11 +50,15 @@ impl Plan {
             Plan::AlterTableClusterKey(alter_table_cluster_key) => {
                 Ok(format!("{:?}", alter_table_cluster_key))
             }
+            Plan::AlterTableColumn(alter_table_column) => Ok(format!("{:?}", alter_table_column)),
             Plan::DropTableClusterKey(drop_table_cluster_key) => {
                 Ok(format!("{:?}", drop_table_cluster_key))
             }
+            Plan::ReclusterTable(recluster_table) => Ok(format!("{:?}", recluster_table)),
             Plan::TruncateTable(truncate_table) => Ok(format!("{:?}", truncate_table)),
             Plan::OptimizeTable(optimize_table) => Ok(format!("{:?}", optimize_table)),
+            Plan::VacuumTable(vacuum_table) => Ok(format!("{:?}", vacuum_table)),
+            Plan::AnalyzeTable(analyze_table) => Ok(format!("{:?}", analyze_table)),
             Plan::ExistsTable(exists_table) => Ok(format!("{:?}", exists_table)),
 
             // Views
@@ -62,9 +66,20 @@ impl Plan {
             Plan::AlterView(alter_view) => Ok(format!("{:?}", alter_view)),
             Plan::DropView(drop_view) => Ok(format!("{:?}", drop_view)),
 
+            // Indexes
+            Plan::CreateIndex(create_index) => Ok(format!("{:?}", create_index)),
+            Plan::DropIndex(drop_index) => Ok(format!("{:?}", drop_index)),
+
             // Insert
             Plan::Insert(insert) => Ok(format!("{:?}", insert)),
             Plan::Delete(delete) => Ok(format!("{:?}", delete)),
+            Plan::Update(update) => Ok(format!("{:?}", update)),
+            Plan::MergeInto(merge_into) => Ok(format!("{:?}", merge_into)),
+
+            // Transactions
+            Plan::Begin => Ok("BEGIN".to_string()),
+            Plan::Commit => Ok("COMMIT".to_string()),
+            Plan::Rollback => Ok("ROLLBACK".to_string()),
 
             // Stages
             Plan::ListStage(s) => Ok(format!("{:?}", s)),
@@ -19,10 +19,12 @@ use common_functions::scalars::FunctionFactory;
 use itertools::Itertools;
 
 use super::FormatTreeNode;
+use crate::sql::optimizer::CostEstimation;
 use crate::sql::optimizer::SExpr;
 use crate::sql::plans::Aggregate;
 use crate::sql::plans::AggregateMode;
 use crate::sql::plans::AndExpr;
+use crate::sql::plans::BoundColumnRef;
 use crate::sql::plans::ComparisonExpr;
 use crate::sql::plans::ComparisonOp;
 use crate::sql::plans::EvalScalar;
@@ -33,11 +35,18 @@ use crate::sql::plans::Limit;
 use crate::sql::plans::LogicalGet;
 use crate::sql::plans::LogicalInnerJoin;
 use crate::sql::plans::PhysicalHashJoin;
+use crate::sql::plans::PhysicalNestedLoopJoin;
 use crate::sql::plans::PhysicalScan;
+use crate::sql::plans::PhysicalSortMergeJoin;
 use crate::sql::plans::Project;
+use crate::sql::plans::RecursiveCte;
 use crate::sql::plans::RelOperator;
 use crate::sql::plans::Scalar;
+use crate::sql::plans::SetOperation;
 use crate::sql::plans::Sort;
+use crate::sql::plans::UnionAll;
+use crate::sql::plans::Window;
+use crate::sql::ColumnBinding;
 use crate::sql::MetadataRef;
 use crate::sql::ScalarExpr;
 
@@ -71,6 +80,10 @@ impl Display for FormatContext {
             RelOperator::LogicalInnerJoin(op) => format_logical_inner_join(f, &self.metadata, op),
             RelOperator::PhysicalScan(op) => format_physical_scan(f, &self.metadata, op),
             RelOperator::PhysicalHashJoin(op) => format_hash_join(f, &self.metadata, op),
+            RelOperator::PhysicalNestedLoopJoin(op) => {
+                format_nested_loop_join(f, &self.metadata, op)
+            }
+            RelOperator::PhysicalSortMergeJoin(op) => format_sort_merge_join(f, &self.metadata, op),
             RelOperator::Project(op) => format_project(f, &self.metadata, op),
             RelOperator::EvalScalar(op) => format_eval_scalar(f, &self.metadata, op),
             RelOperator::Filter(op) => format_filter(f, &self.metadata, op),
@@ -78,6 +91,10 @@ impl Display for FormatContext {
             RelOperator::Sort(op) => format_sort(f, &self.metadata, op),
             RelOperator::Limit(op) => format_limit(f, &self.metadata, op),
             RelOperator::Exchange(op) => format_exchange(f, &self.metadata, op),
+            RelOperator::Window(op) => format_window(f, &self.metadata, op),
+            RelOperator::UnionAll(op) => format_union_all(f, &self.metadata, op),
+            RelOperator::SetOperation(op) => format_set_operation(f, &self.metadata, op),
+            RelOperator::RecursiveCte(op) => format_recursive_cte(f, &self.metadata, op),
             RelOperator::Pattern(_) => write!(f, "Pattern"),
         }
     }
@@ -116,6 +133,7 @@ pub fn format_scalar(metadata: &MetadataRef, scalar: &Scalar) -> String {
             format_scalar(metadata, &comp.right)
         ),
         Scalar::AggregateFunction(agg) => agg.display_name.clone(),
+        Scalar::WindowFunction(func) => func.display_name.clone(),
         Scalar::FunctionCall(func) => {
             format!(
                 "{}({})",
@@ -211,16 +229,81 @@ pub fn format_hash_join(
         .join(", ");
     match op.join_type {
         JoinType::Cross => {
-            write!(f, "CrossJoin")
+            write!(f, "CrossJoin")?;
         }
         _ => {
             write!(
                 f,
                 "HashJoin: {}, build keys: [{}], probe keys: [{}], join filters: [{}]",
                 &op.join_type, build_keys, probe_keys, join_filters,
-            )
+            )?;
         }
     }
+    format_estimation(f, &op.estimation)
+}
+
+pub fn format_nested_loop_join(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &PhysicalNestedLoopJoin,
+) -> std::fmt::Result {
+    let conditions = op
+        .conditions
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    write!(
+        f,
+        "NestedLoopJoin: {}, conditions: [{}]",
+        &op.join_type, conditions
+    )?;
+    format_estimation(f, &op.estimation)
+}
+
+pub fn format_sort_merge_join(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &PhysicalSortMergeJoin,
+) -> std::fmt::Result {
+    let left_keys = op
+        .left_keys
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let right_keys = op
+        .right_keys
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let join_filters = op
+        .other_conditions
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    write!(
+        f,
+        "SortMergeJoin: {}, left keys: [{}], right keys: [{}], join filters: [{}]",
+        &op.join_type, left_keys, right_keys, join_filters,
+    )?;
+    format_estimation(f, &op.estimation)
+}
+
+fn format_estimation(
+    f: &mut std::fmt::Formatter<'_>,
+    estimation: &Option<CostEstimation>,
+) -> std::fmt::Result {
+    if let Some(estimation) = estimation {
+        write!(
+            f,
+            ", estimated rows: {:.0}, cost: {}",
+            estimation.cardinality, estimation.cost
+        )?;
+    }
+    Ok(())
 }
 
 pub fn format_physical_scan(
@@ -347,6 +430,38 @@ pub fn format_limit(
     write!(f, "Limit: [{}], Offset: [{}]", limit, op.offset)
 }
 
+pub fn format_window(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &Window,
+) -> std::fmt::Result {
+    let partition_by = op
+        .function
+        .partition_by
+        .iter()
+        .map(|scalar| format_scalar(metadata, scalar))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let order_by = op
+        .function
+        .order_by
+        .iter()
+        .map(|item| {
+            format!(
+                "{} {}",
+                format_scalar(metadata, &item.expr),
+                if item.asc { "ASC" } else { "DESC" }
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ");
+    write!(
+        f,
+        "Window: [{} (#{})], partition by: [{}], order by: [{}]",
+        &op.function.display_name, op.index, partition_by, order_by
+    )
+}
+
 pub fn format_exchange(
     f: &mut std::fmt::Formatter<'_>,
     metadata: &MetadataRef,
@@ -371,3 +486,77 @@ pub fn format_exchange(
         }
     }
 }
+
+fn format_column_bindings(metadata: &MetadataRef, columns: &[ColumnBinding]) -> String {
+    columns
+        .iter()
+        .map(|column| {
+            format_scalar(
+                metadata,
+                &Scalar::BoundColumnRef(BoundColumnRef {
+                    column: column.clone(),
+                }),
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+pub fn format_union_all(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &UnionAll,
+) -> std::fmt::Result {
+    let output_columns = {
+        let metadata = metadata.read();
+        op.output_columns
+            .iter()
+            .map(|index| format!("{} (#{})", metadata.column(*index).name, index))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    write!(
+        f,
+        "UnionAll: output columns: [{}], left columns: [{}], right columns: [{}]",
+        output_columns,
+        format_column_bindings(metadata, &op.left_columns),
+        format_column_bindings(metadata, &op.right_columns),
+    )
+}
+
+pub fn format_set_operation(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &SetOperation,
+) -> std::fmt::Result {
+    write!(
+        f,
+        "SetOperation: {}, left columns: [{}], right columns: [{}]",
+        &op.op_type,
+        format_column_bindings(metadata, &op.left_columns),
+        format_column_bindings(metadata, &op.right_columns),
+    )
+}
+
+pub fn format_recursive_cte(
+    f: &mut std::fmt::Formatter<'_>,
+    metadata: &MetadataRef,
+    op: &RecursiveCte,
+) -> std::fmt::Result {
+    let output_columns = {
+        let metadata = metadata.read();
+        op.output_columns
+            .iter()
+            .map(|index| format!("{} (#{})", metadata.column(*index).name, index))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    write!(
+        f,
+        "RecursiveCte: {}, output columns: [{}], left columns: [{}], right columns: [{}]",
+        &op.cte_name,
+        output_columns,
+        format_column_bindings(metadata, &op.left_columns),
+        format_column_bindings(metadata, &op.right_columns),
+    )
+}
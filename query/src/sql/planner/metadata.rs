@@ -18,6 +18,7 @@ use std::sync::Arc;
 use common_ast::ast::Expr;
 use common_ast::ast::Literal;
 use common_datavalues::prelude::*;
+use common_fuse_meta::meta::StatisticsOfColumns;
 use common_planners::ReadDataSourcePlan;
 use parking_lot::RwLock;
 
@@ -37,6 +38,9 @@ pub struct TableEntry {
     pub table: Arc<dyn Table>,
 
     pub source: ReadDataSourcePlan,
+
+    /// Statistics of the columns, keyed by the index of the column in the table schema.
+    pub column_statistics: Option<StatisticsOfColumns>,
 }
 
 impl Debug for TableEntry {
@@ -57,6 +61,7 @@ impl TableEntry {
         database: String,
         table: Arc<dyn Table>,
         source: ReadDataSourcePlan,
+        column_statistics: Option<StatisticsOfColumns>,
     ) -> Self {
         TableEntry {
             index,
@@ -65,6 +70,7 @@ impl TableEntry {
             database,
             table,
             source,
+            column_statistics,
         }
     }
 }
@@ -169,6 +175,7 @@ impl Metadata {
         database: String,
         table_meta: Arc<dyn Table>,
         source: ReadDataSourcePlan,
+        column_statistics: Option<StatisticsOfColumns>,
     ) -> IndexType {
         let table_name = table_meta.name().to_string();
         let table_index = self.tables.len();
@@ -179,6 +186,7 @@ impl Metadata {
             catalog,
             table: table_meta.clone(),
             source,
+            column_statistics,
         };
         self.tables.push(table_entry);
         for field in table_meta.schema().fields() {
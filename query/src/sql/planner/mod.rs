@@ -14,6 +14,7 @@
 
 use std::sync::Arc;
 
+use common_ast::ast::Statement;
 use common_ast::parser::parse_sql;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
@@ -59,6 +60,9 @@ impl Planner {
         let tokens = tokenize_sql(sql)?;
         let backtrace = Backtrace::new();
         let (stmt, format) = parse_sql(&tokens, &backtrace)?;
+        if let Statement::Query(query) = &stmt {
+            self.ctx.attach_normalized_query(query.to_string());
+        }
 
         // Step 2: bind AST with catalog, and generate a pure logical SExpr
         let metadata = Arc::new(RwLock::new(Metadata::create()));
@@ -15,6 +15,7 @@
 use common_exception::Result;
 
 use super::JoinType;
+use crate::sql::optimizer::CostEstimation;
 use crate::sql::optimizer::Distribution;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
@@ -34,6 +35,9 @@ pub struct PhysicalHashJoin {
     pub join_type: JoinType,
     pub marker_index: Option<IndexType>,
     pub from_correlated_subquery: bool,
+    /// Estimated cardinality and cost of the join, only available if the join
+    /// order has been chosen by the cascades optimizer.
+    pub estimation: Option<CostEstimation>,
 }
 
 impl Operator for PhysicalHashJoin {
@@ -23,13 +23,20 @@ pub mod insert;
 mod limit;
 mod logical_get;
 mod logical_join;
+mod merge_into;
+mod nested_loop_join;
 mod operator;
 mod pattern;
 mod physical_scan;
 mod presign;
 mod project;
+mod recursive_cte;
 mod scalar;
+mod set_operation;
 mod sort;
+mod sort_merge_join;
+mod union_all;
+mod window;
 
 use std::fmt::Display;
 use std::sync::Arc;
@@ -43,11 +50,14 @@ use common_datavalues::DataSchemaRefExt;
 use common_datavalues::ToDataType;
 use common_datavalues::Vu8;
 use common_planners::AlterTableClusterKeyPlan;
+use common_planners::AlterTableColumnPlan;
 use common_planners::AlterUserPlan;
 use common_planners::AlterUserUDFPlan;
 use common_planners::AlterViewPlan;
+use common_planners::AnalyzeTablePlan;
 use common_planners::CallPlan;
 use common_planners::CreateDatabasePlan;
+use common_planners::CreateIndexPlan;
 use common_planners::CreateRolePlan;
 use common_planners::CreateUserPlan;
 use common_planners::CreateUserStagePlan;
@@ -57,6 +67,7 @@ use common_planners::DeletePlan;
 use common_planners::DescribeTablePlan;
 use common_planners::DescribeUserStagePlan;
 use common_planners::DropDatabasePlan;
+use common_planners::DropIndexPlan;
 use common_planners::DropRolePlan;
 use common_planners::DropTableClusterKeyPlan;
 use common_planners::DropTablePlan;
@@ -70,6 +81,7 @@ use common_planners::GrantRolePlan;
 use common_planners::KillPlan;
 use common_planners::ListPlan;
 use common_planners::OptimizeTablePlan;
+use common_planners::ReclusterTablePlan;
 use common_planners::RemoveUserStagePlan;
 use common_planners::RenameDatabasePlan;
 use common_planners::RenameTablePlan;
@@ -82,7 +94,9 @@ use common_planners::ShowGrantsPlan;
 use common_planners::TruncateTablePlan;
 use common_planners::UndropDatabasePlan;
 use common_planners::UndropTablePlan;
+use common_planners::UpdatePlan;
 use common_planners::UseDatabasePlan;
+use common_planners::VacuumTablePlan;
 pub use copy_v2::CopyPlanV2;
 pub use copy_v2::ValidationMode;
 pub use create_table_v2::CreateTablePlanV2;
@@ -98,15 +112,23 @@ pub use limit::Limit;
 pub use logical_get::LogicalGet;
 pub use logical_join::JoinType;
 pub use logical_join::LogicalInnerJoin;
+pub use merge_into::MergeInto;
+pub use nested_loop_join::PhysicalNestedLoopJoin;
 pub use operator::*;
 pub use pattern::PatternPlan;
 pub use physical_scan::PhysicalScan;
 pub use presign::PresignAction;
 pub use presign::PresignPlan;
 pub use project::Project;
+pub use recursive_cte::RecursiveCte;
 pub use scalar::*;
+pub use set_operation::SetOperation;
+pub use set_operation::SetOperationType;
 pub use sort::Sort;
 pub use sort::SortItem;
+pub use sort_merge_join::PhysicalSortMergeJoin;
+pub use union_all::UnionAll;
+pub use window::Window;
 
 use super::BindContext;
 use super::MetadataRef;
@@ -153,20 +175,35 @@ pub enum Plan {
     UndropTable(Box<UndropTablePlan>),
     RenameTable(Box<RenameTablePlan>),
     AlterTableClusterKey(Box<AlterTableClusterKeyPlan>),
+    AlterTableColumn(Box<AlterTableColumnPlan>),
     DropTableClusterKey(Box<DropTableClusterKeyPlan>),
+    ReclusterTable(Box<ReclusterTablePlan>),
     TruncateTable(Box<TruncateTablePlan>),
     OptimizeTable(Box<OptimizeTablePlan>),
+    VacuumTable(Box<VacuumTablePlan>),
+    AnalyzeTable(Box<AnalyzeTablePlan>),
     ExistsTable(Box<ExistsTablePlan>),
 
     // Insert
     Insert(Box<Insert>),
     Delete(Box<DeletePlan>),
+    Update(Box<UpdatePlan>),
+    MergeInto(Box<MergeInto>),
+
+    // Transactions
+    Begin,
+    Commit,
+    Rollback,
 
     // Views
     CreateView(Box<CreateViewPlan>),
     AlterView(Box<AlterViewPlan>),
     DropView(Box<DropViewPlan>),
 
+    // Indexes
+    CreateIndex(Box<CreateIndexPlan>),
+    DropIndex(Box<DropIndexPlan>),
+
     // Account
     // ShowUsers -> Rewrite to Query,
     AlterUser(Box<AlterUserPlan>),
@@ -222,13 +259,19 @@ impl Display for Plan {
             Plan::UndropTable(_) => write!(f, "UndropTable"),
             Plan::RenameTable(_) => write!(f, "RenameTable"),
             Plan::AlterTableClusterKey(_) => write!(f, "AlterTableClusterKey"),
+            Plan::AlterTableColumn(_) => write!(f, "AlterTableColumn"),
             Plan::DropTableClusterKey(_) => write!(f, "DropTableClusterKey"),
+            Plan::ReclusterTable(_) => write!(f, "ReclusterTable"),
             Plan::TruncateTable(_) => write!(f, "TruncateTable"),
             Plan::OptimizeTable(_) => write!(f, "OptimizeTable"),
+            Plan::VacuumTable(_) => write!(f, "VacuumTable"),
+            Plan::AnalyzeTable(_) => write!(f, "AnalyzeTable"),
             Plan::ExistsTable(_) => write!(f, "ExistsTable"),
             Plan::CreateView(_) => write!(f, "CreateView"),
             Plan::AlterView(_) => write!(f, "AlterView"),
             Plan::DropView(_) => write!(f, "DropView"),
+            Plan::CreateIndex(_) => write!(f, "CreateIndex"),
+            Plan::DropIndex(_) => write!(f, "DropIndex"),
             Plan::AlterUser(_) => write!(f, "AlterUser"),
             Plan::CreateUser(_) => write!(f, "CreateUser"),
             Plan::DropUser(_) => write!(f, "DropUser"),
@@ -249,6 +292,11 @@ impl Display for Plan {
             Plan::DropUDF(_) => write!(f, "DropUDF"),
             Plan::Insert(_) => write!(f, "Insert"),
             Plan::Delete(_) => write!(f, "Delete"),
+            Plan::Update(_) => write!(f, "Update"),
+            Plan::MergeInto(_) => write!(f, "MergeInto"),
+            Plan::Begin => write!(f, "Begin"),
+            Plan::Commit => write!(f, "Commit"),
+            Plan::Rollback => write!(f, "Rollback"),
             Plan::Call(_) => write!(f, "Call"),
             Plan::Presign(_) => write!(f, "Presign"),
             Plan::SetVariable(_) => write!(f, "SetVariable"),
@@ -283,13 +331,19 @@ impl Plan {
             Plan::UndropTable(plan) => plan.schema(),
             Plan::RenameTable(plan) => plan.schema(),
             Plan::AlterTableClusterKey(plan) => plan.schema(),
+            Plan::AlterTableColumn(plan) => plan.schema(),
             Plan::DropTableClusterKey(plan) => plan.schema(),
+            Plan::ReclusterTable(plan) => plan.schema(),
             Plan::TruncateTable(plan) => plan.schema(),
             Plan::OptimizeTable(plan) => plan.schema(),
+            Plan::VacuumTable(plan) => plan.schema(),
+            Plan::AnalyzeTable(plan) => plan.schema(),
             Plan::ExistsTable(plan) => plan.schema(),
             Plan::CreateView(plan) => plan.schema(),
             Plan::AlterView(plan) => plan.schema(),
             Plan::DropView(plan) => plan.schema(),
+            Plan::CreateIndex(plan) => plan.schema(),
+            Plan::DropIndex(plan) => plan.schema(),
             Plan::AlterUser(plan) => plan.schema(),
             Plan::CreateUser(plan) => plan.schema(),
             Plan::DropUser(plan) => plan.schema(),
@@ -310,6 +364,9 @@ impl Plan {
             Plan::DropUDF(_) => Arc::new(DataSchema::empty()),
             Plan::Insert(plan) => plan.schema(),
             Plan::Delete(_) => Arc::new(DataSchema::empty()),
+            Plan::Update(_) => Arc::new(DataSchema::empty()),
+            Plan::MergeInto(plan) => plan.schema(),
+            Plan::Begin | Plan::Commit | Plan::Rollback => Arc::new(DataSchema::empty()),
             Plan::Call(_) => Arc::new(DataSchema::empty()),
             Plan::Presign(plan) => plan.schema(),
             Plan::SetVariable(plan) => plan.schema(),
@@ -0,0 +1,96 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use super::JoinType;
+use crate::sql::optimizer::CostEstimation;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+
+/// Join every row of the left side with all the rows of the right side, and keep the
+/// pairs satisfying the conditions. It's used for the joins without equi-conditions,
+/// the right side is expected to be small since it's kept in memory.
+#[derive(Clone, Debug)]
+pub struct PhysicalNestedLoopJoin {
+    pub conditions: Vec<Scalar>,
+    pub join_type: JoinType,
+    /// Estimated cardinality and cost of the join, only available if the join
+    /// order has been chosen by the cascades optimizer.
+    pub estimation: Option<CostEstimation>,
+}
+
+impl Operator for PhysicalNestedLoopJoin {
+    fn rel_op(&self) -> RelOp {
+        RelOp::PhysicalNestedLoopJoin
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        false
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        None
+    }
+}
+
+impl PhysicalOperator for PhysicalNestedLoopJoin {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        // The right side is broadcasted, so the distribution of left side is kept.
+        let left_prop = rel_expr.derive_physical_prop_child(0)?;
+        Ok(PhysicalProperty {
+            distribution: left_prop.distribution,
+        })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        rel_expr: &RelExpr<'a>,
+        child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        let mut required = required.clone();
+
+        let left_physical_prop = rel_expr.derive_physical_prop_child(0)?;
+        let right_physical_prop = rel_expr.derive_physical_prop_child(1)?;
+
+        if left_physical_prop.distribution == Distribution::Serial
+            || right_physical_prop.distribution == Distribution::Serial
+        {
+            required.distribution = Distribution::Serial;
+        } else if child_index == 0 {
+            required.distribution = Distribution::Any;
+        } else {
+            // Every node joins its part of the left side with the whole right side.
+            required.distribution = Distribution::Broadcast;
+        }
+
+        Ok(required)
+    }
+}
@@ -22,10 +22,16 @@ use super::hash_join::PhysicalHashJoin;
 use super::limit::Limit;
 use super::logical_get::LogicalGet;
 use super::logical_join::LogicalInnerJoin;
+use super::nested_loop_join::PhysicalNestedLoopJoin;
 use super::pattern::PatternPlan;
 use super::physical_scan::PhysicalScan;
 use super::project::Project;
+use super::recursive_cte::RecursiveCte;
+use super::set_operation::SetOperation;
 use super::sort::Sort;
+use super::sort_merge_join::PhysicalSortMergeJoin;
+use super::union_all::UnionAll;
+use super::window::Window;
 use crate::sql::optimizer::PhysicalProperty;
 use crate::sql::optimizer::RelExpr;
 use crate::sql::optimizer::RelationalProperty;
@@ -73,6 +79,8 @@ pub enum RelOp {
     // Physical operators
     PhysicalScan,
     PhysicalHashJoin,
+    PhysicalNestedLoopJoin,
+    PhysicalSortMergeJoin,
 
     // Operators that are both logical and physical
     Project,
@@ -82,6 +90,10 @@ pub enum RelOp {
     Sort,
     Limit,
     Exchange,
+    Window,
+    UnionAll,
+    SetOperation,
+    RecursiveCte,
 
     // Pattern
     Pattern,
@@ -95,6 +107,8 @@ pub enum RelOperator {
 
     PhysicalScan(PhysicalScan),
     PhysicalHashJoin(PhysicalHashJoin),
+    PhysicalNestedLoopJoin(PhysicalNestedLoopJoin),
+    PhysicalSortMergeJoin(PhysicalSortMergeJoin),
 
     Project(Project),
     EvalScalar(EvalScalar),
@@ -103,6 +117,10 @@ pub enum RelOperator {
     Sort(Sort),
     Limit(Limit),
     Exchange(Exchange),
+    Window(Window),
+    UnionAll(UnionAll),
+    SetOperation(SetOperation),
+    RecursiveCte(RecursiveCte),
 
     Pattern(PatternPlan),
 }
@@ -114,6 +132,8 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.rel_op(),
             RelOperator::PhysicalScan(rel_op) => rel_op.rel_op(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.rel_op(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.rel_op(),
+            RelOperator::PhysicalSortMergeJoin(rel_op) => rel_op.rel_op(),
             RelOperator::Project(rel_op) => rel_op.rel_op(),
             RelOperator::EvalScalar(rel_op) => rel_op.rel_op(),
             RelOperator::Filter(rel_op) => rel_op.rel_op(),
@@ -122,6 +142,10 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.rel_op(),
             RelOperator::Pattern(rel_op) => rel_op.rel_op(),
             RelOperator::Exchange(rel_op) => rel_op.rel_op(),
+            RelOperator::Window(rel_op) => rel_op.rel_op(),
+            RelOperator::UnionAll(rel_op) => rel_op.rel_op(),
+            RelOperator::SetOperation(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.rel_op(),
         }
     }
 
@@ -131,6 +155,8 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.is_physical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.is_physical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.is_physical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.is_physical(),
+            RelOperator::PhysicalSortMergeJoin(rel_op) => rel_op.is_physical(),
             RelOperator::Project(rel_op) => rel_op.is_physical(),
             RelOperator::EvalScalar(rel_op) => rel_op.is_physical(),
             RelOperator::Filter(rel_op) => rel_op.is_physical(),
@@ -139,6 +165,10 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.is_physical(),
             RelOperator::Pattern(rel_op) => rel_op.is_physical(),
             RelOperator::Exchange(rel_op) => rel_op.is_physical(),
+            RelOperator::Window(rel_op) => rel_op.is_physical(),
+            RelOperator::UnionAll(rel_op) => rel_op.is_physical(),
+            RelOperator::SetOperation(rel_op) => rel_op.is_physical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.is_physical(),
         }
     }
 
@@ -148,6 +178,8 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.is_logical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.is_logical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.is_logical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.is_logical(),
+            RelOperator::PhysicalSortMergeJoin(rel_op) => rel_op.is_logical(),
             RelOperator::Project(rel_op) => rel_op.is_logical(),
             RelOperator::EvalScalar(rel_op) => rel_op.is_logical(),
             RelOperator::Filter(rel_op) => rel_op.is_logical(),
@@ -156,6 +188,10 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.is_logical(),
             RelOperator::Pattern(rel_op) => rel_op.is_logical(),
             RelOperator::Exchange(rel_op) => rel_op.is_logical(),
+            RelOperator::Window(rel_op) => rel_op.is_logical(),
+            RelOperator::UnionAll(rel_op) => rel_op.is_logical(),
+            RelOperator::SetOperation(rel_op) => rel_op.is_logical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.is_logical(),
         }
     }
 
@@ -165,6 +201,8 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.as_logical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.as_logical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.as_logical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.as_logical(),
+            RelOperator::PhysicalSortMergeJoin(rel_op) => rel_op.as_logical(),
             RelOperator::Project(rel_op) => rel_op.as_logical(),
             RelOperator::EvalScalar(rel_op) => rel_op.as_logical(),
             RelOperator::Filter(rel_op) => rel_op.as_logical(),
@@ -173,6 +211,10 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.as_logical(),
             RelOperator::Pattern(rel_op) => rel_op.as_logical(),
             RelOperator::Exchange(rel_op) => rel_op.as_logical(),
+            RelOperator::Window(rel_op) => rel_op.as_logical(),
+            RelOperator::UnionAll(rel_op) => rel_op.as_logical(),
+            RelOperator::SetOperation(rel_op) => rel_op.as_logical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.as_logical(),
         }
     }
 
@@ -182,6 +224,8 @@ impl Operator for RelOperator {
             RelOperator::LogicalInnerJoin(rel_op) => rel_op.as_physical(),
             RelOperator::PhysicalScan(rel_op) => rel_op.as_physical(),
             RelOperator::PhysicalHashJoin(rel_op) => rel_op.as_physical(),
+            RelOperator::PhysicalNestedLoopJoin(rel_op) => rel_op.as_physical(),
+            RelOperator::PhysicalSortMergeJoin(rel_op) => rel_op.as_physical(),
             RelOperator::Project(rel_op) => rel_op.as_physical(),
             RelOperator::EvalScalar(rel_op) => rel_op.as_physical(),
             RelOperator::Filter(rel_op) => rel_op.as_physical(),
@@ -190,6 +234,10 @@ impl Operator for RelOperator {
             RelOperator::Limit(rel_op) => rel_op.as_physical(),
             RelOperator::Pattern(rel_op) => rel_op.as_physical(),
             RelOperator::Exchange(rel_op) => rel_op.as_physical(),
+            RelOperator::Window(rel_op) => rel_op.as_physical(),
+            RelOperator::UnionAll(rel_op) => rel_op.as_physical(),
+            RelOperator::SetOperation(rel_op) => rel_op.as_physical(),
+            RelOperator::RecursiveCte(rel_op) => rel_op.as_physical(),
         }
     }
 }
@@ -271,6 +319,44 @@ impl TryFrom<RelOperator> for PhysicalHashJoin {
     }
 }
 
+impl From<PhysicalNestedLoopJoin> for RelOperator {
+    fn from(v: PhysicalNestedLoopJoin) -> Self {
+        Self::PhysicalNestedLoopJoin(v)
+    }
+}
+
+impl TryFrom<RelOperator> for PhysicalNestedLoopJoin {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::PhysicalNestedLoopJoin(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to PhysicalNestedLoopJoin",
+            ))
+        }
+    }
+}
+
+impl From<PhysicalSortMergeJoin> for RelOperator {
+    fn from(v: PhysicalSortMergeJoin) -> Self {
+        Self::PhysicalSortMergeJoin(v)
+    }
+}
+
+impl TryFrom<RelOperator> for PhysicalSortMergeJoin {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::PhysicalSortMergeJoin(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to PhysicalSortMergeJoin",
+            ))
+        }
+    }
+}
+
 impl From<Project> for RelOperator {
     fn from(v: Project) -> Self {
         Self::Project(v)
@@ -422,3 +508,79 @@ impl TryFrom<RelOperator> for Exchange {
         }
     }
 }
+
+impl From<Window> for RelOperator {
+    fn from(v: Window) -> Self {
+        Self::Window(v)
+    }
+}
+
+impl TryFrom<RelOperator> for Window {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::Window(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to Window",
+            ))
+        }
+    }
+}
+
+impl From<UnionAll> for RelOperator {
+    fn from(v: UnionAll) -> Self {
+        Self::UnionAll(v)
+    }
+}
+
+impl TryFrom<RelOperator> for UnionAll {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::UnionAll(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to UnionAll",
+            ))
+        }
+    }
+}
+
+impl From<SetOperation> for RelOperator {
+    fn from(v: SetOperation) -> Self {
+        Self::SetOperation(v)
+    }
+}
+
+impl TryFrom<RelOperator> for SetOperation {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::SetOperation(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to SetOperation",
+            ))
+        }
+    }
+}
+
+impl From<RecursiveCte> for RelOperator {
+    fn from(v: RecursiveCte) -> Self {
+        Self::RecursiveCte(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveCte {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveCte(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast RelOperator to RecursiveCte",
+            ))
+        }
+    }
+}
@@ -19,6 +19,7 @@ use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::scalars::FunctionFactory;
+use common_functions::window::WindowFrame;
 
 use crate::sql::binder::ColumnBinding;
 use crate::sql::optimizer::ColumnSet;
@@ -49,6 +50,7 @@ pub enum Scalar {
     OrExpr(OrExpr),
     ComparisonExpr(ComparisonExpr),
     AggregateFunction(AggregateFunction),
+    WindowFunction(WindowFunction),
     FunctionCall(FunctionCall),
     // TODO(leiysky): maybe we don't need this variant any more
     // after making functions static typed?
@@ -65,6 +67,7 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.data_type(),
             Scalar::ComparisonExpr(scalar) => scalar.data_type(),
             Scalar::AggregateFunction(scalar) => scalar.data_type(),
+            Scalar::WindowFunction(scalar) => scalar.data_type(),
             Scalar::FunctionCall(scalar) => scalar.data_type(),
             Scalar::CastExpr(scalar) => scalar.data_type(),
             Scalar::SubqueryExpr(scalar) => scalar.data_type(),
@@ -79,6 +82,7 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.used_columns(),
             Scalar::ComparisonExpr(scalar) => scalar.used_columns(),
             Scalar::AggregateFunction(scalar) => scalar.used_columns(),
+            Scalar::WindowFunction(scalar) => scalar.used_columns(),
             Scalar::FunctionCall(scalar) => scalar.used_columns(),
             Scalar::CastExpr(scalar) => scalar.used_columns(),
             Scalar::SubqueryExpr(scalar) => scalar.used_columns(),
@@ -93,6 +97,7 @@ impl ScalarExpr for Scalar {
             Scalar::OrExpr(scalar) => scalar.is_deterministic(),
             Scalar::ComparisonExpr(scalar) => scalar.is_deterministic(),
             Scalar::AggregateFunction(scalar) => scalar.is_deterministic(),
+            Scalar::WindowFunction(scalar) => scalar.is_deterministic(),
             Scalar::FunctionCall(scalar) => scalar.is_deterministic(),
             Scalar::CastExpr(scalar) => scalar.is_deterministic(),
             Scalar::SubqueryExpr(scalar) => scalar.is_deterministic(),
@@ -210,6 +215,25 @@ impl TryFrom<Scalar> for AggregateFunction {
     }
 }
 
+impl From<WindowFunction> for Scalar {
+    fn from(v: WindowFunction) -> Self {
+        Self::WindowFunction(v)
+    }
+}
+
+impl TryFrom<Scalar> for WindowFunction {
+    type Error = ErrorCode;
+    fn try_from(value: Scalar) -> Result<Self> {
+        if let Scalar::WindowFunction(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::LogicalError(
+                "Cannot downcast Scalar to WindowFunction",
+            ))
+        }
+    }
+}
+
 impl From<FunctionCall> for Scalar {
     fn from(v: FunctionCall) -> Self {
         Self::FunctionCall(v)
@@ -462,6 +486,57 @@ impl ScalarExpr for AggregateFunction {
     }
 }
 
+/// A function call with `OVER` clause, which can be either an aggregate function
+/// or a built-in window function, e.g. `ROW_NUMBER`, `RANK`, `LAG`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct WindowFunction {
+    pub display_name: String,
+
+    pub func_name: String,
+    pub params: Vec<DataValue>,
+    pub args: Vec<Scalar>,
+    pub partition_by: Vec<Scalar>,
+    pub order_by: Vec<WindowOrderBy>,
+    pub frame: WindowFrame,
+    pub return_type: Box<DataTypeImpl>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WindowOrderBy {
+    pub expr: Scalar,
+    pub asc: bool,
+    pub nulls_first: bool,
+}
+
+impl WindowFunction {
+    /// All the scalar expressions the window function depends on, including
+    /// arguments, partition keys and order keys.
+    pub fn input_scalars(&self) -> impl Iterator<Item = &Scalar> {
+        self.args
+            .iter()
+            .chain(self.partition_by.iter())
+            .chain(self.order_by.iter().map(|item| &item.expr))
+    }
+}
+
+impl ScalarExpr for WindowFunction {
+    fn data_type(&self) -> DataTypeImpl {
+        *self.return_type.clone()
+    }
+
+    fn used_columns(&self) -> ColumnSet {
+        let mut result = ColumnSet::new();
+        for scalar in self.input_scalars() {
+            result = result.union(&scalar.used_columns()).cloned().collect();
+        }
+        result
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct FunctionCall {
     pub arguments: Vec<Scalar>,
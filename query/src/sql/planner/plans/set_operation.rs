@@ -0,0 +1,115 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::Display;
+
+use common_exception::Result;
+
+use super::union_all::compute_required_prop_of_set_operation;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+use crate::sql::ColumnBinding;
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SetOperationType {
+    IntersectAll,
+    ExceptAll,
+}
+
+impl Display for SetOperationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SetOperationType::IntersectAll => write!(f, "INTERSECT ALL"),
+            SetOperationType::ExceptAll => write!(f, "EXCEPT ALL"),
+        }
+    }
+}
+
+/// `INTERSECT ALL` or `EXCEPT ALL` of the two inputs, which keeps the duplicated rows.
+///
+/// The rows of right input are counted by hash table, then every row of left input
+/// consumes a count of the equal rows. A left row is output by `INTERSECT ALL` if there
+/// is a count to consume, otherwise it's output by `EXCEPT ALL`.
+#[derive(Clone, Debug)]
+pub struct SetOperation {
+    pub op_type: SetOperationType,
+    pub left_columns: Vec<ColumnBinding>,
+    pub right_columns: Vec<ColumnBinding>,
+}
+
+impl Operator for SetOperation {
+    fn rel_op(&self) -> RelOp {
+        RelOp::SetOperation
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl PhysicalOperator for SetOperation {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        // The output rows are the rows of left input
+        rel_expr.derive_physical_prop_child(0)
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        rel_expr: &RelExpr<'a>,
+        child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        compute_required_prop_of_set_operation(
+            rel_expr,
+            child_index,
+            required,
+            &self.left_columns,
+            &self.right_columns,
+        )
+    }
+}
+
+impl LogicalOperator for SetOperation {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let left_prop = rel_expr.derive_relational_prop_child(0)?;
+        let right_prop = rel_expr.derive_relational_prop_child(1)?;
+
+        Ok(RelationalProperty {
+            output_columns: left_prop.output_columns,
+            outer_columns: left_prop
+                .outer_columns
+                .union(&right_prop.outer_columns)
+                .cloned()
+                .collect(),
+        })
+    }
+}
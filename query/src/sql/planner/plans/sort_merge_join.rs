@@ -0,0 +1,106 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use super::JoinType;
+use crate::sql::optimizer::CostEstimation;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+
+/// Join two sides by merging them in the order of the join keys. Both sides are
+/// sorted before merging, unless they are already in order, e.g. clustered by the keys.
+#[derive(Clone, Debug)]
+pub struct PhysicalSortMergeJoin {
+    pub left_keys: Vec<Scalar>,
+    pub right_keys: Vec<Scalar>,
+    pub other_conditions: Vec<Scalar>,
+    pub join_type: JoinType,
+    /// Estimated cardinality and cost of the join, only available if the join
+    /// order has been chosen by the cascades optimizer.
+    pub estimation: Option<CostEstimation>,
+}
+
+impl Operator for PhysicalSortMergeJoin {
+    fn rel_op(&self) -> RelOp {
+        RelOp::PhysicalSortMergeJoin
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        false
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        None
+    }
+}
+
+impl PhysicalOperator for PhysicalSortMergeJoin {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        let left_prop = rel_expr.derive_physical_prop_child(0)?;
+        let right_prop = rel_expr.derive_physical_prop_child(1)?;
+
+        match (&left_prop.distribution, &right_prop.distribution) {
+            (Distribution::Hash(left_keys), Distribution::Hash(right_keys)) => {
+                Ok(PhysicalProperty {
+                    distribution: Distribution::Hash(
+                        left_keys.iter().chain(right_keys.iter()).cloned().collect(),
+                    ),
+                })
+            }
+            _ => Ok(PhysicalProperty {
+                distribution: left_prop.distribution.clone(),
+            }),
+        }
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        rel_expr: &RelExpr<'a>,
+        child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        let mut required = required.clone();
+
+        let left_physical_prop = rel_expr.derive_physical_prop_child(0)?;
+        let right_physical_prop = rel_expr.derive_physical_prop_child(1)?;
+
+        if left_physical_prop.distribution == Distribution::Serial
+            || right_physical_prop.distribution == Distribution::Serial
+        {
+            required.distribution = Distribution::Serial;
+        } else if child_index == 0 {
+            required.distribution = Distribution::Hash(self.left_keys.clone());
+        } else {
+            required.distribution = Distribution::Hash(self.right_keys.clone());
+        }
+
+        Ok(required)
+    }
+}
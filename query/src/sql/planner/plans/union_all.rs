@@ -0,0 +1,158 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+
+use crate::sql::optimizer::ColumnSet;
+use crate::sql::optimizer::Distribution;
+use crate::sql::optimizer::PhysicalProperty;
+use crate::sql::optimizer::RelExpr;
+use crate::sql::optimizer::RelationalProperty;
+use crate::sql::optimizer::RequiredProperty;
+use crate::sql::plans::BoundColumnRef;
+use crate::sql::plans::LogicalOperator;
+use crate::sql::plans::Operator;
+use crate::sql::plans::PhysicalOperator;
+use crate::sql::plans::RelOp;
+use crate::sql::plans::Scalar;
+use crate::sql::ColumnBinding;
+use crate::sql::IndexType;
+
+/// Concatenate the rows of the two inputs, the i-th output column takes the values of
+/// the i-th column in `left_columns` and `right_columns`, which are of the same type.
+#[derive(Clone, Debug)]
+pub struct UnionAll {
+    pub left_columns: Vec<ColumnBinding>,
+    pub right_columns: Vec<ColumnBinding>,
+    // Indices of the output columns
+    pub output_columns: Vec<IndexType>,
+}
+
+impl Operator for UnionAll {
+    fn rel_op(&self) -> RelOp {
+        RelOp::UnionAll
+    }
+
+    fn is_physical(&self) -> bool {
+        true
+    }
+
+    fn is_logical(&self) -> bool {
+        true
+    }
+
+    fn as_physical(&self) -> Option<&dyn PhysicalOperator> {
+        Some(self)
+    }
+
+    fn as_logical(&self) -> Option<&dyn LogicalOperator> {
+        Some(self)
+    }
+}
+
+impl PhysicalOperator for UnionAll {
+    fn derive_physical_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<PhysicalProperty> {
+        let left_prop = rel_expr.derive_physical_prop_child(0)?;
+        let distribution = match left_prop.distribution {
+            Distribution::Serial => Distribution::Serial,
+            _ => Distribution::Random,
+        };
+        Ok(PhysicalProperty { distribution })
+    }
+
+    fn compute_required_prop_child<'a>(
+        &self,
+        rel_expr: &RelExpr<'a>,
+        _child_index: usize,
+        required: &RequiredProperty,
+    ) -> Result<RequiredProperty> {
+        let mut required = required.clone();
+
+        let left_physical_prop = rel_expr.derive_physical_prop_child(0)?;
+        let right_physical_prop = rel_expr.derive_physical_prop_child(1)?;
+
+        // The rows are only concatenated, so each child can be processed where it's
+        // distributed, unless any child can only be processed by a single node.
+        if left_physical_prop.distribution == Distribution::Serial
+            || right_physical_prop.distribution == Distribution::Serial
+        {
+            required.distribution = Distribution::Serial;
+        } else {
+            required.distribution = Distribution::Any;
+        }
+        Ok(required)
+    }
+}
+
+impl LogicalOperator for UnionAll {
+    fn derive_relational_prop<'a>(&self, rel_expr: &RelExpr<'a>) -> Result<RelationalProperty> {
+        let left_prop = rel_expr.derive_relational_prop_child(0)?;
+        let right_prop = rel_expr.derive_relational_prop_child(1)?;
+
+        // Derive output columns
+        let output_columns = self.output_columns.iter().cloned().collect();
+
+        // Derive outer columns
+        let outer_columns: ColumnSet = left_prop
+            .outer_columns
+            .union(&right_prop.outer_columns)
+            .cloned()
+            .collect();
+
+        Ok(RelationalProperty {
+            output_columns,
+            outer_columns,
+        })
+    }
+}
+
+/// Compute the required property of the children of a binary set operation. The rows equal
+/// to each other must be processed by the same node, so the children are shuffled by all
+/// the columns, unless any child can only be processed by a single node.
+pub(crate) fn compute_required_prop_of_set_operation(
+    rel_expr: &RelExpr,
+    child_index: usize,
+    required: &RequiredProperty,
+    left_columns: &[ColumnBinding],
+    right_columns: &[ColumnBinding],
+) -> Result<RequiredProperty> {
+    let mut required = required.clone();
+
+    let left_physical_prop = rel_expr.derive_physical_prop_child(0)?;
+    let right_physical_prop = rel_expr.derive_physical_prop_child(1)?;
+
+    if left_physical_prop.distribution == Distribution::Serial
+        || right_physical_prop.distribution == Distribution::Serial
+    {
+        required.distribution = Distribution::Serial;
+    } else {
+        let columns = if child_index == 0 {
+            left_columns
+        } else {
+            right_columns
+        };
+        required.distribution = Distribution::Hash(
+            columns
+                .iter()
+                .map(|column| {
+                    Scalar::BoundColumnRef(BoundColumnRef {
+                        column: column.clone(),
+                    })
+                })
+                .collect(),
+        );
+    }
+
+    Ok(required)
+}
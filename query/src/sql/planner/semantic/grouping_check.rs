@@ -63,6 +63,15 @@ impl<'a> GroupingChecker<'a> {
         }
 
         match scalar {
+            Scalar::BoundColumnRef(column)
+                if self
+                    .bind_context
+                    .window_info
+                    .is_window_column(column.column.index) =>
+            {
+                // Output columns of window functions are computed after aggregation
+                Ok(scalar.clone())
+            }
             Scalar::BoundColumnRef(column) => {
                 // If this is a group item, then it should have been replaced with `group_items_map`
                 let mut err_msg = format!(
@@ -140,6 +149,30 @@ impl<'a> GroupingChecker<'a> {
                 }
                 Err(ErrorCode::LogicalError("Invalid aggregate function"))
             }
+
+            Scalar::WindowFunction(window) => {
+                if let Some(column) = self
+                    .bind_context
+                    .window_info
+                    .window_functions_map
+                    .get(&window.display_name)
+                {
+                    let window_func = &self.bind_context.window_info.window_functions[*column];
+                    let column_binding = ColumnBinding {
+                        database_name: None,
+                        table_name: None,
+                        column_name: window.display_name.clone(),
+                        index: window_func.index,
+                        data_type: Box::new(window_func.scalar.data_type()),
+                        visible_in_unqualified_wildcard: true,
+                    };
+                    return Ok(BoundColumnRef {
+                        column: column_binding,
+                    }
+                    .into());
+                }
+                Err(ErrorCode::LogicalError("Invalid window function"))
+            }
         }
     }
 }
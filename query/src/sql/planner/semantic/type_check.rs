@@ -25,12 +25,17 @@ use common_ast::ast::Query;
 use common_ast::ast::SubqueryModifier;
 use common_ast::ast::TrimWhere;
 use common_ast::ast::UnaryOperator;
+use common_ast::ast::WindowFrame as AstWindowFrame;
+use common_ast::ast::WindowFrameBound as AstWindowFrameBound;
+use common_ast::ast::WindowFrameUnits as AstWindowFrameUnits;
+use common_ast::ast::WindowSpec;
 use common_ast::parser::parse_expr;
 use common_ast::parser::token::Token;
 use common_ast::parser::tokenize_sql;
 use common_ast::Backtrace;
 use common_ast::DisplayError;
 use common_datavalues::type_coercion::merge_types;
+use common_datavalues::wrap_nullable;
 use common_datavalues::ArrayType;
 use common_datavalues::BooleanType;
 use common_datavalues::DataField;
@@ -41,6 +46,7 @@ use common_datavalues::IntervalType;
 use common_datavalues::NullType;
 use common_datavalues::StringType;
 use common_datavalues::TimestampType;
+use common_datavalues::UInt64Type;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
@@ -48,6 +54,10 @@ use common_functions::is_builtin_function;
 use common_functions::scalars::CastFunction;
 use common_functions::scalars::FunctionFactory;
 use common_functions::scalars::TupleFunction;
+use common_functions::window::is_builtin_window_function;
+use common_functions::window::WindowFrame;
+use common_functions::window::WindowFrameBound;
+use common_functions::window::WindowFrameUnits;
 use common_planners::validate_function_arg;
 
 use crate::evaluator::Evaluator;
@@ -70,6 +80,8 @@ use crate::sql::plans::OrExpr;
 use crate::sql::plans::Scalar;
 use crate::sql::plans::SubqueryExpr;
 use crate::sql::plans::SubqueryType;
+use crate::sql::plans::WindowFunction;
+use crate::sql::plans::WindowOrderBy;
 use crate::sql::BindContext;
 use crate::sql::ScalarExpr;
 
@@ -90,6 +102,10 @@ pub struct TypeChecker<'a> {
     // true if current expr is inside an aggregate function.
     // This is used to check if there is nested aggregate function.
     in_aggregate_function: bool,
+
+    // true if current expr is inside a window function.
+    // This is used to check if there is nested window function.
+    in_window_function: bool,
 }
 
 impl<'a> TypeChecker<'a> {
@@ -103,6 +119,7 @@ impl<'a> TypeChecker<'a> {
             ctx,
             metadata,
             in_aggregate_function: false,
+            in_window_function: false,
         }
     }
 
@@ -414,6 +431,7 @@ impl<'a> TypeChecker<'a> {
                                 },
                                 args: vec![*operand.clone(), c.clone()],
                                 params: vec![],
+                                window: None,
                             };
                             arguments.push(equal_expr)
                         }
@@ -477,9 +495,19 @@ impl<'a> TypeChecker<'a> {
                 name,
                 args,
                 params,
-                ..
+                window,
             } => {
                 let func_name = name.name.as_str();
+                if let Some(window) = window {
+                    return self
+                        .resolve_window_function(expr, func_name, *distinct, params, args, window)
+                        .await;
+                }
+                if is_builtin_window_function(func_name) {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "window function {func_name} requires an OVER clause"
+                    ))));
+                }
                 if !is_builtin_function(func_name) {
                     return self.resolve_udf(span, func_name, args).await;
                 }
@@ -561,7 +589,16 @@ impl<'a> TypeChecker<'a> {
                 }
             }
 
-            Expr::CountAll { .. } => {
+            Expr::CountAll {
+                window: Some(window),
+                ..
+            } => {
+                return self
+                    .resolve_window_function(expr, "count", false, &[], &[], window)
+                    .await;
+            }
+
+            Expr::CountAll { window: None, .. } => {
                 let agg_func = AggregateFunctionFactory::instance().get("count", vec![], vec![])?;
 
                 Box::new((
@@ -812,6 +849,7 @@ impl<'a> TypeChecker<'a> {
                         },
                         args: vec![expr.clone()],
                         params: vec![],
+                        window: None,
                     };
 
                     args.push(is_not_null_expr);
@@ -1286,6 +1324,233 @@ impl<'a> TypeChecker<'a> {
         }
     }
 
+    /// Resolve function call with `OVER` clause, the function can be either an
+    /// aggregate function or a built-in window function, e.g. `ROW_NUMBER`, `LAG`.
+    #[async_recursion::async_recursion]
+    async fn resolve_window_function(
+        &mut self,
+        expr: &Expr<'_>,
+        func_name: &str,
+        distinct: bool,
+        params: &[Literal],
+        args: &[Expr<'_>],
+        window: &WindowSpec<'_>,
+    ) -> Result<Box<(Scalar, DataTypeImpl)>> {
+        let span = expr.span();
+        if self.in_aggregate_function || self.in_window_function {
+            // Reset the state
+            self.in_aggregate_function = false;
+            self.in_window_function = false;
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "window function calls cannot be nested".to_string(),
+            )));
+        }
+        if distinct {
+            return Err(ErrorCode::UnImplement(span.display_error(
+                "DISTINCT is not supported in window function".to_string(),
+            )));
+        }
+
+        let func_name = func_name.to_lowercase();
+        let is_aggregate = AggregateFunctionFactory::instance().check(&func_name);
+        if !is_aggregate && !is_builtin_window_function(&func_name) {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "function {func_name} cannot be used as a window function"
+            ))));
+        }
+
+        self.in_window_function = true;
+        let mut arguments = Vec::with_capacity(args.len());
+        let mut arg_types = Vec::with_capacity(args.len());
+        for arg in args.iter() {
+            let box (arg, arg_type) = self.resolve(arg, None).await?;
+            arguments.push(arg);
+            arg_types.push(arg_type);
+        }
+        let mut partition_by = Vec::with_capacity(window.partition_by.len());
+        for expr in window.partition_by.iter() {
+            let box (scalar, _) = self.resolve(expr, None).await?;
+            partition_by.push(scalar);
+        }
+        let mut order_by = Vec::with_capacity(window.order_by.len());
+        for order in window.order_by.iter() {
+            let box (scalar, _) = self.resolve(&order.expr, None).await?;
+            order_by.push(WindowOrderBy {
+                expr: scalar,
+                asc: order.asc.unwrap_or(true),
+                nulls_first: order.nulls_first.unwrap_or(false),
+            });
+        }
+        self.in_window_function = false;
+
+        let (params, return_type) = if is_aggregate {
+            let params = params
+                .iter()
+                .map(|literal| {
+                    self.resolve_literal(literal, None)
+                        .map(|box (value, _)| value)
+                })
+                .collect::<Result<Vec<DataValue>>>()?;
+            let data_fields = arg_types
+                .iter()
+                .map(|data_type| DataField::new("", data_type.clone()))
+                .collect();
+            let agg_func = AggregateFunctionFactory::instance()
+                .get(&func_name, params.clone(), data_fields)
+                .map_err(|e| ErrorCode::SemanticError(span.display_error(e.message())))?;
+            (params, agg_func.return_type()?)
+        } else {
+            Self::resolve_builtin_window_function(span, &func_name, &mut arguments, &arg_types)?
+        };
+
+        let frame = match &window.window_frame {
+            Some(frame) => Self::resolve_window_frame(span, frame)?,
+            None => WindowFrame::default(),
+        };
+
+        Ok(Box::new((
+            WindowFunction {
+                display_name: format!("{:#}", expr),
+                func_name,
+                params,
+                args: arguments,
+                partition_by,
+                order_by,
+                frame,
+                return_type: Box::new(return_type.clone()),
+            }
+            .into(),
+            return_type,
+        )))
+    }
+
+    /// Check arguments and infer return type of built-in window functions.
+    /// The offset argument of `LAG` and `LEAD` will be taken out of `arguments`
+    /// as a parameter, and the default value will be casted to the return type.
+    fn resolve_builtin_window_function(
+        span: &[Token<'_>],
+        func_name: &str,
+        arguments: &mut Vec<Scalar>,
+        arg_types: &[DataTypeImpl],
+    ) -> Result<(Vec<DataValue>, DataTypeImpl)> {
+        match func_name {
+            "row_number" | "rank" | "dense_rank" => {
+                if !arguments.is_empty() {
+                    return Err(ErrorCode::SemanticError(
+                        span.display_error(format!("function {func_name} takes no arguments")),
+                    ));
+                }
+                Ok((vec![], UInt64Type::new_impl()))
+            }
+            "first_value" | "last_value" => {
+                if arguments.len() != 1 {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "function {func_name} takes exactly one argument"
+                    ))));
+                }
+                Ok((vec![], wrap_nullable(&arg_types[0])))
+            }
+            "lag" | "lead" => {
+                if arguments.is_empty() || arguments.len() > 3 {
+                    return Err(ErrorCode::SemanticError(span.display_error(format!(
+                        "function {func_name} takes 1 to 3 arguments"
+                    ))));
+                }
+                let offset = match arguments.get(1) {
+                    None => 1,
+                    Some(Scalar::ConstantExpr(ConstantExpr {
+                        value: DataValue::UInt64(offset),
+                        ..
+                    })) => *offset,
+                    Some(_) => {
+                        return Err(ErrorCode::SemanticError(span.display_error(format!(
+                            "offset of function {func_name} must be a non-negative integer constant"
+                        ))));
+                    }
+                };
+                let return_type = wrap_nullable(&arg_types[0]);
+                let mut new_arguments = vec![arguments[0].clone()];
+                if let Some(default) = arguments.get(2) {
+                    new_arguments.push(wrap_cast_if_needed(default.clone(), &return_type));
+                }
+                *arguments = new_arguments;
+                Ok((vec![DataValue::UInt64(offset)], return_type))
+            }
+            _ => Err(ErrorCode::LogicalError(format!(
+                "Invalid window function: {func_name}"
+            ))),
+        }
+    }
+
+    fn resolve_window_frame(span: &[Token<'_>], frame: &AstWindowFrame) -> Result<WindowFrame> {
+        let units = match frame.units {
+            AstWindowFrameUnits::Rows => WindowFrameUnits::Rows,
+            AstWindowFrameUnits::Range => WindowFrameUnits::Range,
+        };
+        let start_bound = Self::resolve_window_frame_bound(span, &frame.start_bound)?;
+        let end_bound = Self::resolve_window_frame_bound(span, &frame.end_bound)?;
+
+        let has_offset = |bound: &WindowFrameBound| {
+            matches!(
+                bound,
+                WindowFrameBound::Preceding(Some(_)) | WindowFrameBound::Following(Some(_))
+            )
+        };
+        if units == WindowFrameUnits::Range && (has_offset(&start_bound) || has_offset(&end_bound))
+        {
+            return Err(ErrorCode::UnImplement(span.display_error(
+                "RANGE frame with offset is not supported yet".to_string(),
+            )));
+        }
+
+        if let WindowFrameBound::Following(None) = start_bound {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "frame start cannot be UNBOUNDED FOLLOWING".to_string(),
+            )));
+        }
+        if let WindowFrameBound::Preceding(None) = end_bound {
+            return Err(ErrorCode::SemanticError(span.display_error(
+                "frame end cannot be UNBOUNDED PRECEDING".to_string(),
+            )));
+        }
+        if start_bound > end_bound {
+            return Err(ErrorCode::SemanticError(span.display_error(format!(
+                "frame start ({start_bound}) cannot be larger than frame end ({end_bound})"
+            ))));
+        }
+
+        Ok(WindowFrame {
+            units,
+            start_bound,
+            end_bound,
+        })
+    }
+
+    fn resolve_window_frame_bound(
+        span: &[Token<'_>],
+        bound: &AstWindowFrameBound,
+    ) -> Result<WindowFrameBound> {
+        let resolve_offset = |offset: &Option<Box<Expr>>| match offset.as_deref() {
+            None => Ok(None),
+            Some(Expr::Literal {
+                lit: Literal::Integer(offset),
+                ..
+            }) => Ok(Some(*offset)),
+            Some(_) => Err(ErrorCode::SemanticError(span.display_error(
+                "offset of window frame must be a non-negative integer literal".to_string(),
+            ))),
+        };
+        Ok(match bound {
+            AstWindowFrameBound::CurrentRow => WindowFrameBound::CurrentRow,
+            AstWindowFrameBound::Preceding(offset) => {
+                WindowFrameBound::Preceding(resolve_offset(offset)?)
+            }
+            AstWindowFrameBound::Following(offset) => {
+                WindowFrameBound::Following(resolve_offset(offset)?)
+            }
+        })
+    }
+
     pub async fn resolve_subquery(
         &mut self,
         typ: SubqueryType,
@@ -1764,6 +2029,7 @@ impl<'a> TypeChecker<'a> {
                     name,
                     args,
                     params,
+                    window,
                 } => Ok(Expr::FunctionCall {
                     span,
                     distinct: *distinct,
@@ -1773,6 +2039,7 @@ impl<'a> TypeChecker<'a> {
                         .map(|arg| self.clone_expr_with_replacement(arg, replacement_fn))
                         .collect::<Result<Vec<Expr>>>()?,
                     params: params.clone(),
+                    window: window.clone(),
                 }),
                 Expr::Case {
                     span,
@@ -39,7 +39,11 @@ impl SQLCommon {
             | SQLDataType::Text => Ok(Vu8::to_data_type()),
 
             SQLDataType::Float(_) => Ok(f32::to_data_type()),
-            SQLDataType::Decimal(_, _) => Ok(f64::to_data_type()),
+            SQLDataType::Decimal(precision, scale) => {
+                let precision = precision.map_or(DEFAULT_DECIMAL_PRECISION, |p| p as usize);
+                let scale = scale.map_or(DEFAULT_DECIMAL_SCALE, |s| s as usize);
+                try_create_decimal_type(precision, scale)
+            }
             SQLDataType::Real | SQLDataType::Double => Ok(f64::to_data_type()),
             SQLDataType::Boolean => Ok(bool::to_data_type()),
             SQLDataType::Date => Ok(DateType::new_impl()),
@@ -0,0 +1,234 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! The ids of the columns of the FUSE tables.
+//!
+//! The statistics and the column metas of the blocks are keyed by the ids of the leaf columns.
+//! By default, the id of a leaf column is its position in the table schema, in DFS order. Once a
+//! column is dropped, the positions of the following columns shift, so the ids are recorded in
+//! the metadata of the schema, which is kept by both the table meta and the snapshots.
+//!
+//! The bloom indexes of the blocks are keyed by the names the columns had when the blocks were
+//! written. The names of the dropped or renamed columns are recorded as retired names, no column
+//! can be renamed to them, otherwise the existing bloom indexes would be applied to it.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use common_arrow::arrow::io::parquet::write::to_parquet_schema;
+use common_arrow::to_parquet_arrow_schema;
+use common_datavalues::DataField;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
+
+use crate::storages::fuse::fuse_part::build_column_leaves;
+use crate::storages::fuse::fuse_part::ColumnLeaf;
+
+/// The ids of the first leaf columns of the fields, separated by commas
+pub const FUSE_SCHEMA_KEY_COLUMN_IDS: &str = "fuse.column_ids";
+/// The id taken by the first leaf column of the next column added
+pub const FUSE_SCHEMA_KEY_NEXT_COLUMN_ID: &str = "fuse.next_column_id";
+/// The names of the dropped or renamed columns, as a JSON array
+pub const FUSE_SCHEMA_KEY_RETIRED_COLUMN_NAMES: &str = "fuse.retired_column_names";
+
+/// Builds the column leaves of the schema as written to parquet, without the ids assigned.
+fn column_leaves_of(schema: &DataSchema) -> Result<Vec<ColumnLeaf>> {
+    let arrow_schema = to_parquet_arrow_schema(&schema.to_arrow());
+    Ok(build_column_leaves(&to_parquet_schema(&arrow_schema)?))
+}
+
+/// Builds the column leaves of the schema, with the ids of the leaf columns assigned.
+pub fn build_table_column_leaves(schema: &DataSchema) -> Result<Vec<ColumnLeaf>> {
+    let column_leaves = column_leaves_of(schema)?;
+    let (first_column_ids, _) = column_ids_of(schema, &column_leaves)?;
+    Ok(column_leaves
+        .into_iter()
+        .zip(first_column_ids)
+        .map(|(column_leaf, id)| column_leaf.with_first_column_id(id))
+        .collect())
+}
+
+/// Returns the ids of all the leaf columns of the schema, in DFS order.
+pub fn leaf_column_ids(schema: &DataSchema) -> Result<Vec<ColumnId>> {
+    Ok(build_table_column_leaves(schema)?
+        .into_iter()
+        .flat_map(|column_leaf| column_leaf.column_ids)
+        .collect())
+}
+
+/// Returns the ids of the first leaf columns of the fields.
+pub fn field_column_ids(schema: &DataSchema) -> Result<Vec<ColumnId>> {
+    let column_leaves = column_leaves_of(schema)?;
+    Ok(column_ids_of(schema, &column_leaves)?.0)
+}
+
+/// Maps the keys of `columns`, the positions of the leaf columns, to the ids of them.
+pub fn remap_column_ids<T>(
+    columns: HashMap<ColumnId, T>,
+    column_ids: &[ColumnId],
+) -> HashMap<ColumnId, T> {
+    columns
+        .into_iter()
+        .map(|(idx, v)| (column_ids.get(idx as usize).copied().unwrap_or(idx), v))
+        .collect()
+}
+
+/// Appends the field to the schema, which takes new ids never used by the table.
+pub fn add_column(schema: &DataSchema, field: DataField) -> Result<DataSchema> {
+    let column_leaves = column_leaves_of(schema)?;
+    let (mut first_column_ids, next_column_id) = column_ids_of(schema, &column_leaves)?;
+
+    let mut fields = schema.fields().clone();
+    fields.push(field);
+    let new_schema = DataSchema::new(fields);
+    let new_leaves = column_leaves_of(&new_schema)?;
+    let num_leaves = new_leaves
+        .last()
+        .map(|v| v.leaf_ids.len())
+        .unwrap_or_default();
+
+    first_column_ids.push(next_column_id);
+    let next_column_id = next_column_id + num_leaves as ColumnId;
+    with_column_ids(
+        new_schema,
+        &first_column_ids,
+        next_column_id,
+        &retired_column_names(schema)?,
+    )
+}
+
+/// Removes the field from the schema, the ids of the other fields are kept.
+pub fn drop_column(schema: &DataSchema, name: &str) -> Result<DataSchema> {
+    let column_leaves = column_leaves_of(schema)?;
+    let (mut first_column_ids, next_column_id) = column_ids_of(schema, &column_leaves)?;
+
+    let idx = schema.index_of(name)?;
+    let mut fields = schema.fields().clone();
+    fields.remove(idx);
+    first_column_ids.remove(idx);
+    let mut retired_names = retired_column_names(schema)?;
+    retired_names.insert(name.to_string());
+    with_column_ids(
+        DataSchema::new(fields),
+        &first_column_ids,
+        next_column_id,
+        &retired_names,
+    )
+}
+
+/// Renames the field of the schema, the id of the field is kept.
+///
+/// The new name is expected not to be a retired name, see the module level docs.
+pub fn rename_column(schema: &DataSchema, name: &str, new_name: &str) -> Result<DataSchema> {
+    let column_leaves = column_leaves_of(schema)?;
+    let (first_column_ids, next_column_id) = column_ids_of(schema, &column_leaves)?;
+
+    let mut retired_names = retired_column_names(schema)?;
+    retired_names.insert(name.to_string());
+
+    let idx = schema.index_of(name)?;
+    let mut fields = schema.fields().clone();
+    let field = &fields[idx];
+    fields[idx] = DataField::new(new_name, field.data_type().clone())
+        .with_default_expr(field.default_expr().cloned());
+    with_column_ids(
+        DataSchema::new(fields),
+        &first_column_ids,
+        next_column_id,
+        &retired_names,
+    )
+}
+
+/// Returns the names of the dropped or renamed columns of the table.
+pub fn retired_column_names(schema: &DataSchema) -> Result<BTreeSet<String>> {
+    match schema.meta().get(FUSE_SCHEMA_KEY_RETIRED_COLUMN_NAMES) {
+        Some(names) => serde_json::from_str(names).map_err(|e| {
+            ErrorCode::StorageOther(format!(
+                "invalid retired column names of the schema, names: '{}', {}",
+                names, e
+            ))
+        }),
+        None => Ok(BTreeSet::new()),
+    }
+}
+
+fn with_column_ids(
+    schema: DataSchema,
+    first_column_ids: &[ColumnId],
+    next_column_id: ColumnId,
+    retired_names: &BTreeSet<String>,
+) -> Result<DataSchema> {
+    let ids = first_column_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut metadata = BTreeMap::new();
+    metadata.insert(FUSE_SCHEMA_KEY_COLUMN_IDS.to_string(), ids);
+    metadata.insert(
+        FUSE_SCHEMA_KEY_NEXT_COLUMN_ID.to_string(),
+        next_column_id.to_string(),
+    );
+    if !retired_names.is_empty() {
+        let names = serde_json::to_string(retired_names).map_err(|e| {
+            ErrorCode::StorageOther(format!("failed to encode the retired column names, {}", e))
+        })?;
+        metadata.insert(FUSE_SCHEMA_KEY_RETIRED_COLUMN_NAMES.to_string(), names);
+    }
+    Ok(DataSchema::new_from(schema.fields().clone(), metadata))
+}
+
+/// Returns the ids of the first leaf columns of the fields, and the next column id.
+fn column_ids_of(
+    schema: &DataSchema,
+    column_leaves: &[ColumnLeaf],
+) -> Result<(Vec<ColumnId>, ColumnId)> {
+    let meta = schema.meta();
+    match (
+        meta.get(FUSE_SCHEMA_KEY_COLUMN_IDS),
+        meta.get(FUSE_SCHEMA_KEY_NEXT_COLUMN_ID),
+    ) {
+        (Some(ids), Some(next_column_id)) => {
+            let invalid = || {
+                ErrorCode::StorageOther(format!(
+                    "invalid column ids of the schema, ids: '{}', next id: '{}'",
+                    ids, next_column_id
+                ))
+            };
+            let first_column_ids = ids
+                .split(',')
+                .filter(|id| !id.is_empty())
+                .map(|id| id.parse::<ColumnId>().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>>>()?;
+            let next_column_id = next_column_id.parse::<ColumnId>().map_err(|_| invalid())?;
+            if first_column_ids.len() != column_leaves.len() {
+                return Err(invalid());
+            }
+            Ok((first_column_ids, next_column_id))
+        }
+        // the ids are the positions of the leaf columns
+        _ => {
+            let mut first_column_ids = Vec::with_capacity(column_leaves.len());
+            let mut next_column_id = 0;
+            for column_leaf in column_leaves {
+                first_column_ids.push(next_column_id);
+                next_column_id += column_leaf.leaf_ids.len() as ColumnId;
+            }
+            Ok((first_column_ids, next_column_id))
+        }
+    }
+}
@@ -20,7 +20,10 @@ use common_arrow::parquet::metadata::SchemaDescriptor;
 use common_arrow::parquet::schema::types::ParquetType;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::Compression;
+use common_fuse_meta::meta::Location;
+use common_fuse_meta::meta::StatisticsOfColumns;
 use common_planners::PartInfo;
 use common_planners::PartInfoPtr;
 
@@ -50,6 +53,10 @@ pub struct FusePartInfo {
     pub nums_rows: usize,
     pub columns_meta: HashMap<usize, ColumnMeta>,
     pub compression: Compression,
+    /// Statistics of the columns in the block, used to prune the block at runtime
+    pub col_stats: StatisticsOfColumns,
+    /// Location of the bloom filter index of the block
+    pub bloom_filter_index_location: Option<Location>,
 }
 
 #[typetag::serde(name = "fuse")]
@@ -73,6 +80,8 @@ impl FusePartInfo {
         rows_count: u64,
         columns_meta: HashMap<usize, ColumnMeta>,
         compression: Compression,
+        col_stats: StatisticsOfColumns,
+        bloom_filter_index_location: Option<Location>,
     ) -> Arc<Box<dyn PartInfo>> {
         Arc::new(Box::new(FusePartInfo {
             location,
@@ -80,6 +89,8 @@ impl FusePartInfo {
             columns_meta,
             nums_rows: rows_count as usize,
             compression,
+            col_stats,
+            bloom_filter_index_location,
         }))
     }
 
@@ -99,20 +110,46 @@ impl FusePartInfo {
 pub struct ColumnLeaf {
     pub name: String,
     // `leaf_ids` is the indices of all the leaf columns in DFS order,
-    // through which we can find the meta information of the leaf columns.
+    // through which we can find the parquet schema of the leaf columns.
     pub leaf_ids: Vec<usize>,
+    // `column_ids` is the ids of the leaf columns, in the same order of `leaf_ids`,
+    // through which we can find the meta information of the leaf columns in the blocks.
+    pub column_ids: Vec<ColumnId>,
     // Optional children column for nested types.
     pub children: Option<Vec<ColumnLeaf>>,
 }
 
 impl ColumnLeaf {
     pub fn new(name: String, leaf_ids: Vec<usize>, children: Option<Vec<ColumnLeaf>>) -> Self {
+        let column_ids = leaf_ids.iter().map(|id| *id as ColumnId).collect();
         Self {
             name,
             leaf_ids,
+            column_ids,
             children,
         }
     }
+
+    /// Assigns the ids to the leaf columns, the leaf columns of a column take consecutive ids
+    /// starting from `first_column_id`.
+    pub(crate) fn with_first_column_id(mut self, first_column_id: ColumnId) -> Self {
+        let first_leaf_id = self.leaf_ids.first().copied().unwrap_or_default();
+        self.assign_column_ids(first_leaf_id, first_column_id);
+        self
+    }
+
+    fn assign_column_ids(&mut self, first_leaf_id: usize, first_column_id: ColumnId) {
+        self.column_ids = self
+            .leaf_ids
+            .iter()
+            .map(|id| first_column_id + (*id - first_leaf_id) as ColumnId)
+            .collect();
+        if let Some(children) = &mut self.children {
+            for child in children {
+                child.assign_column_ids(first_leaf_id, first_column_id);
+            }
+        }
+    }
 }
 
 pub fn build_column_leaves(schema: &SchemaDescriptor) -> Vec<ColumnLeaf> {
@@ -21,21 +21,28 @@ use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::ClusterKey;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::Statistics as FuseStatistics;
+use common_fuse_meta::meta::StatisticsOfColumns;
 use common_fuse_meta::meta::TableSnapshot;
 use common_fuse_meta::meta::Versioned;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::MatchSeq;
+use common_planners::AlterTableColumnPlan;
+use common_planners::AnalyzeTablePlan;
 use common_planners::DeletePlan;
 use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::OptimizeTablePlan;
 use common_planners::Partitions;
 use common_planners::ReadDataSourcePlan;
+use common_planners::ReclusterTablePlan;
 use common_planners::Statistics;
 use common_planners::TruncateTablePlan;
+use common_planners::UpdatePlan;
+use common_planners::VacuumTablePlan;
 use uuid::Uuid;
 
 use crate::pipelines::Pipeline;
@@ -44,15 +51,19 @@ use crate::sql::PlanParser;
 use crate::sql::OPT_KEY_DATABASE_ID;
 use crate::sql::OPT_KEY_LEGACY_SNAPSHOT_LOC;
 use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::column_ids::build_table_column_leaves;
+use crate::storages::fuse::column_ids::leaf_column_ids;
 use crate::storages::fuse::io::write_meta;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::index::BloomIndexOptions;
 use crate::storages::NavigationPoint;
 use crate::storages::StorageContext;
 use crate::storages::StorageDescription;
 use crate::storages::Table;
 use crate::storages::TableStatistics;
+use crate::storages::VacuumStatistics;
 
 #[derive(Clone)]
 pub struct FuseTable {
@@ -148,6 +159,21 @@ impl FuseTable {
             .cloned()
     }
 
+    /// The options of the ngram and token bloom filters and the set indexes,
+    /// from both the table options and the indexes defined, see [BloomIndexOptions].
+    pub fn bloom_index_options(&self) -> Result<BloomIndexOptions> {
+        Ok(
+            BloomIndexOptions::from_table_options(self.table_info.options())?
+                .with_table_indexes(self.table_info.meta.indexes.values()),
+        )
+    }
+
+    /// The ids of the leaf columns of the table, by which the blocks written key their
+    /// column metas and statistics.
+    pub fn column_ids(&self) -> Result<Vec<ColumnId>> {
+        leaf_column_ids(&self.table_info.schema())
+    }
+
     pub fn try_from_table(tbl: &dyn Table) -> Result<&FuseTable> {
         tbl.as_any().downcast_ref::<FuseTable>().ok_or_else(|| {
             ErrorCode::LogicalError(format!(
@@ -168,6 +194,19 @@ impl FuseTable {
         }
     }
 
+    /// The historical data of a table can not be purged in a transaction, since the snapshots
+    /// written in the transaction are not committed yet.
+    pub fn check_not_in_transaction(&self, ctx: &dyn TableContext) -> Result<()> {
+        if ctx.in_transaction() {
+            Err(ErrorCode::UnImplement(format!(
+                "purging the historical data of table {} is not allowed in a transaction",
+                self.table_info.desc.as_str()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+
     pub async fn update_table_meta(
         &self,
         ctx: &dyn TableContext,
@@ -196,8 +235,9 @@ impl FuseTable {
             new_table_meta: meta.clone(),
         };
 
-        let catalog = ctx.get_catalog(catalog_name)?;
-        let result = catalog.update_table_meta(req).await;
+        let result = ctx
+            .update_table_meta(catalog_name, &self.table_info, req)
+            .await;
         match result {
             Ok(_) => {
                 if let Some(snapshot_cache) =
@@ -381,6 +421,9 @@ impl Table for FuseTable {
         truncate_plan: TruncateTablePlan,
     ) -> Result<()> {
         self.check_mutable()?;
+        if truncate_plan.purge {
+            self.check_not_in_transaction(ctx.as_ref())?;
+        }
         self.do_truncate(ctx, truncate_plan.purge, truncate_plan.catalog.as_str())
             .await
     }
@@ -388,9 +431,39 @@ impl Table for FuseTable {
     #[tracing::instrument(level = "debug", name = "fuse_table_optimize", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn optimize(&self, ctx: Arc<dyn TableContext>, keep_last_snapshot: bool) -> Result<()> {
         self.check_mutable()?;
+        self.check_not_in_transaction(ctx.as_ref())?;
         self.do_gc(&ctx, keep_last_snapshot).await
     }
 
+    async fn vacuum(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: VacuumTablePlan,
+    ) -> Result<VacuumStatistics> {
+        self.check_mutable()?;
+        self.check_not_in_transaction(ctx.as_ref())?;
+        self.do_vacuum(&ctx, &plan).await
+    }
+
+    async fn analyze(&self, ctx: Arc<dyn TableContext>, plan: AnalyzeTablePlan) -> Result<()> {
+        self.check_mutable()?;
+        self.do_analyze(&ctx, &plan).await
+    }
+
+    async fn alter_table_column(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: AlterTableColumnPlan,
+    ) -> Result<()> {
+        self.check_mutable()?;
+        self.do_alter_table_column(&ctx, &plan).await
+    }
+
+    async fn build_indexes(&self, ctx: Arc<dyn TableContext>, catalog: &str) -> Result<()> {
+        self.check_mutable()?;
+        self.do_build_indexes(&ctx, catalog).await
+    }
+
     async fn statistics(&self, _ctx: Arc<dyn TableContext>) -> Result<Option<TableStatistics>> {
         let s = &self.table_info.meta.statistics;
         Ok(Some(TableStatistics {
@@ -401,6 +474,57 @@ impl Table for FuseTable {
         }))
     }
 
+    async fn column_statistics(
+        &self,
+        ctx: Arc<dyn TableContext>,
+    ) -> Result<Option<StatisticsOfColumns>> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+
+        // the statistics collected by `ANALYZE TABLE`, the numbers of distinct values of
+        // them are preferred, since they are recomputed from the data rather than merged.
+        let analyzed = match &snapshot.table_statistics_location {
+            Some((location, ver)) => {
+                let reader = MetaReaders::table_snapshot_statistics_reader(ctx.as_ref());
+                Some(reader.read(location, None, *ver).await?)
+            }
+            None => None,
+        };
+
+        // the statistics are kept for the leaf columns,
+        // only the columns which are not nested have statistics of their own.
+        let column_leaves = build_table_column_leaves(&self.table_info.schema())?;
+        let col_stats = column_leaves
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, leaf)| match leaf.column_ids.as_slice() {
+                [leaf_id] if leaf.children.is_none() => {
+                    let leaf_id = *leaf_id;
+                    let analyzed = analyzed
+                        .as_ref()
+                        .and_then(|v| v.column_statistics.get(&leaf_id));
+                    let stats = match (snapshot.summary.col_stats.get(&leaf_id), analyzed) {
+                        (Some(summary), Some(analyzed)) => {
+                            let mut stats = summary.clone();
+                            if analyzed.distinct_values.is_some() {
+                                stats.distinct_values = analyzed.distinct_values.clone();
+                            }
+                            stats
+                        }
+                        (Some(summary), None) => summary.clone(),
+                        (None, Some(analyzed)) => analyzed.clone(),
+                        (None, None) => return None,
+                    };
+                    Some((idx as u32, stats))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(Some(col_stats))
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_navigate_to", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn navigate_to(
         &self,
@@ -422,8 +546,19 @@ impl Table for FuseTable {
         self.do_delete(ctx, &delete_plan).await
     }
 
+    #[tracing::instrument(level = "debug", name = "fuse_table_update", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn update(&self, ctx: Arc<dyn TableContext>, update_plan: UpdatePlan) -> Result<()> {
+        self.do_update(ctx, &update_plan).await
+    }
+
     #[tracing::instrument(level = "debug", name = "fuse_table_compact", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
     async fn compact(&self, ctx: Arc<dyn TableContext>, plan: OptimizeTablePlan) -> Result<()> {
         self.do_compact(ctx, &plan).await
     }
+
+    #[tracing::instrument(level = "debug", name = "fuse_table_recluster", skip(self, ctx), fields(ctx.id = ctx.get_id().as_str()))]
+    async fn recluster(&self, ctx: Arc<dyn TableContext>, plan: ReclusterTablePlan) -> Result<()> {
+        self.check_mutable()?;
+        self.do_recluster(ctx, &plan).await
+    }
 }
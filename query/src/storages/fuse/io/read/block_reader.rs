@@ -21,17 +21,28 @@ use common_arrow::arrow::io::parquet::read::column_iter_to_arrays;
 use common_arrow::arrow::io::parquet::read::ArrayIter;
 use common_arrow::arrow::io::parquet::read::RowGroupDeserializer;
 use common_arrow::arrow::io::parquet::write::to_parquet_schema;
+use common_arrow::from_parquet_array;
 use common_arrow::parquet::compression::Compression as ParquetCompression;
 use common_arrow::parquet::metadata::ColumnDescriptor;
 use common_arrow::parquet::metadata::SchemaDescriptor;
 use common_arrow::parquet::read::BasicDecompressor;
 use common_arrow::parquet::read::PageMetaData;
 use common_arrow::parquet::read::PageReader;
+use common_arrow::to_parquet_arrow_schema;
+use common_arrow::to_parquet_field;
 use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::Column;
+use common_datavalues::ColumnRef;
+use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
+use common_datavalues::Series;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::Compression;
 use common_planners::PartInfoPtr;
 use futures::AsyncReadExt;
@@ -43,13 +54,16 @@ use tracing::debug_span;
 use tracing::warn;
 use tracing::Instrument;
 
-use crate::storages::fuse::fuse_part::build_column_leaves;
+use crate::storages::fuse::column_ids::build_table_column_leaves;
 use crate::storages::fuse::fuse_part::ColumnLeaf;
 use crate::storages::fuse::fuse_part::ColumnMeta;
 use crate::storages::fuse::fuse_part::FusePartInfo;
 use crate::storages::fuse::io::retry;
 use crate::storages::fuse::io::retry::Retryable;
 
+/// The number of rows decoded at a time by [`BlockReader::deserialize_with_filter`].
+const FILTER_BATCH_ROWS: usize = 8192;
+
 #[derive(Clone)]
 pub struct BlockReader {
     operator: Operator,
@@ -58,6 +72,9 @@ pub struct BlockReader {
     projected_schema: DataSchemaRef,
     column_leaves: Vec<ColumnLeaf>,
     parquet_schema_descriptor: SchemaDescriptor,
+    // the values of the projected columns missing from the blocks,
+    // which are written before the columns are added
+    default_values: Vec<DataValue>,
 }
 
 impl BlockReader {
@@ -65,12 +82,27 @@ impl BlockReader {
         operator: Operator,
         schema: DataSchemaRef,
         projection: Vec<usize>,
+    ) -> Result<Arc<BlockReader>> {
+        let default_values = projection
+            .iter()
+            .map(|idx| schema.field(*idx).data_type().default_value())
+            .collect();
+        Self::create_with_default_values(operator, schema, projection, default_values)
+    }
+
+    /// Creates the reader filling the projected columns missing from the blocks with
+    /// `default_values`, one for each of the projected columns.
+    pub fn create_with_default_values(
+        operator: Operator,
+        schema: DataSchemaRef,
+        projection: Vec<usize>,
+        default_values: Vec<DataValue>,
     ) -> Result<Arc<BlockReader>> {
         let projected_schema = DataSchemaRef::new(schema.project(&projection));
 
         let arrow_schema = schema.to_arrow();
-        let parquet_schema_descriptor = to_parquet_schema(&arrow_schema)?;
-        let column_leaves = build_column_leaves(&parquet_schema_descriptor);
+        let parquet_schema_descriptor = to_parquet_schema(&to_parquet_arrow_schema(&arrow_schema))?;
+        let column_leaves = build_table_column_leaves(&schema)?;
 
         Ok(Arc::new(BlockReader {
             operator,
@@ -79,13 +111,27 @@ impl BlockReader {
             parquet_schema_descriptor,
             column_leaves,
             arrow_schema: Arc::new(arrow_schema),
+            default_values,
         }))
     }
 
+    /// Returns whether each of the projected columns is in the block, the blocks written before
+    /// a column is added have none of its leaf columns.
+    fn columns_in_block(&self, has_column_id: impl Fn(ColumnId) -> bool) -> Vec<bool> {
+        self.projection
+            .iter()
+            .map(|proj| {
+                let column_leaf = &self.column_leaves[*proj];
+                column_leaf.column_ids.iter().all(|id| has_column_id(*id))
+            })
+            .collect()
+    }
+
+    /// Creates the iterator of the arrays of the column, each array has at most `chunk_size` rows.
     fn to_array_iter(
         metas: Vec<&ColumnMeta>,
         chunks: Vec<Vec<u8>>,
-        rows: usize,
+        chunk_size: usize,
         column_descriptors: Vec<&ColumnDescriptor>,
         field: Field,
         compression: &Compression,
@@ -115,22 +161,29 @@ impl BlockReader {
             .map(|column_descriptor| &column_descriptor.descriptor.primitive_type)
             .collect::<Vec<_>>();
 
-        Ok(column_iter_to_arrays(columns, types, field, Some(rows))?)
+        // Decimals are read as fixed size binaries, and converted back by the table field
+        let array_iter =
+            column_iter_to_arrays(columns, types, to_parquet_field(&field), Some(chunk_size))?;
+        Ok(Box::new(array_iter.map(move |array| {
+            array.and_then(|array| from_parquet_array(array, &field))
+        })))
     }
 
     // TODO refine these
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read_with_block_meta(&self, meta: &BlockMeta) -> Result<DataBlock> {
-        let (num_rows, columns_array_iter) = self.read_columns_with_block_meta(meta).await?;
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-        self.try_next_block(&mut deserializer)
+        let in_block = self.columns_in_block(|id| meta.col_metas.contains_key(&id));
+        let (num_rows, columns_array_iter) =
+            self.read_columns_with_block_meta(meta, &in_block).await?;
+        self.to_data_block(num_rows, columns_array_iter, &in_block)
     }
     // TODO refine these
 
-    pub async fn read_columns_with_block_meta(
+    async fn read_columns_with_block_meta(
         &self,
         meta: &BlockMeta,
+        in_block: &[bool],
     ) -> Result<(usize, Vec<ArrayIter<'static>>)> {
         let num_rows = meta.row_count as usize;
         let num_cols = self.projection.len();
@@ -138,11 +191,11 @@ impl BlockReader {
 
         let mut columns_meta: HashMap<usize, ColumnMeta> =
             HashMap::with_capacity(meta.col_metas.len());
-        for proj in &self.projection {
+        for (proj, _) in self.projection.iter().zip(in_block).filter(|(_, v)| **v) {
             let column_leaf = &self.column_leaves[*proj];
             let indices = &column_leaf.leaf_ids;
-            for index in indices {
-                let column_meta = &meta.col_metas[&(*index as u32)];
+            for (index, column_id) in indices.iter().zip(&column_leaf.column_ids) {
+                let column_meta = &meta.col_metas[column_id];
 
                 let column_reader = self.operator.object(&meta.location.0);
                 let fut = async move {
@@ -163,13 +216,13 @@ impl BlockReader {
 
         let num_cols = columns_meta.len();
         let chunks = futures::stream::iter(column_chunk_futs)
-            .buffered(std::cmp::min(10, num_cols))
+            .buffered(std::cmp::max(1, std::cmp::min(10, num_cols)))
             .try_collect::<Vec<_>>()
             .await?;
         let mut chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
 
         let mut columns_array_iter = Vec::with_capacity(num_cols);
-        for proj in &self.projection {
+        for (proj, _) in self.projection.iter().zip(in_block).filter(|(_, v)| **v) {
             let field = self.arrow_schema.fields[*proj].clone();
             let column_leaf = &self.column_leaves[*proj];
             let indices = &column_leaf.leaf_ids;
@@ -198,18 +251,22 @@ impl BlockReader {
         Ok((num_rows, columns_array_iter))
     }
 
-    async fn read_columns(&self, part: PartInfoPtr) -> Result<(usize, Vec<ArrayIter<'static>>)> {
+    async fn read_columns(
+        &self,
+        part: PartInfoPtr,
+        in_block: &[bool],
+    ) -> Result<(usize, Vec<ArrayIter<'static>>)> {
         let part = FusePartInfo::from_part(&part)?;
 
         // TODO: add prefetch column data.
         let num_rows = part.nums_rows;
         let num_cols = self.projection.len();
         let mut column_chunk_futs = Vec::with_capacity(num_cols);
-        for proj in &self.projection {
+        for (proj, _) in self.projection.iter().zip(in_block).filter(|(_, v)| **v) {
             let column_leaf = &self.column_leaves[*proj];
             let indices = &column_leaf.leaf_ids;
-            for index in indices {
-                let column_meta = &part.columns_meta[index];
+            for (index, column_id) in indices.iter().zip(&column_leaf.column_ids) {
+                let column_meta = &part.columns_meta[&(*column_id as usize)];
                 let column_reader = self.operator.object(&part.location);
                 let fut = async move {
                     let (idx, column_chunk) = Self::read_column(
@@ -228,37 +285,11 @@ impl BlockReader {
 
         let num_cols = column_chunk_futs.len();
         let chunks = futures::stream::iter(column_chunk_futs)
-            .buffered(std::cmp::min(10, num_cols))
+            .buffered(std::cmp::max(1, std::cmp::min(10, num_cols)))
             .try_collect::<Vec<_>>()
             .await?;
-        let mut chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
-
-        let mut columns_array_iter = Vec::with_capacity(num_cols);
-        for proj in &self.projection {
-            let field = self.arrow_schema.fields[*proj].clone();
-            let column_leaf = &self.column_leaves[*proj];
-            let indices = &column_leaf.leaf_ids;
-
-            let mut column_metas = Vec::with_capacity(indices.len());
-            let mut column_chunks = Vec::with_capacity(indices.len());
-            let mut column_descriptors = Vec::with_capacity(indices.len());
-            for index in indices {
-                let column_meta = &part.columns_meta[index];
-                let column_chunk = chunk_map.remove(index).unwrap();
-                let column_descriptor = &self.parquet_schema_descriptor.columns()[*index];
-                column_metas.push(column_meta);
-                column_chunks.push(column_chunk);
-                column_descriptors.push(column_descriptor);
-            }
-            columns_array_iter.push(Self::to_array_iter(
-                column_metas,
-                column_chunks,
-                num_rows,
-                column_descriptors,
-                field,
-                &part.compression,
-            )?);
-        }
+        let chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
+        let columns_array_iter = self.part_array_iters(part, chunk_map, in_block, num_rows)?;
 
         Ok((num_rows, columns_array_iter))
     }
@@ -269,11 +300,74 @@ impl BlockReader {
         chunks: Vec<(usize, Vec<u8>)>,
     ) -> Result<DataBlock> {
         let part = FusePartInfo::from_part(&part)?;
-        let mut chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
+        let in_block = self.columns_in_block(|id| part.columns_meta.contains_key(&(id as usize)));
+        let chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
+        let columns_array_iter =
+            self.part_array_iters(part, chunk_map, &in_block, part.nums_rows)?;
+
+        self.to_data_block(part.nums_rows, columns_array_iter, &in_block)
+    }
+
+    /// Deserializes the columns read, and returns the rows passing the filter only.
+    ///
+    /// The columns of a block are written as a single page, so the rows can't be skipped by
+    /// pages. Instead, the columns are decoded in batches of `FILTER_BATCH_ROWS` rows and each
+    /// batch is filtered before the next one is decoded, the batches without any row passing
+    /// the filter are dropped right away, so only the rows passing the filter are materialized.
+    pub fn deserialize_with_filter(
+        &self,
+        part: PartInfoPtr,
+        chunks: Vec<(usize, Vec<u8>)>,
+        filter: &BooleanColumn,
+    ) -> Result<DataBlock> {
+        let part = FusePartInfo::from_part(&part)?;
+        let in_block = self.columns_in_block(|id| part.columns_meta.contains_key(&(id as usize)));
+        let chunk_map: HashMap<usize, Vec<u8>> = chunks.into_iter().collect();
+        let columns_array_iter =
+            self.part_array_iters(part, chunk_map, &in_block, FILTER_BATCH_ROWS)?;
+
+        let num_rows = filter.len() - filter.values().unset_bits();
+        let fields_in_block = self.fields_in_block(&in_block);
+        if fields_in_block.is_empty() {
+            return self.fill_default_columns(num_rows, vec![], &in_block);
+        }
+
+        let schema = DataSchemaRefExt::create(fields_in_block);
+        let deserializer = RowGroupDeserializer::new(columns_array_iter, part.nums_rows, None);
+        let mut offset = 0;
+        let mut blocks = vec![];
+        for chunk in deserializer {
+            let chunk = chunk.map_err(ErrorCode::from)?;
+            let batch_filter = filter.slice(offset, chunk.len());
+            let batch_filter: &BooleanColumn = Series::check_get(&batch_filter)?;
+            offset += chunk.len();
+            if batch_filter.values().unset_bits() == batch_filter.len() {
+                continue;
+            }
+            let block = DataBlock::from_chunk(&schema, &chunk)?;
+            blocks.push(DataBlock::filter_block_with_bool_column(
+                block,
+                batch_filter,
+            )?);
+        }
+
+        let columns_in_block = match blocks.is_empty() {
+            true => DataBlock::empty_with_schema(schema).columns().to_vec(),
+            false => DataBlock::concat_blocks(&blocks)?.columns().to_vec(),
+        };
+        self.fill_default_columns(num_rows, columns_in_block, &in_block)
+    }
+
+    fn part_array_iters(
+        &self,
+        part: &FusePartInfo,
+        mut chunk_map: HashMap<usize, Vec<u8>>,
+        in_block: &[bool],
+        chunk_size: usize,
+    ) -> Result<Vec<ArrayIter<'static>>> {
         let mut columns_array_iter = Vec::with_capacity(self.projection.len());
 
-        let num_rows = part.nums_rows;
-        for proj in &self.projection {
+        for (proj, _) in self.projection.iter().zip(in_block).filter(|(_, v)| **v) {
             let field = self.arrow_schema.fields[*proj].clone();
             let column_leaf = &self.column_leaves[*proj];
             let indices = &column_leaf.leaf_ids;
@@ -281,8 +375,8 @@ impl BlockReader {
             let mut column_metas = Vec::with_capacity(indices.len());
             let mut column_chunks = Vec::with_capacity(indices.len());
             let mut column_descriptors = Vec::with_capacity(indices.len());
-            for index in indices {
-                let column_meta = &part.columns_meta[index];
+            for (index, column_id) in indices.iter().zip(&column_leaf.column_ids) {
+                let column_meta = &part.columns_meta[&(*column_id as usize)];
                 let column_chunk = chunk_map.remove(index).unwrap();
                 let column_descriptor = &self.parquet_schema_descriptor.columns()[*index];
                 column_metas.push(column_meta);
@@ -292,16 +386,14 @@ impl BlockReader {
             columns_array_iter.push(Self::to_array_iter(
                 column_metas,
                 column_chunks,
-                num_rows,
+                chunk_size,
                 column_descriptors,
                 field,
                 &part.compression,
             )?);
         }
 
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-
-        self.try_next_block(&mut deserializer)
+        Ok(columns_array_iter)
     }
 
     pub async fn read_columns_data(&self, part: PartInfoPtr) -> Result<Vec<(usize, Vec<u8>)>> {
@@ -312,8 +404,12 @@ impl BlockReader {
             let column_leaf = &self.column_leaves[*proj];
             let indices = &column_leaf.leaf_ids;
 
-            for index in indices {
-                let column_meta = &part.columns_meta[index];
+            for (index, column_id) in indices.iter().zip(&column_leaf.column_ids) {
+                // the column is added after the block is written
+                let column_meta = match part.columns_meta.get(&(*column_id as usize)) {
+                    Some(column_meta) => column_meta,
+                    None => continue,
+                };
 
                 join_handlers.push(Self::read_column(
                     self.operator.object(&part.location),
@@ -356,16 +452,85 @@ impl BlockReader {
 
     #[tracing::instrument(level = "debug", skip_all)]
     pub async fn read(&self, part: PartInfoPtr) -> Result<DataBlock> {
-        let (num_rows, columns_array_iter) = self.read_columns(part).await?;
-        let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
-        self.try_next_block(&mut deserializer)
+        let in_block = {
+            let part = FusePartInfo::from_part(&part)?;
+            self.columns_in_block(|id| part.columns_meta.contains_key(&(id as usize)))
+        };
+        let (num_rows, columns_array_iter) = self.read_columns(part, &in_block).await?;
+        self.to_data_block(num_rows, columns_array_iter, &in_block)
+    }
+
+    /// Deserializes the columns read, and fills the projected columns not in the block
+    /// with the default values.
+    fn to_data_block(
+        &self,
+        num_rows: usize,
+        columns_array_iter: Vec<ArrayIter<'static>>,
+        in_block: &[bool],
+    ) -> Result<DataBlock> {
+        if in_block.iter().all(|v| *v) {
+            let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
+            return Self::try_next_block(&self.projected_schema, &mut deserializer);
+        }
+
+        let fields_in_block = self.fields_in_block(in_block);
+        let columns_in_block = if fields_in_block.is_empty() {
+            vec![]
+        } else {
+            let schema = DataSchemaRefExt::create(fields_in_block);
+            let mut deserializer = RowGroupDeserializer::new(columns_array_iter, num_rows, None);
+            Self::try_next_block(&schema, &mut deserializer)?
+                .columns()
+                .to_vec()
+        };
+        self.fill_default_columns(num_rows, columns_in_block, in_block)
+    }
+
+    fn fields_in_block(&self, in_block: &[bool]) -> Vec<DataField> {
+        self.projected_schema
+            .fields()
+            .iter()
+            .zip(in_block)
+            .filter(|(_, v)| **v)
+            .map(|(field, _)| field.clone())
+            .collect()
     }
 
-    fn try_next_block(&self, deserializer: &mut RowGroupDeserializer) -> Result<DataBlock> {
+    /// Fills the projected columns not in the block with the default values.
+    fn fill_default_columns(
+        &self,
+        num_rows: usize,
+        columns_in_block: Vec<ColumnRef>,
+        in_block: &[bool],
+    ) -> Result<DataBlock> {
+        let fields = self.projected_schema.fields();
+        let mut columns_in_block = columns_in_block.into_iter();
+        let mut columns = Vec::with_capacity(fields.len());
+        for ((field, in_block), default_value) in
+            fields.iter().zip(in_block).zip(&self.default_values)
+        {
+            let column = if *in_block {
+                columns_in_block
+                    .next()
+                    .ok_or_else(|| ErrorCode::ParquetError("fail to get a column"))?
+            } else {
+                field
+                    .data_type()
+                    .create_constant_column(default_value, num_rows)?
+            };
+            columns.push(column);
+        }
+        Ok(DataBlock::create(self.projected_schema.clone(), columns))
+    }
+
+    fn try_next_block(
+        schema: &DataSchemaRef,
+        deserializer: &mut RowGroupDeserializer,
+    ) -> Result<DataBlock> {
         match deserializer.next() {
             None => Err(ErrorCode::ParquetError("fail to get a chunk")),
             Some(Err(cause)) => Err(ErrorCode::from(cause)),
-            Some(Ok(chunk)) => DataBlock::from_chunk(&self.projected_schema, &chunk),
+            Some(Ok(chunk)) => DataBlock::from_chunk(schema, &chunk),
         }
     }
 
@@ -40,6 +40,7 @@ use futures_util::future::try_join_all;
 use opendal::Operator;
 use tracing::Instrument;
 pub use util_v1::load_bloom_filter_by_columns;
+pub use util_v1::load_bloom_filter_column_names;
 
 #[async_trait::async_trait]
 pub trait BlockBloomFilterIndexReader {
@@ -94,12 +95,27 @@ mod util_v1 {
         }
         let row_group = &file_meta.row_groups[0];
 
+        // columns which are not indexed are ignored, e.g. the ngram bloom filters of
+        // the blocks written before the ngram bloom index is enabled.
+        let column_needed = column_needed
+            .iter()
+            .filter(|name| {
+                row_group
+                    .columns()
+                    .iter()
+                    .any(|c| &c.descriptor().path_in_schema[0] == *name)
+            })
+            .collect::<Vec<_>>();
+
         let fields = column_needed
             .iter()
             .map(|name| DataField::new(name, Vu8::to_data_type()))
             .collect::<Vec<_>>();
 
         let schema = Arc::new(DataSchema::new(fields));
+        if column_needed.is_empty() {
+            return Ok(DataBlock::empty_with_schema(schema));
+        }
 
         // 1. load column data, as bytes
         let futs = column_needed
@@ -181,6 +197,22 @@ mod util_v1 {
         }
     }
 
+    /// Returns the names of the index columns in the bloom index file.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn load_bloom_filter_column_names(
+        ctx: Arc<dyn TableContext>,
+        dal: Operator,
+        path: &str,
+    ) -> Result<Vec<String>> {
+        let file_meta = load_index_meta(&ctx, path, &dal).await?;
+        Ok(file_meta
+            .schema_descr
+            .columns()
+            .iter()
+            .map(|c| c.path_in_schema[0].clone())
+            .collect())
+    }
+
     /// Loads bytes and index of the given column.
     /// read data from cache, or populate cache items if possible
     #[tracing::instrument(level = "debug", skip_all)]
@@ -20,6 +20,8 @@ use common_fuse_meta::meta::SegmentInfo;
 use common_fuse_meta::meta::SegmentInfoVersion;
 use common_fuse_meta::meta::SnapshotVersion;
 use common_fuse_meta::meta::TableSnapshot;
+use common_fuse_meta::meta::TableSnapshotStatistics;
+use common_fuse_meta::meta::TableSnapshotStatisticsVersion;
 use futures::io::BufReader;
 use opendal::BytesReader;
 
@@ -40,6 +42,8 @@ pub trait BufReaderProvider {
 
 pub type SegmentInfoReader<'a> = CachedReader<SegmentInfo, &'a dyn TableContext>;
 pub type TableSnapshotReader<'a> = CachedReader<TableSnapshot, &'a dyn TableContext>;
+pub type TableSnapshotStatisticsReader<'a> =
+    CachedReader<TableSnapshotStatistics, &'a dyn TableContext>;
 
 pub struct MetaReaders;
 
@@ -59,6 +63,13 @@ impl MetaReaders {
             "SNAPSHOT_CACHE".to_owned(),
         )
     }
+
+    pub fn table_snapshot_statistics_reader(
+        ctx: &dyn TableContext,
+    ) -> TableSnapshotStatisticsReader {
+        // the statistics are only read by the planner and the system tables, not cached
+        TableSnapshotStatisticsReader::new(None, ctx, "SNAPSHOT_STATISTICS_CACHE".to_owned())
+    }
 }
 
 #[async_trait::async_trait]
@@ -77,6 +88,22 @@ where T: BufReaderProvider + Sync
     }
 }
 
+#[async_trait::async_trait]
+impl<T> Loader<TableSnapshotStatistics> for T
+where T: BufReaderProvider + Sync
+{
+    async fn load(
+        &self,
+        key: &str,
+        length_hint: Option<u64>,
+        version: u64,
+    ) -> Result<TableSnapshotStatistics> {
+        let version = TableSnapshotStatisticsVersion::try_from(version)?;
+        let reader = self.buf_reader(key, length_hint).await?;
+        version.read(reader).await
+    }
+}
+
 #[async_trait::async_trait]
 impl<T> Loader<SegmentInfo> for T
 where T: BufReaderProvider + Sync
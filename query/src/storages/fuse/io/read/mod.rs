@@ -21,7 +21,9 @@ mod versioned_reader;
 
 pub use block_reader::BlockReader;
 pub use bloom_index_reader::load_bloom_filter_by_columns;
+pub use bloom_index_reader::load_bloom_filter_column_names;
 pub use bloom_index_reader::BlockBloomFilterIndexReader;
 pub use meta_readers::MetaReaders;
 pub use meta_readers::SegmentInfoReader;
 pub use meta_readers::TableSnapshotReader;
+pub use meta_readers::TableSnapshotStatisticsReader;
@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::SegmentInfo;
 use common_fuse_meta::meta::Statistics;
 use common_planners::Expression;
@@ -33,6 +34,7 @@ use crate::storages::fuse::io::BlockWriter;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::statistics::BlockStatistics;
 use crate::storages::fuse::statistics::StatisticsAccumulator;
+use crate::storages::index::BloomIndexOptions;
 use crate::storages::index::ClusterKeyInfo;
 
 pub type SegmentInfoStream =
@@ -45,6 +47,8 @@ pub struct BlockStreamWriter {
     statistics_accumulator: Option<StatisticsAccumulator>,
     meta_locations: TableMetaLocationGenerator,
     cluster_key_info: Option<ClusterKeyInfo>,
+    bloom_index_options: BloomIndexOptions,
+    column_ids: Option<Vec<ColumnId>>,
     ctx: Arc<dyn TableContext>,
 }
 
@@ -56,6 +60,8 @@ impl BlockStreamWriter {
         block_per_segment: usize,
         meta_locations: TableMetaLocationGenerator,
         cluster_key_info: Option<ClusterKeyInfo>,
+        bloom_index_options: BloomIndexOptions,
+        column_ids: Option<Vec<ColumnId>>,
     ) -> Result<SegmentInfoStream> {
         // filter out empty blocks
         let block_stream =
@@ -76,6 +82,8 @@ impl BlockStreamWriter {
             meta_locations,
             ctx,
             cluster_key_info,
+            bloom_index_options,
+            column_ids,
         )?;
         let segments = Self::transform(Box::pin(block_stream), block_writer);
 
@@ -87,6 +95,8 @@ impl BlockStreamWriter {
         meta_locations: TableMetaLocationGenerator,
         ctx: Arc<dyn TableContext>,
         cluster_key_info: Option<ClusterKeyInfo>,
+        bloom_index_options: BloomIndexOptions,
+        column_ids: Option<Vec<ColumnId>>,
     ) -> Result<Self> {
         let data_accessor = ctx.get_storage_operator()?;
         Ok(Self {
@@ -96,6 +106,8 @@ impl BlockStreamWriter {
             statistics_accumulator: None,
             meta_locations,
             cluster_key_info,
+            bloom_index_options,
+            column_ids,
             ctx,
         })
     }
@@ -156,7 +168,7 @@ impl BlockStreamWriter {
             )?;
 
             // Remove unused columns before serialize
-            if v.data_schema != input_schema {
+            if v.data_schema.fields() != input_schema.fields() {
                 let executor = if let Some(executor) = &v.expression_executor {
                     executor.clone()
                 } else {
@@ -183,11 +195,18 @@ impl BlockStreamWriter {
             }
         }
 
-        let mut acc = self.statistics_accumulator.take().unwrap_or_default();
+        let mut acc = self
+            .statistics_accumulator
+            .take()
+            .unwrap_or_else(|| StatisticsAccumulator {
+                column_ids: self.column_ids.clone(),
+                ..Default::default()
+            });
         let (location, block_id) = self.meta_locations.gen_block_location();
         let block_statistics = BlockStatistics::from(&block, location.0.clone(), cluster_stats)?;
 
-        let block_writer = BlockWriter::new(&self.ctx, &self.data_accessor, &self.meta_locations);
+        let block_writer = BlockWriter::new(&self.ctx, &self.data_accessor, &self.meta_locations)
+            .with_bloom_index_options(self.bloom_index_options.clone());
         let block_meta = block_writer
             .write_with_location(block, block_id, location)
             .await?;
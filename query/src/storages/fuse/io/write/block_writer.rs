@@ -20,19 +20,23 @@ use common_catalog::table_context::TableContext;
 use common_datablocks::serialize_data_blocks;
 use common_datablocks::serialize_data_blocks_with_compression;
 use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRefExt;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::Location;
 use opendal::Operator;
 use tracing::warn;
 use uuid::Uuid;
 
+use crate::storages::fuse::column_ids::remap_column_ids;
 use crate::storages::fuse::io::retry;
 use crate::storages::fuse::io::retry::Retryable;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::operations::util;
 use crate::storages::fuse::statistics::gen_columns_statistics;
 use crate::storages::index::BloomFilterIndexer;
+use crate::storages::index::BloomIndexOptions;
 
 const DEFAULT_BLOOM_INDEX_WRITE_BUFFER_SIZE: usize = 300 * 1024;
 const DEFAULT_BLOCK_WRITE_BUFFER_SIZE: usize = 100 * 1024 * 1024;
@@ -41,6 +45,8 @@ pub struct BlockWriter<'a> {
     ctx: &'a Arc<dyn TableContext>,
     location_generator: &'a TableMetaLocationGenerator,
     data_accessor: &'a Operator,
+    bloom_index_options: BloomIndexOptions,
+    column_ids: Option<Vec<ColumnId>>,
 }
 
 impl<'a> BlockWriter<'a> {
@@ -53,8 +59,27 @@ impl<'a> BlockWriter<'a> {
             ctx,
             location_generator,
             data_accessor,
+            bloom_index_options: BloomIndexOptions::default(),
+            column_ids: None,
         }
     }
+
+    /// Set the options of the ngram and token bloom filters and the set indexes written
+    /// along with the blocks.
+    #[must_use]
+    pub fn with_bloom_index_options(mut self, bloom_index_options: BloomIndexOptions) -> Self {
+        self.bloom_index_options = bloom_index_options;
+        self
+    }
+
+    /// Set the ids of the leaf columns, by which the column metas and statistics of the
+    /// blocks written are keyed, instead of the positions of the leaf columns.
+    #[must_use]
+    pub fn with_column_ids(mut self, column_ids: Vec<ColumnId>) -> Self {
+        self.column_ids = Some(column_ids);
+        self
+    }
+
     pub async fn write_with_location(
         &self,
         block: DataBlock,
@@ -64,12 +89,16 @@ impl<'a> BlockWriter<'a> {
         let data_accessor = &self.data_accessor;
         let row_count = block.num_rows() as u64;
         let block_size = block.memory_size() as u64;
-        let col_stats = gen_columns_statistics(&block)?;
+        let mut col_stats = gen_columns_statistics(&block)?;
         let (bloom_filter_index_size, bloom_filter_index_location) = self
             .build_block_index(data_accessor, &block, block_id)
             .await?;
         let (file_size, file_meta_data) = write_block(block, data_accessor, &location.0).await?;
-        let col_metas = util::column_metas(&file_meta_data)?;
+        let mut col_metas = util::column_metas(&file_meta_data)?;
+        if let Some(column_ids) = &self.column_ids {
+            col_stats = remap_column_ids(col_stats, column_ids);
+            col_metas = remap_column_ids(col_metas, column_ids);
+        }
         let cluster_stats = None; // TODO confirm this with zhyass
         let block_meta = BlockMeta::new(
             row_count,
@@ -96,13 +125,62 @@ impl<'a> BlockWriter<'a> {
         block: &DataBlock,
         block_id: Uuid,
     ) -> Result<(u64, Location)> {
-        let bloom_index = BloomFilterIndexer::try_create(self.ctx.clone(), &[block])?;
-        let index_block = bloom_index.bloom_block;
+        let bloom_index = BloomFilterIndexer::try_create_with_options(
+            self.ctx.clone(),
+            &[block],
+            &self.bloom_index_options,
+        )?;
+        self.write_block_index(data_accessor, bloom_index.bloom_block, block_id)
+            .await
+    }
+
+    /// Builds the index columns of `block` which are missing in `existing`, and writes them
+    /// together with the columns of `existing` into a new index file.
+    ///
+    /// `block` only needs to contain the columns to index, the index columns already
+    /// in `existing` are kept as they are.
+    pub async fn merge_block_index(
+        &self,
+        data_accessor: &Operator,
+        block: &DataBlock,
+        existing: &DataBlock,
+        block_id: Uuid,
+    ) -> Result<(u64, Location)> {
+        let bloom_index = BloomFilterIndexer::try_create_with_options(
+            self.ctx.clone(),
+            &[block],
+            &self.bloom_index_options,
+        )?;
+
+        let mut fields = existing.schema().fields().clone();
+        let mut columns = existing.columns().to_vec();
+        for (field, column) in bloom_index
+            .bloom_schema
+            .fields()
+            .iter()
+            .zip(bloom_index.bloom_block.columns())
+        {
+            if existing.schema().column_with_name(field.name()).is_none() {
+                fields.push(field.clone());
+                columns.push(column.clone());
+            }
+        }
+        let index_block = DataBlock::create(DataSchemaRefExt::create(fields), columns);
+        self.write_block_index(data_accessor, index_block, block_id)
+            .await
+    }
+
+    async fn write_block_index(
+        &self,
+        data_accessor: &Operator,
+        index_block: DataBlock,
+        block_id: Uuid,
+    ) -> Result<(u64, Location)> {
         let location = self
             .location_generator
             .block_bloom_index_location(&block_id);
         let mut data = Vec::with_capacity(DEFAULT_BLOOM_INDEX_WRITE_BUFFER_SIZE);
-        let index_block_schema = &bloom_index.bloom_schema;
+        let index_block_schema = index_block.schema().clone();
         let (size, _) = serialize_data_blocks_with_compression(
             vec![index_block],
             &index_block_schema,
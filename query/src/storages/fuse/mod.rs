@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+pub mod column_ids;
 mod constants;
 mod fuse_part;
 mod fuse_table;
@@ -0,0 +1,170 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::Statistics;
+use common_fuse_meta::meta::TableSnapshot;
+use common_planners::AlterTableColumnAction;
+use common_planners::AlterTableColumnPlan;
+use common_planners::RequireColumnsVisitor;
+use uuid::Uuid;
+
+use crate::sessions::TableContext;
+use crate::storages::fuse::column_ids;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    /// Adds, drops or renames a column of the table, by committing a new snapshot with the
+    /// new schema.
+    ///
+    /// None of the blocks is rewritten, the blocks written before a column is added are read
+    /// with the default value of it, and the dropped columns are ignored by the readers.
+    pub async fn do_alter_table_column(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        plan: &AlterTableColumnPlan,
+    ) -> Result<()> {
+        let schema = self.table_info.schema();
+        let mut new_table_meta = self.table_info.meta.clone();
+        let new_schema = match &plan.action {
+            AlterTableColumnAction::AddColumn { field, comment } => {
+                if schema.has_field(field.name()) {
+                    return Err(ErrorCode::ColumnAlreadyExists(format!(
+                        "column {} already exists in table {}",
+                        field.name(),
+                        self.table_info.desc
+                    )));
+                }
+                // the comments of the fields are either all given or none of them
+                if new_table_meta.field_comments.len() == schema.num_fields() {
+                    new_table_meta.field_comments.push(comment.clone());
+                }
+                column_ids::add_column(&schema, field.clone())?
+            }
+            AlterTableColumnAction::DropColumn { column } => {
+                let idx = self.column_index(column)?;
+                self.check_column_unreferenced(column)?;
+                if schema.num_fields() == 1 {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "can not drop the only column {} of table {}",
+                        column, self.table_info.desc
+                    )));
+                }
+                if new_table_meta.field_comments.len() == schema.num_fields() {
+                    new_table_meta.field_comments.remove(idx);
+                }
+                column_ids::drop_column(&schema, column)?
+            }
+            AlterTableColumnAction::RenameColumn { column, new_column } => {
+                self.column_index(column)?;
+                self.check_column_unreferenced(column)?;
+                if schema.has_field(new_column) {
+                    return Err(ErrorCode::ColumnAlreadyExists(format!(
+                        "column {} already exists in table {}",
+                        new_column, self.table_info.desc
+                    )));
+                }
+                // the bloom indexes of the existing blocks may be keyed by the name
+                if column_ids::retired_column_names(&schema)?.contains(new_column) {
+                    return Err(ErrorCode::ColumnReferenced(format!(
+                        "column name {} of table {} is referenced by the bloom indexes of a dropped or renamed column",
+                        new_column, self.table_info.desc
+                    )));
+                }
+                column_ids::rename_column(&schema, column, new_column)?
+            }
+        };
+        new_table_meta.schema = Arc::new(new_schema.clone());
+
+        let prev = self.read_table_snapshot(ctx.as_ref()).await?;
+        let prev_version = self.snapshot_format_version();
+        let prev_timestamp = prev.as_ref().and_then(|v| v.timestamp);
+        let prev_snapshot_id = prev.as_ref().map(|v| (v.snapshot_id, prev_version));
+        let (summary, segments) = if let Some(v) = prev {
+            (v.summary.clone(), v.segments.clone())
+        } else {
+            (Statistics::default(), vec![])
+        };
+
+        let new_snapshot = TableSnapshot::new(
+            Uuid::new_v4(),
+            &prev_timestamp,
+            prev_snapshot_id,
+            new_schema,
+            summary,
+            segments,
+            self.cluster_key_meta.clone(),
+        );
+
+        self.update_table_meta(
+            ctx.as_ref(),
+            &plan.catalog,
+            &new_snapshot,
+            &mut new_table_meta,
+        )
+        .await
+    }
+
+    fn column_index(&self, column: &str) -> Result<usize> {
+        self.table_info.schema().index_of(column).map_err(|_| {
+            ErrorCode::UnknownColumn(format!(
+                "column {} not found in table {}",
+                column, self.table_info.desc
+            ))
+        })
+    }
+
+    /// The columns referenced by the cluster keys or the indexes can not be dropped or
+    /// renamed, since they are referenced by the names.
+    fn check_column_unreferenced(&self, column: &str) -> Result<()> {
+        for expr in &self.cluster_keys {
+            if RequireColumnsVisitor::collect_columns_from_expr(expr)?.contains(column) {
+                return Err(ErrorCode::ColumnReferenced(format!(
+                    "column {} of table {} is referenced by the cluster key {}",
+                    column,
+                    self.table_info.desc,
+                    self.cluster_key_meta
+                        .as_ref()
+                        .map(|(_, v)| v.clone())
+                        .unwrap_or_default()
+                )));
+            }
+        }
+
+        if let Some(index) = self
+            .table_info
+            .meta
+            .indexes
+            .values()
+            .find(|index| index.column == column)
+        {
+            return Err(ErrorCode::ColumnReferenced(format!(
+                "column {} of table {} is referenced by the index {}",
+                column, self.table_info.desc, index.name
+            )));
+        }
+
+        let options = self.bloom_index_options()?;
+        if options.ngram_columns.contains(column) || options.token_columns.contains(column) {
+            return Err(ErrorCode::ColumnReferenced(format!(
+                "column {} of table {} is referenced by the bloom index options",
+                column, self.table_info.desc
+            )));
+        }
+        Ok(())
+    }
+}
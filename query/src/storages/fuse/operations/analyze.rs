@@ -0,0 +1,140 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::StatisticsOfColumns;
+use common_fuse_meta::meta::TableSnapshot;
+use common_fuse_meta::meta::TableSnapshotStatistics;
+use common_fuse_meta::meta::Versioned;
+use common_planners::AnalyzeTablePlan;
+use tracing::info;
+
+use crate::sessions::TableContext;
+use crate::storages::fuse::column_ids::build_table_column_leaves;
+use crate::storages::fuse::io::write_meta;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::statistics::gen_columns_statistics_with_distinct_values;
+use crate::storages::fuse::statistics::reduce_block_statistics;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    /// Recomputes the column statistics from all the blocks of the current snapshot.
+    ///
+    /// The statistics are written into a snapshot statistics file, which is referenced by
+    /// a new snapshot sharing the segments of the current one. If only some of the columns
+    /// are analyzed, the statistics of the other columns collected by the previous
+    /// `ANALYZE TABLE` are kept.
+    pub async fn do_analyze(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        plan: &AnalyzeTablePlan,
+    ) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            // empty table, have nothing to do here
+            None => return Ok(()),
+        };
+
+        let schema = self.table_info.schema();
+        let mut projection = if plan.columns.is_empty() {
+            (0..schema.fields().len()).collect::<Vec<_>>()
+        } else {
+            plan.columns
+                .iter()
+                .map(|name| {
+                    schema.index_of(name).map_err(|_| {
+                        ErrorCode::UnknownColumn(format!(
+                            "column {} not found in table {}",
+                            name, self.table_info.desc
+                        ))
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+        projection.sort_unstable();
+        projection.dedup();
+
+        // the leaves of the projected block are numbered from 0, map them back to the
+        // ids of the leaf columns of the table, which the statistics of snapshot are keyed by.
+        let column_leaves = build_table_column_leaves(&schema)?;
+        let leaf_ids = projection
+            .iter()
+            .flat_map(|idx| column_leaves[*idx].column_ids.iter().copied())
+            .collect::<Vec<_>>();
+
+        let num_columns = projection.len();
+        let block_reader = self.create_block_reader(ctx, projection)?;
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut col_stats = StatisticsOfColumns::new();
+        for (location, ver) in &snapshot.segments {
+            let segment = segment_reader.read(location, None, *ver).await?;
+            for block_meta in &segment.blocks {
+                let block = block_reader.read_with_block_meta(block_meta).await?;
+                let block_stats = gen_columns_statistics_with_distinct_values(&block)?
+                    .into_iter()
+                    .filter_map(|(idx, stats)| {
+                        leaf_ids.get(idx as usize).map(|leaf_id| (*leaf_id, stats))
+                    })
+                    .collect::<StatisticsOfColumns>();
+                col_stats = if col_stats.is_empty() {
+                    block_stats
+                } else {
+                    reduce_block_statistics(&[col_stats, block_stats])?
+                };
+            }
+        }
+
+        let mut new_snapshot = TableSnapshot::from_previous(&snapshot);
+
+        // keeps the statistics of the columns not analyzed this time
+        if let Some((location, ver)) = &snapshot.table_statistics_location {
+            let reader = MetaReaders::table_snapshot_statistics_reader(ctx.as_ref());
+            let prev_stats = reader.read(location, None, *ver).await?;
+            for (leaf_id, stats) in &prev_stats.column_statistics {
+                if !leaf_ids.contains(leaf_id) {
+                    col_stats.insert(*leaf_id, stats.clone());
+                }
+            }
+        }
+
+        let table_statistics = TableSnapshotStatistics::new(
+            new_snapshot.snapshot_id,
+            snapshot.summary.row_count,
+            col_stats,
+        );
+        let location = self
+            .meta_location_generator()
+            .gen_snapshot_statistics_location();
+        let operator = ctx.get_storage_operator()?;
+        write_meta(&operator, &location, &table_statistics).await?;
+        new_snapshot.table_statistics_location = Some((location, TableSnapshotStatistics::VERSION));
+
+        info!(
+            "analyze table {}, ident {}, {} columns analyzed",
+            self.table_info.desc, self.table_info.ident, num_columns,
+        );
+
+        let mut new_table_meta = self.table_info.meta.clone();
+        self.update_table_meta(
+            ctx.as_ref(),
+            &plan.catalog,
+            &new_snapshot,
+            &mut new_table_meta,
+        )
+        .await
+    }
+}
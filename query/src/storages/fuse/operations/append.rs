@@ -78,6 +78,8 @@ impl FuseTable {
             block_per_seg,
             self.meta_location_generator().clone(),
             cluster_key_info,
+            self.bloom_index_options()?,
+            Some(self.column_ids()?),
         )
         .await?;
 
@@ -150,7 +152,7 @@ impl FuseTable {
             let output_schema = DataSchemaRefExt::create(merged);
 
             let mut expression_executor = None;
-            if output_schema != input_schema {
+            if output_schema.fields() != input_schema.fields() {
                 pipeline.add_transform(|transform_input_port, transform_output_port| {
                     ExpressionTransform::try_create(
                         transform_input_port,
@@ -209,6 +211,8 @@ impl FuseTable {
             });
         }
 
+        let bloom_index_options = self.bloom_index_options()?;
+        let column_ids = self.column_ids()?;
         let mut sink_pipeline_builder = SinkPipeBuilder::create();
         for _ in 0..pipeline.output_len() {
             let input_port = InputPort::create();
@@ -221,6 +225,8 @@ impl FuseTable {
                     da.clone(),
                     self.meta_location_generator().clone(),
                     cluster_key_info.clone(),
+                    bloom_index_options.clone(),
+                    column_ids.clone(),
                 )?,
             );
         }
@@ -30,7 +30,6 @@ use common_fuse_meta::meta::TableSnapshot;
 use common_fuse_meta::meta::Versioned;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableStatistics;
-use common_meta_app::schema::UpdateTableMetaReply;
 use common_meta_app::schema::UpdateTableMetaReq;
 use common_meta_types::MatchSeq;
 use tracing::debug;
@@ -84,7 +83,8 @@ impl FuseTable {
             .with_max_elapsed_time(Some(max_elapsed))
             .build();
 
-        let transient = self.transient();
+        // the historical data is kept until the transaction is committed
+        let transient = self.transient() && !ctx.in_transaction();
         let catalog_name = catalog_name.as_ref();
         loop {
             match tbl
@@ -235,7 +235,7 @@ impl FuseTable {
             new_segments.append(&mut segments)
         };
 
-        let new_snapshot = TableSnapshot::new(
+        let mut new_snapshot = TableSnapshot::new(
             Uuid::new_v4(),
             &prev_snapshot_timestamp,
             prev_snapshot_id,
@@ -244,6 +244,9 @@ impl FuseTable {
             new_segments,
             cluster_key_meta,
         );
+        // 3. keep the statistics collected by `ANALYZE TABLE`, until the table is analyzed again
+        new_snapshot.table_statistics_location =
+            previous.and_then(|v| v.table_statistics_location.clone());
         Ok(new_snapshot)
     }
 
@@ -253,9 +256,7 @@ impl FuseTable {
         table_info: &TableInfo,
         new_snapshot_location: String,
         stats: &Statistics,
-    ) -> Result<UpdateTableMetaReply> {
-        let catalog = ctx.get_catalog(catalog_name)?;
-
+    ) -> Result<()> {
         let table_id = table_info.ident.table_id;
         let table_version = table_info.ident.seq;
 
@@ -283,7 +284,7 @@ impl FuseTable {
             new_table_meta,
         };
 
-        catalog.update_table_meta(req).await
+        ctx.update_table_meta(catalog_name, table_info, req).await
     }
 
     pub fn merge_append_operations(
@@ -315,7 +316,11 @@ impl FuseTable {
         Ok((seg_locs, s))
     }
 
-    async fn latest(&self, ctx: &dyn TableContext, catalog_name: &str) -> Result<Arc<dyn Table>> {
+    pub(super) async fn latest(
+        &self,
+        ctx: &dyn TableContext,
+        catalog_name: &str,
+    ) -> Result<Arc<dyn Table>> {
         let name = self.table_info.name.clone();
         let tid = self.table_info.ident.table_id;
         let catalog = ctx.get_catalog(catalog_name)?;
@@ -20,6 +20,7 @@ use common_planners::OptimizeTablePlan;
 
 use super::mutation::CompactMutator;
 use crate::sessions::TableContext;
+use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::FuseTable;
 use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
 use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
@@ -69,4 +70,56 @@ impl FuseTable {
         self.update_table_meta(ctx, &plan.catalog, &new_snapshot, &mut new_table_meta)
             .await
     }
+
+    /// Checks whether the table needs to be compacted, by the number of segments and the
+    /// percentage of the small blocks, which have less than half of the rows of a full block.
+    ///
+    /// Returns the reason of the compaction, or None if the table is compact enough.
+    pub async fn check_compact(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        segment_threshold: u64,
+        small_block_ratio: u64,
+    ) -> Result<Option<String>> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+
+        let block_count = snapshot.summary.block_count;
+        if block_count <= 1 {
+            return Ok(None);
+        }
+
+        let row_per_block =
+            self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK) as u64;
+        let block_per_seg =
+            self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT) as u64;
+
+        // too many segments, which are not full
+        let segment_count = snapshot.segments.len() as u64;
+        let full_segment_count = (block_count + block_per_seg - 1) / block_per_seg;
+        if segment_count > segment_threshold && segment_count > full_segment_count {
+            return Ok(Some(format!("{} segments", segment_count)));
+        }
+
+        let reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut small_block_count = 0;
+        for (location, ver) in &snapshot.segments {
+            let segment = reader.read(location, None, *ver).await?;
+            small_block_count += segment
+                .blocks
+                .iter()
+                .filter(|b| b.row_count < row_per_block / 2)
+                .count() as u64;
+        }
+
+        if small_block_count > 1 && small_block_count * 100 > block_count * small_block_ratio {
+            return Ok(Some(format!(
+                "{} of {} blocks are small",
+                small_block_count, block_count
+            )));
+        }
+        Ok(None)
+    }
 }
@@ -69,7 +69,9 @@ impl FuseTable {
         plan: &DeletePlan,
     ) -> Result<()> {
         let mut deletion_collector =
-            DeletionMutator::try_create(&ctx, &self.meta_location_generator, snapshot)?;
+            DeletionMutator::try_create(&ctx, &self.meta_location_generator, snapshot)?
+                .with_bloom_index_options(self.bloom_index_options()?)
+                .with_column_ids(self.column_ids()?);
         let schema = self.table_info.schema();
         // TODO refine pruner
         let extras = Extras {
@@ -80,6 +82,7 @@ impl FuseTable {
         };
         let push_downs = Some(extras);
         let block_metas = BlockPruner::new(snapshot.clone())
+            .with_bloom_index_options(self.bloom_index_options()?)
             .prune(&ctx, schema, &push_downs)
             .await?;
 
@@ -101,11 +104,11 @@ impl FuseTable {
                 }
             }
         }
-        self.commit_deletion(ctx.as_ref(), deletion_collector, &plan.catalog_name)
+        self.commit_mutation(ctx.as_ref(), deletion_collector, &plan.catalog_name)
             .await
     }
 
-    async fn commit_deletion(
+    pub(super) async fn commit_mutation(
         &self,
         ctx: &dyn TableContext,
         del_holder: DeletionMutator<'_>,
@@ -23,6 +23,7 @@ use common_datablocks::serialize_data_blocks_with_compression;
 use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::Location;
 use common_fuse_meta::meta::SegmentInfo;
 use common_fuse_meta::meta::Statistics;
@@ -39,6 +40,7 @@ use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::statistics::BlockStatistics;
 use crate::storages::fuse::statistics::StatisticsAccumulator;
 use crate::storages::index::BloomFilterIndexer;
+use crate::storages::index::BloomIndexOptions;
 use crate::storages::index::ClusterKeyInfo;
 
 struct BloomIndexState {
@@ -75,6 +77,7 @@ pub struct FuseTableSink {
     meta_locations: TableMetaLocationGenerator,
     accumulator: StatisticsAccumulator,
     cluster_key_info: Option<ClusterKeyInfo>,
+    bloom_index_options: BloomIndexOptions,
 }
 
 impl FuseTableSink {
@@ -85,6 +88,8 @@ impl FuseTableSink {
         data_accessor: Operator,
         meta_locations: TableMetaLocationGenerator,
         cluster_key_info: Option<ClusterKeyInfo>,
+        bloom_index_options: BloomIndexOptions,
+        column_ids: Vec<ColumnId>,
     ) -> Result<ProcessorPtr> {
         Ok(ProcessorPtr::create(Box::new(FuseTableSink {
             ctx,
@@ -92,9 +97,10 @@ impl FuseTableSink {
             data_accessor,
             meta_locations,
             state: State::None,
-            accumulator: Default::default(),
+            accumulator: StatisticsAccumulator::new().with_column_ids(column_ids),
             num_block_threshold: num_block_threshold as u64,
             cluster_key_info,
+            bloom_index_options,
         })))
     }
 }
@@ -165,7 +171,11 @@ impl Processor for FuseTableSink {
 
                 let bloom_index_state = {
                     // write index
-                    let bloom_index = BloomFilterIndexer::try_create(self.ctx.clone(), &[&block])?;
+                    let bloom_index = BloomFilterIndexer::try_create_with_options(
+                        self.ctx.clone(),
+                        &[&block],
+                        &self.bloom_index_options,
+                    )?;
                     let index_block = bloom_index.bloom_block;
                     let location = self.meta_locations.block_bloom_index_location(&block_id);
                     let mut data = Vec::with_capacity(100 * 1024);
@@ -199,7 +209,11 @@ impl Processor for FuseTableSink {
                 };
             }
             State::GenerateSegment => {
-                let acc = std::mem::take(&mut self.accumulator);
+                let column_ids = self.accumulator.column_ids.clone();
+                let acc = std::mem::replace(&mut self.accumulator, StatisticsAccumulator {
+                    column_ids,
+                    ..Default::default()
+                });
                 let col_stats = acc.summary()?;
 
                 let segment_info = SegmentInfo::new(acc.blocks_metas, Statistics {
@@ -136,7 +136,7 @@ impl FuseTable {
         .await
     }
 
-    async fn blocks_of(
+    pub(crate) async fn blocks_of(
         &self,
         ctx: &dyn TableContext,
         segments: impl Iterator<Item = &Location>,
@@ -200,7 +200,7 @@ impl FuseTable {
         Ok(())
     }
 
-    async fn remove_location(
+    pub(crate) async fn remove_location(
         &self,
         data_accessor: &Operator,
         location: impl AsRef<str>,
@@ -230,7 +230,7 @@ impl FuseTable {
     }
 
     // collect in the sense of GC
-    async fn collect(
+    pub(crate) async fn collect(
         &self,
         ctx: &dyn TableContext,
         segments_to_be_deleted: HashSet<Location>,
@@ -0,0 +1,262 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use backoff::backoff::Backoff;
+use backoff::ExponentialBackoffBuilder;
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::SegmentInfo;
+use common_fuse_meta::meta::TableSnapshot;
+use common_meta_app::schema::TableStatistics;
+use tracing::debug;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::sessions::TableContext;
+use crate::storages::fuse::io::load_bloom_filter_by_columns;
+use crate::storages::fuse::io::load_bloom_filter_column_names;
+use crate::storages::fuse::io::BlockWriter;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::SegmentWriter;
+use crate::storages::fuse::statistics::reducers::reduce_block_metas;
+use crate::storages::fuse::statistics::reducers::reduce_statistics;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::BloomFilterIndexer;
+use crate::storages::index::BloomIndexOptions;
+use crate::storages::index::SetIndex;
+use crate::storages::Table;
+
+const BUILD_INDEXES_BACKOFF_INIT_DELAY: Duration = Duration::from_millis(5);
+const BUILD_INDEXES_BACKOFF_MAX_DELAY: Duration = Duration::from_secs(20);
+const BUILD_INDEXES_BACKOFF_MAX_ELAPSED: Duration = Duration::from_secs(600);
+
+impl FuseTable {
+    /// Backfills the ngram, token and set indexes defined on the table into the bloom index
+    /// files of the blocks of the current snapshot.
+    ///
+    /// The blocks appended after the index is created are indexed by the block writer, this
+    /// backfills the blocks written before. Only the blocks whose index file lacks some of the
+    /// indexes are read, and only the indexed columns of them. The missing indexes are added
+    /// to a copy of the index file at a new location, and the segments referencing them are
+    /// rewritten into a new snapshot.
+    ///
+    /// A set index is omitted if the block has too many distinct values, such a block is read
+    /// again by the next backfill.
+    ///
+    /// If the table is changed by others meanwhile, the indexes are built again on the latest
+    /// snapshot, the index files of the blocks indexed by the previous attempts are reused.
+    pub async fn do_build_indexes(&self, ctx: &Arc<dyn TableContext>, catalog: &str) -> Result<()> {
+        let mut tbl = self;
+        let mut latest: Arc<dyn Table>;
+        // the indexed block metas, by the locations of the blocks
+        let mut indexed = HashMap::new();
+
+        let mut backoff = ExponentialBackoffBuilder::new()
+            .with_initial_interval(BUILD_INDEXES_BACKOFF_INIT_DELAY)
+            .with_max_interval(BUILD_INDEXES_BACKOFF_MAX_DELAY)
+            .with_randomization_factor(0.5)
+            .with_multiplier(2.0)
+            .with_max_elapsed_time(Some(BUILD_INDEXES_BACKOFF_MAX_ELAPSED))
+            .build();
+        loop {
+            match tbl.build_indexes_once(ctx, catalog, &mut indexed).await {
+                Ok(_) => return Ok(()),
+                Err(e) if e.code() == ErrorCode::table_version_mismatched_code() => {
+                    match backoff.next_backoff() {
+                        Some(d) => {
+                            debug!(
+                                "table {} changed while building indexes, retry {} ms later",
+                                tbl.table_info.desc,
+                                d.as_millis()
+                            );
+                            tokio::time::sleep(d).await;
+                            latest = tbl.latest(ctx.as_ref(), catalog).await?;
+                            tbl = FuseTable::try_from_table(latest.as_ref())?;
+                        }
+                        None => {
+                            return Err(ErrorCode::OCCRetryFailure(format!(
+                                "can not build the indexes of table {}, it keeps changing",
+                                tbl.table_info.desc
+                            )));
+                        }
+                    }
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    async fn build_indexes_once(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        catalog: &str,
+        indexed: &mut HashMap<String, BlockMeta>,
+    ) -> Result<()> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            // empty table, have nothing to do here
+            None => return Ok(()),
+        };
+
+        let options = self.bloom_index_options()?;
+        let index_columns = Self::backfilled_index_columns(&self.table_info.schema(), &options);
+        if index_columns.is_empty() {
+            // the bloom and minmax indexes are always built along with the blocks
+            return Ok(());
+        }
+        let mut projection = index_columns.iter().map(|(i, _)| *i).collect::<Vec<_>>();
+        projection.dedup();
+
+        let operator = ctx.get_storage_operator()?;
+        let location_generator = &self.meta_location_generator;
+        let block_writer =
+            BlockWriter::new(ctx, &operator, location_generator).with_bloom_index_options(options);
+        let block_reader = self.create_block_reader(ctx, projection)?;
+        let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let segment_info_cache = ctx.get_storage_cache_manager().get_table_segment_cache();
+        let segment_writer = SegmentWriter::new(&operator, location_generator, &segment_info_cache);
+
+        let mut segments = Vec::with_capacity(snapshot.segments.len());
+        let mut summaries = Vec::with_capacity(snapshot.segments.len());
+        let mut changed = false;
+        for (location, ver) in &snapshot.segments {
+            let segment = segment_reader.read(location, None, *ver).await?;
+            let mut blocks = Vec::with_capacity(segment.blocks.len());
+            let mut segment_changed = false;
+            for block_meta in &segment.blocks {
+                if let Some(new_block_meta) = indexed.get(&block_meta.location.0) {
+                    blocks.push(new_block_meta.clone());
+                    segment_changed = true;
+                    continue;
+                }
+
+                let existing_columns = match &block_meta.bloom_filter_index_location {
+                    Some(index_location) => {
+                        load_bloom_filter_column_names(
+                            ctx.clone(),
+                            operator.clone(),
+                            &index_location.0,
+                        )
+                        .await?
+                    }
+                    None => vec![],
+                };
+                if index_columns
+                    .iter()
+                    .all(|(_, name)| existing_columns.contains(name))
+                {
+                    blocks.push(block_meta.clone());
+                    continue;
+                }
+
+                let existing = match &block_meta.bloom_filter_index_location {
+                    Some(index_location) => {
+                        load_bloom_filter_by_columns(
+                            ctx.clone(),
+                            operator.clone(),
+                            &existing_columns,
+                            &index_location.0,
+                        )
+                        .await?
+                    }
+                    None => DataBlock::empty(),
+                };
+                let block = block_reader.read_with_block_meta(block_meta).await?;
+                let (size, index_location) = block_writer
+                    .merge_block_index(&operator, &block, &existing, Uuid::new_v4())
+                    .await?;
+                let mut new_block_meta = block_meta.clone();
+                new_block_meta.bloom_filter_index_location = Some(index_location);
+                new_block_meta.bloom_filter_index_size = size;
+                indexed.insert(block_meta.location.0.clone(), new_block_meta.clone());
+                blocks.push(new_block_meta);
+                segment_changed = true;
+            }
+
+            if !segment_changed {
+                segments.push((location.clone(), *ver));
+                summaries.push(segment.summary.clone());
+                continue;
+            }
+            changed = true;
+            let summary = reduce_block_metas(&blocks)?;
+            let new_segment = SegmentInfo::new(blocks, summary.clone());
+            segments.push(segment_writer.write_segment(new_segment).await?);
+            summaries.push(summary);
+        }
+
+        if !changed {
+            info!(
+                "build indexes of table {}, ident {}, all the blocks are indexed already",
+                self.table_info.desc, self.table_info.ident,
+            );
+            return Ok(());
+        }
+
+        let mut new_snapshot = TableSnapshot::from_previous(&snapshot);
+        new_snapshot.segments = segments;
+        new_snapshot.summary = reduce_statistics(&summaries)?;
+
+        info!(
+            "build indexes of table {}, ident {}, {} blocks indexed",
+            self.table_info.desc,
+            self.table_info.ident,
+            indexed.len(),
+        );
+
+        let mut new_table_meta = self.table_info.meta.clone();
+        new_table_meta.statistics = TableStatistics {
+            number_of_rows: new_snapshot.summary.row_count,
+            data_bytes: new_snapshot.summary.uncompressed_byte_size,
+            compressed_data_bytes: new_snapshot.summary.compressed_byte_size,
+            index_data_bytes: new_snapshot.summary.index_size,
+        };
+        self.update_table_meta(ctx.as_ref(), catalog, &new_snapshot, &mut new_table_meta)
+            .await
+    }
+
+    /// The index columns to backfill, with the positions of the columns they are built from.
+    fn backfilled_index_columns(
+        schema: &DataSchema,
+        options: &BloomIndexOptions,
+    ) -> Vec<(usize, String)> {
+        let mut index_columns = vec![];
+        for (i, field) in schema.fields().iter().enumerate() {
+            let name = field.name();
+            if options.set_columns.contains(name) && SetIndex::is_supported_type(field.data_type())
+            {
+                index_columns.push((i, BloomFilterIndexer::to_set_column_name(name)));
+            }
+            if BloomIndexOptions::is_string_column(field) {
+                if options.ngram_columns.contains(name) {
+                    let ngram_column =
+                        BloomFilterIndexer::to_ngram_column_name(name, options.ngram_size);
+                    index_columns.push((i, ngram_column));
+                }
+                if options.token_columns.contains(name) {
+                    index_columns.push((i, BloomFilterIndexer::to_token_column_name(name)));
+                }
+            }
+        }
+        index_columns
+    }
+}
@@ -0,0 +1,617 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_datablocks::DataBlock;
+use common_datavalues::BooleanColumn;
+use common_datavalues::ColumnRef;
+use common_datavalues::ColumnWithField;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
+use common_datavalues::Series;
+use common_datavalues::SeriesFrom;
+use common_datavalues::UInt32Column;
+use common_datavalues::UInt64Column;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::eval_aggr;
+use common_fuse_meta::meta::SegmentInfo;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::MergeIntoPlan;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+use parking_lot::RwLock;
+
+use crate::evaluator::Evaluator;
+use crate::pipelines::processors::transforms::hash_join::HashJoinDesc;
+use crate::pipelines::processors::transforms::hash_join::MarkJoinDesc;
+use crate::pipelines::processors::transforms::hash_join::ProbeState;
+use crate::pipelines::processors::transforms::CheckConstraintsExecutor;
+use crate::pipelines::processors::transforms::ExpressionExecutor;
+use crate::pipelines::processors::transforms::GeneratedColumnsExecutor;
+use crate::pipelines::processors::HashJoinState;
+use crate::pipelines::processors::JoinHashTable;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+use crate::sql::executor::PhysicalScalar;
+use crate::sql::plans::JoinType;
+use crate::storages::fuse::io::BlockWriter;
+use crate::storages::fuse::io::SegmentWriter;
+use crate::storages::fuse::operations::mutation::block_filter::all_the_columns_ids;
+use crate::storages::fuse::operations::mutation::DeletionMutator;
+use crate::storages::fuse::operations::AppendOperationLogEntry;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::reducers::reduce_block_metas;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::MinMaxIndex;
+use crate::storages::index::SupportedType;
+
+/// The column of the ids of the source rows, added to the build side of the join.
+const SOURCE_ROW_ID: &str = "_merge_source_row_id";
+/// The column of the positions of the target rows in their block, added to the probe side.
+const TARGET_ROW_ID: &str = "_merge_target_row_id";
+
+impl FuseTable {
+    /// Merges the source rows into the table.
+    ///
+    /// The source is streamed into the hash table of a hash join, keyed by the equi-join keys,
+    /// then the target blocks which may contain the keys of the source are probed one by one.
+    /// The mutated blocks and the inserted rows are committed as a single snapshot.
+    pub async fn do_merge_into(
+        &self,
+        ctx: Arc<QueryContext>,
+        plan: &MergeIntoPlan,
+        mut source: SendableDataBlockStream,
+    ) -> Result<()> {
+        let table_ctx: Arc<dyn TableContext> = ctx.clone();
+
+        // build the hash table of the source rows, which are numbered in order
+        let mut join_state: Option<Arc<JoinHashTable>> = None;
+        let mut key_ranges = vec![None; plan.source_keys.len()];
+        let mut source_rows = 0;
+        while let Some(block) = source.next().await {
+            let block = block?;
+            if block.is_empty() {
+                continue;
+            }
+            let block = rename_columns(block, &plan.source_columns);
+            let keys = evaluate(
+                &table_ctx,
+                "merge into source keys",
+                &block,
+                &plan.source_keys,
+            )?;
+            update_key_ranges(&mut key_ranges, &keys)?;
+
+            let rows = block.num_rows() as u64;
+            let row_ids = Series::from_data((source_rows..source_rows + rows).collect::<Vec<_>>());
+            source_rows += rows;
+            let block = add_key_columns(block, "source", keys, SOURCE_ROW_ID, row_ids)?;
+
+            let state = match &join_state {
+                Some(state) => state.clone(),
+                None => {
+                    let state = create_join_state(&ctx, &block, plan.source_keys.len())?;
+                    state.attach()?;
+                    join_state = Some(state.clone());
+                    state
+                }
+            };
+            state.build(block)?;
+        }
+        let join_state = match join_state {
+            Some(state) => {
+                state.detach()?;
+                state
+            }
+            // empty source, nothing to merge
+            None => return Ok(()),
+        };
+        let mut source_matched = vec![false; source_rows as usize];
+
+        // the written rows get the generated columns computed and must satisfy the constraints
+        let completer = RowsCompleter {
+            generated_columns: GeneratedColumnsExecutor::try_create(
+                table_ctx.clone(),
+                self.table_info.schema(),
+            )?,
+            check_constraints: CheckConstraintsExecutor::try_create(
+                table_ctx.clone(),
+                &self.table_info,
+            )?,
+        };
+
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => {
+                // no snapshot, all the source rows are unmatched
+                let mut inserted = vec![];
+                for block in join_state.row_space.datablocks() {
+                    for block in self.evaluate_unmatched(&table_ctx, plan, &block)? {
+                        inserted.push(completer.complete(block)?);
+                    }
+                }
+                return self.append_blocks(&table_ctx, plan, inserted).await;
+            }
+        };
+
+        // the mutations are committed as a single snapshot, in the same way as deletion
+        let mut mutator =
+            DeletionMutator::try_create(&table_ctx, &self.meta_location_generator, &snapshot)?
+                .with_bloom_index_options(self.bloom_index_options()?)
+                .with_column_ids(self.column_ids()?);
+
+        // only the blocks overlapping with the range of the source keys may be matched
+        let schema = self.table_info.schema();
+        let push_downs = key_range_filter(plan, &schema, &key_ranges).map(|filter| Extras {
+            projection: None,
+            filters: vec![filter],
+            limit: None,
+            order_by: vec![],
+        });
+        let block_metas = BlockPruner::new(snapshot.clone())
+            .with_bloom_index_options(self.bloom_index_options()?)
+            .prune(&table_ctx, schema.clone(), &push_downs)
+            .await?;
+        let reader = self.create_block_reader(&table_ctx, all_the_columns_ids(self))?;
+
+        // probe the hash table with the target blocks, block by block
+        let mut probe_state =
+            ProbeState::with_capacity(ctx.get_settings().get_max_block_size()? as usize);
+        for (seg_idx, block_meta) in block_metas {
+            let target = reader.read_with_block_meta(&block_meta).await?;
+            let target = rename_columns(target, &plan.target_columns);
+            let target_rows = target.num_rows();
+
+            let keys = evaluate(
+                &table_ctx,
+                "merge into target keys",
+                &target,
+                &plan.target_keys,
+            )?;
+            let row_ids = Series::from_data((0..target_rows as u32).collect::<Vec<_>>());
+            let probe = add_key_columns(target.clone(), "target", keys, TARGET_ROW_ID, row_ids)?;
+            probe_state.clear();
+            let joined = join_state
+                .probe(&probe, &mut probe_state)?
+                .into_iter()
+                .filter(|block| !block.is_empty())
+                .collect::<Vec<_>>();
+            if joined.is_empty() {
+                continue;
+            }
+            let mut joined = DataBlock::concat_blocks(&joined)?;
+            if let Some(join_filter) = &plan.join_filter {
+                let predicates = evaluate_predicate(&table_ctx, &joined, join_filter)?;
+                joined = DataBlock::filter_block_with_bool_column(
+                    joined,
+                    &BooleanColumn::from_arrow_data(Bitmap::from_iter(predicates)),
+                )?;
+                if joined.is_empty() {
+                    continue;
+                }
+            }
+            let target_indices: &UInt32Column =
+                Series::check_get(joined.try_column_by_name(TARGET_ROW_ID)?)?;
+            let source_indices: &UInt64Column =
+                Series::check_get(joined.try_column_by_name(SOURCE_ROW_ID)?)?;
+
+            // each target row can be matched by one source row at most
+            let mut target_matched: Vec<Option<usize>> = vec![None; target_rows];
+            for (pair, (target_row, source_row)) in target_indices
+                .values()
+                .iter()
+                .zip(source_indices.values().iter())
+                .enumerate()
+            {
+                if target_matched[*target_row as usize].is_some() {
+                    return Err(ErrorCode::BadArguments(
+                        "MERGE INTO: a row of target table is matched by multiple rows of source",
+                    ));
+                }
+                target_matched[*target_row as usize] = Some(pair);
+                source_matched[*source_row as usize] = true;
+            }
+
+            // the first clause whose condition holds is applied to the matched row
+            let pairs = joined.num_rows();
+            let mut actions: Vec<Option<usize>> = vec![None; pairs];
+            let mut updates: Vec<Option<Vec<(usize, ColumnRef)>>> =
+                Vec::with_capacity(plan.matched_clauses.len());
+            for (clause_idx, clause) in plan.matched_clauses.iter().enumerate() {
+                let predicates = match &clause.selection {
+                    Some(selection) => evaluate_predicate(&table_ctx, &joined, selection)?,
+                    None => vec![true; pairs],
+                };
+                for (action, predicate) in actions.iter_mut().zip(predicates) {
+                    if action.is_none() && predicate {
+                        *action = Some(clause_idx);
+                    }
+                }
+
+                let update = match &clause.update_list {
+                    Some(update_list) if actions.contains(&Some(clause_idx)) => {
+                        let exprs = update_list
+                            .iter()
+                            .map(|(_, expr)| expr.clone())
+                            .collect::<Vec<_>>();
+                        let columns = evaluate(&table_ctx, "merge into update", &joined, &exprs)?;
+                        let columns = update_list
+                            .iter()
+                            .map(|(idx, _)| *idx)
+                            .zip(columns)
+                            .collect::<Vec<_>>();
+                        Some(columns)
+                    }
+                    _ => None,
+                };
+                updates.push(update);
+            }
+            if actions.iter().all(|action| action.is_none()) {
+                // none of the matched rows are mutated
+                continue;
+            }
+
+            // apply the updates and deletions to the target block
+            let mut columns = Vec::with_capacity(schema.num_fields());
+            for (col_idx, field) in schema.fields().iter().enumerate() {
+                let original = target.column(col_idx);
+                let updated = |row: usize| {
+                    let pair = target_matched[row]?;
+                    let column = updates[actions[pair]?]
+                        .as_ref()?
+                        .iter()
+                        .find(|(idx, _)| *idx == col_idx)
+                        .map(|(_, column)| column)?;
+                    Some(column.get(pair))
+                };
+                if (0..target_rows).all(|row| updated(row).is_none()) {
+                    columns.push(original.clone());
+                    continue;
+                }
+
+                let data_type = field.data_type();
+                let mut builder = data_type.create_mutable(target_rows);
+                for row in 0..target_rows {
+                    let value = updated(row).unwrap_or_else(|| original.get(row));
+                    if value.is_null() && data_type.is_nullable() {
+                        builder.append_default();
+                    } else {
+                        builder.append_data_value(value)?;
+                    }
+                }
+                columns.push(builder.to_column());
+            }
+            let keep = (0..target_rows).map(|row| {
+                target_matched[row]
+                    .and_then(|pair| actions[pair])
+                    .map(|clause_idx| plan.matched_clauses[clause_idx].update_list.is_some())
+                    .unwrap_or(true)
+            });
+            let mutated = DataBlock::filter_block_with_bool_column(
+                DataBlock::create(schema.clone(), columns),
+                &BooleanColumn::from_arrow_data(Bitmap::from_iter(keep)),
+            )?;
+
+            mutator
+                .replace_with(
+                    seg_idx,
+                    block_meta.location.clone(),
+                    completer.complete(mutated)?,
+                )
+                .await?;
+        }
+
+        // the unmatched source rows are appended to the table in the same snapshot
+        for block in join_state.row_space.datablocks() {
+            let source_row_ids: &UInt64Column =
+                Series::check_get(block.try_column_by_name(SOURCE_ROW_ID)?)?;
+            let unmatched = source_row_ids
+                .values()
+                .iter()
+                .map(|row| !source_matched[*row as usize]);
+            let unmatched = DataBlock::filter_block_with_bool_column(
+                block.clone(),
+                &BooleanColumn::from_arrow_data(Bitmap::from_iter(unmatched)),
+            )?;
+            for block in self.evaluate_unmatched(&table_ctx, plan, &unmatched)? {
+                mutator.append(completer.complete(block)?).await?;
+            }
+        }
+
+        self.commit_mutation(ctx.as_ref(), mutator, &plan.catalog_name)
+            .await
+    }
+
+    /// Evaluates the `WHEN NOT MATCHED` clauses on the source rows which match no target rows.
+    fn evaluate_unmatched(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        plan: &MergeIntoPlan,
+        unmatched: &DataBlock,
+    ) -> Result<Vec<DataBlock>> {
+        let rows = unmatched.num_rows();
+        if rows == 0 || plan.unmatched_clauses.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // the first clause whose condition holds is applied to the unmatched row
+        let schema = self.table_info.schema();
+        let mut inserted = vec![false; rows];
+        let mut blocks = vec![];
+        for clause in plan.unmatched_clauses.iter() {
+            let predicates = match &clause.selection {
+                Some(selection) => evaluate_predicate(ctx, unmatched, selection)?,
+                None => vec![true; rows],
+            };
+            let picked = inserted
+                .iter_mut()
+                .zip(predicates)
+                .map(|(inserted, predicate)| {
+                    let picked = !*inserted && predicate;
+                    *inserted |= picked;
+                    picked
+                })
+                .collect::<Vec<_>>();
+            if !picked.contains(&true) {
+                continue;
+            }
+
+            let block = DataBlock::filter_block_with_bool_column(
+                unmatched.clone(),
+                &BooleanColumn::from_arrow_data(Bitmap::from_iter(picked)),
+            )?;
+            let columns = evaluate(ctx, "merge into insert", &block, &clause.values)?;
+            blocks.push(DataBlock::create(schema.clone(), columns));
+        }
+        Ok(blocks)
+    }
+
+    /// Appends the blocks to the table without base snapshot.
+    async fn append_blocks(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        plan: &MergeIntoPlan,
+        blocks: Vec<DataBlock>,
+    ) -> Result<()> {
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let data_accessor = ctx.get_storage_operator()?;
+        let block_writer = BlockWriter::new(ctx, &data_accessor, &self.meta_location_generator)
+            .with_bloom_index_options(self.bloom_index_options()?)
+            .with_column_ids(self.column_ids()?);
+        let mut block_metas = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            block_metas.push(block_writer.write(block).await?);
+        }
+        let summary = reduce_block_metas(&block_metas)?;
+        let segment = SegmentInfo::new(block_metas, summary);
+
+        let segment_info_cache = ctx.get_storage_cache_manager().get_table_segment_cache();
+        let seg_writer = SegmentWriter::new(
+            &data_accessor,
+            &self.meta_location_generator,
+            &segment_info_cache,
+        );
+        let (segment_location, _) = seg_writer.write_segment(segment.clone()).await?;
+        let operation_log = vec![AppendOperationLogEntry::new(
+            segment_location,
+            Arc::new(segment),
+        )];
+        self.try_commit(ctx.as_ref(), &plan.catalog_name, &operation_log, false)
+            .await
+    }
+}
+
+/// Completes the rows inserted or updated by MERGE INTO before they are written.
+struct RowsCompleter {
+    generated_columns: Option<GeneratedColumnsExecutor>,
+    check_constraints: CheckConstraintsExecutor,
+}
+
+impl RowsCompleter {
+    fn complete(&self, block: DataBlock) -> Result<DataBlock> {
+        let block = match &self.generated_columns {
+            Some(executor) => executor.execute(block)?,
+            None => block,
+        };
+        self.check_constraints.check(&block)?;
+        Ok(block)
+    }
+}
+
+fn rename_columns(block: DataBlock, names: &[String]) -> DataBlock {
+    let fields = block
+        .schema()
+        .fields()
+        .iter()
+        .zip(names.iter())
+        .map(|(field, name)| DataField::new(name, field.data_type().clone()))
+        .collect::<Vec<_>>();
+    DataBlock::create(DataSchemaRefExt::create(fields), block.columns().to_vec())
+}
+
+/// Creates the state of the hash join which builds the hash table from the source rows, the
+/// join keys are evaluated into the key columns in advance.
+fn create_join_state(
+    ctx: &Arc<QueryContext>,
+    build_block: &DataBlock,
+    num_keys: usize,
+) -> Result<Arc<JoinHashTable>> {
+    let mut build_keys = Vec::with_capacity(num_keys);
+    let mut probe_keys = Vec::with_capacity(num_keys);
+    for idx in 0..num_keys {
+        let column_id = key_column_name("source", idx);
+        let data_type = build_block
+            .schema()
+            .field_with_name(&column_id)?
+            .data_type()
+            .clone();
+        build_keys.push(PhysicalScalar::Variable {
+            column_id,
+            data_type: data_type.clone(),
+        });
+        probe_keys.push(PhysicalScalar::Variable {
+            column_id: key_column_name("target", idx),
+            data_type,
+        });
+    }
+    let hash_key_types = build_keys
+        .iter()
+        .map(|key| key.data_type())
+        .collect::<Vec<_>>();
+
+    let hash_join_desc = HashJoinDesc {
+        build_keys: Evaluator::eval_physical_scalars(&build_keys)?,
+        probe_keys: Evaluator::eval_physical_scalars(&probe_keys)?,
+        join_type: JoinType::Inner,
+        other_predicate: None,
+        marker_join_desc: MarkJoinDesc {
+            marker_index: None,
+            has_null: RwLock::new(false),
+        },
+        from_correlated_subquery: false,
+    };
+    // the build side is not spilled, the target blocks are probed in place
+    Ok(Arc::new(JoinHashTable::try_create(
+        ctx.clone(),
+        JoinHashTable::create_hash_table(&hash_key_types)?,
+        build_block.schema().clone(),
+        hash_join_desc,
+        None,
+    )?))
+}
+
+fn key_column_name(side: &str, idx: usize) -> String {
+    format!("_merge_{}_key_{}", side, idx)
+}
+
+/// Appends the key columns and the row id column to the block.
+fn add_key_columns(
+    block: DataBlock,
+    side: &str,
+    keys: Vec<ColumnRef>,
+    row_id_name: &str,
+    row_ids: ColumnRef,
+) -> Result<DataBlock> {
+    let mut block = block;
+    for (idx, key) in keys.into_iter().enumerate() {
+        let field = DataField::new(&key_column_name(side, idx), key.data_type());
+        block = block.add_column(key, field)?;
+    }
+    let field = DataField::new(row_id_name, row_ids.data_type());
+    block.add_column(row_ids, field)
+}
+
+/// Extends the range of each source key with the keys of a block, the keys whose type is not
+/// supported by the min/max index have no range.
+fn update_key_ranges(
+    key_ranges: &mut [Option<(DataValue, DataValue)>],
+    keys: &[ColumnRef],
+) -> Result<()> {
+    for (range, key) in key_ranges.iter_mut().zip(keys.iter()) {
+        let data_type = key.data_type();
+        if !MinMaxIndex::is_supported_type(&data_type) {
+            continue;
+        }
+        let column = ColumnWithField::new(key.clone(), DataField::new("", data_type));
+        let min = eval_aggr("min", vec![], &[column.clone()], key.len())?.get(0);
+        let max = eval_aggr("max", vec![], &[column], key.len())?.get(0);
+        // the NULL keys match nothing
+        if min.is_null() || max.is_null() {
+            continue;
+        }
+        *range = match range.take() {
+            Some((prev_min, prev_max)) => Some((
+                if min < prev_min { min } else { prev_min },
+                if max > prev_max { max } else { prev_max },
+            )),
+            None => Some((min, max)),
+        };
+    }
+    Ok(())
+}
+
+/// Builds the filter of the target rows whose keys are within the range of the source keys,
+/// on the target keys which are columns of the table.
+fn key_range_filter(
+    plan: &MergeIntoPlan,
+    schema: &DataSchemaRef,
+    key_ranges: &[Option<(DataValue, DataValue)>],
+) -> Option<Expression> {
+    plan.target_keys
+        .iter()
+        .zip(key_ranges.iter())
+        .filter_map(|(key, range)| {
+            let name = match key {
+                Expression::Column(name) => name,
+                _ => return None,
+            };
+            let idx = plan.target_columns.iter().position(|c| c == name)?;
+            let (min, max) = range.as_ref()?;
+            let column = Expression::Column(schema.field(idx).name().clone());
+            Some(
+                column
+                    .gt_eq(Expression::create_literal(min.clone()))
+                    .and(column.lt_eq(Expression::create_literal(max.clone()))),
+            )
+        })
+        .reduce(|acc, expr| acc.and(expr))
+}
+
+fn evaluate(
+    ctx: &Arc<dyn TableContext>,
+    description: &str,
+    block: &DataBlock,
+    exprs: &[Expression],
+) -> Result<Vec<ColumnRef>> {
+    let input_schema: DataSchemaRef = block.schema().clone();
+    let fields = exprs
+        .iter()
+        .map(|expr| expr.to_data_field(&input_schema))
+        .collect::<Result<Vec<_>>>()?;
+    let expr_exec = ExpressionExecutor::try_create(
+        ctx.clone(),
+        description,
+        input_schema,
+        DataSchemaRefExt::create(fields),
+        exprs.to_vec(),
+        false,
+    )?;
+    let result = expr_exec.execute(block)?;
+    Ok(result
+        .columns()
+        .iter()
+        .map(|column| column.convert_full_column())
+        .collect())
+}
+
+fn evaluate_predicate(
+    ctx: &Arc<dyn TableContext>,
+    block: &DataBlock,
+    expr: &Expression,
+) -> Result<Vec<bool>> {
+    let column = &evaluate(ctx, "merge into predicate", block, &[expr.clone()])?[0];
+    let predicates = DataBlock::cast_to_nonull_boolean(column)?;
+    let boolean_col: &BooleanColumn = Series::check_get(&predicates)?;
+    Ok(boolean_col.values().iter().collect())
+}
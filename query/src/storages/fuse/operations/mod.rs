@@ -12,18 +12,27 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod alter_column;
+mod analyze;
 mod append;
 mod commit;
 mod compact;
 mod delete;
 mod fuse_sink;
 mod gc;
+mod index;
+mod merge_into;
 mod mutation;
 mod navigate;
 mod operation_log;
 mod read;
 mod read_partitions;
+mod read_prewhere;
+mod read_runtime_filter;
+mod recluster;
 mod truncate;
+mod update;
+mod vacuum;
 
 pub mod util;
 
@@ -17,6 +17,7 @@ use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datavalues::BooleanColumn;
+use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::Series;
 use common_exception::Result;
@@ -25,6 +26,7 @@ use common_planners::Expression;
 
 use crate::pipelines::processors::transforms::ExpressionExecutor;
 use crate::sessions::TableContext;
+use crate::sql::PlanParser;
 use crate::storages::fuse::operations::mutation::deletion_mutator::Deletion;
 use crate::storages::fuse::FuseTable;
 
@@ -115,6 +117,139 @@ pub async fn delete_from_block(
     Ok(res)
 }
 
+/// Evaluates the `update_list` on the rows of the block that match the `filter_expr`,
+/// returns None if no rows match.
+pub async fn update_from_block(
+    table: &FuseTable,
+    block_meta: &BlockMeta,
+    ctx: &Arc<dyn TableContext>,
+    filter_column_ids: Vec<usize>,
+    filter_expr: Option<&Expression>,
+    update_list: &[(usize, Expression)],
+) -> Result<Option<DataBlock>> {
+    let schema = table.table_info.schema();
+    let whole_table_proj = all_the_columns_ids(table);
+
+    if let Some(filter_expr) = filter_expr {
+        // check if there are rows to be updated, before reading the whole block
+        let col_ids = if filter_column_ids.is_empty() {
+            whole_table_proj.clone()
+        } else {
+            filter_column_ids
+        };
+        let reader = table.create_block_reader(ctx, col_ids)?;
+        let data_block = reader.read_with_block_meta(block_meta).await?;
+
+        let expr_field = filter_expr.to_data_field(&schema)?;
+        let expr_exec = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "filter expression executor (update) ",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![expr_field]),
+            vec![filter_expr.clone()],
+            false,
+        )?;
+        let filter_result = expr_exec.execute(&data_block)?;
+        let predicates = DataBlock::cast_to_nonull_boolean(filter_result.column(0))?;
+        if let Some(const_bool) = DataBlock::try_as_const_bool(&predicates)? {
+            if !const_bool {
+                // none of the rows should be updated
+                return Ok(None);
+            }
+        } else {
+            let boolean_col: &BooleanColumn = Series::check_get(&predicates)?;
+            if boolean_col.values().unset_bits() == boolean_col.values().len() {
+                // false positive, nothing to update indeed
+                return Ok(None);
+            }
+        }
+    }
+
+    // evaluate the new value of each column, the values of the rows
+    // which do not match the filter are kept, i.e.
+    //   `UPDATE t SET a = expr WHERE filter`
+    // is evaluated as
+    //   `CAST(if(filter, expr, a) AS <type of a>) AS a`
+    let exprs = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            let column = Expression::Column(field.name().clone());
+            match update_list.iter().find(|(col_idx, _)| *col_idx == idx) {
+                Some((_, update_expr)) => {
+                    let value = match filter_expr {
+                        Some(filter_expr) => Expression::ScalarFunction {
+                            op: "if".to_string(),
+                            args: vec![filter_expr.clone(), update_expr.clone(), column],
+                        },
+                        None => update_expr.clone(),
+                    };
+                    Expression::Alias(
+                        field.name().clone(),
+                        Box::new(Expression::Cast {
+                            expr: Box::new(value),
+                            data_type: field.data_type().clone(),
+                            pg_style: false,
+                        }),
+                    )
+                }
+                None => column,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let whole_block_reader = table.create_block_reader(ctx, whole_table_proj)?;
+    let whole_block = whole_block_reader.read_with_block_meta(block_meta).await?;
+
+    let expr_exec = ExpressionExecutor::try_create(
+        ctx.clone(),
+        "update expression executor",
+        schema.clone(),
+        schema.clone(),
+        exprs,
+        true,
+    )?;
+    let updated = expr_exec.execute(&whole_block)?;
+    Ok(Some(compute_generated_columns(ctx, &schema, updated)?))
+}
+
+/// Recomputes the stored generated columns from the updated values of the other columns.
+fn compute_generated_columns(
+    ctx: &Arc<dyn TableContext>,
+    schema: &DataSchemaRef,
+    block: DataBlock,
+) -> Result<DataBlock> {
+    if !schema.fields().iter().any(|field| field.is_generated()) {
+        return Ok(block);
+    }
+
+    let exprs = schema
+        .fields()
+        .iter()
+        .map(|field| match field.generated_expr() {
+            Some(generated_expr) => Ok(Expression::Alias(
+                field.name().clone(),
+                Box::new(Expression::Cast {
+                    expr: Box::new(PlanParser::parse_expr(generated_expr)?),
+                    data_type: field.data_type().clone(),
+                    pg_style: false,
+                }),
+            )),
+            None => Ok(Expression::Column(field.name().clone())),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let expr_exec = ExpressionExecutor::try_create(
+        ctx.clone(),
+        "generated column expression executor",
+        schema.clone(),
+        schema.clone(),
+        exprs,
+        true,
+    )?;
+    expr_exec.execute(&block)
+}
+
 pub fn all_the_columns_ids(table: &FuseTable) -> Vec<usize> {
     (0..table.table_info.schema().fields().len())
         .into_iter()
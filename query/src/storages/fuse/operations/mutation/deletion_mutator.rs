@@ -20,6 +20,7 @@ use common_datablocks::DataBlock;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::Location;
 use common_fuse_meta::meta::SegmentInfo;
 use common_fuse_meta::meta::TableSnapshot;
@@ -33,6 +34,7 @@ use crate::storages::fuse::io::SegmentWriter;
 use crate::storages::fuse::io::TableMetaLocationGenerator;
 use crate::storages::fuse::statistics::reducers::reduce_block_metas;
 use crate::storages::fuse::statistics::reducers::reduce_statistics;
+use crate::storages::index::BloomIndexOptions;
 
 pub enum Deletion {
     NothingDeleted,
@@ -48,10 +50,13 @@ pub type SegmentIndex = usize;
 
 pub struct DeletionMutator<'a> {
     mutations: HashMap<SegmentIndex, Vec<Replacement>>,
+    appended_blocks: Vec<BlockMeta>,
     ctx: &'a Arc<dyn TableContext>,
     location_generator: &'a TableMetaLocationGenerator,
     base_snapshot: &'a TableSnapshot,
     data_accessor: Operator,
+    bloom_index_options: BloomIndexOptions,
+    column_ids: Option<Vec<ColumnId>>,
 }
 
 impl<'a> DeletionMutator<'a> {
@@ -63,13 +68,40 @@ impl<'a> DeletionMutator<'a> {
         let data_accessor = ctx.get_storage_operator()?;
         Ok(Self {
             mutations: HashMap::new(),
+            appended_blocks: vec![],
             ctx,
             location_generator,
             base_snapshot,
             data_accessor,
+            bloom_index_options: BloomIndexOptions::default(),
+            column_ids: None,
         })
     }
 
+    /// Set the options of the ngram and token bloom filters of the blocks written.
+    #[must_use]
+    pub fn with_bloom_index_options(mut self, bloom_index_options: BloomIndexOptions) -> Self {
+        self.bloom_index_options = bloom_index_options;
+        self
+    }
+
+    /// Set the ids of the leaf columns, by which the blocks written key their column metas
+    /// and statistics.
+    #[must_use]
+    pub fn with_column_ids(mut self, column_ids: Vec<ColumnId>) -> Self {
+        self.column_ids = Some(column_ids);
+        self
+    }
+
+    fn block_writer(&self) -> BlockWriter<'_> {
+        let block_writer = BlockWriter::new(self.ctx, &self.data_accessor, self.location_generator)
+            .with_bloom_index_options(self.bloom_index_options.clone());
+        match &self.column_ids {
+            None => block_writer,
+            Some(column_ids) => block_writer.with_column_ids(column_ids.clone()),
+        }
+    }
+
     pub async fn into_new_snapshot(self) -> Result<(TableSnapshot, String)> {
         let snapshot = self.base_snapshot;
         let mut new_snapshot = TableSnapshot::from_previous(snapshot);
@@ -149,6 +181,14 @@ impl<'a> DeletionMutator<'a> {
             }
         }
 
+        // the appended blocks are kept in a new segment
+        if !self.appended_blocks.is_empty() {
+            let summary = reduce_block_metas(&self.appended_blocks)?;
+            let new_segment = SegmentInfo::new(self.appended_blocks, summary);
+            let new_segment_location = seg_writer.write_segment(new_segment).await?;
+            segments_editor.insert(snapshot.segments.len(), new_segment_location);
+        }
+
         // assign back the mutated segments to snapshot
         new_snapshot.segments = segments_editor.into_values().collect();
 
@@ -183,9 +223,7 @@ impl<'a> DeletionMutator<'a> {
         let new_block_meta = if replace_with.num_rows() == 0 {
             None
         } else {
-            let block_writer =
-                BlockWriter::new(self.ctx, &self.data_accessor, self.location_generator);
-            Some(block_writer.write(replace_with).await?)
+            Some(self.block_writer().write(replace_with).await?)
         };
         let original_block_loc = location_of_block_to_be_replaced;
         self.mutations
@@ -197,4 +235,14 @@ impl<'a> DeletionMutator<'a> {
             });
         Ok(())
     }
+
+    /// Records the new block, which is appended to the table when the mutations are committed
+    pub async fn append(&mut self, block: DataBlock) -> Result<()> {
+        if block.num_rows() == 0 {
+            return Ok(());
+        }
+        let block_meta = self.block_writer().write(block).await?;
+        self.appended_blocks.push(block_meta);
+        Ok(())
+    }
 }
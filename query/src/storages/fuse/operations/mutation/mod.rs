@@ -17,5 +17,6 @@ pub mod compact_mutator;
 pub mod deletion_mutator;
 
 pub use block_filter::delete_from_block;
+pub use block_filter::update_from_block;
 pub use compact_mutator::CompactMutator;
 pub use deletion_mutator::DeletionMutator;
@@ -18,19 +18,27 @@ use std::sync::Arc;
 use common_base::base::Progress;
 use common_base::base::ProgressValues;
 use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Extras;
 use common_planners::PartInfoPtr;
 use common_planners::ReadDataSourcePlan;
 
+use super::read_prewhere::PrewhereReader;
+use super::read_runtime_filter::ResolvedRuntimeFilters;
+use super::read_runtime_filter::RuntimeFilterReader;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
 use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::processors::transforms::ExpressionExecutor;
+use crate::pipelines::processors::ProbeRuntimeFilter;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::Pipeline;
 use crate::pipelines::SourcePipeBuilder;
 use crate::sessions::TableContext;
+use crate::sql::PlanParser;
 use crate::storages::fuse::io::BlockReader;
 use crate::storages::fuse::operations::read::State::Generated;
 use crate::storages::fuse::FuseTable;
@@ -43,7 +51,60 @@ impl FuseTable {
     ) -> Result<Arc<BlockReader>> {
         let operator = ctx.get_storage_operator()?;
         let table_schema = self.table_info.schema();
-        BlockReader::create(operator, table_schema, projection)
+        if projection
+            .iter()
+            .all(|idx| table_schema.field(*idx).default_expr().is_none())
+        {
+            return BlockReader::create(operator, table_schema, projection);
+        }
+
+        // the blocks written before a column is added are read with the default value of it
+        let default_values = self.default_values(ctx, &projection)?;
+        BlockReader::create_with_default_values(operator, table_schema, projection, default_values)
+    }
+
+    /// Evaluates the default values of the projected columns.
+    fn default_values(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        projection: &[usize],
+    ) -> Result<Vec<DataValue>> {
+        let table_schema = self.table_info.schema();
+        let mut fields = Vec::with_capacity(projection.len());
+        let mut exprs = Vec::with_capacity(projection.len());
+        for idx in projection {
+            let field = table_schema.field(*idx);
+            let expr = match field.default_expr() {
+                Some(expr) => PlanParser::parse_expr(expr)?,
+                None => Expression::create_literal_with_type(
+                    field.data_type().default_value(),
+                    field.data_type().clone(),
+                ),
+            };
+            exprs.push(Expression::Alias(
+                field.name().to_string(),
+                Box::new(Expression::Cast {
+                    expr: Box::new(expr),
+                    data_type: field.data_type().clone(),
+                    pg_style: false,
+                }),
+            ));
+            fields.push(field.clone());
+        }
+
+        let dummy = DataSchemaRefExt::create(vec![DataField::new("dummy", u8::to_data_type())]);
+        let one_row_block = DataBlock::create(dummy.clone(), vec![Series::from_data(vec![1u8])]);
+        let executor = ExpressionExecutor::try_create(
+            ctx.clone(),
+            "default values of columns",
+            dummy,
+            DataSchemaRefExt::create(fields),
+            exprs,
+            true,
+        )?;
+
+        let block = executor.execute(&one_row_block)?;
+        Ok(block.columns().iter().map(|col| col.get(0)).collect())
     }
 
     pub fn projection_of_push_downs(&self, push_downs: &Option<Extras>) -> Vec<usize> {
@@ -67,8 +128,27 @@ impl FuseTable {
         plan: &ReadDataSourcePlan,
         pipeline: &mut Pipeline,
     ) -> Result<()> {
-        let block_reader =
-            self.create_block_reader(&ctx, self.projection_of_push_downs(&plan.push_downs))?;
+        self.do_read2_with_runtime_filters(ctx, plan, pipeline, vec![])
+    }
+
+    /// Reads the table, skipping the blocks and the rows filtered out by the runtime filters
+    /// on the columns given by the names.
+    pub fn do_read2_with_runtime_filters(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &ReadDataSourcePlan,
+        pipeline: &mut Pipeline,
+        runtime_filters: Vec<(String, ProbeRuntimeFilter)>,
+    ) -> Result<()> {
+        let projection = self.projection_of_push_downs(&plan.push_downs);
+        let block_reader = self.create_block_reader(&ctx, projection.clone())?;
+        let runtime_filter_reader =
+            self.create_runtime_filter_reader(&ctx, &projection, runtime_filters)?;
+        // the remaining columns are deserialized after the runtime filters are applied already
+        let prewhere_reader = match runtime_filter_reader {
+            Some(_) => None,
+            None => self.create_prewhere_reader(&ctx, &plan.push_downs, &projection)?,
+        };
 
         let parts_len = plan.parts.len();
         let max_threads = ctx.get_settings().get_max_threads()? as usize;
@@ -80,7 +160,13 @@ impl FuseTable {
             let output = OutputPort::create();
             source_builder.add_source(
                 output.clone(),
-                FuseTableSource::create(ctx.clone(), output, block_reader.clone())?,
+                FuseTableSource::create(
+                    ctx.clone(),
+                    output,
+                    block_reader.clone(),
+                    runtime_filter_reader.clone(),
+                    prewhere_reader.clone(),
+                )?,
             );
         }
 
@@ -90,8 +176,14 @@ impl FuseTable {
 }
 
 enum State {
+    WaitRuntimeFilters(PartInfoPtr),
     ReadData(PartInfoPtr),
     Deserialize(PartInfoPtr, Vec<(usize, Vec<u8>)>),
+    /// The columns of the filters pushed down are read, to be filtered on
+    PrewhereFilter(PartInfoPtr, Vec<(usize, Vec<u8>)>),
+    /// Some rows pass the filters, the remaining columns are to be read
+    ReadRemain(PartInfoPtr, DataBlock, BooleanColumn),
+    DeserializeRemain(PartInfoPtr, DataBlock, BooleanColumn, Vec<(usize, Vec<u8>)>),
     Generated(Option<PartInfoPtr>, DataBlock),
     Finish,
 }
@@ -102,6 +194,10 @@ struct FuseTableSource {
     scan_progress: Arc<Progress>,
     block_reader: Arc<BlockReader>,
     output: Arc<OutputPort>,
+    runtime_filter_reader: Option<Arc<RuntimeFilterReader>>,
+    /// The runtime filters, resolved once they are published
+    runtime_filters: Option<ResolvedRuntimeFilters>,
+    prewhere_reader: Option<Arc<PrewhereReader>>,
 }
 
 impl FuseTableSource {
@@ -109,6 +205,8 @@ impl FuseTableSource {
         ctx: Arc<dyn TableContext>,
         output: Arc<OutputPort>,
         block_reader: Arc<BlockReader>,
+        runtime_filter_reader: Option<Arc<RuntimeFilterReader>>,
+        prewhere_reader: Option<Arc<PrewhereReader>>,
     ) -> Result<ProcessorPtr> {
         let scan_progress = ctx.get_scan_progress();
         let mut partitions = ctx.try_get_partitions(1)?;
@@ -118,17 +216,50 @@ impl FuseTableSource {
                 output,
                 block_reader,
                 scan_progress,
+                runtime_filter_reader,
+                runtime_filters: None,
+                prewhere_reader,
                 state: State::Finish,
             }))),
-            false => Ok(ProcessorPtr::create(Box::new(FuseTableSource {
-                ctx,
-                output,
-                block_reader,
-                scan_progress,
-                state: State::ReadData(partitions.remove(0)),
-            }))),
+            false => {
+                let part = partitions.remove(0);
+                let state = match runtime_filter_reader {
+                    Some(_) => State::WaitRuntimeFilters(part),
+                    None => State::ReadData(part),
+                };
+                Ok(ProcessorPtr::create(Box::new(FuseTableSource {
+                    ctx,
+                    output,
+                    block_reader,
+                    scan_progress,
+                    runtime_filter_reader,
+                    runtime_filters: None,
+                    prewhere_reader,
+                    state,
+                })))
+            }
         }
     }
+
+    /// Generates the block read, and moves on to the next part.
+    fn generate(&mut self, data_block: DataBlock) -> Result<()> {
+        let mut partitions = self.ctx.try_get_partitions(1)?;
+        self.state = match partitions.is_empty() {
+            true => State::Generated(None, data_block),
+            false => State::Generated(Some(partitions.remove(0)), data_block),
+        };
+        Ok(())
+    }
+
+    /// Moves on to the next part without generating any block.
+    fn skip_to_next_part(&mut self) -> Result<()> {
+        let mut partitions = self.ctx.try_get_partitions(1)?;
+        self.state = match partitions.is_empty() {
+            true => State::Finish,
+            false => State::ReadData(partitions.remove(0)),
+        };
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -169,8 +300,11 @@ impl Processor for FuseTableSource {
 
         match self.state {
             State::Finish => Ok(Event::Finished),
-            State::ReadData(_) => Ok(Event::Async),
-            State::Deserialize(_, _) => Ok(Event::Sync),
+            State::WaitRuntimeFilters(_) | State::ReadData(_) => Ok(Event::Async),
+            State::ReadRemain(_, _, _) => Ok(Event::Async),
+            State::Deserialize(_, _)
+            | State::PrewhereFilter(_, _)
+            | State::DeserializeRemain(_, _, _, _) => Ok(Event::Sync),
             State::Generated(_, _) => Err(ErrorCode::LogicalError("It's a bug.")),
         }
     }
@@ -178,8 +312,10 @@ impl Processor for FuseTableSource {
     fn process(&mut self) -> Result<()> {
         match std::mem::replace(&mut self.state, State::Finish) {
             State::Deserialize(part, chunks) => {
-                let data_block = self.block_reader.deserialize(part, chunks)?;
-                let mut partitions = self.ctx.try_get_partitions(1)?;
+                let data_block = match &self.runtime_filters {
+                    Some(runtime_filters) => runtime_filters.deserialize(part, chunks)?,
+                    None => self.block_reader.deserialize(part, chunks)?,
+                };
 
                 let progress_values = ProgressValues {
                     rows: data_block.num_rows(),
@@ -187,23 +323,81 @@ impl Processor for FuseTableSource {
                 };
                 self.scan_progress.incr(&progress_values);
 
-                self.state = match partitions.is_empty() {
-                    true => State::Generated(None, data_block),
-                    false => State::Generated(Some(partitions.remove(0)), data_block),
+                // all the rows of the block are filtered out by the runtime filters
+                if self.runtime_filters.is_some() && data_block.num_rows() == 0 {
+                    return self.skip_to_next_part();
+                }
+                self.generate(data_block)
+            }
+            State::PrewhereFilter(part, chunks) => {
+                let prewhere_reader = self.prewhere_reader.as_ref().unwrap();
+                let (prewhere_block, filter) =
+                    prewhere_reader.deserialize_prewhere(part.clone(), chunks)?;
+
+                let progress_values = ProgressValues {
+                    rows: prewhere_block.num_rows(),
+                    bytes: prewhere_block.memory_size(),
                 };
+                self.scan_progress.incr(&progress_values);
+
+                // none of the rows passes the filters, the remaining columns are not read at all
+                if filter.values().unset_bits() == filter.len() {
+                    return self.skip_to_next_part();
+                }
+                self.state = State::ReadRemain(part, prewhere_block, filter);
                 Ok(())
             }
+            State::DeserializeRemain(part, prewhere_block, filter, chunks) => {
+                let prewhere_reader = self.prewhere_reader.as_ref().unwrap();
+                let data_block =
+                    prewhere_reader.deserialize_remain(part, chunks, prewhere_block, &filter)?;
+                self.generate(data_block)
+            }
             _ => Err(ErrorCode::LogicalError("It's a bug.")),
         }
     }
 
     async fn async_process(&mut self) -> Result<()> {
         match std::mem::replace(&mut self.state, State::Finish) {
-            State::ReadData(part) => {
+            State::WaitRuntimeFilters(part) => {
+                if let Some(runtime_filter_reader) = &self.runtime_filter_reader {
+                    self.runtime_filters = Some(runtime_filter_reader.clone().resolve().await?);
+                }
+                self.state = State::ReadData(part);
+                Ok(())
+            }
+            State::ReadData(mut part) => {
+                if let Some(runtime_filters) = &self.runtime_filters {
+                    // skip the blocks in which no rows can pass the runtime filters
+                    while !runtime_filters.should_keep(&part).await? {
+                        let mut partitions = self.ctx.try_get_partitions(1)?;
+                        if partitions.is_empty() {
+                            return Ok(());
+                        }
+                        part = partitions.remove(0);
+                    }
+                }
+
+                if let Some(prewhere_reader) = &self.prewhere_reader {
+                    let chunks = prewhere_reader
+                        .read_prewhere_columns_data(part.clone())
+                        .await?;
+                    self.state = State::PrewhereFilter(part, chunks);
+                    return Ok(());
+                }
+
                 let chunks = self.block_reader.read_columns_data(part.clone()).await?;
                 self.state = State::Deserialize(part, chunks);
                 Ok(())
             }
+            State::ReadRemain(part, prewhere_block, filter) => {
+                let prewhere_reader = self.prewhere_reader.as_ref().unwrap();
+                let chunks = prewhere_reader
+                    .read_remain_columns_data(part.clone())
+                    .await?;
+                self.state = State::DeserializeRemain(part, prewhere_block, filter, chunks);
+                Ok(())
+            }
             _ => Err(ErrorCode::LogicalError("It's a bug.")),
         }
     }
@@ -15,7 +15,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use common_arrow::arrow::io::parquet::write::to_parquet_schema;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
 use common_fuse_meta::meta::TableSnapshot;
@@ -25,7 +24,7 @@ use common_planners::Partitions;
 use common_planners::Statistics;
 
 use crate::sessions::TableContext;
-use crate::storages::fuse::fuse_part::build_column_leaves;
+use crate::storages::fuse::column_ids::build_table_column_leaves;
 use crate::storages::fuse::fuse_part::ColumnLeaf;
 use crate::storages::fuse::fuse_part::ColumnMeta;
 use crate::storages::fuse::fuse_part::FusePartInfo;
@@ -46,12 +45,10 @@ impl FuseTable {
                     return Ok(result);
                 }
                 let schema = self.table_info.schema();
-
-                let arrow_schema = schema.to_arrow();
-                let parquet_schema_descriptor = to_parquet_schema(&arrow_schema)?;
-                let column_leaves = build_column_leaves(&parquet_schema_descriptor);
+                let column_leaves = build_table_column_leaves(&schema)?;
 
                 let block_metas = BlockPruner::new(snapshot.clone())
+                    .with_bloom_index_options(self.bloom_index_options()?)
                     .prune(&ctx, schema, &push_downs)
                     .await?
                     .into_iter()
@@ -174,10 +171,11 @@ impl FuseTable {
             statistics.read_rows += rows;
             for projection in projections {
                 let column_leaf = &column_leaves[*projection];
-                let indices = &column_leaf.leaf_ids;
-                for index in indices {
-                    let col_metas = &block_meta.col_metas[&(*index as u32)];
-                    statistics.read_bytes += col_metas.len as usize;
+                for column_id in &column_leaf.column_ids {
+                    // the columns added after the block is written are not read
+                    if let Some(col_metas) = block_meta.col_metas.get(column_id) {
+                        statistics.read_bytes += col_metas.len as usize;
+                    }
                 }
             }
 
@@ -213,6 +211,8 @@ impl FuseTable {
             rows_count,
             columns_meta,
             meta.compression(),
+            meta.col_stats.clone(),
+            meta.bloom_filter_index_location.clone(),
         )
     }
 
@@ -225,12 +225,14 @@ impl FuseTable {
 
         for projection in projections {
             let column_leaf = &column_leaves[*projection];
-            let indices = &column_leaf.leaf_ids;
-            for index in indices {
-                let column_meta = &meta.col_metas[&(*index as u32)];
+            for column_id in &column_leaf.column_ids {
+                let column_meta = match meta.col_metas.get(column_id) {
+                    Some(column_meta) => column_meta,
+                    None => continue,
+                };
 
                 columns_meta.insert(
-                    *index,
+                    *column_id as usize,
                     ColumnMeta::create(column_meta.offset, column_meta.len, column_meta.num_values),
                 );
             }
@@ -248,6 +250,8 @@ impl FuseTable {
             rows_count,
             columns_meta,
             meta.compression(),
+            meta.col_stats.clone(),
+            meta.bloom_filter_index_location.clone(),
         )
     }
 
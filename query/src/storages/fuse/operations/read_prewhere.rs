@@ -0,0 +1,196 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::Extras;
+use common_planners::PartInfoPtr;
+use common_planners::RequireColumnsVisitor;
+
+use crate::pipelines::processors::transforms::ExpressionExecutor;
+use crate::sessions::TableContext;
+use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    /// Creates the reader of the prewhere phase from the filters pushed down, returns `None` if
+    /// prewhere is disabled, or the filters can't be evaluated on the projected columns only.
+    pub(super) fn create_prewhere_reader(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        push_downs: &Option<Extras>,
+        projection: &[usize],
+    ) -> Result<Option<Arc<PrewhereReader>>> {
+        let filters = match push_downs {
+            Some(Extras { filters, .. }) if !filters.is_empty() => filters,
+            _ => return Ok(None),
+        };
+        if ctx.get_settings().get_enable_prewhere()? == 0 {
+            return Ok(None);
+        }
+
+        let table_schema = self.table_info.schema();
+        let filter = filters
+            .iter()
+            .cloned()
+            .reduce(|left, right| left.and(right))
+            .unwrap();
+        let mut prewhere_projection = vec![];
+        for column_name in RequireColumnsVisitor::collect_columns_from_expr(&filter)? {
+            match table_schema.index_of(&column_name) {
+                Ok(index) if projection.contains(&index) => prewhere_projection.push(index),
+                _ => return Ok(None),
+            }
+        }
+
+        // nothing to be saved if all the projected columns are read by the prewhere phase
+        let remain_projection = projection
+            .iter()
+            .copied()
+            .filter(|index| !prewhere_projection.contains(index))
+            .collect::<Vec<_>>();
+        if prewhere_projection.is_empty() || remain_projection.is_empty() {
+            return Ok(None);
+        }
+
+        prewhere_projection.sort_unstable();
+        let prewhere_reader = self.create_block_reader(ctx, prewhere_projection.clone())?;
+        let prewhere_schema = DataSchemaRef::new(table_schema.project(&prewhere_projection));
+        let executor = match Self::prewhere_executor(ctx, prewhere_schema, filter) {
+            Ok(executor) => executor,
+            Err(e) => {
+                tracing::warn!("failed to create the executor of prewhere. {}", e);
+                return Ok(None);
+            }
+        };
+
+        let column_positions = projection
+            .iter()
+            .map(
+                |index| match prewhere_projection.iter().position(|i| i == index) {
+                    Some(position) => (true, position),
+                    None => (
+                        false,
+                        remain_projection.iter().position(|i| i == index).unwrap(),
+                    ),
+                },
+            )
+            .collect();
+        Ok(Some(Arc::new(PrewhereReader {
+            projected_schema: DataSchemaRef::new(table_schema.project(projection)),
+            prewhere_reader,
+            remain_reader: self.create_block_reader(ctx, remain_projection)?,
+            executor,
+            column_positions,
+        })))
+    }
+
+    fn prewhere_executor(
+        ctx: &Arc<dyn TableContext>,
+        schema: DataSchemaRef,
+        filter: Expression,
+    ) -> Result<ExpressionExecutor> {
+        let filter_field = filter.to_data_field(&schema)?;
+        ExpressionExecutor::try_create(
+            ctx.clone(),
+            "filter expression executor (prewhere)",
+            schema,
+            DataSchemaRefExt::create(vec![filter_field]),
+            vec![filter],
+            false,
+        )
+    }
+}
+
+/// Reads the blocks in two phases: the columns of the filters pushed down are read and
+/// evaluated first, then the remaining projected columns are only read and deserialized for
+/// the blocks with rows passing the filters, and only the rows passing the filters of them are
+/// materialized.
+pub struct PrewhereReader {
+    projected_schema: DataSchemaRef,
+    prewhere_reader: Arc<BlockReader>,
+    remain_reader: Arc<BlockReader>,
+    executor: ExpressionExecutor,
+    /// For each projected column, whether it's read by the prewhere phase and its position in
+    /// the block read by `prewhere_reader` or `remain_reader`
+    column_positions: Vec<(bool, usize)>,
+}
+
+impl PrewhereReader {
+    pub async fn read_prewhere_columns_data(
+        &self,
+        part: PartInfoPtr,
+    ) -> Result<Vec<(usize, Vec<u8>)>> {
+        self.prewhere_reader.read_columns_data(part).await
+    }
+
+    /// Deserializes the columns of the filters, and evaluates the filters on them.
+    pub fn deserialize_prewhere(
+        &self,
+        part: PartInfoPtr,
+        chunks: Vec<(usize, Vec<u8>)>,
+    ) -> Result<(DataBlock, BooleanColumn)> {
+        let data_block = self.prewhere_reader.deserialize(part, chunks)?;
+        let filter_result = self.executor.execute(&data_block)?;
+        let predicates = DataBlock::cast_to_nonull_boolean(filter_result.column(0))?;
+        let filter = match DataBlock::try_as_const_bool(&predicates)? {
+            Some(const_bool) => BooleanColumn::from_slice(&vec![const_bool; data_block.num_rows()]),
+            None => Series::check_get::<BooleanColumn>(&predicates)?.clone(),
+        };
+        Ok((data_block, filter))
+    }
+
+    pub async fn read_remain_columns_data(
+        &self,
+        part: PartInfoPtr,
+    ) -> Result<Vec<(usize, Vec<u8>)>> {
+        self.remain_reader.read_columns_data(part).await
+    }
+
+    /// Deserializes the rows passing the filters of the remaining columns, and returns the
+    /// projected columns of the rows passing the filters.
+    pub fn deserialize_remain(
+        &self,
+        part: PartInfoPtr,
+        chunks: Vec<(usize, Vec<u8>)>,
+        prewhere_block: DataBlock,
+        filter: &BooleanColumn,
+    ) -> Result<DataBlock> {
+        let (prewhere_block, remain_block) = if filter.values().unset_bits() == 0 {
+            (
+                prewhere_block,
+                self.remain_reader.deserialize(part, chunks)?,
+            )
+        } else {
+            (
+                DataBlock::filter_block_with_bool_column(prewhere_block, filter)?,
+                self.remain_reader
+                    .deserialize_with_filter(part, chunks, filter)?,
+            )
+        };
+        let columns = self
+            .column_positions
+            .iter()
+            .map(|(prewhere, position)| match prewhere {
+                true => prewhere_block.column(*position).clone(),
+                false => remain_block.column(*position).clone(),
+            })
+            .collect();
+        Ok(DataBlock::create(self.projected_schema.clone(), columns))
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_fuse_meta::meta::ColumnId;
+use common_planners::PartInfoPtr;
+
+use crate::pipelines::processors::ProbeRuntimeFilter;
+use crate::pipelines::processors::RuntimeFilter;
+use crate::sessions::TableContext;
+use crate::storages::fuse::column_ids::build_table_column_leaves;
+use crate::storages::fuse::fuse_part::FusePartInfo;
+use crate::storages::fuse::io::BlockReader;
+use crate::storages::fuse::pruning::new_bloom_filter_pruner;
+use crate::storages::fuse::pruning::new_range_filter_pruner;
+use crate::storages::fuse::pruning::BloomFilterPruner;
+use crate::storages::fuse::pruning::RangeFilterPruner;
+use crate::storages::fuse::FuseTable;
+use crate::storages::index::BloomIndexOptions;
+
+impl FuseTable {
+    /// Creates the reader applying the runtime filters on the projected columns, given by the
+    /// names, returns `None` if none of the filtered columns is projected.
+    pub(super) fn create_runtime_filter_reader(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        projection: &[usize],
+        runtime_filters: Vec<(String, ProbeRuntimeFilter)>,
+    ) -> Result<Option<Arc<RuntimeFilterReader>>> {
+        let table_schema = self.table_info.schema();
+        let mut filters = Vec::with_capacity(runtime_filters.len());
+        for (column_name, runtime_filter) in runtime_filters {
+            if let Ok(index) = table_schema.index_of(&column_name) {
+                if projection.contains(&index) {
+                    filters.push((index, runtime_filter));
+                }
+            }
+        }
+        if filters.is_empty() {
+            return Ok(None);
+        }
+
+        let (filter_projection, remain_projection): (Vec<usize>, Vec<usize>) = projection
+            .iter()
+            .copied()
+            .partition(|index| filters.iter().any(|(i, _)| i == *index));
+        let column_positions = projection
+            .iter()
+            .map(
+                |index| match filter_projection.iter().position(|i| i == index) {
+                    Some(position) => (true, position),
+                    None => (
+                        false,
+                        remain_projection.iter().position(|i| i == index).unwrap(),
+                    ),
+                },
+            )
+            .collect();
+
+        let column_leaves = build_table_column_leaves(&table_schema)?;
+        let mut filter_leaf_ids = vec![];
+        let mut filter_column_ids = vec![];
+        for index in &filter_projection {
+            filter_leaf_ids.extend_from_slice(&column_leaves[*index].leaf_ids);
+            filter_column_ids.extend_from_slice(&column_leaves[*index].column_ids);
+        }
+
+        let remain_reader = match remain_projection.is_empty() {
+            true => None,
+            false => Some(self.create_block_reader(ctx, remain_projection)?),
+        };
+        Ok(Some(Arc::new(RuntimeFilterReader {
+            ctx: ctx.clone(),
+            projected_schema: DataSchemaRef::new(table_schema.project(projection)),
+            table_schema,
+            bloom_index_options: self.bloom_index_options()?,
+            runtime_filters: filters,
+            filter_reader: self.create_block_reader(ctx, filter_projection)?,
+            remain_reader,
+            column_positions,
+            filter_leaf_ids,
+            filter_column_ids,
+        })))
+    }
+}
+
+/// Reads the blocks of a FUSE table, skipping the blocks and the rows filtered out by the
+/// runtime filters pushed down by the hash joins.
+///
+/// The filtered columns are deserialized first, and the remaining projected columns are only
+/// deserialized if some rows of the block pass the filters.
+pub struct RuntimeFilterReader {
+    ctx: Arc<dyn TableContext>,
+    table_schema: DataSchemaRef,
+    projected_schema: DataSchemaRef,
+    bloom_index_options: BloomIndexOptions,
+    /// The runtime filters, with the indices of the filtered columns in the table schema
+    runtime_filters: Vec<(usize, ProbeRuntimeFilter)>,
+    filter_reader: Arc<BlockReader>,
+    /// Reader of the projected columns not filtered, `None` if there are none
+    remain_reader: Option<Arc<BlockReader>>,
+    /// For each projected column, whether it's filtered and its position in the block read
+    /// by `filter_reader` or `remain_reader`
+    column_positions: Vec<(bool, usize)>,
+    /// The indices of the leaf columns of the filtered columns, by which the chunks are split
+    filter_leaf_ids: Vec<usize>,
+    filter_column_ids: Vec<ColumnId>,
+}
+
+impl RuntimeFilterReader {
+    /// Waits until the runtime filters are published, and builds the pruners of the blocks.
+    pub async fn resolve(self: Arc<Self>) -> Result<ResolvedRuntimeFilters> {
+        let mut filters = Vec::with_capacity(self.runtime_filters.len());
+        let mut exprs = Vec::with_capacity(self.runtime_filters.len());
+        for (index, probe_filter) in &self.runtime_filters {
+            if let Some(runtime_filter) = probe_filter.wait().await? {
+                let field = self.table_schema.field(*index);
+                let data_type = remove_nullable(field.data_type());
+                if let Some(expr) = runtime_filter.to_expression(field.name(), &data_type) {
+                    exprs.push(expr);
+                }
+                filters.push((field.name().clone(), runtime_filter));
+            }
+        }
+
+        // the pruners are only optimizations, fall back to prune nothing if they can't be built
+        let expr = exprs.into_iter().reduce(|left, right| left.and(right));
+        let range_pruner =
+            match new_range_filter_pruner(&self.ctx, expr.as_ref(), &self.table_schema) {
+                Ok(pruner) => pruner,
+                Err(e) => {
+                    tracing::warn!("failed to create range pruner of runtime filters. {}", e);
+                    new_range_filter_pruner(&self.ctx, None, &self.table_schema)?
+                }
+            };
+        let operator = self.ctx.get_storage_operator()?;
+        let bloom_pruner = match new_bloom_filter_pruner(
+            &self.ctx,
+            expr.as_ref(),
+            &self.table_schema,
+            operator.clone(),
+            &self.bloom_index_options,
+        ) {
+            Ok(pruner) => pruner,
+            Err(e) => {
+                tracing::warn!("failed to create bloom pruner of runtime filters. {}", e);
+                new_bloom_filter_pruner(
+                    &self.ctx,
+                    None,
+                    &self.table_schema,
+                    operator,
+                    &self.bloom_index_options,
+                )?
+            }
+        };
+
+        Ok(ResolvedRuntimeFilters {
+            reader: self,
+            filters,
+            range_pruner,
+            bloom_pruner,
+        })
+    }
+}
+
+/// The runtime filters published, with which the blocks and the rows are filtered.
+pub struct ResolvedRuntimeFilters {
+    reader: Arc<RuntimeFilterReader>,
+    /// The runtime filters with the names of the filtered columns
+    filters: Vec<(String, Arc<RuntimeFilter>)>,
+    range_pruner: Arc<dyn RangeFilterPruner + Send + Sync>,
+    bloom_pruner: Arc<dyn BloomFilterPruner + Send + Sync>,
+}
+
+impl ResolvedRuntimeFilters {
+    /// Returns false if none of the rows of the block can pass the runtime filters.
+    pub async fn should_keep(&self, part: &PartInfoPtr) -> Result<bool> {
+        if self.filters.iter().any(|(_, filter)| filter.is_empty()) {
+            return Ok(false);
+        }
+
+        let part = FusePartInfo::from_part(part)?;
+        if !self.range_pruner.should_keep(&part.col_stats) {
+            return Ok(false);
+        }
+
+        // the bloom indexes of the blocks written before a column is added have none of it
+        let bloom_applicable = self
+            .reader
+            .filter_column_ids
+            .iter()
+            .all(|id| part.columns_meta.contains_key(&(*id as usize)));
+        Ok(!bloom_applicable
+            || self
+                .bloom_pruner
+                .should_keep(&part.bloom_filter_index_location)
+                .await)
+    }
+
+    /// Deserializes the projected columns of the rows passing the runtime filters.
+    pub fn deserialize(
+        &self,
+        part: PartInfoPtr,
+        chunks: Vec<(usize, Vec<u8>)>,
+    ) -> Result<DataBlock> {
+        let reader = &self.reader;
+        let (filter_chunks, remain_chunks): (Vec<_>, Vec<_>) = chunks
+            .into_iter()
+            .partition(|(index, _)| reader.filter_leaf_ids.contains(index));
+
+        let filter_block = reader
+            .filter_reader
+            .deserialize(part.clone(), filter_chunks)?;
+        let mut picked = vec![true; filter_block.num_rows()];
+        for (column_name, filter) in &self.filters {
+            let column = filter_block.try_column_by_name(column_name)?;
+            let filter_picked = filter.eval(column, reader.ctx.clone())?;
+            for (picked, filter_picked) in picked.iter_mut().zip(filter_picked) {
+                *picked &= filter_picked;
+            }
+        }
+        if !picked.iter().any(|picked| *picked) {
+            return Ok(DataBlock::empty_with_schema(
+                reader.projected_schema.clone(),
+            ));
+        }
+
+        let remain_block = match &reader.remain_reader {
+            Some(remain_reader) => Some(remain_reader.deserialize(part, remain_chunks)?),
+            None => None,
+        };
+        let columns = reader
+            .column_positions
+            .iter()
+            .map(|(filtered, position)| match (filtered, &remain_block) {
+                (false, Some(remain_block)) => remain_block.column(*position).clone(),
+                _ => filter_block.column(*position).clone(),
+            })
+            .collect();
+        let data_block = DataBlock::create(reader.projected_schema.clone(), columns);
+        if picked.iter().all(|picked| *picked) {
+            return Ok(data_block);
+        }
+        DataBlock::filter_block_with_bool_column(data_block, &BooleanColumn::from_slice(&picked))
+    }
+}
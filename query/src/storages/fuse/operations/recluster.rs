@@ -0,0 +1,427 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::ClusterStatistics;
+use common_fuse_meta::meta::SegmentInfo;
+use common_fuse_meta::meta::TableSnapshot;
+use common_meta_app::schema::TableStatistics;
+use common_planners::Extras;
+use common_planners::ReclusterTablePlan;
+use futures::StreamExt;
+use tracing::info;
+
+use super::mutation::block_filter::all_the_columns_ids;
+use crate::pipelines::processors::transforms::ExpressionExecutor;
+use crate::sessions::TableContext;
+use crate::storages::fuse::io::BlockStreamWriter;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::SegmentWriter;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::statistics::reducers::reduce_block_metas;
+use crate::storages::fuse::statistics::reducers::reduce_statistics;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::DEFAULT_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::DEFAULT_ROW_PER_BLOCK;
+use crate::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
+use crate::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+use crate::storages::index::ClusterKeyInfo;
+use crate::storages::Table;
+
+/// The max size of the data reclustered in a single round, which bounds the memory used to
+/// sort the blocks.
+const MAX_RECLUSTER_BYTES_PER_ROUND: u64 = 1024 * 1024 * 1024;
+
+impl FuseTable {
+    /// Reorganizes the blocks overlapping with each other on the cluster key.
+    ///
+    /// The overlapping blocks are picked out by the cluster statistics of the blocks, then they
+    /// are sorted by the cluster key and rewritten into new blocks and segments. A round
+    /// reclusters at most `MAX_RECLUSTER_BYTES_PER_ROUND` of data, with FINAL the rounds are
+    /// repeated until the table is well clustered or a round makes no progress.
+    pub async fn do_recluster(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        plan: &ReclusterTablePlan,
+    ) -> Result<()> {
+        if self.cluster_key_meta.is_none() {
+            return Err(ErrorCode::InvalidClusterKeys(format!(
+                "Table {} is not clustered",
+                self.name()
+            )));
+        }
+
+        let mut tbl = self;
+        let mut latest: Arc<dyn Table>;
+        let mut prev_overlap = (usize::MAX, usize::MAX);
+        loop {
+            let overlap = match tbl.recluster_once(&ctx, plan).await? {
+                Some(overlap) => overlap,
+                // well clustered, have nothing to do here
+                None => break,
+            };
+
+            // stop if the overlap did not shrink since the last round, in case of the blocks
+            // which are hard to be separated, e.g. the strings sharing a long prefix. A round
+            // may only recluster a part of a large group, which reduces the depth of the
+            // overlap but not always the number of the overlapping blocks.
+            let shrunk = overlap.0 <= prev_overlap.0
+                && overlap.1 <= prev_overlap.1
+                && overlap != prev_overlap;
+            if !plan.is_final || !shrunk {
+                break;
+            }
+            prev_overlap = overlap;
+
+            latest = tbl.latest(ctx.as_ref(), &plan.catalog).await?;
+            tbl = FuseTable::try_from_table(latest.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Checks whether the table needs to be reclustered, by the max depth of the blocks
+    /// overlapping with each other on the cluster key.
+    ///
+    /// Returns the reason of the reclustering, or None if the table is well clustered.
+    pub async fn check_recluster(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        depth_threshold: u64,
+    ) -> Result<Option<String>> {
+        let cluster_key_id = match &self.cluster_key_meta {
+            Some((id, _)) => *id,
+            None => return Ok(None),
+        };
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            None => return Ok(None),
+        };
+
+        let reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        let mut blocks = Vec::with_capacity(snapshot.summary.block_count as usize);
+        for (location, ver) in &snapshot.segments {
+            let segment = reader.read(location, None, *ver).await?;
+            blocks.extend(segment.blocks.iter().cloned());
+        }
+
+        // the blocks not sorted by the cluster key are not counted, they are
+        // reclustered along with the overlapping ones
+        let depth = overlapping_groups(cluster_key_id, blocks)
+            .iter()
+            .filter(|group| group.len() > 1)
+            .map(|group| max_depth(group))
+            .max()
+            .unwrap_or(0) as u64;
+        if depth > depth_threshold {
+            return Ok(Some(format!("depth {} on the cluster key", depth)));
+        }
+        Ok(None)
+    }
+
+    /// Reclusters a round, returns the number of the blocks needing to be reclustered and the
+    /// max depth of the overlap before this round, or None if there is none.
+    async fn recluster_once(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        plan: &ReclusterTablePlan,
+    ) -> Result<Option<(usize, usize)>> {
+        let snapshot = match self.read_table_snapshot(ctx.as_ref()).await? {
+            Some(snapshot) => snapshot,
+            // empty table, have nothing to do here
+            None => return Ok(None),
+        };
+
+        // only the blocks which may contain the rows matching the selection are considered
+        let push_downs = plan.selection.as_ref().map(|filter| Extras {
+            projection: None,
+            filters: vec![filter.clone()],
+            limit: None,
+            order_by: vec![],
+        });
+        let block_metas = BlockPruner::new(snapshot.clone())
+            .with_bloom_index_options(self.bloom_index_options()?)
+            .prune(ctx, self.table_info.schema(), &push_downs)
+            .await?;
+
+        let cluster_key_id = self.cluster_key_meta.as_ref().unwrap().0;
+        let blocks = block_metas.into_iter().map(|(_, meta)| meta).collect();
+        let groups = overlapping_groups(cluster_key_id, blocks);
+        let candidates: usize = groups.iter().map(|group| group.len()).sum();
+        if candidates == 0 {
+            return Ok(None);
+        }
+        let depth = groups
+            .iter()
+            .map(|group| max_depth(group))
+            .max()
+            .unwrap_or(0);
+        let groups = select_groups(groups, MAX_RECLUSTER_BYTES_PER_ROUND);
+
+        let new_snapshot = self.recluster_groups(ctx, &snapshot, groups).await?;
+        info!(
+            "recluster table {}, ident {}, {} blocks overlapped, {} blocks after reclustering",
+            self.table_info.desc,
+            self.table_info.ident,
+            candidates,
+            new_snapshot.summary.block_count,
+        );
+
+        let mut new_table_meta = self.table_info.meta.clone();
+        new_table_meta.statistics = TableStatistics {
+            number_of_rows: new_snapshot.summary.row_count,
+            data_bytes: new_snapshot.summary.uncompressed_byte_size,
+            compressed_data_bytes: new_snapshot.summary.compressed_byte_size,
+            index_data_bytes: new_snapshot.summary.index_size,
+        };
+        self.update_table_meta(
+            ctx.as_ref(),
+            &plan.catalog,
+            &new_snapshot,
+            &mut new_table_meta,
+        )
+        .await?;
+        Ok(Some((candidates, depth)))
+    }
+
+    /// Sorts each group of blocks by the cluster key, and replaces them with the sorted ones.
+    async fn recluster_groups(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        snapshot: &TableSnapshot,
+        groups: Vec<Vec<BlockMeta>>,
+    ) -> Result<TableSnapshot> {
+        let row_per_block = self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_ROW_PER_BLOCK);
+        let block_per_seg =
+            self.get_option(FUSE_OPT_KEY_BLOCK_PER_SEGMENT, DEFAULT_BLOCK_PER_SEGMENT);
+
+        // the cluster key expressions are evaluated into extra columns to sort by,
+        // which are removed by the block writer.
+        let input_schema = self.table_info.schema();
+        let mut merged = input_schema.fields().clone();
+        for expr in &self.cluster_keys {
+            let cname = expr.column_name();
+            if !merged.iter().any(|x| x.name() == &cname) {
+                merged.push(expr.to_data_field(&input_schema)?);
+            }
+        }
+        let output_schema = DataSchemaRefExt::create(merged);
+        let expression_executor = if output_schema.fields() != input_schema.fields() {
+            let executor = ExpressionExecutor::try_create(
+                ctx.clone(),
+                "expression executor",
+                input_schema.clone(),
+                output_schema,
+                self.cluster_keys.clone(),
+                false,
+            )?;
+            executor.validate()?;
+            Some(executor)
+        } else {
+            None
+        };
+        let sort_descs: Vec<SortColumnDescription> = self
+            .cluster_keys
+            .iter()
+            .map(|expr| SortColumnDescription {
+                column_name: expr.column_name(),
+                asc: true,
+                nulls_first: false,
+            })
+            .collect();
+        let cluster_key_info = ClusterKeyInfo {
+            cluster_key_id: self.cluster_key_meta.as_ref().unwrap().0,
+            cluster_key_index: vec![],
+            exprs: self.cluster_keys.clone(),
+            expression_executor: None,
+            data_schema: input_schema,
+        };
+
+        let block_reader = self.create_block_reader(ctx, all_the_columns_ids(self))?;
+        let bloom_index_options = self.bloom_index_options()?;
+        let column_ids = self.column_ids()?;
+        let mut reclustered = HashSet::new();
+        let mut new_blocks = Vec::new();
+        for group in &groups {
+            let mut data_blocks = Vec::with_capacity(group.len());
+            for block_meta in group {
+                data_blocks.push(block_reader.read_with_block_meta(block_meta).await?);
+                reclustered.insert(block_meta.location.0.clone());
+            }
+
+            let mut block = DataBlock::concat_blocks(&data_blocks)?;
+            if let Some(executor) = &expression_executor {
+                block = executor.execute(&block)?;
+            }
+            let block = DataBlock::sort_block(&block, &sort_descs, None)?;
+
+            let mut segment_stream = BlockStreamWriter::write_block_stream(
+                ctx.clone(),
+                Box::pin(futures::stream::iter(vec![Ok::<_, ErrorCode>(block)])),
+                row_per_block,
+                block_per_seg,
+                self.meta_location_generator().clone(),
+                Some(cluster_key_info.clone()),
+                bloom_index_options.clone(),
+                Some(column_ids.clone()),
+            )
+            .await?;
+            while let Some(segment) = segment_stream.next().await {
+                new_blocks.extend(segment?.blocks);
+            }
+        }
+
+        // the segments containing no reclustered blocks are kept, the remaining blocks of the
+        // others are reorganized into new segments along with the new blocks.
+        let mut segments = Vec::with_capacity(snapshot.segments.len());
+        let mut summaries = Vec::with_capacity(snapshot.segments.len());
+        let reader = MetaReaders::segment_info_reader(ctx.as_ref());
+        for (location, ver) in &snapshot.segments {
+            let segment = reader.read(location, None, *ver).await?;
+            if segment
+                .blocks
+                .iter()
+                .all(|b| !reclustered.contains(&b.location.0))
+            {
+                segments.push((location.clone(), *ver));
+                summaries.push(segment.summary.clone());
+                continue;
+            }
+            new_blocks.extend(
+                segment
+                    .blocks
+                    .iter()
+                    .filter(|b| !reclustered.contains(&b.location.0))
+                    .cloned(),
+            );
+        }
+
+        let operator = ctx.get_storage_operator()?;
+        let segment_info_cache = ctx.get_storage_cache_manager().get_table_segment_cache();
+        let segment_writer = SegmentWriter::new(
+            &operator,
+            &self.meta_location_generator,
+            &segment_info_cache,
+        );
+        for chunk in new_blocks.chunks(block_per_seg) {
+            let summary = reduce_block_metas(chunk)?;
+            let new_segment = SegmentInfo::new(chunk.to_vec(), summary.clone());
+            segments.push(segment_writer.write_segment(new_segment).await?);
+            summaries.push(summary);
+        }
+
+        let mut new_snapshot = TableSnapshot::from_previous(snapshot);
+        new_snapshot.segments = segments;
+        new_snapshot.summary = reduce_statistics(&summaries)?;
+        Ok(new_snapshot)
+    }
+}
+
+/// Groups the blocks overlapping with each other on the cluster key.
+///
+/// The blocks not sorted by the current cluster key, which are written before the cluster key
+/// is defined or by the compaction, are grouped alone. The blocks overlapping with no other
+/// blocks are left out, the blocks only sharing the boundary values are not overlapping.
+fn overlapping_groups(cluster_key_id: u32, blocks: Vec<BlockMeta>) -> Vec<Vec<BlockMeta>> {
+    let mut groups = Vec::new();
+    let mut sorted: Vec<(ClusterStatistics, BlockMeta)> = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        match block.cluster_stats.clone() {
+            Some(stats) if stats.cluster_key_id == cluster_key_id => sorted.push((stats, block)),
+            _ => groups.push(vec![block]),
+        }
+    }
+    sorted.sort_by(|(a, _), (b, _)| a.min.cmp(&b.min).then_with(|| a.max.cmp(&b.max)));
+
+    let mut group: Vec<BlockMeta> = Vec::new();
+    let mut group_max: Vec<DataValue> = Vec::new();
+    for (stats, block) in sorted {
+        if !group.is_empty() && stats.min < group_max {
+            if stats.max > group_max {
+                group_max = stats.max;
+            }
+            group.push(block);
+            continue;
+        }
+
+        if group.len() > 1 {
+            groups.push(std::mem::take(&mut group));
+        } else {
+            group.clear();
+        }
+        group.push(block);
+        group_max = stats.max;
+    }
+    if group.len() > 1 {
+        groups.push(group);
+    }
+    groups
+}
+
+/// The max number of the blocks overlapping at the same point of the cluster key.
+fn max_depth(group: &[BlockMeta]) -> usize {
+    let mut ranges: Vec<&ClusterStatistics> = group
+        .iter()
+        .filter_map(|b| b.cluster_stats.as_ref())
+        .collect();
+    ranges.sort_by(|a, b| a.min.cmp(&b.min));
+
+    // the max values of the blocks covering the current point
+    let mut ends: BinaryHeap<Reverse<&Vec<DataValue>>> = BinaryHeap::new();
+    let mut depth = 0;
+    for range in ranges {
+        while matches!(ends.peek(), Some(Reverse(end)) if *end <= &range.min) {
+            ends.pop();
+        }
+        ends.push(Reverse(&range.max));
+        depth = std::cmp::max(depth, ends.len());
+    }
+    depth
+}
+
+/// Selects the groups to recluster in a round, the largest groups first, within the size limit.
+fn select_groups(mut groups: Vec<Vec<BlockMeta>>, max_bytes: u64) -> Vec<Vec<BlockMeta>> {
+    groups.sort_by_key(|group| Reverse(group.len()));
+
+    let mut selected = Vec::new();
+    let mut total_bytes = 0;
+    for group in groups {
+        let group_bytes: u64 = group.iter().map(|b| b.block_size).sum();
+        if total_bytes + group_bytes <= max_bytes {
+            total_bytes += group_bytes;
+            selected.push(group);
+        } else if selected.is_empty() {
+            // the group alone exceeds the limit, takes its blocks of the lowest cluster keys
+            let mut part = Vec::new();
+            for block in group {
+                if total_bytes + block.block_size > max_bytes && part.len() > 1 {
+                    break;
+                }
+                total_bytes += block.block_size;
+                part.push(block);
+            }
+            selected.push(part);
+        }
+    }
+    selected
+}
@@ -69,13 +69,12 @@ impl FuseTable {
 
             let table_id = self.table_info.ident.table_id;
             let table_version = self.table_info.ident.seq;
-            ctx.get_catalog(catalog_name)?
-                .update_table_meta(UpdateTableMetaReq {
-                    table_id,
-                    seq: MatchSeq::Exact(table_version),
-                    new_table_meta,
-                })
-                .await?;
+            ctx.update_table_meta(catalog_name, &self.table_info, UpdateTableMetaReq {
+                table_id,
+                seq: MatchSeq::Exact(table_version),
+                new_table_meta,
+            })
+            .await?;
         }
 
         Ok(())
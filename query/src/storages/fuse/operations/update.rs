@@ -0,0 +1,93 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::Extras;
+use common_planners::UpdatePlan;
+
+use crate::pipelines::processors::transforms::CheckConstraintsExecutor;
+use crate::sessions::TableContext;
+use crate::storages::fuse::operations::mutation::update_from_block;
+use crate::storages::fuse::operations::mutation::DeletionMutator;
+use crate::storages::fuse::pruning::BlockPruner;
+use crate::storages::fuse::FuseTable;
+
+impl FuseTable {
+    pub async fn do_update(&self, ctx: Arc<dyn TableContext>, plan: &UpdatePlan) -> Result<()> {
+        let snapshot_opt = self.read_table_snapshot(ctx.as_ref()).await?;
+
+        // check if table is empty
+        let snapshot = if let Some(val) = snapshot_opt {
+            val
+        } else {
+            // no snapshot, no update
+            return Ok(());
+        };
+
+        if snapshot.summary.row_count == 0 {
+            // empty snapshot, no update
+            return Ok(());
+        }
+
+        // the updated blocks are replaced in the same way as deletion
+        let mut mutator =
+            DeletionMutator::try_create(&ctx, &self.meta_location_generator, &snapshot)?
+                .with_bloom_index_options(self.bloom_index_options()?)
+                .with_column_ids(self.column_ids()?);
+
+        // only the blocks whose statistics may match the filter are going to be updated
+        let push_downs = plan.selection.as_ref().map(|filter| Extras {
+            projection: Some(plan.projection.clone()),
+            filters: vec![filter.clone()],
+            limit: None,
+            order_by: vec![],
+        });
+        let schema = self.table_info.schema();
+        let block_metas = BlockPruner::new(snapshot.clone())
+            .with_bloom_index_options(self.bloom_index_options()?)
+            .prune(&ctx, schema, &push_downs)
+            .await?;
+
+        // the updated rows must satisfy the check constraints of the table
+        let check_constraints =
+            CheckConstraintsExecutor::try_create(ctx.clone(), &self.table_info)?;
+
+        // update block one by one.
+        for (seg_idx, block_meta) in block_metas {
+            let proj = plan.projection.clone();
+            let updated = update_from_block(
+                self,
+                &block_meta,
+                &ctx,
+                proj,
+                plan.selection.as_ref(),
+                &plan.update_list,
+            )
+            .await?;
+            if let Some(block) = updated {
+                check_constraints.check(&block)?;
+                // the new block is written with its statistics and bloom filter index,
+                // and replaces the block located at `block_meta.location`
+                mutator
+                    .replace_with(seg_idx, block_meta.location.clone(), block)
+                    .await?;
+            }
+        }
+
+        self.commit_mutation(ctx.as_ref(), mutator, &plan.catalog_name)
+            .await
+    }
+}
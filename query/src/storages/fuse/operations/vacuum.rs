@@ -0,0 +1,297 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Duration;
+use chrono::Utc;
+use common_cache::Cache;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_fuse_meta::meta::Location;
+use common_meta_app::schema::TableMeta;
+use common_planners::VacuumTablePlan;
+use futures::TryStreamExt;
+use tracing::info;
+use tracing::warn;
+
+use crate::sessions::TableContext;
+use crate::sql::OPT_KEY_SNAPSHOT_LOCATION;
+use crate::storages::fuse::io::MetaReaders;
+use crate::storages::fuse::io::TableMetaLocationGenerator;
+use crate::storages::fuse::FuseTable;
+use crate::storages::fuse::FUSE_OPT_KEY_DATA_RETENTION_PERIOD;
+use crate::storages::VacuumStatistics;
+
+impl FuseTable {
+    /// Purges the data which is not reachable from the retained snapshots.
+    ///
+    /// The retained snapshots are
+    /// - the current snapshot
+    /// - the snapshots committed within the retention period
+    /// - the newest snapshot committed before the retention period, so that the table can
+    ///   still travel to any time point within the retention period
+    ///
+    /// The retention period is taken from the plan, the table option `data_retention_period`
+    /// and the setting `data_retention_period`, in that order.
+    pub async fn do_vacuum(
+        &self,
+        ctx: &Arc<dyn TableContext>,
+        plan: &VacuumTablePlan,
+    ) -> Result<VacuumStatistics> {
+        let snapshot_location = match self.snapshot_loc() {
+            Some(loc) => loc,
+            // empty table, have nothing to do here
+            None => return Ok(VacuumStatistics::default()),
+        };
+
+        let retention_hours = match plan.retain_hours {
+            Some(hours) => hours,
+            None => match self
+                .table_info
+                .options()
+                .get(FUSE_OPT_KEY_DATA_RETENTION_PERIOD)
+            {
+                Some(v) => v.parse::<u64>().map_err(|_| {
+                    ErrorCode::BadOption(format!(
+                        "invalid value of table option {}: {}",
+                        FUSE_OPT_KEY_DATA_RETENTION_PERIOD, v
+                    ))
+                })?,
+                None => ctx.get_settings().get_data_retention_period()?,
+            },
+        };
+        // a retention period out of the range of time keeps all the snapshots
+        let cutoff = i64::try_from(retention_hours)
+            .ok()
+            .filter(|hours| *hours <= i64::MAX / 1000 / 3600)
+            .and_then(|hours| Utc::now().checked_sub_signed(Duration::hours(hours)));
+
+        // snapshots are order by timestamp DESC.
+        let reader = MetaReaders::table_snapshot_reader(ctx.as_ref());
+        let mut snapshot_history = reader.snapshot_history(
+            snapshot_location,
+            self.snapshot_format_version(),
+            self.meta_location_generator().clone(),
+        );
+
+        let mut segments_referenced_by_retained: HashSet<Location> = HashSet::new();
+        let mut snapshots_to_be_deleted = Vec::new();
+        let mut segments_of_deleted = Vec::new();
+        let mut statistics_referenced_by_retained = HashSet::new();
+        let mut statistics_of_deleted = HashSet::new();
+        let mut expired = false;
+        while let Some(s) = snapshot_history.try_next().await? {
+            if expired {
+                snapshots_to_be_deleted.push((s.snapshot_id, s.format_version()));
+                segments_of_deleted.extend(s.segments.clone());
+                statistics_of_deleted.extend(s.table_statistics_location.clone());
+            } else {
+                segments_referenced_by_retained.extend(s.segments.clone());
+                statistics_referenced_by_retained.extend(s.table_statistics_location.clone());
+                // snapshots of legacy versions have no timestamp, they are taken as expired
+                expired = match cutoff {
+                    Some(cutoff) => !matches!(s.timestamp, Some(ts) if ts >= cutoff),
+                    None => false,
+                };
+            }
+        }
+
+        let segments_to_be_deleted: HashSet<Location> = segments_of_deleted
+            .into_iter()
+            .filter(|seg| !segments_referenced_by_retained.contains(seg))
+            .collect();
+
+        // blocks and bloom indexes which are no longer referenced by the retained segments
+        let blocks_referenced_by_retained = self
+            .blocks_of(ctx.as_ref(), segments_referenced_by_retained.iter())
+            .await?;
+        let (blocks_to_be_deleted, indexes_to_be_deleted) = self
+            .block_files_of(
+                ctx.as_ref(),
+                segments_to_be_deleted.iter(),
+                &blocks_referenced_by_retained,
+            )
+            .await?;
+
+        let stats = VacuumStatistics {
+            snapshots: snapshots_to_be_deleted.len() as u64,
+            segments: segments_to_be_deleted.len() as u64,
+            blocks: blocks_to_be_deleted.len() as u64,
+            bloom_indexes: indexes_to_be_deleted.len() as u64,
+        };
+
+        info!(
+            "vacuum table {}, ident {}, retention {} hours, dry run {}: {:?}",
+            self.table_info.desc, self.table_info.ident, retention_hours, plan.dry_run, stats,
+        );
+
+        if plan.dry_run {
+            return Ok(stats);
+        }
+
+        // order matters, should always remove the blocks first, segment 2nd, snapshot last
+        let accessor = ctx.get_storage_operator()?;
+        for location in &indexes_to_be_deleted {
+            if let Some(c) = ctx.get_storage_cache_manager().get_bloom_index_meta_cache() {
+                let cache = &mut *c.write().await;
+                cache.pop(location);
+            }
+            self.remove_location(&accessor, location).await?;
+        }
+        for location in &blocks_to_be_deleted {
+            self.remove_location(&accessor, location).await?;
+        }
+        // the statistics files collected by `ANALYZE TABLE`
+        for (location, _) in statistics_of_deleted.difference(&statistics_referenced_by_retained) {
+            self.remove_location(&accessor, location).await?;
+        }
+
+        self.collect(
+            ctx.as_ref(),
+            segments_to_be_deleted,
+            snapshots_to_be_deleted,
+        )
+        .await?;
+
+        Ok(stats)
+    }
+
+    /// Purges the data written by a transaction, which is rolled back or fails to commit.
+    ///
+    /// `self` is the table before the transaction, and `metas` are the metas written by the
+    /// transaction. Their snapshots are never committed, so they can't be reached from the
+    /// snapshot history by `VACUUM`. They are removed, with the segments, blocks, bloom indexes
+    /// and statistics files not referenced by the snapshot of `self`.
+    pub async fn purge_uncommitted(
+        &self,
+        ctx: &dyn TableContext,
+        metas: &[TableMeta],
+    ) -> Result<()> {
+        let base_snapshot_location = self.snapshot_loc();
+        let reader = MetaReaders::table_snapshot_reader(ctx);
+        let mut snapshots_to_be_deleted = Vec::new();
+        let mut segments_of_deleted = HashSet::new();
+        let mut statistics_of_deleted = HashSet::new();
+        for meta in metas {
+            let location = match meta.options.get(OPT_KEY_SNAPSHOT_LOCATION) {
+                Some(location) if Some(location) != base_snapshot_location.as_ref() => location,
+                _ => continue,
+            };
+            let ver = TableMetaLocationGenerator::snapshot_version(location);
+            let snapshot = match reader.read(location, None, ver).await {
+                Err(e) if e.code() == ErrorCode::storage_not_found_code() => continue,
+                Err(e) => return Err(e),
+                Ok(v) => v,
+            };
+            snapshots_to_be_deleted.push((snapshot.snapshot_id, snapshot.format_version()));
+            segments_of_deleted.extend(snapshot.segments.clone());
+            statistics_of_deleted.extend(snapshot.table_statistics_location.clone());
+        }
+        if snapshots_to_be_deleted.is_empty() {
+            return Ok(());
+        }
+
+        let (segments_referenced_by_base, statistics_referenced_by_base) =
+            match self.read_table_snapshot(ctx).await? {
+                Some(snapshot) => (
+                    snapshot.segments.iter().cloned().collect::<HashSet<_>>(),
+                    snapshot.table_statistics_location.clone(),
+                ),
+                None => (HashSet::new(), None),
+            };
+        let segments_to_be_deleted: HashSet<Location> = segments_of_deleted
+            .into_iter()
+            .filter(|seg| !segments_referenced_by_base.contains(seg))
+            .collect();
+        let blocks_referenced_by_base = self
+            .blocks_of(ctx, segments_referenced_by_base.iter())
+            .await?;
+        let (blocks_to_be_deleted, indexes_to_be_deleted) = self
+            .block_files_of(
+                ctx,
+                segments_to_be_deleted.iter(),
+                &blocks_referenced_by_base,
+            )
+            .await?;
+
+        info!(
+            "purge the uncommitted data of table {}, ident {}: {} snapshots, {} segments, {} blocks",
+            self.table_info.desc,
+            self.table_info.ident,
+            snapshots_to_be_deleted.len(),
+            segments_to_be_deleted.len(),
+            blocks_to_be_deleted.len(),
+        );
+
+        let accessor = ctx.get_storage_operator()?;
+        for location in &indexes_to_be_deleted {
+            if let Some(c) = ctx.get_storage_cache_manager().get_bloom_index_meta_cache() {
+                let cache = &mut *c.write().await;
+                cache.pop(location);
+            }
+            self.remove_location(&accessor, location).await?;
+        }
+        for location in &blocks_to_be_deleted {
+            self.remove_location(&accessor, location).await?;
+        }
+        for (location, _) in statistics_of_deleted
+            .iter()
+            .filter(|location| Some(*location) != statistics_referenced_by_base.as_ref())
+        {
+            self.remove_location(&accessor, location).await?;
+        }
+
+        self.collect(ctx, segments_to_be_deleted, snapshots_to_be_deleted)
+            .await
+    }
+
+    /// Returns the locations of the blocks and bloom indexes, which are
+    /// - referenced by any one of `segments`
+    /// - but NOT referenced by `root`
+    async fn block_files_of(
+        &self,
+        ctx: &dyn TableContext,
+        segments: impl Iterator<Item = &Location>,
+        root: &HashSet<String>,
+    ) -> Result<(HashSet<String>, HashSet<String>)> {
+        let mut blocks = HashSet::new();
+        let mut indexes = HashSet::new();
+        let reader = MetaReaders::segment_info_reader(ctx);
+        for (segment_location, ver) in segments {
+            let segment_info = match reader.read(segment_location, None, *ver).await {
+                Err(e) if e.code() == ErrorCode::storage_not_found_code() => {
+                    // concurrent gc: someone else has already collected this segment, ignore it
+                    warn!(
+                        "concurrent gc: segment of location {} already collected. table: {}, ident {}",
+                        segment_location, self.table_info.desc, self.table_info.ident,
+                    );
+                    continue;
+                }
+                Err(e) => return Err(e),
+                Ok(v) => v,
+            };
+            for block_meta in &segment_info.blocks {
+                if !root.contains(block_meta.location.0.as_str()) {
+                    blocks.insert(block_meta.location.0.clone());
+                    if let Some(index_location) = &block_meta.bloom_filter_index_location {
+                        indexes.insert(index_location.0.clone());
+                    }
+                }
+            }
+        }
+        Ok((blocks, indexes))
+    }
+}
@@ -26,6 +26,7 @@ use opendal::Operator;
 
 use crate::storages::fuse::io::BlockBloomFilterIndexReader;
 use crate::storages::index::BloomFilterIndexer;
+use crate::storages::index::BloomIndexOptions;
 
 #[async_trait::async_trait]
 pub trait BloomFilterPruner {
@@ -53,6 +54,8 @@ struct BloomFilterIndexPruner {
     dal: Operator,
     // the schema of data being indexed
     data_schema: DataSchemaRef,
+    // the size of grams of the ngram bloom filters
+    ngram_size: usize,
 }
 
 impl BloomFilterIndexPruner {
@@ -62,6 +65,7 @@ impl BloomFilterIndexPruner {
         filter_expression: Expression,
         dal: Operator,
         data_schema: DataSchemaRef,
+        ngram_size: usize,
     ) -> Self {
         Self {
             ctx,
@@ -69,6 +73,7 @@ impl BloomFilterIndexPruner {
             filter_expression,
             dal,
             data_schema,
+            ngram_size,
         }
     }
 }
@@ -85,6 +90,7 @@ impl BloomFilterPruner for BloomFilterIndexPruner {
                 &self.data_schema,
                 &self.filter_expression,
                 &self.index_columns,
+                self.ngram_size,
                 loc,
             )
             .await
@@ -104,6 +110,7 @@ impl BloomFilterPruner for BloomFilterIndexPruner {
 
 /// try to build the pruner.
 /// if `filter_expr` is none, or is not applicable, e.g. have no point queries
+/// or substring queries on the columns having ngram or token bloom filters,
 /// a [NonPruner] will be return, which prunes nothing.
 /// otherwise, a [BloomFilterIndexer] backed pruner will be return
 pub fn new_bloom_filter_pruner(
@@ -111,25 +118,45 @@ pub fn new_bloom_filter_pruner(
     filter_expr: Option<&Expression>,
     schema: &DataSchemaRef,
     dal: Operator,
+    options: &BloomIndexOptions,
 ) -> Result<Arc<dyn BloomFilterPruner + Send + Sync>> {
     if let Some(expr) = filter_expr {
         // check if there were applicable filter conditions
-        let point_query_cols = columns_names_of_eq_expressions(expr)?;
-        if !point_query_cols.is_empty() {
-            // convert to bloom filter block's column names
-            let filter_block_cols = point_query_cols
-                .into_iter()
-                .map(|n| BloomFilterIndexer::to_bloom_column_name(&n))
-                .collect();
+        let (point_query_cols, substring_query_cols) = columns_names_of_index_expressions(expr)?;
+
+        // convert to bloom filter block's column names
+        let mut filter_block_cols = point_query_cols
+            .iter()
+            .map(|n| BloomFilterIndexer::to_bloom_column_name(n))
+            .collect::<Vec<_>>();
+        for n in point_query_cols.iter() {
+            if options.set_columns.contains(n) {
+                filter_block_cols.push(BloomFilterIndexer::to_set_column_name(n));
+            }
+        }
+        for n in substring_query_cols.iter() {
+            if options.ngram_columns.contains(n) {
+                filter_block_cols.push(BloomFilterIndexer::to_ngram_column_name(
+                    n,
+                    options.ngram_size,
+                ));
+            }
+            if options.token_columns.contains(n) {
+                filter_block_cols.push(BloomFilterIndexer::to_token_column_name(n));
+            }
+        }
+
+        if !filter_block_cols.is_empty() {
             return Ok(Arc::new(BloomFilterIndexPruner::new(
                 ctx.clone(),
                 filter_block_cols,
                 expr.clone(),
                 dal,
                 schema.clone(),
+                options.ngram_size,
             )));
         } else {
-            tracing::debug!("no point filters found, using NonPruner");
+            tracing::debug!("no point or substring filters found, using NonPruner");
         }
     }
     Ok(Arc::new(NonPruner))
@@ -144,6 +171,7 @@ mod util {
         schema: &DataSchemaRef,
         filter_expr: &Expression,
         bloom_index_col_names: &[String],
+        ngram_size: usize,
         index_location: &Location,
     ) -> Result<bool> {
         // load the relevant index columns
@@ -153,18 +181,21 @@ mod util {
 
         // figure it out
         BloomFilterIndexer::from_bloom_block(schema.clone(), bloom_filter_index.into_data(), ctx)?
+            .with_ngram_size(ngram_size)
             .maybe_true(filter_expr)
     }
 
     struct PointQueryVisitor {
         // names of columns which used by point query kept here
         columns: HashSet<String>,
+        // names of columns which used by substring query kept here
+        substring_columns: HashSet<String>,
     }
 
     impl ExpressionVisitor for PointQueryVisitor {
         fn pre_visit(mut self, expr: &Expression) -> Result<Recursion<Self>> {
             // TODO
-            // 1. only binary op "=" is considered, which is NOT enough
+            // 1. only binary op "=" is considered for point queries, which is NOT enough
             // 2. should combine this logic with BloomFilterIndexer
             match expr {
                 Expression::BinaryExpression { left, op, right } if op.as_str() == "=" => {
@@ -177,18 +208,49 @@ mod util {
                         _ => Ok(Recursion::Continue(self)),
                     }
                 }
+                Expression::BinaryExpression { left, op, right }
+                    if op.to_lowercase().as_str() == "like" =>
+                {
+                    match (left.as_ref(), right.as_ref()) {
+                        (Expression::Column(column), Expression::Literal { .. }) => {
+                            self.substring_columns.insert(column.clone());
+                            Ok(Recursion::Stop(self))
+                        }
+                        _ => Ok(Recursion::Continue(self)),
+                    }
+                }
+                Expression::ScalarFunction { op, args }
+                    if matches!(
+                        op.to_lowercase().as_str(),
+                        "position" | "locate" | "instr" | "regexp_like"
+                    ) =>
+                {
+                    for arg in args {
+                        if let Expression::Column(column) = arg {
+                            self.substring_columns.insert(column.clone());
+                        }
+                    }
+                    Ok(Recursion::Stop(self))
+                }
                 _ => Ok(Recursion::Continue(self)),
             }
         }
     }
 
-    pub fn columns_names_of_eq_expressions(filter_expr: &Expression) -> Result<Vec<String>> {
+    /// Returns the names of columns used by the point queries and by the substring queries.
+    pub fn columns_names_of_index_expressions(
+        filter_expr: &Expression,
+    ) -> Result<(Vec<String>, Vec<String>)> {
         let visitor = PointQueryVisitor {
             columns: HashSet::new(),
+            substring_columns: HashSet::new(),
         };
 
-        filter_expr
-            .accept(visitor)
-            .map(|r| r.columns.into_iter().collect())
+        filter_expr.accept(visitor).map(|r| {
+            (
+                r.columns.into_iter().collect(),
+                r.substring_columns.into_iter().collect(),
+            )
+        })
     }
 }
@@ -16,4 +16,8 @@ mod bloom_pruner;
 mod limiter;
 mod pruning_executor;
 mod range_pruner;
+pub(crate) use bloom_pruner::new_bloom_filter_pruner;
+pub(crate) use bloom_pruner::BloomFilterPruner;
 pub use pruning_executor::BlockPruner;
+pub(crate) use range_pruner::new_range_filter_pruner;
+pub(crate) use range_pruner::RangeFilterPruner;
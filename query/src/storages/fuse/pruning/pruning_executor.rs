@@ -20,9 +20,12 @@ use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::Location;
 use common_fuse_meta::meta::TableSnapshot;
+use common_planners::Expression;
 use common_planners::Extras;
+use common_planners::RequireColumnsVisitor;
 use futures::future;
 use futures::StreamExt;
 use futures::TryStreamExt;
@@ -30,19 +33,33 @@ use tracing::Instrument;
 
 use super::bloom_pruner;
 use crate::sessions::TableContext;
+use crate::storages::fuse::column_ids::build_table_column_leaves;
 use crate::storages::fuse::io::MetaReaders;
 use crate::storages::fuse::pruning::limiter;
 use crate::storages::fuse::pruning::range_pruner;
+use crate::storages::index::BloomIndexOptions;
 
 pub struct BlockPruner {
     table_snapshot: Arc<TableSnapshot>,
+    bloom_index_options: BloomIndexOptions,
 }
 
 const FUTURE_BUFFER_SIZE: usize = 10;
 
 impl BlockPruner {
     pub fn new(table_snapshot: Arc<TableSnapshot>) -> Self {
-        Self { table_snapshot }
+        Self {
+            table_snapshot,
+            bloom_index_options: BloomIndexOptions::default(),
+        }
+    }
+
+    /// Set the options of the substring bloom filters, the ngram and token bloom filters
+    /// are only used by the pruning if the options are given.
+    #[must_use]
+    pub fn with_bloom_index_options(mut self, bloom_index_options: BloomIndexOptions) -> Self {
+        self.bloom_index_options = bloom_index_options;
+        self
     }
 
     // prune blocks by utilizing min_max index and bloom filter, according to the pushdowns
@@ -84,8 +101,22 @@ impl BlockPruner {
 
         // prepare the bloom filter, if filter_expression is none, an dummy pruner will be returned
         let dal = ctx.get_storage_operator()?;
-        let bloom_filter_pruner =
-            bloom_pruner::new_bloom_filter_pruner(ctx, filter_expression, &schema, dal)?;
+        let bloom_filter_pruner = bloom_pruner::new_bloom_filter_pruner(
+            ctx,
+            filter_expression,
+            &schema,
+            dal,
+            &self.bloom_index_options,
+        )?;
+
+        // the bloom filters are looked up by the names of the columns, which do not apply to
+        // the blocks written before the columns are added (with the names of dropped ones).
+        // A column can not be renamed to the name of a dropped or renamed one, so the names
+        // of the columns in the existing bloom indexes always refer to the same columns.
+        let filter_column_ids = Arc::new(match filter_expression {
+            Some(expr) => Self::filter_column_ids(expr, &schema)?,
+            None => vec![],
+        });
 
         // 2. kick off
         //
@@ -108,6 +139,7 @@ impl BlockPruner {
             let ctx = ctx.clone();
             let range_filter_pruner = range_filter_pruner.clone();
             let bloom_filter_pruner = bloom_filter_pruner.clone();
+            let filter_column_ids = filter_column_ids.clone();
             let limiter = limiter.clone();
             let segment_pruning_fut = async move {
                 let segment_reader = MetaReaders::segment_info_reader(ctx.as_ref());
@@ -126,9 +158,13 @@ impl BlockPruner {
                         }
                         if range_filter_pruner.should_keep(&block_meta.col_stats) {
                             // prune block using bloom filter
-                            if bloom_filter_pruner
-                                .should_keep(&block_meta.bloom_filter_index_location)
-                                .await
+                            let bloom_applicable = filter_column_ids
+                                .iter()
+                                .all(|id| block_meta.col_metas.contains_key(id));
+                            if !bloom_applicable
+                                || bloom_filter_pruner
+                                    .should_keep(&block_meta.bloom_filter_index_location)
+                                    .await
                             {
                                 if limiter.within_limit(block_meta.row_count) {
                                     result.push((idx, block_meta.clone()));
@@ -161,6 +197,17 @@ impl BlockPruner {
         })
     }
 
+    /// Returns the ids of the leaf columns referenced by the filter.
+    fn filter_column_ids(expr: &Expression, schema: &DataSchemaRef) -> Result<Vec<ColumnId>> {
+        let column_leaves = build_table_column_leaves(schema)?;
+        let columns = RequireColumnsVisitor::collect_columns_from_expr(expr)?;
+        Ok(columns
+            .iter()
+            .filter_map(|name| schema.index_of(name).ok())
+            .flat_map(|idx| column_leaves[idx].column_ids.iter().copied())
+            .collect())
+    }
+
     async fn all_the_blocks(
         segment_locs: Vec<Location>,
         ctx: &dyn TableContext,
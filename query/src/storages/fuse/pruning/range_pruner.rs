@@ -20,6 +20,7 @@ use common_exception::Result;
 use common_fuse_meta::meta::StatisticsOfColumns;
 use common_planners::Expression;
 
+use crate::storages::fuse::column_ids::field_column_ids;
 use crate::storages::index::RangeFilter;
 
 pub trait RangeFilterPruner {
@@ -55,7 +56,8 @@ pub fn new_range_filter_pruner<'a>(
 ) -> Result<Arc<dyn RangeFilterPruner + Send + Sync>> {
     Ok(match filter_expr {
         Some(expr) => {
-            let range_filter = RangeFilter::try_create(ctx.clone(), expr, schema.clone())?;
+            let range_filter = RangeFilter::try_create(ctx.clone(), expr, schema.clone())?
+                .with_column_ids(&field_column_ids(schema)?);
             Arc::new(range_filter)
         }
         _ => Arc::new(NoPruner),
@@ -18,11 +18,13 @@ use common_arrow::parquet::metadata::ThriftFileMetaData;
 use common_datablocks::DataBlock;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
+use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::ColumnMeta;
 use common_fuse_meta::meta::Location;
 use common_fuse_meta::meta::StatisticsOfColumns;
 use common_fuse_meta::meta::Versioned;
 
+use crate::storages::fuse::column_ids::remap_column_ids;
 use crate::storages::fuse::operations::column_metas;
 use crate::storages::fuse::statistics::block_statistics::BlockStatistics;
 
@@ -35,6 +37,9 @@ pub struct StatisticsAccumulator {
     pub in_memory_size: u64,
     pub file_size: u64,
     pub index_size: u64,
+    // the ids of the leaf columns, the column metas and statistics added are keyed by
+    // the positions of the leaf columns if not set
+    pub column_ids: Option<Vec<ColumnId>>,
 }
 
 impl StatisticsAccumulator {
@@ -42,6 +47,14 @@ impl StatisticsAccumulator {
         Default::default()
     }
 
+    /// Set the ids of the leaf columns, by which the column metas and statistics added
+    /// are re-keyed.
+    #[must_use]
+    pub fn with_column_ids(mut self, column_ids: Vec<ColumnId>) -> Self {
+        self.column_ids = Some(column_ids);
+        self
+    }
+
     pub fn add_block(
         &mut self,
         file_size: u64,
@@ -87,10 +100,18 @@ impl StatisticsAccumulator {
         &mut self,
         file_size: u64,
         column_meta: HashMap<u32, ColumnMeta>,
-        block_statistics: BlockStatistics,
+        mut block_statistics: BlockStatistics,
         bloom_filter_index_location: Option<Location>,
         bloom_filter_index_size: u64,
     ) -> Result<()> {
+        let column_meta = match &self.column_ids {
+            None => column_meta,
+            Some(column_ids) => {
+                block_statistics.block_column_statistics =
+                    remap_column_ids(block_statistics.block_column_statistics, column_ids);
+                remap_column_ids(column_meta, column_ids)
+            }
+        };
         self.file_size += file_size;
         self.index_size += bloom_filter_index_size;
         self.summary_block_count += 1;
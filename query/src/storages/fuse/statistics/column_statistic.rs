@@ -12,19 +12,42 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::hash::Hasher;
+
 use common_datablocks::DataBlock;
+use common_datavalues::ColumnRef;
 use common_datavalues::ColumnWithField;
 use common_datavalues::DataField;
 use common_datavalues::DataValue;
 use common_exception::Result;
 use common_functions::aggregates::eval_aggr;
 use common_fuse_meta::meta::ColumnStatistics;
+use common_fuse_meta::meta::HyperLogLog;
 use common_fuse_meta::meta::StatisticsOfColumns;
+use twox_hash::XxHash64;
 
 use crate::storages::index::MinMaxIndex;
 use crate::storages::index::SupportedType;
 
+/// Collects the statistics of the leaf columns of the block, which are kept in the block meta.
+///
+/// The sketches of distinct values are not collected here, they are only collected by
+/// `ANALYZE TABLE`, see [`gen_columns_statistics_with_distinct_values`].
 pub fn gen_columns_statistics(data_block: &DataBlock) -> Result<StatisticsOfColumns> {
+    collect_columns_statistics(data_block, false)
+}
+
+/// Collects the statistics of the leaf columns of the block, with the sketches of distinct values.
+pub fn gen_columns_statistics_with_distinct_values(
+    data_block: &DataBlock,
+) -> Result<StatisticsOfColumns> {
+    collect_columns_statistics(data_block, true)
+}
+
+fn collect_columns_statistics(
+    data_block: &DataBlock,
+    with_distinct_values: bool,
+) -> Result<StatisticsOfColumns> {
     let mut statistics = StatisticsOfColumns::new();
 
     let leaves = traverse::traverse_columns_dfs(data_block.columns())?;
@@ -65,6 +88,11 @@ pub fn gen_columns_statistics(data_block: &DataBlock) -> Result<StatisticsOfColu
             max,
             null_count: unset_bits as u64,
             in_memory_size,
+            distinct_values: if with_distinct_values {
+                Some(distinct_values_of(col))
+            } else {
+                None
+            },
         };
 
         statistics.insert(idx as u32, col_stats);
@@ -72,7 +100,29 @@ pub fn gen_columns_statistics(data_block: &DataBlock) -> Result<StatisticsOfColu
     Ok(statistics)
 }
 
+/// Builds the sketch of the distinct non-null values of the column.
+///
+/// The values are hashed by their serialized bytes, the hash function and the seed should
+/// never be changed, otherwise the sketches of different blocks can not be merged.
+fn distinct_values_of(col: &ColumnRef) -> HyperLogLog {
+    let col = col.convert_full_column();
+    let mut sketch = HyperLogLog::new();
+    let mut buf = Vec::new();
+    for row in 0..col.len() {
+        if col.null_at(row) {
+            continue;
+        }
+        buf.clear();
+        col.serialize(&mut buf, row);
+        let mut hasher = XxHash64::with_seed(0);
+        hasher.write(&buf);
+        sketch.add_hash(hasher.finish());
+    }
+    sketch
+}
+
 pub mod traverse {
+    use common_datavalues::ArrayColumn;
     use common_datavalues::ColumnRef;
     use common_datavalues::DataTypeImpl;
     use common_datavalues::Series;
@@ -98,6 +148,16 @@ pub mod traverse {
                     traverse_recursive(f, leaves)?
                 }
             }
+            DataTypeImpl::Map(_) => {
+                // a map is stored as the leaf columns of keys and values in parquet,
+                // the min/max of the keys are collected like any other leaf column.
+                let full_column = column.convert_full_column();
+                let map_col: &ArrayColumn = Series::check_get(&full_column)?;
+                let entries: &StructColumn = Series::check_get(map_col.values())?;
+                for f in entries.values() {
+                    traverse_recursive(f, leaves)?
+                }
+            }
             _ => {
                 leaves.push(column.clone());
             }
@@ -20,6 +20,7 @@ pub mod reducers;
 pub use accumulator::StatisticsAccumulator;
 pub use block_statistics::BlockStatistics;
 pub use column_statistic::gen_columns_statistics;
+pub use column_statistic::gen_columns_statistics_with_distinct_values;
 pub use column_statistic::traverse;
 pub use reducers::merge_statistics;
 pub use reducers::reduce_block_statistics;
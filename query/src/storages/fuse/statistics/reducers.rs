@@ -20,6 +20,7 @@ use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
 use common_fuse_meta::meta::ColumnId;
 use common_fuse_meta::meta::ColumnStatistics;
+use common_fuse_meta::meta::HyperLogLog;
 use common_fuse_meta::meta::Statistics;
 use common_fuse_meta::meta::StatisticsOfColumns;
 
@@ -42,14 +43,25 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
     // Reduce the `Vec<&ColumnStatistics` into ColumnStatistics`, i.e.:
     // from : `HashMap<ColumnId, Vec<&ColumnStatistics>)>`
     // to   : `type BlockStatistics = HashMap<ColumnId, ColumnStatistics>`
+    //
+    // the columns missing from some of the (non-empty) parts, e.g. which are added after some
+    // of the blocks are written, are left out, their statistics of the parts are incomplete.
     let len = stats_of_columns.len();
+    let num_parts = stats_of_columns
+        .iter()
+        .filter(|item| !item.borrow().is_empty())
+        .count();
     col_to_stats_lit
         .iter()
+        .filter(|(_, stats)| stats.len() == num_parts)
         .try_fold(HashMap::with_capacity(len), |mut acc, (id, stats)| {
             let mut min_stats = Vec::with_capacity(stats.len());
             let mut max_stats = Vec::with_capacity(stats.len());
             let mut null_count = 0;
             let mut in_memory_size = 0;
+            // the sketch is merged only if all the parts have it,
+            // a partial sketch would underestimate the number of distinct values
+            let mut distinct_values = Some(HyperLogLog::new());
 
             for col_stats in stats {
                 min_stats.push(col_stats.min.clone());
@@ -57,6 +69,13 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
 
                 null_count += col_stats.null_count;
                 in_memory_size += col_stats.in_memory_size;
+                distinct_values = match (distinct_values, &col_stats.distinct_values) {
+                    (Some(mut acc), Some(sketch)) => {
+                        acc.merge(sketch);
+                        Some(acc)
+                    }
+                    _ => None,
+                };
             }
 
             // TODO:
@@ -87,6 +106,7 @@ pub fn reduce_block_statistics<T: Borrow<StatisticsOfColumns>>(
                 max,
                 null_count,
                 in_memory_size,
+                distinct_values,
             });
             Ok(acc)
         })
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::num::Wrapping;
 use std::sync::Arc;
 
@@ -26,8 +27,14 @@ use common_planners::Expression;
 use tracing::info;
 
 use crate::pipelines::processors::transforms::ExpressionExecutor;
+use crate::storages::index::text_index;
+use crate::storages::index::text_index::BloomIndexOptions;
+use crate::storages::index::text_index::SubstringFragment;
+use crate::storages::index::text_index::DEFAULT_NGRAM_SIZE;
 use crate::storages::index::IndexSchemaVersion;
+use crate::storages::index::SetIndex;
 use crate::storages::index::SupportedType;
+use crate::storages::index::SET_INDEX_MAX_NUM_VALUES;
 
 /// BloomFilterExprEvalResult represents the evaluation result of an expression by bloom filter.
 ///
@@ -72,6 +79,9 @@ pub struct BloomFilterIndexer {
     pub bloom_block: DataBlock,
 
     pub ctx: Arc<dyn TableContext>,
+
+    // The size of the grams of the ngram bloom filters
+    pub ngram_size: usize,
 }
 
 // with this setting, about 1.25MB per column, for NDV 1M
@@ -87,6 +97,23 @@ impl BloomFilterIndexer {
     pub fn to_bloom_column_name(column_name: &str) -> String {
         format!("Bloom({})", column_name)
     }
+
+    /// The ngram bloom filter of string column is stored with field name 'Ngram{n}(column_name)',
+    /// e.g. 'Ngram3(name)', so that the grams of different sizes are never mixed up.
+    pub fn to_ngram_column_name(column_name: &str, ngram_size: usize) -> String {
+        format!("Ngram{}({})", ngram_size, column_name)
+    }
+
+    /// The token bloom filter of string column is stored with field name 'Token(column_name)'
+    pub fn to_token_column_name(column_name: &str) -> String {
+        format!("Token({})", column_name)
+    }
+
+    /// The set index of column is stored with field name 'Set(column_name)'
+    pub fn to_set_column_name(column_name: &str) -> String {
+        format!("Set({})", column_name)
+    }
+
     pub fn to_bloom_schema(data_schema: &DataSchema) -> DataSchema {
         let mut bloom_fields = vec![];
         let fields = data_schema.fields();
@@ -120,9 +147,17 @@ impl BloomFilterIndexer {
             bloom_schema: bloom_block.schema().clone(),
             bloom_block,
             ctx,
+            ngram_size: DEFAULT_NGRAM_SIZE,
         })
     }
 
+    /// Set the size of grams, which is used to look up the ngram bloom filters.
+    #[must_use]
+    pub fn with_ngram_size(mut self, ngram_size: usize) -> Self {
+        self.ngram_size = ngram_size;
+        self
+    }
+
     /// Create a bloom filter block from source data.
     ///
     /// All input blocks should be belong to a Parquet file, e.g. the block array represents the parquet file in memory.
@@ -130,9 +165,19 @@ impl BloomFilterIndexer {
     pub fn try_create(
         ctx: Arc<dyn TableContext>,
         source_data_blocks: &[&DataBlock],
+    ) -> Result<Self> {
+        Self::try_create_with_options(ctx, source_data_blocks, &BloomIndexOptions::default())
+    }
+
+    /// Create a bloom filter block from source data, with the ngram and token bloom filters
+    /// of the string columns specified by the options.
+    pub fn try_create_with_options(
+        ctx: Arc<dyn TableContext>,
+        source_data_blocks: &[&DataBlock],
+        options: &BloomIndexOptions,
     ) -> Result<Self> {
         let seed = Self::create_seed();
-        Self::try_create_with_seed(source_data_blocks, seed, ctx)
+        Self::build(source_data_blocks, seed, options, ctx)
     }
 
     /// Create a bloom filter block from source data blocks and seed(s).
@@ -142,6 +187,15 @@ impl BloomFilterIndexer {
         blocks: &[&DataBlock],
         seed: u64,
         ctx: Arc<dyn TableContext>,
+    ) -> Result<Self> {
+        Self::build(blocks, seed, &BloomIndexOptions::default(), ctx)
+    }
+
+    fn build(
+        blocks: &[&DataBlock],
+        seed: u64,
+        options: &BloomIndexOptions,
+        ctx: Arc<dyn TableContext>,
     ) -> Result<Self> {
         if blocks.is_empty() {
             return Err(ErrorCode::BadArguments("data blocks is empty"));
@@ -149,6 +203,9 @@ impl BloomFilterIndexer {
 
         let source_schema = blocks[0].schema().clone();
         let total_num_rows = blocks.iter().map(|block| block.num_rows() as u64).sum();
+        let mut bloom_fields = Self::to_bloom_schema(source_schema.as_ref())
+            .fields()
+            .clone();
         let mut bloom_columns = vec![];
 
         let fields = source_schema.fields();
@@ -168,25 +225,108 @@ impl BloomFilterIndexer {
                     bloom_filter.add(col, ctx.clone())?;
                 }
 
-                // create bloom filter column
-                let serialized_bytes = bloom_filter.to_vec()?;
-                let bloom_value = DataValue::String(serialized_bytes);
-                let bloom_column: ColumnRef =
-                    bloom_value.as_const_column(&StringType::new_impl(), 1)?;
-                bloom_columns.push(bloom_column);
+                bloom_columns.push(Self::to_bloom_column(&bloom_filter)?);
+            }
+        }
+
+        for (i, field) in fields.iter().enumerate() {
+            if !options.set_columns.contains(field.name())
+                || !SetIndex::is_supported_type(field.data_type())
+            {
+                continue;
+            }
+            let columns = blocks.iter().map(|b| b.column(i)).collect::<Vec<_>>();
+            // the set index is omitted if there are too many distinct values
+            if let Some(set_index) =
+                SetIndex::try_create(&columns, seed, SET_INDEX_MAX_NUM_VALUES, ctx.clone())?
+            {
+                let name = Self::to_set_column_name(field.name());
+                bloom_fields.push(DataField::new(&name, Vu8::to_data_type()));
+                let set_value = DataValue::String(set_index.to_vec()?);
+                bloom_columns.push(set_value.as_const_column(&StringType::new_impl(), 1)?);
+            }
+        }
+
+        for (i, field) in fields.iter().enumerate() {
+            if !BloomIndexOptions::is_string_column(field) {
+                continue;
+            }
+            if options.ngram_columns.contains(field.name()) {
+                let n = options.ngram_size;
+                let bloom_filter =
+                    Self::build_substring_bloom_filter(blocks, i, seed, &ctx, |v| {
+                        text_index::ngrams(v, n)
+                    })?;
+                let name = Self::to_ngram_column_name(field.name(), n);
+                bloom_fields.push(DataField::new(&name, Vu8::to_data_type()));
+                bloom_columns.push(Self::to_bloom_column(&bloom_filter)?);
+            }
+            if options.token_columns.contains(field.name()) {
+                let bloom_filter =
+                    Self::build_substring_bloom_filter(blocks, i, seed, &ctx, text_index::tokens)?;
+                let name = Self::to_token_column_name(field.name());
+                bloom_fields.push(DataField::new(&name, Vu8::to_data_type()));
+                bloom_columns.push(Self::to_bloom_column(&bloom_filter)?);
             }
         }
 
-        let bloom_schema = Arc::new(Self::to_bloom_schema(source_schema.as_ref()));
+        let bloom_schema = Arc::new(DataSchema::new(bloom_fields));
         let bloom_block = DataBlock::create(bloom_schema.clone(), bloom_columns);
         Ok(Self {
             source_schema,
             bloom_schema,
             bloom_block,
             ctx,
+            ngram_size: options.ngram_size,
         })
     }
 
+    // create bloom filter column
+    fn to_bloom_column(bloom_filter: &BloomFilter) -> Result<ColumnRef> {
+        let serialized_bytes = bloom_filter.to_vec()?;
+        let bloom_value = DataValue::String(serialized_bytes);
+        bloom_value.as_const_column(&StringType::new_impl(), 1)
+    }
+
+    /// Build a bloom filter of the distinct substrings, which are split by `split`,
+    /// of the string column at `column_index`.
+    fn build_substring_bloom_filter<'a, F, I>(
+        blocks: &'a [&DataBlock],
+        column_index: usize,
+        seed: u64,
+        ctx: &Arc<dyn TableContext>,
+        split: F,
+    ) -> Result<BloomFilter>
+    where
+        F: Fn(&'a [u8]) -> I,
+        I: Iterator<Item = &'a [u8]>,
+    {
+        let mut substrings = HashSet::new();
+        for block in blocks.iter() {
+            let viewer = Vu8::try_create_viewer(block.column(column_index))?;
+            for (row, value) in viewer.iter().enumerate() {
+                if viewer.valid_at(row) {
+                    substrings.extend(split(value));
+                }
+            }
+        }
+
+        // at least one item, so that the bloom filter is never zero sized
+        let num_items = std::cmp::max(substrings.len(), 1) as u64;
+        let mut bloom_filter = BloomFilter::with_rate_and_max_bits(
+            num_items,
+            BLOOM_FILTER_DEFAULT_FALSE_POSITIVE_RATE,
+            BLOOM_FILTER_MAX_NUM_BITS,
+            seed,
+        );
+        if !substrings.is_empty() {
+            let substrings = substrings.into_iter().collect::<Vec<_>>();
+            let column = StringColumn::from_slice(&substrings).arc();
+            bloom_filter.add(&column, ctx.clone())?;
+        }
+        Ok(bloom_filter)
+    }
+
     fn find(
         &self,
         column_name: &str,
@@ -194,23 +334,33 @@ impl BloomFilterIndexer {
         typ: DataTypeImpl,
         ctx: Arc<dyn TableContext>,
     ) -> Result<BloomFilterExprEvalResult> {
-        let bloom_column = Self::to_bloom_column_name(column_name);
-        if !self.bloom_block.schema().has_field(&bloom_column)
-            || !BloomFilter::is_supported_type(&typ)
-            || target.is_null()
-        {
-            // The column doesn't have bloom filter bitmap
+        if !BloomFilter::is_supported_type(&typ) || target.is_null() {
             return Ok(BloomFilterExprEvalResult::NotApplicable);
         }
 
-        let bloom_bytes = self.bloom_block.first(&bloom_column)?.as_string()?;
-        let bloom_filter = BloomFilter::from_vec(bloom_bytes.as_ref())?;
+        let mut result = BloomFilterExprEvalResult::NotApplicable;
 
-        if bloom_filter.find(target, typ, ctx)? {
-            Ok(BloomFilterExprEvalResult::Unknown)
-        } else {
-            Ok(BloomFilterExprEvalResult::False)
+        let set_column = Self::to_set_column_name(column_name);
+        if self.bloom_block.schema().has_field(&set_column) {
+            let set_bytes = self.bloom_block.first(&set_column)?.as_string()?;
+            let set_index = SetIndex::from_vec(set_bytes.as_ref())?;
+            if !set_index.find(target.clone(), typ.clone(), ctx.clone())? {
+                return Ok(BloomFilterExprEvalResult::False);
+            }
+            result = BloomFilterExprEvalResult::Unknown;
+        }
+
+        let bloom_column = Self::to_bloom_column_name(column_name);
+        if self.bloom_block.schema().has_field(&bloom_column) {
+            let bloom_bytes = self.bloom_block.first(&bloom_column)?.as_string()?;
+            let bloom_filter = BloomFilter::from_vec(bloom_bytes.as_ref())?;
+            if !bloom_filter.find(target, typ, ctx)? {
+                return Ok(BloomFilterExprEvalResult::False);
+            }
+            result = BloomFilterExprEvalResult::Unknown;
         }
+
+        Ok(result)
     }
 
     /// Returns false when the expression must be false, otherwise true.
@@ -232,14 +382,185 @@ impl BloomFilterIndexer {
         match expr {
             Expression::BinaryExpression { left, op, right } => match op.to_lowercase().as_str() {
                 "=" => self.eval_equivalent_expression(left, right),
+                "like" => self.eval_like_expression(left, right),
                 "and" => self.eval_logical_and(left, right),
                 "or" => self.eval_logical_or(left, right),
+                ">" | ">=" | "<" | "<=" | "!=" | "<>" => {
+                    self.eval_position_comparison(left, op, right)
+                }
                 _ => Ok(BloomFilterExprEvalResult::NotApplicable),
             },
+            Expression::ScalarFunction { op, args } => self.eval_scalar_function(op, args),
+            _ => Ok(BloomFilterExprEvalResult::NotApplicable),
+        }
+    }
+
+    // Evaluate the like expression like "name like '%Alice%'"
+    fn eval_like_expression(
+        &self,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<BloomFilterExprEvalResult> {
+        match (left, right) {
+            (
+                Expression::Column(column),
+                Expression::Literal {
+                    value: DataValue::String(pattern),
+                    ..
+                },
+            ) => self.find_substrings(column, &text_index::like_pattern_fragments(pattern)),
             _ => Ok(BloomFilterExprEvalResult::NotApplicable),
         }
     }
 
+    // Evaluate the comparison of the substring position like "position('Alice' in name) > 0",
+    // the position is 0 if and only if the substring is not found.
+    fn eval_position_comparison(
+        &self,
+        left: &Expression,
+        op: &str,
+        right: &Expression,
+    ) -> Result<BloomFilterExprEvalResult> {
+        let (function, op, value) = match (left, right) {
+            (Expression::ScalarFunction { .. }, Expression::Literal { value, .. }) => {
+                (left, op, value)
+            }
+            (Expression::Literal { value, .. }, Expression::ScalarFunction { .. }) => {
+                let flipped = match op {
+                    ">" => "<",
+                    ">=" => "<=",
+                    "<" => ">",
+                    "<=" => ">=",
+                    _ => op,
+                };
+                (right, flipped, value)
+            }
+            _ => return Ok(BloomFilterExprEvalResult::NotApplicable),
+        };
+
+        // the comparison must be false if the substring is not found
+        let requires_found = match (op, value.as_i64()) {
+            (">", Ok(v)) => v >= 0,
+            (">=", Ok(v)) => v >= 1,
+            ("!=" | "<>", Ok(v)) => v == 0,
+            _ => false,
+        };
+        if !requires_found {
+            return Ok(BloomFilterExprEvalResult::NotApplicable);
+        }
+
+        match function {
+            Expression::ScalarFunction { op, args } => {
+                match Self::substring_of_position_function(op, args) {
+                    Some((column, fragment)) => self.find_substrings(column, &[fragment]),
+                    None => Ok(BloomFilterExprEvalResult::NotApplicable),
+                }
+            }
+            _ => Ok(BloomFilterExprEvalResult::NotApplicable),
+        }
+    }
+
+    // Returns the column and the literal substring of "position(substr in str)",
+    // "locate(substr, str[, pos])" and "instr(str, substr)"
+    fn substring_of_position_function<'a>(
+        op: &str,
+        args: &'a [Expression],
+    ) -> Option<(&'a str, SubstringFragment)> {
+        let (substr, str) = match (op.to_lowercase().as_str(), args) {
+            ("position" | "locate", [substr, str, ..]) => (substr, str),
+            ("instr", [str, substr]) => (substr, str),
+            _ => return None,
+        };
+        match (substr, str) {
+            (
+                Expression::Literal {
+                    value: DataValue::String(substr),
+                    ..
+                },
+                Expression::Column(column),
+            ) => Some((column.as_str(), SubstringFragment::new(substr.clone()))),
+            _ => None,
+        }
+    }
+
+    // Evaluate the functions that are true only if the value contains some literal substrings,
+    // like "regexp_like(name, '^Ali', 'c')".
+    //
+    // The default match type of regexp_like is case insensitive, which the substring bloom filters
+    // do not support.
+    fn eval_scalar_function(
+        &self,
+        op: &str,
+        args: &[Expression],
+    ) -> Result<BloomFilterExprEvalResult> {
+        match (op.to_lowercase().as_str(), args) {
+            (
+                "regexp_like",
+                [
+                    Expression::Column(column),
+                    Expression::Literal {
+                        value: DataValue::String(pattern),
+                        ..
+                    },
+                    Expression::Literal {
+                        value: DataValue::String(match_type),
+                        ..
+                    },
+                ],
+            ) if text_index::is_case_sensitive_match_type(match_type) => {
+                self.find_substrings(column, &text_index::regexp_pattern_fragments(pattern))
+            }
+            _ => Ok(BloomFilterExprEvalResult::NotApplicable),
+        }
+    }
+
+    /// Returns False if any gram or token of the fragments is missing from the ngram or token
+    /// bloom filters of the column, which means none of the values contains all the fragments.
+    fn find_substrings(
+        &self,
+        column: &str,
+        fragments: &[SubstringFragment],
+    ) -> Result<BloomFilterExprEvalResult> {
+        let mut result = BloomFilterExprEvalResult::NotApplicable;
+        let typ = Vu8::to_data_type();
+
+        let ngram_column = Self::to_ngram_column_name(column, self.ngram_size);
+        if let Some(bloom_filter) = self.try_get_bloom_by_index_column(&ngram_column)? {
+            for fragment in fragments {
+                for gram in text_index::ngrams(&fragment.bytes, self.ngram_size) {
+                    let target = DataValue::String(gram.to_vec());
+                    if !bloom_filter.find(target, typ.clone(), self.ctx.clone())? {
+                        return Ok(BloomFilterExprEvalResult::False);
+                    }
+                    result = BloomFilterExprEvalResult::Unknown;
+                }
+            }
+        }
+
+        let token_column = Self::to_token_column_name(column);
+        if let Some(bloom_filter) = self.try_get_bloom_by_index_column(&token_column)? {
+            for fragment in fragments {
+                for token in fragment.complete_tokens() {
+                    let target = DataValue::String(token.to_vec());
+                    if !bloom_filter.find(target, typ.clone(), self.ctx.clone())? {
+                        return Ok(BloomFilterExprEvalResult::False);
+                    }
+                    result = BloomFilterExprEvalResult::Unknown;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn try_get_bloom_by_index_column(&self, index_column: &str) -> Result<Option<BloomFilter>> {
+        if !self.bloom_block.schema().has_field(index_column) {
+            return Ok(None);
+        }
+        let bloom_bytes = self.bloom_block.first(index_column)?.as_string()?;
+        Ok(Some(BloomFilter::from_vec(bloom_bytes.as_ref())?))
+    }
+
     // Evaluate the equivalent expression like "name='Alice'"
     fn eval_equivalent_expression(
         &self,
@@ -475,7 +796,7 @@ impl BloomFilter {
         seed2.0
     }
 
-    fn compute_column_city_hash(
+    pub(crate) fn compute_column_city_hash(
         seed: u64,
         column: &ColumnRef,
         ctx: Arc<dyn TableContext>,
@@ -578,6 +899,49 @@ impl BloomFilter {
         Ok(())
     }
 
+    /// Check the existence of every value of the column, Nulls are never found.
+    ///
+    /// The column should be of the same type as the data added into the bloom filter,
+    /// regardless of the nullability.
+    pub fn find_column(&self, column: &ColumnRef, ctx: Arc<dyn TableContext>) -> Result<Vec<bool>> {
+        if !Self::is_supported_type(&column.data_type()) {
+            return Err(ErrorCode::BadArguments(format!(
+                "Unsupported data type: {} ",
+                column.data_type_id()
+            )));
+        }
+
+        let (is_all_null, validity) = column.validity();
+        if is_all_null {
+            return Ok(vec![false; column.len()]);
+        }
+
+        let (hash1_column, hash2_column) = self.compute_column_double_hashes(column, ctx)?;
+        let hash1_column = Series::remove_nullable(&hash1_column);
+        let column1: &UInt64Column = Series::check_get(&hash1_column)?;
+        let hash2_column = Series::remove_nullable(&hash2_column);
+        let column2: &UInt64Column = Series::check_get(&hash2_column)?;
+
+        let mut found = column1
+            .iter()
+            .zip(column2.iter())
+            .map(|(h1, h2)| {
+                let h1 = Wrapping(*h1);
+                let h2 = Wrapping(*h2);
+                (0..self.num_hashes).all(|i| {
+                    let bit_pos = self.compute_hash_bit_pos(i, h1, h2);
+                    self.container.get(bit_pos).unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        if let Some(validity) = validity {
+            for (found, valid) in found.iter_mut().zip(validity.iter()) {
+                *found &= valid;
+            }
+        }
+        Ok(found)
+    }
+
     fn compute_data_value_double_hashes(
         &self,
         data_value: DataValue,
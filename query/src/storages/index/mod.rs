@@ -15,6 +15,8 @@
 mod bloom_filter;
 mod index_min_max;
 pub mod range_filter;
+mod set_index;
+pub mod text_index;
 pub use bloom_filter::BloomFilter;
 pub use bloom_filter::BloomFilterExprEvalResult;
 pub use bloom_filter::BloomFilterIndexer;
@@ -25,6 +27,9 @@ use common_datavalues::NullableType;
 pub use index_min_max::MinMaxIndex;
 pub use range_filter::ClusterKeyInfo;
 pub use range_filter::RangeFilter;
+pub use set_index::SetIndex;
+pub use set_index::SET_INDEX_MAX_NUM_VALUES;
+pub use text_index::BloomIndexOptions;
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum IndexSchemaVersion {
@@ -84,6 +84,22 @@ impl RangeFilter {
         })
     }
 
+    /// Keys the statistics of the columns by `column_ids`, the ids of the fields of the schema,
+    /// rather than the positions of the fields.
+    #[must_use]
+    pub fn with_column_ids(mut self, column_ids: &[u32]) -> Self {
+        for stat_column in &mut self.stat_columns {
+            stat_column.column_fields = std::mem::take(&mut stat_column.column_fields)
+                .into_iter()
+                .map(|(idx, field)| {
+                    let id = column_ids.get(idx as usize).copied().unwrap_or(idx);
+                    (id, field)
+                })
+                .collect();
+        }
+        self
+    }
+
     #[tracing::instrument(level = "debug", name = "range_filter_eval", skip_all)]
     pub fn eval(&self, stats: &StatisticsOfColumns) -> Result<bool> {
         let mut columns = Vec::with_capacity(self.stat_columns.len());
@@ -212,24 +228,21 @@ impl StatColumn {
         if self.stat_type == StatType::Nulls {
             // The len of column_fields is 1.
             let (k, _) = self.column_fields.iter().next().unwrap();
-            let stat = stats.get(k).ok_or_else(|| {
-                ErrorCode::UnknownException(format!(
-                    "Unable to get the colStats by ColumnId: {}",
-                    k
-                ))
-            })?;
+            // the column is added after the block is written
+            let stat = match stats.get(k) {
+                Some(stat) => stat,
+                None => return Ok(None),
+            };
             return Ok(Some(Series::from_data(vec![stat.null_count])));
         }
 
         let mut single_point = true;
         let mut variables = HashMap::with_capacity(self.column_fields.len());
         for (k, v) in &self.column_fields {
-            let stat = stats.get(k).ok_or_else(|| {
-                ErrorCode::UnknownException(format!(
-                    "Unable to get the colStats by ColumnId: {}",
-                    k
-                ))
-            })?;
+            let stat = match stats.get(k) {
+                Some(stat) => stat,
+                None => return Ok(None),
+            };
 
             if single_point && stat.min != stat.max {
                 single_point = false;
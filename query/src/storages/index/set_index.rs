@@ -0,0 +1,147 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::storages::index::BloomFilter;
+use crate::storages::index::SupportedType;
+
+/// The max number of the distinct values of a column that a set index keeps for a block.
+///
+/// Blocks having more distinct values are not indexed, since the set index would be
+/// as large as the column itself.
+pub const SET_INDEX_MAX_NUM_VALUES: usize = 8192;
+
+/// A set index keeps the hashes of the distinct values of a column in a block.
+///
+/// Unlike the bloom filter, the false positive of the set index only comes from the
+/// collisions of the 64 bits hashes, which makes it a good choice for the columns of
+/// low cardinality. The values are hashed in the same way as the bloom filter.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct SetIndex {
+    seed: u64,
+    // sorted hashes of the distinct values
+    hashes: Vec<u64>,
+}
+
+impl SetIndex {
+    /// Builds the set index of the columns, which are the same column of the blocks
+    /// of a data file. Nulls are skipped like the bloom filter.
+    ///
+    /// Returns None if the number of distinct values exceeds `max_num_values`.
+    pub fn try_create(
+        columns: &[&ColumnRef],
+        seed: u64,
+        max_num_values: usize,
+        ctx: Arc<dyn TableContext>,
+    ) -> Result<Option<Self>> {
+        let mut hashes = BTreeSet::new();
+        for column in columns {
+            if !Self::is_supported_type(&column.data_type()) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Unsupported data type: {} ",
+                    column.data_type_id()
+                )));
+            }
+
+            let (is_all_null, validity) = column.validity();
+            if is_all_null {
+                continue;
+            }
+
+            let hash_column = BloomFilter::compute_column_city_hash(seed, column, ctx.clone())?;
+            let hash_column = Series::remove_nullable(&hash_column);
+            let hash_column: &UInt64Column = Series::check_get(&hash_column)?;
+            match validity {
+                Some(bitmap) if column.is_nullable() => {
+                    for (valid, h) in bitmap.into_iter().zip(hash_column.iter()) {
+                        if valid {
+                            hashes.insert(*h);
+                        }
+                    }
+                }
+                _ => hashes.extend(hash_column.iter()),
+            }
+
+            if hashes.len() > max_num_values {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(Self {
+            seed,
+            hashes: hashes.into_iter().collect(),
+        }))
+    }
+
+    pub fn num_values(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Checks the existence of the value, which should not be Null.
+    ///
+    /// Returns false only if the value never shows up in the column.
+    pub fn find(
+        &self,
+        val: DataValue,
+        typ: DataTypeImpl,
+        ctx: Arc<dyn TableContext>,
+    ) -> Result<bool> {
+        if !Self::is_supported_type(&typ) {
+            return Err(ErrorCode::BadArguments(format!(
+                "Unsupported data type: {:?} ",
+                typ
+            )));
+        }
+
+        if val.is_null() {
+            return Err(ErrorCode::BadArguments("Null value is not supported"));
+        }
+
+        let column = val.as_const_column(&typ, 1)?;
+        let hash_column = BloomFilter::compute_column_city_hash(self.seed, &column, ctx)?;
+        let hash = hash_column.get_u64(0)?;
+        Ok(self.hashes.binary_search(&hash).is_ok())
+    }
+
+    /// Serialize the set index to byte vector.
+    pub fn to_vec(&self) -> Result<Vec<u8>> {
+        match bincode::serde::encode_to_vec(self, bincode::config::standard()) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(ErrorCode::StorageOther(format!(
+                "bincode serialization error: {} ",
+                e
+            ))),
+        }
+    }
+
+    /// Deserialize from a byte slice and return a set index.
+    pub fn from_vec(bytes: &[u8]) -> Result<Self> {
+        match bincode::serde::decode_from_slice(bytes, bincode::config::standard()) {
+            Ok((set_index, _)) => Ok(set_index),
+            Err(e) => Err(ErrorCode::StorageOther(format!(
+                "bincode deserialization error: {} ",
+                e
+            ))),
+        }
+    }
+}
+
+impl SupportedType for SetIndex {}
@@ -0,0 +1,387 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_meta_app::schema::TableIndex;
+use common_meta_app::schema::TableIndexType;
+
+use crate::storages::index::remove_nullable;
+
+/// Table option, the comma separated string columns to build ngram bloom filters for.
+pub const OPT_KEY_NGRAM_BLOOM_INDEX_COLUMNS: &str = "ngram_bloom_index_columns";
+/// Table option, the number of bytes of the grams, 3 by default.
+pub const OPT_KEY_NGRAM_BLOOM_INDEX_SIZE: &str = "ngram_bloom_index_size";
+/// Table option, the comma separated string columns to build token bloom filters for.
+pub const OPT_KEY_TOKEN_BLOOM_INDEX_COLUMNS: &str = "token_bloom_index_columns";
+
+pub const DEFAULT_NGRAM_SIZE: usize = 3;
+const MAX_NGRAM_SIZE: usize = 16;
+
+/// The options of the substring bloom filters and set indexes of a table, which are built
+/// along with the per-column bloom filters and stored in the same index file.
+///
+/// - ngram bloom filter, contains all the `ngram_size` bytes long substrings of the values.
+/// - token bloom filter, contains all the whitespace separated words of the values.
+/// - set index, contains the distinct values, see [`crate::storages::index::SetIndex`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BloomIndexOptions {
+    pub ngram_size: usize,
+    pub ngram_columns: BTreeSet<String>,
+    pub token_columns: BTreeSet<String>,
+    pub set_columns: BTreeSet<String>,
+}
+
+impl Default for BloomIndexOptions {
+    fn default() -> Self {
+        Self {
+            ngram_size: DEFAULT_NGRAM_SIZE,
+            ngram_columns: BTreeSet::new(),
+            token_columns: BTreeSet::new(),
+            set_columns: BTreeSet::new(),
+        }
+    }
+}
+
+impl BloomIndexOptions {
+    pub fn from_table_options(options: &BTreeMap<String, String>) -> Result<Self> {
+        let ngram_size = match options.get(OPT_KEY_NGRAM_BLOOM_INDEX_SIZE) {
+            None => DEFAULT_NGRAM_SIZE,
+            Some(v) => match v.trim().parse::<usize>() {
+                Ok(size) if (1..=MAX_NGRAM_SIZE).contains(&size) => size,
+                _ => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Invalid value of table option {}: {}, expect a number between 1 and {}",
+                        OPT_KEY_NGRAM_BLOOM_INDEX_SIZE, v, MAX_NGRAM_SIZE
+                    )));
+                }
+            },
+        };
+
+        Ok(Self {
+            ngram_size,
+            ngram_columns: Self::parse_columns(options.get(OPT_KEY_NGRAM_BLOOM_INDEX_COLUMNS)),
+            token_columns: Self::parse_columns(options.get(OPT_KEY_TOKEN_BLOOM_INDEX_COLUMNS)),
+            set_columns: BTreeSet::new(),
+        })
+    }
+
+    /// Adds the columns of the ngram and set indexes defined by `CREATE INDEX`.
+    ///
+    /// The bloom and minmax indexes are always built for the eligible columns, nothing to add.
+    #[must_use]
+    pub fn with_table_indexes<'a>(
+        mut self,
+        indexes: impl IntoIterator<Item = &'a TableIndex>,
+    ) -> Self {
+        for index in indexes {
+            match index.index_type {
+                TableIndexType::Ngram => {
+                    self.ngram_columns.insert(index.column.clone());
+                }
+                TableIndexType::Set => {
+                    self.set_columns.insert(index.column.clone());
+                }
+                TableIndexType::Bloom | TableIndexType::MinMax => {}
+            }
+        }
+        self
+    }
+
+    fn parse_columns(value: Option<&String>) -> BTreeSet<String> {
+        value
+            .map(|v| {
+                v.split(',')
+                    .map(|c| c.trim())
+                    .filter(|c| !c.is_empty())
+                    .map(|c| c.to_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Checks that the indexed columns exist in the schema, and are of string type.
+    pub fn check_schema(&self, schema: &DataSchema) -> Result<()> {
+        for (key, column) in self
+            .ngram_columns
+            .iter()
+            .map(|c| (OPT_KEY_NGRAM_BLOOM_INDEX_COLUMNS, c))
+            .chain(
+                self.token_columns
+                    .iter()
+                    .map(|c| (OPT_KEY_TOKEN_BLOOM_INDEX_COLUMNS, c)),
+            )
+        {
+            match schema.column_with_name(column) {
+                Some((_, field)) if Self::is_string_column(field) => {}
+                Some(_) => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Invalid value of table option {}: column {} is not of string type",
+                        key, column
+                    )));
+                }
+                None => {
+                    return Err(ErrorCode::BadOption(format!(
+                        "Invalid value of table option {}: column {} not found",
+                        key, column
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_string_column(field: &DataField) -> bool {
+        remove_nullable(field.data_type()).data_type_id() == TypeID::String
+    }
+}
+
+/// Returns the `n` bytes long grams of the value.
+///
+/// Values shorter than `n` have no grams, neither could they contain a substring
+/// which is at least `n` bytes long.
+pub fn ngrams(value: &[u8], n: usize) -> impl Iterator<Item = &[u8]> {
+    value.windows(n)
+}
+
+/// Returns the non-empty tokens of the value, separated by ASCII whitespaces.
+pub fn tokens(value: &[u8]) -> impl Iterator<Item = &[u8]> {
+    value
+        .split(|b| b.is_ascii_whitespace())
+        .filter(|t| !t.is_empty())
+}
+
+/// A literal substring that must be contained by the values matching a predicate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubstringFragment {
+    pub bytes: Vec<u8>,
+    // the fragment is the prefix of the matched values
+    pub at_start: bool,
+    // the fragment is the suffix of the matched values
+    pub at_end: bool,
+}
+
+impl SubstringFragment {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self {
+            bytes,
+            at_start: false,
+            at_end: false,
+        }
+    }
+
+    /// Returns the tokens of the fragment, which must also be tokens of the matched values.
+    ///
+    /// e.g. for the fragment of `'%connection refused by%'`, only `refused` is a token for sure,
+    /// the value `'reconnection refused bypass'` also matches the pattern.
+    pub fn complete_tokens(&self) -> Vec<&[u8]> {
+        let mut result = vec![];
+        let mut offset = 0;
+        for piece in self.bytes.split(|b| b.is_ascii_whitespace()) {
+            let start = offset;
+            let end = start + piece.len();
+            offset = end + 1;
+            if piece.is_empty() {
+                continue;
+            }
+            let left_complete = start > 0 || self.at_start;
+            let right_complete = end < self.bytes.len() || self.at_end;
+            if left_complete && right_complete {
+                result.push(piece);
+            }
+        }
+        result
+    }
+}
+
+fn is_like_pattern_escape(c: u8) -> bool {
+    c == b'%' || c == b'_' || c == b'\\'
+}
+
+/// Extracts the literal fragments of a LIKE pattern, escapes are handled in the same way
+/// as [`crate::storages::index::range_filter::left_bound_for_like_pattern`].
+pub fn like_pattern_fragments(pattern: &[u8]) -> Vec<SubstringFragment> {
+    let mut fragments = vec![];
+    let mut current = vec![];
+    let mut at_start = true;
+    let mut index = 0;
+    let len = pattern.len();
+    while index < len {
+        match pattern[index] {
+            b'%' | b'_' => {
+                if !current.is_empty() {
+                    fragments.push(SubstringFragment {
+                        bytes: std::mem::take(&mut current),
+                        at_start,
+                        at_end: false,
+                    });
+                }
+                at_start = false;
+                index += 1;
+                continue;
+            }
+            b'\\' => {
+                if index < len - 1 {
+                    index += 1;
+                    if !is_like_pattern_escape(pattern[index]) {
+                        current.push(pattern[index - 1]);
+                    }
+                }
+            }
+            _ => {}
+        }
+        current.push(pattern[index]);
+        index += 1;
+    }
+    if !current.is_empty() {
+        fragments.push(SubstringFragment {
+            bytes: current,
+            at_start,
+            at_end: true,
+        });
+    }
+    fragments
+}
+
+/// Returns true if the regexp match type of `regexp_like` is case sensitive.
+///
+/// The match type is case insensitive by default, the last one of 'c' and 'i' wins.
+pub fn is_case_sensitive_match_type(match_type: &[u8]) -> bool {
+    match match_type.iter().rev().find(|c| **c == b'c' || **c == b'i') {
+        Some(c) => *c == b'c',
+        None => false,
+    }
+}
+
+/// Extracts the literal fragments that the values matching the regular expression must contain.
+///
+/// It is a conservative extraction: patterns with alternations or inline flags have no
+/// fragments, the contents of groups and character classes are skipped, and the literal
+/// made optional by a quantifier is dropped.
+pub fn regexp_pattern_fragments(pattern: &[u8]) -> Vec<SubstringFragment> {
+    if pattern.is_empty() || pattern.contains(&b'|') || pattern.windows(2).any(|w| w == b"(?") {
+        return vec![];
+    }
+
+    let mut fragments = vec![];
+    let mut current = vec![];
+    let mut at_start = false;
+    let mut index = 0;
+    let len = pattern.len();
+    if pattern[0] == b'^' {
+        at_start = true;
+        index = 1;
+    }
+
+    let flush = |fragments: &mut Vec<SubstringFragment>,
+                 current: &mut Vec<u8>,
+                 at_start: &mut bool,
+                 at_end: bool| {
+        if !current.is_empty() {
+            fragments.push(SubstringFragment {
+                bytes: std::mem::take(current),
+                at_start: *at_start,
+                at_end,
+            });
+        }
+        *at_start = false;
+    };
+
+    while index < len {
+        let c = pattern[index];
+        match c {
+            b'\\' => {
+                // escaped characters might be classes like `\d`, skip them
+                flush(&mut fragments, &mut current, &mut at_start, false);
+                index += 2;
+                continue;
+            }
+            b'(' | b'[' | b'{' => {
+                if c == b'{' {
+                    // the quantifier makes the previous character optional
+                    pop_last_char(&mut current);
+                }
+                flush(&mut fragments, &mut current, &mut at_start, false);
+                index = skip_enclosed(pattern, index);
+                continue;
+            }
+            b'?' | b'*' => {
+                pop_last_char(&mut current);
+                flush(&mut fragments, &mut current, &mut at_start, false);
+            }
+            b'$' if index == len - 1 => {
+                flush(&mut fragments, &mut current, &mut at_start, true);
+            }
+            b'+' | b'.' | b'^' | b'$' | b')' | b']' | b'}' => {
+                flush(&mut fragments, &mut current, &mut at_start, false);
+            }
+            _ => current.push(c),
+        }
+        index += 1;
+    }
+    flush(&mut fragments, &mut current, &mut at_start, false);
+    fragments
+}
+
+// pops the last (maybe multi-bytes) utf-8 character
+fn pop_last_char(bytes: &mut Vec<u8>) {
+    while let Some(b) = bytes.pop() {
+        if b & 0xC0 != 0x80 {
+            break;
+        }
+    }
+}
+
+// returns the index next to the end of the group, class or quantifier starting at `start`
+fn skip_enclosed(pattern: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut class_depth = 0;
+    let mut index = start;
+    while index < pattern.len() {
+        match pattern[index] {
+            b'\\' => index += 1,
+            b'[' => {
+                class_depth += 1;
+                // a leading `]` of the class is a literal
+                if pattern.get(index + 1) == Some(&b'^') {
+                    index += 1;
+                }
+                if pattern.get(index + 1) == Some(&b']') {
+                    index += 1;
+                }
+            }
+            b']' if class_depth > 0 => {
+                class_depth -= 1;
+                if class_depth == 0 && depth == 0 {
+                    return index + 1;
+                }
+            }
+            _ if class_depth > 0 => {}
+            b'(' | b'{' => depth += 1,
+            b')' | b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return index + 1;
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    pattern.len()
+}
@@ -68,6 +68,20 @@ impl MemoryTable {
         Ok(Box::new(table))
     }
 
+    /// Create a memory table holding the given blocks, which is not registered in any catalog.
+    /// It's used to hold the intermediate results of a query, e.g. recursive CTE.
+    pub fn create_with_blocks(table_info: TableInfo, blocks: Vec<DataBlock>) -> Self {
+        Self {
+            table_info,
+            blocks: Arc::new(RwLock::new(blocks)),
+        }
+    }
+
+    pub fn replace_blocks(&self, new_blocks: Vec<DataBlock>) {
+        let mut blocks = self.blocks.write();
+        *blocks = new_blocks;
+    }
+
     pub fn description() -> StorageDescription {
         StorageDescription {
             engine_name: "MEMORY".to_string(),
@@ -34,6 +34,7 @@ pub mod view;
 
 pub use common_catalog::table::NavigationPoint;
 pub use common_catalog::table::TableStatistics;
+pub use common_catalog::table::VacuumStatistics;
 pub use storage_context::StorageContext;
 pub use storage_factory::StorageCreator;
 pub use storage_factory::StorageDescription;
@@ -0,0 +1,120 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::sessions::TableContext;
+use crate::storages::system::table::SyncOneBlockSystemTable;
+use crate::storages::system::table::SyncSystemTable;
+use crate::storages::Table;
+
+pub struct BackgroundTasksTable {
+    table_info: TableInfo,
+}
+
+impl SyncSystemTable for BackgroundTasksTable {
+    const NAME: &'static str = "system.background_tasks";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tasks_info = ctx.get_background_tasks_info();
+
+        let mut ids = Vec::with_capacity(tasks_info.len());
+        let mut types = Vec::with_capacity(tasks_info.len());
+        let mut states = Vec::with_capacity(tasks_info.len());
+        let mut databases = Vec::with_capacity(tasks_info.len());
+        let mut tables = Vec::with_capacity(tasks_info.len());
+        let mut reasons = Vec::with_capacity(tasks_info.len());
+        let mut nodes = Vec::with_capacity(tasks_info.len());
+        let mut created_ons = Vec::with_capacity(tasks_info.len());
+        let mut finished_ons = Vec::with_capacity(tasks_info.len());
+        let mut errors = Vec::with_capacity(tasks_info.len());
+
+        for task_info in &tasks_info {
+            ids.push(task_info.id);
+            types.push(task_info.typ.clone().into_bytes());
+            states.push(task_info.state.clone().into_bytes());
+            databases.push(task_info.database.clone().into_bytes());
+            tables.push(task_info.table.clone().into_bytes());
+            reasons.push(task_info.reason.clone().into_bytes());
+            nodes.push(task_info.node.clone().into_bytes());
+            created_ons.push(
+                task_info
+                    .created_on
+                    .format("%Y-%m-%d %H:%M:%S.%3f %z")
+                    .to_string()
+                    .into_bytes(),
+            );
+            finished_ons.push(task_info.finished_on.map(|finished_on| {
+                finished_on
+                    .format("%Y-%m-%d %H:%M:%S.%3f %z")
+                    .to_string()
+                    .into_bytes()
+            }));
+            errors.push(task_info.error.clone().map(|error| error.into_bytes()));
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(ids),
+            Series::from_data(types),
+            Series::from_data(states),
+            Series::from_data(databases),
+            Series::from_data(tables),
+            Series::from_data(reasons),
+            Series::from_data(nodes),
+            Series::from_data(created_ons),
+            Series::from_data(finished_ons),
+            Series::from_data(errors),
+        ]))
+    }
+}
+
+impl BackgroundTasksTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("id", u64::to_data_type()),
+            DataField::new("type", Vu8::to_data_type()),
+            DataField::new("state", Vu8::to_data_type()),
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("table", Vu8::to_data_type()),
+            DataField::new("reason", Vu8::to_data_type()),
+            DataField::new("node", Vu8::to_data_type()),
+            DataField::new("created_on", Vu8::to_data_type()),
+            DataField::new_nullable("finished_on", Vu8::to_data_type()),
+            DataField::new_nullable("error", Vu8::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'background_tasks'".to_string(),
+            name: "background_tasks".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemBgTasks".to_string(),
+                ..Default::default()
+            },
+        };
+
+        SyncOneBlockSystemTable::create(BackgroundTasksTable { table_info })
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::TableMeta;
+
+use crate::catalogs::CATALOG_DEFAULT;
+use crate::sessions::TableContext;
+use crate::storages::system::table::AsyncOneBlockSystemTable;
+use crate::storages::system::table::AsyncSystemTable;
+use crate::storages::Table;
+
+/// Shows the column statistics of tables, which are seen by the planner.
+pub struct ColumnStatisticsTable {
+    table_info: TableInfo,
+}
+
+#[async_trait::async_trait]
+impl AsyncSystemTable for ColumnStatisticsTable {
+    const NAME: &'static str = "system.column_statistics";
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.table_info
+    }
+
+    async fn get_full_data(&self, ctx: Arc<dyn TableContext>) -> Result<DataBlock> {
+        let tenant = ctx.get_tenant();
+        let catalog = ctx.get_catalog(CATALOG_DEFAULT)?;
+        let databases = catalog.list_databases(tenant.as_str()).await?;
+
+        let mut database_names: Vec<Vec<u8>> = vec![];
+        let mut table_names: Vec<Vec<u8>> = vec![];
+        let mut column_names: Vec<Vec<u8>> = vec![];
+        let mut mins: Vec<Vec<u8>> = vec![];
+        let mut maxs: Vec<Vec<u8>> = vec![];
+        let mut null_counts: Vec<u64> = vec![];
+        let mut distinct_values: Vec<Option<u64>> = vec![];
+        for database in databases {
+            for table in catalog
+                .list_tables(tenant.as_str(), database.name())
+                .await?
+            {
+                let col_stats = match table.column_statistics(ctx.clone()).await? {
+                    Some(col_stats) => col_stats,
+                    None => continue,
+                };
+
+                // the statistics are keyed by the index of field
+                for (idx, field) in table.schema().fields().iter().enumerate() {
+                    if let Some(stats) = col_stats.get(&(idx as u32)) {
+                        database_names.push(database.name().as_bytes().to_vec());
+                        table_names.push(table.name().as_bytes().to_vec());
+                        column_names.push(field.name().as_bytes().to_vec());
+                        mins.push(stats.min.to_string().into_bytes());
+                        maxs.push(stats.max.to_string().into_bytes());
+                        null_counts.push(stats.null_count);
+                        distinct_values.push(stats.number_of_distinct_values());
+                    }
+                }
+            }
+        }
+
+        Ok(DataBlock::create(self.table_info.schema(), vec![
+            Series::from_data(database_names),
+            Series::from_data(table_names),
+            Series::from_data(column_names),
+            Series::from_data(mins),
+            Series::from_data(maxs),
+            Series::from_data(null_counts),
+            Series::from_data(distinct_values),
+        ]))
+    }
+}
+
+impl ColumnStatisticsTable {
+    pub fn create(table_id: u64) -> Arc<dyn Table> {
+        let schema = DataSchemaRefExt::create(vec![
+            DataField::new("database", Vu8::to_data_type()),
+            DataField::new("table", Vu8::to_data_type()),
+            DataField::new("column", Vu8::to_data_type()),
+            DataField::new("min", Vu8::to_data_type()),
+            DataField::new("max", Vu8::to_data_type()),
+            DataField::new("null_count", u64::to_data_type()),
+            DataField::new_nullable("number_of_distinct_values", u64::to_data_type()),
+        ]);
+
+        let table_info = TableInfo {
+            desc: "'system'.'column_statistics'".to_string(),
+            name: "column_statistics".to_string(),
+            ident: TableIdent::new(table_id, 0),
+            meta: TableMeta {
+                schema,
+                engine: "SystemColumnStats".to_string(),
+                ..Default::default()
+            },
+        };
+
+        AsyncOneBlockSystemTable::create(ColumnStatisticsTable { table_info })
+    }
+}
@@ -66,11 +66,21 @@ table_cache_block_meta_count = 102400
 table_memory_cache_mb_size = 256
 table_disk_cache_root = "_cache"
 table_disk_cache_mb_size = 1024
+spill_root = "_spill"
+query_result_cache_memory_mb_size = 128
+query_result_cache_disk_root = "_result_cache"
+query_result_cache_disk_mb_size = 1024
 management_mode = false
 jwt_key_file = ""
 async_insert_max_data_size = 10000
 async_insert_busy_timeout = 200
 async_insert_stale_timeout = 0
+background_tasks_enabled = false
+background_tasks_interval_secs = 60
+background_tasks_max_concurrency = 2
+background_compact_segment_threshold = 100
+background_compact_small_block_ratio = 30
+background_recluster_depth_threshold = 8
 
 [log]
 level = "INFO"
@@ -347,6 +357,10 @@ table_cache_block_meta_count = 102400
 table_memory_cache_mb_size = 256
 table_disk_cache_root = "_cache"
 table_disk_cache_mb_size = 1024
+spill_root = "_spill"
+query_result_cache_memory_mb_size = 128
+query_result_cache_disk_root = "_result_cache"
+query_result_cache_disk_mb_size = 1024
 management_mode = false
 jwt_key_file = ""
 async_insert_max_data_size = 10000
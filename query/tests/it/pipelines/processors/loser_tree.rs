@@ -0,0 +1,103 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use databend_query::pipelines::processors::LoserTree;
+
+fn merge_streams(streams: Vec<Vec<(i32, usize)>>) -> Vec<(i32, usize)> {
+    let mut streams = streams.into_iter().map(VecDeque::from).collect::<Vec<_>>();
+    let heads = streams
+        .iter_mut()
+        .map(|stream| stream.pop_front())
+        .collect::<Vec<_>>();
+
+    let mut tree = LoserTree::create(heads, |left: &(i32, usize), right: &(i32, usize)| {
+        left.0.cmp(&right.0)
+    });
+
+    let mut merged = vec![];
+    while let Some(winner) = tree.winner() {
+        merged.push(*tree.peek().unwrap());
+        tree.update(streams[winner].pop_front());
+    }
+    merged
+}
+
+#[test]
+fn test_loser_tree_merge() {
+    let merged = merge_streams(vec![
+        vec![(1, 0), (4, 0), (7, 0)],
+        vec![(2, 1), (5, 1), (8, 1), (9, 1)],
+        vec![],
+        vec![(3, 3), (6, 3)],
+        vec![(0, 4)],
+    ]);
+
+    let values = merged.iter().map(|(value, _)| *value).collect::<Vec<_>>();
+    assert_eq!(values, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_loser_tree_stable() {
+    // The equal values are popped in the order of the streams.
+    let merged = merge_streams(vec![vec![(1, 0), (2, 0)], vec![(1, 1), (2, 1)], vec![
+        (1, 2),
+        (2, 2),
+    ]]);
+
+    assert_eq!(merged, vec![(1, 0), (1, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+}
+
+#[test]
+fn test_loser_tree_empty() {
+    let mut tree = LoserTree::create(Vec::<Option<i32>>::new(), |left: &i32, right: &i32| {
+        left.cmp(right)
+    });
+    assert_eq!(tree.winner(), None);
+    assert_eq!(tree.peek(), None);
+    tree.update(Some(1));
+    assert_eq!(tree.winner(), None);
+
+    let tree = LoserTree::create(vec![None, None], |left: &i32, right: &i32| left.cmp(right));
+    assert_eq!(tree.winner(), None);
+}
+
+#[test]
+fn test_loser_tree_streams() {
+    // Merge every number of streams, the tree is not complete for most of them.
+    for num_streams in 1..=17usize {
+        let streams = (0..num_streams)
+            .map(|stream| {
+                let mut values = (0..(stream * 3 % 7 + 2))
+                    .map(|i| ((i * 31 + stream * 17) % 23) as i32)
+                    .collect::<Vec<_>>();
+                values.sort_unstable();
+                values
+                    .into_iter()
+                    .map(|value| (value, stream))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut expected = streams.concat();
+        expected.sort();
+        assert_eq!(
+            merge_streams(streams),
+            expected,
+            "merge {} streams",
+            num_streams
+        );
+    }
+}
@@ -13,5 +13,6 @@
 // limitations under the License.
 
 mod file_splitter;
+mod loser_tree;
 mod port_test;
 mod resize;
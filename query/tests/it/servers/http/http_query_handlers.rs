@@ -1278,6 +1278,43 @@ async fn test_multi_partition() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_query_result_cache() -> Result<()> {
+    let route = create_endpoint();
+
+    let sqls = vec![
+        (
+            "create table tb_result_cache(id int, c1 varchar) Engine=Fuse;",
+            0,
+        ),
+        (
+            "insert into tb_result_cache values(1, 'mysql'),(2,'databend')",
+            0,
+        ),
+        ("select * from tb_result_cache order by id;", 2),
+        ("select * from tb_result_cache order by id;", 2),
+        ("insert into tb_result_cache values(3, 'mysql')", 0),
+        ("select * from tb_result_cache order by id;", 3),
+        ("select * from tb_result_cache order by id;", 3),
+    ];
+
+    let mut results = vec![];
+    for (sql, data_len) in sqls {
+        let json = serde_json::json!({"sql": sql.to_string(), "pagination": {"wait_time_secs": 5}, "session": { "settings": {"enable_planner_v2": "1", "enable_query_result_cache": "1"}}});
+        let (status, result) = post_json_to_endpoint(&route, &json).await?;
+        assert_eq!(status, StatusCode::OK);
+        assert!(result.error.is_none(), "{:?}", result.error);
+        assert_eq!(result.state, ExecuteStateKind::Succeeded);
+        assert_eq!(result.data.len(), data_len, "{}", sql);
+        results.push(result.data);
+    }
+
+    // The cached results are the same as the results of executing the queries.
+    assert_eq!(results[2], results[3]);
+    assert_eq!(results[5], results[6]);
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_affect() -> Result<()> {
     let route = create_endpoint();
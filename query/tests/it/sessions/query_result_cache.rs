@@ -0,0 +1,80 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_io::prelude::FormatSettings;
+use databend_query::sessions::QueryResultCache;
+use tempfile::TempDir;
+
+#[test]
+fn test_query_result_cache() -> Result<()> {
+    let tmp_dir = TempDir::new().unwrap();
+    let mut conf = crate::tests::ConfigBuilder::create().config();
+    conf.query.query_result_cache_disk_root = tmp_dir.path().to_str().unwrap().to_string();
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", i64::to_data_type()),
+        DataField::new("b", Vu8::to_data_type()),
+    ]);
+    let block = DataBlock::create(schema.clone(), vec![
+        Series::from_data(vec![1i64, 2, 3]),
+        Series::from_data(vec!["x", "y", "z"]),
+    ]);
+
+    let key = QueryResultCache::cache_key(&["SELECT * FROM t", "snapshot_1"]);
+    assert_ne!(
+        key,
+        QueryResultCache::cache_key(&["SELECT * FROM t", "snapshot_2"])
+    );
+    assert_ne!(
+        key,
+        QueryResultCache::cache_key(&["SELECT * FROM t", "snapshot_", "1"])
+    );
+
+    let cache = QueryResultCache::init(&conf.query);
+    assert!(
+        cache
+            .get(&key, schema.clone(), FormatSettings::default())
+            .is_none()
+    );
+
+    cache.put(key.clone(), vec![block])?;
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 2 | y |",
+        "| 3 | z |",
+        "+---+---+",
+    ];
+    let blocks = cache.get(&key, schema.clone(), FormatSettings::default());
+    assert_blocks_eq(expected.clone(), &blocks.unwrap());
+
+    // The entry is persisted in the disk cache.
+    let cache = QueryResultCache::init(&conf.query);
+    let blocks = cache.get(&key, schema.clone(), FormatSettings::default());
+    assert_blocks_eq(expected, &blocks.unwrap());
+
+    // Empty result.
+    let key = QueryResultCache::cache_key(&["SELECT * FROM t WHERE a > 3"]);
+    cache.put(key.clone(), vec![])?;
+    let blocks = cache.get(&key, schema, FormatSettings::default());
+    assert_eq!(blocks.map(|blocks| blocks.len()), Some(0));
+
+    Ok(())
+}
@@ -60,6 +60,11 @@ pub async fn test_heuristic_optimizer_join() -> Result<()> {
             query: "select t.number from numbers(1) as t, numbers(1) as t1, numbers(1) as t2 where t1.number = t2.number and t.number = 1"
                 .to_string(),
             rules: DEFAULT_REWRITE_RULES.clone(),
+        }, Suite {
+            comment: "# Join with non-equi conditions only, implemented with nested-loop join".to_string(),
+            query: "select t.number from numbers(1) as t join numbers(1) as t1 on t.number < t1.number"
+                .to_string(),
+            rules: DEFAULT_REWRITE_RULES.clone(),
         },
     ];
 
@@ -103,6 +103,7 @@ fn test_format() {
         "database".to_string(),
         Arc::new(DummyTable::new("table".to_string())),
         get_dummy_read_source(),
+        None,
     );
 
     let s_expr = SExpr::create_binary(
@@ -150,6 +151,7 @@ fn test_format() {
             join_type: JoinType::Inner,
             marker_index: None,
             from_correlated_subquery: false,
+            estimation: None,
         }
         .into(),
         SExpr::create_unary(
@@ -0,0 +1,107 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::storages::fuse::FuseTable;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_table_alter_column() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 3 blocks of 1 row, the values of column `id` are 0, 1 and 2
+    let table = fixture.latest_default_table().await?;
+    let stream = TestFixture::gen_sample_blocks_stream_ex(3, 1, 0);
+    let blocks = stream.try_collect().await?;
+    fixture
+        .append_commit_blocks(table, blocks, false, true)
+        .await?;
+
+    let qry = format!("alter table {}.{} add column c int default 10", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the column already exists
+    let qry = format!("alter table {}.{} add column c int", db, tbl);
+    expects_err(
+        "add_existing_column",
+        ErrorCode::ColumnAlreadyExists("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    // the column `id` is referenced by the cluster key
+    let qry = format!("alter table {}.{} drop column id", db, tbl);
+    expects_err(
+        "drop_cluster_key_column",
+        ErrorCode::ColumnReferenced("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!("alter table {}.{} drop column t", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let qry = format!("alter table {}.{} rename column c to d", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the bloom indexes of the existing blocks are keyed by the names of `t` and `c`
+    for name in ["t", "c"] {
+        let qry = format!("alter table {}.{} rename column d to {}", db, tbl, name);
+        expects_err(
+            "rename_to_retired_name",
+            ErrorCode::ColumnReferenced("").code(),
+            execute_command(ctx.clone(), qry.as_str()).await,
+        );
+    }
+
+    // the leaf columns of `t` took the ids 1 and 2, the id of the added column is kept
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    assert_eq!(fuse_table.column_ids()?, vec![0, 3]);
+
+    // the blocks written before the column is added are read with the default value
+    let expected = vec![
+        "+----+----+",
+        "| id | d  |",
+        "+----+----+",
+        "| 0  | 10 |",
+        "| 1  | 10 |",
+        "| 2  | 10 |",
+        "+----+----+",
+    ];
+    let qry = format!("select id, d from {}.{} order by id", db, tbl);
+    expects_ok(
+        "read_default_value",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // unknown column
+    let qry = format!("alter table {}.{} drop column not_exists", db, tbl);
+    let r = execute_command(ctx, qry.as_str()).await;
+    assert_eq!(r.unwrap_err().code(), ErrorCode::UnknownColumn("").code());
+
+    Ok(())
+}
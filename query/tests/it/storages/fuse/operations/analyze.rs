@@ -0,0 +1,77 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_base::base::tokio;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_table_analyze() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 3 blocks, the values of column `id` are 0, 1 and 2
+    let table = fixture.latest_default_table().await?;
+    let stream = TestFixture::gen_sample_blocks_stream(3, 0);
+    let blocks = stream.try_collect().await?;
+    fixture
+        .append_commit_blocks(table, blocks, false, true)
+        .await?;
+
+    let qry = format!("analyze table {}.{} columns (id)", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the statistics of the nested column `t` are not collected
+    let table = fixture.latest_default_table().await?;
+    let col_stats = table.column_statistics(ctx.clone()).await?.unwrap();
+    assert_eq!(col_stats.len(), 1);
+    let id_stats = col_stats.get(&0).unwrap();
+    assert_eq!(id_stats.min, DataValue::Int64(0));
+    assert_eq!(id_stats.max, DataValue::Int64(2));
+    assert_eq!(id_stats.null_count, 0);
+    assert_eq!(id_stats.number_of_distinct_values(), Some(3));
+
+    // analyze commits a new snapshot
+    let expected = vec![
+        "+----------+",
+        "| COUNT(*) |",
+        "+----------+",
+        "| 2        |",
+        "+----------+",
+    ];
+    let qry = format!("select count(*) from fuse_snapshot('{}', '{}')", db, tbl);
+    expects_ok(
+        "count_should_be_2",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // unknown column
+    let qry = format!("analyze table {}.{} columns (not_exists)", db, tbl);
+    let r = execute_command(ctx, qry.as_str()).await;
+    assert_eq!(r.unwrap_err().code(), ErrorCode::UnknownColumn("").code());
+
+    Ok(())
+}
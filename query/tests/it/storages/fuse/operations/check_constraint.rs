@@ -0,0 +1,179 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_table_check_constraint() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!(
+        "create table {}.t(a int check (a > 0), b int as (a * 2) stored, c int null, \
+         constraint c_lt_a check (c < a))",
+        db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the generated column is computed from the inserted values, NULL satisfies the constraints
+    let qry = format!("insert into {}.t(a, c) values(1, 0), (2, null)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let qry = format!("insert into {}.t(a, c) values(3, 1), (-1, null)", db);
+    expects_err(
+        "insert_violating_column_check",
+        ErrorCode::CheckConstraintViolated("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!("insert into {}.t(a, c) values(3, 3)", db);
+    expects_err(
+        "insert_violating_table_check",
+        ErrorCode::CheckConstraintViolated("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!("insert into {}.t(a, b) values(3, 3)", db);
+    expects_err(
+        "insert_generated_column",
+        ErrorCode::GeneratedColumnNotWritable("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!("update {}.t set b = 1", db);
+    expects_err(
+        "update_generated_column",
+        ErrorCode::GeneratedColumnNotWritable("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!("update {}.t set a = 0 where a = 1", db);
+    expects_err(
+        "update_violating_check",
+        ErrorCode::CheckConstraintViolated("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    // the generated column is recomputed from the updated values
+    let qry = format!("update {}.t set a = 5 where a = 2", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let expected = vec![
+        "+---+----+------+",
+        "| a | b  | c    |",
+        "+---+----+------+",
+        "| 1 | 2  | 0    |",
+        "| 5 | 10 | NULL |",
+        "+---+----+------+",
+    ];
+    let qry = format!("select a, b, c from {}.t order by a", db);
+    expects_ok(
+        "generated_column",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // MERGE INTO writes the rows in the same way as INSERT and UPDATE
+    let qry = format!("create table {}.s(a int, c int null)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.s values(5, 1), (7, null)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let qry = format!(
+        "merge into {}.t using {}.s on t.a = s.a when matched then update set b = s.c",
+        db, db
+    );
+    expects_err(
+        "merge_update_generated_column",
+        ErrorCode::GeneratedColumnNotWritable("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!(
+        "merge into {}.t using {}.s on t.a = s.a \
+         when not matched then insert (a, b) values(s.a, s.c)",
+        db, db
+    );
+    expects_err(
+        "merge_insert_generated_column",
+        ErrorCode::GeneratedColumnNotWritable("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!(
+        "merge into {}.t using {}.s on t.a = s.a when matched then update set c = s.a",
+        db, db
+    );
+    expects_err(
+        "merge_violating_check",
+        ErrorCode::CheckConstraintViolated("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    let qry = format!(
+        "merge into {}.t using {}.s on t.a = s.a \
+         when matched then update set a = s.a + 1 \
+         when not matched then insert (a, c) values(s.a, s.c)",
+        db, db
+    );
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    let expected = vec![
+        "+---+----+------+",
+        "| a | b  | c    |",
+        "+---+----+------+",
+        "| 1 | 2  | 0    |",
+        "| 6 | 12 | NULL |",
+        "| 7 | 14 | NULL |",
+        "+---+----+------+",
+    ];
+    let qry = format!("select a, b, c from {}.t order by a", db);
+    expects_ok(
+        "merge_generated_column",
+        execute_query(ctx.clone(), qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // the expression of a check constraint must be a boolean
+    let qry = format!("create table {}.t1(a int check (a + 1))", db);
+    expects_err(
+        "non_boolean_check",
+        ErrorCode::SemanticError("").code(),
+        execute_command(ctx.clone(), qry.as_str()).await,
+    );
+
+    // a generated column can not reference another generated column
+    let qry = format!(
+        "create table {}.t1(a int, b int as (a + 1) stored, c int as (b + 1) stored)",
+        db
+    );
+    expects_err(
+        "generated_column_reference",
+        ErrorCode::SemanticError("").code(),
+        execute_command(ctx, qry.as_str()).await,
+    );
+
+    Ok(())
+}
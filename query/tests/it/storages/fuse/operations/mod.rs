@@ -12,6 +12,9 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+mod alter_column;
+mod analyze;
+mod check_constraint;
 mod commit;
 mod mutation;
 mod navigate;
@@ -19,3 +22,5 @@ mod optimize;
 mod purge_drop;
 mod purge_truncate;
 mod read_plan;
+mod recluster;
+mod transaction;
@@ -41,6 +41,7 @@ fn test_to_partitions() -> Result<()> {
         max: DataValue::Int64(2),
         null_count: 0,
         in_memory_size: col_size as u64,
+        distinct_values: None,
     };
 
     let col_metas_gen = |col_size| ColumnMeta {
@@ -52,6 +53,7 @@ fn test_to_partitions() -> Result<()> {
     let col_leaves_gen = |col_id| ColumnLeaf {
         name: "".to_string(),
         leaf_ids: vec![col_id],
+        column_ids: vec![col_id as u32],
         children: None,
     };
 
@@ -0,0 +1,78 @@
+//  Copyright 2021 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use databend_query::sessions::TableContext;
+use databend_query::storages::fuse::FuseTable;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test]
+async fn test_fuse_table_recluster() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let tbl = fixture.default_table_name();
+    let ctx = fixture.ctx();
+    fixture.create_default_table().await?;
+
+    // 2 blocks overlapping on the cluster key `id`, [0, 6] and [1, 7]
+    for ids in [vec![0, 2, 4, 6], vec![1, 3, 5, 7]] {
+        let table = fixture.latest_default_table().await?;
+        fixture
+            .append_commit_blocks(table, vec![sample_block(ids)], false, true)
+            .await?;
+    }
+
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let table_ctx: Arc<dyn TableContext> = ctx.clone();
+    let reason = fuse_table.check_recluster(&table_ctx, 1).await?;
+    assert_eq!(reason, Some("depth 2 on the cluster key".to_string()));
+
+    // no block may contain the matching rows, nothing to recluster
+    let qry = format!("alter table {}.{} recluster where id > 100", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let reason = fuse_table.check_recluster(&table_ctx, 1).await?;
+    assert!(reason.is_some());
+
+    let qry = format!("alter table {}.{} recluster final", db, tbl);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let table = fixture.latest_default_table().await?;
+    let fuse_table = FuseTable::try_from_table(table.as_ref())?;
+    let reason = fuse_table.check_recluster(&table_ctx, 1).await?;
+    assert!(reason.is_none());
+    assert_eq!(table.get_table_info().meta.statistics.number_of_rows, 8);
+
+    Ok(())
+}
+
+fn sample_block(ids: Vec<i32>) -> DataBlock {
+    let tuple_inner_names = vec!["a".to_string(), "b".to_string()];
+    let tuple_inner_data_types = vec![i32::to_data_type(), i32::to_data_type()];
+    let tuple_data_type = StructType::new_impl(Some(tuple_inner_names), tuple_inner_data_types);
+    let column1 = Series::from_data(ids.iter().map(|id| id * 2).collect::<Vec<i32>>());
+    let column2 = Series::from_data(ids.iter().map(|id| id * 3).collect::<Vec<i32>>());
+    let tuple_column = StructColumn::from_data(vec![column1, column2], tuple_data_type).arc();
+    DataBlock::create(TestFixture::default_schema(), vec![
+        Series::from_data(ids),
+        tuple_column,
+    ])
+}
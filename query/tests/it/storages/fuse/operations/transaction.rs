@@ -0,0 +1,131 @@
+//  Copyright 2022 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_base::base::tokio;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use databend_query::sessions::QueryContext;
+
+use crate::storages::fuse::table_test_fixture::check_data_dir;
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_err;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+// every statement runs in a new query context of the session, like the servers do
+async fn new_ctx(ctx: &Arc<QueryContext>) -> Result<Arc<QueryContext>> {
+    ctx.get_current_session().create_query_context().await
+}
+
+#[tokio::test]
+async fn test_fuse_table_transaction() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    for table in ["t1", "t2"] {
+        let qry = format!("create table {}.{}(a int)", db, table);
+        execute_command(new_ctx(&ctx).await?, qry.as_str()).await?;
+    }
+
+    // the writes of a rolled back transaction are discarded
+    execute_command(new_ctx(&ctx).await?, "begin").await?;
+    expects_err(
+        "nested_begin",
+        ErrorCode::TransactionAlreadyStarted("").code(),
+        execute_command(new_ctx(&ctx).await?, "begin transaction").await,
+    );
+    let qry = format!("insert into {}.t1 values(1), (2)", db);
+    execute_command(new_ctx(&ctx).await?, qry.as_str()).await?;
+    let qry = format!("insert into {}.t2 values(3)", db);
+    execute_command(new_ctx(&ctx).await?, qry.as_str()).await?;
+
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 2        |",
+        "+----------+",
+    ];
+    let qry = format!("select count(*) from {}.t1", db);
+    expects_ok(
+        "read_own_writes",
+        execute_query(new_ctx(&ctx).await?, qry.as_str()).await,
+        expected,
+    )
+    .await?;
+    check_data_dir(&fixture, "before_rollback", 2, 2, 2, 2).await;
+
+    // the data written in the rolled back transaction is purged
+    execute_command(new_ctx(&ctx).await?, "rollback").await?;
+    check_data_dir(&fixture, "after_rollback", 0, 0, 0, 0).await;
+
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 0        |",
+        "+----------+",
+    ];
+    for table in ["t1", "t2"] {
+        let qry = format!("select count(*) from {}.{}", db, table);
+        expects_ok(
+            format!("rollback_{}", table),
+            execute_query(new_ctx(&ctx).await?, qry.as_str()).await,
+            expected.clone(),
+        )
+        .await?;
+    }
+
+    // the writes of a committed transaction are visible all together
+    execute_command(new_ctx(&ctx).await?, "begin").await?;
+    let qry = format!("insert into {}.t1 values(1), (2)", db);
+    execute_command(new_ctx(&ctx).await?, qry.as_str()).await?;
+    let qry = format!("insert into {}.t2 values(3)", db);
+    execute_command(new_ctx(&ctx).await?, qry.as_str()).await?;
+    execute_command(new_ctx(&ctx).await?, "commit").await?;
+
+    let expected = vec![
+        "+----------+",
+        "| count(*) |",
+        "+----------+",
+        "| 2        |",
+        "+----------+",
+    ];
+    let qry = format!(
+        "select count(*) from {db}.t1, {db}.t2 where t1.a < t2.a",
+        db = db
+    );
+    expects_ok(
+        "commit",
+        execute_query(new_ctx(&ctx).await?, qry.as_str()).await,
+        expected,
+    )
+    .await?;
+
+    // purging the historical data is not allowed in a transaction
+    execute_command(new_ctx(&ctx).await?, "begin").await?;
+    let qry = format!("optimize table {}.t1 purge", db);
+    expects_err(
+        "purge_in_transaction",
+        ErrorCode::UnImplement("").code(),
+        execute_command(new_ctx(&ctx).await?, qry.as_str()).await,
+    );
+    execute_command(new_ctx(&ctx).await?, "rollback").await?;
+
+    Ok(())
+}
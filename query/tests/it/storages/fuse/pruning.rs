@@ -17,6 +17,7 @@ use std::sync::Arc;
 use common_base::base::tokio;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_fuse_meta::meta::BlockMeta;
 use common_fuse_meta::meta::TableSnapshot;
@@ -31,13 +32,21 @@ use databend_query::interpreters::CreateTableInterpreter;
 use databend_query::interpreters::Interpreter;
 use databend_query::sessions::QueryContext;
 use databend_query::sessions::TableContext;
+use databend_query::sql::executor::PhysicalPlan;
+use databend_query::sql::executor::PhysicalPlanBuilder;
+use databend_query::sql::executor::TableScan;
+use databend_query::sql::plans::Plan;
+use databend_query::sql::Planner;
 use databend_query::sql::OPT_KEY_DATABASE_ID;
 use databend_query::sql::OPT_KEY_SNAPSHOT_LOCATION;
 use databend_query::storages::fuse::io::MetaReaders;
 use databend_query::storages::fuse::pruning::BlockPruner;
 use databend_query::storages::fuse::FUSE_OPT_KEY_BLOCK_PER_SEGMENT;
 use databend_query::storages::fuse::FUSE_OPT_KEY_ROW_PER_BLOCK;
+use futures::TryStreamExt;
 
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
 use crate::storages::fuse::table_test_fixture::TestFixture;
 
 async fn apply_block_pruning(
@@ -311,3 +320,61 @@ async fn test_block_pruner_monotonic() -> Result<()> {
 
     Ok(())
 }
+
+fn find_table_scan(plan: &PhysicalPlan) -> Option<&TableScan> {
+    match plan {
+        PhysicalPlan::TableScan(scan) => Some(scan),
+        PhysicalPlan::Filter(filter) => find_table_scan(&filter.input),
+        PhysicalPlan::Project(project) => find_table_scan(&project.input),
+        PhysicalPlan::EvalScalar(eval_scalar) => find_table_scan(&eval_scalar.input),
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn test_planner_v2_scan_push_downs() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let ctx = fixture.ctx();
+    let db = fixture.default_db_name();
+
+    execute_command(ctx.clone(), &format!("create table {}.t(a int, b int)", db)).await?;
+    // one block for each insertion
+    execute_command(
+        ctx.clone(),
+        &format!("insert into {}.t values (1, 10), (2, 20)", db),
+    )
+    .await?;
+    execute_command(
+        ctx.clone(),
+        &format!("insert into {}.t values (3, 30), (4, 40)", db),
+    )
+    .await?;
+
+    let query = format!("select * from {}.t where a > 2", db);
+    let mut planner = Planner::new(ctx.clone());
+    let (plan, _, _) = planner.plan_sql(&query).await?;
+    let physical_plan = match plan {
+        Plan::Query {
+            s_expr, metadata, ..
+        } => PhysicalPlanBuilder::new(metadata).build(&s_expr)?,
+        _ => return Err(ErrorCode::LogicalError("expects query plan")),
+    };
+    let scan = find_table_scan(&physical_plan).unwrap();
+
+    // the filters are pushed down to the scan, without projection
+    let push_downs = scan.source.push_downs.as_ref().unwrap();
+    assert_eq!(push_downs.filters.len(), 1);
+    assert_eq!(push_downs.projection, None);
+
+    // the partitions are read while binding, before the filters are pushed down,
+    // so none of the blocks is pruned
+    assert_eq!(scan.source.parts.len(), 2);
+
+    let blocks = execute_query(ctx.clone(), &query)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let num_rows = blocks.iter().map(|block| block.num_rows()).sum::<usize>();
+    assert_eq!(num_rows, 2);
+    Ok(())
+}
@@ -20,9 +20,11 @@ use common_catalog::table_context::TableContext;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_fuse_meta::meta::ColumnStatistics;
+use common_fuse_meta::meta::HyperLogLog;
 use databend_query::storages::fuse::io::BlockWriter;
 use databend_query::storages::fuse::io::TableMetaLocationGenerator;
 use databend_query::storages::fuse::statistics::gen_columns_statistics;
+use databend_query::storages::fuse::statistics::gen_columns_statistics_with_distinct_values;
 use databend_query::storages::fuse::statistics::reducers;
 use databend_query::storages::fuse::statistics::BlockStatistics;
 use databend_query::storages::fuse::statistics::StatisticsAccumulator;
@@ -101,6 +103,72 @@ fn test_ft_stats_col_stats_reduce() -> common_exception::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_ft_stats_distinct_values() -> common_exception::Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new_nullable("a", i32::to_data_type())]);
+    let column = Series::from_data(vec![Some(1), Some(2), Some(2), None, Some(3), Some(3)]);
+    let block = DataBlock::create(schema, vec![column]);
+    // the statistics of blocks do not have the sketch
+    let r = gen_columns_statistics(&block)?;
+    assert!(r.get(&0).unwrap().distinct_values.is_none());
+
+    let r = gen_columns_statistics_with_distinct_values(&block)?;
+    let col_stats = r.get(&0).unwrap();
+    assert_eq!(col_stats.null_count, 1);
+    // nulls are not counted, and the estimation is close to exact for small cardinality
+    let ndv = col_stats.number_of_distinct_values().unwrap();
+    assert!((2..=4).contains(&ndv), "ndv: {}", ndv);
+    Ok(())
+}
+
+#[test]
+fn test_ft_stats_distinct_values_reduce() -> common_exception::Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", i64::to_data_type())]);
+    let block_1 = DataBlock::create(schema.clone(), vec![Series::from_data(
+        (0..10000i64).collect::<Vec<_>>(),
+    )]);
+    let block_2 = DataBlock::create(schema, vec![Series::from_data(
+        (5000..15000i64).collect::<Vec<_>>(),
+    )]);
+    let col_stats = vec![
+        gen_columns_statistics_with_distinct_values(&block_1)?,
+        gen_columns_statistics_with_distinct_values(&block_2)?,
+    ];
+
+    let r = reducers::reduce_block_statistics(&col_stats)?;
+    let ndv = r.get(&0).unwrap().number_of_distinct_values().unwrap();
+    // the standard error of the sketch is about 6.5%
+    assert!((11250..=18750).contains(&ndv), "ndv: {}", ndv);
+
+    // the sketch is dropped if any of the parts does not have it, e.g. of legacy versions
+    let mut legacy = col_stats[1].clone();
+    legacy.get_mut(&0).unwrap().distinct_values = None;
+    let r = reducers::reduce_block_statistics(&[&col_stats[0], &legacy])?;
+    assert!(r.get(&0).unwrap().distinct_values.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_hyper_log_log_serde() -> common_exception::Result<()> {
+    let mut sketch = HyperLogLog::new();
+    for i in 0..1000u64 {
+        sketch.add_hash(i.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    }
+    let json = serde_json::to_string(&sketch)?;
+    let decoded: HyperLogLog = serde_json::from_str(&json)?;
+    assert_eq!(sketch, decoded);
+    assert_eq!(sketch.estimate(), decoded.estimate());
+
+    // statistics of legacy versions do not have the sketch
+    let legacy = r#"{"min":{"Int64":1},"max":{"Int64":3},"null_count":0,"in_memory_size":24}"#;
+    let col_stats: ColumnStatistics = serde_json::from_str(legacy)?;
+    assert!(col_stats.distinct_values.is_none());
+
+    // and the statistics without the sketch are serialized as before
+    assert_eq!(serde_json::to_string(&col_stats)?, legacy);
+    Ok(())
+}
+
 #[test]
 fn test_reduce_block_statistics_in_memory_size() -> common_exception::Result<()> {
     let iter = |mut idx| {
@@ -111,6 +179,7 @@ fn test_reduce_block_statistics_in_memory_size() -> common_exception::Result<()>
                 max: DataValue::Null,
                 null_count: 1,
                 in_memory_size: 1,
+                distinct_values: None,
             }))
         })
     };
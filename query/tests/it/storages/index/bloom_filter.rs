@@ -11,6 +11,8 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
+use std::collections::BTreeMap;
+
 use common_base::base::tokio;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
@@ -19,6 +21,8 @@ use common_planners::*;
 use databend_query::storages::index::BloomFilter;
 use databend_query::storages::index::BloomFilterExprEvalResult;
 use databend_query::storages::index::BloomFilterIndexer;
+use databend_query::storages::index::BloomIndexOptions;
+use databend_query::storages::index::SetIndex;
 use pretty_assertions::assert_eq;
 
 use crate::tests::create_query_context;
@@ -487,3 +491,236 @@ async fn test_bloom_indexer_logical_or_prune() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test]
+async fn test_bloom_indexer_substring_prune() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        expr: Expression,
+        expected_eval_result: BloomFilterExprEvalResult,
+    }
+
+    let position = |substr: &str, column: &str| {
+        Expression::create_scalar_function("position", vec![lit(substr.as_bytes()), col(column)])
+    };
+    let regexp_like = |column: &str, args: &[&str]| {
+        let mut args = args.iter().map(|v| lit(v.as_bytes())).collect::<Vec<_>>();
+        args.insert(0, col(column));
+        Expression::create_scalar_function("regexp_like", args)
+    };
+    let like = |column: &str, pattern: &str| {
+        Expression::create_binary_expression("like", vec![col(column), lit(pattern.as_bytes())])
+    };
+
+    let tests: Vec<Test> = vec![
+        Test {
+            name: "message like '%refused%'",
+            expr: like("message", "%refused%"),
+            expected_eval_result: BloomFilterExprEvalResult::Unknown,
+        },
+        Test {
+            name: "message like '%xyzzy%'",
+            expr: like("message", "%xyzzy%"),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            // fragments shorter than the grams are not applicable
+            name: "message like '%ab%'",
+            expr: like("message", "%ab%"),
+            expected_eval_result: BloomFilterExprEvalResult::NotApplicable,
+        },
+        Test {
+            name: "message like 'disk%xyzzy'",
+            expr: like("message", "disk%xyzzy"),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            name: "position('xyzzy' in message) > 0",
+            expr: position("xyzzy", "message").gt(lit(0u64)),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            name: "position('xyzzy' in message) = 0",
+            expr: position("xyzzy", "message").eq(lit(0u64)),
+            expected_eval_result: BloomFilterExprEvalResult::NotApplicable,
+        },
+        Test {
+            name: "0 < position('xyzzy' in message)",
+            expr: lit(0u64).lt(position("xyzzy", "message")),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            name: "locate('refused', message) >= 1",
+            expr: Expression::create_scalar_function("locate", vec![
+                lit("refused".as_bytes()),
+                col("message"),
+            ])
+            .gt_eq(lit(1u64)),
+            expected_eval_result: BloomFilterExprEvalResult::Unknown,
+        },
+        Test {
+            name: "instr(message, 'xyzzy') != 0",
+            expr: Expression::create_scalar_function("instr", vec![
+                col("message"),
+                lit("xyzzy".as_bytes()),
+            ])
+            .not_eq(lit(0u64)),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            name: "regexp_like(message, '^disk.*full$', 'c')",
+            expr: regexp_like("message", &["^disk.*full$", "c"]),
+            expected_eval_result: BloomFilterExprEvalResult::Unknown,
+        },
+        Test {
+            name: "regexp_like(message, 'disk xyzzy', 'c')",
+            expr: regexp_like("message", &["disk xyzzy", "c"]),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            // case insensitive by default
+            name: "regexp_like(message, 'xyzzy')",
+            expr: regexp_like("message", &["xyzzy"]),
+            expected_eval_result: BloomFilterExprEvalResult::NotApplicable,
+        },
+        Test {
+            name: "tags like 'dev'",
+            expr: like("tags", "dev"),
+            expected_eval_result: BloomFilterExprEvalResult::Unknown,
+        },
+        Test {
+            name: "tags like 'qa'",
+            expr: like("tags", "qa"),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            name: "tags like '% xyzzy %'",
+            expr: like("tags", "% xyzzy %"),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+        Test {
+            // 'xyzzy' might be a part of some token
+            name: "tags like '%xyzzy%'",
+            expr: like("tags", "%xyzzy%"),
+            expected_eval_result: BloomFilterExprEvalResult::NotApplicable,
+        },
+        Test {
+            name: "message like '%xyzzy%' or message like '%refused%'",
+            expr: like("message", "%xyzzy%").or(like("message", "%refused%")),
+            expected_eval_result: BloomFilterExprEvalResult::Unknown,
+        },
+        Test {
+            name: "id = 1 and message like '%xyzzy%'",
+            expr: col("id").eq(lit(1i32)).and(like("message", "%xyzzy%")),
+            expected_eval_result: BloomFilterExprEvalResult::False,
+        },
+    ];
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("id", i32::to_data_type()),
+        DataField::new("message", Vu8::to_data_type()),
+        DataField::new("tags", wrap_nullable(&Vu8::to_data_type())),
+    ]);
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec![1_i32, 2, 3]),
+        Series::from_data(vec!["connection refused by peer", "disk is full", "ok"]),
+        Series::from_data(vec![
+            Some("prod urgent".as_bytes()),
+            None,
+            Some("dev".as_bytes()),
+        ]),
+    ]);
+
+    let mut table_options = BTreeMap::new();
+    table_options.insert(
+        "ngram_bloom_index_columns".to_string(),
+        "message".to_string(),
+    );
+    table_options.insert(
+        "token_bloom_index_columns".to_string(),
+        "message, tags".to_string(),
+    );
+    let options = BloomIndexOptions::from_table_options(&table_options)?;
+
+    let ctx = create_query_context().await?;
+    let indexer = BloomFilterIndexer::try_create_with_options(ctx, &[&block], &options)?;
+    assert!(indexer.bloom_schema.has_field("Ngram3(message)"));
+    assert!(indexer.bloom_schema.has_field("Token(message)"));
+    assert!(indexer.bloom_schema.has_field("Token(tags)"));
+    assert!(!indexer.bloom_schema.has_field("Ngram3(tags)"));
+
+    for test in tests {
+        let res = indexer.eval(&test.expr)?;
+        assert_eq!(res, test.expected_eval_result, "{}", test.name);
+    }
+
+    // the ngram bloom filters of other sizes are not used
+    let indexer = indexer.with_ngram_size(4);
+    let res = indexer.eval(&like("message", "%xyzzy%"))?;
+    assert_eq!(res, BloomFilterExprEvalResult::NotApplicable);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_index() -> Result<()> {
+    let column = Series::from_data(vec![
+        Some("prod".as_bytes()),
+        None,
+        Some("dev".as_bytes()),
+        Some("prod".as_bytes()),
+    ]);
+
+    let ctx = create_query_context().await?;
+    let set_index = SetIndex::try_create(&[&column], create_seed(), 8, ctx.clone())?.unwrap();
+    assert_eq!(set_index.num_values(), 2);
+
+    let buf = set_index.to_vec()?;
+    let set_index = SetIndex::from_vec(buf.as_slice())?;
+
+    let typ = StringType::new_impl();
+    assert!(set_index.find(
+        DataValue::String(b"prod".to_vec()),
+        typ.clone(),
+        ctx.clone()
+    )?);
+    assert!(set_index.find(DataValue::String(b"dev".to_vec()), typ.clone(), ctx.clone())?);
+    assert!(!set_index.find(
+        DataValue::String(b"test".to_vec()),
+        typ.clone(),
+        ctx.clone()
+    )?);
+    assert!(set_index.find(DataValue::Null, typ, ctx.clone()).is_err());
+
+    // too many distinct values
+    let column = Series::from_data((0..10_i32).collect::<Vec<_>>());
+    assert!(SetIndex::try_create(&[&column], create_seed(), 8, ctx)?.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_bloom_indexer_with_set_index() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("env", Vu8::to_data_type()),
+        DataField::new("id", i32::to_data_type()),
+    ]);
+    let block = DataBlock::create(schema, vec![
+        Series::from_data(vec!["prod", "dev", "prod"]),
+        Series::from_data(vec![1_i32, 2, 3]),
+    ]);
+
+    let options = BloomIndexOptions {
+        set_columns: ["env".to_string()].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let ctx = create_query_context().await?;
+    let indexer = BloomFilterIndexer::try_create_with_options(ctx, &[&block], &options)?;
+    assert!(indexer.bloom_schema.has_field("Set(env)"));
+    assert!(!indexer.bloom_schema.has_field("Set(id)"));
+
+    let res = indexer.eval(&col("env").eq(lit("test".as_bytes())))?;
+    assert_eq!(res, BloomFilterExprEvalResult::False);
+    let res = indexer.eval(&col("env").eq(lit("dev".as_bytes())))?;
+    assert_eq!(res, BloomFilterExprEvalResult::Unknown);
+    Ok(())
+}
@@ -14,3 +14,4 @@
 
 mod bloom_filter;
 mod range_filter;
+mod text_index;
@@ -42,18 +42,21 @@ async fn test_range_filter() -> Result<()> {
         max: DataValue::Int64(20),
         null_count: 1,
         in_memory_size: 0,
+        distinct_values: None,
     });
     stats.insert(1u32, ColumnStatistics {
         min: DataValue::Int64(3),
         max: DataValue::Int64(10),
         null_count: 0,
         in_memory_size: 0,
+        distinct_values: None,
     });
     stats.insert(2u32, ColumnStatistics {
         min: DataValue::String("abc".as_bytes().to_vec()),
         max: DataValue::String("bcd".as_bytes().to_vec()),
         null_count: 0,
         in_memory_size: 0,
+        distinct_values: None,
     });
 
     struct Test {
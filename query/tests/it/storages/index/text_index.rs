@@ -0,0 +1,230 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_meta_app::schema::TableIndex;
+use common_meta_app::schema::TableIndexType;
+use databend_query::storages::index::text_index::*;
+use pretty_assertions::assert_eq;
+
+fn fragment(bytes: &str, at_start: bool, at_end: bool) -> SubstringFragment {
+    SubstringFragment {
+        bytes: bytes.as_bytes().to_vec(),
+        at_start,
+        at_end,
+    }
+}
+
+#[test]
+fn test_like_pattern_fragments() {
+    let tests = vec![
+        ("abc", vec![fragment("abc", true, true)]),
+        ("abc%", vec![fragment("abc", true, false)]),
+        ("%abc", vec![fragment("abc", false, true)]),
+        ("_abc_", vec![fragment("abc", false, false)]),
+        ("%ab_cd%ef", vec![
+            fragment("ab", false, false),
+            fragment("cd", false, false),
+            fragment("ef", false, true),
+        ]),
+        ("%a\\%b\\_c%", vec![fragment("a%b_c", false, false)]),
+        ("%a\\bc%", vec![fragment("a\\bc", false, false)]),
+        ("%%", vec![]),
+    ];
+
+    for (pattern, expected) in tests {
+        assert_eq!(
+            like_pattern_fragments(pattern.as_bytes()),
+            expected,
+            "{}",
+            pattern
+        );
+    }
+}
+
+#[test]
+fn test_regexp_pattern_fragments() {
+    let tests = vec![
+        ("abc", vec![fragment("abc", false, false)]),
+        ("^disk.*full$", vec![
+            fragment("disk", true, false),
+            fragment("full", false, true),
+        ]),
+        ("ab?cd", vec![
+            fragment("a", false, false),
+            fragment("cd", false, false),
+        ]),
+        ("ab*cd", vec![
+            fragment("a", false, false),
+            fragment("cd", false, false),
+        ]),
+        ("ab+cd", vec![
+            fragment("ab", false, false),
+            fragment("cd", false, false),
+        ]),
+        ("ab{0,2}cd", vec![
+            fragment("a", false, false),
+            fragment("cd", false, false),
+        ]),
+        ("\\d+abc", vec![fragment("abc", false, false)]),
+        ("(abc)?def", vec![fragment("def", false, false)]),
+        ("[]a]bc", vec![fragment("bc", false, false)]),
+        ("[[a]b]cd", vec![fragment("cd", false, false)]),
+        ("日本?語", vec![
+            fragment("日", false, false),
+            fragment("語", false, false),
+        ]),
+        ("abc|def", vec![]),
+        ("(?i)abc", vec![]),
+        ("", vec![]),
+    ];
+
+    for (pattern, expected) in tests {
+        assert_eq!(
+            regexp_pattern_fragments(pattern.as_bytes()),
+            expected,
+            "{}",
+            pattern
+        );
+    }
+}
+
+#[test]
+fn test_regexp_match_type() {
+    assert!(is_case_sensitive_match_type(b"c"));
+    assert!(is_case_sensitive_match_type(b"icm"));
+    assert!(!is_case_sensitive_match_type(b""));
+    assert!(!is_case_sensitive_match_type(b"m"));
+    assert!(!is_case_sensitive_match_type(b"ci"));
+}
+
+#[test]
+fn test_complete_tokens() {
+    let tests = vec![
+        (fragment("connection refused by", false, false), vec![
+            "refused",
+        ]),
+        (fragment("connection refused by", true, false), vec![
+            "connection",
+            "refused",
+        ]),
+        (fragment("connection refused by", true, true), vec![
+            "connection",
+            "refused",
+            "by",
+        ]),
+        (fragment(" refused ", false, false), vec!["refused"]),
+        (fragment("refused", false, false), vec![]),
+    ];
+
+    for (fragment, expected) in tests {
+        let tokens = fragment.complete_tokens();
+        let expected = expected.iter().map(|t| t.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(tokens, expected, "{:?}", fragment);
+    }
+}
+
+#[test]
+fn test_ngrams_and_tokens() {
+    let grams = ngrams(b"abcd", 3).collect::<Vec<_>>();
+    assert_eq!(grams, vec![b"abc".as_slice(), b"bcd".as_slice()]);
+    assert_eq!(ngrams(b"ab", 3).count(), 0);
+
+    let tokens = tokens(b" disk  is\tfull\n").collect::<Vec<_>>();
+    assert_eq!(tokens, vec![
+        b"disk".as_slice(),
+        b"is".as_slice(),
+        b"full".as_slice()
+    ]);
+}
+
+#[test]
+fn test_bloom_index_options() -> Result<()> {
+    let mut table_options = BTreeMap::new();
+    let options = BloomIndexOptions::from_table_options(&table_options)?;
+    assert_eq!(options, BloomIndexOptions::default());
+
+    table_options.insert(
+        OPT_KEY_NGRAM_BLOOM_INDEX_COLUMNS.to_string(),
+        "a, b,".to_string(),
+    );
+    table_options.insert(OPT_KEY_NGRAM_BLOOM_INDEX_SIZE.to_string(), "4".to_string());
+    table_options.insert(
+        OPT_KEY_TOKEN_BLOOM_INDEX_COLUMNS.to_string(),
+        "c".to_string(),
+    );
+    let options = BloomIndexOptions::from_table_options(&table_options)?;
+    assert_eq!(options.ngram_size, 4);
+    assert_eq!(options.ngram_columns.iter().collect::<Vec<_>>(), vec![
+        "a", "b"
+    ]);
+    assert_eq!(options.token_columns.iter().collect::<Vec<_>>(), vec!["c"]);
+
+    let schema = DataSchema::new(vec![
+        DataField::new("a", Vu8::to_data_type()),
+        DataField::new("b", wrap_nullable(&Vu8::to_data_type())),
+        DataField::new("c", Vu8::to_data_type()),
+        DataField::new("d", i32::to_data_type()),
+    ]);
+    options.check_schema(&schema)?;
+
+    // not a string column
+    table_options.insert(
+        OPT_KEY_TOKEN_BLOOM_INDEX_COLUMNS.to_string(),
+        "d".to_string(),
+    );
+    let options = BloomIndexOptions::from_table_options(&table_options)?;
+    assert_eq!(options.check_schema(&schema).unwrap_err().code(), 1022);
+
+    // unknown column
+    table_options.insert(
+        OPT_KEY_TOKEN_BLOOM_INDEX_COLUMNS.to_string(),
+        "e".to_string(),
+    );
+    let options = BloomIndexOptions::from_table_options(&table_options)?;
+    assert_eq!(options.check_schema(&schema).unwrap_err().code(), 1022);
+
+    // invalid size of grams
+    for size in ["0", "abc", "17"] {
+        table_options.insert(OPT_KEY_NGRAM_BLOOM_INDEX_SIZE.to_string(), size.to_string());
+        let res = BloomIndexOptions::from_table_options(&table_options);
+        assert_eq!(res.unwrap_err().code(), 1022, "{}", size);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_bloom_index_options_with_table_indexes() -> Result<()> {
+    let index = |name: &str, column: &str, index_type| TableIndex {
+        name: name.to_string(),
+        column: column.to_string(),
+        index_type,
+    };
+    let indexes = vec![
+        index("idx_a", "a", TableIndexType::Ngram),
+        index("idx_b", "b", TableIndexType::Set),
+        index("idx_c", "c", TableIndexType::Bloom),
+        index("idx_d", "d", TableIndexType::MinMax),
+    ];
+
+    let options = BloomIndexOptions::default().with_table_indexes(&indexes);
+    assert_eq!(options.ngram_columns.iter().collect::<Vec<_>>(), vec!["a"]);
+    assert_eq!(options.set_columns.iter().collect::<Vec<_>>(), vec!["b"]);
+    assert!(options.token_columns.is_empty());
+    Ok(())
+}
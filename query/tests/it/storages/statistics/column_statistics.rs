@@ -15,12 +15,16 @@
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::ArrayColumn;
 use common_datavalues::ColumnRef;
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataTypeImpl;
+use common_datavalues::DataValue;
+use common_datavalues::MapType;
 use common_datavalues::Series;
 use common_datavalues::SeriesFrom;
+use common_datavalues::StringType;
 use common_datavalues::StructColumn;
 use common_datavalues::StructType;
 use common_datavalues::ToDataType;
@@ -118,3 +122,38 @@ fn test_column_statistic() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_map_column_statistic() -> Result<()> {
+    // map column: {'a': 1, 'c': 3}, {'b': 2}
+    let map_type = MapType::create(StringType::new_impl(), i64::to_data_type());
+    let schema = DataSchemaRefExt::create(vec![DataField::new(
+        "m",
+        DataTypeImpl::Map(map_type.clone()),
+    )]);
+
+    let col_keys = Series::from_data(vec!["a", "c", "b"]);
+    let col_values = Series::from_data(vec![1i64, 3, 2]);
+    let entries: ColumnRef = Arc::new(StructColumn::from_data(
+        vec![col_keys.clone(), col_values.clone()],
+        map_type.entries_type(),
+    ));
+    let col_map: ColumnRef = Arc::new(ArrayColumn::from_data(
+        DataTypeImpl::Map(map_type),
+        vec![0i64, 2, 3].into(),
+        entries,
+    ));
+    let block = DataBlock::create(schema, vec![col_map]);
+
+    let cols = traverse::traverse_columns_dfs(block.columns())?;
+    assert_eq!(2, cols.len());
+    assert_eq!(cols[0], col_keys);
+    assert_eq!(cols[1], col_values);
+
+    let col_stats = gen_columns_statistics(&block)?;
+    let key_stats = col_stats.get(&0).unwrap();
+    assert_eq!(key_stats.min, DataValue::String(b"a".to_vec()));
+    assert_eq!(key_stats.max, DataValue::String(b"c".to_vec()));
+
+    Ok(())
+}
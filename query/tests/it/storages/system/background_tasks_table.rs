@@ -0,0 +1,87 @@
+// Copyright 2021 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::Duration;
+
+use common_base::base::tokio;
+use common_datablocks::DataBlock;
+use common_datavalues::DataValue;
+use common_exception::Result;
+use databend_query::storages::system::BackgroundTasksTable;
+use databend_query::storages::TableStreamReadWrap;
+use databend_query::storages::ToReadDataSourcePlan;
+use futures::TryStreamExt;
+
+use crate::storages::fuse::table_test_fixture::execute_command;
+use crate::storages::fuse::table_test_fixture::execute_query;
+use crate::storages::fuse::table_test_fixture::expects_ok;
+use crate::storages::fuse::table_test_fixture::TestFixture;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_background_tasks_table() -> Result<()> {
+    let ctx = crate::tests::create_query_context().await?;
+
+    let table = BackgroundTasksTable::create(1);
+    let source_plan = table.read_plan(ctx.clone(), None).await?;
+
+    let stream = table.read(ctx, &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 10);
+    // background tasks are disabled by default
+    assert_eq!(block.num_rows(), 0);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_background_tasks_table_build_index() -> Result<()> {
+    let fixture = TestFixture::new().await;
+    let db = fixture.default_db_name();
+    let ctx = fixture.ctx();
+
+    let qry = format!("create table {}.t(id int, s string)", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("insert into {}.t values(1, 'abc'), (2, 'bcd')", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+    let qry = format!("create index idx on {}.t (s) type ngram", db);
+    execute_command(ctx.clone(), qry.as_str()).await?;
+
+    // the block written before the index is created is indexed by a task
+    let qry = "select count(*) from system.background_tasks where state <> 'Running'";
+    let mut finished = false;
+    for _ in 0..100 {
+        let blocks: Vec<DataBlock> = execute_query(ctx.clone(), qry).await?.try_collect().await?;
+        if blocks[0].column(0).get(0) == DataValue::UInt64(1) {
+            finished = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(finished);
+
+    let expected = vec![
+        "+------------+-----------+-------+-------------------+",
+        "| type       | state     | table | reason            |",
+        "+------------+-----------+-------+-------------------+",
+        "| BuildIndex | Succeeded | t     | index idx created |",
+        "+------------+-----------+-------+-------------------+",
+    ];
+    let qry = "select type, state, table, reason from system.background_tasks";
+    expects_ok(
+        "build_index_task",
+        execute_query(ctx.clone(), qry).await,
+        expected,
+    )
+    .await?;
+    Ok(())
+}
@@ -70,6 +70,12 @@ async fn test_configs_table() -> Result<()> {
         "| query   | async_insert_busy_timeout            | 200                       |             |",
         "| query   | async_insert_max_data_size           | 10000                     |             |",
         "| query   | async_insert_stale_timeout           | 0                         |             |",
+        "| query   | background_compact_segment_threshold | 100                       |             |",
+        "| query   | background_compact_small_block_ratio | 30                        |             |",
+        "| query   | background_recluster_depth_threshold | 8                         |             |",
+        "| query   | background_tasks_enabled             | false                     |             |",
+        "| query   | background_tasks_interval_secs       | 60                        |             |",
+        "| query   | background_tasks_max_concurrency     | 2                         |             |",
         "| query   | clickhouse_handler_host              | 127.0.0.1                 |             |",
         "| query   | clickhouse_handler_port              | 9000                      |             |",
         "| query   | clickhouse_http_handler_host         | 127.0.0.1                 |             |",
@@ -91,10 +97,14 @@ async fn test_configs_table() -> Result<()> {
         "| query   | mysql_handler_host                   | 127.0.0.1                 |             |",
         "| query   | mysql_handler_port                   | 3307                      |             |",
         "| query   | num_cpus                             | 0                         |             |",
+        "| query   | query_result_cache_disk_mb_size      | 1024                      |             |",
+        "| query   | query_result_cache_disk_root         | _result_cache             |             |",
+        "| query   | query_result_cache_memory_mb_size    | 128                       |             |",
         "| query   | rpc_tls_query_server_root_ca_cert    |                           |             |",
         "| query   | rpc_tls_query_service_domain_name    | localhost                 |             |",
         "| query   | rpc_tls_server_cert                  |                           |             |",
         "| query   | rpc_tls_server_key                   |                           |             |",
+        "| query   | spill_root                           | _spill                    |             |",
         "| query   | table_cache_block_meta_count         | 102400                    |             |",
         "| query   | table_cache_enabled                  | false                     |             |",
         "| query   | table_cache_segment_count            | 10240                     |             |",
@@ -192,6 +202,12 @@ async fn test_configs_table_redact() -> Result<()> {
         "| query   | async_insert_busy_timeout            | 200                       |             |",
         "| query   | async_insert_max_data_size           | 10000                     |             |",
         "| query   | async_insert_stale_timeout           | 0                         |             |",
+        "| query   | background_compact_segment_threshold | 100                       |             |",
+        "| query   | background_compact_small_block_ratio | 30                        |             |",
+        "| query   | background_recluster_depth_threshold | 8                         |             |",
+        "| query   | background_tasks_enabled             | false                     |             |",
+        "| query   | background_tasks_interval_secs       | 60                        |             |",
+        "| query   | background_tasks_max_concurrency     | 2                         |             |",
         "| query   | clickhouse_handler_host              | 127.0.0.1                 |             |",
         "| query   | clickhouse_handler_port              | 9000                      |             |",
         "| query   | clickhouse_http_handler_host         | 127.0.0.1                 |             |",
@@ -213,10 +229,14 @@ async fn test_configs_table_redact() -> Result<()> {
         "| query   | mysql_handler_host                   | 127.0.0.1                 |             |",
         "| query   | mysql_handler_port                   | 3307                      |             |",
         "| query   | num_cpus                             | 0                         |             |",
+        "| query   | query_result_cache_disk_mb_size      | 1024                      |             |",
+        "| query   | query_result_cache_disk_root         | _result_cache             |             |",
+        "| query   | query_result_cache_memory_mb_size    | 128                       |             |",
         "| query   | rpc_tls_query_server_root_ca_cert    |                           |             |",
         "| query   | rpc_tls_query_service_domain_name    | localhost                 |             |",
         "| query   | rpc_tls_server_cert                  |                           |             |",
         "| query   | rpc_tls_server_key                   |                           |             |",
+        "| query   | spill_root                           | _spill                    |             |",
         "| query   | table_cache_block_meta_count         | 102400                    |             |",
         "| query   | table_cache_enabled                  | false                     |             |",
         "| query   | table_cache_segment_count            | 10240                     |             |",
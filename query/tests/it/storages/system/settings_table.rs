@@ -36,13 +36,22 @@ async fn test_settings_table() -> Result<()> {
         "+--------------------------------+---------+---------+---------+----------------------------------------------------------------------------------------------------+--------+",
         "| enable_async_insert            | 0       | 0       | SESSION | Whether the client open async insert mode, default value: 0                                        | UInt64 |",
         "| compression                    | None    | None    | SESSION | Format compression, default value: None                                                            | String |",
+        "| data_retention_period          | 24      | 24      | SESSION | The retention period in hours of the table snapshots kept by VACUUM, default value: 24             | UInt64 |",
         "| empty_as_default               | 1       | 1       | SESSION | Format empty_as_default, default value: 1                                                          | UInt64 |",
+        "| enable_cbo                     | 1       | 1       | SESSION | Enable cost-based join reordering of planner v2 if value != 0, default value: 1                    | UInt64 |",
         "| enable_new_processor_framework | 1       | 1       | SESSION | Enable new processor framework if value != 0, default value: 1                                     | UInt64 |",
         "| enable_planner_v2              | 0       | 0       | SESSION | Enable planner v2 by setting this variable to 1, default value: 0                                  | UInt64 |",
+        "| enable_prewhere                | 1       | 1       | SESSION | Enable prewhere, reading the columns of push-down filters first if value != 0, default value: 1    | UInt64 |",
+        "| enable_query_result_cache      | 0       | 0       | SESSION | Enable the result cache of SELECT queries on FUSE tables if value != 0, default value: 0           | UInt64 |",
+        "| enable_runtime_filter          | 1       | 1       | SESSION | Enable runtime filters pushed from hash join into FUSE table scans if value != 0, default value: 1 | UInt64 |",
         "| field_delimiter                | ,       | ,       | SESSION | Format field delimiter, default value: ,                                                           | String |",
         "| flight_client_timeout          | 60      | 60      | SESSION | Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds | UInt64 |",
+        "| group_by_spilling_memory_limit | 0       | 0       | SESSION | The query memory limit in bytes to spill group by states, 0 disables spilling, default value: 0    | UInt64 |",
         "| group_by_two_level_threshold   | 10000   | 10000   | SESSION | The threshold of keys to open two-level aggregation, default value: 10000                          | UInt64 |",
+        "| join_spilling_memory_limit     | 0       | 0       | SESSION | The build side bytes of hash join to spill partitions, 0 disables spilling, default value: 0       | UInt64 |",
         "| max_block_size                 | 10000   | 10000   | SESSION | Maximum block size for reading                                                                     | UInt64 |",
+        "| max_bytes_before_external_sort | 0       | 0       | SESSION | The bytes of sorted blocks to spill in ORDER BY, 0 disables external sort, default value: 0        | UInt64 |",
+        "| max_cte_recursive_depth        | 1000    | 1000    | SESSION | Max iterations of recursive common table expression, default value: 1000                           | UInt64 |",
         "| max_threads                    | 2       | 16      | SESSION | The maximum number of threads to execute the request. By default, it is determined automatically.  | UInt64 |",
         "| record_delimiter               | \"\\n\"    | \"\\n\"    | SESSION | Format record_delimiter, default value: \"\\n\"                                                       | String |",
         "| skip_header                    | 0       | 0       | SESSION | Whether to skip the input header, default value: 0                                                 | UInt64 |",